@@ -0,0 +1,14 @@
+// src/common/input.rs
+// 交互式练习共用的输入工具：打印提示，读一行，去掉首尾空白
+
+use std::io::{self, Write};
+
+pub fn read_line(prompt: &str) -> String {
+    print!("{}", prompt);
+    // print! 不带换行，stdout 是行缓冲的，不手动 flush 提示语可能不会立刻显示
+    io::stdout().flush().expect("刷新输出失败");
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("读取失败");
+    input.trim().to_string()
+}
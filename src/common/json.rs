@@ -0,0 +1,249 @@
+// src/common/json.rs
+// 手写的迷你 JSON：实验室坚持只用标准库 + sha2/hex，不为了存几个文件引入 serde
+// 只支持整数 (不支持小数和指数)，够保存证明、区块这类结构化数据
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    // i128：u64 和 i64 的全部取值都放得下 (金额、nonce 可能超过 i64::MAX，存成 i64 会变成负数，读回来就失败了)
+    Number(i128),
+    Str(String),
+    Array(Vec<Json>),
+    // 用 Vec 而不是 HashMap：保持字段顺序，序列化结果稳定，方便肉眼比对和 diff
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object(fields: Vec<(&str, Json)>) -> Json {
+        Json::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Json::Number(n) => u64::try_from(*n).ok(),
+            _ => None,
+        }
+    }
+
+    // 超出 u32 范围返回 None，不做截断
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            Json::Number(n) => u32::try_from(*n).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    // 常见的"取字段并转类型，失败时给出字段名"，省得每个调用方都写一遍 ok_or
+    pub fn field<'a, T>(&'a self, key: &str, convert: impl Fn(&'a Json) -> Option<T>) -> Result<T, String> {
+        self.get(key).and_then(convert).ok_or_else(|| format!("缺少字段或类型不对: \"{}\"", key))
+    }
+}
+
+// ==========================================
+// 序列化：Display 直接输出紧凑的 JSON 文本
+// ==========================================
+fn write_escaped(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) => write!(f, "{}", n),
+            Json::Str(s) => write_escaped(f, s),
+            Json::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_escaped(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+// ==========================================
+// 解析：递归下降，一个字符一个字符地吃
+// ==========================================
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("期望 '{}'，遇到 '{}'", expected, c)),
+            None => Err(format!("期望 '{}'，但文本已结束", expected)),
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        for expected in word.chars() {
+            if self.chars.next() != Some(expected) {
+                return Err(format!("无法识别的字面量，期望 {}", word));
+            }
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            Some('n') => self.keyword("null", Json::Null),
+            Some('t') => self.keyword("true", Json::Bool(true)),
+            Some('f') => self.keyword("false", Json::Bool(false)),
+            Some('"') => self.string().map(Json::Str),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some(c) if *c == '-' || c.is_ascii_digit() => self.number(),
+            Some(c) => Err(format!("意外的字符 '{}'", c)),
+            None => Err(String::from("意外的文本结尾")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let mut text = String::new();
+        if let Some(minus) = self.chars.next_if_eq(&'-') {
+            text.push(minus);
+        }
+        while let Some(d) = self.chars.next_if(|c| c.is_ascii_digit()) {
+            text.push(d);
+        }
+        text.parse().map(Json::Number).map_err(|_| format!("无效的整数 \"{}\"", text))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            match self.chars.next().ok_or("字符串没有闭合")? {
+                '"' => return Ok(out),
+                '\\' => match self.chars.next().ok_or("字符串没有闭合")? {
+                    '"' => out.push('"'),
+                    '\\' => out.push('\\'),
+                    '/' => out.push('/'),
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    'r' => out.push('\r'),
+                    'u' => {
+                        let code: String = (0..4).filter_map(|_| self.chars.next()).collect();
+                        let c = u32::from_str_radix(&code, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("无效的 \\u 转义 \"{}\"", code))?;
+                        out.push(c);
+                    }
+                    c => return Err(format!("不支持的转义 \\{}", c)),
+                },
+                c => out.push(c),
+            }
+        }
+    }
+
+    // 数组和对象共用的"逗号分隔、直到遇到 close"循环
+    fn list<T>(&mut self, close: char, mut item: impl FnMut(&mut Self) -> Result<T, String>) -> Result<Vec<T>, String> {
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&close).is_some() {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some(',') => continue,
+                Some(c) if c == close => return Ok(items),
+                _ => return Err(format!("期望 ',' 或 '{}'", close)),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.expect('[')?;
+        self.list(']', |p| p.value()).map(Json::Array)
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect('{')?;
+        self.list('}', |p| {
+            p.skip_whitespace();
+            let key = p.string()?;
+            p.expect(':')?;
+            Ok((key, p.value()?))
+        })
+        .map(Json::Object)
+    }
+}
+
+pub fn parse(text: &str) -> Result<Json, String> {
+    let mut parser = Parser { chars: text.chars().peekable() };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    match parser.chars.next() {
+        None => Ok(value),
+        Some(c) => Err(format!("JSON 结束后还有多余的字符 '{}'", c)),
+    }
+}
@@ -0,0 +1,6 @@
+// src/common/mod.rs
+// 各板块共用的小工具 (不属于任何一个练习)
+
+pub mod input;
+pub mod json;
+pub mod rng;
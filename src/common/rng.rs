@@ -0,0 +1,39 @@
+// src/common/rng.rs
+// 一个极简的伪随机数生成器 (xorshift64*)
+// 实验室坚持只用标准库，不引入 rand crate；同一个 seed 永远产生同一串数，方便复现实验
+
+pub struct SimpleRng {
+    state: u64,
+}
+
+impl SimpleRng {
+    pub fn new(seed: u64) -> Self {
+        // 先用 splitmix64 打散一下 seed：相邻的 seed (1, 2, 3...) 也能得到差别很大的初始状态
+        // xorshift 的状态不能是 0，否则永远输出 0，所以最后 | 1 兜底
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        SimpleRng { state: (z ^ (z >> 31)) | 1 }
+    }
+
+    // 不关心复现、只想要"每次运行都不一样"时，用当前时间的纳秒数做 seed
+    pub fn from_time() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Self::new(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // [0, bound) 之间的随机数 (bound 很小时取模带来的偏差可以忽略)
+    pub fn gen_range(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
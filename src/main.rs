@@ -1,10 +1,14 @@
 // src/main.rs
 
+mod common;
 mod s01_memory;
 mod s02_abstraction; 
 mod s03_smart_pointers;
 mod s04_concurrency;
 mod s05_zk_lab;
+mod s06_chain;
+mod s07_vm;
+mod s08_async;
 
 use std::io;
 
@@ -18,6 +22,9 @@ fn main() {
         println!("3. S03: 智能指针 (Smart Pointers) [已解锁]");
         println!("4. S04: 并发安全性 (Concurrency) [已解锁]");
         println!("5. S05: 零知识证明实验室 (ZK Lab) [已解锁]");
+        println!("6. S06: 玩具区块链 (Toy Chain) [已解锁]");
+        println!("7. S07: 合约虚拟机 (Toy VM) [已解锁]");
+        println!("8. S08: 异步 (Async) [已解锁]");
         println!("0. 退出系统");
         println!("请选择板块:");
 
@@ -33,7 +40,10 @@ fn main() {
                 println!("👋 再见!");
                 break;
             },
-            "5" => s05_zk_lab::run(),
+            "5" => s05_zk_lab::run_experiments(),
+            "6" => s06_chain::run_experiments(),
+            "7" => s07_vm::run_experiments(),
+            "8" => s08_async::run_experiments(),
             _ => println!("❌ 无效选择"),
         }
     }
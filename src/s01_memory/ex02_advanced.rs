@@ -1,43 +1,106 @@
-// src/s01_memory.rs
+// src/s01_memory/ex02_advanced.rs
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/*
+业务场景：区块空间不够，谁先上链？
+    最早的 Mempool 是一个 Vec，pop_first 用 remove(0) 按先来后到拿交易。
+    真实的交易池是一个"手续费市场"：矿工每次都拿手续费最高的，池子满了就把手续费最低的踢出去。
+
+本练习：
+    1. 给 Transaction 加上 fee，手写 Ord：手续费高的排前面，一样高时先到的排前面
+    2. Mempool 改用 BinaryHeap (最大堆)：pop_first 变成 pop_highest_fee
+    3. 容量上限：满了之后新交易要比池子里最低的手续费高，才能把它挤出去；否则被拒绝 (所有权还给调用方)
+    4. 三个所有权陷阱：从集合里移出元素、悬垂引用、部分移动
+    5. 对照原来的先来后到 (Vec + remove(0))
+*/
+
+// ==========================================
+// 交易与排序规则
+// ==========================================
 
 #[derive(Debug)]
 struct Transaction {
-    id: u64,
+    id: u64, // 越小越早到
+    fee: u64,
     payload: String, // 交易数据，堆内存
 }
 
+// ❌ 陷阱：#[derive(PartialEq)] 会比较所有字段 (包括 payload)，而下面的 Ord 只看 (fee, id)。
+// Ord 要求 a.cmp(&b) == Equal 当且仅当 a == b，两者不一致时 BinaryHeap / BTreeMap 的行为是未定义的 (逻辑错误，不是 UB)。
+// 所以 PartialEq 也要手写，和 cmp 用同一套规则。
+impl PartialEq for Transaction {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Transaction {}
+
+// BinaryHeap 是最大堆，"最大"的先出来：
+//   先比手续费，高的大；手续费一样时 id 小的 (先到的) 大 —— 所以 id 的比较要反过来
+impl Ord for Transaction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.fee.cmp(&other.fee).then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+// 有了全序，偏序直接复用，避免两套规则
+impl PartialOrd for Transaction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// ==========================================
+// 手续费市场版 Mempool
+// ==========================================
+
 #[derive(Debug)]
 struct Mempool {
-    txs: Vec<Transaction>, // 交易列表
+    txs: BinaryHeap<Transaction>, // 交易列表，按手续费排好的最大堆
+    capacity: usize,
 }
 
 impl Mempool {
-    fn new() -> Mempool {
-        Mempool { txs: Vec::new() }
+    fn new(capacity: usize) -> Mempool {
+        Mempool { txs: BinaryHeap::new(), capacity }
     }
 
-    fn add(&mut self, tx: Transaction) {
-        self.txs.push(tx); // 所有权移入 Vec
+    // 返回值把所有权的去向写清楚：
+    //   Ok(None)        收下了 (所有权移入堆)
+    //   Ok(Some(old))   收下了，但挤掉了手续费最低的 old (所有权交给调用方，比如通知钱包"你的交易被踢了")
+    //   Err(tx)         池子满了，而且 tx 的手续费不够高：原样还给调用方，可以加价重发
+    fn add(&mut self, tx: Transaction) -> Result<Option<Transaction>, Transaction> {
+        if self.txs.len() < self.capacity {
+            self.txs.push(tx);
+            return Ok(None);
+        }
+        // 最大堆只能 O(1) 看到最大值；找最小值要把所有元素扫一遍 O(n)
+        let lowest_id = match self.txs.iter().min() {
+            Some(lowest) if tx > *lowest => lowest.id,
+            _ => return Err(tx),
+        };
+        // BinaryHeap 不能按位置删除：拆成 Vec，swap_remove 拿出最低的那笔，再重新建堆 O(n)
+        let mut txs = std::mem::take(&mut self.txs).into_vec();
+        let index = txs.iter().position(|t| t.id == lowest_id).expect("lowest tx is in the pool");
+        let evicted = txs.swap_remove(index);
+        self.txs = BinaryHeap::from(txs);
+        self.txs.push(tx);
+        Ok(Some(evicted))
     }
 
     // ❌ 陷阱 1: 集合中的所有权移动
-    // 场景：矿工想从交易池里“拿走”第一笔交易去打包
-    // 提示：Vec 拥有交易的所有权，直接用索引 [0] 能拿走吗？
-    fn pop_first(&mut self) -> Option<Transaction> {
-        if self.txs.is_empty() {
-            return None;
-        }
-        
-        // 错误写法：
+    // 场景：矿工想从交易池里“拿走”手续费最高的交易去打包 (原来是 pop_first：拿走第一笔)
+    // 提示：集合拥有交易的所有权，直接用索引 [0] 能拿走吗？
+    fn pop_highest_fee(&mut self) -> Option<Transaction> {
+        // 错误写法 (Vec 版)：
         // let tx = self.txs[0]; // 编译器会报错：cannot move out of index
         // return Some(tx);
 
-        // 请修复上面，使用 Vec 提供的正确方法弹出元素
-
-        let tx = self.txs.remove(0); // 正确写法：使用 remove 方法移除并返回第一个元素
-        Some(tx)
-        
-        // None // 临时占位，修复后删掉这行
+        // Vec 版的正确写法：let tx = self.txs.remove(0); 移除并返回第一个元素
+        // BinaryHeap 版：pop 交出堆顶 (手续费最高的)，O(log n)，同样是把所有权移出集合
+        self.txs.pop()
 
         /*
         出错原因：
@@ -57,14 +120,22 @@ impl Mempool {
             1. 读取 index 0 的数据到新位置（所有权转移）
             2. 将后续元素往前移动一位
             3. 更新 Vec 的长度，避免重复 drop 所造成的 double free
+
+        BinaryHeap::pop 同理：底层也是一个 Vec，先把堆顶和最后一个元素交换，
+        再用 Vec::pop 把它移出 (不用挪动其余元素)，最后把换上来的元素下沉，恢复堆序
          */
     }
 
+    // 只看不拿：peek 返回引用，交易还留在堆里
+    fn peek_highest_fee(&self) -> Option<&Transaction> {
+        self.txs.peek()
+    }
+
     // ❌ 陷阱 2: 悬垂引用 (Dangling Reference)
-    // 场景：我们想获取最新交易的 payload 的切片用于打印日志
+    // 场景：我们想获取下一笔要打包的交易的 payload 的切片用于打印日志
     // 这是一个非常隐蔽的错误，请仔细阅读报错
-    fn get_latest_payload_preview(&self) -> &str {
-        if let Some(tx) = self.txs.last() {
+    fn get_next_payload_preview(&self) -> &str {
+        if let Some(tx) = self.peek_highest_fee() {
             // 假设我们要对 payload 做一些处理（比如截取前4位）再返回
             // let temp_str = tx.payload.clone(); // 克隆了一份数据到局部变量
             // return &temp_str[0..4];            // 返回局部变量的引用
@@ -83,8 +154,8 @@ impl Mempool {
             直接返回 tx.payload 的切片。因为 tx 是 self 数据的引用，
             所以 &tx.payload[0..4] 的生命周期与 self 相同，是安全的。
 
-            这里if let Some(tx) = self.txs.last() 
-            右边的 self.txs.last() 返回的是对 Vec 中最后一个 Transaction 的引用
+            这里if let Some(tx) = self.peek_highest_fee()
+            右边的 peek 返回的是对堆顶那个 Transaction 的引用
 
             这里的 tx 是一个引用类型 &Transaction。
             切片 &tx.payload[0..4] 也是一个引用类型 &str，
@@ -108,6 +179,7 @@ impl Mempool {
 fn check_partial_move() {
     let tx = Transaction {
         id: 101,
+        fee: 0,
         payload: String::from("Mint 100 BTC"),
     };
 
@@ -145,20 +217,89 @@ fn check_partial_move() {
 }
 
 pub fn run_experiments() {
-    println!("--- S01 进阶: 内存深水区 ---");
+    println!("--- S01 进阶: 内存深水区 (手续费市场版 Mempool) ---");
 
-    let mut pool = Mempool::new();
-    pool.add(Transaction { id: 1, payload: String::from("Tx_A") });
-    pool.add(Transaction { id: 2, payload: String::from("Tx_B") });
+    let mut pool = Mempool::new(10);
+    let _ = pool.add(Transaction { id: 1, fee: 2, payload: String::from("Tx_A") });
+    let _ = pool.add(Transaction { id: 2, fee: 8, payload: String::from("Tx_B") });
 
-    // 1. 尝试修复 pop_first
-    let first_tx = pool.pop_first(); 
+    // 1. 尝试修复 pop_highest_fee
+    let first_tx = pool.pop_highest_fee();
     println!("打包交易: {:?}", first_tx);
 
     // 2. 尝试修复悬垂引用
-    let preview = pool.get_latest_payload_preview();
-    println!("最新交易预览: {}", preview);
+    let preview = pool.get_next_payload_preview();
+    println!("下一笔交易预览: {}", preview);
 
     // 3. 部分移动实验
     check_partial_move();
-}
\ No newline at end of file
+
+    let arrivals = [(1, 5, "Alice -> Bob"), (2, 20, "Carol -> Dave"), (3, 1, "Eve -> Frank"), (4, 12, "Bob -> Carol"), (5, 3, "Dave -> Eve"), (6, 30, "Frank -> Alice")];
+
+    // ==========================================
+    // 4. 容量上限与驱逐
+    // ==========================================
+    println!("\n[4] 容量 3 的交易池，依次到达 {} 笔交易", arrivals.len());
+    let mut pool = Mempool::new(3);
+    for (id, fee, payload) in arrivals {
+        let tx = Transaction { id, fee, payload: String::from(payload) };
+        match pool.add(tx) {
+            Ok(None) => println!("  Tx#{} (fee {:>2}) ✅ 进池", id, fee),
+            Ok(Some(old)) => println!("  Tx#{} (fee {:>2}) ✅ 进池，挤掉了 Tx#{} (fee {}) \"{}\"", id, fee, old.id, old.fee, old.payload),
+            // 被拒绝的交易所有权回到这里，payload 仍然可以用
+            Err(rejected) => println!("  Tx#{} (fee {:>2}) ❌ 池子满了，手续费不够，退回 \"{}\"", id, fee, rejected.payload),
+        }
+    }
+    if let Some(top) = pool.peek_highest_fee() {
+        println!("  peek: 下一笔要打包的是 Tx#{} (fee {})，它还在池子里", top.id, top.fee);
+    }
+
+    // ==========================================
+    // 5. 打包顺序
+    // ==========================================
+    println!("\n[5] 矿工每次 pop_highest_fee");
+    while let Some(tx) = pool.pop_highest_fee() {
+        println!("  打包 Tx#{} fee {:>2} \"{}\"", tx.id, tx.fee, tx.payload);
+    }
+
+    println!("\n[6] 手续费一样时先到先得");
+    let mut tie = Mempool::new(10);
+    for (id, payload) in [(7, "late"), (3, "early"), (5, "middle")] {
+        let _ = tie.add(Transaction { id, fee: 10, payload: String::from(payload) });
+    }
+    let order: Vec<String> = std::iter::from_fn(|| tie.pop_highest_fee()).map(|tx| format!("Tx#{} {}", tx.id, tx.payload)).collect();
+    println!("  {}", order.join(" -> "));
+
+    println!("\n[7] 对照原来的先来后到 (Vec + remove(0))");
+    let mut fifo: Vec<Transaction> = arrivals.iter().map(|&(id, fee, payload)| Transaction { id, fee, payload: String::from(payload) }).collect();
+    let mut order = Vec::new();
+    while !fifo.is_empty() {
+        let tx = fifo.remove(0);
+        order.push(format!("#{}({})", tx.id, tx.fee));
+    }
+    println!("  FIFO    : {}", order.join(" "));
+    let mut market = Mempool::new(arrivals.len());
+    for (id, fee, payload) in arrivals {
+        let _ = market.add(Transaction { id, fee, payload: String::from(payload) });
+    }
+    let order: Vec<String> = std::iter::from_fn(|| market.pop_highest_fee()).map(|tx| format!("#{}({})", tx.id, tx.fee)).collect();
+    println!("  手续费市场: {}", order.join(" "));
+}
+
+/*
+关键点总结：
+    1. 自定义 Ord：
+        BinaryHeap 按 Ord 排序，而 Ord 完全由我们决定。"手续费高优先、同价先到优先"就是 cmp 里的两行。
+        PartialEq / PartialOrd 必须和 Ord 一致，所以全部手写并复用 cmp。
+
+    2. 所有权的三种去向：
+        add 的返回值 Result<Option<Transaction>, Transaction> 保证每笔交易都有明确的主人：
+        留在池子里、作为被挤掉的交易交还、或者作为被拒绝的交易原样退回。没有交易被悄悄 drop。
+
+    3. 复杂度：
+        push / pop 最大值 O(log n)，peek O(1)；但"找最小值并删除"在最大堆上是 O(n)。
+        真实节点的交易池同时要按手续费取最大 (打包) 和最小 (驱逐)，通常用 BTreeSet 或者双堆。
+
+    4. 真实的比特币按"手续费率" (sat/vB) 排序，而不是总手续费：区块空间按字节卖。
+        只要把 cmp 换成 self.fee * other.size 对比 other.fee * self.size 即可。
+*/
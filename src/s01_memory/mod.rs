@@ -11,7 +11,7 @@ pub fn run_experiments() {
     loop {
         println!("\n--- 🧠 S01 内存基本法 (Memory) ---");
         println!("1. 基础篇：Account 结构体与布局");
-        println!("2. 进阶篇：Mempool、所有权陷阱 (NEW!)");
+        println!("2. 进阶篇：Mempool (BinaryHeap 手续费市场)、所有权陷阱");
         println!("0. 返回主菜单");
         println!("请输入练习编号:");
 
@@ -20,7 +20,7 @@ pub fn run_experiments() {
 
         match input.trim() {
             "1" => ex01_basic::run_experiments(),     // 运行你刚才写的 Account
-            "2" => ex02_advanced::run_experiments(),  // 运行新的 Mempool 题目
+            "2" => ex02_advanced::run_experiments(),  // 运行 Mempool 题目
             "0" => break,                 // 跳出循环，返回 main
             _ => println!("❌ 无效选择，请重试"),
         }
@@ -6,14 +6,16 @@ pub trait Asset {
 }
 
 // 2. 定义两种不同的资产
-struct Token {
-    symbol: String,
-    amount: u64,
+// pub：ex05 的 NFT 登记簿复用这几个类型
+pub struct Token {
+    pub symbol: String,
+    pub amount: u64,
 }
 
-struct NFT {
-    id: u64,
-    url: String,
+#[derive(Clone)]
+pub struct NFT {
+    pub id: u64,
+    pub url: String,
 }
 
 impl Asset for Token {
@@ -29,7 +31,7 @@ impl Asset for NFT {
 }
 
 // 3. 混合钱包 (The Mixed Bag)
-struct Wallet {
+pub struct Wallet {
     // ❌ 错误做法：使用泛型 T
     // Vec<T> 意味着：虽然 T 可以是任何实现了 Asset 的类型
     // 但一旦确定了是 Token，整个 Vec 就只能装 Token，不能混入 NFT
@@ -76,13 +78,13 @@ struct Wallet {
 }
 
 impl Wallet {
-    fn new() -> Wallet {
+    pub fn new() -> Wallet {
         Wallet { assets: Vec::new() }
     }
 
     // 这里的参数为什么必须是 Box<dyn Asset>？
     // 因为 dyn Asset 是一个"不定长类型"(Unsized)，不能直接放在栈上传递
-    fn add_asset(&mut self, asset: Box<dyn Asset>) {
+    pub fn add_asset(&mut self, asset: Box<dyn Asset>) {
         self.assets.push(asset);
     }
     /*
@@ -93,7 +95,7 @@ impl Wallet {
      */
 
 
-    fn show_portfolio(&self) {
+    pub fn show_portfolio(&self) {
         println!("--- Wallet Portfolio ---");
         for (i, item) in self.assets.iter().enumerate() {
             // 这里发生了 "动态分发" (Dynamic Dispatch)
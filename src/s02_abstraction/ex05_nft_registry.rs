@@ -0,0 +1,212 @@
+// src/s02_abstraction/ex05_nft_registry.rs
+use std::collections::{BTreeMap, HashMap};
+
+use super::ex02_trait_objects::{Token, Wallet, NFT};
+
+/*
+业务场景：Ex02 的钱包里能放 NFT，但 NFT 是谁发的、现在归谁、能不能转给别人，钱包说了不算。
+    真正的 NFT 合约是一本"登记簿"：铸造、转移、查询 ownerOf，每个 token 还挂着自己的元数据 (名字、描述、属性)。
+
+本练习：
+    1. 铸造：Alice 拿到 #1 #2，Bob 拿到 #3；按 id 查主人和元数据
+    2. 转移：合法的转移，以及 "转别人的 NFT"、"转不存在的 NFT" 两种失败
+    3. 改元数据：只有主人能改 (get_mut 拿到 &mut 再改)
+    4. 销毁：remove 把 NFT 的所有权从 HashMap 里"拿出来"交给调用者
+    5. 钱包展示：按主人把 NFT 装进 Ex02 的 Wallet (Box<dyn Asset>)，和 Token 混在一起打印
+*/
+
+#[derive(Debug, Clone)]
+pub struct Metadata {
+    pub name: String,
+    pub description: String,
+    // BTreeMap：打印时属性按键排序，每次一样
+    pub attributes: BTreeMap<String, String>,
+}
+
+pub struct NftRegistry {
+    next_id: u64,
+    // id -> NFT 本体。HashMap 拥有这些 NFT (不是引用)，登记簿销毁时它们也跟着销毁
+    tokens: HashMap<u64, NFT>,
+    // id -> 主人；ownerOf 查的就是这张表
+    owners: HashMap<u64, String>,
+    metadata: HashMap<u64, Metadata>,
+}
+
+impl NftRegistry {
+    pub fn new() -> Self {
+        NftRegistry { next_id: 1, tokens: HashMap::new(), owners: HashMap::new(), metadata: HashMap::new() }
+    }
+
+    // 三张表用同一个 id 当键：一次铸造同时插三处
+    pub fn mint(&mut self, to: &str, url: &str, metadata: Metadata) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.tokens.insert(id, NFT { id, url: url.to_string() });
+        self.owners.insert(id, to.to_string());
+        self.metadata.insert(id, metadata);
+        id
+    }
+
+    // 返回 Option<&str>：借出一个视图，不把 String 克隆一份
+    pub fn owner_of(&self, id: u64) -> Option<&str> {
+        self.owners.get(&id).map(|owner| owner.as_str())
+    }
+
+    pub fn metadata(&self, id: u64) -> Option<&Metadata> {
+        self.metadata.get(&id)
+    }
+
+    // HashMap 的遍历顺序是随机的，排个序再给人看
+    pub fn tokens_of(&self, owner: &str) -> Vec<u64> {
+        let mut ids: Vec<u64> = self.owners.iter().filter(|(_, o)| o.as_str() == owner).map(|(id, _)| *id).collect();
+        ids.sort();
+        ids
+    }
+
+    // 检查调用者是不是主人：失败原因分两种 (不存在 / 不是你的)
+    fn check_owner(&self, caller: &str, id: u64) -> Result<(), String> {
+        match self.owner_of(id) {
+            None => Err(format!("#{} 不存在", id)),
+            Some(owner) if owner != caller => Err(format!("#{} 属于 {}，{} 无权操作", id, owner, caller)),
+            Some(_) => Ok(()),
+        }
+    }
+
+    pub fn transfer(&mut self, from: &str, to: &str, id: u64) -> Result<(), String> {
+        self.check_owner(from, id)?;
+        // insert 返回旧值 (原主人的 String)，这里直接丢掉
+        self.owners.insert(id, to.to_string());
+        Ok(())
+    }
+
+    pub fn set_attribute(&mut self, caller: &str, id: u64, key: &str, value: &str) -> Result<(), String> {
+        self.check_owner(caller, id)?;
+        // get_mut：拿到元数据的 &mut，原地修改，不用取出来再放回去
+        let metadata = self.metadata.get_mut(&id).ok_or_else(|| format!("#{} 没有元数据 (不应该发生)", id))?;
+        metadata.attributes.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    // remove 返回 Option<NFT>：值的所有权从表里转移给调用者
+    pub fn burn(&mut self, caller: &str, id: u64) -> Result<NFT, String> {
+        self.check_owner(caller, id)?;
+        self.owners.remove(&id);
+        self.metadata.remove(&id);
+        self.tokens.remove(&id).ok_or_else(|| format!("#{} 没有本体 (不应该发生)", id))
+    }
+
+    // 钱包里的是 Box<dyn Asset>，要拥有数据；登记簿还得留着原件，所以 clone 一份装进去
+    pub fn wallet_of(&self, owner: &str) -> Wallet {
+        let mut wallet = Wallet::new();
+        for id in self.tokens_of(owner) {
+            if let Some(nft) = self.tokens.get(&id) {
+                wallet.add_asset(Box::new(nft.clone()));
+            }
+        }
+        wallet
+    }
+}
+
+fn metadata(name: &str, description: &str, attributes: &[(&str, &str)]) -> Metadata {
+    Metadata {
+        name: name.to_string(),
+        description: description.to_string(),
+        attributes: attributes.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+    }
+}
+
+fn show_token(registry: &NftRegistry, id: u64) {
+    match (registry.owner_of(id), registry.metadata(id)) {
+        (Some(owner), Some(meta)) => {
+            let attributes: Vec<String> = meta.attributes.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            println!("  #{} 主人 {}：{} —— {} [{}]", id, owner, meta.name, meta.description, attributes.join(", "));
+        }
+        _ => println!("  #{} 不存在", id),
+    }
+}
+
+fn show_result(label: &str, result: Result<(), String>) {
+    match result {
+        Ok(()) => println!("  {}: ✅", label),
+        Err(e) => println!("  {}: ❌ {}", label, e),
+    }
+}
+
+pub fn run() {
+    println!("--- S02 Ex05: NFT 登记簿 (HashMap 所有权 + Trait 对象钱包) ---");
+    let mut registry = NftRegistry::new();
+
+    // ==========================================
+    // 1. 铸造
+    // ==========================================
+    println!("\n[1] 铸造");
+    let cat = registry.mint("Alice", "ipfs://cat", metadata("像素猫", "一只 8-bit 的猫", &[("颜色", "橙"), ("稀有度", "普通")]));
+    let dog = registry.mint("Alice", "ipfs://dog", metadata("像素狗", "一只 8-bit 的狗", &[("颜色", "黑"), ("稀有度", "稀有")]));
+    let crab = registry.mint("Bob", "ipfs://crab", metadata("Ferris", "Rust 吉祥物", &[("颜色", "红"), ("稀有度", "传说")]));
+    for id in [cat, dog, crab] {
+        show_token(&registry, id);
+    }
+    println!("  Alice 持有 {:?}，Bob 持有 {:?}", registry.tokens_of("Alice"), registry.tokens_of("Bob"));
+
+    // ==========================================
+    // 2. 转移
+    // ==========================================
+    println!("\n[2] 转移");
+    show_result(&format!("Alice 把 #{} 转给 Bob", dog), registry.transfer("Alice", "Bob", dog));
+    show_result(&format!("Alice 想把 #{} 转给自己", crab), registry.transfer("Alice", "Alice", crab));
+    show_result("Alice 转 #99", registry.transfer("Alice", "Bob", 99));
+    println!("  ownerOf(#{}) = {:?}", dog, registry.owner_of(dog));
+    println!("  Alice 持有 {:?}，Bob 持有 {:?}", registry.tokens_of("Alice"), registry.tokens_of("Bob"));
+
+    // ==========================================
+    // 3. 改元数据
+    // ==========================================
+    println!("\n[3] 改元数据");
+    show_result(&format!("Bob 给 #{} 加属性 名字=旺财", dog), registry.set_attribute("Bob", dog, "名字", "旺财"));
+    show_result(&format!("Alice 给 #{} 改稀有度 (已经不是她的了)", dog), registry.set_attribute("Alice", dog, "稀有度", "传说"));
+    show_token(&registry, dog);
+
+    // ==========================================
+    // 4. 销毁
+    // ==========================================
+    println!("\n[4] 销毁");
+    match registry.burn("Bob", cat) {
+        Ok(_) => println!("  Bob 销毁 #{}: ✅ (不应该发生)", cat),
+        Err(e) => println!("  Bob 销毁 #{}: ❌ {}", cat, e),
+    }
+    match registry.burn("Alice", cat) {
+        // burned 是一个拥有所有权的 NFT 值，离开这个分支时被 drop
+        Ok(burned) => println!("  Alice 销毁 #{}: ✅ 拿回了本体 (url {})，登记簿里已经没有它", burned.id, burned.url),
+        Err(e) => println!("  Alice 销毁 #{}: ❌ {} (不应该发生)", cat, e),
+    }
+    show_token(&registry, cat);
+
+    // ==========================================
+    // 5. 钱包展示
+    // ==========================================
+    println!("\n[5] 钱包 (Ex02 的 Wallet：NFT 和 Token 装进同一个 Vec<Box<dyn Asset>>)");
+    for owner in ["Alice", "Bob"] {
+        let mut wallet = registry.wallet_of(owner);
+        wallet.add_asset(Box::new(Token { symbol: String::from("USDT"), amount: 100 }));
+        println!("  {}:", owner);
+        wallet.show_portfolio();
+    }
+}
+
+/*
+关键点总结：
+    1. 几张表共用一个键：
+        tokens / owners / metadata 都以 token id 为键，各管一件事。查主人不用碰元数据，改元数据不用碰主人表，
+        借用检查器也乐意：对不同字段的借用互不冲突。
+
+    2. 借出 vs 交出：
+        owner_of 返回 Option<&str> —— 只借出视图，调用者不能改、也不用付克隆的代价；
+        burn 用 remove 把 NFT 从表里"拿出来"，所有权交给调用者，表里就再也没有它了。
+
+    3. get_mut 原地修改：
+        拿到 &mut Metadata 直接改属性，这个 &mut 活着的期间整个 metadata 表都不能再被借用 —— 编译器替你排除了并发修改。
+
+    4. 钱包要的是所有权：
+        Box<dyn Asset> 默认要求 'static，装不进借来的 &NFT；登记簿又必须留着原件，所以这里 clone。
+        如果不想复制，可以改成 Rc<NFT> 共享 (见 S03)。
+*/
@@ -5,6 +5,7 @@ pub mod ex01_generics;
 pub mod ex02_trait_objects;
 pub mod ex03_closures;
 pub mod ex04_lifetimes;
+pub mod ex05_nft_registry;
 
 use std::io;
 
@@ -15,6 +16,7 @@ pub fn run_experiments() {
         println!("2. Trait 对象 (Multi-Asset Wallet)");
         println!("3. 闭包与迭代器 (Tx Filter)");
         println!("4. 生命周期 (Zero-Copy Validator)");
+        println!("5. HashMap 所有权 (NFT Registry)");
         println!("0. 返回主菜单");
         println!("请输入练习编号:");
 
@@ -26,6 +28,7 @@ pub fn run_experiments() {
             "2" => ex02_trait_objects::run(),
             "3" => ex03_closures::run(),
             "4" => ex04_lifetimes::run(),
+            "5" => ex05_nft_registry::run(),
             "0" => break,
             _ => println!("❌ 无效选择，请重试"),
         }
@@ -4,24 +4,25 @@ use std::cell::RefCell;
 
 // 1. 定义交易池 (底层数据)
 #[derive(Debug)]
-struct Mempool {
-    txs: Vec<String>,
+pub struct Mempool {
+    pub txs: Vec<String>,
 }
 
 // 2. 定义节点 (持有交易池的共享引用)
-struct Node {
-    id: u64,
+// S04 ex14 会试着把它交给别的线程 (编译不过)，再一步步改成 Arc<Mutex<Mempool>>
+pub struct Node {
+    pub id: u64,
     // 关键组合拳：Rc 让大家共享，RefCell 让大家修改
-    pool: Rc<RefCell<Mempool>>, 
+    pub pool: Rc<RefCell<Mempool>>, 
 }
 
 impl Node {
-    fn new(id: u64, pool: Rc<RefCell<Mempool>>) -> Self {
+    pub fn new(id: u64, pool: Rc<RefCell<Mempool>>) -> Self {
         Node { id, pool }
     }
 
     // 提交交易
-    fn submit_tx(&self, tx: &str) {
+    pub fn submit_tx(&self, tx: &str) {
         // --- 关键动作慢放：borrow_mut() 的生命周期 ---
         
         // 第一步：申请锁 (Request)
@@ -55,7 +56,7 @@ impl Node {
         // 它负责将堆上的 borrow_flag 从 -1 改回 0。
     }
 
-    fn print_pool(&self) {
+    pub fn print_pool(&self) {
         // borrow() 同样会触发运行时检查：将 borrow_flag 从 0 变为 1 (或 N+1)
         let pool_guard = self.pool.borrow();
         println!("Node {} sees pool: {:?}", self.id, pool_guard.txs);
@@ -0,0 +1,164 @@
+// src/s04_concurrency/chaos.rs
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::common::rng::SimpleRng;
+
+use super::{ex02_sync, ex03_channel, ex05_gossip};
+
+/*
+混沌调度器 (Chaos Scheduler)：给 S04 的练习做"压力回放"
+
+问题：
+    并发 bug 往往只在某种特定的线程交错 (interleaving) 下才出现。
+    直接 cargo run 十次，操作系统调度器大概率给你十次几乎一样的顺序，bug 就藏起来了。
+
+思路：
+    1. 在练习代码里埋"插桩点" (instrumentation point)：chaos.point()
+    2. 每到一个插桩点，按随机数决定：什么都不做 / thread::yield_now() 让出 CPU / sleep 几十微秒
+    3. 随机数来自 seed —— 同一个 seed 注入的扰动序列完全相同，出问题时可以按 seed 复现
+    4. 换 N 个 seed 重跑 N 次，检查每一次的不变量 (invariant) 是否都成立
+
+注意：seed 只能固定"我们注入的扰动"，操作系统的调度本身仍然不确定，
+     所以这是一种轻量级的压力测试 (stress testing)，不是穷举所有交错的模型检查。
+*/
+
+// 整个实验共享的配置：Copy 类型，可以随手复制进每个线程
+#[derive(Debug, Clone, Copy)]
+pub struct ChaosScheduler {
+    seed: u64,
+    max_sleep_us: u64,
+    enabled: bool,
+}
+
+impl ChaosScheduler {
+    pub fn new(seed: u64, max_sleep_us: u64) -> Self {
+        ChaosScheduler { seed, max_sleep_us, enabled: true }
+    }
+
+    // 关闭扰动：插桩点变成空操作，用来对照
+    pub fn disabled() -> Self {
+        ChaosScheduler { seed: 0, max_sleep_us: 0, enabled: false }
+    }
+
+    // 为某个线程派生一个独立的扰动源
+    // 每个线程各自拥有 (move 进去) 自己的 RNG，不需要任何锁
+    pub fn for_thread(&self, thread_id: u64) -> ThreadChaos {
+        ThreadChaos {
+            rng: SimpleRng::new(self.seed ^ thread_id.wrapping_mul(0x9E37_79B9)),
+            max_sleep_us: self.max_sleep_us,
+            enabled: self.enabled,
+        }
+    }
+}
+
+pub struct ThreadChaos {
+    rng: SimpleRng,
+    max_sleep_us: u64,
+    enabled: bool,
+}
+
+impl ThreadChaos {
+    // 插桩点：一半概率什么都不做，1/4 让出 CPU，1/4 睡一小会
+    pub fn point(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        match self.rng.gen_range(4) {
+            2 => thread::yield_now(),
+            3 => thread::sleep(Duration::from_micros(self.rng.gen_range(self.max_sleep_us) + 1)),
+            _ => {}
+        }
+    }
+}
+
+// ==========================================
+// 对照组：一个故意写错的存款逻辑
+// ==========================================
+// 读余额和写余额分成了两次加锁，中间插桩点一扰动，别的线程就可能插进来 -> 丢失更新 (Lost Update)
+// 每一步单独看都"加了锁"，但"读-改-写"整体不是原子的
+fn broken_deposit_under_chaos(chaos: &ChaosScheduler) -> i32 {
+    let account = Arc::new(Mutex::new(0));
+    let mut handles = vec![];
+
+    for i in 0..10 {
+        let account_ref = Arc::clone(&account);
+        let mut chaos = chaos.for_thread(i);
+        handles.push(thread::spawn(move || {
+            let balance = *account_ref.lock().unwrap(); // 第一次加锁：读，锁立刻释放
+            chaos.point();
+            *account_ref.lock().unwrap() = balance + 10; // 第二次加锁：写回旧值 + 10
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let balance = *account.lock().unwrap();
+    balance
+}
+
+pub fn run() {
+    println!("--- S04 Chaos: 混沌调度回放 (Differential Replay) ---");
+
+    let runs = 30;
+    let base_seed = 2024;
+    println!("每个场景用 seed {}..{} 各重跑一次\n", base_seed, base_seed + runs - 1);
+
+    // 1. ex02：Arc + Mutex 存款，不变量：最终余额 == 100
+    let mut held = 0;
+    let mut orders = HashSet::new();
+    for r in 0..runs {
+        let chaos = ChaosScheduler::new(base_seed + r, 200);
+        let (balance, order) = ex02_sync::deposit_under_chaos(&chaos);
+        if balance == 100 {
+            held += 1;
+        }
+        orders.insert(order);
+    }
+    println!("[ex02 Arc+Mutex] 余额 == 100 成立: {}/{} | 观察到 {} 种不同的加锁顺序", held, runs, orders.len());
+
+    // 2. ex03：Channel，不变量：5 笔交易一笔不少，且同一个钱包发出的顺序不乱 (FIFO)
+    let mut held = 0;
+    let mut orders = HashSet::new();
+    for r in 0..runs {
+        let chaos = ChaosScheduler::new(base_seed + r, 200);
+        let received = ex03_channel::channel_under_chaos(&chaos);
+        if ex03_channel::check_channel_invariant(&received) {
+            held += 1;
+        }
+        orders.insert(received);
+    }
+    println!("[ex03 Channel]    全部送达且各钱包内有序: {}/{} | 观察到 {} 种不同的接收顺序", held, runs, orders.len());
+
+    // 3. ex05：Gossip，不变量：所有节点的交易池和链头一致 (拓扑固定，只有线程交错在变)
+    let held = (0..runs).filter(|r| ex05_gossip::gossip_under_chaos(&ChaosScheduler::new(base_seed + r, 200))).count();
+    println!("[ex05 Gossip]     所有节点的交易池和链头一致: {}/{}", held, runs);
+
+    // 4. 对照组：没有扰动时，错误代码也常常"看起来没问题"
+    let calm = broken_deposit_under_chaos(&ChaosScheduler::disabled());
+    println!("\n[对照组] 错误的两段式加锁，不加扰动跑一次: 余额 = {}", calm);
+    let mut held = 0;
+    for r in 0..runs {
+        if broken_deposit_under_chaos(&ChaosScheduler::new(base_seed + r, 200)) == 100 {
+            held += 1;
+        }
+    }
+    println!("[对照组] 加上混沌扰动重跑: 余额 == 100 只成立 {}/{} 次 ❌", held, runs);
+}
+
+/*
+关键点总结：
+    1. "观察到 N 种不同的顺序" 说明扰动真的改变了线程交错，
+       而不变量在每一种交错下都成立，才说明 Arc<Mutex> / Channel 的写法是对的。
+
+    2. 对照组展示了混沌调度的价值：
+        错误代码在平静的调度下常常碰巧算对 (线程启动有先后，几乎不重叠)，
+        插桩点把"读"和"写"之间的窗口拉大，丢失更新立刻暴露出来。
+
+    3. 为什么 ChaosScheduler 是 Copy，而 ThreadChaos 不是？
+        ChaosScheduler 只是几个数字组成的配置，复制一份没有代价；
+        ThreadChaos 内部有会变化的 RNG 状态，每个线程 move 进去一份独占使用，
+        这样插桩点本身不需要任何同步 —— 否则"观测工具"自己就会改变被观测的交错。
+*/
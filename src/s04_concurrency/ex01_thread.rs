@@ -1,6 +1,12 @@
 // src/s04_concurrency/ex01_thread.rs
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use crate::common::input::read_line;
+use crate::s05_zk_lab::hash::{sha256, Digest};
+use crate::s06_chain::pow::leading_zero_bits;
 
 pub fn run() {
     println!("--- S04 Ex01: 线程基础 ---");
@@ -98,6 +104,9 @@ pub fn run() {
      */
     
     println!("Main: 任务全部完成，安全退出。");
+
+    // 4. 进阶：上面的矿工只是 sleep 假装在算，下面让几个线程真的分头找 nonce
+    parallel_mining();
 }
 
 
@@ -114,4 +123,100 @@ pub fn run() {
 // +----------------------+                            +-------------------------+
 //        |
 //        | 等待 (join)
-//        v
\ No newline at end of file
+//        v
+
+
+// ==========================================
+// 进阶：多个矿工线程并行找 nonce
+// ==========================================
+// 难度：SHA-256(区块数据 || nonce) 的前 DIFFICULTY_BITS 位为 0
+const DIFFICULTY_BITS: u32 = 16;
+const BLOCK_DATA: &[u8] = b"Block#101: [Tx3, Tx4]";
+// 每算这么多次哈希看一眼"找到了"旗子
+const CHECK_EVERY: u64 = 256;
+
+struct Winner {
+    miner: u64,
+    nonce: u64,
+    at: Duration,
+}
+
+fn block_hash(nonce: u64) -> Digest {
+    let mut bytes = BLOCK_DATA.to_vec();
+    bytes.extend_from_slice(&nonce.to_le_bytes());
+    sha256(&bytes)
+}
+
+fn parallel_mining() {
+    println!("\n--- 进阶: 并行挖矿 (分段搜索 nonce，AtomicBool 喊停，channel 送回结果) ---");
+    let miners = read_line("矿工线程数 (回车默认 4): ").parse::<u64>().ok().filter(|&n| (1..=64).contains(&n)).unwrap_or(4);
+    let span = u64::MAX / miners;
+    println!("Main: {} 个矿工，每人负责一段 nonce (约 {:.1e} 个)，难度 {} 位 (平均约 {} 次哈希)", miners, span as f64, DIFFICULTY_BITS, 1u64 << DIFFICULTY_BITS);
+
+    // found 是唯一共享的可变状态：只有"有没有人找到"这一位，用不着 Mutex
+    let found = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = mpsc::channel();
+    let start = Instant::now();
+
+    let handles: Vec<_> = (0..miners)
+        .map(|miner| {
+            let found = Arc::clone(&found);
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut hashes = 0u64;
+                for nonce in miner * span..(miner + 1) * span {
+                    // 合作式取消：没有办法从外面杀掉一个线程，只能让它自己定期看旗子、自己退出
+                    if hashes.is_multiple_of(CHECK_EVERY) && found.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    hashes += 1;
+                    if leading_zero_bits(&block_hash(nonce)) >= DIFFICULTY_BITS {
+                        // swap 返回旧值：只有把 false 换成 true 的那个线程是第一名，几乎同时找到的人不再发送
+                        if !found.swap(true, Ordering::Relaxed) {
+                            tx.send(Winner { miner, nonce, at: start.elapsed() }).unwrap();
+                        }
+                        break;
+                    }
+                }
+                (hashes, start.elapsed())
+            })
+        })
+        .collect();
+    // 自己手里的 tx 也要 drop：万一所有矿工都没找到就退出了，recv() 会返回 Err 而不是永远等下去
+    drop(tx);
+
+    let winner = match rx.recv() {
+        Ok(winner) => winner,
+        Err(_) => {
+            println!("Main: 所有矿工都退出了，没人找到 nonce (不应该发生)");
+            return;
+        }
+    };
+    println!("Main: 🏆 矿工 {} 在 {:.1?} 找到 nonce = {}", winner.miner, winner.at, winner.nonce);
+
+    let mut total = 0;
+    for (miner, handle) in handles.into_iter().enumerate() {
+        let (hashes, stopped) = handle.join().unwrap();
+        total += hashes;
+        if miner as u64 == winner.miner {
+            println!("  🏆 矿工 {}: 算了 {:>6} 次哈希，旗子是它自己插的", miner, hashes);
+        } else {
+            println!("     矿工 {}: 算了 {:>6} 次哈希，喊停之后 {:>9.1?} 才收工", miner, hashes, stopped.saturating_sub(winner.at));
+        }
+    }
+    let digest = block_hash(winner.nonce);
+    let ok = if leading_zero_bits(&digest) >= DIFFICULTY_BITS { "✅" } else { "❌" };
+    println!("Main: 一共算了 {} 次哈希，用时 {:.1?}；主线程重算一次验证: {}... {}", total, start.elapsed(), &hex::encode(digest)[..16], ok);
+}
+
+/*
+并行挖矿要点：
+    1. 切分搜索空间：每个线程一段互不重叠的 nonce，不会有两个线程算同一个 nonce，也不需要分配任务的锁。
+    2. 合作式取消：Rust (和大多数语言) 不能安全地从外面强行终止一个线程 —— 它可能正拿着锁、写到一半。
+        所以由赢家把 AtomicBool 置为 true，其余线程每 CHECK_EVERY 次哈希自己看一眼、自己退出。
+        看得越勤，收工越快，但每次 load 也有一点开销；"喊停之后多久才收工"就是这个取舍。
+    3. 结果走 channel，状态走原子变量：
+        nonce 只需要从赢家交给主线程一次，channel 正合适；"找到了"要被所有线程反复读，一个原子布尔就够。
+        swap(true) 一次完成"看旧值 + 写新值"，保证只有一个赢家 (load 再 store 会有两个线程都以为自己是第一)。
+    4. 单核机器上几个线程是轮流跑的，并行挖矿不会更快；核数够时，期望耗时约为单线程的 1 / N。
+*/
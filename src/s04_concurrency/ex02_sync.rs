@@ -3,6 +3,8 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+use super::chaos::ChaosScheduler;
+
 /*
  业务逻辑 (Business Logic)
     这就好比 10 个柜员同时在给同一个银行账户存钱：
@@ -33,7 +35,9 @@ pub fn run() {
         let handle = thread::spawn(move || {
             // 3. 获取锁
             // lock() 会阻塞，直到拿到锁
-            // unwrap() 是因为如果别的线程 panic 了，锁会"中毒"(Poisoned)，这里简化处理，直接崩溃
+            // 如果之前持有锁的线程 panic 了，锁会"中毒"(Poisoned)，lock() 返回 Err(PoisonError)
+            // unwrap() 让这个线程也跟着 panic，主线程的 handle.join().unwrap() 再接着 panic —— 一路崩溃
+            // 传播错误、用 into_inner() 恢复的写法见 ex11_poison
            let mut num = account_ref.lock().unwrap();
 
             // 4. 修改数据
@@ -54,6 +58,37 @@ pub fn run() {
     println!("Final Balance: {}", *account.lock().unwrap());
 }
 
+// 同一个存款场景的"插桩版"，供 chaos 模块反复回放
+// 不打印，只返回 (最终余额, 各线程拿到锁的先后顺序)
+// chaos.point() 就是插桩点：在那里注入随机的 yield / sleep，人为制造不同的线程交错
+pub fn deposit_under_chaos(chaos: &ChaosScheduler) -> (i32, Vec<u64>) {
+    let account = Arc::new(Mutex::new(0));
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let mut handles = vec![];
+
+    for i in 0..10 {
+        let account_ref = Arc::clone(&account);
+        let order_ref = Arc::clone(&order);
+        let mut chaos = chaos.for_thread(i);
+
+        handles.push(thread::spawn(move || {
+            chaos.point(); // 插桩点 1：线程刚启动
+            let mut num = account_ref.lock().unwrap();
+            chaos.point(); // 插桩点 2：持有锁期间被打断，别的线程只能干等
+            *num += 10;
+            order_ref.lock().unwrap().push(i);
+        }));
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let balance = *account.lock().unwrap();
+    let order = order.lock().unwrap().clone();
+    (balance, order)
+}
+
 /*
  解析：
     Arc (Atomic Reference Counted)：
@@ -1,7 +1,12 @@
 // src/s04_concurrency/ex03_channel.rs
 use std::sync::mpsc; // mpsc = Multiple Producer, Single Consumer
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use crate::common::input::read_line;
+
+use super::chaos::ChaosScheduler;
 
 /*
 一、 核心思想：并发哲学的转变
@@ -16,8 +21,21 @@ use std::time::Duration;
 - 生产者 (Wallets)：多个线程 (tx, tx1) 异步产生交易数据。
 - 消费者 (Node)：单个主线程 (rx) 接收并打包数据。
 - 优势：数据像水流一样，由上游流向下游，解耦了生产与消费，且数据的所有权随消息物理转移，根除了数据竞争。
+
+第二部分：有界队列 sync_channel(n)
+- 钱包发得快 (每 30ms 一笔)、节点打包得慢 (每 150ms 一笔)。
+- channel() 的缓冲区无限大，节点跟不上时交易在内存里越堆越多；
+  sync_channel(n) 最多缓冲 n 条，满了 send 就阻塞，生产者被迫放慢到消费者的速度 —— 这就是背压 (Backpressure)。
+- n = 0 是"会合通道"：没有缓冲区，send 要一直等到接收方把消息取走。
 */
 
+// 有界队列演示的参数：每个钱包发几笔，发送间隔和节点的处理时间
+const BOUNDED_TXS: usize = 4;
+const PRODUCE_MS: u64 = 30;
+const CONSUME_MS: u64 = 150;
+// send 等了超过这么久，就算"被队列满挡住了"
+const BLOCKED_MS: u128 = 20;
+
 pub fn run() {
     println!("--- S04 Ex03: 消息传递 (Channel) ---");
 
@@ -59,6 +77,97 @@ pub fn run() {
     }
 
     println!("Node: All senders disconnected. Exiting.");
+
+    let capacity = read_line("\n有界队列 sync_channel(n)，缓冲区大小 n (回车默认 2，0 = 会合通道): ").parse::<usize>().unwrap_or(2);
+    bounded_channel(capacity);
+}
+
+// 有界队列：两个快钱包 + 一个慢节点，打印每次 send 是否被挡住、当时队列里有几条
+fn bounded_channel(capacity: usize) {
+    println!("\n--- sync_channel({}): 钱包每 {}ms 发一笔，节点每 {}ms 处理一笔 ---", capacity, PRODUCE_MS, CONSUME_MS);
+    let (tx, rx) = mpsc::sync_channel::<String>(capacity);
+    // (已发出, 已取走)：两者之差就是队列深度；用 ex02 学过的 Arc<Mutex> 共享
+    let counts = Arc::new(Mutex::new((0usize, 0usize)));
+    let start = Instant::now();
+
+    let mut wallets = Vec::new();
+    for name in ["A", "B"] {
+        let tx = tx.clone();
+        let counts = Arc::clone(&counts);
+        wallets.push(thread::spawn(move || {
+            let mut blocked = 0;
+            for i in 1..=BOUNDED_TXS {
+                let t = format!("Tx_{}{}", name, i);
+                let before = Instant::now();
+                // 队列满时 send 在这里阻塞，直到节点取走一条腾出位置
+                tx.send(t.clone()).unwrap();
+                let waited = before.elapsed().as_millis();
+                // send 返回到这里加锁之间，节点可能已经取走了下一条还没来得及计数，差值会短暂多 1，按容量封顶
+                let depth = {
+                    let mut c = counts.lock().unwrap();
+                    c.0 += 1;
+                    c.0.saturating_sub(c.1).min(capacity)
+                };
+                if waited >= BLOCKED_MS {
+                    blocked += 1;
+                    println!("[{:>4}ms] Wallet {} sent {} ⏸ 队列满，阻塞了 {}ms (队列深度 {}/{})", start.elapsed().as_millis(), name, t, waited, depth, capacity);
+                } else {
+                    println!("[{:>4}ms] Wallet {} sent {} 立即返回 (队列深度 {}/{})", start.elapsed().as_millis(), name, t, depth, capacity);
+                }
+                thread::sleep(Duration::from_millis(PRODUCE_MS));
+            }
+            blocked
+        }));
+    }
+    // 和第一部分一样：主线程手里的 tx 不 drop，下面的 for 永远等不到结束
+    drop(tx);
+
+    for received in rx {
+        let depth = {
+            let mut c = counts.lock().unwrap();
+            c.1 += 1;
+            c.0.saturating_sub(c.1)
+        };
+        println!("[{:>4}ms] Node: Got {} (剩 {} 条排队)", start.elapsed().as_millis(), received, depth);
+        thread::sleep(Duration::from_millis(CONSUME_MS));
+    }
+
+    let blocked: usize = wallets.into_iter().map(|w| w.join().unwrap()).sum();
+    println!("Node: 处理完 {} 笔，用时 {}ms；钱包一共被挡住 {} 次", 2 * BOUNDED_TXS, start.elapsed().as_millis(), blocked);
+    println!("缓冲区最多 {} 条：钱包的发送速度被压到了和节点的处理速度一样", capacity);
+}
+
+// 同一个 Channel 场景的"插桩版"，供 chaos 模块反复回放
+// 用 chaos.point() 代替固定的 sleep，返回节点实际收到交易的顺序
+pub fn channel_under_chaos(chaos: &ChaosScheduler) -> Vec<String> {
+    let (tx, rx) = mpsc::channel();
+
+    let wallets = [("A", vec!["Tx_A1", "Tx_A2", "Tx_A3"]), ("B", vec!["Tx_B1", "Tx_B2"])];
+    for (i, (_, txs)) in wallets.into_iter().enumerate() {
+        let tx = tx.clone();
+        let mut chaos = chaos.for_thread(i as u64);
+        thread::spawn(move || {
+            for t in txs {
+                chaos.point(); // 插桩点：每次发送前随机扰动
+                tx.send(String::from(t)).unwrap();
+            }
+        });
+    }
+    // 主线程手里的 tx 必须 drop，否则 rx 永远等不到"所有发送端关闭"
+    drop(tx);
+
+    rx.iter().collect()
+}
+
+// 不变量：5 笔交易全部送达，且同一个钱包的交易保持发送顺序 (Channel 是 FIFO)
+// 不同钱包之间的先后顺序是不确定的，这不算错
+pub fn check_channel_invariant(received: &[String]) -> bool {
+    let from = |prefix: &str| -> Vec<&String> {
+        received.iter().filter(|t| t.starts_with(prefix)).collect()
+    };
+    received.len() == 5
+        && from("Tx_A") == ["Tx_A1", "Tx_A2", "Tx_A3"]
+        && from("Tx_B") == ["Tx_B1", "Tx_B2"]
 }
 
 
@@ -115,7 +224,7 @@ pub fn run() {
       
    C. 背压警告 (Backpressure Warning)
       注意 `send` 永远不会阻塞。如果消费者处理过慢，`Nodes` 会在堆上无限堆积，
-      最终导致 OOM (Out of Memory)。生产环境常推荐 `sync_channel` (有界队列)。
+      最终导致 OOM (Out of Memory)。生产环境常推荐 `sync_channel` (有界队列)，见 run() 的第二部分。
 
 
 3. 接收数据：缓存优化与性能 (Cache Efficiency)
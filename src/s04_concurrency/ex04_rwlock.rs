@@ -0,0 +1,205 @@
+// src/s04_concurrency/ex04_rwlock.rs
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::common::input::read_line;
+
+/*
+业务场景：节点的链头 (tip) 被很多线程读 —— RPC 查询、钱包同步、交易池检查；只有出块线程偶尔写一次。
+    ex02 的 Mutex 不分读写一律排队：8 个查询明明可以同时进行，却只能一个一个来。
+    RwLock (读写锁)：读锁可以被很多线程同时持有，写锁独占 (拿写锁时不能有任何读者)。
+
+本练习：
+    1. 同一份负载：N 个读线程 (默认 8) 反复查询链头 (每次在锁里待 READ_MS)，一个写线程每 WRITE_EVERY_MS 出一个块
+       分别跑在 Mutex 和 RwLock 上，比较查询吞吐量和写者等锁的时间
+    2. 写者饥饿 (writer starvation)：读者一个接一个，读锁几乎从来没有"空"的时候
+       write() 会排队：std 的 RwLock (Linux 上基于 futex) 看到有写者在等，就不再放新读者进来，写者最多等一轮读
+       "礼貌的"写者只用 try_write()，失败了让出 CPU 再试：它从不排队，读锁永远有人拿着，它就永远拿不到
+*/
+
+const RUN_MS: u64 = 600;
+const READ_MS: u64 = 1;
+const WRITE_EVERY_MS: u64 = 20;
+const WRITE_MS: u64 = 1;
+
+#[derive(Debug, Clone, Copy)]
+struct ChainTip {
+    height: u64,
+    hash: u64,
+}
+
+impl ChainTip {
+    fn next(&self) -> ChainTip {
+        ChainTip { height: self.height + 1, hash: self.hash.wrapping_mul(0x9E37_79B9_7F4A_7C15).rotate_left(17) ^ self.height }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Strategy {
+    Mutex,
+    RwLock,
+    // RwLock，但写者只用 try_write
+    PoliteWriter,
+}
+
+impl Strategy {
+    fn name(&self) -> &'static str {
+        match self {
+            Strategy::Mutex => "Mutex",
+            Strategy::RwLock => "RwLock + write()",
+            Strategy::PoliteWriter => "RwLock + try_write()",
+        }
+    }
+}
+
+// 两种锁包一层：读和写都是"拿到锁，在锁里执行闭包"，负载代码不用关心底下是哪种锁
+enum SharedTip {
+    Mutex(Mutex<ChainTip>),
+    RwLock(RwLock<ChainTip>),
+}
+
+impl SharedTip {
+    fn read<R>(&self, f: impl FnOnce(&ChainTip) -> R) -> R {
+        match self {
+            SharedTip::Mutex(m) => f(&m.lock().unwrap()),
+            SharedTip::RwLock(l) => f(&l.read().unwrap()),
+        }
+    }
+
+    // 阻塞等锁 (排队)
+    fn write(&self, f: impl FnOnce(&mut ChainTip)) {
+        match self {
+            SharedTip::Mutex(m) => f(&mut m.lock().unwrap()),
+            SharedTip::RwLock(l) => f(&mut l.write().unwrap()),
+        }
+    }
+
+    // 只试一次：锁被占着就返回 false，不排队
+    fn try_write(&self, f: impl FnOnce(&mut ChainTip)) -> bool {
+        match self {
+            SharedTip::Mutex(m) => m.try_lock().map(|mut tip| f(&mut tip)).is_ok(),
+            SharedTip::RwLock(l) => l.try_write().map(|mut tip| f(&mut tip)).is_ok(),
+        }
+    }
+}
+
+struct Report {
+    reads: u64,
+    blocks: u64,
+    final_height: u64,
+    max_write_wait: Duration,
+    // 写者一共调了几次 try_write (只有 PoliteWriter 用)
+    tries: u64,
+}
+
+fn run_workload(strategy: Strategy, readers: usize) -> Report {
+    let genesis = ChainTip { height: 0, hash: 0x5eed };
+    let tip = Arc::new(match strategy {
+        Strategy::Mutex => SharedTip::Mutex(Mutex::new(genesis)),
+        Strategy::RwLock | Strategy::PoliteWriter => SharedTip::RwLock(RwLock::new(genesis)),
+    });
+    let deadline = Instant::now() + Duration::from_millis(RUN_MS);
+
+    // 1. 读线程：到点之前反复查询链头；在锁里待 READ_MS，模拟把链头序列化成 RPC 响应
+    let mut handles = Vec::new();
+    for _ in 0..readers {
+        let tip = Arc::clone(&tip);
+        handles.push(thread::spawn(move || {
+            let mut reads = 0u64;
+            while Instant::now() < deadline {
+                tip.read(|t| {
+                    thread::sleep(Duration::from_millis(READ_MS));
+                    t.height
+                });
+                reads += 1;
+            }
+            reads
+        }));
+    }
+
+    // 2. 写线程：每隔 WRITE_EVERY_MS 出一个块，记录从"想写"到"拿到写锁"等了多久
+    let writer_tip = Arc::clone(&tip);
+    let writer = thread::spawn(move || {
+        let (mut blocks, mut tries, mut max_wait) = (0u64, 0u64, Duration::ZERO);
+        let append = |t: &mut ChainTip| {
+            thread::sleep(Duration::from_millis(WRITE_MS));
+            *t = t.next();
+        };
+        while Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(WRITE_EVERY_MS));
+            let asked = Instant::now();
+            let written = if strategy == Strategy::PoliteWriter {
+                loop {
+                    tries += 1;
+                    if writer_tip.try_write(append) {
+                        break true;
+                    }
+                    if Instant::now() >= deadline {
+                        break false;
+                    }
+                    thread::yield_now();
+                }
+            } else {
+                writer_tip.write(append);
+                true
+            };
+            max_wait = max_wait.max(asked.elapsed());
+            if written {
+                blocks += 1;
+            }
+        }
+        (blocks, tries, max_wait)
+    });
+
+    let reads = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    let (blocks, tries, max_write_wait) = writer.join().unwrap();
+    let final_height = tip.read(|t| t.height);
+    Report { reads, blocks, final_height, max_write_wait, tries }
+}
+
+pub fn run() {
+    println!("--- S04 Ex04: 读写锁 (RwLock：很多读者，偶尔一个写者) ---");
+    let readers = read_line("读线程个数 (回车默认 8): ").parse::<usize>().ok().filter(|&n| n > 0).unwrap_or(8);
+    println!("每种方案跑 {}ms：{} 个读线程每次持锁 {}ms，写线程每 {}ms 出一个块 (持锁 {}ms)", RUN_MS, readers, READ_MS, WRITE_EVERY_MS, WRITE_MS);
+    println!("理想情况：查询 ≈ {} 次 (读者完全并行)，出块 ≈ {} 个\n", readers as u64 * RUN_MS / READ_MS, RUN_MS / (WRITE_EVERY_MS + WRITE_MS));
+
+    println!("{:<22} {:>8} {:>6} {:>14}", "方案", "查询次数", "出块", "写者最长等待");
+    let mut reads_of = Vec::new();
+    for strategy in [Strategy::Mutex, Strategy::RwLock, Strategy::PoliteWriter] {
+        let report = run_workload(strategy, readers);
+        let mut line = format!("{:<22} {:>10} {:>8} {:>16}", strategy.name(), report.reads, report.blocks, format!("{:.1?}", report.max_write_wait));
+        if strategy == Strategy::PoliteWriter {
+            line.push_str(&format!("   (try_write {} 次)", report.tries));
+        }
+        if report.final_height != report.blocks {
+            line.push_str(&format!("   ❌ 链高 {} 和出块数对不上", report.final_height));
+        }
+        println!("{}", line);
+        reads_of.push(report.reads);
+    }
+
+    // 3. 解读
+    println!();
+    println!("Mutex: 读者之间也互斥，{} 个读线程排成一队，吞吐量 ≈ 一个线程的量", readers);
+    if reads_of[0] > 0 {
+        println!("RwLock: 读者并行，查询次数是 Mutex 的 {:.1} 倍", reads_of[1] as f64 / reads_of[0] as f64);
+    }
+    println!("write() 排队等锁，读者再多也能按时出块；只用 try_write() 的写者一直在和读者抢空档，出块寥寥无几 (饥饿)");
+}
+
+/*
+关键点总结：
+    1. 读多写少 -> RwLock：
+        RwLockReadGuard 可以同时存在很多个，RwLockWriteGuard 只能有一个，而且两者不能共存；
+        这正是借用规则 (很多 & 或者一个 &mut) 在运行时的版本。
+
+    2. RwLock 不是免费的：
+        读锁也要原子地修改读者计数，临界区很短时 (只是读一个 u64) 它可能比 Mutex 还慢；
+        临界区越长、读者越多，RwLock 的优势越明显。
+
+    3. 饥饿取决于"写者怎么等"和锁的策略：
+        读者优先的锁：只要还有读者，新读者就能进来，写者可能永远等下去；
+        std 的 RwLock 在不同平台上策略不同 (文档不做保证)，Linux 上有写者在排队时新读者会被挡住。
+        但如果写者自己不排队 (只用 try_write 轮询)，任何锁都救不了它。
+*/
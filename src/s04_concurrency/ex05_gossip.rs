@@ -0,0 +1,270 @@
+// src/s04_concurrency/ex05_gossip.rs
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::common::rng::SimpleRng;
+
+use super::chaos::{ChaosScheduler, ThreadChaos};
+
+/*
+业务场景：P2P 网络里没有中心服务器，交易和区块靠"八卦" (gossip) 传遍全网
+    每个节点只认识几个邻居 (peer)。收到一条新消息：自己处理一遍，再转发给除了来源以外的所有邻居。
+    网络里有环 (A-B-C-A)，同一条消息会从不同的路径绕回来：
+        没有去重 -> 消息在环里永远转下去 (广播风暴)
+        有 seen 集合 -> 第二次见到就丢掉，不处理也不转发
+
+本练习：
+    1. N 个节点线程，随机拓扑 (先连一棵随机生成树保证连通，再随机加几条边制造环)
+    2. 每个节点一个收件箱 (mpsc::Receiver)，手里拿着邻居收件箱的 Sender —— ex03 的"多生产者单消费者"正好对应"多个邻居往我这里发"
+    3. 主线程往随机节点注入交易和区块 (区块会把交易从交易池里拿走，而且可能比交易先到)
+    4. 每个节点第一次见到一条消息就向主线程报告；主线程收齐 N × 消息数 份报告后发 Shutdown
+    5. 最后检查：所有节点的交易池和链头完全一致
+*/
+
+const NODES: usize = 8;
+const EXTRA_EDGES: usize = 4;
+const TXS: u64 = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Tx { id: u64 },
+    Block { height: u64, txs: Vec<u64> },
+}
+
+// seen 集合里存的是"是哪条消息"，而不是消息本身
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Key {
+    Tx(u64),
+    Block(u64),
+}
+
+impl Item {
+    fn key(&self) -> Key {
+        match self {
+            Item::Tx { id, .. } => Key::Tx(*id),
+            Item::Block { height, .. } => Key::Block(*height),
+        }
+    }
+}
+
+enum Message {
+    // from：上一跳是谁 (主线程注入时是 None)；hops：离源头几跳
+    Gossip { from: Option<usize>, hops: u32, item: Item },
+    Shutdown,
+}
+
+// 节点 -> 主线程：第一次见到某条消息
+struct Report {
+    key: Key,
+    hops: u32,
+}
+
+#[derive(Debug)]
+struct NodeSummary {
+    mempool: BTreeSet<u64>,
+    tip: u64,
+    received: usize,
+    duplicates: usize,
+    forwarded: usize,
+}
+
+struct Simulation {
+    peers: Vec<Vec<usize>>,
+    summaries: Vec<NodeSummary>,
+    // 每条消息传遍全网用了几跳 (最远的那个节点)
+    spread: BTreeMap<Key, u32>,
+    expected_mempool: BTreeSet<u64>,
+    expected_tip: u64,
+}
+
+// ==========================================
+// 1. 随机拓扑
+// ==========================================
+fn random_topology(n: usize, extra: usize, rng: &mut SimpleRng) -> Vec<Vec<usize>> {
+    let mut peers = vec![Vec::new(); n];
+    let connect = |a: usize, b: usize, peers: &mut Vec<Vec<usize>>| {
+        if a != b && !peers[a].contains(&b) {
+            peers[a].push(b);
+            peers[b].push(a);
+        }
+    };
+    // 第 i 个节点连到前面随便一个节点：得到一棵生成树，保证任意两个节点之间有路
+    for i in 1..n {
+        let j = rng.gen_range(i as u64) as usize;
+        connect(i, j, &mut peers);
+    }
+    for _ in 0..extra {
+        let (a, b) = (rng.gen_range(n as u64) as usize, rng.gen_range(n as u64) as usize);
+        connect(a, b, &mut peers);
+    }
+    for list in &mut peers {
+        list.sort_unstable();
+    }
+    peers
+}
+
+// ==========================================
+// 2. 节点线程
+// ==========================================
+fn node_loop(me: usize, inbox: Receiver<Message>, peers: Vec<(usize, Sender<Message>)>, reports: Sender<Report>, mut chaos: ThreadChaos) -> NodeSummary {
+    let mut seen = HashSet::new();
+    let mut confirmed = HashSet::new();
+    let mut summary = NodeSummary { mempool: BTreeSet::new(), tip: 0, received: 0, duplicates: 0, forwarded: 0 };
+
+    for message in inbox {
+        let Message::Gossip { from, hops, item } = message else {
+            break;
+        };
+        chaos.point();
+        summary.received += 1;
+        // insert 返回 false = 以前见过：不处理、不转发，环在这里被切断
+        if !seen.insert(item.key()) {
+            summary.duplicates += 1;
+            continue;
+        }
+        match &item {
+            // 区块可能比它里面的交易先到：已经确认过的交易不能再进交易池
+            Item::Tx { id, .. } => {
+                if !confirmed.contains(id) {
+                    summary.mempool.insert(*id);
+                }
+            }
+            Item::Block { height, txs } => {
+                for id in txs {
+                    confirmed.insert(*id);
+                    summary.mempool.remove(id);
+                }
+                summary.tip = summary.tip.max(*height);
+            }
+        }
+        // 主线程可能已经收齐报告走了；发送失败就算了
+        let _ = reports.send(Report { key: item.key(), hops });
+        for (peer, sender) in &peers {
+            if Some(*peer) != from {
+                // 邻居可能已经 Shutdown，它的 Receiver 被 drop 了，send 会返回 Err —— 同样忽略
+                let _ = sender.send(Message::Gossip { from: Some(me), hops: hops + 1, item: item.clone() });
+                summary.forwarded += 1;
+            }
+        }
+    }
+    summary
+}
+
+// ==========================================
+// 3. 整个模拟：建网、注入、等收敛、收尾
+// ==========================================
+fn simulate(topology_seed: u64, chaos: &ChaosScheduler) -> Simulation {
+    let mut rng = SimpleRng::new(topology_seed);
+    let peers = random_topology(NODES, EXTRA_EDGES, &mut rng);
+
+    let (inbox_senders, inboxes): (Vec<Sender<Message>>, Vec<Receiver<Message>>) = (0..NODES).map(|_| mpsc::channel()).unzip();
+    let (report_sender, reports) = mpsc::channel();
+    let mut handles = Vec::new();
+    for (me, inbox) in inboxes.into_iter().enumerate() {
+        let links: Vec<(usize, Sender<Message>)> = peers[me].iter().map(|&p| (p, inbox_senders[p].clone())).collect();
+        let reports = report_sender.clone();
+        let chaos = chaos.for_thread(me as u64);
+        handles.push(thread::spawn(move || node_loop(me, inbox, links, reports, chaos)));
+    }
+    drop(report_sender);
+
+    // 交易和区块交错注入到随机节点：区块 1 打包 0..4，区块 2 打包 4..7，剩下的留在交易池
+    let blocks = [(1, 0..4), (2, 4..7)];
+    let mut items: Vec<Item> = (0..TXS).map(|id| Item::Tx { id }).collect();
+    for (height, range) in blocks.clone() {
+        let at = rng.gen_range(items.len() as u64) as usize;
+        items.insert(at, Item::Block { height, txs: range.collect() });
+    }
+    let total = items.len();
+    for item in items {
+        let origin = rng.gen_range(NODES as u64) as usize;
+        let _ = inbox_senders[origin].send(Message::Gossip { from: None, hops: 0, item });
+    }
+
+    // 每个节点对每条消息恰好报告一次 (seen 集合保证)，收齐就说明全网都见过所有消息
+    let mut spread = BTreeMap::new();
+    for report in reports.iter().take(NODES * total) {
+        let hops = spread.entry(report.key).or_insert(0);
+        *hops = (*hops).max(report.hops);
+    }
+    for sender in &inbox_senders {
+        let _ = sender.send(Message::Shutdown);
+    }
+    let summaries = handles.into_iter().map(|h| h.join().expect("node thread panicked")).collect();
+
+    let confirmed: HashSet<u64> = blocks.iter().flat_map(|(_, range)| range.clone()).collect();
+    Simulation {
+        peers,
+        summaries,
+        spread,
+        expected_mempool: (0..TXS).filter(|id| !confirmed.contains(id)).collect(),
+        expected_tip: blocks.len() as u64,
+    }
+}
+
+// 不变量：每个节点的交易池 = 没被打包的交易，链头 = 最高的区块
+fn converged(sim: &Simulation) -> bool {
+    sim.summaries.iter().all(|s| s.mempool == sim.expected_mempool && s.tip == sim.expected_tip)
+}
+
+// 供 chaos 模块回放：拓扑固定，只改变线程交错
+pub fn gossip_under_chaos(chaos: &ChaosScheduler) -> bool {
+    converged(&simulate(7, chaos))
+}
+
+pub fn run() {
+    println!("--- S04 Ex05: P2P Gossip (Channel 洪泛) ---");
+    let seed = SimpleRng::from_time().next_u64() % 10_000;
+    let sim = simulate(seed, &ChaosScheduler::new(seed, 200));
+
+    println!("\n[1] 拓扑 (seed {}，{} 个节点，{} 条边)", seed, NODES, sim.peers.iter().map(Vec::len).sum::<usize>() / 2);
+    for (me, list) in sim.peers.iter().enumerate() {
+        println!("  节点 {} <-> {:?}", me, list);
+    }
+
+    println!("\n[2] 每条消息传遍全网用的跳数");
+    let line: Vec<String> = sim
+        .spread
+        .iter()
+        .map(|(key, hops)| match key {
+            Key::Tx(id) => format!("tx{}:{}", id, hops),
+            Key::Block(height) => format!("块{}:{}", height, hops),
+        })
+        .collect();
+    println!("  {}", line.join("  "));
+
+    println!("\n[3] 各节点统计");
+    println!("  {:>4} {:>6} {:>6} {:>6} {:>6} {:>4}  交易池", "节点", "收到", "重复", "转发", "邻居", "链头");
+    for (me, s) in sim.summaries.iter().enumerate() {
+        println!("  {:>6} {:>8} {:>8} {:>8} {:>8} {:>6}  {:?}", me, s.received, s.duplicates, s.forwarded, sim.peers[me].len(), s.tip, s.mempool);
+    }
+    let (received, duplicates): (usize, usize) = sim.summaries.iter().fold((0, 0), |(r, d), s| (r + s.received, d + s.duplicates));
+    println!("  合计收到 {} 条，其中 {} 条是重复的 (被 seen 集合挡住)；没有它，环上的消息会一直转下去", received, duplicates);
+
+    println!("\n[4] 一致性检查");
+    println!("  期望交易池 {:?}，链头 #{}", sim.expected_mempool, sim.expected_tip);
+    if converged(&sim) {
+        println!("  ✅ {} 个节点全部收敛", NODES);
+    } else {
+        println!("  ❌ 有节点没收敛 (不应该发生)");
+    }
+}
+
+/*
+关键点总结：
+    1. 洪泛 + 去重：
+        "转发给除来源以外的所有邻居"保证消息能到达连通图里的每个节点；
+        seen 集合保证每个节点对每条消息只处理、只转发一次，总转发次数 ≈ 边数 × 2 × 消息数，而不是无穷。
+
+    2. 顺序不可靠：
+        区块可能比它打包的交易先到。节点记着 confirmed 集合，迟到的交易不再进交易池 ——
+        收敛的是"集合"，不依赖到达顺序。
+
+    3. 怎么知道"传完了"：
+        节点之间互相持有 Sender，收件箱永远不会因为"所有发送端关闭"而结束 (ex03 的退出方式在这里不管用)。
+        这里让每个节点第一次见到消息时报告主线程，主线程数够 N × 消息数 份报告，再显式发 Shutdown。
+
+    4. 关机时的 send 失败是正常的：
+        先关机的节点 drop 了 Receiver，邻居再往它发就会 Err —— 真实网络里对端断开也一样，忽略即可。
+*/
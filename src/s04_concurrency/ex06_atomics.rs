@@ -0,0 +1,140 @@
+// src/s04_concurrency/ex06_atomics.rs
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/*
+业务场景：还是 ex02 那个账户，10 个柜员同时存钱。
+    账户里只有一个数字，为了 "+10" 每次都要加锁、解锁，还可能让线程睡下去等锁，有点杀鸡用牛刀。
+    CPU 本身就提供"原子地读-改-写一个字"的指令 (x86 上是 LOCK XADD)：AtomicU64::fetch_add。
+
+本练习：
+    1. ex02 的存款场景，把 Arc<Mutex<i32>> 换成 Arc<AtomicU64>，不需要锁也不需要 MutexGuard
+    2. 故意写错的版本：load 读出余额，再 store(余额 + 10) 写回去
+       两步各自都是原子的，合起来不是 —— 两个线程读到同一个旧值，一笔存款就丢了 (Lost Update)
+    3. 加大负载 (每个线程存 DEPOSITS 次)，三种做法比一比：最终余额对不对、花了多久
+*/
+
+const THREADS: u64 = 10;
+const DEPOSITS: u64 = 100_000;
+
+#[derive(Debug, Clone, Copy)]
+enum Approach {
+    Mutex,
+    FetchAdd,
+    // load + store：会丢更新
+    LoadStore,
+}
+
+impl Approach {
+    fn name(&self) -> &'static str {
+        match self {
+            Approach::Mutex => "Arc<Mutex<u64>>",
+            Approach::FetchAdd => "AtomicU64::fetch_add",
+            Approach::LoadStore => "load + store (错误)",
+        }
+    }
+}
+
+// THREADS 个线程，每个存 deposits 次、每次 amount；返回 (最终余额, 用时)
+fn deposit(approach: Approach, deposits: u64, amount: u64) -> (u64, Duration) {
+    let start = Instant::now();
+    let balance = match approach {
+        Approach::Mutex => {
+            let account = Arc::new(Mutex::new(0u64));
+            let handles: Vec<_> = (0..THREADS)
+                .map(|_| {
+                    let account = Arc::clone(&account);
+                    thread::spawn(move || {
+                        for _ in 0..deposits {
+                            *account.lock().unwrap() += amount;
+                        }
+                    })
+                })
+                .collect();
+            handles.into_iter().for_each(|h| h.join().unwrap());
+            let balance = *account.lock().unwrap();
+            balance
+        }
+        Approach::FetchAdd | Approach::LoadStore => {
+            let account = Arc::new(AtomicU64::new(0));
+            let handles: Vec<_> = (0..THREADS)
+                .map(|_| {
+                    let account = Arc::clone(&account);
+                    thread::spawn(move || {
+                        for _ in 0..deposits {
+                            if let Approach::FetchAdd = approach {
+                                account.fetch_add(amount, Ordering::Relaxed);
+                            } else {
+                                // ❌ 读和写之间，别的线程可能已经存过钱了，这里写回的是过时的值
+                                let current = account.load(Ordering::Relaxed);
+                                account.store(current + amount, Ordering::Relaxed);
+                            }
+                        }
+                    })
+                })
+                .collect();
+            handles.into_iter().for_each(|h| h.join().unwrap());
+            account.load(Ordering::Relaxed)
+        }
+    };
+    (balance, start.elapsed())
+}
+
+pub fn run() {
+    println!("--- S04 Ex06: 原子操作 (AtomicU64：不用锁的计数器) ---");
+
+    // ==========================================
+    // 1. ex02 的存款场景，换成原子变量
+    // ==========================================
+    println!("\n[1] 10 个线程各存 10 (和 ex02 一样，只是没有锁)");
+    let account = Arc::new(AtomicU64::new(0));
+    let mut handles = vec![];
+    for i in 0..THREADS {
+        let account_ref = Arc::clone(&account);
+        handles.push(thread::spawn(move || {
+            // fetch_add 返回的是加之前的值：每个线程都能知道"自己存完之后"的余额，而且不会和别人重复
+            let before = account_ref.fetch_add(10, Ordering::Relaxed);
+            println!("Thread {} deposited 10. Balance: {}", i, before + 10);
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    println!("Final Balance: {}", account.load(Ordering::Relaxed));
+
+    // ==========================================
+    // 2. 三种做法，加大负载
+    // ==========================================
+    let expected = THREADS * DEPOSITS;
+    println!("\n[2] {} 个线程，每个存 {} 次、每次 1，应该得到 {}", THREADS, DEPOSITS, expected);
+    for approach in [Approach::Mutex, Approach::FetchAdd, Approach::LoadStore] {
+        let (balance, elapsed) = deposit(approach, DEPOSITS, 1);
+        let verdict = if balance == expected {
+            String::from("✅")
+        } else {
+            format!("❌ 丢了 {} 笔 ({:.1}%)", expected - balance, (expected - balance) as f64 * 100.0 / expected as f64)
+        };
+        println!("  {:<24} 余额 {:>9}  用时 {:>10.2?}  {}", approach.name(), balance, elapsed, verdict);
+    }
+    println!("  load + store 有时候也会碰巧算对 (线程恰好没有交错)，多跑几次就能看到它丢钱；它不丢钱的时候也只是运气好");
+}
+
+/*
+关键点总结：
+    1. fetch_add 是一条指令完成的"读-改-写"：
+        多个核同时 fetch_add 同一个地址，硬件保证它们排成某个先后顺序，没有一笔会丢；
+        load 和 store 分别是原子的，但组合起来就不是 —— 和 chaos.rs 里"两次加锁"的错误版本是同一个 bug。
+
+    2. 原子操作不是免费的：
+        所有线程抢同一条缓存行，这条缓存行在核之间来回搬 (cache line bouncing)；
+        它比 Mutex 快，是因为永远不会让线程睡下去等锁，但仍然远慢于单线程的普通加法。
+
+    3. Ordering::Relaxed 够不够：
+        计数器只关心"这个数本身"，不依赖其他内存的可见性，Relaxed 就够了；
+        如果要用原子变量"发布"别的数据 (比如设置 ready 标志，通知别的线程去读一个结构体)，就要用 Release / Acquire。
+
+    4. 原子变量只能保护一个字：
+        要同时更新余额和交易记录 (两个字段保持一致)，还是需要 Mutex。
+*/
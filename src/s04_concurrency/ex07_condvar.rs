@@ -0,0 +1,214 @@
+// src/s04_concurrency/ex07_condvar.rs
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::common::rng::SimpleRng;
+
+/*
+业务场景：几个矿工线程挖出候选区块，放进一个共享队列；一个验证线程从队列里取出来验证。
+    队列空的时候验证线程该干什么？
+        忙等 (busy polling)：不停地加锁看一眼、解锁、再看一眼 —— 一个核被白白烧满
+        Condvar (条件变量)：睡下去，等矿工放进区块之后 notify 把它叫醒，睡着的时候不占 CPU
+
+本练习 (MINERS 个矿工各挖 BLOCKS_PER_MINER 个块，挖一个块要 10~40ms)：
+    1. 忙等：数一数验证线程一共看了多少次队列
+    2. Condvar + 手写 while 循环：wait 醒来不代表条件成立 ——
+       另有一个"心跳"线程每 HEARTBEAT_MS 毫秒 notify_all 一次 (模拟别的条件的通知和虚假唤醒 spurious wakeup)，
+       数一数醒来之后发现队列还是空的次数
+    3. Condvar::wait_while：同一个循环由标准库替你写好，闭包返回 true 就继续睡
+*/
+
+const MINERS: usize = 3;
+const BLOCKS_PER_MINER: u64 = 5;
+const HEARTBEAT_MS: u64 = 3;
+const VALIDATE_MS: u64 = 2;
+
+#[derive(Debug)]
+struct Candidate {
+    miner: usize,
+    nonce: u64,
+    mined_at: Instant,
+}
+
+// 条件变量永远和一把 Mutex 配对：它等的"条件"就是这把锁保护的数据
+#[derive(Default)]
+struct Queue {
+    blocks: VecDeque<Candidate>,
+    // 所有矿工都收工了：验证线程处理完剩下的就可以退出
+    done: bool,
+}
+
+type Shared = Arc<(Mutex<Queue>, Condvar)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Waiting {
+    BusyPoll,
+    WaitLoop,
+    WaitWhile,
+}
+
+#[derive(Default)]
+struct Stats {
+    validated: u64,
+    // 加锁看队列的次数 (忙等) / 被唤醒的次数 (Condvar)
+    checks: u64,
+    // 醒来 (或看一眼) 发现队列还是空的
+    empty_checks: u64,
+    // 从区块放进队列到被取走的最长时间
+    max_latency: Duration,
+}
+
+fn spawn_miners(shared: &Shared, seed: u64) -> Vec<JoinHandle<()>> {
+    (0..MINERS)
+        .map(|miner| {
+            let shared = Arc::clone(shared);
+            thread::spawn(move || {
+                let mut rng = SimpleRng::new(seed ^ (miner as u64 + 1).wrapping_mul(0x9E37_79B9));
+                for _ in 0..BLOCKS_PER_MINER {
+                    thread::sleep(Duration::from_millis(10 + rng.gen_range(31)));
+                    let (lock, cvar) = &*shared;
+                    lock.lock().unwrap().blocks.push_back(Candidate { miner, nonce: rng.next_u64() % 1_000_000, mined_at: Instant::now() });
+                    // 放进去之后通知：先改数据、再 notify，等待的一方醒来一定能看到新数据
+                    cvar.notify_one();
+                }
+            })
+        })
+        .collect()
+}
+
+// 取出一个区块并验证；验证在锁外面做，别让矿工等
+fn validate(block: Candidate, stats: &mut Stats, verbose: bool) {
+    let queued = block.mined_at.elapsed();
+    stats.validated += 1;
+    stats.max_latency = stats.max_latency.max(queued);
+    thread::sleep(Duration::from_millis(VALIDATE_MS));
+    if verbose {
+        println!("  Validator: ✅ 矿工 {} 的区块 nonce={:<6} (在队列里等了 {:.1?})", block.miner, block.nonce, queued);
+    }
+}
+
+fn validator(shared: Shared, waiting: Waiting, verbose: bool) -> Stats {
+    let mut stats = Stats::default();
+    let (lock, cvar) = &*shared;
+    loop {
+        let mut queue = lock.lock().unwrap();
+        match waiting {
+            // 看一眼就走，锁立刻释放，马上再来
+            Waiting::BusyPoll => {
+                stats.checks += 1;
+                if queue.blocks.is_empty() && !queue.done {
+                    stats.empty_checks += 1;
+                    continue;
+                }
+            }
+            // wait 会原子地"释放锁并睡下"，醒来时重新拿到锁；醒来之后必须再检查一遍条件
+            Waiting::WaitLoop => {
+                while queue.blocks.is_empty() && !queue.done {
+                    queue = cvar.wait(queue).unwrap();
+                    stats.checks += 1;
+                    if queue.blocks.is_empty() && !queue.done {
+                        stats.empty_checks += 1;
+                    }
+                }
+            }
+            // 同一个 while 循环，标准库替你写：闭包返回 true 表示"还要继续等"
+            Waiting::WaitWhile => {
+                let mut first = true;
+                queue = cvar
+                    .wait_while(queue, |q| {
+                        let waiting = q.blocks.is_empty() && !q.done;
+                        if !first {
+                            stats.checks += 1;
+                            if waiting {
+                                stats.empty_checks += 1;
+                            }
+                        }
+                        first = false;
+                        waiting
+                    })
+                    .unwrap();
+            }
+        }
+        match queue.blocks.pop_front() {
+            Some(block) => {
+                drop(queue);
+                validate(block, &mut stats, verbose);
+            }
+            None => return stats,
+        }
+    }
+}
+
+fn run_round(waiting: Waiting, seed: u64, verbose: bool) -> (Stats, Duration) {
+    let start = Instant::now();
+    let shared: Shared = Arc::new((Mutex::new(Queue::default()), Condvar::new()));
+    let miners = spawn_miners(&shared, seed);
+
+    let validator_shared = Arc::clone(&shared);
+    let validator = thread::spawn(move || validator(validator_shared, waiting, verbose));
+
+    // 心跳线程：不放任何东西，只是不停地 notify_all
+    let heartbeat_shared = Arc::clone(&shared);
+    let heartbeat = thread::spawn(move || {
+        let (lock, cvar) = &*heartbeat_shared;
+        while !lock.lock().unwrap().done {
+            cvar.notify_all();
+            thread::sleep(Duration::from_millis(HEARTBEAT_MS));
+        }
+    });
+
+    for miner in miners {
+        miner.join().unwrap();
+    }
+    // 收工：改 done 和放区块一样，要在锁里改、改完再通知
+    let (lock, cvar) = &*shared;
+    lock.lock().unwrap().done = true;
+    cvar.notify_all();
+
+    let stats = validator.join().unwrap();
+    heartbeat.join().unwrap();
+    (stats, start.elapsed())
+}
+
+pub fn run() {
+    println!("--- S04 Ex07: 条件变量 (Mutex + Condvar：验证线程等区块，而不是忙等) ---");
+    let seed = SimpleRng::from_time().next_u64();
+    let total = MINERS as u64 * BLOCKS_PER_MINER;
+    println!("{} 个矿工各挖 {} 个块；同一个 seed 的三轮，矿工出块的节奏完全一样", MINERS, BLOCKS_PER_MINER);
+
+    let rounds = [
+        (Waiting::BusyPoll, "[1] 忙等：加锁看一眼，空的就马上再看"),
+        (Waiting::WaitLoop, "[2] Condvar + 手写 while 循环 (心跳线程在旁边不停 notify_all)"),
+        (Waiting::WaitWhile, "[3] Condvar::wait_while (同样有心跳线程)"),
+    ];
+    for (waiting, title) in rounds {
+        println!("\n{}", title);
+        let (stats, elapsed) = run_round(waiting, seed, waiting == Waiting::WaitWhile);
+        let ok = if stats.validated == total { "✅" } else { "❌" };
+        println!("  验证了 {}/{} 个块 {}，用时 {:.0?}，区块最长排队 {:.1?}", stats.validated, total, ok, elapsed, stats.max_latency);
+        match waiting {
+            Waiting::BusyPoll => println!("  看了 {} 次队列，其中 {} 次是空的 —— 这些全是白烧的 CPU", stats.checks, stats.empty_checks),
+            _ => println!("  被唤醒 {} 次，其中 {} 次醒来发现队列还是空的 (心跳 / 虚假唤醒)，又睡了回去", stats.checks, stats.empty_checks),
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. Condvar 的三件套：一把 Mutex、一个条件 (队列非空 || 收工)、一个 Condvar
+        等待方：拿锁 -> 检查条件 -> 不满足就 wait (释放锁 + 睡下是原子的，不会错过中间的 notify) -> 醒来时重新持有锁
+        通知方：拿锁 -> 改数据 -> notify_one / notify_all
+
+    2. 醒来 ≠ 条件成立：
+        虚假唤醒 (操作系统层面没人 notify 也会醒)、notify_all 叫醒了所有人但只有一个抢到区块、别的条件的通知……
+        所以 wait 永远写在 while 循环里；wait_while 就是这个循环的标准写法，不会忘。
+
+    3. 忙等 vs 等待：
+        两种写法验证的区块一样多、延迟也差不多，但忙等把等待的时间全部花在加锁解锁上，还和矿工抢同一把锁。
+        Condvar 等待时线程挂起在内核里 (futex)，不占 CPU。
+
+    4. 收工信号也是条件的一部分：
+        只等"队列非空"，矿工全部退出后验证线程会永远睡下去；done 标志 + notify_all 让它有机会醒来退出。
+*/
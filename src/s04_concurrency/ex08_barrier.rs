@@ -0,0 +1,138 @@
+// src/s04_concurrency/ex08_barrier.rs
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Barrier, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::common::rng::SimpleRng;
+
+/*
+业务场景：一场按轮进行的挖矿比赛。每一轮大家拿到同一个区块模板，同时开挖，谁先找到 nonce 谁赢；
+    所有人都交卷之后公布结果，再一起开始下一轮。
+    "同时开始"和"都交卷了才公布"都是在等别的线程 —— 不需要传消息，只需要一个集合点：Barrier。
+
+本练习 (MINERS 个矿工线程，ROUNDS 轮)：
+    1. 每轮开始前在 start 栅栏集合：最后一个到的线程一到，所有线程同时放行 (打印各线程出发时间的差距)
+    2. 各自挖矿，把结果写进共享的成绩表 (Arc<Mutex<Vec<..>>>，没有 channel)
+    3. 在 finish 栅栏集合：wait() 返回值里恰好有一个是"领头的" (is_leader)，由它公布本轮结果、清空成绩表
+    4. 领头的线程公布完也要回到下一轮的 start 栅栏，别人在那里等它 —— 轮与轮之间不会串
+*/
+
+const MINERS: usize = 4;
+const ROUNDS: u64 = 4;
+// 哈希的前 DIFFICULTY_BITS 位是 0 才算挖到
+const DIFFICULTY_BITS: u32 = 14;
+
+#[derive(Debug, Clone, Copy)]
+struct Attempt {
+    miner: usize,
+    nonce: u64,
+    hashes: u64,
+    started: Instant,
+    elapsed: Duration,
+}
+
+fn block_hash(round: u64, miner: usize, nonce: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (round, miner, nonce).hash(&mut hasher);
+    hasher.finish()
+}
+
+// 从随机起点往上数，直到哈希的前 DIFFICULTY_BITS 位全是 0
+fn mine(round: u64, miner: usize, rng: &mut SimpleRng) -> (u64, u64) {
+    let mut nonce = rng.next_u64() >> 16;
+    let mut hashes = 0;
+    loop {
+        hashes += 1;
+        if block_hash(round, miner, nonce).leading_zeros() >= DIFFICULTY_BITS {
+            return (nonce, hashes);
+        }
+        nonce += 1;
+    }
+}
+
+// 领头的线程在 finish 栅栏之后调用：此时所有人都交卷了，成绩表是完整的
+// 按各自的用时排名 (从各自真正跑起来算起)，不受出发时间差距的影响
+fn announce(round: u64, board: &mut Vec<Attempt>, wins: &mut [u32]) {
+    board.sort_by_key(|a| a.elapsed);
+    let first_start = board.iter().map(|a| a.started).min();
+    let last_start = board.iter().map(|a| a.started).max();
+    let skew = match (first_start, last_start) {
+        (Some(first), Some(last)) => last - first,
+        _ => Duration::ZERO,
+    };
+    println!("\n  第 {} 轮 ({} 人交卷，出发时间最多差 {:.1?})", round, board.len(), skew);
+    for (rank, a) in board.iter().enumerate() {
+        let mark = if rank == 0 { "🏆" } else { "  " };
+        println!("    {} 矿工 {}  nonce={:<16} 算了 {:>6} 次哈希  用时 {:>9.2?}", mark, a.miner, a.nonce, a.hashes, a.elapsed);
+    }
+    if let Some(winner) = board.first() {
+        wins[winner.miner] += 1;
+    }
+    board.clear();
+}
+
+pub fn run() {
+    println!("--- S04 Ex08: 栅栏 (Barrier：按轮同时开挖、全部交卷再公布) ---");
+    println!("{} 个矿工，{} 轮，难度: 哈希前 {} 位为 0 (平均约 {} 次哈希)", MINERS, ROUNDS, DIFFICULTY_BITS, 1u64 << DIFFICULTY_BITS);
+
+    // 两个栅栏都是 MINERS 个参与者：第 MINERS 个线程调用 wait() 时，所有人一起放行，栅栏自动复位给下一轮用
+    let start = Arc::new(Barrier::new(MINERS));
+    let finish = Arc::new(Barrier::new(MINERS));
+    let board = Arc::new(Mutex::new(Vec::new()));
+    let wins = Arc::new(Mutex::new(vec![0u32; MINERS]));
+    let seed = SimpleRng::from_time().next_u64();
+
+    let mut handles = Vec::new();
+    for miner in 0..MINERS {
+        let (start, finish, board, wins) = (Arc::clone(&start), Arc::clone(&finish), Arc::clone(&board), Arc::clone(&wins));
+        handles.push(thread::spawn(move || {
+            let mut rng = SimpleRng::new(seed ^ (miner as u64 + 1).wrapping_mul(0x9E37_79B9));
+            let mut led = 0;
+            for round in 1..=ROUNDS {
+                // 1. 集合：先到的线程在这里睡着，等最后一个
+                start.wait();
+                let started = Instant::now();
+
+                // 2. 各挖各的，挖完写成绩表
+                let (nonce, hashes) = mine(round, miner, &mut rng);
+                board.lock().unwrap().push(Attempt { miner, nonce, hashes, started, elapsed: started.elapsed() });
+
+                // 3. 交卷：只有一个线程拿到 is_leader() == true，由它公布
+                if finish.wait().is_leader() {
+                    led += 1;
+                    announce(round, &mut board.lock().unwrap(), &mut wins.lock().unwrap());
+                }
+            }
+            led
+        }));
+    }
+
+    let led: Vec<u32> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    let wins = wins.lock().unwrap();
+    println!("\n  最终比分:");
+    for miner in 0..MINERS {
+        println!("    矿工 {}  赢了 {} 轮，当了 {} 次公布人", miner, wins[miner], led[miner]);
+    }
+    println!("  公布人一共 {} 次 = 轮数 {}：每轮恰好一个领头的", led.iter().sum::<u32>(), ROUNDS);
+}
+
+/*
+关键点总结：
+    1. Barrier::new(n) 是一个能重复使用的集合点：
+        前 n - 1 个 wait() 的线程睡下，第 n 个到达时所有人一起醒来，计数归零，下一轮接着用。
+        所以"所有人同时开始"不需要主线程发令，也不需要 channel。
+
+    2. is_leader 解决"谁来做一次性的收尾工作"：
+        每一轮恰好有一个线程的 wait() 返回 is_leader() == true；
+        由它公布成绩、清空成绩表，别的线程已经走向下一轮的 start 栅栏，在那里等它回来。
+
+    3. "同时放行"不等于"同时在跑"：
+        栅栏一起叫醒所有线程，但线程要等操作系统把它调度到某个核上才真正开始；
+        核比线程少 (单核机器上尤其明显) 时，出发时间能差出几个毫秒。
+
+    4. 栅栏要求人数固定：
+        有一个线程提前退出 (比如 panic)，其余的线程会在 wait() 上永远睡下去。
+        参与者会动态增减的场景，用 Condvar 自己写计数，或者改成 channel 汇报。
+*/
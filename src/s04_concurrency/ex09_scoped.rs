@@ -0,0 +1,151 @@
+// src/s04_concurrency/ex09_scoped.rs
+use std::mem;
+use std::thread;
+
+use crate::common::rng::SimpleRng;
+
+/*
+业务场景：打包节点手里有一批交易 (主线程栈上的一个 Vec)，想让几个线程并行检查手续费、统计总额。
+    ex01 里 thread::spawn 必须 move：子线程可能比 run() 活得久，不能借用主线程栈上的东西。
+    想共享只能 Arc (改写数据结构) 或者 clone 一份 (复制整批交易)。
+    但这里我们明明会等所有线程结束才往下走 —— thread::scope 把这个"保证"告诉编译器。
+
+本练习：
+    1. ex01 的老办法：每个线程 clone 一份交易，数一数复制了多少字节
+    2. thread::scope：每个线程借一段 &[Transaction] (同一块内存，指针一样)，还共享借用同一份规则
+    3. 可变借用也行：chunks_mut 把结果数组切成互不重叠的几段，每个线程写自己那段
+    4. scope 结束之后，借用全部归还：主线程照常使用 txs 和结果，不需要 Arc、不需要 join 句柄
+*/
+
+const TXS: usize = 1000;
+const WORKERS: usize = 4;
+
+#[derive(Debug, Clone)]
+struct Transaction {
+    from: String,
+    to: String,
+    amount: u64,
+    fee: u64,
+}
+
+// 栈上的一份规则：所有线程共享借用
+struct FeeRules {
+    min_fee: u64,
+    max_amount: u64,
+}
+
+impl FeeRules {
+    fn check(&self, tx: &Transaction) -> bool {
+        tx.fee >= self.min_fee && tx.amount > 0 && tx.amount <= self.max_amount && tx.from != tx.to
+    }
+}
+
+fn random_txs(rng: &mut SimpleRng) -> Vec<Transaction> {
+    let names = ["Alice", "Bob", "Carol", "Dave"];
+    (0..TXS)
+        .map(|_| Transaction {
+            from: names[rng.gen_range(4) as usize].to_string(),
+            to: names[rng.gen_range(4) as usize].to_string(),
+            amount: rng.gen_range(1_200),
+            fee: rng.gen_range(10),
+        })
+        .collect()
+}
+
+fn heap_bytes(txs: &[Transaction]) -> usize {
+    mem::size_of_val(txs) + txs.iter().map(|tx| tx.from.capacity() + tx.to.capacity()).sum::<usize>()
+}
+
+pub fn run() {
+    println!("--- S04 Ex09: 作用域线程 (thread::scope：子线程直接借用主线程栈上的数据) ---");
+    let mut rng = SimpleRng::from_time();
+    let txs = random_txs(&mut rng);
+    let rules = FeeRules { min_fee: 2, max_amount: 1_000 };
+    let chunk = TXS.div_ceil(WORKERS);
+
+    // ==========================================
+    // 1. ex01 的老办法：move 一份拷贝进去
+    // ==========================================
+    println!("\n[1] thread::spawn + move：每个线程 clone 一份交易");
+    // ❌ 直接借用编译不过：
+    //     thread::spawn(|| txs.len());
+    //     error[E0373]: closure may outlive the current function, but it borrows `txs`
+    let mut copied = 0;
+    let handles: Vec<_> = (0..WORKERS)
+        .map(|w| {
+            let mine: Vec<Transaction> = txs[w * chunk..((w + 1) * chunk).min(TXS)].to_vec();
+            copied += heap_bytes(&mine);
+            thread::spawn(move || mine.iter().map(|tx| tx.fee).sum::<u64>())
+        })
+        .collect();
+    let fees: u64 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    println!("  手续费合计 {}，为此复制了 {} 字节 (交易结构体 + 两个 String 的堆内存)", fees, copied);
+
+    // ==========================================
+    // 2. thread::scope：借用
+    // ==========================================
+    println!("\n[2] thread::scope：每个线程借一段 &txs[..]，共享借用同一份 &rules");
+    let (fees, valid) = thread::scope(|s| {
+        let handles: Vec<_> = txs
+            .chunks(chunk)
+            .enumerate()
+            .map(|(w, part)| {
+                let rules = &rules;
+                // part 指向的就是 txs 里第 w * chunk 个元素，没有任何拷贝
+                let same_memory = std::ptr::eq(part.as_ptr(), &txs[w * chunk]);
+                s.spawn(move || {
+                    // move 进来的只是两个引用 (part 和 rules) —— 复制引用不复制数据
+                    let fees: u64 = part.iter().map(|tx| tx.fee).sum();
+                    let valid = part.iter().filter(|tx| rules.check(tx)).count();
+                    println!("  Worker {}: 交易 #{}..#{}  合规 {:>3}/{}  手续费 {:>4}  和主线程的 Vec 是同一块内存 {}", w, w * chunk, w * chunk + part.len() - 1, valid, part.len(), fees, if same_memory { "✅" } else { "❌" });
+                    (fees, valid)
+                })
+            })
+            .collect();
+        // scope 的闭包返回之前，所有没 join 的线程都会被自动 join
+        handles.into_iter().map(|h| h.join().unwrap()).fold((0, 0), |(f, v), (fees, valid)| (f + fees, v + valid))
+    });
+    println!("  手续费合计 {}，合规 {}/{}，复制了 0 字节", fees, valid, TXS);
+
+    // ==========================================
+    // 3. 可变借用：每个线程写自己那一段结果
+    // ==========================================
+    println!("\n[3] chunks_mut：结果数组切成 {} 段互不重叠的 &mut [bool]，各写各的", WORKERS);
+    let mut verdicts = vec![false; TXS];
+    thread::scope(|s| {
+        for (part, out) in txs.chunks(chunk).zip(verdicts.chunks_mut(chunk)) {
+            let rules = &rules;
+            s.spawn(move || {
+                for (tx, verdict) in part.iter().zip(out.iter_mut()) {
+                    *verdict = rules.check(tx);
+                }
+            });
+        }
+        // ❌ 在这里读 verdicts 编译不过：它正被几个线程可变借用着
+    });
+
+    // ==========================================
+    // 4. scope 结束：借用归还
+    // ==========================================
+    let rejected: Vec<&Transaction> = txs.iter().zip(&verdicts).filter(|(_, ok)| !**ok).map(|(tx, _)| tx).collect();
+    println!("\n[4] scope 结束，txs 和 verdicts 又归主线程独占：拒绝 {} 笔，前 3 笔:", rejected.len());
+    for tx in rejected.iter().take(3) {
+        println!("    {} -> {} 金额 {} 手续费 {}", tx.from, tx.to, tx.amount, tx.fee);
+    }
+    let agree = verdicts.iter().filter(|ok| **ok).count() == valid;
+    println!("  和第 [2] 步统计的合规数一致: {}", if agree { "✅" } else { "❌" });
+}
+
+/*
+关键点总结：
+    1. 为什么 spawn 要 'static，scope 不用：
+        thread::spawn 返回的线程可以一直跑下去，编译器只能要求它不借用任何会先消失的东西 (F: 'static)；
+        thread::scope 保证在 scope 返回之前 join 所有子线程，所以子线程可以借用 scope 外面活得更久的数据。
+
+    2. 借用规则照常生效，只是跨线程了：
+        很多线程同时 & 借用 txs 和 rules；chunks_mut 切出的 &mut 互不重叠，每个线程独占自己那一段；
+        scope 里面主线程不能再碰被可变借用的 verdicts —— 和单线程的借用检查一模一样。
+
+    3. 什么时候还需要 Arc：
+        线程要比创建它的函数活得久 (后台任务、线程池里的长期工作者) —— 那就没有一个"scope"可以等，只能共享所有权。
+*/
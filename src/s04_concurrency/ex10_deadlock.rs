@@ -0,0 +1,187 @@
+// src/s04_concurrency/ex10_deadlock.rs
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::common::rng::SimpleRng;
+
+/*
+业务场景：转账要同时改两个账户，所以要同时拿两把锁。
+    线程 A：Alice -> Bob，先锁 Alice，再锁 Bob
+    线程 B：Bob -> Alice，先锁 Bob，再锁 Alice
+    A 拿着 Alice 等 Bob，B 拿着 Bob 等 Alice —— 谁也不会放手，两个线程永远睡下去：死锁 (Deadlock)。
+    编译器挡不住它：每一次 lock() 单独看都完全合法，错的是"两个线程加锁的顺序相反"。
+
+本练习 (两个线程互相转账，各转 TRANSFERS 次；拿到第一把锁之后停 HOLD_MS，模拟查风控、写日志)：
+    1. 按转账方向加锁：死锁。主线程当看门狗，WATCHDOG_MS 内没等到两个线程都收工就判定卡死，
+       再用 try_lock 看一眼：两把锁都被占着，而且永远不会释放
+    2. 全局加锁顺序：不管转账方向，永远先锁 id 小的账户 —— 不可能形成"互相等待"的环
+    3. try_lock 重试：拿不到第二把锁就把第一把也放掉，随机退避之后重来 (数一数重试了多少次)
+*/
+
+const INITIAL: u64 = 1_000;
+const TRANSFERS: u64 = 20;
+const HOLD_MS: u64 = 5;
+const WATCHDOG_MS: u64 = 1_000;
+
+// id 放在锁外面：决定加锁顺序时不需要先加锁
+struct Account {
+    id: u32,
+    name: &'static str,
+    balance: Mutex<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Locking {
+    // 先锁转出方，再锁转入方
+    Naive,
+    // 先锁 id 小的
+    Ordered,
+    // 第二把锁只 try_lock，拿不到就全部放掉重来
+    TryLock,
+}
+
+enum Outcome {
+    Finished { elapsed: Duration, retries: u64, alice: u64, bob: u64 },
+    // 看门狗超时：几个线程收工了、两把锁当时是不是被占着
+    Hung { finished: usize, alice_locked: bool, bob_locked: bool },
+}
+
+// 转一笔账，返回重试次数 (只有 TryLock 会重试)
+fn transfer(from: &Account, to: &Account, amount: u64, locking: Locking, rng: &mut SimpleRng) -> u64 {
+    let hold = Duration::from_millis(HOLD_MS);
+    match locking {
+        Locking::Naive => {
+            let mut a = from.balance.lock().unwrap();
+            println!("  [{} -> {}] 锁住了 {}，去拿 {} 的锁...", from.name, to.name, from.name, to.name);
+            thread::sleep(hold);
+            let mut b = to.balance.lock().unwrap();
+            *a -= amount;
+            *b += amount;
+            0
+        }
+        Locking::Ordered => {
+            let (first, second) = if from.id < to.id { (from, to) } else { (to, from) };
+            let mut g1 = first.balance.lock().unwrap();
+            thread::sleep(hold);
+            let mut g2 = second.balance.lock().unwrap();
+            // 加锁顺序和转账方向无关；两把锁都拿到之后再分清谁转出、谁转入
+            let (a, b) = if from.id < to.id { (&mut g1, &mut g2) } else { (&mut g2, &mut g1) };
+            **a -= amount;
+            **b += amount;
+            0
+        }
+        Locking::TryLock => {
+            let mut retries = 0;
+            loop {
+                let mut a = from.balance.lock().unwrap();
+                thread::sleep(hold);
+                if let Ok(mut b) = to.balance.try_lock() {
+                    *a -= amount;
+                    *b += amount;
+                    return retries;
+                }
+                // 拿不到就连第一把一起放掉，让对方先走；退避时间随机，免得两个线程步调一致地反复撞车 (活锁)
+                drop(a);
+                retries += 1;
+                thread::sleep(Duration::from_millis(rng.gen_range(HOLD_MS * 2)));
+            }
+        }
+    }
+}
+
+// A: Alice -> Bob 每次 3，B: Bob -> Alice 每次 2
+fn run_pair(locking: Locking, seed: u64) -> Outcome {
+    let alice = Arc::new(Account { id: 1, name: "Alice", balance: Mutex::new(INITIAL) });
+    let bob = Arc::new(Account { id: 2, name: "Bob", balance: Mutex::new(INITIAL) });
+    let start = Instant::now();
+    let (done_tx, done_rx) = mpsc::channel();
+
+    let jobs = [(Arc::clone(&alice), Arc::clone(&bob), 3), (Arc::clone(&bob), Arc::clone(&alice), 2)];
+    let mut handles = Vec::new();
+    for (i, (from, to, amount)) in jobs.into_iter().enumerate() {
+        let done_tx = done_tx.clone();
+        handles.push(thread::spawn(move || {
+            let mut rng = SimpleRng::new(seed ^ (i as u64 + 1).wrapping_mul(0x9E37_79B9));
+            let retries = (0..TRANSFERS).map(|_| transfer(&from, &to, amount, locking, &mut rng)).sum::<u64>();
+            done_tx.send(retries).unwrap();
+        }));
+    }
+
+    // 看门狗：给两个线程 WATCHDOG_MS 收工，过了时间还没等到就判定卡死
+    let deadline = start + Duration::from_millis(WATCHDOG_MS);
+    let mut retries = 0;
+    for finished in 0..handles.len() {
+        match done_rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(r) => retries += r,
+            Err(_) => {
+                // 卡死的线程没法 join (会陪着一起卡死)，只能丢下 JoinHandle 不管它们
+                let alice_locked = alice.balance.try_lock().is_err();
+                let bob_locked = bob.balance.try_lock().is_err();
+                return Outcome::Hung { finished, alice_locked, bob_locked };
+            }
+        }
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let elapsed = start.elapsed();
+    let (alice, bob) = (*alice.balance.lock().unwrap(), *bob.balance.lock().unwrap());
+    Outcome::Finished { elapsed, retries, alice, bob }
+}
+
+pub fn run() {
+    println!("--- S04 Ex10: 死锁 (两个线程反方向加锁，看门狗，加锁顺序和 try_lock 重试) ---");
+    println!("Alice 和 Bob 各有 {}；A 线程 Alice -> Bob 转 {} 次、每次 3，B 线程 Bob -> Alice 转 {} 次、每次 2", INITIAL, TRANSFERS, TRANSFERS);
+    println!("拿到第一把锁之后停 {}ms；看门狗 {}ms", HOLD_MS, WATCHDOG_MS);
+    let seed = SimpleRng::from_time().next_u64();
+    let expected = (INITIAL - 3 * TRANSFERS + 2 * TRANSFERS, INITIAL + 3 * TRANSFERS - 2 * TRANSFERS);
+
+    let rounds = [
+        (Locking::Naive, "[1] 按转账方向加锁 (先锁转出方)"),
+        (Locking::Ordered, "[2] 全局加锁顺序 (永远先锁 id 小的账户)"),
+        (Locking::TryLock, "[3] 第二把锁用 try_lock，失败就全部放掉、随机退避后重试"),
+    ];
+    for (locking, title) in rounds {
+        println!("\n{}", title);
+        match run_pair(locking, seed) {
+            Outcome::Hung { finished, alice_locked, bob_locked } => {
+                let held = |locked: bool| if locked { "被占着 🔒" } else { "空闲" };
+                println!("  看门狗: {}ms 内只有 {}/2 个线程收工 -> ❌ 判定卡死", WATCHDOG_MS, finished);
+                println!("  try_lock 看一眼: Alice 的锁 {}，Bob 的锁 {}", held(alice_locked), held(bob_locked));
+                if alice_locked && bob_locked {
+                    println!("  A 拿着 Alice 等 Bob，B 拿着 Bob 等 Alice：等待形成了一个环，谁都不会先放手");
+                }
+                println!("  这两个线程永远醒不过来了 (锁没法从外面强行解开)，只能丢下不管，直到进程退出");
+            }
+            Outcome::Finished { elapsed, retries, alice, bob } => {
+                let ok = if (alice, bob) == expected { "✅" } else { "❌" };
+                println!("  {} 笔转账全部完成，用时 {:.0?}；Alice {}，Bob {}，合计 {} {}", TRANSFERS * 2, elapsed, alice, bob, alice + bob, ok);
+                match locking {
+                    Locking::Naive => println!("  这次没卡住只是运气好 (两个线程恰好没有同时拿着各自的第一把锁)"),
+                    Locking::Ordered => println!("  两个线程都先抢 Alice：没抢到的一方手里什么锁都没有，不可能和对方互相等待"),
+                    Locking::TryLock => println!("  一共放弃重来 {} 次：不会死锁，但重试浪费了时间，退避不随机还可能变成活锁", retries),
+                }
+            }
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 死锁的四个条件同时成立才会发生：
+        互斥 (Mutex)、持有并等待 (拿着 Alice 等 Bob)、不可抢占 (锁没法从外面夺走)、循环等待 (A 等 B、B 等 A)。
+        破坏任意一个就不会死锁：加锁顺序破坏"循环等待"，try_lock + 放手破坏"持有并等待"。
+
+    2. 全局加锁顺序是最常用的解法：
+        给每把锁一个编号 (账户 id、地址……)，所有代码都按同一个顺序加锁；
+        难点在于它是一条"约定"，编译器不会检查 —— 一处代码忘了遵守，死锁就回来了。
+
+    3. try_lock 重试的代价：
+        不会死锁，但可能一直让来让去 (活锁)，所以退避时间要随机；
+        重试次数取决于调度，没有上限保证。
+
+    4. 死锁不会报错、不会 panic，程序只是"不动了"：
+        生产环境里靠看门狗 / 超时发现它；Rust 的所有权系统防得住数据竞争，防不住死锁。
+*/
@@ -0,0 +1,158 @@
+// src/s04_concurrency/ex11_poison.rs
+use std::panic;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+
+/*
+业务场景：还是 ex02 那个 10 个柜员存钱的账户，这次账户里除了余额还有一本账本 (每笔存款一条记录)。
+    不变量：余额 == 账本合计。改账户要分两步：先加余额，再记账本。
+    有个柜员加完余额、记账本时 panic 了 —— 余额多了 10，账本里没有这笔：不变量被破坏了一半。
+    持有锁的线程 panic，锁仍然会被释放 (MutexGuard 的 Drop 照样执行)，但 Mutex 会被标记为"中毒" (poisoned)：
+    之后每个 lock() 都返回 Err(PoisonError)，提醒你"里面的数据可能是改了一半的"。
+
+本练习：
+    1. 坏柜员：持有锁时 panic，主线程 join() 拿到 Err，账户 is_poisoned() == true
+    2. 剩下 9 个柜员继续存钱，三种处理 PoisonError 的方式：
+        a. unwrap()：和 ex02 一样，中毒的锁让每个后来的线程都跟着 panic (连锁崩溃)
+        b. 向上传播：存款函数返回 Err，调用方拒绝这笔存款，程序继续运行，但账户冻结了
+        c. 恢复：PoisonError::into_inner() 照样拿到 MutexGuard，按账本修复余额，clear_poison() 解除中毒
+*/
+
+const TELLERS: usize = 9;
+
+#[derive(Debug, Default)]
+struct Account {
+    balance: u64,
+    ledger: Vec<u64>,
+}
+
+impl Account {
+    fn consistent(&self) -> bool {
+        self.balance == self.ledger.iter().sum::<u64>()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Policy {
+    Unwrap,
+    Propagate,
+    Recover,
+}
+
+// 存一笔；Ok 里是存完之后的余额
+fn deposit(account: &Mutex<Account>, amount: u64, policy: Policy) -> Result<u64, String> {
+    let mut acc = match policy {
+        Policy::Unwrap => account.lock().unwrap(),
+        Policy::Propagate => account.lock().map_err(|_| String::from("账户锁已中毒，拒绝存款"))?,
+        Policy::Recover => account.lock().unwrap_or_else(|poisoned| {
+            // into_inner 拿到的是普通的 MutexGuard：锁是真的拿到了，数据可能是改了一半的
+            let mut acc = poisoned.into_inner();
+            if !acc.consistent() {
+                let repaired = acc.ledger.iter().sum();
+                println!("  🔧 {}: 发现余额 {} 和账本合计 {} 对不上，以账本为准修复", thread::current().name().unwrap_or("?"), acc.balance, repaired);
+                acc.balance = repaired;
+            }
+            // 数据修好了，解除中毒：后面的 lock() 会直接返回 Ok
+            account.clear_poison();
+            acc
+        }),
+    };
+    acc.balance += amount;
+    acc.ledger.push(amount);
+    Ok(acc.balance)
+}
+
+fn run_round(policy: Policy) {
+    let account = Arc::new(Mutex::new(Account::default()));
+
+    // ==========================================
+    // 1. 坏柜员：持有锁时 panic
+    // ==========================================
+    let faulty_ref = Arc::clone(&account);
+    let faulty = thread::Builder::new()
+        .name(String::from("柜员 0"))
+        .spawn(move || {
+            let mut acc = faulty_ref.lock().unwrap();
+            acc.balance += 10;
+            // 余额已经加上了，记账本时金额解析失败 -> panic，账本里没有这一笔
+            let memo = "10元";
+            acc.ledger.push(memo.parse().unwrap());
+        })
+        .unwrap();
+    let joined = faulty.join();
+    println!("  柜员 0 join(): {}，账户 is_poisoned() = {}", if joined.is_err() { "Err (线程 panic 了)" } else { "Ok" }, account.is_poisoned());
+
+    // ==========================================
+    // 2. 其余柜员继续存钱
+    // ==========================================
+    let handles: Vec<_> = (1..=TELLERS)
+        .map(|i| {
+            let account_ref = Arc::clone(&account);
+            thread::Builder::new().name(format!("柜员 {}", i)).spawn(move || deposit(&account_ref, 10, policy)).unwrap()
+        })
+        .collect();
+    let (mut deposited, mut refused, mut panicked) = (0, 0, 0);
+    for handle in handles {
+        match handle.join() {
+            Ok(Ok(_)) => deposited += 1,
+            Ok(Err(e)) => {
+                if refused == 0 {
+                    println!("  存款函数返回 Err: {}", e);
+                }
+                refused += 1;
+            }
+            Err(_) => panicked += 1,
+        }
+    }
+    println!("  其余 {} 个柜员: 存进去 {} 笔，被拒绝 {} 笔，panic {} 个", TELLERS, deposited, refused, panicked);
+
+    // 主线程只看一眼：不管中没中毒都把数据拿出来
+    let poisoned = account.is_poisoned();
+    let acc = account.lock().unwrap_or_else(PoisonError::into_inner);
+    let ok = if acc.consistent() { "✅" } else { "❌" };
+    println!("  最终: 余额 {}，账本 {} 笔合计 {}，一致 {}，仍然中毒 {}", acc.balance, acc.ledger.len(), acc.ledger.iter().sum::<u64>(), ok, poisoned);
+}
+
+pub fn run() {
+    println!("--- S04 Ex11: 锁中毒 (持锁线程 panic 之后，PoisonError 怎么处理) ---");
+
+    // 默认的 panic 信息会打印一大段到 stderr；这里换成一行，练习结束后换回去
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        let payload = info.payload();
+        let msg = payload.downcast_ref::<String>().map(String::as_str).or_else(|| payload.downcast_ref::<&str>().copied()).unwrap_or("?");
+        println!("  💥 {} panic: {}", thread::current().name().unwrap_or("?"), msg);
+    }));
+
+    let rounds = [
+        (Policy::Unwrap, "[a] lock().unwrap()：和 ex02 的写法一样"),
+        (Policy::Propagate, "[b] lock().map_err(..)?：把失败作为 Err 传给调用方"),
+        (Policy::Recover, "[c] unwrap_or_else(PoisonError::into_inner)：检查、修复、clear_poison()"),
+    ];
+    for (policy, title) in rounds {
+        println!("\n{}", title);
+        run_round(policy);
+    }
+
+    panic::set_hook(default_hook);
+    println!("\n  unwrap 把一个柜员的 bug 变成了所有柜员的 panic；传播保住了程序但账户冻结；恢复要求你知道怎么把数据修回一致");
+}
+
+/*
+关键点总结：
+    1. 中毒不是锁坏了，是一个提醒：
+        panic 时 MutexGuard 的 Drop 照样执行，锁会被释放，不会卡死别的线程；
+        Mutex 只是记下"上一个持有者 panic 了"，之后 lock() 返回 Err(PoisonError)，但 PoisonError 里装着真正的 MutexGuard。
+
+    2. 三种处理方式：
+        unwrap()：认为"数据可能坏了"就不该继续 —— 错误会一路连锁 panic 下去；
+        传播 (? / map_err)：把决定权交给调用方，比如拒绝这笔业务、报警、让上层重启这个服务；
+        into_inner()：自己承担检查的责任。只有你能验证 (或修复) 不变量时才这么做，修好之后 clear_poison()。
+
+    3. 什么时候 unwrap 是合理的：
+        临界区里的代码不可能 panic，或者一旦 panic 整个程序本来就该退出 —— 这也是大多数示例直接 unwrap 的原因。
+
+    4. 不变量跨越多个字段时最危险：
+        只改一个数 (像 ex06 的原子计数器) 不存在"改了一半"；
+        余额 + 账本这种要一起改的数据，panic 留下的正是半成品。
+*/
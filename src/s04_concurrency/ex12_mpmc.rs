@@ -0,0 +1,279 @@
+// src/s04_concurrency/ex12_mpmc.rs
+use std::collections::VecDeque;
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/*
+业务场景：交易池前面的一条有界队列 —— 好几个 P2P 连接线程往里塞交易 (多生产者)，
+    好几个验证线程从里面取交易 (多消费者)。
+    std::sync::mpsc 是 Multi-Producer Single-Consumer：Receiver 不能 clone，只能有一个消费者。
+    那就自己写一个：Mutex<VecDeque> 存数据，两个 Condvar 分别等"不满"和"不空"。
+
+本练习：
+    1. 手写 BoundedQueue：push 满了在 not_full 上睡，pop 空了在 not_empty 上睡；close() 叫醒所有人收工
+       小规模跑一遍，看每个交易被哪个验证线程取走；关闭之后 push 会把交易原样退回
+    2. 和 std mpsc 对照语义：多消费者只能把 Receiver 包进 Arc<Mutex<..>>；关闭靠"所有 Sender 都被 drop"
+    3. 同样的负载 (PRODUCERS 个生产者、CONSUMERS 个消费者、容量 CAPACITY)，比一比：
+       手写队列 / sync_channel + Arc<Mutex<Receiver>> / sync_channel 单消费者
+*/
+
+const ITEMS: u64 = 200_000;
+const PRODUCERS: u64 = 4;
+const CONSUMERS: usize = 4;
+const CAPACITY: usize = 64;
+
+struct State<T> {
+    items: VecDeque<T>,
+    closed: bool,
+    // 生产者因为队列满、消费者因为队列空而睡下的次数
+    full_waits: u64,
+    empty_waits: u64,
+}
+
+// 两个 Condvar 共用同一把锁：它们等的是同一份数据上的两个不同条件
+struct BoundedQueue<T> {
+    state: Mutex<State<T>>,
+    not_full: Condvar,
+    not_empty: Condvar,
+    capacity: usize,
+}
+
+impl<T> BoundedQueue<T> {
+    fn new(capacity: usize) -> Self {
+        BoundedQueue {
+            state: Mutex::new(State { items: VecDeque::with_capacity(capacity), closed: false, full_waits: 0, empty_waits: 0 }),
+            not_full: Condvar::new(),
+            not_empty: Condvar::new(),
+            capacity,
+        }
+    }
+
+    // 满了就等；队列已关闭时把 item 原样退回
+    fn push(&self, item: T) -> Result<(), T> {
+        let mut state = self.state.lock().unwrap();
+        while state.items.len() == self.capacity && !state.closed {
+            state.full_waits += 1;
+            state = self.not_full.wait(state).unwrap();
+        }
+        if state.closed {
+            return Err(item);
+        }
+        state.items.push_back(item);
+        drop(state);
+        // 放进去一个，只可能让一个消费者有活干：notify_one 就够
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    // 空了就等；返回 None 表示队列已关闭而且取空了
+    fn pop(&self) -> Option<T> {
+        let mut state = self.state.lock().unwrap();
+        while state.items.is_empty() && !state.closed {
+            state.empty_waits += 1;
+            state = self.not_empty.wait(state).unwrap();
+        }
+        let item = state.items.pop_front();
+        drop(state);
+        if item.is_some() {
+            self.not_full.notify_one();
+        }
+        item
+    }
+
+    // 关闭：以后的 push 失败，pop 取完剩下的就返回 None；两边睡着的人全部叫醒
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.not_full.notify_all();
+        self.not_empty.notify_all();
+    }
+
+    fn waits(&self) -> (u64, u64) {
+        let state = self.state.lock().unwrap();
+        (state.full_waits, state.empty_waits)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transport {
+    HandBuilt,
+    // sync_channel，多个消费者共用 Arc<Mutex<Receiver>>
+    StdShared,
+    // sync_channel，只有一个消费者
+    StdSingle,
+}
+
+impl Transport {
+    fn name(&self) -> &'static str {
+        match self {
+            Transport::HandBuilt => "手写 BoundedQueue",
+            Transport::StdShared => "sync_channel + Arc<Mutex<Receiver>>",
+            Transport::StdSingle => "sync_channel 单消费者",
+        }
+    }
+}
+
+struct Report {
+    elapsed: Duration,
+    // 每个消费者取到的 (个数, id 之和)
+    per_consumer: Vec<(u64, u64)>,
+    waits: Option<(u64, u64)>,
+}
+
+// 生产者 p 负责 id 区间 [p * per, (p + 1) * per)，所有 id 之和是固定的，丢一个、重复一个都看得出来
+fn producer_range(p: u64) -> std::ops::Range<u64> {
+    let per = ITEMS / PRODUCERS;
+    p * per..(p + 1) * per
+}
+
+fn run_workload(transport: Transport) -> Report {
+    let start = Instant::now();
+    let consumers = if transport == Transport::StdSingle { 1 } else { CONSUMERS };
+    let drain = |mut next: Box<dyn FnMut() -> Option<u64> + Send>| {
+        thread::spawn(move || {
+            let (mut count, mut sum) = (0, 0);
+            while let Some(id) = next() {
+                count += 1;
+                sum += id;
+            }
+            (count, sum)
+        })
+    };
+
+    let (per_consumer, waits) = match transport {
+        Transport::HandBuilt => {
+            let queue = Arc::new(BoundedQueue::new(CAPACITY));
+            let producers: Vec<_> = (0..PRODUCERS)
+                .map(|p| {
+                    let queue = Arc::clone(&queue);
+                    thread::spawn(move || producer_range(p).for_each(|id| queue.push(id).unwrap()))
+                })
+                .collect();
+            let handles: Vec<_> = (0..consumers)
+                .map(|_| {
+                    let queue = Arc::clone(&queue);
+                    drain(Box::new(move || queue.pop()))
+                })
+                .collect();
+            producers.into_iter().for_each(|h| h.join().unwrap());
+            // 手写队列不知道生产者什么时候全部结束，要有人显式 close
+            queue.close();
+            (handles.into_iter().map(|h| h.join().unwrap()).collect(), Some(queue.waits()))
+        }
+        Transport::StdShared | Transport::StdSingle => {
+            let (tx, rx) = mpsc::sync_channel(CAPACITY);
+            for p in 0..PRODUCERS {
+                let tx = tx.clone();
+                thread::spawn(move || producer_range(p).for_each(|id| tx.send(id).unwrap()));
+            }
+            // 最初的 tx 也要 drop：所有 Sender 都没了，recv() 才会返回 Err，消费者才会退出
+            drop(tx);
+            let rx = Arc::new(Mutex::new(rx));
+            let handles: Vec<_> = (0..consumers)
+                .map(|_| {
+                    let rx = Arc::clone(&rx);
+                    // 拿着 Receiver 的锁阻塞在 recv() 上：同一时刻只有一个消费者在等，其余的在等锁
+                    drain(Box::new(move || rx.lock().unwrap().recv().ok()))
+                })
+                .collect();
+            (handles.into_iter().map(|h| h.join().unwrap()).collect(), None)
+        }
+    };
+    Report { elapsed: start.elapsed(), per_consumer, waits }
+}
+
+fn small_demo() {
+    let queue = Arc::new(BoundedQueue::new(3));
+    let producers: Vec<_> = (0..2)
+        .map(|p| {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                for n in 0..5 {
+                    queue.push(format!("tx-{}{}", ["A", "B"][p], n)).unwrap();
+                    thread::sleep(Duration::from_millis(2));
+                }
+            })
+        })
+        .collect();
+    let consumers: Vec<_> = (0..2)
+        .map(|c| {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                while let Some(tx) = queue.pop() {
+                    println!("  Validator {}: 取到 {}", c, tx);
+                    thread::sleep(Duration::from_millis(3));
+                }
+                println!("  Validator {}: 队列已关闭且取空，收工", c);
+            })
+        })
+        .collect();
+    producers.into_iter().for_each(|h| h.join().unwrap());
+    queue.close();
+    consumers.into_iter().for_each(|h| h.join().unwrap());
+
+    match queue.push(String::from("tx-late")) {
+        Ok(()) => println!("  关闭之后 push 成功了 ❌ (不应该发生)"),
+        Err(tx) => println!("  关闭之后 push({}) 返回 Err，交易原样退回给调用方 ✅", tx),
+    }
+    let (full, empty) = queue.waits();
+    println!("  生产者因为队列满睡了 {} 次，验证线程因为队列空睡了 {} 次", full, empty);
+}
+
+pub fn run() {
+    println!("--- S04 Ex12: 手写 MPMC 队列 (Mutex<VecDeque> + 两个 Condvar，对照 std mpsc) ---");
+
+    // ==========================================
+    // 1. 小规模：2 个生产者、2 个验证线程、容量 3
+    // ==========================================
+    println!("\n[1] 2 个生产者各放 5 笔交易，2 个验证线程来取，队列容量 3");
+    small_demo();
+
+    // ==========================================
+    // 2. 语义对照
+    // ==========================================
+    println!("\n[2] 和 std mpsc 的语义差别");
+    println!("  多消费者: 手写队列 Arc::clone 就行；mpsc 的 Receiver 不能 clone，");
+    println!("            rx.clone() 编译报错 (no method named `clone` found for struct `Receiver`)，只能包进 Arc<Mutex<Receiver>>");
+    println!("  关闭:     手写队列要有人显式 close()；mpsc 在最后一个 Sender 被 drop 时自动关闭 (RAII)");
+    println!("  关闭之后: 手写队列的 push 退回 Err(item)；mpsc 的 send 也退回 Err(SendError(item))，两边都不会悄悄丢数据");
+
+    // ==========================================
+    // 3. 性能对比
+    // ==========================================
+    let expected_sum = ITEMS * (ITEMS - 1) / 2;
+    println!("\n[3] {} 个生产者共放 {} 笔，{} 个消费者，容量 {}", PRODUCERS, ITEMS, CONSUMERS, CAPACITY);
+    println!("  {:<38} {:>10} {:>14}  各消费者取到", "方案", "用时", "吞吐 (笔/秒)");
+    for transport in [Transport::HandBuilt, Transport::StdShared, Transport::StdSingle] {
+        let report = run_workload(transport);
+        let count: u64 = report.per_consumer.iter().map(|(c, _)| c).sum();
+        let sum: u64 = report.per_consumer.iter().map(|(_, s)| s).sum();
+        let ok = if count == ITEMS && sum == expected_sum { "✅" } else { "❌ 有交易丢了或重复了" };
+        let split: Vec<String> = report.per_consumer.iter().map(|(c, _)| c.to_string()).collect();
+        println!("  {:<38} {:>10.1?} {:>14.0}  [{}] {}", transport.name(), report.elapsed, ITEMS as f64 / report.elapsed.as_secs_f64(), split.join(", "), ok);
+        if let Some((full, empty)) = report.waits {
+            println!("  {:<38} 生产者睡了 {} 次，消费者睡了 {} 次", "", full, empty);
+        }
+    }
+    println!("  结果和核数关系很大：std 的 channel 内部是无锁的 (移植自 crossbeam)，核多、争抢激烈时优势明显；");
+    println!("  手写版每次 push/pop 都抢同一把锁，单核机器上没有真正的并行争抢，它反而可能更快");
+}
+
+/*
+关键点总结：
+    1. 为什么是两个 Condvar：
+        生产者等"不满"，消费者等"不空"，是两个不同的条件。
+        只用一个 Condvar 时，notify_one 可能叫醒了同一边的人 (生产者叫醒生产者)，真正该醒的那个继续睡 —— 只能改成 notify_all，白白叫醒一群人。
+
+    2. close 是条件的一部分：
+        等待条件写成"满了 && 没关闭"/"空了 && 没关闭"，close() 之后 notify_all，所有人都能醒来看到 closed 退出；
+        pop 在关闭之后先把剩下的取完，再返回 None，一笔都不丢。
+
+    3. mpsc 的"单消费者"是设计取舍：
+        只有一个接收端，内部实现可以更简单、更快；要多消费者就得自己加锁 (Arc<Mutex<Receiver>>)，
+        而拿着锁阻塞在 recv() 上，等于所有消费者排队。需要真正的 MPMC 时用 crossbeam-channel 这样的库。
+
+    4. 自动关闭 vs 显式关闭：
+        mpsc 用 Drop 计数 Sender，忘了 drop 最初那个 tx，消费者就会永远等下去；
+        手写队列把关闭交给调用方，忘了 close 也是同样的结果 —— 两种写法都要想清楚"谁负责说结束了"。
+*/
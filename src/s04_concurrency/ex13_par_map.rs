@@ -0,0 +1,149 @@
+// src/s04_concurrency/ex13_par_map.rs
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::crypto::schnorr::{self, Keypair};
+use crate::s05_zk_lab::hash::{sha256, Digest};
+
+/*
+业务场景：节点收到一个大区块，要给 10 万笔交易逐个算哈希 (txid)、钱包要批量签名 —— 每笔互不相关，正适合并行。
+    S02 ex03 的迭代器链 txs.iter().map(..).collect() 是顺序的，一个核在干活。
+    自己写一个 par_map：把切片切成几段，每段一个线程 map，最后按段的顺序拼回来 —— 结果和顺序版一模一样。
+
+本练习：
+    1. par_map 的样子：10 笔交易、3 个线程，看切段和拼接；再用它重算 S02 ex03 的 total_reward
+    2. 给 TXS 笔交易算 SHA-256：顺序迭代器链 vs par_map 1/2/4/8 个线程，报告加速比
+    3. 同样的交易再做 Schnorr 签名 (每笔两次哈希加一次模幂)，同样对比
+*/
+
+const TXS: usize = 100_000;
+const THREAD_COUNTS: [usize; 4] = [1, 2, 4, 8];
+
+#[derive(Debug, Clone)]
+struct Transaction {
+    from: u32,
+    to: u32,
+    amount: u32,
+    nonce: u64,
+}
+
+impl Transaction {
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(20);
+        bytes.extend_from_slice(&self.from.to_le_bytes());
+        bytes.extend_from_slice(&self.to.to_le_bytes());
+        bytes.extend_from_slice(&self.amount.to_le_bytes());
+        bytes.extend_from_slice(&self.nonce.to_le_bytes());
+        bytes
+    }
+}
+
+// 把 items 切成 threads 段，每段一个线程执行 f，结果按输入顺序拼回来
+// 借用 items 和 f 用的是 thread::scope (ex09)：不需要 Arc，也不需要 'static
+pub fn par_map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if items.is_empty() {
+        return Vec::new();
+    }
+    let chunk = items.len().div_ceil(threads.max(1));
+    let f = &f;
+    thread::scope(|s| {
+        let handles: Vec<_> = items.chunks(chunk).map(|part| s.spawn(move || part.iter().map(f).collect::<Vec<R>>())).collect();
+        // 按 spawn 的顺序 join：第 i 段的结果一定接在第 i - 1 段后面，和线程谁先跑完无关
+        let mut out = Vec::with_capacity(items.len());
+        for handle in handles {
+            out.extend(handle.join().unwrap());
+        }
+        out
+    })
+}
+
+fn random_txs(n: usize, rng: &mut SimpleRng) -> Vec<Transaction> {
+    (0..n)
+        .map(|i| Transaction { from: rng.gen_range(1_000) as u32, to: rng.gen_range(1_000) as u32, amount: rng.gen_range(30) as u32, nonce: i as u64 })
+        .collect()
+}
+
+// 顺序版跑一次、par_map 每种线程数各跑一次，打印用时和加速比，并检查结果和顺序版完全一致
+fn benchmark<R: PartialEq + Send>(txs: &[Transaction], f: impl Fn(&Transaction) -> R + Sync) {
+    let start = Instant::now();
+    let expected: Vec<R> = txs.iter().map(&f).collect();
+    let sequential = start.elapsed();
+    println!("  {:<26} {:>10.1?}", "顺序 iter().map().collect()", sequential);
+
+    for threads in THREAD_COUNTS {
+        let start = Instant::now();
+        let got = par_map(txs, threads, &f);
+        let elapsed = start.elapsed();
+        let same = if got == expected { "✅ 和顺序版逐项相同" } else { "❌ 结果不一致" };
+        println!("  {:<26} {:>10.1?}   加速 {:>4.2}x   {}", format!("par_map {} 线程", threads), elapsed, speedup(sequential, elapsed), same);
+    }
+}
+
+fn speedup(sequential: Duration, parallel: Duration) -> f64 {
+    sequential.as_secs_f64() / parallel.as_secs_f64().max(1e-9)
+}
+
+pub fn run() {
+    println!("--- S04 Ex13: 手写 par_map (切段、多线程 map、按顺序拼回) ---");
+    let cores = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    println!("本机可用并行度: {} (加速比的上限)", cores);
+    let mut rng = SimpleRng::from_time();
+
+    // ==========================================
+    // 1. par_map 的样子
+    // ==========================================
+    println!("\n[1] 10 笔交易、3 个线程");
+    let small = random_txs(10, &mut rng);
+    let tagged = par_map(&small, 3, |tx| format!("#{}:{}", tx.nonce, tx.amount));
+    println!("  每段 {} 笔，结果按原顺序拼回: {:?}", small.len().div_ceil(3), tagged);
+    // S02 ex03 的任务 1：amount > 10 的交易金额 * 2 求和 —— map 并行做，sum 在主线程做
+    let reward_seq: u32 = small.iter().filter(|tx| tx.amount > 10).map(|tx| tx.amount * 2).sum();
+    let reward_par: u32 = par_map(&small, 3, |tx| if tx.amount > 10 { tx.amount * 2 } else { 0 }).into_iter().sum();
+    println!("  S02 ex03 的 total_reward: 顺序 {}，par_map {} {}", reward_seq, reward_par, if reward_seq == reward_par { "✅" } else { "❌" });
+
+    // ==========================================
+    // 2. 算哈希
+    // ==========================================
+    let txs = random_txs(TXS, &mut rng);
+    println!("\n[2] {} 笔交易算 SHA-256 (txid)", TXS);
+    benchmark(&txs, |tx| -> Digest { sha256(&tx.encode()) });
+
+    // ==========================================
+    // 3. 签名
+    // ==========================================
+    let keys: Keypair = Keypair::generate(&mut rng);
+    println!("\n[3] {} 笔交易做 Schnorr 签名 (每笔两次哈希加一次模幂)", TXS);
+    // 签名的随机数 k 由消息派生 (RFC 6979 的思路，真实实现还要混入私钥)：
+    // 各线程不用共享一个 RNG，同一笔交易在哪个线程上签，签名都一样，才能和顺序版逐项比较
+    benchmark(&txs, |tx| {
+        let msg = tx.encode();
+        let digest = sha256(&msg);
+        let mut nonce_rng = SimpleRng::new(u64::from_le_bytes(digest[..8].try_into().unwrap()));
+        schnorr::sign(&keys, &msg, &mut nonce_rng)
+    });
+
+    println!("\n  加速比受核数限制：线程数超过核数只会增加切换开销；核数为 1 时所有线程轮流跑，par_map 不会比顺序版快");
+}
+
+/*
+关键点总结：
+    1. 保持顺序靠"按段拼接"：
+        每个线程只处理自己那一段，返回一个 Vec；主线程按段的顺序 join 再 extend —— 不需要给结果排序，也不需要锁。
+
+    2. 为什么要求 T: Sync、R: Send、F: Sync：
+        多个线程同时拿着 &[T] 和 &F (共享引用跨线程 -> Sync)；
+        每个线程算出的 Vec<R> 要交回主线程 (值跨线程 -> Send)。
+
+    3. 什么样的工作值得并行：
+        每项工作越重 (签名 > 哈希 > 加法)，启动线程、拼接结果的固定开销占比越小，加速比越接近核数；
+        像 [1] 里 10 笔交易乘 2，开线程的时间比干活长得多。
+
+    4. 这就是 rayon 的 par_iter().map().collect() 做的事：
+        rayon 用工作窃取 (work stealing) 把任务切得更细、动态分配给空闲线程，各段工作量不均时也能跑满所有核。
+*/
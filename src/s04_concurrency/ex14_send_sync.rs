@@ -0,0 +1,202 @@
+// src/s04_concurrency/ex14_send_sync.rs
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+use std::thread;
+
+use crate::s03_smart_pointers::ex03_refcell::{Mempool, Node};
+
+/*
+业务场景：S03 ex03 里两个 Node 共享一个交易池：Rc<RefCell<Mempool>>，单线程跑得好好的。
+    现在每个节点要跑在自己的线程上 —— 把 Node 交给 thread::spawn，编译器不答应。
+    挡住它的是两个自动 trait (auto trait)：
+        Send：这个值可以被"搬"到另一个线程 (所有权跨线程)
+        Sync：这个值可以被多个线程"同时借用" (&T 跨线程)；T: Sync 等价于 &T: Send
+    编译器根据字段自动推导：一个结构体的所有字段都是 Send，它就是 Send。
+
+本练习：
+    1. S03 的设计原样跑一遍 (单线程，没问题)
+    2. 把 Node 交给 thread::spawn：编译错误逐行解读 —— Rc 不是 Send
+    3. 只把 Rc 换成 Arc：还是编译不过 —— RefCell 不是 Sync
+    4. 一张表：用"探针"在运行时打印各个类型是不是 Send / Sync (结论全是编译期就定好的)
+    5. Arc<Mutex<Mempool>>：4 个节点线程同时提交交易
+    6. 运行时的行为对照：RefCell 被占用时 try_borrow_mut 返回 Err，Mutex 被占用时 try_lock 返回 WouldBlock
+*/
+
+// ==========================================
+// 探针：检查一个具体类型是不是 Send / Sync
+// ==========================================
+// 固有 (inherent) 关联常量优先于 trait 里的同名常量，但只有 T 满足约束时固有的那个才存在；
+// 不满足就退回 trait 的默认值 false —— 结论在编译期就确定了，运行时只是把它打印出来
+struct SendProbe<T: ?Sized>(PhantomData<T>);
+trait NotSend {
+    const SEND: bool = false;
+}
+impl<T: ?Sized> NotSend for SendProbe<T> {}
+impl<T: ?Sized + Send> SendProbe<T> {
+    const SEND: bool = true;
+}
+
+struct SyncProbe<T: ?Sized>(PhantomData<T>);
+trait NotSync {
+    const SYNC: bool = false;
+}
+impl<T: ?Sized> NotSync for SyncProbe<T> {}
+impl<T: ?Sized + Sync> SyncProbe<T> {
+    const SYNC: bool = true;
+}
+
+macro_rules! probe {
+    ($t:ty, $why:expr) => {
+        println!("  {:<30} {:<6} {:<6} {}", stringify!($t), mark(<SendProbe<$t>>::SEND), mark(<SyncProbe<$t>>::SYNC), $why)
+    };
+}
+
+fn mark(yes: bool) -> &'static str {
+    if yes {
+        "✅"
+    } else {
+        "❌"
+    }
+}
+
+// ==========================================
+// 迁移后的节点：和 S03 的 Node 一一对应
+// ==========================================
+struct SyncNode {
+    id: u64,
+    // Rc -> Arc (引用计数改成原子操作)，RefCell -> Mutex (借用标志改成锁)
+    pool: Arc<Mutex<Mempool>>,
+}
+
+impl SyncNode {
+    fn new(id: u64, pool: Arc<Mutex<Mempool>>) -> Self {
+        SyncNode { id, pool }
+    }
+
+    // borrow_mut() -> lock()：别人占着时不再 panic，而是等
+    fn submit_tx(&self, tx: &str) {
+        self.pool.lock().unwrap().txs.push(format!("Node{}: {}", self.id, tx));
+    }
+
+    fn pool_size(&self) -> usize {
+        self.pool.lock().unwrap().txs.len()
+    }
+}
+
+pub fn run() {
+    println!("--- S04 Ex14: Send 与 Sync (把 S03 的 Rc<RefCell<Mempool>> 搬到多线程) ---");
+
+    // ==========================================
+    // 1. S03 的设计，单线程
+    // ==========================================
+    println!("\n[1] S03 ex03 原样：两个 Node 共享 Rc<RefCell<Mempool>>，都在主线程上");
+    let shared_pool = Rc::new(RefCell::new(Mempool { txs: Vec::new() }));
+    let node1 = Node::new(1, Rc::clone(&shared_pool));
+    let node2 = Node::new(2, Rc::clone(&shared_pool));
+    node1.submit_tx("Mint 100 BTC");
+    node2.submit_tx("Transfer 50 BTC");
+    node1.print_pool();
+
+    // ==========================================
+    // 2. 交给 thread::spawn
+    // ==========================================
+    println!("\n[2] 让 node2 跑在自己的线程上：");
+    println!("      thread::spawn(move || node2.submit_tx(\"Transfer 20 BTC\"));");
+    println!("  编译器:");
+    println!("      error[E0277]: `Rc<RefCell<Mempool>>` cannot be sent between threads safely");
+    println!("        = help: within `{{closure}}`, the trait `Send` is not implemented for `Rc<RefCell<Mempool>>`");
+    println!("      note: required because it's used within this closure");
+    println!("      note: required by a bound in `spawn`");
+    println!("  逐行解读:");
+    println!("      spawn 的签名要求 F: Send + 'static —— 闭包要被搬到新线程上去");
+    println!("      闭包 move 捕获了 node2，node2 的字段 pool 是 Rc，所以闭包也不是 Send");
+    println!("      Rc 的引用计数是普通整数：两个线程同时 clone / drop，计数会算错 (ex06 的 load + store 丢更新)，");
+    println!("      计数提前归零就是 use-after-free。所以 Rc 干脆不实现 Send，这种 bug 在编译期就被挡住了");
+
+    // ==========================================
+    // 3. Rc -> Arc，还不够
+    // ==========================================
+    println!("\n[3] 只把 Rc 换成 Arc：Arc<RefCell<Mempool>>");
+    println!("  编译器:");
+    println!("      error[E0277]: `RefCell<Mempool>` cannot be shared between threads safely");
+    println!("        = help: the trait `Sync` is not implemented for `RefCell<Mempool>`");
+    println!("        = note: if you want to do aliasing and mutation between multiple threads, use `std::sync::RwLock` instead");
+    println!("        = note: required for `Arc<RefCell<Mempool>>` to implement `Send`");
+    println!("  逐行解读:");
+    println!("      计数没问题了，但两个线程会同时拿着同一个 RefCell 的 & 引用 —— 这要求 RefCell: Sync");
+    println!("      RefCell 的借用标志也是普通整数，两个线程可能同时 borrow_mut 成功：两个 &mut 指向同一份数据 = 数据竞争");
+    println!("      所以 Arc<T> 只有在 T: Send + Sync 时才是 Send；连编译器都提示了：换成 RwLock (或 Mutex)");
+
+    // ==========================================
+    // 4. 自动 trait 一览
+    // ==========================================
+    println!("\n[4] 各个类型是不是 Send / Sync");
+    println!("  {:<30} {:<6} {:<6} 原因", "类型", "Send", "Sync");
+    probe!(Mempool, "字段 Vec<String> 都是 Send + Sync，自动推导");
+    probe!(Rc<RefCell<Mempool>>, "非原子的引用计数");
+    probe!(RefCell<Mempool>, "可以整个搬走 (Send)，但借用标志不能被多个线程同时改");
+    probe!(Arc<RefCell<Mempool>>, "Arc<T>: Send 需要 T: Send + Sync");
+    probe!(Mutex<Mempool>, "锁把同时访问变成排队：只要 T: Send 就是 Sync");
+    probe!(Arc<Mutex<Mempool>>, "✔ 可以交给 thread::spawn");
+    probe!(MutexGuard<'static, Mempool>, "锁必须在加锁的那个线程上解开");
+
+    // ==========================================
+    // 5. Arc<Mutex<Mempool>>
+    // ==========================================
+    println!("\n[5] Arc<Mutex<Mempool>>：4 个节点线程，各提交 3 笔交易");
+    let pool = Arc::new(Mutex::new(Mempool { txs: Vec::new() }));
+    let handles: Vec<_> = (1..=4)
+        .map(|id| {
+            let node = SyncNode::new(id, Arc::clone(&pool));
+            thread::spawn(move || {
+                for n in 1..=3 {
+                    node.submit_tx(&format!("Transfer {} BTC", id * 10 + n));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let observer = SyncNode::new(0, Arc::clone(&pool));
+    let size = observer.pool_size();
+    println!("  池子里 {} 笔交易 (应该是 12) {}", size, mark(size == 12));
+    println!("  前 4 笔 (顺序取决于线程调度): {:?}", &pool.lock().unwrap().txs[..4]);
+
+    // ==========================================
+    // 6. 运行时的行为对照
+    // ==========================================
+    println!("\n[6] 数据被占用时再申请写权限");
+    let held = shared_pool.borrow_mut();
+    match shared_pool.try_borrow_mut() {
+        Ok(_) => println!("  RefCell: 又借到了 ❌ (不应该发生)"),
+        Err(e) => println!("  RefCell 被 borrow_mut 占着: try_borrow_mut -> Err({}) —— 直接 borrow_mut() 会 panic", e),
+    }
+    drop(held);
+    let guard = pool.lock().unwrap();
+    match pool.try_lock() {
+        Ok(_) => println!("  Mutex: 又锁上了 ❌ (不应该发生)"),
+        Err(TryLockError::WouldBlock) => println!("  Mutex 被 lock 占着: try_lock -> WouldBlock —— 直接 lock() 会一直等 (别的线程持有时等它释放，同一个线程里就是死锁)"),
+        Err(TryLockError::Poisoned(_)) => println!("  Mutex 中毒了 (见 ex11)"),
+    }
+    drop(guard);
+}
+
+/*
+关键点总结：
+    1. S03 -> S04 的对应关系：
+        Rc<T>          -> Arc<T>          引用计数改成原子操作
+        RefCell<T>     -> Mutex<T> / RwLock<T>   运行时借用标志改成锁
+        borrow_mut()   -> lock().unwrap() 冲突时从 panic 变成等待
+        borrow()       -> RwLock::read()  多个读者并存 (ex04)
+
+    2. Send / Sync 是自动推导的标记 trait：
+        没有方法，只是"承诺"；编译器根据字段自动实现，Rc、RefCell、裸指针等少数类型主动声明自己不是；
+        spawn、scope.spawn、Arc 的约束把这些承诺串起来 —— 线程安全问题因此变成编译错误，而不是偶发的 bug。
+
+    3. 为什么单线程版本不直接用 Arc<Mutex>：
+        原子操作和锁都有成本 (ex06 的计时)；只在一个线程里共享时，Rc<RefCell> 更便宜，
+        而且一旦有人想把它带进别的线程，编译器会提醒你换掉。
+*/
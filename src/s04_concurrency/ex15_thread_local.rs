@@ -0,0 +1,269 @@
+// src/s04_concurrency/ex15_thread_local.rs
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::sha256;
+use crate::s06_chain::pow::leading_zero_bits;
+
+/*
+业务场景：矿池按"份额" (share) 给矿工记账：哈希的前 SHARE_BITS 位为 0 就算一份，比真正出块的难度低得多。
+    每个矿工线程要记自己算了多少次哈希、交了多少份额、最好的一次有几位 0；还要一个随机数发生器挑起始 nonce。
+    这些状态只有矿工自己改，放进共享的 Arc<Mutex> / Atomic 里，每算一次哈希就去碰一次共享内存，纯属浪费。
+    thread_local!：每个线程一份独立的变量，用的时候不加锁、不用原子指令，也不用一层层当参数传下去。
+
+本练习：
+    1. 每个线程一份：主线程和子线程各自记账，互不影响；nonce 计数器第一次用到时才在本线程初始化
+    2. 矿池记账 (thread_local 版)：MINERS 个线程，计数器和 RNG 都在线程本地，收工时把统计交回主线程汇总
+    3. 同样的活，改用共享的 AtomicU64：所有线程从一个 nonce 计数器取号、往同一组计数器上加
+    4. 只比计数本身：线程本地 Cell<u64> vs 共享 AtomicU64::fetch_add
+*/
+
+const MINERS: u64 = 4;
+const HASHES_PER_MINER: u64 = 50_000;
+const SHARE_BITS: u32 = 8;
+const BLOCK_DATA: &[u8] = b"Block#102: [Tx5, Tx6]";
+const INCREMENTS: u64 = 5_000_000;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct MinerStats {
+    start_nonce: u64,
+    hashes: u64,
+    shares: u64,
+    best_bits: u32,
+}
+
+// 给每个线程的 RNG 一个不同的种子：同一纳秒启动的两个线程也不会挑到同一段 nonce
+static SEED_SEQ: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    // 初始化表达式是惰性的：某个线程第一次访问时，才在那个线程上执行一次
+    static MINER_RNG: RefCell<SimpleRng> = RefCell::new(SimpleRng::new(SimpleRng::from_time().next_u64() ^ SEED_SEQ.fetch_add(1, Ordering::Relaxed)));
+    // 本线程下一个要试的 nonce：从 RNG 挑一个随机起点，之后逐个递增
+    // 2^64 的空间里几个随机起点撞到一起的概率可以忽略，所以各线程不用商量怎么分段
+    static NEXT_NONCE: Cell<u64> = Cell::new(MINER_RNG.with(|rng| rng.borrow_mut().next_u64()));
+    static STATS: Cell<MinerStats> = Cell::new(MinerStats::default());
+}
+
+fn block_hash_bits(nonce: u64) -> u32 {
+    let mut bytes = BLOCK_DATA.to_vec();
+    bytes.extend_from_slice(&nonce.to_le_bytes());
+    leading_zero_bits(&sha256(&bytes))
+}
+
+// 从本线程的计数器取一个 nonce —— 没有参数，也没有锁
+fn take_nonce() -> u64 {
+    NEXT_NONCE.with(|next| {
+        let nonce = next.get();
+        next.set(nonce.wrapping_add(1));
+        nonce
+    })
+}
+
+// 记一次哈希的结果到本线程的账本
+fn record(nonce: u64, bits: u32) {
+    STATS.with(|stats| {
+        let mut s = stats.get();
+        if s.hashes == 0 {
+            s.start_nonce = nonce;
+        }
+        s.hashes += 1;
+        if bits >= SHARE_BITS {
+            s.shares += 1;
+        }
+        s.best_bits = s.best_bits.max(bits);
+        stats.set(s);
+    });
+}
+
+// 线程结束时 thread_local 的值跟着销毁，所以要在线程里把账本取出来，通过 join 交回去
+fn take_stats() -> MinerStats {
+    STATS.with(|stats| stats.take())
+}
+
+fn mine_local(hashes: u64) {
+    for _ in 0..hashes {
+        let nonce = take_nonce();
+        record(nonce, block_hash_bits(nonce));
+    }
+}
+
+// 矿池记账的共享版本：一个全局 nonce 计数器 + 三个全局统计
+#[derive(Default)]
+struct SharedPool {
+    next_nonce: AtomicU64,
+    hashes: AtomicU64,
+    shares: AtomicU64,
+    best_bits: AtomicU64,
+}
+
+fn mine_shared(pool: &SharedPool, hashes: u64) {
+    for _ in 0..hashes {
+        // 每一次哈希：三次 fetch_add + 一次 fetch_max，全都落在所有线程共用的那几个字上
+        let nonce = pool.next_nonce.fetch_add(1, Ordering::Relaxed);
+        let bits = block_hash_bits(nonce);
+        pool.hashes.fetch_add(1, Ordering::Relaxed);
+        if bits >= SHARE_BITS {
+            pool.shares.fetch_add(1, Ordering::Relaxed);
+        }
+        pool.best_bits.fetch_max(bits as u64, Ordering::Relaxed);
+    }
+}
+
+// MINERS 个线程各计数 INCREMENTS 次，返回 (总数, 用时)
+fn count_local() -> (u64, Duration) {
+    thread_local! {
+        static COUNTER: Cell<u64> = const { Cell::new(0) };
+    }
+    let start = Instant::now();
+    let handles: Vec<_> = (0..MINERS)
+        .map(|_| {
+            thread::spawn(|| {
+                for _ in 0..INCREMENTS {
+                    COUNTER.with(|c| c.set(c.get() + 1));
+                }
+                COUNTER.with(|c| c.get())
+            })
+        })
+        .collect();
+    let total = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    (total, start.elapsed())
+}
+
+fn count_shared() -> (u64, Duration) {
+    let counter = Arc::new(AtomicU64::new(0));
+    let start = Instant::now();
+    let handles: Vec<_> = (0..MINERS)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..INCREMENTS {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    (counter.load(Ordering::Relaxed), start.elapsed())
+}
+
+pub fn run() {
+    println!("--- S04 Ex15: 线程本地存储 (thread_local! 的 nonce 计数器、RNG 和矿工账本) ---");
+
+    // ==========================================
+    // 1. 每个线程一份
+    // ==========================================
+    println!("\n[1] 主线程和一个子线程用同一个 STATS / NEXT_NONCE");
+    for _ in 0..3 {
+        let nonce = take_nonce();
+        record(nonce, block_hash_bits(nonce));
+    }
+    let main_before = STATS.with(|s| s.get());
+    println!("  主线程: 算了 {} 次，起始 nonce {:#018x}", main_before.hashes, main_before.start_nonce);
+    let child = thread::spawn(|| {
+        let seen = STATS.with(|s| s.get()).hashes;
+        for _ in 0..5 {
+            let nonce = take_nonce();
+            record(nonce, block_hash_bits(nonce));
+        }
+        (seen, take_stats())
+    })
+    .join()
+    .unwrap();
+    println!("  子线程: 一开始看到 {} 次 (不是主线程的 3)，自己算了 {} 次，起始 nonce {:#018x}", child.0, child.1.hashes, child.1.start_nonce);
+    let main_after = take_stats();
+    let ok = if main_after.hashes == 3 && child.1.start_nonce != main_after.start_nonce { "✅" } else { "❌" };
+    println!("  主线程: 还是 {} 次；两个线程的 RNG 各自初始化，起始 nonce 不同 {}", main_after.hashes, ok);
+
+    // ==========================================
+    // 2. thread_local 版矿池
+    // ==========================================
+    println!("\n[2] {} 个矿工，每人 {} 次哈希，份额难度 {} 位 (期望每 {} 次一份)", MINERS, HASHES_PER_MINER, SHARE_BITS, 1u64 << SHARE_BITS);
+    let start = Instant::now();
+    let handles: Vec<_> = (0..MINERS)
+        .map(|miner| {
+            thread::Builder::new()
+                .name(format!("miner-{}", miner))
+                .spawn(|| {
+                    mine_local(HASHES_PER_MINER);
+                    take_stats()
+                })
+                .unwrap()
+        })
+        .collect();
+    let reports: Vec<MinerStats> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    let local_time = start.elapsed();
+    println!("  {:<7} {:>18} {:>6} {:>4} {:>7}", "矿工", "起始 nonce", "哈希", "份额", "最好");
+    for (miner, s) in reports.iter().enumerate() {
+        println!("  miner-{:<3} {:>#20x} {:>8} {:>6} {:>6} 位", miner, s.start_nonce, s.hashes, s.shares, s.best_bits);
+    }
+    let hashes: u64 = reports.iter().map(|s| s.hashes).sum();
+    let shares: u64 = reports.iter().map(|s| s.shares).sum();
+    let best = reports.iter().map(|s| s.best_bits).max().unwrap_or(0);
+    let ok = if hashes == MINERS * HASHES_PER_MINER { "✅" } else { "❌" };
+    println!("  汇总: {} 次哈希 {}，{} 份，最好 {} 位，用时 {:.1?}", hashes, ok, shares, best, local_time);
+    println!("  矿池按份额分账: 每个矿工的份额都记在它自己线程的账本里，跑的时候谁也不碰谁");
+
+    // ==========================================
+    // 3. 共享原子计数器版
+    // ==========================================
+    println!("\n[3] 同样的活，改用共享的 AtomicU64 (一个 nonce 计数器 + 哈希 / 份额 / 最好成绩)");
+    let pool = Arc::new(SharedPool::default());
+    let start = Instant::now();
+    let handles: Vec<_> = (0..MINERS)
+        .map(|_| {
+            let pool = Arc::clone(&pool);
+            thread::spawn(move || mine_shared(&pool, HASHES_PER_MINER))
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let shared_time = start.elapsed();
+    let hashes = pool.hashes.load(Ordering::Relaxed);
+    let ok = if hashes == MINERS * HASHES_PER_MINER && pool.next_nonce.load(Ordering::Relaxed) == hashes { "✅" } else { "❌" };
+    println!("  汇总: {} 次哈希 {}，{} 份，最好 {} 位，用时 {:.1?}", hashes, ok, pool.shares.load(Ordering::Relaxed), pool.best_bits.load(Ordering::Relaxed), shared_time);
+    println!("  总数一样对，但每个矿工各交了几份已经分不出来了 —— 要按矿工记账，还得再给每人开一组计数器");
+    println!("  用时差别不大 ({:.1?} vs {:.1?})：一次 SHA-256 比一次 fetch_add 贵得多，计数的开销被淹没了", local_time, shared_time);
+
+    // ==========================================
+    // 4. 只比计数
+    // ==========================================
+    println!("\n[4] {} 个线程各计数 {} 次，不算哈希", MINERS, INCREMENTS);
+    let (local_total, local_time) = count_local();
+    let (shared_total, shared_time) = count_shared();
+    let expected = MINERS * INCREMENTS;
+    println!("  {:<28} 总数 {} {}  用时 {:>8.1?}", "thread_local Cell<u64>", local_total, if local_total == expected { "✅" } else { "❌" }, local_time);
+    println!("  {:<28} 总数 {} {}  用时 {:>8.1?}", "共享 AtomicU64::fetch_add", shared_total, if shared_total == expected { "✅" } else { "❌" }, shared_time);
+    println!("  多核上 fetch_add 要把那条缓存行在各个核之间搬来搬去 (缓存行乒乓)，线程越多越慢；");
+    println!("  Cell 只是本线程内存里的普通加法，--release 下编译器甚至能把整个循环合并成一次加法。");
+    if local_time >= shared_time {
+        println!("  这次 thread_local 没有更快：debug 构建里每次 .with() 都是一次没内联的函数调用，单核机器上也没有别的核来抢缓存行");
+        println!("  (换成 cargo run --release 再看一次)");
+    }
+}
+
+/*
+关键点总结：
+    1. thread_local! 的三个特点：
+        每个线程一份：同一个 static 名字，不同线程看到的是不同的值；
+        惰性初始化：线程第一次 .with() 时才执行初始化表达式 (所以能用本线程的 RNG 给本线程的 nonce 计数器挑起点)；
+        随线程销毁：线程结束时值被 drop，想保留结果就要在线程里取出来 (take_stats)，通过 join 或 channel 交出去。
+
+    2. 为什么里面放 Cell / RefCell，而不是 Mutex：
+        只有本线程能访问，不存在"别的线程同时来改"，单线程的内部可变性 (S03) 就够了；
+        .with() 只给 &T，不能把引用带出闭包，也不能交给别的线程 —— 编译器保证它永远只在本线程用。
+
+    3. 什么时候用 thread_local，什么时候用共享原子：
+        状态只有本线程改，最后才汇总 (计数器、统计、RNG、缓冲区) -> thread_local，跑的时候零争用；
+        状态必须被所有线程实时看到 (ex01 的"找到了"旗子、全局唯一的递增编号) -> 原子变量 / 锁。
+
+    4. 这个模式到处都是：
+        rand::thread_rng() 就是一个 thread_local 的 RNG；内存分配器给每个线程一个本地缓存；
+        rayon、tokio 的工作线程各有一个本地任务队列，空了才去别的线程那里偷。
+*/
@@ -1,6 +1,21 @@
+// 混沌调度器：给本板块的练习注入随机扰动、反复回放
+pub mod chaos;
+
 pub mod ex01_thread;
 pub mod ex02_sync;
 pub mod ex03_channel; 
+pub mod ex04_rwlock;
+pub mod ex05_gossip;
+pub mod ex06_atomics;
+pub mod ex07_condvar;
+pub mod ex08_barrier;
+pub mod ex09_scoped;
+pub mod ex10_deadlock;
+pub mod ex11_poison;
+pub mod ex12_mpmc;
+pub mod ex13_par_map;
+pub mod ex14_send_sync;
+pub mod ex15_thread_local;
 
 use std::io;
 
@@ -10,6 +25,19 @@ pub fn run_experiments() {
         println!("1. 线程基础与 Move (Mining Simulator)");
         println!("2. 共享状态 (Arc + Mutex)");
         println!("3. 消息传递 (Channel)");
+        println!("4. 读写锁 (RwLock：多读者 vs Mutex，写者饥饿)");
+        println!("5. P2P Gossip (多个节点线程互相洪泛交易和区块)");
+        println!("6. 原子操作 (AtomicU64 计数器 vs Mutex，load + store 丢更新)");
+        println!("7. 条件变量 (Mutex + Condvar 的区块队列，wait_while 与虚假唤醒)");
+        println!("8. 栅栏 (Barrier：矿工按轮同时开挖，领头的线程公布结果)");
+        println!("9. 作用域线程 (thread::scope：不用 Arc、不用 clone，直接借用栈上的交易)");
+        println!("10. 死锁 (两个线程反方向加锁、看门狗，加锁顺序与 try_lock 重试)");
+        println!("11. 锁中毒 (持锁线程 panic，PoisonError：unwrap / 传播 / into_inner 恢复)");
+        println!("12. 手写 MPMC 队列 (Mutex<VecDeque> + 两个 Condvar，对照 std mpsc)");
+        println!("13. 手写 par_map (切段多线程 map，批量哈希 / 签名交易的加速比)");
+        println!("14. Send 与 Sync (S03 的 Rc<RefCell<Mempool>> 为什么不能跨线程，迁移到 Arc<Mutex>)");
+        println!("15. 线程本地存储 (thread_local! 的 nonce 计数器和 RNG，对照共享原子计数器)");
+        println!("c. 混沌调度回放 (Chaos Replay)");
         println!("0. 返回主菜单");
         println!("请输入练习编号:");
 
@@ -20,6 +48,19 @@ pub fn run_experiments() {
             "1" => ex01_thread::run(),
             "2" => ex02_sync::run(),
             "3" => ex03_channel::run(),
+            "4" => ex04_rwlock::run(),
+            "5" => ex05_gossip::run(),
+            "6" => ex06_atomics::run(),
+            "7" => ex07_condvar::run(),
+            "8" => ex08_barrier::run(),
+            "9" => ex09_scoped::run(),
+            "10" => ex10_deadlock::run(),
+            "11" => ex11_poison::run(),
+            "12" => ex12_mpmc::run(),
+            "13" => ex13_par_map::run(),
+            "14" => ex14_send_sync::run(),
+            "15" => ex15_thread_local::run(),
+            "c" => chaos::run(),
             "0" => break,
             _ => println!("❌ 无效选择"),
         }
@@ -0,0 +1,61 @@
+// src/s05_zk_lab/bitcoin_merkle.rs
+use super::ex01_merkle::Hasher;
+use super::hash::{sha256d, Digest};
+
+/*
+比特币区块头里的 Merkle 根是怎么算的
+
+    1. 叶子就是交易的 txid (= sha256d(原始交易字节))，叶子本身不再哈希
+    2. 父节点 = sha256d(left || right)，拼接的是 32 字节的原始摘要，不是十六进制字符串
+    3. 某一层是奇数个时，复制最后一个和自己配对 (与 ex01 的补齐规则一致)
+    4. 字节序：摘要在协议里按"内部字节序"存储，浏览器和 RPC 显示时把 32 个字节整体反转
+       —— 所以 txid 和 Merkle 根在拼接之前要反转一次，算完再反转回来显示
+
+本文件把这套规则实现成 ex01 的 Hasher，Merkle 树的构建、证明、验证逻辑一行不用改。
+树里保存的哈希一律是"内部字节序"的十六进制，显示给人看时用 display_order 转换。
+*/
+
+// 32 字节摘要的十六进制，在内部字节序和显示字节序之间转换 (反转是自身的逆操作)
+pub fn display_order(hex_digest: &str) -> String {
+    match hex::decode(hex_digest) {
+        Ok(mut bytes) => {
+            bytes.reverse();
+            hex::encode(bytes)
+        }
+        Err(_) => hex_digest.to_string(),
+    }
+}
+
+// 叶子：浏览器里看到的 txid (显示字节序)，转成内部字节序
+// 不是 64 位十六进制的输入被当成原始交易，先算 txid = sha256d(raw)
+fn bitcoin_leaf(data: &[u8]) -> String {
+    match std::str::from_utf8(data) {
+        Ok(txid) if hex::decode(txid).is_ok_and(|bytes| bytes.len() == 32) => display_order(txid),
+        _ => hex::encode(sha256d(data)),
+    }
+}
+
+fn bitcoin_node(left: &str, right: &str) -> String {
+    let mut buf = hex::decode(left).unwrap_or_default();
+    buf.extend(hex::decode(right).unwrap_or_default());
+    hex::encode(sha256d(&buf))
+}
+
+// 比特币没有域分离：叶子不加前缀 (txid 本身已经是哈希)，节点也不加，所以 leaf / node 都要重写
+#[derive(Debug, Clone, Copy)]
+pub struct BitcoinHasher;
+
+impl Hasher for BitcoinHasher {
+    fn name(&self) -> &'static str {
+        "bitcoin-sha256d"
+    }
+    fn hash(&self, data: &[u8]) -> Digest {
+        sha256d(data)
+    }
+    fn leaf(&self, data: &[u8]) -> String {
+        bitcoin_leaf(data)
+    }
+    fn node(&self, left: &str, right: &str) -> String {
+        bitcoin_node(left, right)
+    }
+}
@@ -0,0 +1,158 @@
+// src/s05_zk_lab/bulletproof.rs
+use crate::common::rng::SimpleRng;
+
+use super::commitments::pedersen::{Commitment, PedersenParams};
+use super::ipa::{self, challenge, inner_product, multi_exp, IpaGenerators, IpaProof};
+use super::math::group::{random_scalar, GroupElement, Scalar, GROUP_ORDER};
+
+/*
+Bulletproofs 范围证明 (Bünz et al. 2018, 第 4.2 节)：把 range_proof.rs 的 O(n) 压到 O(log n)
+
+同样是证明 V = g^v * h^γ 里的 v ∈ [0, 2^n)，同样从位分解出发：
+    a_L = v 的二进制位，a_R = a_L - 1
+    需要证明三件事：<a_L, 2^n> = v，a_L ∘ a_R = 0 (每一位是 0 或 1)，a_R = a_L - 1
+range_proof.rs 对每一位单独做一个 OR 证明；这里用随机挑战 y、z 把 n 个约束揉成一个多项式等式：
+    l(X) = (a_L - z) + s_L X
+    r(X) = y^n ∘ (a_R + z + s_R X) + z^2 * 2^n
+    t(X) = <l(X), r(X)> = t0 + t1 X + t2 X^2，其中 t0 = v z^2 + δ(y, z) 当且仅当三件事都成立
+        δ(y, z) = (z - z^2) <1, y^n> - z^3 <1, 2^n>
+    s_L、s_R 是随机的掩码向量，保证发出去的 l(x)、r(x) 不泄露 a_L、a_R。
+
+证明者发 A、S (向量承诺)、T1、T2 (t1、t2 的承诺)，在挑战 x 处：
+    1. 验证者检查 g^t̂ * h^τx == V^(z^2) * g^δ * T1^x * T2^(x^2)   —— t̂ = t(x) 与 v 一致
+    2. l = l(x)、r = r(x) 本来要整个发过去 (2n 个标量)，改用 ipa.rs 的内积论证证明 <l, r> = t̂，
+       只要 2 log2(n) 个群元素 + 2 个标量 —— 这就是"压缩"发生的地方。
+H' = H_i^(y^-i)：把 r(X) 里的 y^n 吸收进生成元，这样 P 才是标准的 g^l * h'^r 形式。
+
+⚠️ 玩具化：群的阶 q = 1019，和 range_proof.rs 一样要求 2^n < q。
+*/
+
+#[derive(Debug, Clone)]
+pub struct BulletproofRangeProof {
+    pub a: GroupElement,
+    pub s: GroupElement,
+    pub t1: GroupElement,
+    pub t2: GroupElement,
+    pub tau_x: Scalar,
+    pub mu: Scalar,
+    pub t_hat: Scalar,
+    pub ipa: IpaProof,
+}
+
+impl BulletproofRangeProof {
+    // (群元素个数, 标量个数)：4 + 2 log2(n) 个群元素，3 + 2 个标量
+    pub fn size(&self) -> (usize, usize) {
+        let (points, scalars) = self.ipa.size();
+        (4 + points, 3 + scalars)
+    }
+}
+
+fn powers(base: Scalar, n: usize) -> Vec<Scalar> {
+    (0..n).scan(Scalar::one(), |acc, _| {
+        let current = *acc;
+        *acc = *acc * base;
+        Some(current)
+    }).collect()
+}
+
+fn delta(y: Scalar, z: Scalar, n: usize) -> Scalar {
+    let sum_y = powers(y, n).into_iter().fold(Scalar::zero(), |acc, p| acc + p);
+    let sum_2 = Scalar::new((1 << n) - 1);
+    (z - z * z) * sum_y - z * z * z * sum_2
+}
+
+// y、z 由 (V, A, S) 决定；x 再加上 T1、T2；u 的缩放因子 w 再加上三个标量
+fn challenges_yz(commitment: &Commitment, a: GroupElement, s: GroupElement) -> (Scalar, Scalar) {
+    let parts = [commitment.0.value(), a.value(), s.value()];
+    (challenge(&[&parts[..], &[1]].concat()), challenge(&[&parts[..], &[2]].concat()))
+}
+
+// H'_i = H_i^(y^-i)
+fn h_prime(gens: &IpaGenerators, y: Scalar) -> Vec<GroupElement> {
+    gens.h.iter().zip(powers(Scalar::one() / y, gens.h.len())).map(|(&h, e)| h.pow(e)).collect()
+}
+
+// 按给定的"位"生成证明：诚实的调用者只传 0 / 1，传 2 之类的数就是作弊 (a_L ∘ a_R ≠ 0)
+pub fn prove_bits(params: &PedersenParams, bits: &[u64], blinding: Scalar, rng: &mut SimpleRng) -> BulletproofRangeProof {
+    let n = bits.len();
+    let gens = IpaGenerators::setup(n);
+    let value = bits.iter().enumerate().fold(Scalar::zero(), |acc, (i, &b)| acc + Scalar::new(b << i));
+    let commitment = params.commit(value, blinding);
+
+    let a_l: Vec<Scalar> = bits.iter().map(|&b| Scalar::new(b)).collect();
+    let a_r: Vec<Scalar> = a_l.iter().map(|&b| b - Scalar::one()).collect();
+    let alpha = random_scalar(rng);
+    let a = params.h.pow(alpha) * multi_exp(&gens.g, &a_l) * multi_exp(&gens.h, &a_r);
+    let s_l: Vec<Scalar> = (0..n).map(|_| random_scalar(rng)).collect();
+    let s_r: Vec<Scalar> = (0..n).map(|_| random_scalar(rng)).collect();
+    let rho = random_scalar(rng);
+    let s = params.h.pow(rho) * multi_exp(&gens.g, &s_l) * multi_exp(&gens.h, &s_r);
+
+    let (y, z) = challenges_yz(&commitment, a, s);
+    let y_n = powers(y, n);
+    let two_n = powers(Scalar::new(2), n);
+    let l0: Vec<Scalar> = a_l.iter().map(|&b| b - z).collect();
+    let r0: Vec<Scalar> = (0..n).map(|i| y_n[i] * (a_r[i] + z) + z * z * two_n[i]).collect();
+    let r1: Vec<Scalar> = (0..n).map(|i| y_n[i] * s_r[i]).collect();
+    let t1 = inner_product(&l0, &r1) + inner_product(&s_l, &r0);
+    let t2 = inner_product(&s_l, &r1);
+    let (tau1, tau2) = (random_scalar(rng), random_scalar(rng));
+    let big_t1 = params.commit(t1, tau1).0;
+    let big_t2 = params.commit(t2, tau2).0;
+
+    let x = challenge(&[commitment.0.value(), a.value(), s.value(), big_t1.value(), big_t2.value()]);
+    let l: Vec<Scalar> = (0..n).map(|i| l0[i] + s_l[i] * x).collect();
+    let r: Vec<Scalar> = (0..n).map(|i| r0[i] + r1[i] * x).collect();
+    let t_hat = inner_product(&l, &r);
+    let tau_x = tau2 * x * x + tau1 * x + z * z * blinding;
+    let mu = alpha + rho * x;
+
+    // 内积论证：P = G^l * H'^r * u'^t̂
+    let w = challenge(&[x.value(), tau_x.value(), mu.value(), t_hat.value()]);
+    let u = gens.u.pow(w);
+    let h = h_prime(&gens, y);
+    let p = multi_exp(&gens.g, &l) * multi_exp(&h, &r) * u.pow(t_hat);
+    let ipa = ipa::prove(&gens.g, &h, u, p, &l, &r, false);
+    BulletproofRangeProof { a, s, t1: big_t1, t2: big_t2, tau_x, mu, t_hat, ipa }
+}
+
+// 诚实证明者：v >= 2^n 时拆不出 n 个 0/1 位
+pub fn prove(params: &PedersenParams, value: u64, blinding: Scalar, n: usize, rng: &mut SimpleRng) -> Option<BulletproofRangeProof> {
+    assert!(n.is_power_of_two() && (1u64 << n) < GROUP_ORDER, "n must be a power of two with 2^n below the group order");
+    if value >= 1 << n {
+        return None;
+    }
+    let bits: Vec<u64> = (0..n).map(|i| (value >> i) & 1).collect();
+    Some(prove_bits(params, &bits, blinding, rng))
+}
+
+pub fn verify(params: &PedersenParams, commitment: &Commitment, n: usize, proof: &BulletproofRangeProof) -> Result<(), String> {
+    if !n.is_power_of_two() || (1u64 << n) >= GROUP_ORDER {
+        return Err(format!("位数 {} 必须是 2 的幂且 2^n < q", n));
+    }
+    let gens = IpaGenerators::setup(n);
+    let (y, z) = challenges_yz(commitment, proof.a, proof.s);
+    let x = challenge(&[commitment.0.value(), proof.a.value(), proof.s.value(), proof.t1.value(), proof.t2.value()]);
+
+    // 1. t̂ 和承诺里的 v 一致
+    let lhs = params.commit(proof.t_hat, proof.tau_x).0;
+    let rhs = commitment.0.pow(z * z) * params.g.pow(delta(y, z, n)) * proof.t1.pow(x) * proof.t2.pow(x * x);
+    if lhs != rhs {
+        return Err(String::from("g^t̂ * h^τx != V^(z^2) * g^δ * T1^x * T2^(x^2)：t(x) 和承诺的值对不上"));
+    }
+
+    // 2. 验证者自己拼出 P = A * S^x * G^(-z) * H'^(z y^n + z^2 2^n) * h^(-μ) * u'^t̂，交给内积论证
+    let h = h_prime(&gens, y);
+    let y_n = powers(y, n);
+    let two_n = powers(Scalar::new(2), n);
+    let h_exps: Vec<Scalar> = (0..n).map(|i| z * y_n[i] + z * z * two_n[i]).collect();
+    let w = challenge(&[x.value(), proof.tau_x.value(), proof.mu.value(), proof.t_hat.value()]);
+    let u = gens.u.pow(w);
+    let p = proof.a
+        * proof.s.pow(x)
+        * multi_exp(&gens.g, &vec![-z; n])
+        * multi_exp(&h, &h_exps)
+        * params.h.pow(-proof.mu)
+        * u.pow(proof.t_hat);
+    ipa::verify(&gens.g, &h, u, p, &proof.ipa).map_err(|e| format!("内积论证无效: {}", e))
+}
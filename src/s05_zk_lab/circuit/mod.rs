@@ -0,0 +1,253 @@
+// src/s05_zk_lab/circuit/mod.rs
+use std::fmt;
+
+use super::math::field::Fp;
+
+pub mod plonk;
+pub mod r1cs;
+pub mod witness;
+
+use plonk::PlonkCircuit;
+use r1cs::{Constraint, R1cs};
+
+/*
+算术电路 DSL：用"搭积木"的方式写约束，而不是手写 R1CS 矩阵
+
+    let mut c = Circuit::<97>::new();
+    let x = c.private_input("x");
+    let y = c.public_input("y");
+    let x2 = c.mul(x, x);
+    c.assert_eq(x2, y);          // 证明"我知道 y 的平方根"
+
+概念：
+    - 导线 (Wire)：电路里的一个值。0 号导线永远是常数 1 (Wire::ONE)，线性组合里的常数项都靠它。
+    - 门 (Gate)   ：加法门、乘法门、常量门，以及断言两根导线相等的 AssertEq。
+    - 公开输入 / 私有输入：验证者能看到前者，后者就是证明者要隐藏的"见证"。
+
+构建器保证：每个门的输入导线都在它之前创建，所以 gates 天然是拓扑序，
+evaluate 从头到尾走一遍就能算出所有导线的值。
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Wire(pub usize);
+
+impl Wire {
+    pub const ONE: Wire = Wire(0);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    Private,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub name: String,
+    pub wire: Wire,
+    pub visibility: Visibility,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gate<const P: u64> {
+    Const { value: Fp<P>, out: Wire },
+    Add { left: Wire, right: Wire, out: Wire },
+    Mul { left: Wire, right: Wire, out: Wire },
+    AssertEq { left: Wire, right: Wire },
+}
+
+#[derive(Debug, Clone)]
+pub struct Circuit<const P: u64> {
+    num_wires: usize,
+    inputs: Vec<Input>,
+    gates: Vec<Gate<P>>,
+}
+
+impl<const P: u64> Default for Circuit<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const P: u64> Circuit<P> {
+    pub fn new() -> Self {
+        Circuit {
+            num_wires: 1, // Wire::ONE
+            inputs: Vec::new(),
+            gates: Vec::new(),
+        }
+    }
+
+    fn fresh_wire(&mut self) -> Wire {
+        let wire = Wire(self.num_wires);
+        self.num_wires += 1;
+        wire
+    }
+
+    fn input(&mut self, name: &str, visibility: Visibility) -> Wire {
+        let wire = self.fresh_wire();
+        self.inputs.push(Input {
+            name: name.to_string(),
+            wire,
+            visibility,
+        });
+        wire
+    }
+
+    pub fn public_input(&mut self, name: &str) -> Wire {
+        self.input(name, Visibility::Public)
+    }
+
+    pub fn private_input(&mut self, name: &str) -> Wire {
+        self.input(name, Visibility::Private)
+    }
+
+    pub fn constant(&mut self, value: u64) -> Wire {
+        let out = self.fresh_wire();
+        self.gates.push(Gate::Const { value: Fp::new(value), out });
+        out
+    }
+
+    pub fn add(&mut self, left: Wire, right: Wire) -> Wire {
+        let out = self.fresh_wire();
+        self.gates.push(Gate::Add { left, right, out });
+        out
+    }
+
+    pub fn mul(&mut self, left: Wire, right: Wire) -> Wire {
+        let out = self.fresh_wire();
+        self.gates.push(Gate::Mul { left, right, out });
+        out
+    }
+
+    pub fn assert_eq(&mut self, left: Wire, right: Wire) {
+        self.gates.push(Gate::AssertEq { left, right });
+    }
+
+    pub fn num_wires(&self) -> usize {
+        self.num_wires
+    }
+
+    pub fn inputs(&self) -> &[Input] {
+        &self.inputs
+    }
+
+    pub fn gates(&self) -> &[Gate<P>] {
+        &self.gates
+    }
+
+    // 打印用：输入导线显示名字，ONE 显示 1，其余显示 w3 这样的编号
+    pub fn wire_name(&self, wire: Wire) -> String {
+        if wire == Wire::ONE {
+            return String::from("1");
+        }
+        match self.inputs.iter().find(|input| input.wire == wire) {
+            Some(input) => input.name.clone(),
+            None => format!("w{}", wire.0),
+        }
+    }
+
+    // 求值器：公开输入、私有输入分别按声明顺序给出，返回全部导线的值
+    // 个数对不上返回 None。AssertEq 在这里不检查 —— 计算归计算，"对不对"交给 R1CS 去判
+    pub fn evaluate(&self, public: &[Fp<P>], private: &[Fp<P>]) -> Option<Vec<Fp<P>>> {
+        let count = |v| self.inputs.iter().filter(|i| i.visibility == v).count();
+        if count(Visibility::Public) != public.len() || count(Visibility::Private) != private.len() {
+            return None;
+        }
+
+        let mut wires = vec![Fp::zero(); self.num_wires];
+        wires[Wire::ONE.0] = Fp::one();
+        let (mut pub_iter, mut priv_iter) = (public.iter(), private.iter());
+        for input in &self.inputs {
+            let value = match input.visibility {
+                Visibility::Public => pub_iter.next(),
+                Visibility::Private => priv_iter.next(),
+            };
+            wires[input.wire.0] = *value?;
+        }
+
+        for gate in &self.gates {
+            match *gate {
+                Gate::Const { value, out } => wires[out.0] = value,
+                Gate::Add { left, right, out } => wires[out.0] = wires[left.0] + wires[right.0],
+                Gate::Mul { left, right, out } => wires[out.0] = wires[left.0] * wires[right.0],
+                Gate::AssertEq { .. } => {}
+            }
+        }
+        Some(wires)
+    }
+
+    // 编译成 R1CS：每个门变成一条 <a, z> * <b, z> = <c, z> 约束，z 就是全部导线的值
+    //   Const    : (v·1) * 1 = out
+    //   Add      : (l + r) * 1 = out      (加法是"免费"的，只是借一个乘 1 凑成 R1CS 的形状)
+    //   Mul      : l * r = out
+    //   AssertEq : (l - r) * 1 = 0
+    pub fn to_r1cs(&self) -> R1cs<P> {
+        let one = Fp::one();
+        let constraints = self
+            .gates
+            .iter()
+            .map(|gate| match *gate {
+                Gate::Const { value, out } => Constraint {
+                    a: vec![(Wire::ONE.0, value)],
+                    b: vec![(Wire::ONE.0, one)],
+                    c: vec![(out.0, one)],
+                },
+                Gate::Add { left, right, out } => Constraint {
+                    a: vec![(left.0, one), (right.0, one)],
+                    b: vec![(Wire::ONE.0, one)],
+                    c: vec![(out.0, one)],
+                },
+                Gate::Mul { left, right, out } => Constraint {
+                    a: vec![(left.0, one)],
+                    b: vec![(right.0, one)],
+                    c: vec![(out.0, one)],
+                },
+                Gate::AssertEq { left, right } => Constraint {
+                    a: vec![(left.0, one), (right.0, -one)],
+                    b: vec![(Wire::ONE.0, one)],
+                    c: vec![],
+                },
+            })
+            .collect();
+        let public = self
+            .inputs
+            .iter()
+            .filter(|i| i.visibility == Visibility::Public)
+            .map(|i| i.wire.0)
+            .collect();
+        R1cs {
+            num_vars: self.num_wires,
+            public,
+            constraints,
+        }
+    }
+
+    // 编译成 PLONK 风格的门表 (plonk.rs)：每个门一行，导线之间的相等关系另用置换 σ 表达
+    pub fn to_plonk(&self) -> Result<PlonkCircuit<P>, String> {
+        PlonkCircuit::compile(self)
+    }
+}
+
+// 打印成一行一个门的"汇编"风格
+impl<const P: u64> fmt::Display for Circuit<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for input in &self.inputs {
+            let tag = match input.visibility {
+                Visibility::Public => "public ",
+                Visibility::Private => "private",
+            };
+            writeln!(f, "  {} {:<6} (w{})", tag, input.name, input.wire.0)?;
+        }
+        for gate in &self.gates {
+            let n = |w: Wire| self.wire_name(w);
+            match *gate {
+                Gate::Const { value, out } => writeln!(f, "  {} = {}", n(out), value)?,
+                Gate::Add { left, right, out } => writeln!(f, "  {} = {} + {}", n(out), n(left), n(right))?,
+                Gate::Mul { left, right, out } => writeln!(f, "  {} = {} * {}", n(out), n(left), n(right))?,
+                Gate::AssertEq { left, right } => writeln!(f, "  assert {} == {}", n(left), n(right))?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+// src/s05_zk_lab/circuit/plonk.rs
+use crate::s05_zk_lab::math::field::Fp;
+use crate::s05_zk_lab::math::poly::Polynomial;
+
+use super::{Circuit, Gate, Visibility, Wire};
+
+/*
+PLONK 风格的门约束 (Gabizon, Williamson, Ciobotaru 2019)
+
+    每一行 (row) 只有三根导线 a、b、c 和五个选择子 (selector)，满足同一个方程：
+        qL·a + qR·b + qM·a·b + qO·c + qC + PI = 0
+    选择子的取值决定这一行是什么门：
+        加法门  a + b = c   : qL = 1, qR = 1, qO = -1
+        乘法门  a * b = c   : qM = 1, qO = -1
+        常量门  a = v       : qL = 1, qC = -v
+        断言    a = b       : qL = 1, qR = -1
+        公开输入 a = x      : qL = 1，x 放在 PI 里 (PI = -x)，验证者自己算这一项
+
+和 R1CS 的区别：R1CS 的变量是全局的 —— 两条约束引用同一个下标就自动是同一个值。
+PLONK 每一行的 a、b、c 都是"本行私有"的格子，"第 3 行的 b 就是第 1 行的 a"这种
+复制约束 (copy constraint) 必须单独证明：把所有格子按导线分组，每组连成一个环，
+得到一个置换 σ，再证明"按 σ 重排之后整张表不变"。
+
+多项式化：行数补齐到 2 的幂 n，第 i 行对应单位根 ω^i (定义域 H = {ω^0 .. ω^(n-1)})。
+    每一列选择子、每一列导线值都插值成次数 < n 的多项式，
+    "每一行门方程都成立" ⇔ G(X) = qL·a + qR·b + qM·a·b + qO·c + qC + PI 在 H 上处处为 0
+                        ⇔ Z_H(X) = Π (X - ω^i) 整除 G(X)
+*/
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlonkRow<const P: u64> {
+    pub q_l: Fp<P>,
+    pub q_r: Fp<P>,
+    pub q_m: Fp<P>,
+    pub q_o: Fp<P>,
+    pub q_c: Fp<P>,
+    // 这一行的 a、b、c 分别接哪根电路导线；None 表示这个格子没用上，不参与复制约束
+    pub wires: [Option<Wire>; 3],
+}
+
+impl<const P: u64> PlonkRow<P> {
+    fn new(q_l: i64, q_r: i64, q_m: i64, q_o: i64, q_c: Fp<P>, wires: [Option<Wire>; 3]) -> Self {
+        PlonkRow {
+            q_l: Fp::from_i64(q_l),
+            q_r: Fp::from_i64(q_r),
+            q_m: Fp::from_i64(q_m),
+            q_o: Fp::from_i64(q_o),
+            q_c,
+            wires,
+        }
+    }
+}
+
+pub const COLUMN_NAMES: [&str; 3] = ["a", "b", "c"];
+
+#[derive(Debug, Clone)]
+pub struct PlonkCircuit<const P: u64> {
+    pub rows: Vec<PlonkRow<P>>,
+    // (行号, 公开输入导线)：这些行的 PI 由验证者填
+    pub public_rows: Vec<(usize, Wire)>,
+    // 被同一根导线连起来的格子 (格子编号 = 列 * n + 行)，只列出出现两次以上的导线
+    pub copies: Vec<(Wire, Vec<usize>)>,
+    // σ：每个格子指向同组里的下一个格子，没有复制约束的格子指向自己
+    pub sigma: Vec<usize>,
+    // H = {ω^0, ω^1, ..., ω^(n-1)}
+    pub domain: Vec<Fp<P>>,
+    // 三列的陪集偏移 k_a = 1、k_b、k_c：格子 (j, i) 的标签是 k_j * ω^i，三列的标签互不相同
+    pub shifts: [Fp<P>; 3],
+}
+
+// n 次单位根的原根：需要 n | P - 1；n 是 2 的幂，所以 ω^(n/2) ≠ 1 就说明阶恰好是 n
+fn root_of_unity<const P: u64>(n: usize) -> Option<Fp<P>> {
+    let n = n as u64;
+    if !(P - 1).is_multiple_of(n) {
+        return None;
+    }
+    (2..P)
+        .map(|c| Fp::new(c).pow((P - 1) / n))
+        .find(|w| n == 1 || w.pow(n / 2) != Fp::one())
+}
+
+// k_b ∉ H，k_c ∉ H ∪ k_b·H：x ∈ H ⇔ x^n = 1
+fn coset_shifts<const P: u64>(n: usize) -> Option<[Fp<P>; 3]> {
+    let outside = |x: Fp<P>| x.pow(n as u64) != Fp::one();
+    let k_b = (2..P).map(Fp::new).find(|&k| outside(k))?;
+    let k_c = (2..P).map(Fp::new).find(|&k| outside(k) && outside(k / k_b))?;
+    Some([Fp::one(), k_b, k_c])
+}
+
+impl<const P: u64> PlonkCircuit<P> {
+    // 从电路 DSL 编译：公开输入各占一行，然后每个门一行，最后补空行 (选择子全 0) 到 2 的幂
+    pub fn compile(circuit: &Circuit<P>) -> Result<Self, String> {
+        let mut rows = Vec::new();
+        let mut public_rows = Vec::new();
+        for input in circuit.inputs().iter().filter(|i| i.visibility == Visibility::Public) {
+            public_rows.push((rows.len(), input.wire));
+            rows.push(PlonkRow::new(1, 0, 0, 0, Fp::zero(), [Some(input.wire), None, None]));
+        }
+        // R1CS 里常数 1 是 z[0]，天然可用；PLONK 里 Wire::ONE 只是普通格子，被引用了就得先钉死它
+        let uses_one = circuit.gates().iter().any(|g| match *g {
+            Gate::Add { left, right, .. } | Gate::Mul { left, right, .. } | Gate::AssertEq { left, right } => {
+                left == Wire::ONE || right == Wire::ONE
+            }
+            Gate::Const { .. } => false,
+        });
+        if uses_one {
+            rows.push(PlonkRow::new(1, 0, 0, 0, -Fp::one(), [Some(Wire::ONE), None, None]));
+        }
+        for gate in circuit.gates() {
+            rows.push(match *gate {
+                Gate::Const { value, out } => PlonkRow::new(1, 0, 0, 0, -value, [Some(out), None, None]),
+                Gate::Add { left, right, out } => PlonkRow::new(1, 1, 0, -1, Fp::zero(), [Some(left), Some(right), Some(out)]),
+                Gate::Mul { left, right, out } => PlonkRow::new(0, 0, 1, -1, Fp::zero(), [Some(left), Some(right), Some(out)]),
+                Gate::AssertEq { left, right } => PlonkRow::new(1, -1, 0, 0, Fp::zero(), [Some(left), Some(right), None]),
+            });
+        }
+
+        let n = rows.len().next_power_of_two();
+        rows.resize(n, PlonkRow::new(0, 0, 0, 0, Fp::zero(), [None; 3]));
+        let omega = root_of_unity(n).ok_or_else(|| format!("F_{} 里没有 {} 次单位根 ({} ∤ {})", P, n, n, P - 1))?;
+        let shifts = coset_shifts(n).ok_or_else(|| format!("F_{} 太小，找不到三个互不相交的陪集", P))?;
+        let domain = (0..n as u64).map(|i| omega.pow(i)).collect();
+
+        // 按导线分组，每组首尾相连成一个环
+        let mut copies: Vec<(Wire, Vec<usize>)> = Vec::new();
+        for col in 0..3 {
+            for (i, row) in rows.iter().enumerate() {
+                let Some(wire) = row.wires[col] else { continue };
+                match copies.iter_mut().find(|(w, _)| *w == wire) {
+                    Some((_, cells)) => cells.push(col * n + i),
+                    None => copies.push((wire, vec![col * n + i])),
+                }
+            }
+        }
+        copies.retain(|(_, cells)| cells.len() > 1);
+        copies.sort_by_key(|(wire, _)| *wire);
+        let mut sigma: Vec<usize> = (0..3 * n).collect();
+        for (_, cells) in &copies {
+            for (k, &cell) in cells.iter().enumerate() {
+                sigma[cell] = cells[(k + 1) % cells.len()];
+            }
+        }
+
+        Ok(PlonkCircuit { rows, public_rows, copies, sigma, domain, shifts })
+    }
+
+    pub fn size(&self) -> usize {
+        self.rows.len()
+    }
+
+    // 格子编号 -> "b3" 这样的名字
+    pub fn cell_name(&self, cell: usize) -> String {
+        format!("{}{}", COLUMN_NAMES[cell / self.size()], cell % self.size())
+    }
+
+    // 证明者的见证表：按每行的 wires 从电路导线值里抄，没用上的格子填 0
+    pub fn columns(&self, wires: &[Fp<P>]) -> [Vec<Fp<P>>; 3] {
+        [0, 1, 2].map(|col| {
+            self.rows
+                .iter()
+                .map(|row| row.wires[col].map_or(Fp::zero(), |w| wires[w.0]))
+                .collect()
+        })
+    }
+
+    // 验证者由公开输入算出的 PI 列 (公开输入按声明顺序给出)
+    pub fn public_column(&self, public: &[Fp<P>]) -> Vec<Fp<P>> {
+        let mut pi = vec![Fp::zero(); self.size()];
+        for (&(row, _), &value) in self.public_rows.iter().zip(public) {
+            pi[row] = -value;
+        }
+        pi
+    }
+
+    pub fn gate_value(&self, i: usize, columns: &[Vec<Fp<P>>; 3], pi: &[Fp<P>]) -> Fp<P> {
+        let row = &self.rows[i];
+        let (a, b, c) = (columns[0][i], columns[1][i], columns[2][i]);
+        row.q_l * a + row.q_r * b + row.q_m * a * b + row.q_o * c + row.q_c + pi[i]
+    }
+
+    // 逐行检查门方程，返回第一条不成立的行
+    pub fn first_failing_gate(&self, columns: &[Vec<Fp<P>>; 3], pi: &[Fp<P>]) -> Option<usize> {
+        (0..self.size()).find(|&i| !self.gate_value(i, columns, pi).is_zero())
+    }
+
+    // 一列取值在 H 上插值：第 i 个值落在 ω^i
+    pub fn interpolate(&self, values: &[Fp<P>]) -> Polynomial<P> {
+        let points: Vec<(Fp<P>, Fp<P>)> = self.domain.iter().copied().zip(values.iter().copied()).collect();
+        Polynomial::interpolate(&points).expect("roots of unity are distinct")
+    }
+
+    // 五个选择子多项式 qL(X) .. qC(X)：只依赖电路，可以预处理 (PLONK 的"preprocessing")
+    pub fn selector_polys(&self) -> [(&'static str, Polynomial<P>); 5] {
+        let column = |f: fn(&PlonkRow<P>) -> Fp<P>| self.interpolate(&self.rows.iter().map(f).collect::<Vec<_>>());
+        [
+            ("qL", column(|r| r.q_l)),
+            ("qR", column(|r| r.q_r)),
+            ("qM", column(|r| r.q_m)),
+            ("qO", column(|r| r.q_o)),
+            ("qC", column(|r| r.q_c)),
+        ]
+    }
+
+    // 多项式版的门检查：拼出 G(X)，依次除以 (X - ω^i)。
+    // 任何一步有余数 ⇔ 某一行门方程不成立 ⇒ None；否则返回商 t(X) = G(X) / Z_H(X)
+    pub fn gate_quotient(&self, columns: &[Vec<Fp<P>>; 3], pi: &[Fp<P>]) -> Option<Polynomial<P>> {
+        let [q_l, q_r, q_m, q_o, q_c] = self.selector_polys().map(|(_, poly)| poly);
+        let [a, b, c] = columns.each_ref().map(|col| self.interpolate(col));
+        let mut g = &(&q_l * &a) + &(&q_r * &b);
+        g = &g + &(&(&q_m * &a) * &b);
+        g = &g + &(&q_o * &c);
+        g = &g + &q_c;
+        g = &g + &self.interpolate(pi);
+        self.domain.iter().try_fold(g, |acc, &root| {
+            let (quotient, remainder) = acc.divide_by_linear(root);
+            remainder.is_zero().then_some(quotient)
+        })
+    }
+
+    // 直接检查复制约束：返回第一对 (格子, σ(格子)) 取值不同的
+    pub fn first_broken_copy(&self, columns: &[Vec<Fp<P>>; 3]) -> Option<(usize, usize)> {
+        let n = self.size();
+        let value = |cell: usize| columns[cell / n][cell % n];
+        (0..3 * n).map(|cell| (cell, self.sigma[cell])).find(|&(cell, next)| value(cell) != value(next))
+    }
+
+    fn label(&self, cell: usize) -> Fp<P> {
+        self.shifts[cell / self.size()] * self.domain[cell % self.size()]
+    }
+
+    // 置换论证的累乘多项式 Z 在 ω^0 .. ω^n 上的取值 (n + 1 个，首尾都应是 1)：
+    //   Z(ω^0) = 1
+    //   Z(ω^(i+1)) = Z(ω^i) * Π_j (w_j(i) + β·k_j·ω^i + γ) / (w_j(i) + β·σ(j, i) + γ)
+    // 复制约束全部成立时，分子和分母是同一个多重集 {(值, 标签)} 换了个顺序，乘完一圈回到 1。
+    // 某个分母恰好为 0 时返回 None，调用者换一组 β、γ 重来
+    pub fn grand_product(&self, columns: &[Vec<Fp<P>>; 3], beta: Fp<P>, gamma: Fp<P>) -> Option<Vec<Fp<P>>> {
+        let n = self.size();
+        let mut z = vec![Fp::one()];
+        for i in 0..n {
+            let (mut num, mut den) = (Fp::one(), Fp::one());
+            for (j, column) in columns.iter().enumerate() {
+                let cell = j * n + i;
+                num = num * (column[i] + beta * self.label(cell) + gamma);
+                den = den * (column[i] + beta * self.label(self.sigma[cell]) + gamma);
+            }
+            if den.is_zero() {
+                return None;
+            }
+            z.push(z[i] * num / den);
+        }
+        Some(z)
+    }
+}
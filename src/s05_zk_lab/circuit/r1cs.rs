@@ -0,0 +1,74 @@
+// src/s05_zk_lab/circuit/r1cs.rs
+use crate::s05_zk_lab::math::field::Fp;
+
+/*
+R1CS (Rank-1 Constraint System，秩 1 约束系统)
+
+    变量向量 z = [1, w1, w2, ...]  (第 0 个永远是常数 1)
+    每条约束：<A_i, z> * <B_i, z> = <C_i, z>
+        <A_i, z> 是 z 的一个线性组合 (带系数的加权和)
+
+"秩 1"指的是：每条约束里只允许【一次】乘法 —— 左边一个线性组合乘以另一个线性组合。
+Groth16、Spartan 等证明系统的输入就是这种格式；电路 DSL 只是让人不必手写它。
+
+稀疏存储：线性组合只记录非零项 (变量下标, 系数)，而不是整行矩阵。
+*/
+
+pub type LinearCombination<const P: u64> = Vec<(usize, Fp<P>)>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint<const P: u64> {
+    pub a: LinearCombination<P>,
+    pub b: LinearCombination<P>,
+    pub c: LinearCombination<P>,
+}
+
+#[derive(Debug, Clone)]
+pub struct R1cs<const P: u64> {
+    pub num_vars: usize,
+    // 公开变量的下标 (验证者知道它们的值)，其余都是见证
+    pub public: Vec<usize>,
+    pub constraints: Vec<Constraint<P>>,
+}
+
+pub fn eval_lc<const P: u64>(lc: &LinearCombination<P>, z: &[Fp<P>]) -> Fp<P> {
+    lc.iter().fold(Fp::zero(), |acc, &(var, coeff)| acc + coeff * z[var])
+}
+
+impl<const P: u64> R1cs<P> {
+    // 检查赋值 z 是否满足全部约束，返回第一条不满足的约束编号
+    // z 的长度不对时直接返回 Some(0)：连"代进去"都做不到
+    pub fn first_unsatisfied(&self, z: &[Fp<P>]) -> Option<usize> {
+        if z.len() != self.num_vars || z[0] != Fp::one() {
+            return Some(0);
+        }
+        self.constraints
+            .iter()
+            .position(|con| eval_lc(&con.a, z) * eval_lc(&con.b, z) != eval_lc(&con.c, z))
+    }
+
+    pub fn is_satisfied(&self, z: &[Fp<P>]) -> bool {
+        self.first_unsatisfied(z).is_none()
+    }
+
+    // 把一条约束格式化成 (w1 + w2) * (1) = (w3)，name 负责把下标翻译成名字
+    pub fn format_constraint(&self, index: usize, name: impl Fn(usize) -> String) -> String {
+        let fmt_lc = |lc: &LinearCombination<P>| {
+            if lc.is_empty() {
+                return String::from("0");
+            }
+            let terms: Vec<String> = lc
+                .iter()
+                .map(|&(var, coeff)| match coeff.value() {
+                    1 => name(var),
+                    v if v == P - 1 => format!("-{}", name(var)),
+                    _ if var == 0 => coeff.to_string(),
+                    _ => format!("{}·{}", coeff, name(var)),
+                })
+                .collect();
+            terms.join(" + ").replace("+ -", "- ")
+        };
+        let con = &self.constraints[index];
+        format!("({}) * ({}) = ({})", fmt_lc(&con.a), fmt_lc(&con.b), fmt_lc(&con.c))
+    }
+}
@@ -0,0 +1,146 @@
+// src/s05_zk_lab/circuit/witness.rs
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use crate::s05_zk_lab::math::field::Fp;
+
+use super::{Circuit, Gate, Wire};
+
+/*
+见证生成 (Witness Generation)：输入赋值 -> 全部导线的值 z
+
+Circuit::evaluate 要求调用者按声明顺序排好输入，并且假设 gates 已经是拓扑序。
+这里换一种更健壮的做法：
+    - 输入按名字赋值 ("x" = 3)，顺序无所谓，缺了、多了、给了两个不同的值都会报错
+    - 门按依赖关系做拓扑求值 (Kahn 算法)：一个门的输入导线都算出来了，它才进入就绪队列
+      不依赖 gates 的存储顺序，哪怕门是乱序加进来的也能算
+
+产物 Witness::values 就是 R1cs::is_satisfied 要吃的 z 向量。
+*/
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WitnessError {
+    // 电路声明了这个输入，但赋值里没有
+    MissingInput(String),
+    // 赋值里有，但电路里没有这个名字 (多半是拼写错误)
+    UnknownInput(String),
+    // 同一个输入被赋了两个不同的值
+    ConflictingInput { name: String, first: u64, second: u64 },
+    // 有门的输入永远算不出来 (依赖了不存在或成环的导线)
+    Unreachable { gate: usize },
+}
+
+impl fmt::Display for WitnessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WitnessError::MissingInput(name) => write!(f, "缺少输入 `{}`", name),
+            WitnessError::UnknownInput(name) => write!(f, "电路中没有名为 `{}` 的输入", name),
+            WitnessError::ConflictingInput { name, first, second } => {
+                write!(f, "输入 `{}` 被赋值了两次且不一致: {} vs {}", name, first, second)
+            }
+            WitnessError::Unreachable { gate } => write!(f, "门 #{} 的输入永远无法求值", gate),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Witness<const P: u64> {
+    // 全部导线的值，下标就是 Wire 编号
+    pub values: Vec<Fp<P>>,
+    // 门的实际求值顺序 (gates 的下标)，方便观察拓扑排序
+    pub order: Vec<usize>,
+}
+
+// 门读哪些导线、写哪根导线
+fn gate_io<const P: u64>(gate: &Gate<P>) -> (Vec<Wire>, Option<Wire>) {
+    match *gate {
+        Gate::Const { out, .. } => (vec![], Some(out)),
+        Gate::Add { left, right, out } | Gate::Mul { left, right, out } => (vec![left, right], Some(out)),
+        Gate::AssertEq { left, right } => (vec![left, right], None),
+    }
+}
+
+pub fn generate_witness<const P: u64>(
+    circuit: &Circuit<P>,
+    assignments: &[(&str, Fp<P>)],
+) -> Result<Witness<P>, WitnessError> {
+    // 1. 整理赋值：检查冲突和未知名字
+    let mut assigned: HashMap<&str, Fp<P>> = HashMap::new();
+    for &(name, value) in assignments {
+        if !circuit.inputs().iter().any(|input| input.name == name) {
+            return Err(WitnessError::UnknownInput(name.to_string()));
+        }
+        if let Some(&first) = assigned.get(name) {
+            if first != value {
+                return Err(WitnessError::ConflictingInput {
+                    name: name.to_string(),
+                    first: first.value(),
+                    second: value.value(),
+                });
+            }
+        }
+        assigned.insert(name, value);
+    }
+
+    // 2. 填入 ONE 和全部输入
+    let mut values = vec![Fp::zero(); circuit.num_wires()];
+    let mut known = vec![false; circuit.num_wires()];
+    values[Wire::ONE.0] = Fp::one();
+    known[Wire::ONE.0] = true;
+    for input in circuit.inputs() {
+        let value = assigned
+            .get(input.name.as_str())
+            .ok_or_else(|| WitnessError::MissingInput(input.name.clone()))?;
+        values[input.wire.0] = *value;
+        known[input.wire.0] = true;
+    }
+
+    // 3. Kahn 拓扑求值
+    //    pending[g] = 门 g 还有几个输入没算出来；waiting[w] = 哪些门在等导线 w
+    let gates = circuit.gates();
+    let mut pending = vec![0usize; gates.len()];
+    let mut waiting: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut ready = VecDeque::new();
+    for (g, gate) in gates.iter().enumerate() {
+        let (reads, _) = gate_io(gate);
+        for wire in reads {
+            if wire.0 >= values.len() {
+                return Err(WitnessError::Unreachable { gate: g });
+            }
+            if !known[wire.0] {
+                pending[g] += 1;
+                waiting.entry(wire.0).or_default().push(g);
+            }
+        }
+        if pending[g] == 0 {
+            ready.push_back(g);
+        }
+    }
+
+    let mut order = Vec::with_capacity(gates.len());
+    while let Some(g) = ready.pop_front() {
+        order.push(g);
+        let value = match gates[g] {
+            Gate::Const { value, .. } => value,
+            Gate::Add { left, right, .. } => values[left.0] + values[right.0],
+            Gate::Mul { left, right, .. } => values[left.0] * values[right.0],
+            Gate::AssertEq { .. } => continue, // 只读不写，R1CS 负责检查
+        };
+        let out = gate_io(&gates[g]).1.expect("non-assert gates have an output");
+        values[out.0] = value;
+        known[out.0] = true;
+        // 唤醒等这根导线的门 (一个门可能两个输入是同一根导线，所以按出现次数递减)
+        for next in waiting.remove(&out.0).unwrap_or_default() {
+            pending[next] -= 1;
+            if pending[next] == 0 {
+                ready.push_back(next);
+            }
+        }
+    }
+
+    // 4. 还有门没算到：它的某个输入永远不会有值
+    if let Some(g) = (0..gates.len()).find(|g| !order.contains(g)) {
+        return Err(WitnessError::Unreachable { gate: g });
+    }
+    Ok(Witness { values, order })
+}
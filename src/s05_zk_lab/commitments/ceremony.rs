@@ -0,0 +1,79 @@
+// src/s05_zk_lab/commitments/ceremony.rs
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::crypto::schnorr::{self, Keypair, Signature};
+use crate::s05_zk_lab::math::group::{GroupElement, Scalar};
+
+use super::kzg::{mock_pairing, Srs};
+
+/*
+Powers of Tau 多方仪式 (Zcash Sapling、以太坊 KZG Ceremony 都是这个结构)
+
+    起点：τ = 1 的 SRS，也就是 [g, g, ..., g] —— 人人都知道，完全不安全
+    第 i 棒：参与者选秘密 s_i，把 SRS 更新为 τ * s_i (Srs::contribute)，然后销毁 s_i
+    终点：τ = s_1 * s_2 * ... * s_n
+
+    想知道 τ，必须集齐【所有】 s_i —— 只要有一个人真的销毁了自己那份，τ 就没人知道 (1-of-n 信任)。
+
+协调者对每一棒的检查 (verify_contribution)：
+    1. g^s != 1              —— s = 0 会把整个 SRS 清成单位元，之前所有人的贡献作废
+    2. 知道 s               —— 用 s 作私钥对新 SRS 签 Schnorr 签名 (知识证明)
+    3. 新 τ = 旧 τ * s       —— e(g^τ', g) == e(g^τ, g^s)：不能丢掉前面的人、自己另起炉灶
+    4. 新 SRS 的结构正确     —— e(g^(τ^(i+1)), g) == e(g^(τ^i), g^τ)：每一项都是前一项再乘一个 τ
+
+⚠️ 配对用的是 kzg.rs 里的 mock_pairing (暴力离散对数)，只有玩具群才跑得动。
+*/
+
+pub struct Contribution {
+    pub participant: String,
+    // g^s：公开的"这一棒乘了多少"，但看不出 s 本身
+    pub public: GroupElement,
+    pub signature: Signature,
+    pub srs: Srs,
+}
+
+fn srs_bytes(srs: &Srs) -> Vec<u8> {
+    srs.powers.iter().flat_map(|p| p.value().to_be_bytes()).collect()
+}
+
+impl Contribution {
+    // 在 base 的基础上贡献 secret；调用者之后应当丢掉 secret
+    pub fn new(participant: &str, base: &Srs, secret: Scalar, rng: &mut SimpleRng) -> Self {
+        let keypair: Keypair = Keypair::from_secret(secret);
+        let srs = base.contribute(secret);
+        let signature = schnorr::sign(&keypair, &srs_bytes(&srs), rng);
+        Contribution { participant: participant.to_string(), public: keypair.public, signature, srs }
+    }
+}
+
+pub fn verify_contribution(previous: &Srs, contribution: &Contribution) -> Result<(), String> {
+    let g = GroupElement::generator();
+    let powers = &contribution.srs.powers;
+    if powers.len() != previous.powers.len() || powers[0] != g {
+        return Err(String::from("SRS 的长度或第 0 项被篡改"));
+    }
+    if contribution.public == GroupElement::identity() {
+        return Err(String::from("g^s = 1：s = 0 会清空之前所有人的贡献"));
+    }
+    if !schnorr::verify(contribution.public, &srs_bytes(&contribution.srs), &contribution.signature) {
+        return Err(String::from("知识证明 (Schnorr 签名) 无效"));
+    }
+    if mock_pairing(powers[1], g) != mock_pairing(previous.powers[1], contribution.public) {
+        return Err(String::from("e(g^τ', g) != e(g^τ, g^s)：新 τ 不是在上一棒的基础上乘出来的"));
+    }
+    for i in 1..powers.len() - 1 {
+        if mock_pairing(powers[i + 1], g) != mock_pairing(powers[i], powers[1]) {
+            return Err(format!("第 {} 项不等于第 {} 项再乘 τ", i + 1, i));
+        }
+    }
+    Ok(())
+}
+
+// 任何人都可以事后重放整个仪式：从公开的起点出发，逐棒检查
+pub fn verify_ceremony(initial: &Srs, contributions: &[Contribution]) -> Result<(), String> {
+    let mut previous = initial;
+    for contribution in contributions {
+        verify_contribution(previous, contribution).map_err(|e| format!("{}: {}", contribution.participant, e))?;
+        previous = &contribution.srs;
+    }
+    Ok(())
+}
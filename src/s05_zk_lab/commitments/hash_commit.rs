@@ -0,0 +1,46 @@
+// src/s05_zk_lab/commitments/hash_commit.rs
+use std::fmt;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::{sha256, short_hex, Digest};
+
+/*
+哈希承诺：C = SHA-256(nonce || value)
+
+    隐藏性：nonce 是 32 字节随机数，看到 C 也没法穷举 value —— 哪怕 value 只有 0 / 1 两种可能。
+            (去掉 nonce，C = H(value) 对小取值空间毫无隐藏性，见 ex21)
+    绑定性：想换一个 value' 打开同一个 C，就要找到 H 的碰撞。
+
+与 Pedersen (pedersen.rs) 对比：
+    哈希承诺只需要哈希函数，简单、快；但它没有同态性，也不方便在 ZK 证明里使用。
+*/
+
+pub type Nonce = [u8; 32];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashCommitment(pub Digest);
+
+pub fn random_nonce(rng: &mut SimpleRng) -> Nonce {
+    let mut nonce = [0u8; 32];
+    for chunk in nonce.chunks_mut(8) {
+        chunk.copy_from_slice(&rng.next_u64().to_be_bytes());
+    }
+    nonce
+}
+
+// nonce 是定长的放在前面，不会出现 "ab" || "c" 和 "a" || "bc" 的拼接歧义
+pub fn commit(value: &[u8], nonce: &Nonce) -> HashCommitment {
+    let mut data = nonce.to_vec();
+    data.extend_from_slice(value);
+    HashCommitment(sha256(&data))
+}
+
+pub fn verify(commitment: &HashCommitment, value: &[u8], nonce: &Nonce) -> bool {
+    commit(value, nonce) == *commitment
+}
+
+impl fmt::Display for HashCommitment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..", short_hex(&self.0))
+    }
+}
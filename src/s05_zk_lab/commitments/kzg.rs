@@ -0,0 +1,116 @@
+// src/s05_zk_lab/commitments/kzg.rs
+use crate::s05_zk_lab::math::group::{GroupElement, Scalar, GROUP_ORDER};
+use crate::s05_zk_lab::math::poly::Polynomial;
+
+/*
+KZG 多项式承诺 (Kate-Zaverucha-Goldberg, 2010)：用【一个】群元素承诺一整个多项式，
+之后可以在任意点 z 打开，证明 p(z) = y，证明也只有【一个】群元素。
+
+    可信设置 (Trusted Setup)：随机选 τ，公布 SRS = [g, g^τ, g^(τ^2), ..., g^(τ^d)]，然后销毁 τ
+    承诺 (Commit)           ：C = Π (g^(τ^i))^(c_i) = g^p(τ)     (不知道 τ 也能算)
+    打开 (Open)             ：y = p(z)，商多项式 q(x) = (p(x) - y) / (x - z)，证明 π = g^q(τ)
+    验证 (Verify)           ：检查 p(τ) - y == q(τ) * (τ - z)，但验证者不知道 τ ……
+                              真实 KZG 用双线性配对：e(C / g^y, g) == e(π, g^τ / g^z)
+
+==================== 哪些是模拟的 (Mocked) ====================
+    ✅ 真实的：SRS 的结构、commit/open 的全部计算、商多项式、验证方程的形状
+    ❌ 模拟的：配对 e(·,·)。
+        真实配对是椭圆曲线上的一个双线性映射 e: G1 × G2 -> GT，满足 e(g^a, g^b) = e(g, g)^(ab)，
+        计算它【不需要】知道 a 和 b。
+        我们的玩具群没有配对，于是 mock_pairing 直接暴力求出 a、b 的离散对数再相乘 ——
+        这只在 q = 1019 时可行，而且等于承认"离散对数在这里不难"，所以它只能用来演示验证方程，
+        不提供任何安全性。
+==============================================================
+*/
+
+// 系数活在标量域 F_q 里的多项式 (q 是群的阶，因为 g^p(τ) 的指数只需 mod q)
+pub type ScalarPoly = Polynomial<GROUP_ORDER>;
+
+#[derive(Clone)]
+pub struct Srs {
+    // [g^(τ^0), g^(τ^1), ..., g^(τ^d)]
+    pub powers: Vec<GroupElement>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KzgCommitment(pub GroupElement);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KzgProof(pub GroupElement);
+
+impl Srs {
+    // 可信设置：调用者传入 τ 之后应当立刻把它"忘掉" (有毒废料 Toxic Waste)
+    pub fn setup(max_degree: usize, tau: Scalar) -> Self {
+        let g = GroupElement::generator();
+        let mut powers = Vec::with_capacity(max_degree + 1);
+        let mut tau_i = Scalar::one();
+        for _ in 0..=max_degree {
+            powers.push(g.pow(tau_i));
+            tau_i = tau_i * tau;
+        }
+        Srs { powers }
+    }
+
+    // 多方仪式中的一棒 (见 ceremony.rs)：把 SRS 里的 τ 换成 τ * s，全程不需要知道 τ
+    // (g^(τ^i))^(s^i) = g^((τ * s)^i)
+    pub fn contribute(&self, secret: Scalar) -> Srs {
+        let mut s_i = Scalar::one();
+        let powers = self
+            .powers
+            .iter()
+            .map(|&p| {
+                let updated = p.pow(s_i);
+                s_i = s_i * secret;
+                updated
+            })
+            .collect();
+        Srs { powers }
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.powers.len() - 1
+    }
+
+    // g^p(τ)：用 SRS 做"指数上的多项式求值"，全程不碰 τ
+    fn eval_in_exponent(&self, poly: &ScalarPoly) -> Option<GroupElement> {
+        if poly.coeffs().len() > self.powers.len() {
+            return None; // 次数超过 SRS 的支持范围
+        }
+        Some(
+            poly.coeffs()
+                .iter()
+                .zip(&self.powers)
+                .fold(GroupElement::identity(), |acc, (&c, &p)| acc * p.pow(c)),
+        )
+    }
+
+    pub fn commit(&self, poly: &ScalarPoly) -> Option<KzgCommitment> {
+        self.eval_in_exponent(poly).map(KzgCommitment)
+    }
+
+    // 打开：返回 (y = p(z), π = g^q(τ))
+    pub fn open(&self, poly: &ScalarPoly, z: Scalar) -> Option<(Scalar, KzgProof)> {
+        // p(x) - y 在 z 处为 0，所以能被 (x - z) 整除，余数就是 y
+        let (quotient, y) = poly.divide_by_linear(z);
+        self.eval_in_exponent(&quotient).map(|pi| (y, KzgProof(pi)))
+    }
+
+    // 验证 e(C / g^y, g) == e(π, g^τ / g^z)
+    // 只用到 SRS 的前两项 g 和 g^τ：验证者的成本与多项式的次数无关
+    pub fn verify(&self, commitment: &KzgCommitment, z: Scalar, y: Scalar, proof: &KzgProof) -> bool {
+        let g = self.powers[0];
+        let g_tau = self.powers[1];
+        let lhs = mock_pairing(commitment.0 * g.pow(y).inverse(), g);
+        let rhs = mock_pairing(proof.0, g_tau * g.pow(z).inverse());
+        lhs == rhs
+    }
+}
+
+// ❌ 模拟的配对：e(g^a, g^b) = "gT^(ab)"，用指数 ab 代表目标群元素
+// 靠暴力离散对数实现，真实系统里这一步由椭圆曲线上的 Miller 循环完成，不需要知道 a、b
+pub fn mock_pairing(left: GroupElement, right: GroupElement) -> Scalar {
+    let g = GroupElement::generator();
+    let a = left.brute_force_log(g).expect("element lies in the subgroup");
+    let b = right.brute_force_log(g).expect("element lies in the subgroup");
+    a * b
+}
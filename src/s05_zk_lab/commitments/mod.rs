@@ -0,0 +1,9 @@
+// src/s05_zk_lab/commitments/mod.rs
+// 承诺方案 (Commitment Schemes)：先"封进信封"，之后再"拆开验证"
+
+pub mod ceremony;
+pub mod hash_commit;
+pub mod kzg;
+pub mod pedersen;
+pub mod rsa_accumulator;
+pub mod verkle;
@@ -0,0 +1,60 @@
+// src/s05_zk_lab/commitments/pedersen.rs
+use std::ops::Mul;
+
+use crate::s05_zk_lab::math::group::{Group, GroupElement, Scalar};
+
+/*
+Pedersen 承诺：C = g^v * h^r
+
+    v：要承诺的值 (value)
+    r：随机盲因子 (blinding factor)，承诺者自己保密
+    g, h：两个公开的生成元，关键要求是【谁都不知道 log_g(h)】
+
+两个性质：
+    隐藏性 (Hiding)：看到 C 猜不出 v。
+        r 是均匀随机的，h^r 就是均匀随机的群元素，把 g^v 完全"淹没"了。
+        这是【完美】隐藏：哪怕算力无限，C 对每个 v 都有可能 (见练习里的陷门演示)。
+    绑定性 (Binding)：承诺后不能换一个值打开。
+        如果能找到 (v, r) ≠ (v', r') 打开同一个 C，就能算出 log_g(h) = (v - v') / (r' - r)。
+        所以绑定性 = 离散对数问题的困难性 (【计算】绑定)。
+
+群 G 是泛型参数 (默认 group.rs 的 mod p 乘法群)，换成 math::curve::CurvePoint 就是椭圆曲线上的 Pedersen 承诺。
+*/
+
+#[derive(Debug, Clone, Copy)]
+pub struct PedersenParams<G = GroupElement> {
+    pub g: G,
+    pub h: G,
+}
+
+// 承诺本身就是一个群元素，包一层 newtype 防止和普通群元素混用
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Commitment<G = GroupElement>(pub G);
+
+impl<G: Group> PedersenParams<G> {
+    // h 由公开字符串哈希得到，没有人"挑选"过它，也就没人知道 log_g(h)
+    pub fn setup() -> Self {
+        PedersenParams {
+            g: G::generator(),
+            h: G::hash_to_group("rust-zk-lab/pedersen/h"),
+        }
+    }
+
+    pub fn commit(&self, value: Scalar, blinding: Scalar) -> Commitment<G> {
+        Commitment(self.g.pow(value).op(self.h.pow(blinding)))
+    }
+
+    // 打开 (Open)：承诺者公布 (v, r)，验证者重新计算一遍对比
+    pub fn verify(&self, commitment: &Commitment<G>, value: Scalar, blinding: Scalar) -> bool {
+        self.commit(value, blinding) == *commitment
+    }
+}
+
+// 加法同态：C(v1, r1) * C(v2, r2) = g^(v1+v2) * h^(r1+r2) = C(v1+v2, r1+r2)
+// 不打开承诺，就能对里面的值做加法 —— 机密交易 (Confidential Tx) 的核心
+impl<G: Group> Mul for Commitment<G> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Commitment(self.0.op(rhs.0))
+    }
+}
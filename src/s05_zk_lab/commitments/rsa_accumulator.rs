@@ -0,0 +1,168 @@
+// src/s05_zk_lab/commitments/rsa_accumulator.rs
+use crate::s05_zk_lab::hash::sha256;
+use crate::s05_zk_lab::math::field::ext_gcd;
+
+/*
+RSA 累加器 (Benaloh-de Mare 1993, Camenisch-Lysyanskaya 2002)：把一个集合压成一个数
+
+    公共参数：N = p * q (p、q 在 setup 之后销毁)，生成元 g
+    累加值：  A = g^(x_1 * x_2 * ... * x_n) mod N
+    成员证明：x 的见证 w = g^(除 x 以外所有元素之积)，验证 w^x == A
+    —— 不管集合有多大，A 和 w 都只是一个 mod N 的数 (常数大小)
+
+为什么元素必须是素数？
+    如果集合里有 6，那么 w_6^3 就是 "2 的见证"：(w_6^3)^2 = w_6^6 = A。
+    所以数据先经过 hash_to_prime 映射成素数，任何一个素数都不可能整除其他素数的乘积。
+
+为什么 p、q 必须销毁？
+    知道 φ(N) = (p-1)(q-1) 就能算 x 模 φ(N) 的逆，直接对任意 x 开 x 次方根：w = A^(1/x)，
+    不在集合里的元素也能"证明"成员身份 —— 和 KZG 的 τ 一样是有毒废料 (toxic waste)。
+
+⚠️ 玩具化的地方：
+    N 只有 60 位 (真实系统 2048 位)，分解它是瞬间的事；素数代表只有 32 位。
+*/
+
+// 两个常见的 30 位素数，N ≈ 10^18 < 2^63，乘法用 u128 防溢出
+const P: u64 = 1_000_000_007;
+const Q: u64 = 998_244_353;
+const GENERATOR: u64 = 3;
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+// Miller-Rabin：用前 12 个素数做底，对所有 u64 都是确定性的
+fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(&b) = BASES.iter().find(|&&b| n.is_multiple_of(b)) {
+        return n == b;
+    }
+    let (mut d, mut s) = (n - 1, 0);
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+    BASES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+// 数据 -> 32 位素数：取哈希的前 4 字节，置最高位，向上找第一个素数
+pub fn hash_to_prime(data: &str) -> u64 {
+    let digest = sha256(data.as_bytes());
+    let mut candidate = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) as u64 | (1 << 31) | 1;
+    while !is_prime(candidate) {
+        candidate += 2;
+    }
+    candidate
+}
+
+// 验证者只需要 N 和当前的累加值 A
+pub fn verify(modulus: u64, accumulator: u64, data: &str, witness: u64) -> bool {
+    pow_mod(witness, hash_to_prime(data), modulus) == accumulator
+}
+
+// 集合里加入新元素 y 之后，旧见证也要跟着升一次幂：w' = w^y
+// 持有者只需要知道 y，不需要知道集合里的其他元素
+pub fn update_witness(modulus: u64, witness: u64, added: &str) -> u64 {
+    pow_mod(witness, hash_to_prime(added), modulus)
+}
+
+pub struct RsaAccumulator {
+    pub modulus: u64,
+    pub generator: u64,
+    pub value: u64,
+    // 证明者保存全集 (素数代表) 才能生成见证；验证者不需要
+    primes: Vec<u64>,
+}
+
+impl RsaAccumulator {
+    pub fn new() -> Self {
+        RsaAccumulator { modulus: P * Q, generator: GENERATOR, value: GENERATOR, primes: Vec::new() }
+    }
+
+    // 加入一个元素：A <- A^x，一次模幂，和集合大小无关
+    pub fn add(&mut self, data: &str) -> u64 {
+        let x = hash_to_prime(data);
+        self.value = pow_mod(self.value, x, self.modulus);
+        self.primes.push(x);
+        x
+    }
+
+    // 见证 = g 依次对其他所有元素做模幂：O(n) 次模幂 (Merkle 证明是 O(log n) 次查表)
+    pub fn witness(&self, data: &str) -> Option<u64> {
+        let x = hash_to_prime(data);
+        let position = self.primes.iter().position(|&p| p == x)?;
+        let witness = self
+            .primes
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != position)
+            .fold(self.generator, |w, (_, &p)| pow_mod(w, p, self.modulus));
+        Some(witness)
+    }
+}
+
+impl Default for RsaAccumulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ==========================================
+// ❌ 陷门：如果 setup 的人偷偷留下了 p、q
+// ==========================================
+// w = A^(x^-1 mod φ(N))，则 w^x = A^(x * x^-1) = A (欧拉定理)
+pub fn forge_witness_with_trapdoor(accumulator: u64, data: &str) -> Option<u64> {
+    let phi = ((P - 1) as i128) * ((Q - 1) as i128);
+    let x = hash_to_prime(data) as i128;
+    let (g, inverse, _) = ext_gcd(x, phi);
+    if g != 1 {
+        return None;
+    }
+    Some(pow_mod(accumulator, inverse.rem_euclid(phi) as u64, P * Q))
+}
+
+// ❌ 不映射成素数、直接把整数当元素时的伪造 (见文件开头)：
+// 已知 composite 的见证，对它的任意因子 d 伪造见证 w^(composite / d)
+pub fn forge_divisor_witness(modulus: u64, composite_witness: u64, composite: u64, divisor: u64) -> Option<u64> {
+    if divisor <= 1 || !composite.is_multiple_of(divisor) {
+        return None;
+    }
+    Some(pow_mod(composite_witness, composite / divisor, modulus))
+}
+
+// 原始整数版本的累加 (没有 hash_to_prime)，只给上面的攻击演示用
+pub fn accumulate_raw(modulus: u64, generator: u64, elements: &[u64]) -> u64 {
+    elements.iter().fold(generator, |acc, &e| pow_mod(acc, e, modulus))
+}
+
+pub fn verify_raw(modulus: u64, accumulator: u64, element: u64, witness: u64) -> bool {
+    pow_mod(witness, element, modulus) == accumulator
+}
@@ -0,0 +1,157 @@
+// src/s05_zk_lab/commitments/verkle.rs
+use super::kzg::{KzgCommitment, KzgProof, ScalarPoly, Srs};
+use crate::s05_zk_lab::hash::sha256;
+use crate::s05_zk_lab::math::group::Scalar;
+
+/*
+Verkle 树 (Vector commitment + Merkle)：以太坊无状态客户端的候选方案
+
+    Merkle 树：父节点 = H(左孩子 || 右孩子)，证明要带上每一层的【所有兄弟】
+    Verkle 树：父节点 = VC(孩子 0, 孩子 1, ..., 孩子 k-1)，证明每一层只需要【一个打开证明】
+
+这里的向量承诺 (VC) 用 KZG 实现：
+    把 k 个孩子的值看成多项式在 x = 0..k-1 上的取值，插值后做 KZG 承诺；
+    证明"第 i 个孩子是 v"就是在 x = i 处打开。
+    孩子本身是一个承诺 (群元素)，先哈希成标量，才能作为父节点多项式的取值。
+
+宽度 k 越大，树越矮，证明越短 —— 而 Merkle 树加宽反而让证明变长 (每层要带 k-1 个兄弟)。
+
+⚠️ 玩具化的地方：
+    1. 群只有 1019 个元素，"承诺哈希成标量"很容易碰撞，没有任何安全性
+    2. 真实的 Verkle 树 (EIP-6800) 把所有层的打开聚合成【一个】多重证明，这里逐层单独打开
+*/
+
+// 叶子数据 -> 标量
+pub fn leaf_to_scalar(data: &str) -> Scalar {
+    bytes_to_scalar(data.as_bytes())
+}
+
+// 子节点承诺 -> 标量 (父节点多项式的取值)
+fn commitment_to_scalar(commitment: &KzgCommitment) -> Scalar {
+    bytes_to_scalar(&commitment.0.value().to_be_bytes())
+}
+
+fn bytes_to_scalar(data: &[u8]) -> Scalar {
+    let digest = sha256(data);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    Scalar::new(u64::from_be_bytes(bytes))
+}
+
+struct VerkleNode {
+    poly: ScalarPoly,
+    commitment: KzgCommitment,
+}
+
+pub struct VerkleTree {
+    width: usize,
+    srs: Srs,
+    // levels[0] 是最底层的内部节点，最后一层只有根
+    levels: Vec<Vec<VerkleNode>>,
+    pub leaves: Vec<String>,
+}
+
+// 自底向上每层一步：该层节点的承诺 (最顶层是根，不用发送)、孩子的值和打开证明
+#[derive(Debug, Clone)]
+pub struct VerkleStep {
+    pub commitment: KzgCommitment,
+    pub value: Scalar,
+    pub opening: KzgProof,
+}
+
+#[derive(Debug, Clone)]
+pub struct VerkleProof {
+    pub index: usize,
+    pub steps: Vec<VerkleStep>,
+}
+
+impl VerkleTree {
+    // tau 只在 SRS 里用一次，调用者之后应当忘掉它 (见 kzg.rs)
+    pub fn new(data: Vec<String>, width: usize, tau: Scalar) -> Self {
+        assert!(width >= 2, "width must be at least 2");
+        let srs = Srs::setup(width - 1, tau);
+        let domain: Vec<Scalar> = (0..width as u64).map(Scalar::new).collect();
+
+        let mut levels = Vec::new();
+        let mut values: Vec<Scalar> = data.iter().map(|d| leaf_to_scalar(d)).collect();
+        while !values.is_empty() {
+            // 不满 width 的最后一组用 0 补齐
+            let nodes: Vec<VerkleNode> = values
+                .chunks(width)
+                .map(|chunk| {
+                    let points: Vec<(Scalar, Scalar)> = domain
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &x)| (x, chunk.get(i).copied().unwrap_or(Scalar::zero())))
+                        .collect();
+                    let poly = ScalarPoly::interpolate(&points).expect("domain points are distinct");
+                    let commitment = srs.commit(&poly).expect("degree < width");
+                    VerkleNode { poly, commitment }
+                })
+                .collect();
+            let done = nodes.len() == 1;
+            values = nodes.iter().map(|n| commitment_to_scalar(&n.commitment)).collect();
+            levels.push(nodes);
+            if done {
+                break;
+            }
+        }
+        VerkleTree { width, srs, levels, leaves: data }
+    }
+
+    pub fn srs(&self) -> &Srs {
+        &self.srs
+    }
+
+    pub fn depth(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn root(&self) -> Option<KzgCommitment> {
+        self.levels.last().map(|level| level[0].commitment)
+    }
+
+    pub fn prove(&self, index: usize) -> Option<VerkleProof> {
+        if index >= self.leaves.len() {
+            return None;
+        }
+        let mut position = index;
+        let steps = self
+            .levels
+            .iter()
+            .map(|level| {
+                let node = &level[position / self.width];
+                let (value, opening) = self
+                    .srs
+                    .open(&node.poly, Scalar::new((position % self.width) as u64))
+                    .expect("degree < width");
+                position /= self.width;
+                VerkleStep { commitment: node.commitment, value, opening }
+            })
+            .collect();
+        Some(VerkleProof { index, steps })
+    }
+}
+
+impl VerkleProof {
+    // 证明里要传输的群元素个数：每层 1 个打开证明 + 除根以外每层 1 个承诺
+    pub fn group_elements(&self) -> usize {
+        2 * self.steps.len() - 1
+    }
+
+    pub fn verify(&self, leaf: &str, root: &KzgCommitment, srs: &Srs) -> bool {
+        let width = srs.max_degree() + 1;
+        let mut expected = leaf_to_scalar(leaf);
+        let mut position = self.index;
+        for step in &self.steps {
+            let x = Scalar::new((position % width) as u64);
+            if step.value != expected || !srs.verify(&step.commitment, x, step.value, &step.opening) {
+                return false;
+            }
+            expected = commitment_to_scalar(&step.commitment);
+            position /= width;
+        }
+        // 走到顶层时 position 必须归零，否则 index 超出了这棵树
+        position == 0 && self.steps.last().is_some_and(|top| top.commitment == *root)
+    }
+}
@@ -0,0 +1,6 @@
+// src/s05_zk_lab/crypto/mod.rs
+// 密码学原语：签名、身份认证……都建立在 math::group 的离散对数之上
+
+pub mod bls;
+pub mod schnorr;
+pub mod vrf;
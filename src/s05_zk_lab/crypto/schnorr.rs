@@ -0,0 +1,112 @@
+// src/s05_zk_lab/crypto/schnorr.rs
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::sha256;
+use crate::s05_zk_lab::math::group::{random_scalar, Group, GroupElement, Scalar};
+
+/*
+Schnorr 协议：证明"我知道 P = g^x 的离散对数 x"，却不泄露 x
+
+身份认证 (交互式，三步走)：
+    1. 承诺 (Commitment)：证明者随机选 k，发送 R = g^k
+    2. 挑战 (Challenge)  ：验证者随机选 c 发回去
+    3. 响应 (Response)   ：证明者发送 s = k + c * x
+    验证：g^s == R * P^c   (因为 g^(k + cx) = g^k * (g^x)^c)
+
+签名 (非交互式，Fiat-Shamir 变换)：
+    没有验证者来出挑战，就让哈希函数扮演验证者：c = H(R || P || msg)
+    签名 = (R, s)。任何人都能重算 c 并做同样的验证。
+
+和 Pedersen 一样对群 G 泛型 (默认 mod p 乘法群)；换成椭圆曲线就是 EC-Schnorr (比特币 Taproot 的 BIP-340)。
+*/
+
+#[derive(Debug, Clone, Copy)]
+pub struct Keypair<G = GroupElement> {
+    pub secret: Scalar,
+    pub public: G,
+}
+
+impl<G: Group> Keypair<G> {
+    // 私钥不能是 0 (公钥会变成单位元)，抽到 0 就重抽
+    pub fn generate(rng: &mut SimpleRng) -> Self {
+        loop {
+            let secret = random_scalar(rng);
+            if !secret.is_zero() {
+                return Self::from_secret(secret);
+            }
+        }
+    }
+
+    pub fn from_secret(secret: Scalar) -> Self {
+        Keypair {
+            secret,
+            public: G::generator().pow(secret),
+        }
+    }
+}
+
+// ==========================================
+// 交互式身份认证
+// ==========================================
+
+// 已经发出承诺、等待挑战的证明者
+// respond 接收 self (move)：一个 nonce 只能回答一次挑战，复用 nonce 在编译期就被禁止了
+pub struct SchnorrProver {
+    secret: Scalar,
+    nonce: Scalar,
+}
+
+impl SchnorrProver {
+    // 第 1 步：选随机 k，返回 (等待挑战的证明者, R = g^k)
+    pub fn commit<G: Group>(keypair: &Keypair<G>, rng: &mut SimpleRng) -> (Self, G) {
+        let nonce = random_scalar(rng);
+        let prover = SchnorrProver {
+            secret: keypair.secret,
+            nonce,
+        };
+        (prover, G::generator().pow(nonce))
+    }
+
+    // 第 3 步：s = k + c * x
+    pub fn respond(self, challenge: Scalar) -> Scalar {
+        self.nonce + challenge * self.secret
+    }
+}
+
+// 验证者检查：g^s == R * P^c
+pub fn verify_response<G: Group>(public: G, commitment: G, challenge: Scalar, response: Scalar) -> bool {
+    G::generator().pow(response) == commitment.op(public.pow(challenge))
+}
+
+// ==========================================
+// Schnorr 签名 (Fiat-Shamir)
+// ==========================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature<G = GroupElement> {
+    pub r: G,
+    pub s: Scalar,
+}
+
+// c = H(R || P || msg)，取前 8 字节再 mod q (Scalar::new 自动取模)
+// 把公钥 P 也放进哈希 (key prefixing)，防止同一个签名被"挪用"到另一把公钥上
+pub fn challenge_hash<G: Group>(r: G, public: G, msg: &[u8]) -> Scalar {
+    let mut data = Vec::with_capacity(16 + msg.len());
+    data.extend_from_slice(&r.encode().to_be_bytes());
+    data.extend_from_slice(&public.encode().to_be_bytes());
+    data.extend_from_slice(msg);
+    let digest = sha256(&data);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    Scalar::new(u64::from_be_bytes(bytes))
+}
+
+pub fn sign<G: Group>(keypair: &Keypair<G>, msg: &[u8], rng: &mut SimpleRng) -> Signature<G> {
+    let (prover, r) = SchnorrProver::commit(keypair, rng);
+    let c = challenge_hash(r, keypair.public, msg);
+    Signature { r, s: prover.respond(c) }
+}
+
+pub fn verify<G: Group>(public: G, msg: &[u8], sig: &Signature<G>) -> bool {
+    let c = challenge_hash(sig.r, public, msg);
+    verify_response(public, sig.r, c, sig.s)
+}
@@ -0,0 +1,101 @@
+// src/s05_zk_lab/crypto/vrf.rs
+use std::fmt;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::{sha256, short_hex, Digest};
+use crate::s05_zk_lab::math::group::{GroupElement, Scalar};
+use crate::s05_zk_lab::sigma::dlog::{DleqStatement, DlogEquality};
+use crate::s05_zk_lab::sigma::{Pair, SigmaProtocol};
+
+use super::schnorr::Keypair;
+
+/*
+可验证随机函数 (Verifiable Random Function, VRF)，结构同 RFC 9381 的 ECVRF
+
+    持有私钥 x 的人对任意输入 α 算出一个看起来随机的输出 β，并附带证明 π；
+    任何人拿公钥 Y = g^x 就能验证 "β 确实是 x 对 α 的输出"，但没有 x 就没法提前算出 β。
+
+    求值：
+        H = hash_to_group(Y, α)       输入先哈希到群里 (真实系统是 hash-to-curve)
+        Γ = H^x                       核心：对同一个 (x, α)，Γ 只有一个
+        β = SHA-256("vrf-output" || Γ)
+    证明：Γ 和 Y 用的是同一个 x —— 正是 sigma/dlog.rs 的 DLEQ：log_g(Y) == log_H(Γ)
+        用 Fiat-Shamir 把挑战换成 c = H(Y, H, Γ, a1, a2)，变成非交互
+
+和签名的区别：Schnorr 签名里有随机 nonce，同一条消息能签出无数个不同的合法签名；
+VRF 的输出只由 Γ 决定，而 Γ 由 (x, α) 唯一确定 —— 证明里的 nonce 变了，β 不变 (唯一性)。
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VrfOutput(pub Digest);
+
+impl VrfOutput {
+    // 映射到 [0, 1)：取前 8 字节当作 64 位定点小数
+    pub fn fraction(&self) -> f64 {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&self.0[..8]);
+        u64::from_be_bytes(bytes) as f64 / (u64::MAX as f64 + 1.0)
+    }
+}
+
+impl fmt::Display for VrfOutput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..", short_hex(&self.0))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VrfProof {
+    pub gamma: GroupElement,
+    pub commitment: Pair<GroupElement, GroupElement>,
+    pub response: Scalar,
+}
+
+// 输入绑定公钥：同一个 α 对不同的人映射到不同的 H
+pub fn hash_input(public: GroupElement, alpha: &str) -> GroupElement {
+    GroupElement::hash_to_group(&format!("rust-zk-lab/vrf/{}/{}", public, alpha))
+}
+
+fn output_from_gamma(gamma: GroupElement) -> VrfOutput {
+    let data: Vec<u8> = [b"vrf-output".as_slice(), &gamma.value().to_be_bytes()].concat();
+    VrfOutput(sha256(&data))
+}
+
+fn challenge(st: &DleqStatement, commitment: &Pair<GroupElement, GroupElement>) -> Scalar {
+    let parts = [st.h1, st.g2, st.h2, commitment.0, commitment.1];
+    let data: Vec<u8> = parts.iter().flat_map(|p| p.value().to_be_bytes()).collect();
+    let digest = sha256(&data);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    Scalar::new(u64::from_be_bytes(bytes))
+}
+
+fn statement(public: GroupElement, alpha: &str, gamma: GroupElement) -> DleqStatement {
+    DleqStatement {
+        g1: GroupElement::generator(),
+        h1: public,
+        g2: hash_input(public, alpha),
+        h2: gamma,
+    }
+}
+
+// rng 只用于 DLEQ 证明里的 nonce：换一个 rng，π 会变，β 不会
+pub fn prove(keypair: &Keypair, alpha: &str, rng: &mut SimpleRng) -> (VrfOutput, VrfProof) {
+    let gamma = hash_input(keypair.public, alpha).pow(keypair.secret);
+    let st = statement(keypair.public, alpha, gamma);
+    let (nonce, commitment) = DlogEquality::commit(&st, &keypair.secret, rng);
+    let c = challenge(&st, &commitment);
+    let response = DlogEquality::respond(&st, &keypair.secret, nonce, c);
+    (output_from_gamma(gamma), VrfProof { gamma, commitment, response })
+}
+
+// 验证通过时返回 β：验证者从证明里的 Γ 自己算出输出，不需要证明者另外报
+pub fn verify(public: GroupElement, alpha: &str, proof: &VrfProof) -> Result<VrfOutput, String> {
+    let st = statement(public, alpha, proof.gamma);
+    let c = challenge(&st, &proof.commitment);
+    if DlogEquality::verify(&st, &proof.commitment, c, &proof.response) {
+        Ok(output_from_gamma(proof.gamma))
+    } else {
+        Err(String::from("DLEQ 证明无效：Γ 不是这把公钥对这个输入的 H^x"))
+    }
+}
@@ -0,0 +1,794 @@
+// src/s05_zk_lab/ex01_merkle.rs
+// use std::fmt;
+use std::borrow::Cow;
+use std::mem;
+
+use crate::common::json::{self, Json};
+
+// 简易哈希模拟函数已搬到 hash 模块，供 S05 各练习共用
+use super::bitcoin_merkle::BitcoinHasher;
+use super::hash::{keccak256, mock_digest, mock_hash, sha256, Digest};
+use super::mimc::MimcHasher;
+use super::poseidon::PoseidonHasher;
+
+// ==========================================
+// 0. 可替换的哈希函数
+// ==========================================
+// 后端只需要回答一个问题：任意字节 -> 32 字节摘要 (hash)。
+// 叶子怎么哈希、两个孩子怎么合并 (leaf / node) 有默认规则，有特殊需求的后端可以重写
+// (比特币不给叶子加前缀，Poseidon 直接压缩两个域元素)。
+// 树里的哈希统一以文本保存，证明和 JSON 序列化不需要知道具体用了哪个后端。
+//
+// 最早这里是一个装着两个函数指针的结构体 —— 够用，但每加一个后端都要手写 leaf 和 node 两个函数；
+// 换成 trait 后大多数后端只写 hash，MerkleTree<H> 的泛型参数有默认值，不关心哈希的代码照旧写 MerkleTree。
+pub trait Hasher {
+    // 写进 JSON 的名字，读回来时靠它找回哈希函数 (hasher_by_name)
+    fn name(&self) -> &'static str;
+
+    fn hash(&self, data: &[u8]) -> Digest;
+
+    // ✅ 默认规则：域分离，叶子前面加 0x00，内部节点前面加 0x01 (RFC 6962 Certificate Transparency 的做法)
+    // 两类输入的第一个字节不同，叶子哈希和节点哈希就永远不会在同一个输入上相撞
+    fn leaf(&self, data: &[u8]) -> String {
+        let mut buf = Vec::with_capacity(1 + data.len());
+        buf.push(LEAF_PREFIX);
+        buf.extend_from_slice(data);
+        hex::encode(self.hash(&buf))
+    }
+
+    // 孩子是十六进制摘要，先还原成原始字节再拼接 (不是 hex 的就按文本字节处理)
+    fn node(&self, left: &str, right: &str) -> String {
+        let mut buf = vec![NODE_PREFIX];
+        for child in [left, right] {
+            buf.extend(hex::decode(child).unwrap_or_else(|_| child.as_bytes().to_vec()));
+        }
+        hex::encode(self.hash(&buf))
+    }
+}
+
+pub const LEAF_PREFIX: u8 = 0x00;
+pub const NODE_PREFIX: u8 = 0x01;
+
+// 引用和 Box 也是 Hasher：不同类型的后端可以放进同一个数组 ([&dyn Hasher; N])，
+// 也可以在运行时按名字选出来 (Box<dyn Hasher>)。重写过的 leaf / node 也要转发，否则会退回默认规则
+impl<H: Hasher + ?Sized> Hasher for &H {
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+    fn hash(&self, data: &[u8]) -> Digest {
+        (**self).hash(data)
+    }
+    fn leaf(&self, data: &[u8]) -> String {
+        (**self).leaf(data)
+    }
+    fn node(&self, left: &str, right: &str) -> String {
+        (**self).node(left, right)
+    }
+}
+
+impl<H: Hasher + ?Sized> Hasher for Box<H> {
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+    fn hash(&self, data: &[u8]) -> Digest {
+        (**self).hash(data)
+    }
+    fn leaf(&self, data: &[u8]) -> String {
+        (**self).leaf(data)
+    }
+    fn node(&self, left: &str, right: &str) -> String {
+        (**self).node(left, right)
+    }
+}
+
+// ❌ 最初的规则：leaf = mock_hash(data)，parent = mock_hash(left.hash + right.hash)
+// 叶子和内部节点用的是同一个函数，"两个孩子哈希拼起来的字符串"也能被当成一片叶子 (见 ex24 的第二原像攻击)
+// 保留下来只为了教学演示，新代码请用 PrefixedHasher
+#[derive(Debug, Clone, Copy)]
+pub struct MockHasher;
+
+impl Hasher for MockHasher {
+    fn name(&self) -> &'static str {
+        "mock_hash"
+    }
+    fn hash(&self, data: &[u8]) -> Digest {
+        mock_digest(data)
+    }
+    fn leaf(&self, data: &[u8]) -> String {
+        mock_hash(&String::from_utf8_lossy(data))
+    }
+    fn node(&self, left: &str, right: &str) -> String {
+        mock_hash(&format!("{}{}", left, right))
+    }
+}
+
+// ✅ mock_hash + 域分离。MerkleTree::new / new_iterative 和两种证明的 verify 默认都用它
+// mock_hash 只有 64 位，树里沿用它原来的十六进制文本 (而不是补零到 32 字节)，所以 leaf / node 也重写了
+// mock_hash 的输入是文本：字符串叶子的根和以前完全一样，非 UTF-8 的字节按 from_utf8_lossy 处理
+#[derive(Debug, Clone, Copy)]
+pub struct PrefixedHasher;
+
+impl Hasher for PrefixedHasher {
+    fn name(&self) -> &'static str {
+        "mock_hash+prefix"
+    }
+    fn hash(&self, data: &[u8]) -> Digest {
+        mock_digest(data)
+    }
+    fn leaf(&self, data: &[u8]) -> String {
+        mock_hash(&format!("{}{}", LEAF_PREFIX as char, String::from_utf8_lossy(data)))
+    }
+    fn node(&self, left: &str, right: &str) -> String {
+        mock_hash(&format!("{}{}{}", NODE_PREFIX as char, left, right))
+    }
+}
+
+// 真正的密码学哈希：只实现 hash，树的规则全用默认的域分离
+#[derive(Debug, Clone, Copy)]
+pub struct Sha256Hasher;
+
+impl Hasher for Sha256Hasher {
+    fn name(&self) -> &'static str {
+        "sha256"
+    }
+    fn hash(&self, data: &[u8]) -> Digest {
+        sha256(data)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Keccak256Hasher;
+
+impl Hasher for Keccak256Hasher {
+    fn name(&self) -> &'static str {
+        "keccak256"
+    }
+    fn hash(&self, data: &[u8]) -> Digest {
+        keccak256(data)
+    }
+}
+
+// 实验室里所有的后端
+pub fn all_hashers() -> Vec<Box<dyn Hasher>> {
+    vec![
+        Box::new(MockHasher),
+        Box::new(PrefixedHasher),
+        Box::new(Sha256Hasher),
+        Box::new(Keccak256Hasher),
+        Box::new(PoseidonHasher),
+        Box::new(MimcHasher),
+        Box::new(BitcoinHasher),
+    ]
+}
+
+// 后端没法写进文件，序列化时只存名字，读回来时再按名字找
+pub fn hasher_by_name(name: &str) -> Option<Box<dyn Hasher>> {
+    all_hashers().into_iter().find(|h| h.name() == name)
+}
+
+// ==========================================
+// 0.5 叶子的编码
+// ==========================================
+// 树只关心叶子的字节。字符串、Vec<u8>、[u8; 32] 这类本身就是字节的类型直接借用 (AsRef<[u8]>)；
+// 结构体 (比如 ex30 的 Transaction) 自己实现 leaf_bytes，给出规范化的编码，不用先拼成字符串再建树。
+// 返回 Cow：借用的类型零拷贝，需要现场编码的类型返回一个新分配的 Vec
+pub trait MerkleLeaf {
+    fn leaf_bytes(&self) -> Cow<'_, [u8]>;
+}
+
+impl<T: AsRef<[u8]> + ?Sized> MerkleLeaf for T {
+    fn leaf_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_ref())
+    }
+}
+
+// ==========================================
+// 1. 定义 Merkle 节点 (递归结构) - S03 Box
+// ==========================================
+#[derive(Debug, Clone)]
+struct Node {
+    hash: String,
+    // 左孩子和右孩子。如果是叶子节点 (Leaf)，这两个都是 None
+    left: Option<Box<Node>>,
+    right: Option<Box<Node>>,
+}
+
+impl Node {
+    // 创建叶子节点
+    fn new_leaf<L: MerkleLeaf, H: Hasher>(data: &L, hasher: &H) -> Self {
+        Node {
+            hash: hasher.leaf(&data.leaf_bytes()),
+            left: None,
+            right: None,
+        }
+    }
+
+    // 创建中间节点
+    fn new_internal<H: Hasher>(left: Box<Node>, right: Box<Node>, hasher: &H) -> Self {
+        // ❌ 任务 1：计算父节点的哈希
+        // 规则：parent_hash = hash(0x01 + left.hash + right.hash)
+        // 提示：默认的 PrefixedHasher::node 就是用 format! 拼接字符串 (前面多了一个 0x01)，然后调用 mock_hash
+        // 具体怎么拼、用哪个哈希，交给 hasher.node
+        let new_hash = hasher.node(&left.hash, &right.hash);
+
+        /*
+        参数 left: Box<Node>：没有 &。说明这个函数是个强盗，它会把传入的子节点的所有权直接抢过来。
+        left: Some(left)：抢过来的所有权，直接装进自己的口袋（Option 字段）。
+        这就是为什么 build_recursive 可以不用 clone 的原因：
+            子节点自愿把自己“献祭”给了父节点，成为了父节点的一部分。
+         */
+        Node {
+            hash: new_hash,
+            left: Some(left),   // 所有权移入
+            right: Some(right), // 所有权移入
+        }
+    }
+
+    // 奇数个节点时的"提升"父节点 (给迭代式构建用)
+    // 哈希规则和补齐最后一个节点完全一样：H(left.hash + left.hash)
+    // 区别在于不再 clone 整棵左子树，right 留空 —— 只有 left 的内部节点就代表"右边是左边的复制品"
+    fn new_promoted<H: Hasher>(left: Box<Node>, hasher: &H) -> Self {
+        Node {
+            hash: hasher.node(&left.hash, &left.hash),
+            left: Some(left),
+            right: None,
+        }
+    }
+}
+
+// ==========================================
+// 2. Merkle Tree 结构体
+// ==========================================
+// 泛型参数带默认值：只写 MerkleTree 就是 MerkleTree<String, PrefixedHasher>，已有的代码不用改
+// L 是叶子的类型，树保存原始叶子 (而不是它们的编码)，调用方拿回来的还是自己的结构体
+pub struct MerkleTree<L: MerkleLeaf = String, H: Hasher = PrefixedHasher> {
+    root: Option<Box<Node>>,
+    pub leaves: Vec<L>, // 保存原始数据，便于验证
+    hasher: H,
+}
+
+impl<L: MerkleLeaf> MerkleTree<L> {
+    pub fn new(data: Vec<L>) -> Self {
+        if data.is_empty() {
+            return MerkleTree { root: None, leaves: vec![], hasher: PrefixedHasher };
+        }
+        /*
+        在其他语言可能会因为空数组导致数组越界 (IndexOutOfBounds) 或者递归死循环。
+        Rust 这里做了一个优雅的防御：如果给了空数据，我直接还你一棵“空树”（root: None）。
+        vec![] 宏创建了一个空的 Vector
+         */
+
+        // 第一步：把所有数据变成叶子节点 (S01 Iterator)
+        let nodes: Vec<Box<Node>> = data.iter()//
+            .map(|d| Box::new(Node::new_leaf(d, &PrefixedHasher)))
+            .collect();
+        /*
+        data.iter().map(...).collect() (链式调用)：
+        .iter()：借用 data 里的元素。
+        .map(|d| ...)：闭包 (Closure)。把每个字符串 d 变成一个 Box::new(Node::new_leaf(d))。
+        .collect()：这是 Rust 迭代器最强的地方。它会自动根据左边的类型标注 Vec<Box<Node>>，
+            把 map 产出的元素收集成一个 Vector。
+        
+        Box::new(...)：
+        Box 是堆内存分配。因为 Node 是递归结构，大小不定，如果不装在箱子（指针）里，编译器无法确定大小。
+         */
+
+        // 第二步：递归构建树 
+        // 这是最外层调用
+        let root = Self::build_recursive(nodes, &PrefixedHasher);
+        // 调用关联函数 (Associated Function)，传入节点列表，返回根节点
+        // 这里把刚才打包好的那箱 nodes（所有权）直接扔给了 build_recursive。
+        // 所有权转移：在这行之后，new 函数里的 nodes 变量就不能用了。它归 build_recursive 管了。
+
+
+        MerkleTree {
+            root: Some(root),
+            leaves: data,// 因为之前使用的是 data.iter()，data 仍然拥有所有权，可以直接用
+            hasher: PrefixedHasher,
+        }
+
+        /*
+        总结 new 做了什么？
+            它是一个完美的转换器：
+            输入 Vec<L> （一堆生肉，通常是 Vec<String>）
+            --> 映射转化为 Vec<Box<Node>> 赋给变量nodes（做成香肠）
+            --> 调用build_recursive递归压缩为 Box<Node> 返回给root（打包成礼盒）
+            --> 输出 MerkleTree 对象 （发货）
+         */
+    }
+
+    // 迭代式构建 (Iterative Builder)
+    // 和 new 产出同一个根哈希，但有两点不同：
+    //   1. 用 loop 逐层归约，而不是每层递归调用一次自己
+    //   2. 奇数层不再 clone 最后一个 Box<Node> (那是整棵子树的深拷贝！)，改用 new_promoted
+    pub fn new_iterative(data: Vec<L>) -> Self {
+        Self::with_hasher(data, PrefixedHasher)
+    }
+
+}
+
+impl<L: MerkleLeaf, H: Hasher> MerkleTree<L, H> {
+    // 递归构建函数 (核心逻辑)
+    // 输入：一排节点
+    // 输出：这排节点归约后的唯一根节点
+    fn build_recursive(mut nodes: Vec<Box<Node>>, hasher: &H) -> Box<Node> {
+        // 递归基准条件 (Base Case)
+        if nodes.len() == 1 {
+            return nodes.pop().unwrap(); // 拿出最后一个，返回
+        }
+        /*
+        pop()：从 nodes 列表末尾弹出一个元素。返回 Option<Box<Node>>。
+        unwrap()：因为我们已经确认 len() == 1，所以这里一定有值。直接拆包拿出来返回。
+         */
+
+        // 如果节点数是奇数，复制最后一个节点凑成偶数 (Bitcoin 的做法)
+        if nodes.len() % 2 != 0 {
+            let last = nodes.last().unwrap().clone();
+            nodes.push(last);
+        }
+        /*
+        nodes.last()：借用看一下最后一个元素（不拿走）。
+        unwrap()：确认有值，拆包拿出来。将会得到 &Box<Node>。
+        .clone()：深拷贝。这里必须克隆，因为我们要把它复制一份追加到队尾。
+        nodes.push(last)：把克隆体塞进列表。如果不加 mut 关键字，这行就会报错。
+         */
+
+        let mut next_level = Vec::new();// 保存上一层节点的容器
+
+        // ❌ 任务 2：成对处理节点，生成上一层
+        // 提示：使用 chunks(2) 迭代，每次拿两个节点 left 和 right
+        // 注意：chunks 给的是引用，你需要处理所有权问题 (clone 或 重新设计)
+        // 更简单的做法：使用 Vec::drain 或 windows，或者直接用 for 循环 + index
+        
+        // 建议方案：使用 while 循环从 nodes 里弹出
+        // (这是对 S01 Move语义 和 S03 Box 的综合考验)
+        
+        // --- 你的代码区域 Start ---
+        // 伪代码提示：
+        // 遍历 nodes (步长为2):
+        //    left = nodes[i]
+        //    right = nodes[i+1]
+        //    parent = Node::new_internal(left, right)
+        //    next_level.push(parent)
+        
+        /* 
+        // 原始实现（虽然可行，但因为 chunks 只给引用，导致必须 clone Box<Node> 即深拷贝整棵子树，效率较低）：
+        for chunk in nodes.chunks(2) {
+             let left = chunk[0].clone();
+             let right = chunk[1].clone();
+             next_level.push(Box::new(Node::new_internal(left, right)));
+        }
+        */
+
+        // 优化方案：把 nodes 的所有权转移给迭代器，避免 clone 整个子树
+        // 注意：into_iter 会按原顺序逐个产出节点，保证 Merkle 树顺序一致
+        // into_iter 不是借用，会消耗 nodes，之后不能再用它，后面新一轮就用 next_level 了
+        let mut iter = nodes.into_iter();
+        // 一次循环调两次next(),先后取出 left 和 right
+        // 第一次拿 left。如果拿不到（None），说明传送带空了，循环结束 (while let）。
+        while let Some(left) = iter.next() {
+            // 由于上面已保证节点数为偶数，这里一定能取到 right
+            // 第二次拿 right。因为前面补齐了偶数，所以这里必然有值。
+            // expect()：如果取不到就 panic，提示“节点数应该是偶数”。但是这里不会发生。
+            let right = iter.next().expect("node count should be even");
+            // 调用 new_internal 创建父节点 
+            // 注意：left 和 right 的所有权被转移进 new_internal
+            // new_internal 将左右两棵子树合并，返回一个 Node 类型
+            let parent = Node::new_internal(left, right, hasher);
+            next_level.push(Box::new(parent));
+        }
+        
+        // --- 你的代码区域 End ---
+
+        // 递归调用：构建上一层
+        Self::build_recursive(next_level, hasher)
+
+        /*
+        第一层：输入 4 个，产出 [P1, P2] -> 扔给自己。
+        第二层：输入 2 个，产出 [Root] -> 扔给自己。
+        第三层：输入 1 个 -> 触发 [阶段 1]，直接返回 Root。
+        砰！砰！砰！ 递归栈层层弹回，最终最外层函数拿到了那个 Root。
+        
+        总结 build_recursive
+            它是一个不需要垃圾回收的内存机器。
+            通过 into_iter 和 Option 配合，它像贪吃蛇一样吞噬掉上一层的所有节点，
+            将它们的所有权转移给下一层，直到最后只剩一个头。没有任何内存泄漏，也没有任何不必要的复制。
+         */
+
+    }
+
+    // 迭代式构建 + 自选哈希函数 (例如 ex18 的 Poseidon)
+    pub fn with_hasher(data: Vec<L>, hasher: H) -> Self {
+        if data.is_empty() {
+            return MerkleTree { root: None, leaves: vec![], hasher };
+        }
+
+        let mut level: Vec<Box<Node>> = data.iter()
+            .map(|d| Box::new(Node::new_leaf(d, &hasher)))
+            .collect();
+
+        // 每一轮 level 被 into_iter 吃掉，产出的 next_level 成为新的 level
+        while level.len() > 1 {
+            // 上一层的节点数可以提前算出来，一次分配到位
+            let mut next_level = Vec::with_capacity(level.len().div_ceil(2));
+            let mut iter = level.into_iter();
+            while let Some(left) = iter.next() {
+                let parent = match iter.next() {
+                    Some(right) => Node::new_internal(left, right, &hasher),
+                    None => Node::new_promoted(left, &hasher), // 落单的最后一个
+                };
+                next_level.push(Box::new(parent));
+            }
+            level = next_level;
+        }
+
+        MerkleTree {
+            root: level.pop(),
+            leaves: data,
+            hasher,
+        }
+    }
+
+    // 统计树里一共分配了多少个 Box<Node>
+    // 注意这里也不用递归：手动维护一个栈 (Vec<&Node>) 做深度优先遍历
+    pub fn node_count(&self) -> usize {
+        let mut count = 0;
+        let mut stack: Vec<&Node> = self.root.iter().map(|node| node.as_ref()).collect();
+        while let Some(node) = stack.pop() {
+            count += 1;
+            if let Some(left) = &node.left {
+                stack.push(left);
+            }
+            if let Some(right) = &node.right {
+                stack.push(right);
+            }
+        }
+        count
+    }
+
+    // 所有 Box<Node> 占用的堆内存：节点本身 (两个指针 + String 头) + 哈希文本，不含 leaves
+    pub fn heap_bytes(&self) -> usize {
+        let mut bytes = 0;
+        let mut stack: Vec<&Node> = self.root.iter().map(|node| node.as_ref()).collect();
+        while let Some(node) = stack.pop() {
+            bytes += mem::size_of::<Node>() + node.hash.capacity();
+            stack.extend(node.left.iter().chain(node.right.iter()).map(|child| child.as_ref()));
+        }
+        bytes
+    }
+
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    pub fn root_hash(&self) -> String {
+        match &self.root {
+            // node.hash 是 String 类型。
+            // .clone()：因为我要返回一个 String 给外部，而我手里只有一个借来的引用。
+            //  我不能把别人的东西送人，所以我必须复印一份（深拷贝字符串内容）送出去
+            Some(node) => node.hash.clone(),
+            None => String::from(""),
+        }
+    }
+}
+
+// ==========================================
+// 3. Merkle 证明 (Inclusion Proof)
+// ==========================================
+// 证明"第 index 片叶子在树里"：只需要从叶子到根路径上每一层的兄弟哈希，共 log2(n) 个
+#[derive(Debug, Clone)]
+pub struct MerkleProof {
+    pub index: usize,
+    // 自底向上的兄弟哈希；bool 为 true 表示兄弟在左边 (自己是右孩子)
+    pub siblings: Vec<(String, bool)>,
+}
+
+impl<L: MerkleLeaf, H: Hasher> MerkleTree<L, H> {
+    // 从根往下走：index 的二进制位从高到低决定每一层往左还是往右
+    // 奇数补齐规则保证所有叶子都在同一深度 (树高 = ceil(log2 n))
+    pub fn prove(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.leaves.len() {
+            return None;
+        }
+        let height = self.leaves.len().next_power_of_two().trailing_zeros();
+        let mut node = self.root.as_deref()?;
+        let mut siblings = Vec::with_capacity(height as usize);
+        for level in (0..height).rev() {
+            let go_right = (index >> level) & 1 == 1;
+            let left = node.left.as_deref()?;
+            // new_promoted 产生的节点 right 为 None：右边就是左边的复制品
+            let right = node.right.as_deref().unwrap_or(left);
+            let (next, sibling) = if go_right { (right, left) } else { (left, right) };
+            siblings.push((sibling.hash.clone(), go_right));
+            node = next;
+        }
+        siblings.reverse();
+        Some(MerkleProof { index, siblings })
+    }
+}
+
+impl MerkleProof {
+    // 从叶子数据出发，依次和兄弟拼接哈希，最后应该得到根
+    // 叶子可以是 &str，也可以是建树时用的结构体：只要编码出来的字节相同就能通过
+    pub fn verify<L: MerkleLeaf + ?Sized>(&self, leaf: &L, root: &str) -> bool {
+        self.verify_with(leaf, root, &PrefixedHasher)
+    }
+
+    // 哈希函数是公开参数：验证者必须用和建树时相同的 Hasher
+    pub fn verify_with<L: MerkleLeaf + ?Sized, H: Hasher + ?Sized>(&self, leaf: &L, root: &str, hasher: &H) -> bool {
+        let computed = self.siblings.iter().fold(hasher.leaf(&leaf.leaf_bytes()), |acc, (sibling, sibling_is_left)| {
+            if *sibling_is_left {
+                hasher.node(sibling, &acc)
+            } else {
+                hasher.node(&acc, sibling)
+            }
+        });
+        computed == root
+    }
+}
+
+// ==========================================
+// 4. 批量证明 (Multiproof)
+// ==========================================
+// 同时证明多片叶子：路径在靠近根的地方会汇合，共享的内部节点只需要发一次；
+// 如果两个被证明的节点互为兄弟，验证者自己就能算出父节点，连兄弟哈希都不用发
+#[derive(Debug, Clone)]
+pub struct MultiProof {
+    pub num_leaves: usize,
+    // 排序去重后的叶子下标，verify 时叶子数据按这个顺序给出
+    pub indices: Vec<usize>,
+    // 按"自底向上、每层从左到右"的顺序排列的缺失兄弟哈希
+    pub hashes: Vec<String>,
+}
+
+impl<L: MerkleLeaf, H: Hasher> MerkleTree<L, H> {
+    // 每一层的哈希 (第 0 层是叶子)，第 k 层只取前 ceil(n / 2^k) 个
+    // new 会把落单的节点连同子树 clone 一份追加在最右边，截断后两种构建方式得到同样的结果
+    fn level_hashes(&self) -> Vec<Vec<String>> {
+        let mut sizes = vec![self.leaves.len()];
+        while sizes[sizes.len() - 1] > 1 {
+            sizes.push(sizes[sizes.len() - 1].div_ceil(2));
+        }
+        let mut levels = Vec::with_capacity(sizes.len());
+        let mut current: Vec<&Node> = self.root.iter().map(|node| node.as_ref()).collect();
+        while !current.is_empty() {
+            levels.push(current.iter().map(|node| node.hash.clone()).collect::<Vec<_>>());
+            current = current
+                .iter()
+                .flat_map(|node| node.left.iter().chain(node.right.iter()))
+                .map(|node| node.as_ref())
+                .collect();
+        }
+        levels.reverse();
+        levels.iter_mut().zip(&sizes).for_each(|(level, &size)| level.truncate(size));
+        levels
+    }
+
+    pub fn prove_batch(&self, indices: &[usize]) -> Option<MultiProof> {
+        let mut known = indices.to_vec();
+        known.sort_unstable();
+        known.dedup();
+        if known.is_empty() || known[known.len() - 1] >= self.leaves.len() {
+            return None;
+        }
+        let proof_indices = known.clone();
+
+        let levels = self.level_hashes();
+        let mut hashes = Vec::new();
+        for level in &levels[..levels.len() - 1] {
+            for &i in &known {
+                let sibling = i ^ 1;
+                // 兄弟不存在 (落单，和自己配对) 或者验证者已经知道它，都不用发
+                if sibling < level.len() && known.binary_search(&sibling).is_err() {
+                    hashes.push(level[sibling].clone());
+                }
+            }
+            known = known.iter().map(|i| i / 2).collect();
+            known.dedup();
+        }
+        Some(MultiProof { num_leaves: self.leaves.len(), indices: proof_indices, hashes })
+    }
+}
+
+impl MultiProof {
+    // leaves[k] 是第 indices[k] 片叶子的原始数据
+    pub fn verify<L: MerkleLeaf>(&self, leaves: &[L], root: &str) -> bool {
+        self.verify_with(leaves, root, &PrefixedHasher)
+    }
+
+    pub fn verify_with<L: MerkleLeaf, H: Hasher + ?Sized>(&self, leaves: &[L], root: &str, hasher: &H) -> bool {
+        if leaves.len() != self.indices.len() {
+            return false;
+        }
+        let mut known: Vec<(usize, String)> = self.indices.iter().zip(leaves).map(|(&i, leaf)| (i, hasher.leaf(&leaf.leaf_bytes()))).collect();
+        let mut hashes = self.hashes.iter();
+        let mut size = self.num_leaves;
+        while size > 1 {
+            let mut next = Vec::with_capacity(known.len());
+            let mut k = 0;
+            while k < known.len() {
+                let (i, hash) = &known[k];
+                let parent = if i % 2 == 0 {
+                    let right = if known.get(k + 1).is_some_and(|(j, _)| *j == i + 1) {
+                        k += 1;
+                        known[k].1.clone()
+                    } else if i + 1 >= size {
+                        hash.clone() // 落单，和自己配对
+                    } else {
+                        match hashes.next() {
+                            Some(h) => h.clone(),
+                            None => return false,
+                        }
+                    };
+                    hasher.node(hash, &right)
+                } else {
+                    // 左兄弟如果已知，早在上一步就和自己配对消耗掉了
+                    match hashes.next() {
+                        Some(left) => hasher.node(left, hash),
+                        None => return false,
+                    }
+                };
+                next.push((i / 2, parent));
+                k += 1;
+            }
+            known = next;
+            size = size.div_ceil(2);
+        }
+        // 多余的哈希也算无效证明
+        hashes.next().is_none() && known.len() == 1 && known[0].1 == root
+    }
+}
+
+// ==========================================
+// 5. 序列化 (JSON)
+// ==========================================
+// 叶子统一按 leaf_bytes 的编码转成 hex 再存，避免转义问题 (读回来一律当 UTF-8 字符串)；
+// 哈希本身已经是文本 (mock_hash 输出十六进制，Poseidon / MiMC 输出十进制)，原样保存
+fn decode_leaf(value: &Json) -> Result<String, String> {
+    let text = value.as_str().ok_or("叶子必须是 hex 字符串")?;
+    let bytes = hex::decode(text).map_err(|e| format!("叶子不是合法的 hex: {}", e))?;
+    String::from_utf8(bytes).map_err(|_| String::from("叶子不是合法的 UTF-8"))
+}
+
+impl<L: MerkleLeaf, H: Hasher> MerkleTree<L, H> {
+    pub fn to_json(&self) -> String {
+        Json::object(vec![
+            ("hasher", Json::Str(self.hasher.name().to_string())),
+            ("root", Json::Str(self.root_hash())),
+            ("leaves", Json::Array(self.leaves.iter().map(|leaf| Json::Str(hex::encode(leaf.leaf_bytes()))).collect())),
+        ])
+        .to_string()
+    }
+}
+
+// 读回来的树用哪个哈希函数，要看文件里写的名字，只能在运行时决定
+pub type DynMerkleTree = MerkleTree<String, Box<dyn Hasher>>;
+
+impl DynMerkleTree {
+    // 文件里的 root 只是"声明"：按叶子重建一遍，对不上就拒绝 (文件可能被改过)
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let value = json::parse(text)?;
+        let name = value.field("hasher", Json::as_str)?;
+        let hasher = hasher_by_name(name).ok_or_else(|| format!("未知的哈希函数 \"{}\"", name))?;
+        let leaves = value.field("leaves", Json::as_array)?.iter().map(decode_leaf).collect::<Result<Vec<_>, _>>()?;
+        let claimed_root = value.field("root", Json::as_str)?;
+        let tree = Self::with_hasher(leaves, hasher);
+        if tree.root_hash() != claimed_root {
+            return Err(format!("根不一致: 文件声明 {}，按叶子重建得到 {}", claimed_root, tree.root_hash()));
+        }
+        Ok(tree)
+    }
+}
+
+impl MerkleProof {
+    // 返回 Json 值而不是字符串，方便嵌进更大的文件 (ex30 把叶子和证明放在同一个文件里)
+    pub fn to_json_value(&self) -> Json {
+        let siblings = self
+            .siblings
+            .iter()
+            .map(|(hash, is_left)| Json::object(vec![("hash", Json::Str(hash.clone())), ("left", Json::Bool(*is_left))]))
+            .collect();
+        Json::object(vec![("index", Json::Number(self.index as i128)), ("siblings", Json::Array(siblings))])
+    }
+
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_string()
+    }
+
+    pub fn from_json_value(value: &Json) -> Result<Self, String> {
+        let index = value.field("index", Json::as_u64)? as usize;
+        let siblings = value
+            .field("siblings", Json::as_array)?
+            .iter()
+            .map(|s| Ok((s.field("hash", Json::as_str)?.to_string(), s.field("left", Json::as_bool)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(MerkleProof { index, siblings })
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        Self::from_json_value(&json::parse(text)?)
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex01: ZK Lab (Merkle Tree) ---");
+
+    // 模拟区块链交易
+    let transactions = vec![
+        String::from("Tx1: Alice->Bob"),
+        String::from("Tx2: Bob->Charlie"),
+        String::from("Tx3: Charlie->Dave"),
+    ];
+
+    println!("Building Merkle Tree for {} transactions...", transactions.len());
+    let tree = MerkleTree::new(transactions);
+
+    /*
+    交接：这是最关键的一行。
+        调用了 new。
+        所有权移交：transactions 变量被传入 new。
+        从此以后，run 函数里再也不能使用 transactions 这个变量了！
+        它已经属于 tree 对象内部了（变成了 tree.leaves）。
+    内部发生的事：
+        mock_hash 突突突地生成指纹。
+        build_recursive 呼啦啦地递归构建。
+        最终，所有的计算瞬间完成，返回一个封装好的 tree 对象。
+     */
+
+    println!("Root Hash: {}", tree.root_hash());
+
+    // ❌ 任务 3：手动验证 (费曼前置)
+    // 请画出这棵树的结构（Tx3 被复制了一次，所以是 4 个叶子）
+    // 计算路径：
+    // H(Root) = H( H(Tx1+Tx2) + H(Tx3+Tx3) )
+    // (每次 H 的输入前面还有一个域分离字节：叶子 0x00，内部节点 0x01)
+    // 请运行代码，看输出是否符合你的预期。
+    println!("\n--- Manual Verification ---");
+    // transactions 所有权移进去了，从 tree.leaves 拿
+    let h1 = mock_hash(&format!("\x00{}", tree.leaves[0]));
+    let h2 = mock_hash(&format!("\x00{}", tree.leaves[1]));
+    let h3 = mock_hash(&format!("\x00{}", tree.leaves[2]));
+    let h4 = h3.clone(); // 奇数个，复制最后一个
+
+    let p1 = mock_hash(&format!("\x01{}{}", h1, h2));
+    let p2 = mock_hash(&format!("\x01{}{}", h3, h4));
+    let expected_root = mock_hash(&format!("\x01{}{}", p1, p2));
+
+    println!("Manual Calc: {}", expected_root);
+    
+    // transactions 所有权已移交给 tree，所以这里从 tree.leaves 取数据验证
+    if tree.root_hash() == expected_root {
+        println!("✅ Verification Success!");
+    } else {
+        println!("❌ Verification Failed!");
+    }
+
+    // ==========================================
+    // 批量证明：路径共享的部分只发一次
+    // ==========================================
+    println!("\n--- Multiproof ---");
+    let blocks: Vec<String> = (0..16).map(|i| format!("Tx{}", i)).collect();
+    let big_tree = MerkleTree::new_iterative(blocks);
+    let wanted = [2, 3, 5, 12];
+    let batch = big_tree.prove_batch(&wanted).expect("indices in range");
+    let single_total: usize = wanted
+        .iter()
+        .map(|&i| big_tree.prove(i).expect("index in range").siblings.len())
+        .sum();
+    println!("16 leaves, proving {:?}", wanted);
+    println!("Independent single proofs: {} hashes", single_total);
+    println!("Multiproof:                {} hashes (saved {})", batch.hashes.len(), single_total - batch.hashes.len());
+
+    let leaves: Vec<&str> = batch.indices.iter().map(|&i| big_tree.leaves[i].as_str()).collect();
+    if batch.verify(&leaves, &big_tree.root_hash()) {
+        println!("✅ Multiproof verified against root");
+    } else {
+        println!("❌ Multiproof rejected");
+    }
+    // 换掉其中一片叶子，重建出来的根就对不上了
+    let mut forged = leaves.clone();
+    forged[0] = "Tx2: Mallory->Mallory";
+    if !batch.verify(&forged, &big_tree.root_hash()) {
+        println!("✅ Tampered leaf rejected");
+    }
+}
@@ -0,0 +1,280 @@
+// src/s05_zk_lab/ex02_smt.rs
+use std::collections::BTreeMap;
+
+use super::hash::{hash_pair, sha256, short_hex, Digest};
+
+/*
+业务场景：Rollup 的账户状态树
+    ex01 的 MerkleTree 是"稠密"的：叶子按数组下标排好，有几笔交易就有几片叶子。
+    但状态树要回答的问题是："地址 X 的余额是多少？地址 Y 是不是根本不存在？"
+    地址空间是 2^256，不可能把每个位置都存下来。
+
+稀疏 Merkle 树 (Sparse Merkle Tree, SMT) 的思路：
+    1. 把 key 哈希成 256 bit，这 256 bit 就是从根走到叶子的路径 (0 往左，1 往右)。
+    2. 逻辑上是一棵深度 256 的满二叉树，有 2^256 片叶子，绝大部分是"空"。
+    3. 空子树的哈希是可以提前算好的常量 (default hash)，所以根本不用存它们。
+    4. 既然每个 key 的位置是固定的，证明"某位置是空的"就和证明"某位置有值"一样简单
+       —— 这就是稠密 MerkleTree 做不到的【非成员证明 (Non-membership Proof)】。
+*/
+
+// 树的深度 = key 的比特数
+const DEPTH: usize = 256;
+
+// 空叶子的哈希：约定为全 0
+const EMPTY_LEAF: Digest = [0u8; 32];
+
+// ==========================================
+// 1. 默认空子树哈希 (Default Hashes)
+// ==========================================
+// defaults[h] = 高度为 h 的"全空子树"的根哈希
+//   defaults[0]   = EMPTY_LEAF
+//   defaults[h+1] = H(defaults[h] || defaults[h])
+// 一共 257 个，算一次存起来，之后所有空分支直接查表
+fn default_hashes() -> Vec<Digest> {
+    let mut defaults = Vec::with_capacity(DEPTH + 1);
+    defaults.push(EMPTY_LEAF);
+    for h in 0..DEPTH {
+        let below = defaults[h];
+        defaults.push(hash_pair(&below, &below));
+    }
+    defaults
+}
+
+// 取 key 从最高位数起的第 i 个 bit (0 = 往左，1 = 往右)
+fn bit_at(key: &Digest, i: usize) -> u8 {
+    (key[i / 8] >> (7 - i % 8)) & 1
+}
+
+// 叶子哈希：给 value 加一个 0x00 前缀再哈希，保证真实叶子不可能等于全 0 的 EMPTY_LEAF
+fn leaf_hash(value: &[u8]) -> Digest {
+    let mut buf = Vec::with_capacity(value.len() + 1);
+    buf.push(0x00);
+    buf.extend_from_slice(value);
+    sha256(&buf)
+}
+
+// ==========================================
+// 2. 证明结构
+// ==========================================
+// 同一个结构既能表达成员证明 (value = Some)，也能表达非成员证明 (value = None)
+#[derive(Debug, Clone)]
+pub struct SmtProof {
+    pub key: Digest,
+    pub value: Option<Vec<u8>>,
+    // 从叶子往上的 256 个兄弟节点
+    // None 表示"这个兄弟是空子树"，验证时用 defaults[h] 补上 —— 这就是证明压缩
+    pub siblings: Vec<Option<Digest>>,
+}
+
+impl SmtProof {
+    // 非默认兄弟的个数：真正需要传输的哈希数量
+    pub fn non_default_count(&self) -> usize {
+        self.siblings.iter().filter(|s| s.is_some()).count()
+    }
+
+    // 验证：从叶子一路哈希到根，和给定的 root 对比
+    // 注意：验证者只需要 root，不需要整棵树
+    pub fn verify(&self, root: &Digest, defaults: &[Digest]) -> bool {
+        if self.siblings.len() != DEPTH {
+            return false;
+        }
+        let mut current = match &self.value {
+            Some(v) => leaf_hash(v),
+            None => EMPTY_LEAF, // 非成员证明：断言这个位置是空叶子
+        };
+        for (h, sibling) in self.siblings.iter().enumerate() {
+            let sibling = sibling.unwrap_or(defaults[h]);
+            // 高度 h 的节点对应 key 的第 (DEPTH - 1 - h) 位
+            current = if bit_at(&self.key, DEPTH - 1 - h) == 0 {
+                hash_pair(&current, &sibling)
+            } else {
+                hash_pair(&sibling, &current)
+            };
+        }
+        &current == root
+    }
+}
+
+// ==========================================
+// 3. 稀疏 Merkle 树
+// ==========================================
+pub struct SparseMerkleTree {
+    // 只存真正有值的叶子：路径 -> (原始 value)
+    // 用 BTreeMap 而不是 HashMap：key 有序，同一子树里的叶子在区间里是连续的
+    leaves: BTreeMap<Digest, Vec<u8>>,
+    defaults: Vec<Digest>,
+}
+
+impl SparseMerkleTree {
+    pub fn new() -> Self {
+        SparseMerkleTree {
+            leaves: BTreeMap::new(),
+            defaults: default_hashes(),
+        }
+    }
+
+    // 任意字符串 key -> 256 bit 路径
+    pub fn path_of(key: &str) -> Digest {
+        sha256(key.as_bytes())
+    }
+
+    pub fn defaults(&self) -> &[Digest] {
+        &self.defaults
+    }
+
+    pub fn insert(&mut self, key: &str, value: &[u8]) {
+        // 已存在则覆盖 (BTreeMap::insert 会返回旧值，这里直接丢弃)
+        self.leaves.insert(Self::path_of(key), value.to_vec());
+    }
+
+    pub fn root(&self) -> Digest {
+        let all: Vec<(&Digest, &Vec<u8>)> = self.leaves.iter().collect();
+        self.subtree_hash(&all, DEPTH)
+    }
+
+    // 计算"高度为 height、包含 leaves 这些叶子"的子树根
+    // leaves 已按 key 排序，且都落在同一个子树里
+    fn subtree_hash(&self, leaves: &[(&Digest, &Vec<u8>)], height: usize) -> Digest {
+        if leaves.is_empty() {
+            // 空子树：直接查表，2^height 个空位一次搞定
+            return self.defaults[height];
+        }
+        if height == 0 {
+            return leaf_hash(leaves[0].1);
+        }
+        // 在这一层看 key 的第 (DEPTH - height) 位，0 的在左，1 的在右
+        // 因为 leaves 有序且前缀相同，这一位是单调的 0...0 1...1，可以二分切开
+        let bit = DEPTH - height;
+        let split = leaves.partition_point(|(k, _)| bit_at(k, bit) == 0);
+        let (left, right) = leaves.split_at(split);
+        hash_pair(
+            &self.subtree_hash(left, height - 1),
+            &self.subtree_hash(right, height - 1),
+        )
+    }
+
+    // 为 key 生成证明：存在就是成员证明，不存在就是非成员证明
+    pub fn prove(&self, key: &str) -> SmtProof {
+        let path = Self::path_of(key);
+        let all: Vec<(&Digest, &Vec<u8>)> = self.leaves.iter().collect();
+
+        // 从根往下走，每一层记录"另一边"子树的哈希
+        let mut siblings = vec![None; DEPTH];
+        let mut current: &[(&Digest, &Vec<u8>)] = &all;
+        for height in (1..=DEPTH).rev() {
+            let bit = DEPTH - height;
+            let split = current.partition_point(|(k, _)| bit_at(k, bit) == 0);
+            let (left, right) = current.split_at(split);
+            let (mine, other) = if bit_at(&path, bit) == 0 {
+                (left, right)
+            } else {
+                (right, left)
+            };
+            // 兄弟子树为空时不存哈希，只记 None
+            if !other.is_empty() {
+                siblings[height - 1] = Some(self.subtree_hash(other, height - 1));
+            }
+            current = mine;
+        }
+
+        SmtProof {
+            key: path,
+            value: self.leaves.get(&path).cloned(),
+            siblings,
+        }
+    }
+}
+
+impl Default for SparseMerkleTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn describe(proof: &SmtProof, root: &Digest, defaults: &[Digest]) {
+    let kind = match &proof.value {
+        Some(v) => format!("成员证明 (value = {})", String::from_utf8_lossy(v)),
+        None => String::from("非成员证明 (该位置为空)"),
+    };
+    println!(
+        "  {} | 路径前缀 {}.. | 非默认兄弟 {}/{} | 验证: {}",
+        kind,
+        short_hex(&proof.key),
+        proof.non_default_count(),
+        DEPTH,
+        if proof.verify(root, defaults) { "✅" } else { "❌" }
+    );
+}
+
+pub fn run() {
+    println!("--- S05 Ex02: 稀疏 Merkle 树 (Sparse Merkle Tree) ---");
+
+    let mut smt = SparseMerkleTree::new();
+    println!("空树根 = defaults[256] = {}..", short_hex(&smt.root()));
+    println!(
+        "前几层默认哈希: d0={} d1={} d2={} ...",
+        short_hex(&smt.defaults()[0]),
+        short_hex(&smt.defaults()[1]),
+        short_hex(&smt.defaults()[2])
+    );
+
+    // 1. 写入账户余额
+    smt.insert("Alice", b"100");
+    smt.insert("Bob", b"50");
+    smt.insert("Carol", b"7");
+    let root = smt.root();
+    println!("\n写入 Alice/Bob/Carol 后的根: {}..", short_hex(&root));
+
+    // 2. 成员证明
+    println!("\n[成员证明]");
+    let alice = smt.prove("Alice");
+    describe(&alice, &root, smt.defaults());
+
+    // 3. 非成员证明：Dave 从来没出现过
+    println!("\n[非成员证明]");
+    let dave = smt.prove("Dave");
+    describe(&dave, &root, smt.defaults());
+
+    // ❌ 陷阱：拿着 Alice 的兄弟路径，谎称 "Alice 不存在"
+    // 把 value 改成 None，叶子就变成 EMPTY_LEAF，一路算上去必然得不到同一个根
+    println!("\n[伪造] 用 Alice 的路径谎称 Alice 不存在:");
+    let mut forged = alice.clone();
+    forged.value = None;
+    describe(&forged, &root, smt.defaults());
+
+    // ❌ 陷阱：篡改余额
+    println!("[伪造] 把 Alice 的余额改成 1000000:");
+    let mut forged = alice;
+    forged.value = Some(b"1000000".to_vec());
+    describe(&forged, &root, smt.defaults());
+
+    // 4. 更新：覆盖写入后旧证明失效
+    smt.insert("Alice", b"90");
+    let new_root = smt.root();
+    println!("\nAlice 余额改为 90，新根: {}..", short_hex(&new_root));
+    println!("旧的 Dave 非成员证明对新根: ");
+    describe(&dave, &new_root, smt.defaults());
+    println!("重新生成 Dave 的非成员证明: ");
+    describe(&smt.prove("Dave"), &new_root, smt.defaults());
+}
+
+/*
+关键点总结：
+    1. 为什么 256 层的树不会爆内存？
+        我们只存了 3 片真实叶子 (BTreeMap)，其余 2^256 - 3 个位置全是"空"，
+        空子树的哈希从 defaults 表里查，subtree_hash 遇到空切片立刻返回。
+
+    2. 证明压缩：
+        每个证明逻辑上有 256 个兄弟，但只有靠近叶子和路径分叉处的几个是非默认的。
+        SmtProof 用 Option<Digest> 存兄弟，None 的部分验证者自己用 defaults 补齐，
+        所以上面输出里"非默认兄弟"只有个位数。
+
+    3. 非成员证明为什么可靠？
+        key 的位置由 sha256(key) 唯一决定，该位置只可能是 EMPTY_LEAF 或 leaf_hash(value)。
+        leaf_hash 前面加了 0x00 前缀，真实叶子不会碰巧等于全 0。
+        所以"该位置是 EMPTY_LEAF 且能算出同一个根"就等于"key 不在树里"。
+
+    4. 旧证明为什么失效？
+        任何一次写入都会改变根。Dave 的旧证明里有一个兄弟是"包含 Alice 的子树哈希"，
+        Alice 变了，这个兄弟就过期了。状态树的证明永远要绑定一个具体的根 (区块高度)。
+*/
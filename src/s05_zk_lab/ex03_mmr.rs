@@ -0,0 +1,247 @@
+// src/s05_zk_lab/ex03_mmr.rs
+use super::hash::{hash_pair, sha256, short_hex, Digest};
+
+/*
+业务场景：只追加的交易日志 (Append-only Accumulator)
+    ex01 的 MerkleTree::new 要求一次性给出全部叶子，想追加一笔交易？
+    只能把 Vec<String> 拿出来 push，再整棵树重建一遍 —— 每次 O(n) 次哈希。
+
+Merkle 山脉 (Merkle Mountain Range, MMR) 的思路：
+    1. 它不是一棵树，而是一排"完美二叉树" (山峰, Peak)，高度从左到右递减。
+    2. 叶子数 n 的二进制表示就决定了山脉形状：
+         n = 7 = 0b111 -> 高度 2、1、0 三座山 (4 + 2 + 1 片叶子)
+    3. 追加一片叶子 = 二进制 +1：如果最右边出现两座等高的山，就合并成一座更高的 (进位)。
+       每次追加平均只需要 ~1 次额外哈希，老节点永远不会被修改 (只追加)。
+    4. 最后把所有山峰"装袋" (Bagging) 成一个根，作为整个日志的承诺。
+*/
+
+// ==========================================
+// 1. MMR 结构
+// ==========================================
+// levels[h] 保存高度为 h 的所有节点 (从左到右)
+//   levels[0] = 所有叶子哈希
+//   levels[h][i] = H(levels[h-1][2i] || levels[h-1][2i+1])
+// 一个节点是"山峰"⇔ 它还没有父节点 ⇔ 它是 levels[h] 的最后一个且 levels[h].len() 为奇数
+pub struct MerkleMountainRange {
+    levels: Vec<Vec<Digest>>,
+}
+
+// 包含证明：叶子 -> 所在山峰的路径 + 所有山峰 (用来装袋)
+#[derive(Debug, Clone)]
+pub struct MmrProof {
+    pub leaf_index: usize,
+    // (兄弟哈希, 兄弟是否在左边)
+    pub path: Vec<(Digest, bool)>,
+    pub peaks: Vec<Digest>,
+    // 叶子所在的山峰在 peaks 里的下标
+    pub peak_pos: usize,
+}
+
+impl MerkleMountainRange {
+    pub fn new() -> Self {
+        MerkleMountainRange { levels: vec![Vec::new()] }
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    // 追加一片叶子，返回本次追加做了多少次哈希 (叶子本身 + 向上合并)
+    pub fn push(&mut self, data: &str) -> usize {
+        self.levels[0].push(sha256(data.as_bytes()));
+        let mut hashes = 1;
+
+        // 像二进制加法的进位：本层凑成偶数个 -> 最后两个合并，送到上一层
+        let mut h = 0;
+        while self.levels[h].len().is_multiple_of(2) {
+            let len = self.levels[h].len();
+            let parent = hash_pair(&self.levels[h][len - 2], &self.levels[h][len - 1]);
+            hashes += 1;
+            if self.levels.len() == h + 1 {
+                self.levels.push(Vec::new());
+            }
+            self.levels[h + 1].push(parent);
+            h += 1;
+        }
+        hashes
+    }
+
+    // 山峰列表：从最高 (最左) 到最低 (最右)，每项是 (高度, 哈希)
+    pub fn peaks(&self) -> Vec<(usize, Digest)> {
+        let mut peaks = Vec::new();
+        for h in (0..self.levels.len()).rev() {
+            let level = &self.levels[h];
+            if level.len() % 2 == 1 {
+                peaks.push((h, *level.last().unwrap()));
+            }
+        }
+        peaks
+    }
+
+    // 装袋：从右往左折叠 root = H(p0 || H(p1 || H(p2 ...)))
+    pub fn bag_peaks(peaks: &[Digest]) -> Digest {
+        let mut iter = peaks.iter().rev();
+        let mut bag = match iter.next() {
+            Some(last) => *last,
+            None => return [0u8; 32], // 空 MMR
+        };
+        for peak in iter {
+            bag = hash_pair(peak, &bag);
+        }
+        bag
+    }
+
+    pub fn root(&self) -> Digest {
+        let peaks: Vec<Digest> = self.peaks().into_iter().map(|(_, d)| d).collect();
+        Self::bag_peaks(&peaks)
+    }
+
+    pub fn prove(&self, leaf_index: usize) -> Option<MmrProof> {
+        if leaf_index >= self.leaf_count() {
+            return None;
+        }
+        // 沿着 levels 往上爬，直到当前节点没有父节点 (它就是山峰)
+        let mut path = Vec::new();
+        let mut idx = leaf_index;
+        let mut h = 0;
+        while h + 1 < self.levels.len() && idx / 2 < self.levels[h + 1].len() {
+            let sibling = idx ^ 1;
+            path.push((self.levels[h][sibling], sibling < idx));
+            idx /= 2;
+            h += 1;
+        }
+        // 爬到了高度 h 的山峰，每个高度最多一座山，按高度找它的位置
+        let all_peaks = self.peaks();
+        let peak_pos = all_peaks.iter().position(|(peak_h, _)| *peak_h == h)?;
+        let peaks = all_peaks.into_iter().map(|(_, d)| d).collect();
+        Some(MmrProof { leaf_index, path, peaks, peak_pos })
+    }
+}
+
+impl Default for MerkleMountainRange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MmrProof {
+    pub fn verify(&self, data: &str, root: &Digest) -> bool {
+        // 第一段：叶子 -> 山峰
+        let mut current = sha256(data.as_bytes());
+        for (sibling, sibling_is_left) in &self.path {
+            current = if *sibling_is_left {
+                hash_pair(sibling, &current)
+            } else {
+                hash_pair(&current, sibling)
+            };
+        }
+        match self.peaks.get(self.peak_pos) {
+            Some(peak) if *peak == current => {}
+            _ => return false,
+        }
+        // 第二段：山峰们 -> 根
+        &MerkleMountainRange::bag_peaks(&self.peaks) == root
+    }
+}
+
+// 对比：如果用 ex01 的方式每次全量重建，n 片叶子要做多少次哈希
+// (叶子 n 次 + 每层两两合并，奇数补齐)
+fn full_rebuild_cost(n: usize) -> usize {
+    let mut total = n;
+    let mut width = n;
+    while width > 1 {
+        width = width.div_ceil(2);
+        total += width;
+    }
+    total
+}
+
+pub fn run() {
+    println!("--- S05 Ex03: Merkle 山脉 (Append-only MMR) ---");
+
+    let txs = [
+        "Tx1: Alice->Bob",
+        "Tx2: Bob->Charlie",
+        "Tx3: Charlie->Dave",
+        "Tx4: Dave->Eve",
+        "Tx5: Eve->Frank",
+        "Tx6: Frank->Grace",
+        "Tx7: Grace->Heidi",
+    ];
+
+    let mut mmr = MerkleMountainRange::new();
+    let mut mmr_total = 0;
+    let mut rebuild_total = 0;
+
+    // 1. 逐笔追加，观察山脉形状随二进制变化
+    for tx in txs.iter() {
+        let cost = mmr.push(tx);
+        mmr_total += cost;
+        rebuild_total += full_rebuild_cost(mmr.leaf_count());
+
+        let shape: Vec<String> = mmr
+            .peaks()
+            .iter()
+            .map(|(h, d)| format!("h{}:{}", h, short_hex(d)))
+            .collect();
+        println!(
+            "追加 {:<20} n={} (0b{:03b}) | 本次哈希 {} 次 | 山峰 [{}]",
+            tx,
+            mmr.leaf_count(),
+            mmr.leaf_count(),
+            cost,
+            shape.join(", ")
+        );
+    }
+    println!(
+        "\n累计哈希次数：MMR 追加 {} 次 vs 每次全量重建 {} 次",
+        mmr_total, rebuild_total
+    );
+
+    // 2. 装袋得到根
+    let root = mmr.root();
+    println!("MMR 根 (装袋后): {}..", short_hex(&root));
+
+    // 3. 包含证明
+    let proof = mmr.prove(2).expect("leaf 2 exists");
+    println!(
+        "\n证明 Tx3 (下标 2)：路径 {} 个兄弟 + {} 个山峰，落在第 {} 座山",
+        proof.path.len(),
+        proof.peaks.len(),
+        proof.peak_pos
+    );
+    println!("验证 Tx3: {}", if proof.verify(txs[2], &root) { "✅" } else { "❌" });
+    println!("伪造 Tx3 内容: {}", if proof.verify("Tx3: Charlie->Mallory", &root) { "✅" } else { "❌" });
+
+    // 4. 继续追加：旧根失效，但旧山峰仍是新山脉的一部分
+    mmr.push("Tx8: Heidi->Ivan");
+    let new_root = mmr.root();
+    println!("\n追加 Tx8 后 n=8 (0b1000)，三座山合并成一座: {} 个山峰", mmr.peaks().len());
+    println!("旧证明对新根: {}", if proof.verify(txs[2], &new_root) { "✅" } else { "❌ (需要刷新)" });
+    let refreshed = mmr.prove(2).expect("leaf 2 exists");
+    println!(
+        "刷新后的证明 (路径 {} 个兄弟): {}",
+        refreshed.path.len(),
+        if refreshed.verify(txs[2], &new_root) { "✅" } else { "❌" }
+    );
+    println!("越界下标 99 的证明: {:?}", mmr.prove(99).map(|p| p.leaf_index));
+}
+
+/*
+关键点总结：
+    1. 为什么追加这么便宜？
+        push 就是二进制 +1。n 从 0b0111 变成 0b1000 时会连续进位 3 次 (合并 3 次)，
+        但大多数时候只进位 0~1 次。摊还下来每片叶子约 2 次哈希，而全量重建是 O(n)。
+
+    2. 只追加 (Append-only) 意味着什么？
+        levels 里的老节点一旦写入就再也不会改变 —— 我们只在各层末尾 push。
+        这正是区块链历史日志想要的性质：过去的承诺不可篡改，只能往后续写。
+
+    3. 证明由两段组成：
+        叶子 -> 山峰 (O(log n) 个兄弟) + 全部山峰 (最多 log n 个) 装袋 -> 根。
+        山峰合并后证明路径会变长，所以轻客户端需要在新根上"刷新"证明。
+
+    4. 与 ex01 的 Box<Node> 递归树对比：
+        这里没有任何指针，整个结构就是 Vec<Vec<Digest>>，父子关系全靠下标算 (idx / 2, idx ^ 1)。
+        这是 Rust 里表达树结构的另一种常见手法：用索引代替引用，彻底绕开所有权问题。
+*/
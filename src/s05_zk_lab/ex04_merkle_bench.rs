@@ -0,0 +1,93 @@
+// src/s05_zk_lab/ex04_merkle_bench.rs
+use std::time::{Duration, Instant};
+
+use super::ex01_merkle::MerkleTree;
+
+/*
+业务场景：给一个百万级交易的区块建 Merkle 树
+    ex01 的 build_recursive 在玩具数据 (3 笔交易) 上毫无问题，
+    但把叶子数放大到 10^5 ~ 10^6，两个隐藏成本就暴露出来了：
+
+    1. 奇数补齐时的 clone：
+        nodes.last().unwrap().clone() 拷贝的是 Box<Node> —— 整棵子树的深拷贝。
+        越往上层，落单的节点下面挂的子树越大，拷贝的节点也越多。
+        最坏情况 n = 2^k + 1：每一层都是奇数，几乎要多复制出半棵树。
+
+    2. 递归：
+        每一层调用一次自己。对平衡二叉树来说深度只有 log2(n) ≈ 20，栈不会真的爆，
+        但递归把"循环"藏进了调用栈里，每一层的 Vec 都压在栈帧上等待返回。
+        迭代版用 loop + 变量重绑定 (level = next_level) 把控制流摊开，
+        上一层的 Vec 在 into_iter 后立即被消耗掉，一目了然。
+
+对比实验：同样的数据，分别用 MerkleTree::new (递归) 和 MerkleTree::new_iterative 构建，
+比较耗时、分配的节点数，并确认根哈希一致。
+*/
+
+struct BenchResult {
+    elapsed: Duration,
+    nodes: usize,
+    root: String,
+}
+
+// 构建一棵树，记录耗时和节点数，然后立刻丢弃 (drop) 以释放内存
+fn measure(builder: fn(Vec<String>) -> MerkleTree, data: Vec<String>) -> BenchResult {
+    let start = Instant::now();
+    let tree = builder(data);
+    let elapsed = start.elapsed();
+    BenchResult {
+        elapsed,
+        nodes: tree.node_count(),
+        root: tree.root_hash(),
+    }
+    // tree 在这里离开作用域，百万个 Box<Node> 一起释放
+}
+
+pub fn run() {
+    println!("--- S05 Ex04: 迭代式 Merkle 构建 vs 递归构建 ---");
+    println!("(提示：用 cargo run --release 运行，耗时对比更接近真实情况)\n");
+
+    // 1_000 是普通规模；131_073 = 2^17 + 1 是"每层都是奇数"的最坏情况；1_000_000 是百万级
+    let sizes = [1_000usize, 131_073, 1_000_000];
+
+    println!(
+        "{:>10} | {:>12} {:>10} | {:>12} {:>10} | 根一致?",
+        "叶子数", "递归耗时", "节点数", "迭代耗时", "节点数"
+    );
+    for &n in sizes.iter() {
+        let data: Vec<String> = (0..n).map(|i| format!("Tx{}", i)).collect();
+
+        // 函数名本身就是 fn 指针，可以直接当参数传
+        let recursive = measure(MerkleTree::new, data.clone());
+        let iterative = measure(MerkleTree::new_iterative, data);
+
+        println!(
+            "{:>10} | {:>10.1?} {:>12} | {:>10.1?} {:>12} | {}",
+            n,
+            recursive.elapsed,
+            recursive.nodes,
+            iterative.elapsed,
+            iterative.nodes,
+            if recursive.root == iterative.root { "✅" } else { "❌" }
+        );
+    }
+
+    println!("\n理论上一棵不带复制的树恰好有 2n - 1 个节点 (n 个叶子 + n - 1 个内部节点)，");
+    println!("迭代版每遇到落单节点只多分配 1 个提升节点；递归版多出来的节点全部来自深拷贝。");
+}
+
+/*
+关键点总结：
+    1. 节点数差异：
+        递归版在 131_073 这一行会多出将近一倍的节点 —— 每层落单的子树都被完整复制了一份。
+        迭代版的 new_promoted 只新建一个父节点，右孩子留空，哈希规则 H(left + left) 不变，
+        所以两种方法的根哈希完全相同，但内存少得多。
+
+    2. 为什么迭代版不需要 clone？
+        递归版要求"先补成偶数再两两配对"，补进去的那个必须是一个独立拥有所有权的 Box<Node>。
+        迭代版换了个思路：配对时用 iter.next() 取右孩子，取不到 (None) 就说明落单了，
+        直接把 left 的所有权交给提升节点 —— 所有权只移动、不复制。
+
+    3. fn 指针：
+        measure 的参数 builder: fn(Vec<String>) -> MerkleTree 接收的是普通函数指针，
+        MerkleTree::new 和 MerkleTree::new_iterative 签名相同，所以可以互换传入。
+*/
@@ -0,0 +1,143 @@
+// src/s05_zk_lab/ex05_parallel_merkle.rs
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::ex01_merkle::{Hasher, MerkleTree, PrefixedHasher};
+
+/*
+业务场景：多核并行建树 (S04 线程 + S05 Merkle)
+    Merkle 树天然可以分治：左半边和右半边的子树互不依赖，可以交给不同的线程去算，
+    最后只需要把几个子树根再合并一次。
+
+关键约束：并行版算出的根必须和单线程版一模一样！
+    1. 切分点必须对齐到 2 的幂：每个 worker 负责 2^k 片叶子，
+       这样每个子树的根正好就是完整树第 k 层上的某个节点。
+    2. 最后一块可能不满 2^k 片：它在完整树里会一路"自己和自己配对" (奇数补齐规则)，
+       所以 worker 算完后还要把根提升到第 k 层：r = H(r + r)，直到高度够为止。
+    3. 合并阶段：把各子树根当作第 k 层，按同样的"奇数复制最后一个"规则往上归约。
+*/
+
+// n 片叶子的树高 (根所在的层号)，1 片叶子时为 0
+fn tree_height(leaf_count: usize) -> u32 {
+    leaf_count.next_power_of_two().trailing_zeros()
+}
+
+// 按 ex01 的规则，把一层哈希归约成一个根 (奇数时复制最后一个)
+// 节点哈希要和 new_iterative 默认的 PrefixedHasher 一致，否则根对不上
+fn reduce_level(mut level: Vec<String>) -> String {
+    while level.len() > 1 {
+        let mut next_level = Vec::with_capacity(level.len().div_ceil(2));
+        for pair in level.chunks(2) {
+            let right = pair.get(1).unwrap_or(&pair[0]);
+            next_level.push(PrefixedHasher.node(&pair[0], right));
+        }
+        level = next_level;
+    }
+    level.pop().unwrap_or_default()
+}
+
+// 把数据切成若干块，每块 chunk_size 片叶子 (最后一块可能不满)
+// 用 split_off 转移所有权，而不是 chunks().to_vec() 再复制一遍字符串
+fn split_owned(mut data: Vec<String>, chunk_size: usize) -> Vec<Vec<String>> {
+    let mut chunks = Vec::new();
+    while data.len() > chunk_size {
+        let tail = data.split_off(chunk_size); // data 留下前 chunk_size 个，tail 拿走剩余
+        chunks.push(data);
+        data = tail;
+    }
+    chunks.push(data);
+    chunks
+}
+
+pub fn build_parallel(data: Vec<String>, workers: usize) -> (String, Vec<Duration>) {
+    if data.is_empty() {
+        return (String::new(), vec![]);
+    }
+    // 每个 worker 至少分到 ceil(n / workers) 片，再向上取到 2 的幂以保证对齐
+    let chunk_size = data.len().div_ceil(workers).next_power_of_two();
+    let chunk_height = tree_height(chunk_size);
+
+    // 1. 分发：每个线程拿走自己那一块的所有权 (move)
+    let handles: Vec<_> = split_owned(data, chunk_size)
+        .into_iter()
+        .map(|chunk| {
+            thread::spawn(move || {
+                let start = Instant::now();
+                let height = tree_height(chunk.len());
+                let mut root = MerkleTree::new_iterative(chunk).root_hash();
+                // 不满的最后一块：把根提升到和其他块同样的高度
+                for _ in height..chunk_height {
+                    root = PrefixedHasher.node(&root, &root);
+                }
+                // 闭包的返回值会通过 JoinHandle 交还给主线程
+                (root, start.elapsed())
+            })
+        })
+        .collect();
+
+    // 2. 汇总：join 的顺序就是切分的顺序，保证子树根从左到右排列
+    let mut subtree_roots = Vec::with_capacity(handles.len());
+    let mut timings = Vec::with_capacity(handles.len());
+    for handle in handles {
+        let (root, elapsed) = handle.join().expect("worker 线程 panic");
+        subtree_roots.push(root);
+        timings.push(elapsed);
+    }
+
+    // 3. 合并：子树根只有几个，主线程单独算即可
+    (reduce_level(subtree_roots), timings)
+}
+
+pub fn run() {
+    println!("--- S05 Ex05: 多线程并行构建 Merkle 树 ---");
+
+    let n = 1_000_000;
+    let data: Vec<String> = (0..n).map(|i| format!("Tx{}", i)).collect();
+    let cores = thread::available_parallelism().map(|c| c.get()).unwrap_or(1);
+    println!("叶子数: {} | 本机可用并行度: {}", n, cores);
+    println!("(提示：用 cargo run --release 运行，加速比更明显)\n");
+
+    // 1. 单线程基线
+    let start = Instant::now();
+    let baseline = MerkleTree::new_iterative(data.clone()).root_hash();
+    let single = start.elapsed();
+    println!("单线程: {:>10.1?} | 根 {}", single, baseline);
+
+    // 2. 不同线程数
+    for workers in [2usize, 4, 8] {
+        let start = Instant::now();
+        let (root, timings) = build_parallel(data.clone(), workers);
+        let total = start.elapsed();
+
+        let per_worker: Vec<String> = timings.iter().map(|t| format!("{:.1?}", t)).collect();
+        println!(
+            "{} 线程: {:>10.1?} | 加速比 {:.2}x | 根一致: {} | 各 worker 耗时 [{}]",
+            workers,
+            total,
+            single.as_secs_f64() / total.as_secs_f64(),
+            if root == baseline { "✅" } else { "❌" },
+            per_worker.join(", ")
+        );
+    }
+}
+
+/*
+关键点总结：
+    1. 所有权的流动：
+        data (Vec<String>) -> split_owned 切成几个 Vec<String> -> 每个 move 进一个线程
+        -> 线程内部 MerkleTree::new_iterative 吃掉 chunk -> 只把根 (String) 交回主线程。
+        全程没有 Arc，也没有锁：每块数据在任意时刻只属于一个线程。
+
+    2. 为什么不用 Arc<Vec<String>> 共享整份数据？
+        可以，但每个线程都只读自己那一段，共享整份数据反而要处理生命周期/引用计数。
+        "把数据切开分给大家" 是并行计算里最省心的模式 (S04 ex01 的 move 思路)。
+
+    3. 加速比为什么到不了线程数？
+        - 最后的合并和 split_owned 在主线程串行执行 (Amdahl 定律)。
+        - mock_hash 里的 format! 频繁申请堆内存，多个线程会竞争全局分配器。
+        - 线程数超过物理核心数后，再加线程只会增加调度开销。
+
+    4. 对齐为什么重要？
+        如果把 1_000_000 片叶子平均切成 3 份 (333_334 片)，子树边界就不在 2 的幂上，
+        各子树根在完整树里根本不存在对应的节点，合并出来的根必然不同。
+*/
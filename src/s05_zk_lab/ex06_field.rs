@@ -0,0 +1,118 @@
+// src/s05_zk_lab/ex06_field.rs
+use super::math::field::{ext_gcd, Fp, F17, F97};
+
+/*
+为什么几乎所有 ZK 证明系统都建立在"有限域"上？
+
+    1. 封闭：加减乘除 (除数非 0) 的结果永远还在 {0..p-1} 里，数字不会越算越大。
+       电路里的每根"导线"都是一个域元素，固定大小，便于承诺和哈希。
+    2. 除法永远可行：每个非 0 元素都有逆元 -> 线性方程 a*x = b 永远有唯一解，
+       拉格朗日插值、多项式除法这些 ZK 的核心工具才能成立。
+    3. 多项式的"代数性质"成立：d 次非零多项式最多 d 个根。
+       这是 Schwartz-Zippel 引理的基础 —— 随机点一测，两个不同多项式几乎不可能碰巧相等。
+
+本练习在 F_17 和 F_97 上把这三点逐一跑一遍。
+*/
+
+fn mark(ok: bool) -> &'static str {
+    if ok { "✅" } else { "❌" }
+}
+
+pub fn run() {
+    println!("--- S05 Ex06: 素数域算术 (Finite Field F_p) ---");
+
+    // ==========================================
+    // 1. 钟表算术：一切都在 mod 17 里打转
+    // ==========================================
+    println!("\n[1] F_{} 上的加减乘 (钟表算术)", F17::MODULUS);
+    let a = F17::new(15);
+    let b = F17::new(5);
+    println!("  15 + 5 = {}   (20 mod 17)", a + b);
+    println!("   5 - 15 = {}  (不会出现负数，-10 ≡ 7)", b - a);
+    println!("  15 * 5 = {}   (75 mod 17)", a * b);
+    println!("  -1 = {}      (from_i64(-1) = {})", -F17::one(), F17::from_i64(-1));
+
+    // ==========================================
+    // 2. 逆元：扩展欧几里得
+    // ==========================================
+    println!("\n[2] 乘法逆元 (扩展欧几里得)");
+    let (g, x, y) = ext_gcd(5, 17);
+    println!("  ext_gcd(5, 17) = (g={}, x={}, y={})  即 5*({}) + 17*({}) = {}", g, x, y, x, y, g);
+    println!("  两边 mod 17 => 5 * {} ≡ 1，所以 5 的逆元是 {}", x.rem_euclid(17), F17::new(5).inv().unwrap());
+
+    let mut table = Vec::new();
+    let mut all_ok = true;
+    for v in 1..F17::MODULUS {
+        let e = F17::new(v);
+        let inv = e.inv().expect("nonzero element has an inverse");
+        all_ok &= (e * inv) == F17::one();
+        table.push(format!("{}→{}", v, inv));
+    }
+    println!("  逆元表: {}", table.join(" "));
+    println!("  每个非零元素 a 都满足 a * a^-1 = 1: {}", mark(all_ok));
+    println!("  0 的逆元: {:?} (和整数一样，0 不能做除数)", F17::zero().inv());
+
+    // ==========================================
+    // 3. 费马小定理：a^(p-1) = 1，所以 a^(p-2) 就是逆元
+    // ==========================================
+    println!("\n[3] 费马小定理");
+    let e = F97::new(42);
+    let p = F97::MODULUS;
+    println!("  42^{} mod {} = {}  {}", p - 1, p, e.pow(p - 1), mark(e.pow(p - 1) == F97::one()));
+    println!(
+        "  42^{} = {}，扩展欧几里得算出的逆元 = {}  {}",
+        p - 2,
+        e.pow(p - 2),
+        e.inv().unwrap(),
+        mark(Some(e.pow(p - 2)) == e.inv())
+    );
+
+    // ==========================================
+    // 4. ❌ 陷阱：模数不是素数会怎样？
+    // ==========================================
+    println!("\n[4] ❌ 陷阱：用合数 15 当模数");
+    type Z15 = Fp<15>;
+    let three = Z15::new(3);
+    let five = Z15::new(5);
+    println!("  3 * 5 = {} —— 两个非零数相乘得 0 (零因子)", three * five);
+    let (g, _, _) = ext_gcd(6, 15);
+    println!("  gcd(6, 15) = {}，所以 6 没有逆元: {:?}", g, Z15::new(6).inv());
+    println!("  结论：只有素数模数才能保证'除法永远可行'，Fp 的 P 必须是素数");
+
+    // ==========================================
+    // 5. ZK 视角：把"我知道 x"写成域上的方程
+    // ==========================================
+    // 经典例子：证明我知道 x 使得 x^3 + x + 5 = 35 (答案 x = 3)
+    println!("\n[5] ZK 视角：约束 x^3 + x + 5 = 35 在 F_97 上");
+    let constraint = |x: F97| x.pow(3) + x + F97::new(5) + F97::from_i64(-35);
+    let roots: Vec<u64> = (0..F97::MODULUS)
+        .map(F97::new)
+        .filter(|x| constraint(*x).is_zero())
+        .map(|x| x.value())
+        .collect();
+    println!("  穷举整个域，满足约束的 x: {:?}", roots);
+    println!("  三次多项式在域上最多 3 个根 —— 随便猜一个 x 蒙对的概率 ≤ 3/97");
+
+    // 线性方程永远有唯一解：a * x = b  =>  x = b / a
+    let a = F97::new(13);
+    let b = F97::new(7);
+    let x = b / a;
+    println!("  解 13 * x = 7：x = 7 / 13 = {}，验算 13 * {} = {}  {}", x, x, a * x, mark(a * x == b));
+}
+
+/*
+关键点总结：
+    1. 运算符重载 (impl Add/Sub/Mul/Div/Neg for Fp<P>)：
+        让 x.pow(3) + x + F97::new(5) 读起来像数学公式。
+        Fp 是 Copy 类型 (只是一个 u64)，所以 a + b 不会移走 a 和 b 的所有权。
+
+    2. const 泛型的类型安全：
+        F17::new(1) + F97::new(1) 直接编译失败 —— 两个不同的域不能混算。
+        而在本练习的 [4] 里，我们故意定义了 Fp<15>，编译器不会阻止 (它不知道 15 不是素数)，
+        但运行时 inv() 返回 None 暴露了问题。
+
+    3. 为什么逆元用扩展欧几里得而不是费马小定理？
+        两者结果一样 (见 [3])。扩展欧几里得是 O(log p) 次除法，
+        费马小定理是 O(log p) 次乘法 —— 但后者要求 p 是素数，前者对任何互素的数都适用，
+        而且在 [4] 里能顺便告诉你"为什么没有逆元" (gcd ≠ 1)。
+*/
@@ -0,0 +1,116 @@
+// src/s05_zk_lab/ex07_poly.rs
+use crate::common::input::read_line;
+
+use super::math::field::F97;
+use super::math::poly::Polynomial;
+
+/*
+多项式是 ZK 的"通用语言"：
+    - 一整列见证数据 (witness) 可以编码成一个多项式 (插值)
+    - "所有约束都满足" 可以变成 "某个多项式在某些点上为 0"
+    - 验证者只需在一个随机点上检查多项式等式，就能以极高概率确信整体正确
+
+本练习在 F_97 上实现：求值 (霍纳法则)、加法、乘法、拉格朗日插值，
+最后让你自己输入几个点，看插值出来的多项式是否真的穿过它们。
+*/
+
+type Poly = Polynomial<97>;
+
+// 解析一行 "x y"，失败返回 None
+fn parse_point(line: &str) -> Option<(F97, F97)> {
+    let mut parts = line.split_whitespace();
+    let x: i64 = parts.next()?.parse().ok()?;
+    let y: i64 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((F97::from_i64(x), F97::from_i64(y)))
+}
+
+fn show_interpolation(points: &[(F97, F97)]) {
+    match Poly::interpolate(points) {
+        Some(poly) => {
+            let degree = poly.degree().map_or(String::from("-∞ (零多项式)"), |d| d.to_string());
+            println!("  插值结果: p(x) = {}  (次数 {})", poly, degree);
+            for (x, y) in points {
+                let got = poly.evaluate(*x);
+                println!("    p({}) = {}  期望 {}  {}", x, got, y, if got == *y { "✅" } else { "❌" });
+            }
+        }
+        None => println!("  ❌ 存在重复的 x：两个不同的 y 不可能来自同一个函数，插值无解"),
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex07: 多项式与拉格朗日插值 (over F_97) ---");
+
+    // ==========================================
+    // 1. 基本运算
+    // ==========================================
+    let p = Poly::from_u64(&[1, 3, 2]); // 2x^2 + 3x + 1
+    let q = Poly::from_u64(&[96, 1]); //    x - 1  (96 ≡ -1)
+    println!("\n[1] 基本运算");
+    println!("  p(x) = {}   系数 (低次在前) = {:?}", p, p.coeffs().iter().map(|c| c.value()).collect::<Vec<_>>());
+    println!("  q(x) = {}", q);
+    println!("  p + q = {}", &p + &q);
+    println!("  p * q = {}", &p * &q);
+    println!("  p(5) = {}  (霍纳法则: ((2*5) + 3)*5 + 1 = 66)", p.evaluate(F97::new(5)));
+
+    // ==========================================
+    // 2. 拉格朗日基：每个基只在"自己的点"上为 1
+    // ==========================================
+    println!("\n[2] 拉格朗日基多项式 (xs = 1, 2, 3)");
+    let xs = [F97::new(1), F97::new(2), F97::new(3)];
+    for i in 0..xs.len() {
+        let basis = Poly::lagrange_basis(&xs, i);
+        let values: Vec<String> = xs.iter().map(|x| basis.evaluate(*x).to_string()).collect();
+        println!("  L{}(x) = {:<16} 在 x=1,2,3 处取值: [{}]", i, basis.to_string(), values.join(", "));
+    }
+
+    // ==========================================
+    // 3. 插值能还原多项式：从 p 上取 3 个点，插值回来应该就是 p
+    // ==========================================
+    println!("\n[3] 从 p(x) 上取 3 个点插值，应当还原 p");
+    let samples: Vec<(F97, F97)> = xs.iter().map(|&x| (x, p.evaluate(x))).collect();
+    let recovered = Poly::interpolate(&samples).expect("distinct xs");
+    println!("  还原出 {}  与 p 相等: {}", recovered, if recovered == p { "✅" } else { "❌" });
+
+    // ==========================================
+    // 4. 交互：用户自己给点
+    // ==========================================
+    println!("\n[4] 轮到你了：每行输入一个点 \"x y\" (支持负数)，空行结束");
+    println!("    什么都不输入直接回车，将使用示例点 (0,5) (1,6) (2,9) (3,14)");
+    let mut points = Vec::new();
+    loop {
+        let line = read_line(&format!("  点 #{}: ", points.len() + 1));
+        if line.is_empty() {
+            break;
+        }
+        match parse_point(&line) {
+            Some(point) => points.push(point),
+            None => println!("  ❌ 格式应为 \"x y\"，例如: 2 9"),
+        }
+    }
+    if points.is_empty() {
+        points = [(0, 5), (1, 6), (2, 9), (3, 14)]
+            .iter()
+            .map(|&(x, y)| (F97::new(x), F97::new(y)))
+            .collect();
+    }
+    show_interpolation(&points);
+}
+
+/*
+关键点总结：
+    1. n 个点唯一确定一条次数 < n 的多项式：
+        示例点 (0,5) (1,6) (2,9) (3,14) 给了 4 个点，但它们其实来自 x^2 + 5，
+        插值结果的次数是 2 而不是 3 —— 最高次系数恰好算出来是 0，被 trim 掉了。
+
+    2. 为什么只能在"域"上做插值？
+        L_i(x) 的分母是 Π (x_i - x_j)，需要做除法。在整数上除不尽，
+        在 F_p 上只要 x 互不相同，分母就非 0，逆元一定存在 (ex06 的结论)。
+
+    3. 为什么 Add/Mul 为 &Polynomial 实现？
+        Polynomial 持有 Vec，不是 Copy。如果为值类型实现，p + q 会把 p 和 q 都 move 掉，
+        后面就不能再用了。为引用实现后，&p + &q 只借用，返回一个新的 Polynomial。
+*/
@@ -0,0 +1,140 @@
+// src/s05_zk_lab/ex08_pedersen.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::commitments::pedersen::{Commitment, PedersenParams};
+use super::math::group::{random_scalar, GroupElement, Scalar, GROUP_ORDER, MODULUS};
+
+/*
+业务场景：密封拍卖 (Sealed-bid Auction)
+    每个竞拍者先提交一个"承诺"，截止后再统一打开。
+    - 承诺阶段：别人看不到你的出价 (隐藏性)
+    - 打开阶段：你不能看了别人的出价再改口 (绑定性)
+
+Pedersen 承诺 C = g^v * h^r 同时满足这两点 —— 前提是没人知道 log_g(h)。
+本练习在 q = 1019 的玩具群里演示：
+    1. 完美隐藏：同一个 C，对每一个 v 都存在能打开它的 r
+    2. 绑定被破：群太小，暴力算出 log_g(h) 后就能"改口"
+    3. 加法同态：不打开承诺也能验证 "a + b = c"
+    4. 交互：自己承诺一个数，再试着打开它
+*/
+
+fn parse_scalar(line: &str) -> Option<Scalar> {
+    line.trim().parse::<i64>().ok().map(Scalar::from_i64)
+}
+
+pub fn run() {
+    println!("--- S05 Ex08: Pedersen 承诺 (p = {}, q = {}) ---", MODULUS, GROUP_ORDER);
+    let params: PedersenParams = PedersenParams::setup();
+    let mut rng = SimpleRng::from_time();
+
+    println!("\n[0] 公共参数");
+    println!("  g = {}  (生成元)", params.g);
+    println!("  h = {}  (由字符串哈希得到，没人知道 log_g(h))", params.h);
+    // Scalar 活在 F_q 里装不下 q 本身，用 g^(q-1) * g 来算 g^q
+    let g_q = params.g.pow(Scalar::new(GROUP_ORDER - 1)) * params.g;
+    println!("  g^q = {}  (q 阶子群：指数只需 mod q)", g_q.value());
+
+    // ==========================================
+    // 1. 隐藏性：C 对每个 v 都"说得通"
+    // ==========================================
+    println!("\n[1] 隐藏性 (Hiding)");
+    let bid = Scalar::new(100);
+    let r = random_scalar(&mut rng);
+    let c = params.commit(bid, r);
+    println!("  Alice 出价 v = {}，盲因子 r = {}  =>  C = {}", bid, r, c.0);
+
+    // 上帝视角：假设有人知道陷门 x = log_g(h)，那么对任意 v'，取 r' = r + (v - v') / x 都能打开 C
+    // 这说明 C 里"没有"关于 v 的信息：每个出价都同样可能
+    let x = params.h.brute_force_log(params.g).expect("h lies in the subgroup generated by g");
+    for guess in [0u64, 1, 42, 999] {
+        let v2 = Scalar::new(guess);
+        let r2 = r + (bid - v2) / x;
+        let ok = params.verify(&c, v2, r2);
+        println!("  v' = {:>3}  r' = {:>4}  能打开 C: {}", guess, r2, if ok { "✅" } else { "❌" });
+    }
+    println!("  => 看到 C 的人 (哪怕算力无限) 也无法判断 Alice 出了多少");
+
+    // ==========================================
+    // 2. 绑定性：只在离散对数困难时成立
+    // ==========================================
+    println!("\n[2] 绑定性 (Binding) —— 以及它如何被破坏");
+    println!("  ❌ 群的阶只有 {}，暴力搜索 log_g(h) ...", GROUP_ORDER);
+    println!("  找到 x = {}，验证 g^x = {} == h = {}", x, params.g.pow(x), params.h);
+
+    // Mallory 先承诺出价 500，开标时发现最高价只有 300，想改口成 301
+    let mallory_bid = Scalar::new(500);
+    let mallory_r = random_scalar(&mut rng);
+    let mallory_c = params.commit(mallory_bid, mallory_r);
+    let forged_bid = Scalar::new(301);
+    let forged_r = mallory_r + (mallory_bid - forged_bid) / x;
+    println!("  Mallory 承诺 C = {} (v = {}, r = {})", mallory_c.0, mallory_bid, mallory_r);
+    println!(
+        "  改口打开为 v' = {}, r' = {}  验证: {}",
+        forged_bid,
+        forged_r,
+        if params.verify(&mallory_c, forged_bid, forged_r) { "✅ 通过 (拍卖被操纵!)" } else { "❌ 拒绝" }
+    );
+    println!("  ✅ 真实参数 (256-bit 椭圆曲线) 下没人能算出 x，两个不同的打开就不可能找到");
+
+    // ==========================================
+    // 3. 加法同态
+    // ==========================================
+    println!("\n[3] 加法同态：C(a, r1) * C(b, r2) = C(a + b, r1 + r2)");
+    let (a, b) = (Scalar::new(30), Scalar::new(12));
+    let (r1, r2) = (random_scalar(&mut rng), random_scalar(&mut rng));
+    let sum = params.commit(a, r1) * params.commit(b, r2);
+    println!("  C(30) * C(12) = {}", sum.0);
+    println!(
+        "  用 (42, r1 + r2) 打开乘积: {}",
+        if params.verify(&sum, a + b, r1 + r2) { "✅" } else { "❌" }
+    );
+    // 减法同样成立：乘以逆元。输入和 = 输出和 时，差值是对 0 的承诺 (机密交易就这样验证"没凭空造钱")
+    let diff = Commitment(sum.0 * params.commit(a + b, r1 + r2).0.inverse());
+    println!(
+        "  C(42) / C(30 + 12) 是否为单位元: {}",
+        if diff.0 == GroupElement::identity() { "✅ (对 0 的承诺，且盲因子也为 0)" } else { "❌" }
+    );
+
+    // ==========================================
+    // 4. 交互：承诺 -> 打开 -> 验证
+    // ==========================================
+    println!("\n[4] 轮到你了");
+    let secret = loop {
+        match parse_scalar(&read_line("  输入你要承诺的秘密整数: ")) {
+            Some(v) => break v,
+            None => println!("  ❌ 请输入整数"),
+        }
+    };
+    let blinding = random_scalar(&mut rng);
+    let commitment = params.commit(secret, blinding);
+    println!("  已承诺: C = {}  (盲因子 r = {}，请记下来)", commitment.0, blinding);
+
+    println!("  现在打开承诺 (可以试试输错值或盲因子)");
+    let value = parse_scalar(&read_line("  v = ")).unwrap_or_else(Scalar::zero);
+    let r_open = parse_scalar(&read_line("  r = ")).unwrap_or_else(Scalar::zero);
+    if params.verify(&commitment, value, r_open) {
+        println!("  ✅ 打开成功: C 确实是对 {} 的承诺", value);
+    } else {
+        println!("  ❌ 打开失败: g^{} * h^{} = {} ≠ C", value, r_open, params.commit(value, r_open).0);
+    }
+}
+
+/*
+关键点总结：
+    1. 隐藏是"完美"的，绑定是"计算"的：
+        给定 C，每个 v 都对应唯一一个 r 能打开它 (共 q 个打开)，所以 C 不泄露 v；
+        但只要知道 log_g(h)，就能在这 q 个打开之间随意切换。
+        (反过来的设计 —— 完美绑定、计算隐藏 —— 例如 C = H(v || r)，二者不可兼得)
+
+    2. h 必须"无人知晓其离散对数"：
+        如果 h = g^x 是由某个人挑选的，那个人就握着陷门，随时可以改口。
+        所以 setup 用 hash_to_group 从公开字符串派生 h (Nothing up my sleeve)。
+
+    3. 盲因子 r 必须随机且保密：
+        r 固定或可预测时，攻击者可以对候选 v 逐个计算 g^v * h^r 比对，隐藏性立刻消失。
+
+    4. 同态性是双刃剑：
+        好处：Confidential Transaction 用它在密文上验证"输入和 = 输出和"；
+        坏处：承诺是可延展的 (malleable)，拿到 C 就能造出 C * g (对 v + 1 的承诺)。
+*/
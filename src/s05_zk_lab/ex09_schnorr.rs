@@ -0,0 +1,149 @@
+// src/s05_zk_lab/ex09_schnorr.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::crypto::schnorr::{self, challenge_hash, verify_response, Keypair, SchnorrProver};
+use super::math::group::{random_scalar, GroupElement, Scalar};
+use super::transcript::session;
+
+/*
+业务场景：无密码登录 (Passwordless Login)
+    服务器只存用户的公钥 P = g^x。登录时用户不发送 x，
+    而是现场证明"我知道 x" —— 服务器就算被拖库，攻击者也拿不到任何能用来登录的东西。
+
+本练习：
+    1. 交互式身份认证：你来扮演验证者，亲手出挑战
+    2. 作弊者为什么过不了：不知道 x 时，必须在看到挑战之前"猜中"它
+    3. 模拟器：如果能先看挑战再发承诺，谁都能伪造 —— 两份 transcript 并排，分不出哪份是真的 (零知识)
+    4. Schnorr 签名：Fiat-Shamir 把挑战换成哈希
+    5. ❌ nonce 复用：同一个 k 签两条消息，私钥当场泄露
+*/
+
+fn parse_scalar(line: &str) -> Option<Scalar> {
+    line.trim().parse::<i64>().ok().map(Scalar::from_i64)
+}
+
+pub fn run() {
+    println!("--- S05 Ex09: Schnorr 身份认证与签名 ---");
+    let mut rng = SimpleRng::from_time();
+    let g = GroupElement::generator();
+    let alice: Keypair = Keypair::generate(&mut rng);
+    println!("\nAlice 的公钥 P = g^x = {}  (私钥 x 只有她知道)", alice.public);
+
+    // ==========================================
+    // 1. 交互式会话：承诺 -> 挑战 -> 响应
+    // ==========================================
+    println!("\n[1] 交互式身份认证 (你是验证者)");
+    let (alice_session, you) = session("Schnorr (真实)", true);
+    let (prover, r) = SchnorrProver::commit(&alice, &mut rng);
+    let r = alice_session.send("承诺 R = g^k", r);
+    let line = read_line("  输入挑战 c (直接回车则随机生成): ");
+    let c = you.send("挑战 c", parse_scalar(&line).unwrap_or_else(|| random_scalar(&mut rng)));
+    let s = alice_session.send("响应 s = k + c*x", prover.respond(c));
+    // prover.respond(c) 再调用一次会编译失败：prover 已被 move，nonce 不可能被复用
+    let label = format!("g^s = {}  vs  R * P^c = {}", g.pow(s), r * alice.public.pow(c));
+    let ok = you.check(&label, verify_response(alice.public, r, c, s));
+    println!("  结论: {}", if ok { "✅ Alice 确实知道 x" } else { "❌ 验证失败" });
+    let real = you.finish();
+
+    // ==========================================
+    // 2. 不知道 x 的冒充者
+    // ==========================================
+    println!("\n[2] 冒充者 Eve (不知道 x) 试 1000 次");
+    // Eve 的最佳策略：先猜一个挑战 c'，提前算好能通过 c' 的 (R, s)；验证者的 c 恰好等于 c' 才能过
+    let mut passed = 0;
+    for _ in 0..1000 {
+        let guess = random_scalar(&mut rng);
+        let fake_s = random_scalar(&mut rng);
+        let fake_r = g.pow(fake_s) * alice.public.pow(guess).inverse();
+        let challenge = random_scalar(&mut rng);
+        if verify_response(alice.public, fake_r, challenge, fake_s) {
+            passed += 1;
+        }
+    }
+    println!("  通过 {} 次 (理论概率 1/q ≈ 0.1%)", passed);
+
+    // ==========================================
+    // 3. 模拟器：先知道挑战，就能凭空造出合法 transcript
+    // ==========================================
+    println!("\n[3] 模拟器 (Simulator)：先选 c 和 s，再倒推 R = g^s * P^(-c)");
+    let sim_c = random_scalar(&mut rng);
+    let sim_s = random_scalar(&mut rng);
+    let sim_r = g.pow(sim_s) * alice.public.pow(sim_c).inverse();
+    // 生成顺序是 c, s, R，但记录时按协议顺序排好 —— 旁观者只能看到这份记录
+    let (sim_prover, sim_verifier) = session("Schnorr (模拟，不知道 x)", false);
+    sim_prover.send("承诺 R = g^k", sim_r);
+    sim_verifier.send("挑战 c", sim_c);
+    sim_prover.send("响应 s = k + c*x", sim_s);
+    let label = format!("g^s = {}  vs  R * P^c = {}", g.pow(sim_s), sim_r * alice.public.pow(sim_c));
+    sim_verifier.check(&label, verify_response(alice.public, sim_r, sim_c, sim_s));
+    real.dump();
+    sim_verifier.finish().dump();
+    println!("  => 这种 transcript 不需要 x 也能造，且分布与真实的一样：所以看 transcript 学不到 x");
+    println!("     协议安全的关键只有一个：R 必须在 c 之前被固定下来");
+
+    // ==========================================
+    // 4. 签名：用哈希代替验证者
+    // ==========================================
+    println!("\n[4] Schnorr 签名 (Fiat-Shamir)");
+    let mut msg = read_line("  输入要签名的消息 (直接回车使用 \"transfer 10 coins to Bob\"): ");
+    if msg.is_empty() {
+        msg = String::from("transfer 10 coins to Bob");
+    }
+    let sig = schnorr::sign(&alice, msg.as_bytes(), &mut rng);
+    println!("  签名 (R, s) = ({}, {})", sig.r, sig.s);
+    println!("  c = H(R || P || msg) = {}", challenge_hash(sig.r, alice.public, msg.as_bytes()));
+    let ok = schnorr::verify(alice.public, msg.as_bytes(), &sig);
+    println!("  原消息验证: {}", if ok { "✅" } else { "❌" });
+    let tampered = format!("{}!", msg);
+    let ok = schnorr::verify(alice.public, tampered.as_bytes(), &sig);
+    println!("  篡改为 \"{}\" 后验证: {}", tampered, if ok { "✅ (哈希碰撞，玩具群 q 太小)" } else { "❌ 拒绝" });
+    let mallory = Keypair::generate(&mut rng);
+    let ok = schnorr::verify(mallory.public, msg.as_bytes(), &sig);
+    println!("  换成 Mallory 的公钥验证: {}", if ok { "✅ (玩具群碰撞)" } else { "❌ 拒绝" });
+
+    // ==========================================
+    // 5. 陷阱：nonce 复用
+    // ==========================================
+    println!("\n[5] ❌ 陷阱：一个有 bug 的签名器对两条消息用了同一个 k");
+    // 这里绕过 SchnorrProver 手工计算，模拟"k 来自坏掉的随机数生成器"
+    let k = random_scalar(&mut rng);
+    let r = g.pow(k);
+    let c1 = challenge_hash(r, alice.public, b"msg one");
+    let c2 = challenge_hash(r, alice.public, b"msg two");
+    let s1 = k + c1 * alice.secret;
+    let s2 = k + c2 * alice.secret;
+    println!("  签名1: (R = {}, s1 = {})  签名2: (R = {}, s2 = {})  —— R 一样！", r, s1, r, s2);
+    if c1 == c2 {
+        println!("  (两个挑战恰好相同，玩具群里偶尔会发生，这次攻击者运气不好)");
+    } else {
+        // s1 - s2 = (c1 - c2) * x  =>  x = (s1 - s2) / (c1 - c2)
+        let recovered = (s1 - s2) / (c1 - c2);
+        println!(
+            "  攻击者计算 x = (s1 - s2) / (c1 - c2) = {}  真实私钥 = {}  {}",
+            recovered,
+            alice.secret,
+            if Keypair::<GroupElement>::from_secret(recovered).public == alice.public { "💀 私钥泄露" } else { "" }
+        );
+    }
+}
+
+/*
+关键点总结：
+    1. 三个性质：
+        完备性 (Completeness)：诚实的 Alice 总能通过 —— g^(k+cx) = R * P^c。
+        可靠性 (Soundness)   ：同一个 R 若能回答两个不同的 c，就能解出 x (第 5 节的公式)，
+                              所以不知道 x 的人最多只能以 1/q 的概率蒙对 (第 2 节)。
+        零知识 (Zero-Knowledge)：模拟器不用 x 也能造出同分布的 transcript (第 3 节)。
+
+    2. 第 5 节与可靠性是同一个公式：
+        "两个挑战 + 同一个承诺 => 提取出 x" 既是安全证明的工具 (提取器)，
+        也是 nonce 复用漏洞的根源 (PS3 的 ECDSA 私钥就是这么泄露的)。
+
+    3. 用类型系统防止 nonce 复用：
+        SchnorrProver::respond 获取 self 的所有权，调用一次后 prover 就被 move 掉了。
+        S01 学的所有权规则，在这里变成了一条密码学安全保证。
+
+    4. Fiat-Shamir 的哈希必须包含 R：
+        如果 c 不依赖 R，攻击者就能先拿到 c 再用第 3 节的模拟器倒推 R —— 签名可以随意伪造。
+*/
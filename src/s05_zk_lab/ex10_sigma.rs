@@ -0,0 +1,113 @@
+// src/s05_zk_lab/ex10_sigma.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::math::group::{random_scalar, GroupElement, Scalar};
+use super::sigma::compose::{And, Either, Or};
+use super::sigma::dlog::{DleqStatement, DlogEquality, DlogKnowledge};
+use super::sigma::{run_protocol, SigmaProtocol};
+
+/*
+业务场景：环签名式的匿名投票资格
+    "我是 Alice 或 Bob 之一" —— 验证者确信你有资格，却不知道你是谁。
+
+ex09 把 Schnorr 写死成了一套函数。本练习把它抽象成 SigmaProtocol trait：
+    1. DLOG  ：Schnorr，换成 trait 实现后用通用运行器跑
+    2. DLEQ  ：证明两个公钥背后是同一把私钥
+    3. AND   ：同时知道两把私钥
+    4. OR    ：知道其中一把就够，而且验证者看不出是哪一把 (你来选)
+    5. 模拟器：任何协议都能"先看挑战再造承诺"
+*/
+
+// 通用函数：对任意协议，用模拟器伪造一份 transcript 并交给 verify
+fn simulate_and_check<P: SigmaProtocol>(statement: &P::Statement, rng: &mut SimpleRng) {
+    let c = P::challenge(rng);
+    let (a, z) = P::simulate(statement, c, rng);
+    let ok = P::verify(statement, &a, c, &z);
+    println!("  [{}] 模拟 transcript: a = {}, c = {}, z = {}  验证: {}", P::NAME, a, c, z, if ok { "✅" } else { "❌" });
+}
+
+pub fn run() {
+    println!("--- S05 Ex10: Sigma 协议框架 (trait + 组合) ---");
+    let mut rng = SimpleRng::from_time();
+    let g = GroupElement::generator();
+
+    let x1 = random_scalar(&mut rng);
+    let x2 = random_scalar(&mut rng);
+    let (p1, p2) = (g.pow(x1), g.pow(x2));
+    println!("\nAlice 公钥 P1 = {}，Bob 公钥 P2 = {}", p1, p2);
+
+    // ==========================================
+    // 1. DLOG
+    // ==========================================
+    println!("\n[1] 离散对数知识 (Schnorr)");
+    run_protocol::<DlogKnowledge>(&p1, &x1, &mut rng);
+
+    // ==========================================
+    // 2. DLEQ
+    // ==========================================
+    println!("\n[2] 离散对数相等 (Chaum-Pedersen)");
+    let g2 = GroupElement::hash_to_group("rust-zk-lab/sigma/g2");
+    let honest = DleqStatement { g1: g, h1: p1, g2, h2: g2.pow(x1) };
+    println!("  陈述: {}", honest);
+    run_protocol::<DlogEquality>(&honest, &x1, &mut rng);
+
+    // 陈述为假：h2 用的是另一个指数。证明者即便知道 x1 也无法让两个等式同时成立
+    let lying = DleqStatement { h2: g2.pow(x1 + Scalar::one()), ..honest };
+    println!("  ❌ 假陈述: {}  (第二个指数是 x1 + 1)", lying);
+    run_protocol::<DlogEquality>(&lying, &x1, &mut rng);
+
+    // ==========================================
+    // 3. AND
+    // ==========================================
+    println!("\n[3] AND：同时知道 P1 和 P2 的私钥");
+    run_protocol::<And<DlogKnowledge, DlogKnowledge>>(&(p1, p2), &(x1, x2), &mut rng);
+
+    // ==========================================
+    // 4. OR
+    // ==========================================
+    println!("\n[4] OR：知道 P1 或 P2 其中之一的私钥");
+    let witness = loop {
+        match read_line("  你想扮演谁？1 = Alice (知道 x1)，2 = Bob (知道 x2)，直接回车默认 1: ").as_str() {
+            // stdin 关了 (EOF) 时 read_line 也返回空串：当作回车，别再循环
+            "" | "1" => break Either::Left(x1),
+            "2" => break Either::Right(x2),
+            _ => println!("  ❌ 请输入 1 或 2"),
+        }
+    };
+    run_protocol::<Or<DlogKnowledge, DlogKnowledge>>(&(p1, p2), &witness, &mut rng);
+    println!("  => 两个分支都有合法的 (c_i, z_i)，c_left + c_right = c；哪一支是模拟出来的，验证者无从分辨");
+
+    // 不知道任何一个私钥的人只能两支都模拟，但两个挑战必须在看到 c 之前就选好，
+    // 它们的和恰好等于 c 的概率只有 1/q
+    let (real_c, fake_c) = (Or::<DlogKnowledge, DlogKnowledge>::challenge(&mut rng), random_scalar(&mut rng));
+    let (a, z) = Or::<DlogKnowledge, DlogKnowledge>::simulate(&(p1, p2), fake_c, &mut rng);
+    let ok = Or::<DlogKnowledge, DlogKnowledge>::verify(&(p1, p2), &a, real_c, &z);
+    println!("  ❌ Eve 两支都伪造 (预先猜的 c = {}, 实际 c = {}): {}", fake_c, real_c, if ok { "✅ 蒙对了" } else { "❌ 拒绝" });
+
+    // ==========================================
+    // 5. 模拟器：零知识的来源
+    // ==========================================
+    println!("\n[5] 模拟器：不用任何 witness，只要能先选挑战，每个协议都能造出合法 transcript");
+    simulate_and_check::<DlogKnowledge>(&p1, &mut rng);
+    simulate_and_check::<DlogEquality>(&honest, &mut rng);
+    simulate_and_check::<And<DlogKnowledge, DlogKnowledge>>(&(p1, p2), &mut rng);
+}
+
+/*
+关键点总结：
+    1. 关联类型 (Associated Types) 让一个 trait 描述一整族协议：
+        每个协议自己决定 Statement / Witness / Commitment 长什么样，
+        run_protocol::<P> 和 And<A, B> 只依赖 trait，不关心具体类型。
+
+    2. 为什么方法都没有 self？
+        协议本身是"规则"，没有数据；DlogKnowledge 是零大小的单元结构体，
+        And<A, B> 里只有 PhantomData。调用写成 P::commit(...)，零运行时开销。
+
+    3. OR 组合的关键是 simulate：
+        真实分支走 commit/respond，另一支用模拟器预先伪造，
+        挑战被拆成 c = c_left + c_right，证明者只能自由选择其中一个。
+
+    4. 组合出来的还是 Sigma 协议：
+        And<Or<A, B>, C> 这样的嵌套开箱即用 —— 复杂的"资格证明"就是这样搭积木搭出来的。
+*/
@@ -0,0 +1,124 @@
+// src/s05_zk_lab/ex11_circuit.rs
+use crate::common::input::read_line;
+
+use super::circuit::{Circuit, Gate, Wire};
+use super::math::field::F97;
+
+/*
+业务场景：证明"我知道方程 x^3 + x + 5 = 35 的解"，但不说出 x
+
+ZK 证明系统的流水线：
+    高层程序  ->  算术电路 (本练习)  ->  R1CS 约束  ->  多项式  ->  证明
+本练习只走前两步：用 Circuit 构建器写出电路，求值得到每根导线的值，
+再编译成 R1CS，检查这组值是否满足全部约束。
+*/
+
+// 构建 x^3 + x + 5 == out
+fn cubic_circuit() -> Circuit<97> {
+    let mut c = Circuit::new();
+    let out = c.public_input("out");
+    let x = c.private_input("x");
+    let x2 = c.mul(x, x);
+    let x3 = c.mul(x2, x);
+    let sum = c.add(x3, x);
+    let five = c.constant(5);
+    let result = c.add(sum, five);
+    c.assert_eq(result, out);
+    c
+}
+
+fn show_values(circuit: &Circuit<97>, wires: &[F97]) {
+    let values: Vec<String> = (0..circuit.num_wires())
+        .map(|i| format!("{}={}", circuit.wire_name(Wire(i)), wires[i]))
+        .collect();
+    println!("  z = [{}]", values.join(", "));
+}
+
+pub fn run() {
+    println!("--- S05 Ex11: 算术电路 DSL -> R1CS ---");
+
+    // ==========================================
+    // 1. 写电路
+    // ==========================================
+    let circuit = cubic_circuit();
+    let muls = circuit.gates().iter().filter(|g| matches!(g, Gate::Mul { .. })).count();
+    println!(
+        "\n[1] 电路 x^3 + x + 5 == out  ({} 个输入, {} 根导线, {} 个门, 其中乘法门 {} 个)",
+        circuit.inputs().len(),
+        circuit.num_wires(),
+        circuit.gates().len(),
+        muls
+    );
+    print!("{}", circuit);
+
+    // ==========================================
+    // 2. 编译成 R1CS
+    // ==========================================
+    let r1cs = circuit.to_r1cs();
+    let name = |i: usize| circuit.wire_name(Wire(i));
+    println!("\n[2] R1CS：{} 个变量，{} 条约束", r1cs.num_vars, r1cs.constraints.len());
+    let public: Vec<String> = r1cs.public.iter().map(|&i| name(i)).collect();
+    println!("  公开变量: [1, {}]，其余为见证", public.join(", "));
+    for i in 0..r1cs.constraints.len() {
+        println!("  #{} {}", i, r1cs.format_constraint(i, name));
+    }
+
+    // ==========================================
+    // 3. 正确的见证
+    // ==========================================
+    println!("\n[3] 见证 x = 3, out = 35");
+    let wires = circuit.evaluate(&[F97::new(35)], &[F97::new(3)]).expect("input counts match");
+    show_values(&circuit, &wires);
+    println!("  R1CS 满足: {}", if r1cs.is_satisfied(&wires) { "✅" } else { "❌" });
+
+    // ==========================================
+    // 4. 错误的见证 / 被篡改的导线
+    // ==========================================
+    println!("\n[4] ❌ 错误的见证 x = 4 (4^3 + 4 + 5 = 73 ≠ 35)");
+    let wires = circuit.evaluate(&[F97::new(35)], &[F97::new(4)]).expect("input counts match");
+    match r1cs.first_unsatisfied(&wires) {
+        Some(i) => println!("  第 #{} 条约束不满足: {}", i, r1cs.format_constraint(i, name)),
+        None => println!("  ✅ 全部满足 (不应该发生)"),
+    }
+
+    println!("\n[4'] ❌ 作弊：x = 4，但把中间导线 x^3 改成 26，让最终结果凑成 35");
+    let mut forged = wires.clone();
+    forged[4] = F97::new(26); // w4 = x^3
+    forged[5] = F97::new(30); // w5 = x^3 + x
+    forged[7] = F97::new(35); // w7 = w5 + 5
+    match r1cs.first_unsatisfied(&forged) {
+        Some(i) => println!("  最终断言过了，但第 #{} 条约束露馅: {}", i, r1cs.format_constraint(i, name)),
+        None => println!("  ✅ 全部满足 (不应该发生)"),
+    }
+
+    // ==========================================
+    // 5. 交互：自己找解
+    // ==========================================
+    println!("\n[5] 轮到你了：在 F_97 里，x^3 + x + 5 = out");
+    let out: u64 = read_line("  输入公开的 out (直接回车默认 35): ").parse().unwrap_or(35);
+    let x: u64 = read_line("  输入你的私有见证 x: ").parse().unwrap_or(0);
+    let wires = circuit.evaluate(&[F97::new(out)], &[F97::new(x)]).expect("input counts match");
+    show_values(&circuit, &wires);
+    match r1cs.first_unsatisfied(&wires) {
+        None => println!("  ✅ 见证有效：你确实知道 out = {} 的一个解", out),
+        Some(i) => println!("  ❌ 第 #{} 条约束不满足: {}", i, r1cs.format_constraint(i, name)),
+    }
+}
+
+/*
+关键点总结：
+    1. 电路 = 计算的"拍扁"：没有分支、没有循环，只有加法门和乘法门。
+        x^3 需要两个乘法门 (x*x, 再 *x)，因为 R1CS 每条约束只能做一次乘法。
+
+    2. 求值和检查是两回事：
+        evaluate 只是"照着算"，它不管结果对不对；
+        R1CS 只做检查，不做计算 —— 验证者手里只有约束，没有电路的执行过程。
+
+    3. 为什么篡改中间导线也会被发现？
+        每个门都变成了一条约束，"x^3 = 26"和"x * x^2 = x^3"对不上。
+        证明者必须给出【每一根】导线的值，而它们要同时满足所有约束。
+
+    4. 加法门其实很浪费：
+        (l + r) * 1 = out 专门占一条约束。真实的编译器会把加法折叠进下一个乘法门的线性组合里，
+        R1CS 的成本只由乘法门决定。
+*/
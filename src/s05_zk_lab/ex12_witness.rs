@@ -0,0 +1,139 @@
+// src/s05_zk_lab/ex12_witness.rs
+use crate::common::input::read_line;
+
+use super::circuit::witness::generate_witness;
+use super::circuit::{Circuit, Gate, Wire};
+use super::math::field::F97;
+
+/*
+业务场景：证明"我知道一个直角三角形的两条直角边"
+    公开：斜边 c    私有：a, b    约束：a^2 + b^2 + 7 == c^2 + 7   (+7 只是为了引入一个常量门)
+
+ex11 里 evaluate 要求按顺序传入 [公开输入] 和 [私有输入]，顺序错了就悄悄算出错误结果。
+本练习改用见证生成器：
+    1. 按名字赋值，拓扑顺序自动求值，打印每一步
+    2. 缺输入、写错名字、同一个输入给了两个值 —— 都变成明确的错误，而不是一个错的 z
+    3. 交互：自己输入 "a=3 b=4 c=5"
+*/
+
+type Assignment<'a> = Vec<(&'a str, F97)>;
+
+fn pythagoras_circuit() -> Circuit<97> {
+    let mut circuit = Circuit::new();
+    let c = circuit.public_input("c");
+    let a = circuit.private_input("a");
+    let b = circuit.private_input("b");
+    let a2 = circuit.mul(a, a);
+    let b2 = circuit.mul(b, b);
+    let c2 = circuit.mul(c, c);
+    let seven = circuit.constant(7);
+    let lhs = circuit.add(a2, b2);
+    let lhs = circuit.add(lhs, seven);
+    let rhs = circuit.add(c2, seven);
+    circuit.assert_eq(lhs, rhs);
+    circuit
+}
+
+// 解析 "a=3 b=4 c=5"
+fn parse_assignment(line: &str) -> Option<Assignment<'_>> {
+    line.split_whitespace()
+        .map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            Some((name, F97::from_i64(value.parse().ok()?)))
+        })
+        .collect()
+}
+
+fn describe_gate(circuit: &Circuit<97>, gate: &Gate<97>, values: &[F97]) -> String {
+    let n = |w: Wire| circuit.wire_name(w);
+    match *gate {
+        Gate::Const { value, out } => format!("{} = {}", n(out), value),
+        Gate::Add { left, right, out } => {
+            format!("{} = {} + {} = {} + {} = {}", n(out), n(left), n(right), values[left.0], values[right.0], values[out.0])
+        }
+        Gate::Mul { left, right, out } => {
+            format!("{} = {} * {} = {} * {} = {}", n(out), n(left), n(right), values[left.0], values[right.0], values[out.0])
+        }
+        Gate::AssertEq { left, right } => {
+            format!("assert {} == {}  ({} vs {})", n(left), n(right), values[left.0], values[right.0])
+        }
+    }
+}
+
+fn try_assignment(circuit: &Circuit<97>, assignment: &[(&str, F97)]) {
+    let shown: Vec<String> = assignment.iter().map(|(n, v)| format!("{}={}", n, v)).collect();
+    println!("  赋值: {}", shown.join(" "));
+    match generate_witness(circuit, assignment) {
+        Ok(witness) => {
+            for (step, &g) in witness.order.iter().enumerate() {
+                println!("    step {}: 门 #{}  {}", step, g, describe_gate(circuit, &circuit.gates()[g], &witness.values));
+            }
+            let r1cs = circuit.to_r1cs();
+            match r1cs.first_unsatisfied(&witness.values) {
+                None => println!("  ✅ 见证已生成，R1CS 全部满足"),
+                Some(i) => println!("  ❌ 见证已生成，但约束 #{} 不满足 (输入本身不是一个合法的解)", i),
+            }
+        }
+        Err(err) => println!("  ❌ 见证生成失败: {}", err),
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex12: 从电路自动生成见证 (over F_97) ---");
+    let circuit = pythagoras_circuit();
+    println!("\n电路:");
+    print!("{}", circuit);
+
+    // ==========================================
+    // 1. 正常路径：乱序赋值，拓扑求值
+    // ==========================================
+    println!("\n[1] 乱序给出输入 (b, c, a)；注意门 #6 比门 #5 先执行 —— 谁的输入先就绪谁先算");
+    try_assignment(&circuit, &[("b", F97::new(4)), ("c", F97::new(5)), ("a", F97::new(3))]);
+
+    // ==========================================
+    // 2. 三种输入错误
+    // ==========================================
+    println!("\n[2] ❌ 漏掉 b");
+    try_assignment(&circuit, &[("a", F97::new(3)), ("c", F97::new(5))]);
+
+    println!("\n[3] ❌ 把 b 写成了 B");
+    try_assignment(&circuit, &[("a", F97::new(3)), ("B", F97::new(4)), ("c", F97::new(5))]);
+
+    println!("\n[4] ❌ a 被赋值两次 (同一个值可以容忍，不同的值报错)");
+    try_assignment(&circuit, &[("a", F97::new(3)), ("b", F97::new(4)), ("a", F97::new(3)), ("c", F97::new(5))]);
+    try_assignment(&circuit, &[("a", F97::new(3)), ("b", F97::new(4)), ("a", F97::new(6)), ("c", F97::new(5))]);
+
+    // 对比：同样的错误，用 ex11 的位置式 evaluate 会发生什么
+    let swapped = circuit.evaluate(&[F97::new(3)], &[F97::new(5), F97::new(4)]).expect("input counts match");
+    println!(
+        "\n  对比: 用 evaluate 把 c 和 a 的位置放反，不会有任何报错，只是悄悄得到一个错误的 z (R1CS 满足: {})",
+        circuit.to_r1cs().is_satisfied(&swapped)
+    );
+
+    // ==========================================
+    // 5. 交互
+    // ==========================================
+    println!("\n[5] 轮到你了：输入形如 a=3 b=4 c=5 的赋值 (直接回车使用 a=5 b=12 c=13)");
+    let line = read_line("  > ");
+    let line = if line.is_empty() { String::from("a=5 b=12 c=13") } else { line };
+    match parse_assignment(&line) {
+        Some(assignment) => try_assignment(&circuit, &assignment),
+        None => println!("  ❌ 格式错误，应为 name=value，用空格分隔"),
+    }
+}
+
+/*
+关键点总结：
+    1. 拓扑求值 (Kahn 算法)：
+        每个门记录"还差几个输入"，差 0 个就进就绪队列；算完一个门，唤醒等它输出的门。
+        求值顺序由依赖决定，而不是 gates 的存储顺序：[1] 里门 #6 只依赖 w6、w7，
+        它们在门 #4 之前就全部就绪，所以门 #6 排在了门 #5 (要等 w8) 的前面。
+
+    2. 用 Result + 自定义错误枚举代替 panic / None：
+        None 只能说"失败了"，WitnessError 能说"哪个名字、哪两个值冲突了"。
+        实现 Display 之后，调用方直接 println!("{}", err) 就是一条人能看懂的报错。
+
+    3. 见证生成器 ≠ 约束检查器：
+        它只负责"算出 z"，算出来的 z 是否满足约束，仍然交给 R1CS 判断 (见 [1] 和 [5] 输错时的输出)。
+        在真实框架 (circom/arkworks) 里，这两部分也是分开的：witness calculator + constraint system。
+*/
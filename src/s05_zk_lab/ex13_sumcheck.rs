@@ -0,0 +1,141 @@
+// src/s05_zk_lab/ex13_sumcheck.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::math::field::F97;
+use super::math::multilinear::MultilinearPoly;
+use super::sumcheck::{SumcheckProver, SumcheckVerifier};
+use super::transcript::session;
+
+/*
+业务场景：外包计算 (Delegated Computation)
+    云服务器声称"这张 2^n 行的表，逐行 f1 * f2 的总和是 H"。
+    你不想自己把 2^n 行都算一遍，但又不想盲目相信它。
+
+Sum-check 让你只做 n 轮"小检查" + 1 次求值就能确信 H 是对的。
+本练习 n = 3，在 F_97 上：
+    1. 你来选：和诚实的证明者对话，还是和作弊的证明者对话
+    2. 每一轮你都可以亲手输入挑战 r_i，看单变量多项式 g_i(X) 长什么样
+    3. 最后跑 1000 次自动作弊，统计作弊者侥幸成功的概率
+*/
+
+type Mle = MultilinearPoly<97>;
+
+fn sample_tables() -> (Mle, Mle) {
+    (
+        Mle::from_u64(&[3, 1, 4, 1, 5, 9, 2, 6]),
+        Mle::from_u64(&[2, 7, 1, 8, 2, 8, 1, 8]),
+    )
+}
+
+fn random_challenge(rng: &mut SimpleRng) -> F97 {
+    F97::new(rng.gen_range(F97::MODULUS))
+}
+
+// 跑一整轮协议，所有消息都记进 transcript；verbose 时边记边打印，并允许用户输入挑战。返回验证者是否接受
+fn run_session(mut prover: SumcheckProver<97>, f1: &Mle, f2: &Mle, rng: &mut SimpleRng, verbose: bool) -> bool {
+    let (p_session, v_session) = session("sum-check", verbose);
+    let mut verifier = SumcheckVerifier::new(p_session.send("声称 H", prover.claim()));
+    for round in 1..=f1.num_vars() {
+        if verbose {
+            println!("\n  --- 第 {} 轮 ---", round);
+        }
+        let poly = p_session.send(&format!("g_{}(X)", round), prover.round_poly());
+        let (g0, g1) = (poly.evaluate(F97::zero()), poly.evaluate(F97::one()));
+        let label = format!("g_{}(0) + g_{}(1) = {} + {} = {}  vs 声明值 {}", round, round, g0, g1, g0 + g1, verifier.claim());
+        if !v_session.check(&label, verifier.check_round(&poly)) {
+            if verbose {
+                println!("  ❌ 第 {} 轮被拒绝", round);
+            }
+            return false;
+        }
+        let r = if verbose {
+            let line = read_line(&format!("  输入挑战 r_{} (直接回车随机): ", round));
+            line.parse::<i64>().map(F97::from_i64).unwrap_or_else(|_| random_challenge(rng))
+        } else {
+            random_challenge(rng)
+        };
+        let r = v_session.send(&format!("r_{}", round), r);
+        verifier.bind(&poly, r);
+        prover.receive_challenge(&poly, r);
+        if verbose {
+            println!("  新的声明值 g_{}(r_{}) = {}", round, round, verifier.claim());
+        }
+    }
+
+    if verbose {
+        println!("\n  --- 最终检查 (唯一一次查询 g) ---");
+    }
+    let point: Vec<String> = verifier.challenges().iter().map(|r| r.to_string()).collect();
+    let actual = f1.evaluate(verifier.challenges()) * f2.evaluate(verifier.challenges());
+    let label = format!("g({}) = f1 * f2 = {}  vs 最后的声明值 {}", point.join(", "), actual, verifier.claim());
+    v_session.check(&label, verifier.final_check(f1, f2))
+}
+
+pub fn run() {
+    println!("--- S05 Ex13: Sum-check 协议 (n = 3, over F_97) ---");
+    let mut rng = SimpleRng::from_time();
+    let (f1, f2) = sample_tables();
+    let true_sum = SumcheckProver::true_sum(&f1, &f2);
+    println!("\nf1 在 {{0,1}}^3 上的取值: {:?}", f1.evals().iter().map(|v| v.value()).collect::<Vec<_>>());
+    println!("f2 在 {{0,1}}^3 上的取值: {:?}", f2.evals().iter().map(|v| v.value()).collect::<Vec<_>>());
+    println!("(上帝视角) 真实的和 Σ f1*f2 = {}", true_sum);
+
+    // ==========================================
+    // 1. 交互式会话
+    // ==========================================
+    let cheating = loop {
+        match read_line("\n选择证明者: 1 = 诚实, 2 = 作弊 (声称 H + 1)，直接回车默认 1: ").as_str() {
+            // 回车和 EOF 都走默认：重问只会在 stdin 关闭时无限打印提示
+            "" | "1" => break false,
+            "2" => break true,
+            _ => println!("❌ 请输入 1 或 2"),
+        }
+    };
+    let prover = if cheating {
+        let guess = random_challenge(&mut rng);
+        println!("  (作弊者私下押注验证者会选 r = {})", guess);
+        SumcheckProver::cheating(f1.clone(), f2.clone(), true_sum + F97::one(), guess)
+    } else {
+        SumcheckProver::honest(f1.clone(), f2.clone())
+    };
+    let accepted = run_session(prover, &f1, &f2, &mut rng, true);
+    println!("\n  结论: {}", if accepted { "✅ 验证者接受" } else { "❌ 验证者拒绝 —— 作弊被抓" });
+
+    // ==========================================
+    // 2. 统计：作弊者有多大机会蒙混过关？
+    // ==========================================
+    println!("\n[统计] 作弊者自动跑 1000 次 (随机挑战)");
+    let trials = 1000;
+    let fooled = (0..trials)
+        .filter(|_| {
+            let guess = random_challenge(&mut rng);
+            let prover = SumcheckProver::cheating(f1.clone(), f2.clone(), true_sum + F97::one(), guess);
+            run_session(prover, &f1, &f2, &mut rng, false)
+        })
+        .count();
+    println!("  作弊成功 {} / {} 次", fooled, trials);
+    println!("  这个作弊者每轮只押一个点，约 3 / 97 ≈ 3.1%");
+    println!("  理论上界: n * d / |F| = 3 * 2 / 97 ≈ 6.2%  (真实系统 |F| ≈ 2^255，这个概率可以忽略)");
+}
+
+/*
+关键点总结：
+    1. 为什么作弊会被抓？
+        作弊者在第一轮就必须发一个"假"的 g_1'(X) ≠ g_1(X)。两个不同的 2 次多项式最多在 2 个点上相等，
+        验证者随机选 r_1，大概率 g_1'(r_1) ≠ g_1(r_1) —— 谎言被"传递"到下一轮的声明值里。
+        一路传到最后，验证者亲自算一次 g(r1, r2, r3)，谎言当场对不上。
+
+    2. 作弊者什么时候能赢？
+        某一轮验证者恰好抽中了 g_i' 和 g_i 的交点 —— 谎言被"洗白"，之后诚实作答即可。
+        两个不同的 d 次多项式最多有 d 个交点 (Schwartz-Zippel)，每轮概率 <= d / |F|，
+        n 轮加起来 <= n * d / |F|。F_97 太小，所以统计里能看到几十次成功。
+        (只做常数平移的作弊者反而永远赢不了：g' - g 是非零常数，没有交点)
+
+    3. 验证者的工作量：
+        n 轮，每轮检查一个常数次多项式 + 最后 1 次 g 的求值，而不是 2^n 次。
+        GKR、Spartan、HyperPlonk 都以 sum-check 为核心引擎。
+
+    4. 多线性扩展 (MLE) 是让"布尔超立方体上的表"可以在任意点求值的关键：
+        fix_first_variable 做的 (1 - r) * a + r * b，就是在两个布尔点之间做线性插值。
+*/
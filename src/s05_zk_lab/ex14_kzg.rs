@@ -0,0 +1,112 @@
+// src/s05_zk_lab/ex14_kzg.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::commitments::kzg::{mock_pairing, KzgProof, ScalarPoly, Srs};
+use super::math::group::{random_scalar, GroupElement, Scalar};
+
+/*
+业务场景：SNARK 的"压缩器"
+    ex07 里一个多项式要靠发送全部系数来传递；ex13 的 sum-check 最后要求验证者"查询一次 g"。
+    在真实的 SNARK 里，证明者先对多项式做 KZG 承诺 (1 个群元素)，
+    验证者想知道哪个点的值，证明者就在哪个点打开 (又是 1 个群元素)。
+
+本练习：
+    1. 可信设置，生成 SRS
+    2. 承诺一个 3 次多项式，在你选的点上打开并验证
+    3. ❌ 谎报取值：诚实的证明无法通过
+    4. ❌ 有毒废料泄露：知道 τ 的人可以为任意错误取值伪造证明
+*/
+
+pub fn run() {
+    println!("--- S05 Ex14: KZG 多项式承诺 (模拟配对) ---");
+    let mut rng = SimpleRng::from_time();
+
+    // ==========================================
+    // 1. 可信设置
+    // ==========================================
+    println!("\n[1] 可信设置 (Trusted Setup)");
+    let tau = loop {
+        let t = random_scalar(&mut rng);
+        if !t.is_zero() {
+            break t;
+        }
+    };
+    let srs = Srs::setup(4, tau);
+    let shown: Vec<String> = srs.powers.iter().map(|p| p.to_string()).collect();
+    println!("  SRS = [g^(τ^0) .. g^(τ^{})] = [{}]", srs.max_degree(), shown.join(", "));
+    println!("  (τ = {} 是有毒废料，仪式结束后必须销毁；下面的诚实流程不会再用到它)", tau);
+
+    // ==========================================
+    // 2. 承诺与打开
+    // ==========================================
+    let poly = ScalarPoly::from_u64(&[5, 2, 0, 3]); // 3x^3 + 2x + 5
+    let commitment = srs.commit(&poly).expect("degree within SRS");
+    println!("\n[2] 承诺 p(x) = {}", poly);
+    println!("  C = g^p(τ) = {}  (无论多项式多大，承诺都只有 1 个群元素)", commitment.0);
+
+    let z: u64 = read_line("  你想在哪个点打开？z = ").parse().unwrap_or(7);
+    let z = Scalar::new(z);
+    let (y, proof) = srs.open(&poly, z).expect("degree within SRS");
+    let (quotient, _) = poly.divide_by_linear(z);
+    println!("  y = p({}) = {}", z, y);
+    println!("  商多项式 q(x) = (p(x) - y) / (x - {}) = {}", z, quotient);
+    println!("  证明 π = g^q(τ) = {}", proof.0);
+    println!("  验证 e(C / g^y, g) == e(π, g^τ / g^z): {}", if srs.verify(&commitment, z, y, &proof) { "✅" } else { "❌" });
+
+    // ==========================================
+    // 3. 谎报取值
+    // ==========================================
+    println!("\n[3] ❌ 证明者谎称 p({}) = {}", z, y + Scalar::one());
+    let lie = y + Scalar::one();
+    let ok = srs.verify(&commitment, z, lie, &proof);
+    println!("  拿诚实的 π 去配: {}", if ok { "✅ (不应该发生)" } else { "❌ 拒绝" });
+    // 想为 lie 造证明，就要求 (p(x) - lie) / (x - z)，但它有非零余数，不是多项式
+    let shifted = &poly + &ScalarPoly::new(vec![-lie]);
+    let (_, remainder) = shifted.divide_by_linear(z);
+    println!("  (p(x) - {}) 除以 (x - {}) 的余数 = {} ≠ 0，商多项式不存在，也就造不出 π", lie, z, remainder);
+
+    // ==========================================
+    // 4. 有毒废料泄露
+    // ==========================================
+    println!("\n[4] ❌ 如果 τ 没有被销毁");
+    // π' = g^((p(τ) - lie) / (τ - z))：直接在指数上做除法，根本不需要商多项式
+    if tau == z {
+        println!("  (τ 恰好等于 z，跳过这个演示)");
+    } else {
+        let forged = KzgProof(GroupElement::generator().pow((poly.evaluate(tau) - lie) / (tau - z)));
+        let ok = srs.verify(&commitment, z, lie, &forged);
+        println!("  知道 τ 的人伪造 π' = {}，证明 p({}) = {}: {}", forged.0, z, lie, if ok { "✅ 通过 —— 承诺彻底失效" } else { "❌" });
+    }
+
+    // ==========================================
+    // 5. 配对到底 mock 了什么
+    // ==========================================
+    println!("\n[5] 模拟配对的真面目");
+    let g = GroupElement::generator();
+    let (a, b) = (Scalar::new(12), Scalar::new(34));
+    println!(
+        "  e(g^12, g^34) = {}  == 12 * 34 mod q = {}  (内部暴力求了两次离散对数，只有玩具群才能这么干)",
+        mock_pairing(g.pow(a), g.pow(b)),
+        a * b
+    );
+}
+
+/*
+关键点总结：
+    1. 为什么 KZG 是"SNARK 的积木"？
+        多项式等式 p(x) - y = q(x) * (x - z) 如果在随机点 τ 上成立，几乎可以肯定它恒成立 (Schwartz-Zippel)。
+        KZG 把"在 τ 上检查"搬进了指数里：谁都不知道 τ，却都能在 g^τ 上做检查。
+
+    2. 有毒废料 (Toxic Waste)：
+        知道 τ 就能为任意值伪造证明 (第 4 节)。Zcash、以太坊 EIP-4844 都举行过多方参与的设置仪式：
+        只要有一个参与者诚实地销毁了自己那份随机数，τ 就没人知道。
+
+    3. 本练习 mock 了配对：
+        验证方程 e(C / g^y, g) == e(π, g^τ / g^z) 的形状是真的，但 mock_pairing 靠暴力求离散对数。
+        真实的配对 (BN254、BLS12-381 曲线) 能在不知道指数的情况下计算 e(g^a, g^b) = e(g,g)^(ab)。
+
+    4. 与 Merkle 树的对比：
+        Merkle 证明的大小随数据量 log n 增长；KZG 的承诺和证明永远是 1 个群元素 ——
+        代价是可信设置和昂贵的配对运算。
+*/
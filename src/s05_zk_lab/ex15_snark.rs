@@ -0,0 +1,125 @@
+// src/s05_zk_lab/ex15_snark.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::circuit::witness::generate_witness;
+use super::circuit::Circuit;
+use super::commitments::kzg::Srs;
+use super::math::group::{random_scalar, Scalar, GROUP_ORDER};
+use super::snark;
+
+/*
+毕业设计：端到端的玩具 SNARK
+
+业务场景：隐私空投 (Private Airdrop)
+    Merkle 树的叶子是 leaf = MiMC(secret)。领取空投时你要证明"我知道某个叶子的原像"，
+    但不能公开 secret —— 否则别人拿去就能冒领。
+
+为什么不用 SHA-256？
+    SHA-256 是按位运算设计的，写成算术电路需要几万个约束。
+    MiMC 只有"加常数、立方"两种操作，在域上天然便宜：每轮只要 2 个乘法门。
+    (x -> x^3 在 F_q 上是置换，要求 gcd(3, q - 1) = 1；q = 1019 时 q - 1 = 2 * 509，满足)
+
+流水线：
+    [1] 电路      MiMC 两轮：h = ((x + c1)^3 + c2)^3，断言 h == leaf
+    [2] R1CS      电路编译
+    [3] 见证      generate_witness
+    [4] 证明      QAP -> H(x) -> KZG 承诺 -> Fiat-Shamir -> 打开
+    [5] 验证      KZG 打开 + 随机点上的 QAP 等式
+*/
+
+const ROUND_CONSTANTS: [u64; 2] = [7, 42];
+
+// 原生 (电路外) 计算，用来生成叶子
+fn mimc(x: Scalar) -> Scalar {
+    ROUND_CONSTANTS.iter().fold(x, |h, &c| {
+        let t = h + Scalar::new(c);
+        t * t * t
+    })
+}
+
+fn mimc_circuit() -> Circuit<GROUP_ORDER> {
+    let mut c = Circuit::new();
+    let leaf = c.public_input("leaf");
+    let x = c.private_input("secret");
+    let mut h = x;
+    for &rc in &ROUND_CONSTANTS {
+        let k = c.constant(rc);
+        let t = c.add(h, k);
+        let t2 = c.mul(t, t);
+        h = c.mul(t2, t);
+    }
+    c.assert_eq(h, leaf);
+    c
+}
+
+pub fn run() {
+    println!("--- S05 Ex15: 端到端玩具 SNARK (电路 -> R1CS -> QAP -> KZG) ---");
+    let mut rng = SimpleRng::from_time();
+
+    let secret = Scalar::new(read_line("  输入你的 secret (直接回车默认 123): ").parse().unwrap_or(123));
+    let leaf = mimc(secret);
+    println!("  Merkle 叶子 leaf = MiMC({}) = {}  (公开)", secret, leaf);
+
+    // [1] + [2]
+    let circuit = mimc_circuit();
+    println!("\n[1] 电路");
+    print!("{}", circuit);
+    let r1cs = circuit.to_r1cs();
+    println!("\n[2] R1CS：{} 个变量，{} 条约束", r1cs.num_vars, r1cs.constraints.len());
+
+    // [3]
+    let witness = generate_witness(&circuit, &[("leaf", leaf), ("secret", secret)]).expect("all inputs provided");
+    println!("\n[3] 见证 z = {:?}", witness.values.iter().map(|v| v.value()).collect::<Vec<_>>());
+    println!("    R1CS 满足: {}", if r1cs.is_satisfied(&witness.values) { "✅" } else { "❌" });
+
+    // 可信设置：SRS 需要覆盖 A/B/C (次数 < m) 和 H (次数 <= m - 2)
+    let tau = loop {
+        let t = random_scalar(&mut rng);
+        if !t.is_zero() {
+            break t;
+        }
+    };
+    let srs = Srs::setup(r1cs.constraints.len(), tau);
+
+    // [4]
+    println!("\n[4] 证明");
+    let proof = snark::prove(&srs, &r1cs, &witness.values).expect("valid witness");
+    println!("    证明大小: 4 个承诺 + 4 个取值 + 4 个打开证明 (与电路规模无关)");
+
+    // [5]
+    println!("\n[5] 验证 (验证者只知道电路、leaf 和证明)");
+    let ok = snark::verify(&srs, &r1cs, &[leaf], &proof);
+    println!("  结论: {}", if ok { "✅ 证明者知道 leaf 的原像" } else { "❌ 拒绝" });
+
+    // ==========================================
+    // 攻击面
+    // ==========================================
+    println!("\n[6] ❌ 拿同一个证明去领另一个叶子 (leaf + 1)");
+    let ok = snark::verify(&srs, &r1cs, &[leaf + Scalar::one()], &proof);
+    println!("  结论: {}", if ok { "✅ (不应该发生)" } else { "❌ 拒绝 —— 公开输入被绑定在证明里" });
+
+    println!("\n[7] ❌ 不知道原像的人：随便填一个 secret，见证不满足约束");
+    let fake = generate_witness(&circuit, &[("leaf", leaf), ("secret", secret + Scalar::one())]).expect("all inputs provided");
+    if snark::prove(&srs, &r1cs, &fake.values).is_none() {
+        println!("  结论: ❌ 连证明都生成不了");
+    }
+}
+
+/*
+关键点总结：
+    1. 每一层都在"压缩"：
+        程序 -> 电路 (去掉控制流)
+        电路 -> R1CS (每个门一条约束)
+        R1CS -> QAP (m 条约束变成一个整除关系 P(x) = H(x) * Z(x))
+        QAP  -> KZG (多项式变成一个群元素，整除关系只在随机点 s 上检查)
+
+    2. 为什么在随机点检查就够了？
+        如果 P(x) ≠ H(x) * Z(x)，两边之差是一个非零多项式，次数 <= 2m，
+        随机的 s 恰好是它的根的概率 <= 2m / q (Schwartz-Zippel)。s 由 Fiat-Shamir 从承诺中导出，
+        证明者在承诺之前无法预知它。
+
+    3. 这个"SNARK"离真的还差什么 (见 snark.rs 文件头)：
+        没有强制 A/B/C 来自同一组 z、没有零知识盲化、配对是模拟的。
+        Groth16 用更精巧的 SRS 结构把证明压到 3 个群元素，并同时解决前两个问题。
+*/
@@ -0,0 +1,100 @@
+// src/s05_zk_lab/ex16_stark.rs
+use crate::common::input::read_line;
+
+use super::math::field::F97;
+use super::stark::{self, StarkProof, LDE_SIZE, NUM_QUERIES, TRACE_LEN};
+
+/*
+业务场景：可验证计算 (Verifiable Computation)，StarkNet / zkVM 的核心
+    服务器声称"从 (1, 1) 出发跑 8 步斐波那契，最后一项是 21"。
+    你不想重跑 (真实场景里是上百万步的虚拟机执行)，只想花对数级的时间检查一个证明。
+
+本练习：
+    1. 诚实证明：轨迹 -> LDE -> Merkle 承诺 -> 组合多项式 -> FRI -> 查询
+    2. ❌ 谎报结果：轨迹是真的，但声称最后一项是别的数
+    3. ❌ 篡改轨迹：中间某一步算错，却照样声称 21
+    4. ❌ 篡改证明：把某次查询打开的值改掉
+
+全部挑战都来自 Fiat-Shamir 信道 (stark/mod.rs)，整个证明是非交互的。
+*/
+
+fn show_verdict(result: Result<(), String>) {
+    match result {
+        Ok(()) => println!("  结论: ✅ 验证通过"),
+        Err(reason) => println!("  结论: ❌ 拒绝 —— {}", reason),
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex16: 玩具 STARK (斐波那契轨迹 + FRI, over F_97) ---");
+    let trace = stark::fibonacci_trace(1, 1);
+    let claimed = trace[TRACE_LEN - 1];
+    println!("\n执行轨迹 a_0..a_7 = {:?}", trace.iter().map(|v| v.value()).collect::<Vec<_>>());
+    println!("公开声明: a_7 = {}", claimed);
+
+    // ==========================================
+    // 1. 诚实证明
+    // ==========================================
+    println!("\n[1] 诚实的证明者");
+    let proof = stark::prove(&trace, claimed);
+    println!("  证明大小 ≈ {} 个哈希 ({} 次查询，每次 3 条轨迹路径 + 每层 FRI 2 条路径)", proof.hash_count(), NUM_QUERIES);
+    println!("  验证：");
+    show_verdict(stark::verify(&proof));
+
+    // ==========================================
+    // 2. 谎报结果
+    // ==========================================
+    let lie: u64 = read_line(&format!("\n[2] ❌ 轨迹不变，你想谎称 a_7 等于多少？(直接回车默认 {}) ", claimed + F97::one()))
+        .parse()
+        .unwrap_or((claimed + F97::one()).value());
+    let lie = F97::new(lie);
+    if lie == claimed {
+        println!("  这就是真话，换一个数才算作弊");
+    } else {
+        // 边界约束 f(g^7) = lie 不成立，q2 = (f - lie) / (x - g^7) 不再是多项式，CP 也就不是低次的
+        let forged = stark::prove(&trace, lie);
+        println!("  验证：");
+        show_verdict(stark::verify(&forged));
+    }
+
+    // ==========================================
+    // 3. 篡改轨迹
+    // ==========================================
+    println!("\n[3] ❌ 第 5 步算错 (a_5 = 9 而不是 8)，却依然声称 a_7 = {}", claimed);
+    let mut bad_trace = trace.clone();
+    bad_trace[5] = F97::new(9);
+    let forged = stark::prove(&bad_trace, claimed);
+    println!("  验证：");
+    show_verdict(stark::verify(&forged));
+
+    // ==========================================
+    // 4. 篡改证明
+    // ==========================================
+    println!("\n[4] ❌ 拿诚实的证明，把第一次查询里 f(x) 的值 +1");
+    let mut tampered: StarkProof = proof.clone();
+    tampered.queries[0].0.values[0].0 = tampered.queries[0].0.values[0].0 + F97::one();
+    show_verdict(stark::verify(&tampered));
+
+    println!("\n(LDE 大小 {}，blowup {}：作弊的 CP 与任何低次多项式至少在大半个 D 上不同，", LDE_SIZE, LDE_SIZE / TRACE_LEN);
+    println!(" 每次查询都有很大概率踩中，{} 次查询全部躲过的概率很小)", NUM_QUERIES);
+}
+
+/*
+关键点总结：
+    1. STARK 的三步：
+        算术化  ：把"程序执行正确"变成"若干约束多项式在轨迹域上为 0"
+        组合    ：约束 ÷ 消失多项式 = 商；随机线性组合成一个 CP
+        低次测试：FRI 证明 CP 是低次多项式 —— 只要有一条约束不满足，CP 就不是
+
+    2. 为什么要低次扩展 (blowup)？
+        作弊的 CP 在轨迹域的 8 个点上也许能"看起来对"，但在 32 个点的 D 上它必然偏离任何低次多项式。
+        blowup 越大，每次查询抓住作弊的概率越高，需要的查询次数越少。
+
+    3. 与 SNARK (ex15) 的取舍：
+        STARK：透明 (无可信设置)、只依赖哈希、抗量子；证明是几十 KB 级别
+        SNARK：需要可信设置和配对；证明只有几百字节
+
+    4. 玩具化的地方：
+        F_97 太小，查询次数只有 4 次，Fiat-Shamir 用的是自制信道，Merkle 树用的是 ex01 的 mock_hash。
+        真实系统 (Stone、Winterfell、Plonky3) 用 64 位或更大的域、几十次查询，并在扩域上抽挑战。
+*/
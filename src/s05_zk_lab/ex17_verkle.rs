@@ -0,0 +1,120 @@
+// src/s05_zk_lab/ex17_verkle.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::commitments::verkle::VerkleTree;
+use super::ex01_merkle::MerkleTree;
+use super::math::group::{random_scalar, Scalar};
+
+/*
+业务场景：无状态客户端 (Stateless Client)
+    以太坊节点想在不存储全部状态的情况下验证区块：区块里要附带"每个被访问账户的存在证明"。
+    状态有上亿个账户，Merkle 证明 (十六叉 MPT 每层要带 15 个兄弟) 太大，塞满了区块。
+    Verkle 树把每层的证明压成一个打开证明，见证体积能缩小一个数量级。
+
+本练习：
+    1. 同一组账户分别建二叉 Merkle 树 (ex01) 和 4 叉 Verkle 树 (KZG 向量承诺)
+    2. 为你选的账户生成两种证明并验证
+    3. ❌ 拿证明去冒充另一个账户
+    4. 不同规模、不同宽度下的证明大小对比
+*/
+
+// 真实系统里一个哈希 32 字节，一个 BLS12-381 G1 压缩点 48 字节
+const HASH_BYTES: usize = 32;
+const GROUP_ELEMENT_BYTES: usize = 48;
+
+fn accounts(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("account-{}:balance={}", i, i * 10)).collect()
+}
+
+fn random_tau(rng: &mut SimpleRng) -> Scalar {
+    loop {
+        let t = random_scalar(rng);
+        if !t.is_zero() {
+            break t;
+        }
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex17: Verkle 树 (KZG 向量承诺) vs Merkle 树 ---");
+    let mut rng = SimpleRng::from_time();
+
+    // ==========================================
+    // 1. 建树
+    // ==========================================
+    let data = accounts(64);
+    let merkle = MerkleTree::new_iterative(data.clone());
+    let verkle = VerkleTree::new(data.clone(), 4, random_tau(&mut rng));
+    let root = verkle.root().expect("non-empty tree");
+    println!("\n[1] 64 个账户");
+    println!("  二叉 Merkle 树：根 = {}", merkle.root_hash());
+    println!("  4 叉 Verkle 树：根 = {} (一个群元素)，深度 {}", root.0, verkle.depth());
+
+    // ==========================================
+    // 2. 存在证明
+    // ==========================================
+    let index = read_line("\n[2] 想证明哪个账户？(0-63，直接回车默认 42) ")
+        .parse::<usize>()
+        .ok()
+        .filter(|&i| i < data.len())
+        .unwrap_or(42);
+    let leaf = &data[index];
+    println!("  叶子: {}", leaf);
+
+    let merkle_proof = merkle.prove(index).expect("index in range");
+    println!("  Merkle 证明：{} 个兄弟哈希", merkle_proof.siblings.len());
+    println!("    验证: {}", if merkle_proof.verify(leaf, &merkle.root_hash()) { "✅" } else { "❌" });
+
+    let verkle_proof = verkle.prove(index).expect("index in range");
+    println!("  Verkle 证明：");
+    for (level, step) in verkle_proof.steps.iter().enumerate() {
+        println!("    第 {} 层：节点承诺 C = {}，打开 p({}) = {}，π = {}", level, step.commitment.0, (index >> (2 * level)) % 4, step.value, step.opening.0);
+    }
+    println!("    (最顶层的 C 就是根，验证者已知，不用传输) 共 {} 个群元素", verkle_proof.group_elements());
+    println!("    验证: {}", if verkle_proof.verify(leaf, &root, verkle.srs()) { "✅" } else { "❌" });
+
+    // ==========================================
+    // 3. 冒充
+    // ==========================================
+    let other = &data[(index + 1) % data.len()];
+    println!("\n[3] ❌ 用同一个证明冒充 {}", other);
+    println!("  Merkle: {}", if merkle_proof.verify(other, &merkle.root_hash()) { "✅ (不应该发生)" } else { "❌ 拒绝" });
+    println!("  Verkle: {}", if verkle_proof.verify(other, &root, verkle.srs()) { "✅ (不应该发生)" } else { "❌ 拒绝" });
+
+    // ==========================================
+    // 4. 证明大小对比
+    // ==========================================
+    println!("\n[4] 单个叶子的证明大小 (哈希按 {} 字节、群元素按 {} 字节计)", HASH_BYTES, GROUP_ELEMENT_BYTES);
+    println!("  {:>6} | {:>16} | {:>16} | {:>16}", "叶子数", "二叉 Merkle", "4 叉 Verkle", "16 叉 Verkle");
+    let tau = random_tau(&mut rng);
+    for n in [16, 64, 256, 1024] {
+        let data = accounts(n);
+        let merkle_bytes = MerkleTree::new_iterative(data.clone()).prove(0).expect("non-empty").siblings.len() * HASH_BYTES;
+        let verkle_bytes = |width| {
+            let proof = VerkleTree::new(data.clone(), width, tau).prove(0).expect("non-empty");
+            proof.group_elements() * GROUP_ELEMENT_BYTES
+        };
+        println!("  {:>6} | {:>14} B | {:>14} B | {:>14} B", n, merkle_bytes, verkle_bytes(4), verkle_bytes(16));
+    }
+    println!("  逐层打开时，Verkle 要足够宽才能赢过二叉 Merkle (群元素比哈希大)；");
+    println!("  但同样 16 叉的 Merkle 树每层要带 15 个兄弟：1024 个叶子时是 3 * 15 * 32 = 1440 B");
+}
+
+/*
+关键点总结：
+    1. 证明大小从哪里省下来的？
+        Merkle：每层带上所有兄弟，宽度 k 的树证明是 (k-1) * log_k(n) 个哈希 —— 加宽反而更大
+        Verkle：每层只带 1 个打开证明 (+ 1 个中间承诺)，证明是 O(log_k(n)) 个群元素 —— 越宽越小
+        代价是宽度 k 越大，更新一个叶子时重新承诺的成本越高 (插值 + k 次群运算)。
+
+    2. 逐层打开还不是终点：
+        真实的 Verkle 树用多重证明 (multiproof) 把所有层、甚至一个区块里所有账户的打开聚合成一个，
+        证明体积几乎与访问的账户数无关。
+
+    3. 为什么孩子的承诺要先"哈希成标量"？
+        KZG 承诺的是标量 (多项式的取值)，而孩子是群元素。
+        以太坊的 Verkle 提案用 Pedersen/IPA 承诺，同样需要一个"群元素 -> 标量"的映射 (map_to_field)。
+
+    4. 对比 ex01：Merkle 树只依赖哈希，没有可信设置；KZG 版的 Verkle 树需要 SRS。
+*/
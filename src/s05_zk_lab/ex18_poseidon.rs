@@ -0,0 +1,113 @@
+// src/s05_zk_lab/ex18_poseidon.rs
+use std::time::Instant;
+
+use crate::common::input::read_line;
+
+use super::ex01_merkle::{Hasher, MerkleTree, PrefixedHasher};
+use super::hash::{hash_pair, sha256};
+use super::math::field::M31;
+use super::poseidon::{self, PoseidonHasher};
+
+/*
+业务场景：ZK 友好的 Merkle 树 (Tornado Cash、Zcash Sapling、StarkNet 的状态树)
+    ex15 的隐私空投里，证明者要在电路里重算一条 Merkle 路径。
+    路径上每一层都是一次哈希 —— 哈希在电路里有多贵，证明就有多慢。
+
+为什么 SHA-256 在电路里很贵？
+    它是为 CPU 设计的：32 位字上的异或、循环移位、模 2^32 加法。
+    电路只会做域上的加法和乘法，每个"位"都要拆成一个变量并约束它是 0 或 1，
+    一次压缩函数大约 2.7 万个 R1CS 约束。
+    Poseidon 直接在域上做 x^5 和线性混合，一次 2 -> 1 压缩只要一百来个乘法。
+
+本练习：
+    1. 看一眼 Poseidon 的雪崩效应
+    2. 把 ex01 的 Merkle 树换成 Poseidon，证明照样能验证
+    3. 电路成本 vs CPU 速度：两种哈希各有主场
+*/
+
+// 常被引用的估算值：SHA-256 一次压缩在 R1CS 里约 27,000 个约束
+const SHA256_CONSTRAINTS: usize = 27_000;
+
+pub fn run() {
+    println!("--- S05 Ex18: Poseidon 海绵哈希 (域原生哈希) ---");
+    println!(
+        "参数: F_(2^31 - 1)，宽度 {} (rate {} + capacity {})，{} 个全轮 + {} 个部分轮，S-box x^5",
+        poseidon::WIDTH,
+        poseidon::RATE,
+        poseidon::WIDTH - poseidon::RATE,
+        poseidon::FULL_ROUNDS,
+        poseidon::PARTIAL_ROUNDS
+    );
+
+    // ==========================================
+    // 1. 雪崩效应
+    // ==========================================
+    let x: u64 = read_line("\n[1] 输入一个数 x (直接回车默认 42): ").parse().unwrap_or(42);
+    let x = M31::new(x);
+    let y = x + M31::one();
+    println!("  Poseidon([{}]) = {}", x, poseidon::hash(&[x]));
+    println!("  Poseidon([{}]) = {}  (输入只差 1，输出毫无关系)", y, poseidon::hash(&[y]));
+    println!("  Poseidon([{}, 0]) = {}  (capacity 里记了长度，补 0 也换不来同一个哈希)", x, poseidon::hash(&[x, M31::zero()]));
+
+    // ==========================================
+    // 2. 换掉 Merkle 树的哈希函数
+    // ==========================================
+    println!("\n[2] 同一批交易，两种哈希");
+    let txs: Vec<String> = (1..=8).map(|i| format!("Tx{}: deposit {}", i, i * 100)).collect();
+    let index = 5;
+    for hasher in [&PrefixedHasher as &dyn Hasher, &PoseidonHasher] {
+        let tree = MerkleTree::with_hasher(txs.clone(), hasher);
+        let proof = tree.prove(index).expect("index in range");
+        let ok = proof.verify_with(&tree.leaves[index], &tree.root_hash(), hasher);
+        println!("  {:<16} 根 = {:<18} {} 的证明: {}", hasher.name(), tree.root_hash(), tree.leaves[index], if ok { "✅" } else { "❌" });
+    }
+    let tree = MerkleTree::with_hasher(txs, PoseidonHasher);
+    let proof = tree.prove(index).expect("index in range");
+    println!("  ❌ 验证者用错哈希函数 (拿默认的 mock_hash 去验 Poseidon 树): {}", if proof.verify(&tree.leaves[index], &tree.root_hash()) { "✅ (不应该发生)" } else { "❌ 拒绝" });
+
+    // ==========================================
+    // 3. 成本对比
+    // ==========================================
+    let depth = 20; // 约一百万个叶子
+    let poseidon_cost = poseidon::multiplications_per_permutation();
+    println!("\n[3] 在电路里证明一条深度 {} 的 Merkle 路径 (约 100 万个叶子)", depth);
+    println!("  SHA-256 : {:>6} 约束/次 x {} = {:>7}", SHA256_CONSTRAINTS, depth, SHA256_CONSTRAINTS * depth);
+    println!("  Poseidon: {:>6} 乘法/次 x {} = {:>7}  (约 {} 倍的差距)", poseidon_cost, depth, poseidon_cost * depth, SHA256_CONSTRAINTS / poseidon_cost);
+
+    // 到了 CPU 上就没有几百倍的差距了：SHA-256 在 release 构建下有专门优化 (甚至硬件指令)
+    let rounds = 20_000;
+    let start = Instant::now();
+    let mut digest = sha256(b"seed");
+    for _ in 0..rounds {
+        digest = hash_pair(&digest, &digest);
+    }
+    let sha_time = start.elapsed();
+    let start = Instant::now();
+    let mut h = M31::one();
+    for _ in 0..rounds {
+        h = poseidon::hash(&[h, h]);
+    }
+    let poseidon_time = start.elapsed();
+    println!("\n  CPU 上各做 {} 次 2 -> 1 压缩 (debug 构建下仅供参考):", rounds);
+    println!("  SHA-256 : {:?} (最后一个摘要开头 {:02x}{:02x})", sha_time, digest[0], digest[1]);
+    println!("  Poseidon: {:?} (最后一个输出 {})", poseidon_time, h);
+}
+
+/*
+关键点总结：
+    1. "ZK 友好"指的是电路里便宜，不是 CPU 上快：
+        SHA-256 的位运算在电路里要逐位拆解；Poseidon 的 x^5 在域上就是 3 个乘法门，
+        线性混合只有加法，在 R1CS 里完全免费 (并进线性组合)。
+
+    2. 海绵结构：
+        rate 部分负责吸收输入、挤出输出；capacity 部分从不直接暴露，它的大小决定安全性。
+        用 capacity 记录输入长度，避免 [a] 和 [a, 0] 撞车。
+
+    3. 为什么 S-box 是 x^5？
+        它必须是域上的置换 (可逆)，要求 gcd(5, p - 1) = 1。BN254 的标量域满足这个条件；
+        我们选的 M31 也满足。x^3 在这里不行：p - 1 能被 3 整除。
+
+    4. 接入现有代码的方式：
+        Merkle 树只关心"叶子怎么哈希、两个孩子怎么合并"，所以 ex01 把这两步抽成 Hasher trait，
+        换哈希函数不需要改树的任何构建或证明逻辑。
+*/
@@ -0,0 +1,162 @@
+// src/s05_zk_lab/ex19_curve.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::commitments::pedersen::PedersenParams;
+use super::crypto::schnorr::{self, Keypair};
+use super::math::curve::{CurvePoint, Fq, CURVE_A, CURVE_B, CURVE_ORDER, FIELD_MODULUS};
+use super::math::group::{random_scalar, Group, Scalar};
+
+/*
+业务场景：从"教科书群"换到"真实世界的群"
+    比特币 (secp256k1)、以太坊、Zcash (BLS12-381) 用的都是椭圆曲线。
+    ex08 的 Pedersen 和 ex09 的 Schnorr 只依赖抽象的 Group trait，
+    这里把它们原封不动地搬到一条玩具曲线上。
+
+本练习：
+    1. 数点：曲线上一共有多少个点？(Hasse 定理)
+    2. 画出来：有限域上的"曲线"是一团点，但关于 y = p/2 对称
+    3. 验证群公理：封闭、单位元、逆元、交换律、结合律、阶
+    4. 手算一次 P + Q
+    5. 曲线上的 Pedersen 承诺与 Schnorr 签名
+*/
+
+const PLOT_WIDTH: usize = 64;
+const PLOT_HEIGHT: usize = 24;
+
+fn random_point(rng: &mut SimpleRng) -> CurvePoint {
+    CurvePoint::generator().pow(random_scalar(rng))
+}
+
+// 把 p x p 的平面压缩成 PLOT_WIDTH x PLOT_HEIGHT 的字符画，格子里点越多字符越"重"
+fn plot(points: &[CurvePoint]) {
+    let mut grid = vec![[0usize; PLOT_WIDTH]; PLOT_HEIGHT];
+    for point in points {
+        if let CurvePoint::Affine { x, y } = point {
+            let col = x.value() as usize * PLOT_WIDTH / FIELD_MODULUS as usize;
+            let row = y.value() as usize * PLOT_HEIGHT / FIELD_MODULUS as usize;
+            grid[PLOT_HEIGHT - 1 - row][col] += 1;
+        }
+    }
+    for row in &grid {
+        let line: String = row
+            .iter()
+            .map(|&n| match n {
+                0 => ' ',
+                1 => '.',
+                2 => ':',
+                _ => '#',
+            })
+            .collect();
+        println!("  |{}|", line);
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex19: 椭圆曲线 y^2 = x^3 + {}x + {} (over F_{}) ---", CURVE_A, CURVE_B, FIELD_MODULUS);
+    let mut rng = SimpleRng::from_time();
+
+    // ==========================================
+    // 1. 数点
+    // ==========================================
+    let points = CurvePoint::all_points();
+    let bound = 2.0 * (FIELD_MODULUS as f64).sqrt();
+    println!("\n[1] 一共 {} 个点 (含无穷远点 O)", points.len());
+    println!("  Hasse 定理: |#E - (p + 1)| = |{} - {}| = {} <= 2√p ≈ {:.1}", points.len(), FIELD_MODULUS + 1, (points.len() as i64 - FIELD_MODULUS as i64 - 1).abs(), bound);
+    println!("  {} 是素数 => 除 O 以外每个点都是生成元，标量域就是 F_{}", CURVE_ORDER, CURVE_ORDER);
+
+    // ==========================================
+    // 2. 画图
+    // ==========================================
+    println!("\n[2] 所有点的分布 (横轴 x，纵轴 y，都从 0 到 {})", FIELD_MODULUS - 1);
+    plot(&points);
+    println!("  (y 和 p - y 成对出现：图像关于中线上下对称 —— 这就是实数曲线关于 x 轴的对称)");
+
+    // ==========================================
+    // 3. 群公理
+    // ==========================================
+    let trials = 50;
+    let o = CurvePoint::identity();
+    let mut passed = [0usize; 6];
+    for _ in 0..trials {
+        let (p, q, r) = (random_point(&mut rng), random_point(&mut rng), random_point(&mut rng));
+        let checks = [
+            (p + q).is_on_curve(),
+            p + o == p,
+            p + p.inverse() == o,
+            p + q == q + p,
+            (p + q) + r == p + (q + r),
+            p.mul_naive(Scalar::new(CURVE_ORDER - 1)) + p == o, // q * P = O
+        ];
+        for (count, ok) in passed.iter_mut().zip(checks) {
+            *count += ok as usize;
+        }
+    }
+    println!("\n[3] 随机抽 {} 组 (P, Q, R) 检查群公理", trials);
+    let names = ["封闭  P + Q 在曲线上", "单位元 P + O = P", "逆元  P + (-P) = O", "交换律 P + Q = Q + P", "结合律 (P + Q) + R = P + (Q + R)", "阶    q * P = O"];
+    for (name, count) in names.iter().zip(passed) {
+        println!("  {:<36} {}/{} {}", name, count, trials, if count == trials { "✅" } else { "❌" });
+    }
+
+    // ==========================================
+    // 4. 手算一次 P + Q
+    // ==========================================
+    let x: u64 = read_line("\n[4] 选一个 x 作为 P 的横坐标 (直接回车默认 7): ").parse().unwrap_or(7);
+    let p = (x..x + FIELD_MODULUS)
+        .find_map(|x| CurvePoint::lift_x(Fq::new(x)))
+        .expect("curve has affine points");
+    let q = CurvePoint::generator();
+    if let (CurvePoint::Affine { x: x1, y: y1 }, CurvePoint::Affine { x: x2, y: y2 }) = (p, q) {
+        if x1.value() != x % FIELD_MODULUS {
+            println!("  x = {} 在曲线上没有对应的点，顺延到 x = {}", x, x1);
+        }
+        println!("  P = {}，Q = G = {}", p, q);
+        if x1 == x2 {
+            println!("  P、Q 横坐标相同，走切线/竖直线分支: P + Q = {}", p + q);
+        } else {
+            let lambda = (y2 - y1) / (x2 - x1);
+            let x3 = lambda * lambda - x1 - x2;
+            println!("  λ  = (y2 - y1) / (x2 - x1) = {}", lambda);
+            println!("  x3 = λ^2 - x1 - x2         = {}", x3);
+            println!("  y3 = λ(x1 - x3) - y1       = {}", lambda * (x1 - x3) - y1);
+            println!("  P + Q = {}  (在曲线上: {})", p + q, if (p + q).is_on_curve() { "✅" } else { "❌" });
+        }
+    }
+
+    // ==========================================
+    // 5. 同一份 Pedersen / Schnorr 代码，换一个群
+    // ==========================================
+    println!("\n[5] 曲线上的 Pedersen 承诺");
+    let params: PedersenParams<CurvePoint> = PedersenParams::setup();
+    println!("  g = {}，h = hash_to_curve(...) = {}", params.g, params.h);
+    let (a, b) = (Scalar::new(30), Scalar::new(12));
+    let (r1, r2) = (random_scalar(&mut rng), random_scalar(&mut rng));
+    let sum = params.commit(a, r1) * params.commit(b, r2);
+    println!("  C(30) + C(12) = {}，按 (42, r1 + r2) 打开: {}", sum.0, if params.verify(&sum, a + b, r1 + r2) { "✅ 同态依然成立" } else { "❌" });
+
+    println!("\n[6] 曲线上的 Schnorr 签名 (EC-Schnorr)");
+    let alice: Keypair<CurvePoint> = Keypair::generate(&mut rng);
+    let msg = b"pay bob 10 coins";
+    let sig = schnorr::sign(&alice, msg, &mut rng);
+    println!("  公钥 P = x * G = {}，签名 (R, s) = ({}, {})", alice.public, sig.r, sig.s);
+    println!("  验证原消息: {}", if schnorr::verify(alice.public, msg, &sig) { "✅" } else { "❌" });
+    println!("  验证篡改后的消息: {}", if schnorr::verify(alice.public, b"pay bob 99 coins", &sig) { "✅ (不应该发生)" } else { "❌ 拒绝" });
+}
+
+/*
+关键点总结：
+    1. 椭圆曲线上的"点加"满足群公理，所以前面所有基于离散对数的构造 (Pedersen、Schnorr、Sigma) 都能直接搬过来：
+        g^x (乘法群)  <->  x * G (曲线，加法记号)
+        g^a * g^b     <->  a*G + b*G
+
+    2. 为什么真实世界偏爱椭圆曲线？
+        mod p 乘法群有"指数演算"这种亚指数时间的离散对数算法，p 要 3072 bit 才有 128 bit 安全；
+        一般的椭圆曲线只有通用攻击 (Pollard rho, 约 √q)，256 bit 的曲线就够了。
+
+    3. 抽象的价值：
+        Pedersen / Schnorr 只依赖 Group trait (群运算、标量乘、求逆、hash_to_group、编码)。
+        新增一个群只需实现这个 trait，协议代码一行都不用改。
+
+    4. 群公理检查里的 q * P 用的是 k 次点加 (mul_naive)，复杂度 O(q)，真实的 256 bit 曲线上根本跑不完；
+        Group::pow 用的是 ex20 的 Montgomery ladder。
+*/
@@ -0,0 +1,105 @@
+// src/s05_zk_lab/ex20_scalar_mul.rs
+use std::time::{Duration, Instant};
+
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::math::curve::CurvePoint;
+use super::math::group::{random_scalar, Group, Scalar, GROUP_ORDER};
+
+/*
+业务场景：签名服务器的计时侧信道 (Timing Side Channel)
+    服务器用私钥 x 算 x * G (或签名时的 k * G)。攻击者看不到 x，但能测量每次响应花了多久。
+    如果耗时和 x 的二进制有关，攻击者就能一点点把 x 拼出来 (Brumley & Boneh 2003 远程攻破了 OpenSSL)。
+
+本练习：
+    1. 三种标量乘结果一致：k 次点加、double-and-add、Montgomery ladder
+    2. 运算次数：double-and-add 随汉明重量变化，ladder 恒定
+    3. 实测计时：低重量 vs 高重量的标量
+*/
+
+const REPS: u32 = 20_000;
+
+fn time_it(reps: u32, mut f: impl FnMut() -> CurvePoint) -> Duration {
+    let start = Instant::now();
+    let mut last = CurvePoint::Infinity;
+    for _ in 0..reps {
+        last = f();
+    }
+    // 用一下结果，别让编译器把循环整个优化掉
+    assert!(last.is_on_curve());
+    start.elapsed() / reps
+}
+
+// double-and-add：位长次倍点 + 汉明重量次点加
+fn double_and_add_ops(k: u64) -> (u32, u32) {
+    (u64::BITS - k.leading_zeros(), k.count_ones())
+}
+
+pub fn run() {
+    println!("--- S05 Ex20: 标量乘 (double-and-add vs Montgomery ladder) ---");
+    let mut rng = SimpleRng::from_time();
+    let g = CurvePoint::generator();
+
+    // ==========================================
+    // 1. 正确性
+    // ==========================================
+    let trials = 100;
+    let agree = (0..trials)
+        .filter(|_| {
+            let k = random_scalar(&mut rng);
+            let naive = g.mul_naive(k);
+            naive == g.mul_double_and_add(k) && naive == g.mul_ladder(k)
+        })
+        .count();
+    println!("\n[1] 随机 {} 个 k：k 次点加 / double-and-add / ladder 结果一致 {}/{} {}", trials, agree, trials, if agree == trials { "✅" } else { "❌" });
+
+    // ==========================================
+    // 2. 运算次数
+    // ==========================================
+    let k: u64 = read_line(&format!("\n[2] 输入一个标量 k (0..{}，直接回车默认 600): ", GROUP_ORDER - 1)).parse().unwrap_or(600);
+    let k = Scalar::new(k);
+    let (doubles, adds) = double_and_add_ops(k.value());
+    println!("  k = {} = 0b{:b}", k, k.value());
+    println!("  k 次点加        : {} 次点加", k.value());
+    println!("  double-and-add  : {} 次倍点 + {} 次点加 (取决于 k 的位长和 1 的个数)", doubles, adds);
+    println!("  Montgomery ladder: 10 次倍点 + 10 次点加 (对任何 k 都一样)");
+    println!("  k * G = {}", g.mul_ladder(k));
+
+    // ==========================================
+    // 3. 计时
+    // ==========================================
+    // 位长相同 (都是 10 位)，只有汉明重量不同
+    let light = Scalar::new(0b10_0000_0000); // 512，1 个 1
+    let heavy = Scalar::new(0b11_1111_0111); // 1015，9 个 1
+    println!("\n[3] 各算 {} 次取平均 (debug 构建下数字偏大，看相对差距)", REPS);
+    println!("  {:<18} | {:>14} | {:>14}", "", "k = 512 (1 个 1)", "k = 1015 (9 个 1)");
+    let da = [light, heavy].map(|k| time_it(REPS, || g.mul_double_and_add(k)));
+    let ladder = [light, heavy].map(|k| time_it(REPS, || g.mul_ladder(k)));
+    let naive = [light, heavy].map(|k| time_it(REPS / 100, || g.mul_naive(k)));
+    println!("  {:<18} | {:>14?} | {:>14?}", "k 次点加", naive[0], naive[1]);
+    println!("  {:<18} | {:>14?} | {:>14?}  ❌ 耗时泄露了 1 的个数", "double-and-add", da[0], da[1]);
+    println!("  {:<18} | {:>14?} | {:>14?}  ✅ 基本持平", "Montgomery ladder", ladder[0], ladder[1]);
+
+    // 协议代码 (Pedersen / Schnorr) 通过 Group::pow 用的就是 ladder
+    let x = random_scalar(&mut rng);
+    println!("\n  Group::pow 与 ladder 一致: {}", if g.pow(x) == g.mul_ladder(x) { "✅" } else { "❌" });
+}
+
+/*
+关键点总结：
+    1. 从 O(k) 到 O(log k)：
+        k 次点加在 256 bit 的曲线上要 2^256 步；double-and-add 只要 256 次倍点 + 约 128 次点加。
+
+    2. 为什么 double-and-add 不安全？
+        "位是 1 才加" 这个分支让运算次数 = 位长 + 汉明重量。
+        攻击者测时间就能知道私钥里大概有几个 1；配合功耗分析 (SPA) 甚至能逐位读出"倍点、点加"的序列。
+
+    3. Montgomery ladder 的做法：
+        维持 R1 - R0 = P 不变，每一位都做一次点加和一次倍点，只是按位决定结果写到哪里 (条件交换)。
+        再把标量补成固定位长 (k + q 或 k + 2q)，连前导 0 都不泄露。
+
+    4. 这里仍然不是真正的常数时间：
+        cswap 用了 if，仿射坐标的点加遇到 O 会走捷径，域运算的取模也不保证常数时间。
+        生产级实现 (libsecp256k1、curve25519-dalek) 用投影坐标、完备的加法公式和无分支的掩码交换。
+*/
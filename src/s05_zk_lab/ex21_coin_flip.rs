@@ -0,0 +1,166 @@
+// src/s05_zk_lab/ex21_coin_flip.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::commitments::hash_commit::{self, random_nonce, HashCommitment, Nonce};
+use super::hash::sha256;
+
+/*
+业务场景：电话抛硬币 (Blum, 1981) / 链上随机数
+    Alice 和 Bob 隔着网络抛硬币，谁也不信任谁。
+    如果 Alice 先说、Bob 后说，Bob 总能"看着 Alice 的答案"选对自己有利的。
+
+承诺-揭示 (Commit-Reveal)：
+    1. Alice 选 a ∈ {0, 1} 和随机 nonce，发送 C = H(nonce || a)   —— 锁定了 a，但 Bob 看不到
+    2. Bob 公开发送 b ∈ {0, 1}
+    3. Alice 公开 (a, nonce)，Bob 检查 H(nonce || a) == C
+    结果 coin = a XOR b：0 算 Alice 赢，1 算 Bob 赢。只要有一方是随机的，结果就是随机的。
+
+本练习可以选择不同的场景，看看每一种作弊怎么被发现 (或者为什么协议需要某个步骤)。
+*/
+
+// 揭示的截止时间 (以"轮"计)：超时不公开，直接判负
+const REVEAL_DEADLINE: u64 = 3;
+
+struct Reveal {
+    bit: u8,
+    nonce: Nonce,
+    at_round: u64,
+}
+
+// 裁判 (也就是 Bob 的验证逻辑)：Ok(coin) 或者 Err(Alice 被判负的原因)
+fn judge(commitment: &HashCommitment, reveal: Option<&Reveal>) -> Result<u8, String> {
+    let Some(reveal) = reveal else {
+        return Err(String::from("Alice 拒绝公开"));
+    };
+    if reveal.at_round > REVEAL_DEADLINE {
+        return Err(format!("第 {} 轮才公开，超过截止时间 (第 {} 轮)", reveal.at_round, REVEAL_DEADLINE));
+    }
+    if reveal.bit > 1 {
+        return Err(format!("公开的 {} 不是 0 或 1", reveal.bit));
+    }
+    if !hash_commit::verify(commitment, &[reveal.bit], &reveal.nonce) {
+        return Err(String::from("公开的 (a, nonce) 与承诺不符"));
+    }
+    Ok(reveal.bit)
+}
+
+fn announce(result: Result<u8, String>, bob_bit: u8) {
+    match result {
+        Ok(a) => {
+            let coin = a ^ bob_bit;
+            println!("  a = {}，b = {}，coin = a XOR b = {} -> {}", a, bob_bit, coin, if coin == 0 { "Alice 赢" } else { "Bob 赢" });
+        }
+        Err(reason) => println!("  ❌ {}：判 Alice 负，Bob 赢", reason),
+    }
+}
+
+fn random_bit(rng: &mut SimpleRng) -> u8 {
+    rng.gen_range(2) as u8
+}
+
+pub fn run() {
+    println!("--- S05 Ex21: 承诺-揭示抛硬币 (Commit-Reveal Coin Flip) ---");
+    let mut rng = SimpleRng::from_time();
+
+    loop {
+        println!("\n选择场景:");
+        println!("  1. 诚实的一局");
+        println!("  2. ❌ 不用承诺：Alice 等 Bob 先出再出");
+        println!("  3. ❌ Alice 输了想改口 (换一个 a 公开)");
+        println!("  4. ❌ Alice 输了就拖着不公开");
+        println!("  5. ❌ 承诺里不加 nonce：C = H(a)");
+        println!("  6. 公平性统计 (诚实 1000 局)");
+        println!("  0. 返回");
+        let choice = read_line("请输入: ");
+
+        match choice.as_str() {
+            "1" => {
+                let a = random_bit(&mut rng);
+                let nonce = random_nonce(&mut rng);
+                let c = hash_commit::commit(&[a], &nonce);
+                println!("  [1] Alice -> Bob  C = {}", c);
+                let b = random_bit(&mut rng);
+                println!("  [2] Bob -> Alice  b = {}", b);
+                let reveal = Reveal { bit: a, nonce, at_round: 2 };
+                println!("  [3] Alice -> Bob  a = {}，nonce = {}..", a, hex::encode(&nonce[..4]));
+                announce(judge(&c, Some(&reveal)), b);
+            }
+            "2" => {
+                // 没有第 1 步：Alice 直接根据 b 选 a = b，coin 永远是 0
+                let rounds = 100;
+                let alice_wins = (0..rounds)
+                    .filter(|_| {
+                        let b = random_bit(&mut rng);
+                        let a = b;
+                        a ^ b == 0
+                    })
+                    .count();
+                println!("  Alice 后出手，{} 局赢了 {} 局 —— 承诺的作用就是让先说的一方没法反悔、后说的一方看不到", rounds, alice_wins);
+            }
+            "3" => {
+                let a = random_bit(&mut rng);
+                let nonce = random_nonce(&mut rng);
+                let c = hash_commit::commit(&[a], &nonce);
+                // Bob 恰好出了让 Alice 输的那个 b
+                let b = 1 - a;
+                println!("  Alice 承诺了 a = {}，Bob 出 b = {}，coin = 1，Alice 要输", a, b);
+                let forged = Reveal { bit: 1 - a, nonce, at_round: 2 };
+                println!("  Alice 改口公开 a = {} (同一个 nonce)", forged.bit);
+                announce(judge(&c, Some(&forged)), b);
+                println!("  (想成功改口，得找到 nonce' 使 H(nonce' || {}) == C，也就是 SHA-256 的碰撞)", 1 - a);
+            }
+            "4" => {
+                let a = random_bit(&mut rng);
+                let nonce = random_nonce(&mut rng);
+                let c = hash_commit::commit(&[a], &nonce);
+                let b = 1 - a;
+                println!("  Alice 承诺了 a = {}，Bob 出 b = {}，Alice 一看要输，开始拖延……", a, b);
+                let late = Reveal { bit: a, nonce, at_round: 10 };
+                announce(judge(&c, Some(&late)), b);
+                announce(judge(&c, None), b);
+                println!("  (没有截止时间和\"不公开判负\"的规则，Alice 就能用中止来否决对自己不利的结果)");
+            }
+            "5" => {
+                let a = random_bit(&mut rng);
+                let c = HashCommitment(sha256(&[a]));
+                println!("  Alice -> Bob  C = H(a) = {}", c);
+                // 取值空间只有 {0, 1}，Bob 把两种可能都算一遍
+                let guessed = (0..=1u8).find(|&x| HashCommitment(sha256(&[x])) == c).expect("a is 0 or 1");
+                let b = 1 - guessed; // 让 coin = 1
+                println!("  Bob 算出 H(0)、H(1)，发现 a = {}，于是出 b = {}", guessed, b);
+                println!("  coin = {} -> Bob 每次都赢。隐藏性来自 nonce，不是来自哈希本身", a ^ b);
+            }
+            "6" => {
+                let rounds = 1000;
+                let alice_wins = (0..rounds)
+                    .filter(|_| {
+                        let a = random_bit(&mut rng);
+                        let nonce = random_nonce(&mut rng);
+                        let c = hash_commit::commit(&[a], &nonce);
+                        let b = random_bit(&mut rng);
+                        let reveal = Reveal { bit: a, nonce, at_round: 2 };
+                        judge(&c, Some(&reveal)).is_ok_and(|a| a ^ b == 0)
+                    })
+                    .count();
+                println!("  诚实 {} 局：Alice 赢 {}，Bob 赢 {}", rounds, alice_wins, rounds - alice_wins);
+            }
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 承诺的两个性质各自防住了一方：
+        绑定性 (Binding)：Alice 承诺之后不能改口 (场景 3)
+        隐藏性 (Hiding) ：Bob 看到承诺也猜不出 a (场景 5 说明 nonce 必不可少)
+
+    2. 承诺-揭示挡不住"中止" (场景 4)：
+        后揭示的一方总能看到结果后选择不揭示。协议层面必须规定超时判负，
+        链上的做法是押金 (deposit)：不按时揭示就罚没。
+
+    3. 链上随机数 (RANDAO) 就是多人版的承诺-揭示：所有人的值异或在一起，
+        最后一个揭示者依然有"放弃揭示"这 1 bit 的影响力 —— 这也是 VRF / VDF 被引入的原因。
+*/
@@ -0,0 +1,152 @@
+// src/s05_zk_lab/ex22_range_proof.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::bulletproof;
+use super::commitments::pedersen::PedersenParams;
+use super::math::group::{random_scalar, Scalar, GROUP_ORDER};
+use super::range_proof::{self, MAX_BITS};
+
+/*
+业务场景：机密交易 (Confidential Transactions, Monero / Mimblewimble)
+    金额被 Pedersen 承诺藏起来，节点只用同态性检查 "输入之和 == 输出之和"。
+    问题是承诺里的数活在 F_q 里：-100 就是 q - 100。
+    攻击者可以花 100，输出 200 和 "-100" —— 加起来还是 100，平衡检查通过，凭空多出 100 块钱。
+
+范围证明堵住这个洞：每个输出都附带"金额在 [0, 2^n) 之间"的零知识证明。
+
+本练习 (n = 8，金额范围 0..256)：
+    1. 为你输入的金额生成并验证范围证明
+    2. ❌ 超出范围的金额：某一位只能写成 2，OR 证明通不过
+    3. ❌ 负数金额的通胀攻击：平衡检查通过，但范围证明拦下了它
+    4. ❌ 篡改证明：交换两位的承诺
+    5. 同一个陈述换成 Bulletproofs：证明从 O(n) 缩到 O(log n)，作弊照样被拒
+*/
+
+const BITS: usize = 8;
+
+pub fn run() {
+    println!("--- S05 Ex22: 位分解范围证明 (0 <= v < 2^{}) ---", BITS);
+    println!("(群的阶 q = {}，n 最多只能到 {}，否则 2^n 会超过 q)", GROUP_ORDER, MAX_BITS);
+    let params: PedersenParams = PedersenParams::setup();
+    let mut rng = SimpleRng::from_time();
+
+    // ==========================================
+    // 1. 诚实证明
+    // ==========================================
+    let v: u64 = read_line(&format!("\n[1] 输入一个金额 (0..{}，直接回车默认 200): ", 1 << BITS)).parse().unwrap_or(200);
+    let r = random_scalar(&mut rng);
+    let c = params.commit(Scalar::new(v), r);
+    println!("  C = g^{} * h^r = {}", v, c.0);
+    match range_proof::prove(&params, v, r, BITS, &mut rng) {
+        None => println!("  ❌ {} 不在 [0, {}) 里，诚实的证明者拆不出 {} 个 0/1 位", v, 1 << BITS, BITS),
+        Some(proof) => {
+            let shown: Vec<String> = proof.bits.iter().map(|b| b.commitment.0.to_string()).collect();
+            println!("  位承诺 C_0..C_{} = [{}]", BITS - 1, shown.join(", "));
+            println!("  (每个 C_i 都是一个新的 Pedersen 承诺，看不出是 0 还是 1)");
+            match range_proof::verify(&params, &c, BITS, &proof) {
+                Ok(()) => println!("  验证: ✅ {} 个 OR 证明全部通过，Π C_i^(2^i) == C", BITS),
+                Err(reason) => println!("  验证: ❌ {}", reason),
+            }
+        }
+    }
+
+    // ==========================================
+    // 2. 超出范围
+    // ==========================================
+    // 300 = 2 * 128 + 44：最高位写成 2，其余是 44 的二进制
+    let big = 300;
+    println!("\n[2] ❌ 金额 {} 超出范围", big);
+    let r = random_scalar(&mut rng);
+    let c = params.commit(Scalar::new(big), r);
+    println!("  诚实的证明者: {}", if range_proof::prove(&params, big, r, BITS, &mut rng).is_none() { "拒绝生成证明" } else { "(不应该发生)" });
+    let mut bits: Vec<u64> = (0..BITS).map(|i| (44 >> i) & 1).collect();
+    bits[BITS - 1] = 2;
+    println!("  作弊者把它写成 \"位\" {:?} (低位在前)", bits);
+    let forged = range_proof::prove_bits(&params, &bits, r, &mut rng);
+    match range_proof::verify(&params, &c, BITS, &forged) {
+        Ok(()) => println!("  验证: ✅ (不应该发生)"),
+        Err(reason) => println!("  验证: ❌ {}", reason),
+    }
+
+    // ==========================================
+    // 3. 通胀攻击
+    // ==========================================
+    println!("\n[3] ❌ 机密交易通胀攻击：输入 100，输出 200 和 -100");
+    let (r_out1, r_out2) = (random_scalar(&mut rng), random_scalar(&mut rng));
+    let input = params.commit(Scalar::new(100), r_out1 + r_out2);
+    let minus_100 = Scalar::from_i64(-100);
+    let out1 = params.commit(Scalar::new(200), r_out1);
+    let out2 = params.commit(minus_100, r_out2);
+    println!("  -100 在 F_q 里就是 {}", minus_100);
+    println!("  平衡检查 C_in == C_out1 * C_out2: {}", if input == out1 * out2 { "✅ 通过 —— 单靠同态性拦不住" } else { "❌" });
+    let proof1 = range_proof::prove(&params, 200, r_out1, BITS, &mut rng).expect("200 is in range");
+    println!("  输出 1 (200) 的范围证明: {}", if range_proof::verify(&params, &out1, BITS, &proof1).is_ok() { "✅" } else { "❌" });
+    match range_proof::prove(&params, minus_100.value(), r_out2, BITS, &mut rng) {
+        None => println!("  输出 2 ({}) 的范围证明: ❌ 生成不了 —— 交易被拒绝", minus_100),
+        Some(_) => println!("  输出 2 的范围证明: (不应该发生)"),
+    }
+
+    // ==========================================
+    // 4. 篡改
+    // ==========================================
+    println!("\n[4] ❌ 拿一个合法证明 (金额 5 = 0b101)，交换第 0 位和第 1 位的承诺");
+    let r = random_scalar(&mut rng);
+    let c = params.commit(Scalar::new(5), r);
+    let mut proof = range_proof::prove(&params, 5, r, BITS, &mut rng).expect("5 is in range");
+    proof.bits.swap(0, 1);
+    match range_proof::verify(&params, &c, BITS, &proof) {
+        Ok(()) => println!("  验证: ✅ (不应该发生)"),
+        Err(reason) => println!("  验证: ❌ {}", reason),
+    }
+
+    // ==========================================
+    // 5. Bulletproofs
+    // ==========================================
+    let v = if v < 1 << BITS { v } else { 200 };
+    println!("\n[5] 同样证明金额 {} 在 [0, {}) 里，换成 Bulletproofs (内积论证见 ex35)", v, 1 << BITS);
+    let r = random_scalar(&mut rng);
+    let value_commitment = params.commit(Scalar::new(v), r);
+    let bits_proof = range_proof::prove(&params, v, r, BITS, &mut rng).expect("value is in range");
+    let bp = bulletproof::prove(&params, v, r, BITS, &mut rng).expect("value is in range");
+    let ((bit_points, bit_scalars), (bp_points, bp_scalars)) = (bits_proof.size(), bp.size());
+    println!("  位分解:      {} 个群元素 + {} 个标量 = {}", bit_points, bit_scalars, bit_points + bit_scalars);
+    println!("  Bulletproofs: {} 个群元素 + {} 个标量 = {}  (A, S, T1, T2 + 内积论证 {} 轮的 L, R)", bp_points, bp_scalars, bp_points + bp_scalars, bp.ipa.l.len());
+    match bulletproof::verify(&params, &value_commitment, BITS, &bp) {
+        Ok(()) => println!("  验证: ✅"),
+        Err(reason) => println!("  验证: ❌ {}", reason),
+    }
+    let r = random_scalar(&mut rng);
+    let c = params.commit(Scalar::new(big), r);
+    let mut bits: Vec<u64> = (0..BITS).map(|i| (44 >> i) & 1).collect();
+    bits[BITS - 1] = 2;
+    let forged = bulletproof::prove_bits(&params, &bits, r, &mut rng);
+    match bulletproof::verify(&params, &c, BITS, &forged) {
+        Ok(()) => println!("  ❌ 金额 {} 写成带 2 的\"位\": ✅ (不应该发生)", big),
+        Err(reason) => println!("  ❌ 金额 {} 写成带 2 的\"位\": ❌ {}", big, reason),
+    }
+    let mut tampered = bp.clone();
+    tampered.ipa.a = tampered.ipa.a + Scalar::one();
+    match bulletproof::verify(&params, &value_commitment, BITS, &tampered) {
+        Ok(()) => println!("  ❌ 改动内积论证的最后一个标量: ✅ (不应该发生)"),
+        Err(reason) => println!("  ❌ 改动内积论证的最后一个标量: ❌ {}", reason),
+    }
+}
+
+/*
+关键点总结：
+    1. 三个积木的组合：
+        Pedersen 承诺 (ex08) 的同态性   -> 把位承诺"加权相乘"还原成值承诺
+        OR 组合 (ex10)                 -> 每一位证明 "是 0 或是 1"，不暴露是哪一个
+        Fiat-Shamir (ex09)             -> 挑战绑定了值承诺、位置和位承诺，证明不能被挪用或调换
+
+    2. 为什么需要范围证明？
+        承诺里的数活在 F_q 里，没有"负数"的概念。没有范围证明，机密交易里的 -100 就是 q - 100，
+        平衡检查照样通过 —— 钱被凭空造了出来。
+
+    3. 证明大小：
+        每一位 1 个承诺 + 2 个承诺 (OR 的两支) + 3 个标量，n 位就是 O(n)。
+        Bulletproofs 用内积论证把 64 位的范围证明压到约 700 字节 (O(log n))，Monero 从 2018 年起使用。
+        第 5 节：8 位时是 24 + 24 对 10 + 5；64 位时是 192 + 192 对 16 + 5，位数越多差距越大。
+        代价是验证者要做 O(n) 次幂运算 (折叠生成元)，验证比位分解慢。
+*/
@@ -0,0 +1,96 @@
+// src/s05_zk_lab/ex23_mimc.rs
+use crate::common::input::read_line;
+
+use super::ex01_merkle::{Hasher, MerkleTree, PrefixedHasher};
+use super::math::field::M31;
+use super::mimc::{self, MimcHasher};
+use super::poseidon::{self, PoseidonHasher};
+
+/*
+业务场景：选一个哈希函数给 ZK 电路用 (Tornado Cash 的第一版 Merkle 树用的就是 MiMC)
+    ex18 说明了 SHA-256 在电路里为什么贵，并给出了 Poseidon。
+    MiMC 是更早、更朴素的方案：整个状态只有 1 个域元素，每轮就是 (x + k + c)^d。
+
+本练习：
+    1. MiMC 首先是一个分组密码：加密、解密、换密钥
+    2. 第三种 Merkle 哈希：同一批数据，三个根，证明互不通用
+    3. 约束数对比：SHA-256 vs Poseidon vs MiMC
+*/
+
+// 与 ex18 相同的估算值：SHA-256 一次压缩在 R1CS 里约 27,000 个约束
+const SHA256_CONSTRAINTS: usize = 27_000;
+
+pub fn run() {
+    println!("--- S05 Ex23: MiMC 哈希 (最朴素的域原生哈希) ---");
+    println!("参数: F_(2^31 - 1)，{} 轮，S-box x^{}，Miyaguchi-Preneel 哈希模式", mimc::ROUNDS, mimc::EXPONENT);
+
+    // ==========================================
+    // 1. 分组密码
+    // ==========================================
+    let m: u64 = read_line("\n[1] 输入明文 m (直接回车默认 42): ").parse().unwrap_or(42);
+    let m = M31::new(m);
+    let key = M31::new(7);
+    let c = mimc::encrypt(key, m);
+    println!("  E_{}({}) = {}", key, m, c);
+    println!("  D_{}({}) = {}  {}", key, c, mimc::decrypt(key, c), if mimc::decrypt(key, c) == m { "✅ 还原" } else { "❌" });
+    println!("  E_{}({}) = {}  (密钥只差 1，密文毫无关系)", key + M31::one(), m, mimc::encrypt(key + M31::one(), m));
+    println!("  MiMC([{}]) = {}   MiMC([{}, 0]) = {}", m, mimc::hash(&[m]), m, mimc::hash(&[m, M31::zero()]));
+
+    // ==========================================
+    // 2. 三种哈希的 Merkle 树
+    // ==========================================
+    println!("\n[2] 同一批交易，三种哈希");
+    let txs: Vec<String> = (1..=8).map(|i| format!("Tx{}: deposit {}", i, i * 100)).collect();
+    let index = 2;
+    let hashers: [&dyn Hasher; 3] = [&PrefixedHasher, &PoseidonHasher, &MimcHasher];
+    let trees: Vec<MerkleTree<String, &dyn Hasher>> = hashers.iter().map(|&h| MerkleTree::with_hasher(txs.clone(), h)).collect();
+    for tree in &trees {
+        let proof = tree.prove(index).expect("index in range");
+        let ok = proof.verify_with(&tree.leaves[index], &tree.root_hash(), tree.hasher());
+        println!("  {:<16} 根 = {:<18} {} 的证明: {}", tree.hasher().name(), tree.root_hash(), tree.leaves[index], if ok { "✅" } else { "❌" });
+    }
+    // 交叉验证：每棵树的证明拿另外两种哈希去验
+    let mimc_tree = &trees[2];
+    let proof = mimc_tree.prove(index).expect("index in range");
+    for other in &hashers[..2] {
+        let ok = proof.verify_with(&mimc_tree.leaves[index], &mimc_tree.root_hash(), other);
+        println!("  ❌ 用 {} 去验 MiMC 树的证明: {}", other.name(), if ok { "✅ (不应该发生)" } else { "❌ 拒绝" });
+    }
+
+    // ==========================================
+    // 3. 约束数对比
+    // ==========================================
+    // 一次 2 -> 1 压缩：Poseidon 一次置换吸收 2 个元素；MiMC 每个元素一次加密
+    let depth = 20;
+    let poseidon_cost = poseidon::multiplications_per_permutation();
+    let mimc_cost = mimc::multiplications_per_block() * 2;
+    println!("\n[3] 在电路里证明一条深度 {} 的 Merkle 路径", depth);
+    println!("  哈希      每次压缩   整条路径");
+    println!("  {:<9} {:>8} {:>10}", "SHA-256", SHA256_CONSTRAINTS, SHA256_CONSTRAINTS * depth);
+    println!("  {:<9} {:>8} {:>10}", "Poseidon", poseidon_cost, poseidon_cost * depth);
+    println!("  {:<9} {:>8} {:>10}", "MiMC", mimc_cost, mimc_cost * depth);
+    println!("  (SHA-256 是约束数的常见估算，另外两个是本仓库参数下实际的乘法次数)");
+    println!("  => 我们的玩具参数下 MiMC 甚至更省；但在 254 位的 BN254 上，MiMC7 需要 91 轮 (每轮 4 个乘法)，");
+    println!("     而 Poseidon 的宽状态一次吸收多个元素，压缩 2 个元素大约 240 个约束，MiMC 要 700 多个");
+}
+
+/*
+关键点总结：
+    1. MiMC 的全部结构：加常数、加密钥、x^d，重复到代数次数超过 p。
+        没有线性层，也没有宽状态 —— 所以轮数只能靠"d^轮数 >= p"来堆，域越大轮数越多。
+
+    2. d 的选择由域决定：
+        要求 gcd(d, p - 1) = 1。M31 上 3 和 7 都整除 p - 1，最小的可选值是 5；
+        ex18 的 Poseidon S-box 也是因为同一个理由选了 x^5 (mimc::inverse_exponent 里的 assert 就在检查它)。
+
+    3. 电路里只算正向：
+        证明者在电路里只需要验证 y = x^5 (3 个乘法门)；
+        解密的 x^(1/5) 在 CPU 上要 30 多次乘法，但哈希永远用不到它。
+
+    4. Poseidon 为什么后来居上：
+        宽状态 (WIDTH 个元素) 每次置换吸收 RATE 个输入，部分轮只给一个元素过 S-box，
+        在大域上单位输入的约束数比 MiMC 低好几倍。MiMC 胜在简单、分析时间更长。
+
+    5. Hasher 抽象又用了一次：
+        MiMC 接入 Merkle 树只实现了一个 trait，树的构建、证明、验证一行没改。
+*/
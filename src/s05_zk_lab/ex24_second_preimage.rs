@@ -0,0 +1,97 @@
+// src/s05_zk_lab/ex24_second_preimage.rs
+use super::ex01_merkle::{Hasher, MerkleProof, MerkleTree, MockHasher, PrefixedHasher};
+
+/*
+业务场景：轻节点 (SPV) 只存区块头里的 Merkle 根
+    全节点发来"交易 X 在区块里"的证明，轻节点用根验证一下就相信了。
+    如果攻击者能拿一段【不是交易】的数据通过验证，轻节点就会被骗。
+
+第二原像 (Second Preimage) 攻击：
+    最初的 ex01 规则里，叶子是 H(data)，内部节点是 H(left + right)，用的是同一个 H。
+    那么字符串 "left.hash + right.hash" 本身就可以当成一片"叶子"：
+        H(伪造叶子) = H(left.hash + right.hash) = 内部节点的哈希
+    攻击者把树"砍掉"最底下一层，得到一棵更矮、根却完全相同的树。
+
+修复 (RFC 6962)：叶子哈希 H(0x00 + data)，内部节点 H(0x01 + left + right)。
+    伪造的"叶子"会被加上 0x00，永远算不出那个以 0x01 开头的内部节点哈希。
+
+本练习：
+    1. 对 MockHasher 的树发动攻击：伪造叶子 + 伪造的更矮的树
+    2. 同样的攻击打在 PrefixedHasher 上
+*/
+
+// 攻击者能拿到的全部信息：叶子原文 (公开数据) 和 Merkle 根
+// 返回伪造的叶子、它的证明，以及用第 1 层节点当叶子重建出来的根
+fn attack<H: Hasher + Copy>(tree: &MerkleTree<String, H>) -> (String, MerkleProof, String) {
+    let hasher = *tree.hasher();
+    let leaf_hashes: Vec<String> = tree.leaves.iter().map(|leaf| hasher.leaf(leaf.as_bytes())).collect();
+    // 最底层两两拼接：原本是"交给 node 的输入"，现在假装它是叶子原文
+    let fake_leaves: Vec<String> = leaf_hashes.chunks(2).map(|pair| format!("{}{}", pair[0], pair[1])).collect();
+    // 伪造叶子 0 的证明：拿真实的 Tx1 证明，去掉最底层那个兄弟就行
+    let real = tree.prove(0).expect("tree is non-empty");
+    let proof = MerkleProof { index: 0, siblings: real.siblings[1..].to_vec() };
+    let shorter_root = MerkleTree::with_hasher(fake_leaves.clone(), hasher).root_hash();
+    (fake_leaves[0].clone(), proof, shorter_root)
+}
+
+fn show<H: Hasher + Copy>(hasher: H) {
+    let txs: Vec<String> = ["Tx1: Alice->Bob", "Tx2: Bob->Charlie", "Tx3: Charlie->Dave", "Tx4: Dave->Eve"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let tree = MerkleTree::with_hasher(txs, hasher);
+    let root = tree.root_hash();
+    println!("  4 笔交易的根 = {:?}", root);
+
+    let (fake_leaf, proof, shorter_root) = attack(&tree);
+    println!("  伪造的\"叶子\" = {:?}", fake_leaf);
+    println!("    (这不是任何一笔交易，只是 H(Tx1) 和 H(Tx2) 拼在一起)");
+    let ok = proof.verify_with(&fake_leaf, &root, &hasher);
+    println!("  伪造叶子 + {} 个兄弟哈希的证明: {}", proof.siblings.len(), if ok { "✅ 通过 —— 轻节点被骗了！" } else { "❌ 拒绝" });
+    println!(
+        "  用 2 片伪造叶子重建的树: 根 = {:?}  {}",
+        shorter_root,
+        if shorter_root == root { "== 原根 (两棵不同的树，同一个根)" } else { "≠ 原根" }
+    );
+}
+
+pub fn run() {
+    println!("--- S05 Ex24: Merkle 第二原像攻击与域分离 ---");
+
+    // ==========================================
+    // 1. 没有域分离
+    // ==========================================
+    println!("\n[1] ❌ {}: leaf = H(data)，node = H(left + right)", MockHasher.name());
+    show(MockHasher);
+
+    // ==========================================
+    // 2. 加上 0x00 / 0x01 前缀
+    // ==========================================
+    println!("\n[2] ✅ {}: leaf = H(0x00 + data)，node = H(0x01 + left + right)", PrefixedHasher.name());
+    show(PrefixedHasher);
+    println!("  => 伪造叶子被哈希成 H(0x00 + ...)，而它要冒充的节点是 H(0x01 + ...)，两者不可能相等");
+    println!("     MerkleTree::new / new_iterative 现在默认使用 {}", PrefixedHasher.name());
+}
+
+/*
+关键点总结：
+    1. 问题的根源是"同一个哈希函数，两种含义"：
+        验证者拿到一个哈希值 (真实系统里是 32 字节)，分不清它是"数据的哈希"还是"两个孩子的哈希"。
+        证明里也没写树有多高，攻击者就可以少给一层兄弟，把内部节点冒充成叶子。
+
+    2. 域分离 (Domain Separation)：
+        给不同用途的哈希输入加上不同的固定前缀，让它们的输入空间互不相交。
+        也可以换成两个独立的哈希函数、或在证明里固定树高 —— 前缀是最便宜的办法。
+
+    3. 真实世界：
+        Certificate Transparency (RFC 6962) 用的就是 0x00 / 0x01；
+        比特币没有做域分离，64 字节的交易恰好能被解释成两个 32 字节的哈希，
+        所以 Bitcoin Core 后来把 64 字节的交易列为非标准交易 (共识清理提案要求彻底禁止)。
+
+    4. 前缀解决不了的另一个歧义：
+        奇数个节点时"复制最后一个"，[Tx1, Tx2, Tx3] 和 [Tx1, Tx2, Tx3, Tx3] 的根相同 (CVE-2012-2459)。
+        这是补齐规则的问题，和哈希的域分离无关：要么拒绝重复的叶子，要么换一种补齐规则。
+
+    5. 兼容性：
+        换了哈希规则，所有的根都会变。旧的 MockHasher 保留在 ex01 里，只用于这个演示。
+*/
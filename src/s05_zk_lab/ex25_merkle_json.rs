@@ -0,0 +1,126 @@
+// src/s05_zk_lab/ex25_merkle_json.rs
+use std::fs;
+use std::path::Path;
+
+use crate::common::input::read_line;
+
+use super::ex01_merkle::{DynMerkleTree, MerkleProof, MerkleTree};
+
+/*
+业务场景：证明要能"离开"生成它的进程
+    区块浏览器生成一份 Merkle 证明，用户把它存成文件、发给交易所、交易所在另一台机器上验证。
+    之前的练习里，树和证明都只活在一次 run() 的内存里，程序一退出就没了。
+
+本练习：
+    1. 建树、生成证明，分别写进 merkle_tree.json 和 merkle_proof.json
+    2. 只从文件读回来再验证 —— 可以退出程序，甚至换一台机器，再选本练习直接回车
+    3. ❌ 篡改文件：改一个兄弟哈希、改一片叶子、截断 JSON
+*/
+
+const TREE_FILE: &str = "merkle_tree.json";
+const PROOF_FILE: &str = "merkle_proof.json";
+
+fn generate(index: usize) {
+    let txs: Vec<String> = (1..=8).map(|i| format!("Tx{}: payout {} coins", i, i * 10)).collect();
+    let tree = MerkleTree::new_iterative(txs);
+    let proof = tree.prove(index).expect("index in range");
+    let tree_json = tree.to_json();
+    let proof_json = proof.to_json();
+    println!("  {} ({} 字节): {}...", TREE_FILE, tree_json.len(), &tree_json[..80]);
+    println!("  {} ({} 字节): {}", PROOF_FILE, proof_json.len(), proof_json);
+    for (path, text) in [(TREE_FILE, tree_json), (PROOF_FILE, proof_json)] {
+        if let Err(e) = fs::write(path, text) {
+            println!("  ❌ 写入 {} 失败: {}", path, e);
+        }
+    }
+}
+
+// 验证方只相信树文件里的根 (相当于区块头)，证明文件来自不可信的第三方
+fn verify_files(tree_text: &str, proof_text: &str) -> Result<(DynMerkleTree, MerkleProof), String> {
+    let tree = DynMerkleTree::from_json(tree_text).map_err(|e| format!("树文件无效: {}", e))?;
+    let proof = MerkleProof::from_json(proof_text).map_err(|e| format!("证明文件无效: {}", e))?;
+    Ok((tree, proof))
+}
+
+fn check(label: &str, tree_text: &str, proof_text: &str) {
+    match verify_files(tree_text, proof_text) {
+        Ok((tree, proof)) => {
+            let leaf = tree.leaves.get(proof.index).map(String::as_str).unwrap_or("");
+            let ok = proof.verify_with(leaf, &tree.root_hash(), tree.hasher());
+            println!("  {}: 第 {} 片叶子 {:?} -> {}", label, proof.index, leaf, if ok { "✅ 验证通过" } else { "❌ 证明与根不符" });
+        }
+        Err(e) => println!("  {}: ❌ {}", label, e),
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex25: Merkle 树与证明的 JSON 序列化 ---");
+
+    // ==========================================
+    // 1. 生成并保存
+    // ==========================================
+    let have_files = Path::new(TREE_FILE).exists() && Path::new(PROOF_FILE).exists();
+    let line = read_line(&format!(
+        "\n[1] 输入要证明的叶子下标 0-7 (直接回车: {}): ",
+        if have_files { "跳过生成，只验证已有文件" } else { "证明第 0 片" }
+    ));
+    match line.parse::<usize>() {
+        Ok(index) if index < 8 => generate(index),
+        Ok(_) => println!("  ❌ 下标超出范围，跳过生成"),
+        Err(_) if have_files => println!("  使用上一次保存的文件"),
+        Err(_) => generate(0),
+    }
+
+    // ==========================================
+    // 2. 只从文件读回来验证
+    // ==========================================
+    println!("\n[2] 从磁盘读回 (这一步不依赖内存里的任何东西)");
+    let (tree_text, proof_text) = match (fs::read_to_string(TREE_FILE), fs::read_to_string(PROOF_FILE)) {
+        (Ok(t), Ok(p)) => (t, p),
+        _ => {
+            println!("  ❌ 读取 {} / {} 失败", TREE_FILE, PROOF_FILE);
+            return;
+        }
+    };
+    check("原样读回", &tree_text, &proof_text);
+
+    // ==========================================
+    // 3. 篡改
+    // ==========================================
+    println!("\n[3] ❌ 篡改文件内容");
+    // 把证明里第一个兄弟哈希的第一个字符换掉
+    let tampered_proof = match proof_text.find("\"hash\":\"") {
+        Some(pos) => {
+            let at = pos + "\"hash\":\"".len();
+            let flipped = if proof_text[at..].starts_with('0') { "1" } else { "0" };
+            format!("{}{}{}", &proof_text[..at], flipped, &proof_text[at + 1..])
+        }
+        None => proof_text.clone(),
+    };
+    check("改一个兄弟哈希", &tree_text, &tampered_proof);
+    // 想把某片叶子换掉，却懒得 (或者没法) 同步更新根
+    let tampered_tree = tree_text.replacen(&hex::encode("Tx1"), &hex::encode("Tx9"), 1);
+    check("改一片叶子", &tampered_tree, &proof_text);
+    check("JSON 被截断", &tree_text, &proof_text[..proof_text.len() / 2]);
+
+    println!("\n  提示: 把两个文件拷到另一台机器，运行本程序选 25 后直接回车，就能独立验证");
+}
+
+/*
+关键点总结：
+    1. 序列化的是"数据"，不是"行为"：
+        哈希后端是代码，写不进文件。文件只存哈希函数的名字，读回来时按名字查表 (hasher_by_name)；
+        验证者不认识的名字直接拒绝，而不是猜一个默认值。
+
+    2. 反序列化 = 重新验证：
+        MerkleTree::from_json 不直接相信文件里的 root，而是按叶子重建一遍再比对。
+        任何从磁盘或网络读进来的东西都是不可信输入。
+
+    3. 编码选择：
+        叶子是任意字符串，hex 编码后不需要处理引号、换行等转义；
+        哈希本身已经是文本，原样保存。真实系统会直接存 32 字节，用 hex 或 base64 显示。
+
+    4. 不引入 serde 的代价：
+        common/json.rs 手写了一个只支持整数的小解析器；字段名、类型检查都要自己写 (Json::field)。
+        serde 的 derive 可以省掉这些样板代码，但实验室的原则是尽量只用标准库。
+*/
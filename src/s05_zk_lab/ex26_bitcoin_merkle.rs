@@ -0,0 +1,157 @@
+// src/s05_zk_lab/ex26_bitcoin_merkle.rs
+use crate::common::input::read_line;
+
+use super::bitcoin_merkle::{display_order, BitcoinHasher};
+use super::ex01_merkle::MerkleTree;
+use super::hash::sha256d;
+
+/*
+业务场景：用真实区块校验实验室的代码
+    前面的 Merkle 树都是自己和自己比对：自己建树、自己验证，错了也发现不了。
+    比特币区块是公开数据：交易列表和区块头里的 Merkle 根谁都能查到，正好当"标准答案"。
+
+本练习：
+    1. 两个真实区块：170 (中本聪给 Hal Finney 的第一笔转账) 和 100000
+    2. ❌ 常见错误：忘了反转字节序
+    3. SPV 证明：轻钱包只下载区块头，也能确认交易被打包
+    4. 复制最后一个的规则，以及它带来的 CVE-2012-2459
+    5. 自己粘贴一个区块的 txid 试试
+*/
+
+struct Block {
+    height: u64,
+    merkle_root: &'static str,
+    txids: &'static [&'static str],
+}
+
+// 数据来自任意区块浏览器，全部是显示字节序
+const BLOCKS: [Block; 2] = [
+    Block {
+        height: 170,
+        merkle_root: "7dac2c5666815c17a3b36427de37bb9d2e2c5ccec3f8633eb91a4205cb4c10ff",
+        txids: &[
+            "b1fea52486ce0c62bb442b530a3f0132b826c74e473d1f2c220bfa78111c5082",
+            "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16",
+        ],
+    },
+    Block {
+        height: 100000,
+        merkle_root: "f3e94742aca4b5ef85488dc37c06c3282295ffec960994b2c0d5ac2a25a95766",
+        txids: &[
+            "8c14f0db3df150123e6f3dbbf30f8b955a8249b62ac1d1ff16284aefa3d06d87",
+            "fff2525b8931402dd09222c50775608f75787bd2b87e56995a7bdd30f79702c4",
+            "6359f0868171b1d194cbee1af2f16ea598ae8fad666d9b012c8ed2b79a236ec4",
+            "e9a66845e05d5abc0ad04ec80f774a7e585c6e8db975962d069a522137b80c1d",
+        ],
+    },
+];
+
+fn bitcoin_root(txids: &[&str]) -> String {
+    let tree = MerkleTree::with_hasher(txids.iter().map(|t| t.to_string()).collect(), BitcoinHasher);
+    display_order(&tree.root_hash())
+}
+
+// ❌ 错误示范：直接拿显示出来的 txid 字节去拼接，最后也不反转
+fn naive_root(txids: &[&str]) -> String {
+    let mut level: Vec<Vec<u8>> = txids.iter().map(|t| hex::decode(t).unwrap_or_default()).collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let right = pair.get(1).unwrap_or(&pair[0]);
+                sha256d(&[pair[0].as_slice(), right.as_slice()].concat()).to_vec()
+            })
+            .collect();
+    }
+    hex::encode(&level[0])
+}
+
+fn short(hex_digest: &str) -> &str {
+    &hex_digest[..16.min(hex_digest.len())]
+}
+
+pub fn run() {
+    println!("--- S05 Ex26: 比特币兼容的 Merkle 根 (double SHA-256) ---");
+
+    // ==========================================
+    // 1. 真实区块
+    // ==========================================
+    println!("\n[1] 用真实区块验证 BitcoinHasher");
+    for block in &BLOCKS {
+        let root = bitcoin_root(block.txids);
+        println!("  区块 #{} ({} 笔交易)", block.height, block.txids.len());
+        println!("    算出的根: {}", root);
+        println!("    区块头里: {}  {}", block.merkle_root, if root == block.merkle_root { "✅ 一致" } else { "❌ 不一致" });
+    }
+
+    // ==========================================
+    // 2. 字节序陷阱
+    // ==========================================
+    let block = &BLOCKS[1];
+    println!("\n[2] ❌ 不反转字节序，直接拼接显示出来的 txid");
+    let naive = naive_root(block.txids);
+    println!("  算出的根: {}  {}", naive, if naive == block.merkle_root { "✅" } else { "❌ 对不上" });
+    println!("  反转后:   {}  {}", display_order(&naive), if display_order(&naive) == block.merkle_root { "✅" } else { "❌ 还是对不上" });
+    println!("  => 只反转结果不够：拼接时用的字节顺序不同，哈希的输入就完全不同了");
+
+    // ==========================================
+    // 3. SPV 证明
+    // ==========================================
+    let index = 2;
+    let txs: Vec<String> = block.txids.iter().map(|t| t.to_string()).collect();
+    let tree = MerkleTree::with_hasher(txs, BitcoinHasher);
+    let proof = tree.prove(index).expect("index in range");
+    println!("\n[3] SPV：证明 txid {}... 在区块 #{} 里", short(block.txids[index]), block.height);
+    for (i, (sibling, is_left)) in proof.siblings.iter().enumerate() {
+        println!("  第 {} 层兄弟 ({}): {}...", i, if *is_left { "左" } else { "右" }, short(&display_order(sibling)));
+    }
+    // 轻钱包手里只有区块头 (显示字节序的根)，先转成内部字节序再验证
+    let header_root = display_order(block.merkle_root);
+    let ok = proof.verify_with(block.txids[index], &header_root, &BitcoinHasher);
+    println!("  用区块头的根验证: {}  (只需 {} 个哈希，而不是下载全部 {} 笔交易)", if ok { "✅" } else { "❌" }, proof.siblings.len(), block.txids.len());
+
+    // ==========================================
+    // 4. 奇数个叶子
+    // ==========================================
+    println!("\n[4] 奇数个叶子：复制最后一个");
+    let three = &block.txids[..3];
+    let mutated = [three[0], three[1], three[2], three[2]];
+    let (a, b) = (bitcoin_root(three), bitcoin_root(&mutated));
+    println!("  [tx0, tx1, tx2]      的根: {}...", short(&a));
+    println!("  [tx0, tx1, tx2, tx2] 的根: {}...  {}", short(&b), if a == b { "⚠️ 完全相同" } else { "不同" });
+    println!("  => CVE-2012-2459：攻击者把合法区块\"改\"成含重复交易的无效区块，区块哈希不变；");
+    println!("     节点如果把这个区块哈希标记为无效，就再也不会接受那个合法区块。");
+    println!("     Bitcoin Core 的修复：计算 Merkle 根时检测这种重复，把区块判为\"被篡改\"而不是\"无效\"");
+
+    // ==========================================
+    // 5. 自己试
+    // ==========================================
+    let line = read_line("\n[5] 粘贴一个区块的全部 txid (空格或逗号分隔，直接回车跳过): ");
+    let txids: Vec<&str> = line.split([' ', ',']).filter(|s| !s.is_empty()).collect();
+    if txids.is_empty() {
+        return;
+    }
+    let root = bitcoin_root(&txids);
+    println!("  {} 笔交易，算出的 Merkle 根: {}", txids.len(), root);
+    let expected = read_line("  粘贴区块头里的 Merkle 根 (直接回车跳过): ");
+    if !expected.is_empty() {
+        println!("  {}", if root == expected.to_lowercase() { "✅ 一致" } else { "❌ 不一致 (txid 的顺序要和区块里完全相同)" });
+    }
+}
+
+/*
+关键点总结：
+    1. 比特币的叶子不再哈希：txid 本身已经是 sha256d(交易)，直接当叶子用。
+        这也意味着比特币没有 ex24 的 0x00 / 0x01 域分离，64 字节交易的歧义要靠别的规则堵上。
+
+    2. 字节序是最常见的坑：
+        协议内部按哈希函数的原始输出顺序存储，浏览器显示时整体反转 (历史原因：当作小端序的 256 位整数显示)。
+        拼接、哈希都在内部字节序上做，只有输入输出时转换。
+
+    3. 复用 Hasher 抽象：
+        BitcoinHasher 的 leaf 做字节序转换，node 做 sha256d；ex01 的 new_promoted 和"复制最后一个"
+        算出的哈希相同，所以迭代式构建直接就是比特币的规则，SPV 证明也能直接复用 MerkleProof。
+
+    4. 用外部数据校验自己的实现：
+        自己写的测试只能证明"前后一致"，对上真实区块的根才能证明"和别人一致"。
+*/
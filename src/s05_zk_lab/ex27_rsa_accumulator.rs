@@ -0,0 +1,144 @@
+// src/s05_zk_lab/ex27_rsa_accumulator.rs
+use crate::common::input::read_line;
+
+use super::commitments::rsa_accumulator::{self, RsaAccumulator};
+use super::ex01_merkle::MerkleTree;
+
+/*
+业务场景：白名单 (Allowlist)
+    链上合约只存一个很小的值，用户自己提交"我在名单里"的证明。
+    Merkle 树：存根 (32 字节)，证明是 log2(n) 个哈希 —— 名单越大，证明越长。
+    RSA 累加器：存累加值，证明永远只有一个数 —— 代价是可信设置和昂贵的见证计算。
+
+本练习：
+    1. 建立 8 人白名单，与同样数据的 Merkle 树并排
+    2. 成员证明 / 非成员冒充
+    3. 名单新增成员：旧见证失效，以及如何低成本地更新
+    4. 证明大小与计算量对比
+    5. ❌ 陷门：setup 的人留下了 p、q
+    6. ❌ 不映射成素数时，因子也能冒充成员
+*/
+
+// 真实系统：RSA-2048 的累加值和见证都是 256 字节，SHA-256 哈希 32 字节
+const RSA_ELEMENT_BYTES: usize = 256;
+const HASH_BYTES: usize = 32;
+
+pub fn run() {
+    println!("--- S05 Ex27: RSA 累加器 vs Merkle 树 ---");
+
+    // ==========================================
+    // 1. 建立白名单
+    // ==========================================
+    let members = ["alice", "bob", "carol", "dave", "erin", "frank", "grace", "heidi"];
+    let mut acc = RsaAccumulator::new();
+    println!("\n[1] 公共参数 N = {} (p、q 已销毁)，g = {}", acc.modulus, acc.generator);
+    for name in members {
+        let prime = acc.add(name);
+        println!("  加入 {:<6} -> 素数代表 {}", name, prime);
+    }
+    let merkle = MerkleTree::new_iterative(members.iter().map(|m| m.to_string()).collect());
+    println!("  累加值 A = {}", acc.value);
+    println!("  Merkle 根 = {}", merkle.root_hash());
+
+    // ==========================================
+    // 2. 成员证明
+    // ==========================================
+    let mut who = read_line("\n[2] 输入要证明的成员 (直接回车默认 carol): ");
+    if who.is_empty() {
+        who = String::from("carol");
+    }
+    let Some(witness) = acc.witness(&who) else {
+        println!("  ❌ {} 不在名单里，证明者生成不了见证", who);
+        return;
+    };
+    let ok = rsa_accumulator::verify(acc.modulus, acc.value, &who, witness);
+    println!("  累加器见证 w = {}  验证 w^x == A: {}", witness, if ok { "✅" } else { "❌" });
+    let index = members.iter().position(|m| *m == who).expect("witness exists only for members");
+    let proof = merkle.prove(index).expect("index in range");
+    let ok = proof.verify(&who, &merkle.root_hash());
+    println!("  Merkle 证明 {} 个哈希，验证: {}", proof.siblings.len(), if ok { "✅" } else { "❌" });
+    let ok = rsa_accumulator::verify(acc.modulus, acc.value, "mallory", witness);
+    println!("  ❌ mallory 拿 {} 的见证冒充: {}", who, if ok { "✅ (不应该发生)" } else { "❌ 拒绝" });
+
+    // ==========================================
+    // 3. 新增成员
+    // ==========================================
+    println!("\n[3] ivan 加入名单");
+    acc.add("ivan");
+    let ok = rsa_accumulator::verify(acc.modulus, acc.value, &who, witness);
+    println!("  新 A = {}，{} 的旧见证: {}", acc.value, who, if ok { "✅" } else { "❌ 失效" });
+    // 持有者只需要知道"新加了谁"，自己升一次幂，不需要向证明者重新索取
+    let updated = rsa_accumulator::update_witness(acc.modulus, witness, "ivan");
+    let ok = rsa_accumulator::verify(acc.modulus, acc.value, &who, updated);
+    println!("  w' = w^x_ivan = {}  验证: {}  (与重新计算的 {} 相同)", updated, if ok { "✅" } else { "❌" }, acc.witness(&who).unwrap_or(0));
+
+    // ==========================================
+    // 4. 并排对比
+    // ==========================================
+    println!("\n[4] 证明大小与计算量 (真实参数：RSA-2048，SHA-256)");
+    println!("  名单大小 | Merkle 证明 | 累加器见证 | 生成一个见证 (累加器 vs Merkle)");
+    for exp in [3u32, 10, 20, 30] {
+        let n = 1usize << exp;
+        println!(
+            "  {:>8} | {:>6} 字节 | {:>5} 字节 | {} 次模幂 vs {} 次查表",
+            format!("2^{}", exp),
+            exp as usize * HASH_BYTES,
+            RSA_ELEMENT_BYTES,
+            n - 1,
+            exp
+        );
+    }
+    println!("  => 名单超过 2^8 人，累加器的证明就比 Merkle 短；但证明者每生成一个见证都要 O(n) 次模幂");
+    println!("     (可以预先算好所有见证：分治法 O(n log n)，或者让每个用户自己维护，如第 3 节)");
+
+    // ==========================================
+    // 5. 陷门
+    // ==========================================
+    println!("\n[5] ❌ setup 的人偷偷留下了 p、q");
+    match rsa_accumulator::forge_witness_with_trapdoor(acc.value, "mallory") {
+        Some(fake) => {
+            let ok = rsa_accumulator::verify(acc.modulus, acc.value, "mallory", fake);
+            println!("  伪造 mallory 的见证 w = A^(1/x) = {}  验证: {}", fake, if ok { "✅ 通过 (白名单被绕过!)" } else { "❌" });
+        }
+        None => println!("  (mallory 的素数恰好整除 φ(N)，换个名字试试)"),
+    }
+    println!("  ✅ 真实部署用 MPC 仪式生成 N，或者改用未知阶的类群 (class group)，不需要任何人知道陷门");
+
+    // ==========================================
+    // 6. 不映射成素数
+    // ==========================================
+    println!("\n[6] ❌ 如果直接把整数放进累加器：集合 {{6, 35, 11}}");
+    let raw = [6u64, 35, 11];
+    let raw_acc = rsa_accumulator::accumulate_raw(acc.modulus, acc.generator, &raw);
+    let w6 = rsa_accumulator::accumulate_raw(acc.modulus, acc.generator, &[35, 11]);
+    println!("  6 的见证 = g^(35*11)，验证: {}", if rsa_accumulator::verify_raw(acc.modulus, raw_acc, 6, w6) { "✅" } else { "❌" });
+    for d in [2u64, 3] {
+        if let Some(fake) = rsa_accumulator::forge_divisor_witness(acc.modulus, w6, 6, d) {
+            let ok = rsa_accumulator::verify_raw(acc.modulus, raw_acc, d, fake);
+            println!("  伪造 {} 的见证 = w6^{}  验证: {}", d, 6 / d, if ok { "✅ 通过 (它根本不在集合里!)" } else { "❌" });
+        }
+    }
+    println!("  ✅ hash_to_prime 之后，每个元素都是素数，不存在这种因子关系");
+}
+
+/*
+关键点总结：
+    1. 常数大小：
+        不管集合有多大，累加值和见证都是一个 mod N 的数。代价是每个数 2048 位，
+        小集合时反而比 Merkle 证明大 —— 交叉点在几百个元素左右。
+
+    2. 两种方案的信任模型不同：
+        Merkle 树只依赖哈希函数的抗碰撞性，没有任何秘密；
+        RSA 累加器依赖"没人知道 N 的分解" (强 RSA 假设)，需要可信设置 (第 5 节)。
+
+    3. 计算量分布不同：
+        Merkle：建树 O(n) 个哈希，之后每个证明 O(log n) 次查表。
+        累加器：加入元素 O(1)，但见证生成 O(n) 次模幂；见证的增量更新 (第 3 节) 很便宜。
+
+    4. 元素必须是素数 (第 6 节)：
+        见证的本质是"A 的 x 次方根"。如果 x 不是素数，知道 x 次方根就能算出它每个因子的方根。
+
+    5. 累加器还能做的事 (本练习没有实现)：
+        非成员证明 (Bézout 系数)、批量成员证明 (一个见证证明多个元素)、
+        删除元素 (需要陷门或全部重算)。
+*/
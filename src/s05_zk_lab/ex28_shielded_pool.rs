@@ -0,0 +1,270 @@
+// src/s05_zk_lab/ex28_shielded_pool.rs
+use crate::common::rng::SimpleRng;
+
+use super::commitments::hash_commit::random_nonce;
+use super::ex02_smt::SparseMerkleTree;
+use super::ex03_mmr::{MerkleMountainRange, MmrProof};
+use super::hash::{sha256, short_hex, Digest};
+
+/*
+业务场景：屏蔽池 (Shielded Pool，Zcash Sapling / Tornado Cash 的核心模型)
+    链上看不到"谁给谁转了多少钱"，但仍然要保证：没人能凭空造钱、同一笔钱不能花两次。
+
+两个集合：
+    1. 票据承诺 (Note Commitment) 树：只追加。收款时把 cm = H(pk, value, rho) 追加进去 (ex03 的 MMR)。
+    2. 作废符 (Nullifier) 集合：花费时公开 nf = H(sk, rho)，节点检查 nf 从没出现过 (ex02 的 SMT 非成员证明)。
+
+为什么要两个集合？
+    cm 和 nf 都由同一个 rho 派生，但 nf 还需要私钥 sk：旁观者无法把 nf 和任何一个 cm 对上，
+    所以看不出花掉的是哪一张票据；同一张票据却永远只能算出同一个 nf，花第二次必然撞车。
+
+⚠️ 模拟的部分：
+    真实系统里，"cm 在树里、nf 由 sk 正确派生、金额守恒"这些检查是在 zk-SNARK 电路里做的，
+    验证者只看到 nf、锚点 (anchor) 和新的 cm。这里用一个 SpendWitness 结构体代表"电路的私有输入"，
+    由 check_circuit 在明处检查，其余代码只使用公开部分。
+*/
+
+// ==========================================
+// 1. 密钥与票据
+// ==========================================
+struct Wallet {
+    name: &'static str,
+    sk: Digest,
+    pk: Digest,
+}
+
+impl Wallet {
+    fn new(name: &'static str, rng: &mut SimpleRng) -> Self {
+        let sk = random_nonce(rng);
+        Wallet { name, sk, pk: derive_pk(&sk) }
+    }
+}
+
+fn tagged_hash(tag: &str, parts: &[&[u8]]) -> Digest {
+    let mut data = tag.as_bytes().to_vec();
+    for part in parts {
+        data.extend_from_slice(part);
+    }
+    sha256(&data)
+}
+
+fn derive_pk(sk: &Digest) -> Digest {
+    tagged_hash("pk", &[sk])
+}
+
+#[derive(Debug, Clone)]
+struct Note {
+    owner_pk: Digest,
+    value: u64,
+    // 每张票据独有的随机数：既让 cm 隐藏金额，又决定了它的 nf
+    rho: Digest,
+}
+
+impl Note {
+    fn new(owner: &Wallet, value: u64, rng: &mut SimpleRng) -> Self {
+        Note { owner_pk: owner.pk, value, rho: random_nonce(rng) }
+    }
+
+    // 承诺以 hex 字符串形式追加进 MMR (MMR 的叶子接口是 &str)
+    fn commitment(&self) -> String {
+        hex::encode(tagged_hash("cm", &[&self.owner_pk, &self.value.to_be_bytes(), &self.rho]))
+    }
+
+    fn nullifier(&self, sk: &Digest) -> Digest {
+        tagged_hash("nf", &[sk, &self.rho])
+    }
+}
+
+// ==========================================
+// 2. 花费交易
+// ==========================================
+// 电路的私有输入：链上永远看不到
+struct SpendWitness {
+    note: Note,
+    sk: Digest,
+    membership: MmrProof,
+}
+
+// 链上公开的部分；witness 站在 SNARK 证明的位置上，节点只把它交给 check_circuit
+struct SpendTx {
+    anchor: Digest,
+    nullifier: Digest,
+    outputs: Vec<Note>,
+    witness: SpendWitness,
+}
+
+struct ShieldedPool {
+    commitments: MerkleMountainRange,
+    nullifiers: SparseMerkleTree,
+    // 历史上出现过的所有承诺树根：花费时可以引用任意一个旧根，不必追着最新的根跑
+    anchors: Vec<Digest>,
+}
+
+impl ShieldedPool {
+    fn new() -> Self {
+        let commitments = MerkleMountainRange::new();
+        let anchors = vec![commitments.root()];
+        ShieldedPool { commitments, nullifiers: SparseMerkleTree::new(), anchors }
+    }
+
+    // 返回票据在承诺树里的位置，花费时要用它生成成员证明
+    fn append(&mut self, note: &Note) -> usize {
+        self.commitments.push(&note.commitment());
+        self.anchors.push(self.commitments.root());
+        self.commitments.leaf_count() - 1
+    }
+
+    fn spend(&self, wallet: &Wallet, note: &Note, position: usize, outputs: Vec<Note>) -> SpendTx {
+        let membership = self.commitments.prove(position).expect("note position exists");
+        SpendTx {
+            anchor: self.commitments.root(),
+            nullifier: note.nullifier(&wallet.sk),
+            outputs,
+            witness: SpendWitness { note: note.clone(), sk: wallet.sk, membership },
+        }
+    }
+
+    // 真实系统里这一步是验证一个 SNARK 证明，下面每一条都是电路里的一个约束
+    fn check_circuit(tx: &SpendTx) -> Result<(), String> {
+        let w = &tx.witness;
+        if !w.membership.verify(&w.note.commitment(), &tx.anchor) {
+            return Err(String::from("票据承诺不在锚点对应的树里"));
+        }
+        if derive_pk(&w.sk) != w.note.owner_pk {
+            return Err(String::from("花费者不知道票据主人的私钥"));
+        }
+        if w.note.nullifier(&w.sk) != tx.nullifier {
+            return Err(String::from("公开的作废符不是由这张票据派生的"));
+        }
+        let out: u64 = tx.outputs.iter().map(|n| n.value).sum();
+        if out != w.note.value {
+            return Err(format!("金额不守恒: 输入 {}，输出 {}", w.note.value, out));
+        }
+        Ok(())
+    }
+
+    // 节点对公开部分的检查 + "验证 SNARK"，全部通过才更新状态
+    fn submit(&mut self, tx: &SpendTx) -> Result<Vec<usize>, String> {
+        if !self.anchors.contains(&tx.anchor) {
+            return Err(String::from("锚点不是历史上的任何一个承诺树根"));
+        }
+        let key = hex::encode(tx.nullifier);
+        let proof = self.nullifiers.prove(&key);
+        if !proof.verify(&self.nullifiers.root(), self.nullifiers.defaults()) {
+            return Err(String::from("作废符集合的证明无效"));
+        }
+        if proof.value.is_some() {
+            return Err(format!("作废符 {}.. 已经出现过：双花!", short_hex(&tx.nullifier)));
+        }
+        Self::check_circuit(tx)?;
+        self.nullifiers.insert(&key, b"spent");
+        Ok(tx.outputs.iter().map(|note| self.append(note)).collect())
+    }
+}
+
+fn report(label: &str, result: &Result<Vec<usize>, String>) {
+    match result {
+        Ok(positions) => println!("  {}: ✅ 接受，新票据位于 {:?}", label, positions),
+        Err(e) => println!("  {}: ❌ 拒绝 —— {}", label, e),
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex28: 屏蔽池 (票据承诺 + 作废符) ---");
+    let mut rng = SimpleRng::from_time();
+    let alice = Wallet::new("Alice", &mut rng);
+    let bob = Wallet::new("Bob", &mut rng);
+    let mallory = Wallet::new("Mallory", &mut rng);
+    let mut pool = ShieldedPool::new();
+
+    // ==========================================
+    // 1. 存入
+    // ==========================================
+    println!("\n[1] 存入：三张票据的承诺追加进 MMR");
+    let alice_100 = Note::new(&alice, 100, &mut rng);
+    let alice_50 = Note::new(&alice, 50, &mut rng);
+    let bob_30 = Note::new(&bob, 30, &mut rng);
+    let pos_100 = pool.append(&alice_100);
+    pool.append(&alice_50);
+    pool.append(&bob_30);
+    for (owner, note) in [(&alice, &alice_100), (&alice, &alice_50), (&bob, &bob_30)] {
+        println!("  {} 的 {} 币 -> cm = {}..", owner.name, note.value, &note.commitment()[..8]);
+    }
+    println!("  承诺树根 (锚点) = {}..", short_hex(&pool.commitments.root()));
+    let old_anchor_tx = pool.spend(&bob, &bob_30, 2, vec![Note::new(&bob, 30, &mut rng)]);
+
+    // ==========================================
+    // 2. 正常花费
+    // ==========================================
+    println!("\n[2] Alice 花掉 100：付给 Bob 70，找零 30 给自己");
+    let outputs = vec![Note::new(&bob, 70, &mut rng), Note::new(&alice, 30, &mut rng)];
+    let tx = pool.spend(&alice, &alice_100, pos_100, outputs);
+    println!("  链上公开: 锚点 {}.. | 作废符 {}.. | 2 个新承诺", short_hex(&tx.anchor), short_hex(&tx.nullifier));
+    report("提交", &pool.submit(&tx));
+
+    // ==========================================
+    // 3. 双花
+    // ==========================================
+    println!("\n[3] ❌ Alice 把同一张 100 币票据再花一次");
+    let again = pool.spend(&alice, &alice_100, pos_100, vec![Note::new(&alice, 100, &mut rng)]);
+    println!("  新交易的作废符 {}.. (同一张票据 + 同一个 sk，只能算出同一个 nf)", short_hex(&again.nullifier));
+    report("提交", &pool.submit(&again));
+
+    // ==========================================
+    // 4. 其他攻击
+    // ==========================================
+    println!("\n[4] ❌ 其他作弊方式");
+    // Mallory 看到了 Alice 的 50 币票据明文，但没有 Alice 的 sk
+    let stolen = pool.spend(&mallory, &alice_50, 1, vec![Note::new(&mallory, 50, &mut rng)]);
+    report("Mallory 用自己的 sk 花 Alice 的票据", &pool.submit(&stolen));
+    // 凭空造一张 1000 币的票据：它的承诺从未进过树
+    let fake = Note::new(&mallory, 1000, &mut rng);
+    let mut forged = pool.spend(&mallory, &alice_50, 1, vec![Note::new(&mallory, 1000, &mut rng)]);
+    forged.nullifier = fake.nullifier(&mallory.sk);
+    forged.witness = SpendWitness { note: fake, sk: mallory.sk, membership: forged.witness.membership };
+    report("Mallory 伪造 1000 币票据", &pool.submit(&forged));
+    // 输出比输入多
+    let inflate = pool.spend(&alice, &alice_50, 1, vec![Note::new(&alice, 500, &mut rng)]);
+    report("Alice 50 币换 500 币", &pool.submit(&inflate));
+    // 用一个不存在的锚点
+    let mut bad_anchor = pool.spend(&alice, &alice_50, 1, vec![Note::new(&alice, 50, &mut rng)]);
+    bad_anchor.anchor = sha256(b"made-up root");
+    report("引用编造的锚点", &pool.submit(&bad_anchor));
+
+    // ==========================================
+    // 5. 旧锚点
+    // ==========================================
+    println!("\n[5] Bob 在 [2] 之前就签好了交易 (引用的是旧根)");
+    report("提交", &pool.submit(&old_anchor_tx));
+    println!("  => 节点保留历史根：钱包离线一段时间后生成的证明依然有效");
+
+    // ==========================================
+    // 6. 旁观者视角
+    // ==========================================
+    println!("\n[6] 链上能看到的全部信息");
+    println!("  承诺 {} 个 | 作废符 2 个 | 承诺树根 {}.. | 作废符树根 {}..", pool.commitments.leaf_count(), short_hex(&pool.commitments.root()), short_hex(&pool.nullifiers.root()));
+    println!("  作废符 {}.. 对应哪一个承诺？没有 sk 就无从得知 —— 交易图被切断了", short_hex(&tx.nullifier));
+}
+
+/*
+关键点总结：
+    1. 承诺 + 作废符 = 隐私 + 防双花：
+        cm = H(pk, value, rho) 在收款时公开，nf = H(sk, rho) 在花费时公开。
+        两者共享 rho，所以同一张票据的 nf 是唯一的；但只有知道 sk 的人能把它们联系起来。
+
+    2. 作废符必须依赖私钥：
+        如果 nf = H(cm)，任何人都能算出每个承诺的 nf，花费时一眼就能看出花的是哪张票据；
+        如果 nf 不依赖票据，同一张票据就能生成不同的 nf，双花检测就失效了。
+
+    3. 两种 Merkle 结构各司其职：
+        承诺集合只追加 -> MMR (ex03)，追加便宜，老证明只需少量更新；
+        作废符集合要回答"不存在" -> SMT (ex02)，非成员证明是它的强项。
+        (Zcash 实际上把作废符存在普通的键值集合里，SMT 让轻节点也能验证"没有双花")
+
+    4. 锚点历史：
+        证明引用的是生成证明那一刻的根。只接受最新根的话，每来一笔存款，所有还没上链的花费都会失效。
+
+    5. 这里缺了什么：
+        真实系统里 check_circuit 的内容被 zk-SNARK 取代 (ex15)，验证者看不到 SpendWitness；
+        输出票据要用接收者的公钥加密后发出去，Bob 才知道自己收到了钱。
+*/
@@ -0,0 +1,198 @@
+// src/s05_zk_lab/ex29_ceremony.rs
+use std::mem;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::commitments::ceremony::{self, Contribution};
+use super::commitments::kzg::{KzgProof, ScalarPoly, Srs};
+use super::math::group::{random_scalar, GroupElement, Scalar};
+
+/*
+业务场景：KZG 的可信设置由谁来做？
+    ex14 里 τ 是一个人选的，他只要偷偷记下 τ，就能为任意错误取值伪造证明。
+    以太坊 EIP-4844 的仪式有 14 万人参与：每人往 τ 里乘一个自己的随机数，然后销毁它。
+    只要其中一个人诚实，最终的 τ 就没人知道。
+
+本练习：
+    1. 5 个参与者各是一个线程，通过 channel 串行接力 SRS；你来决定谁诚实地销毁了秘密
+    2. 协调者逐棒验证，最终的 SRS 可以正常做 KZG 承诺
+    3. 留了后门的人联手，试图拼出 τ
+    4. 对照：所有人都留了后门
+    5. ❌ 恶意贡献：s = 0、另起炉灶、签名后篡改
+*/
+
+const PARTICIPANTS: [&str; 5] = ["alice", "bob", "carol", "dave", "erin"];
+const MAX_DEGREE: usize = 4;
+
+fn nonzero_scalar(rng: &mut SimpleRng) -> Scalar {
+    loop {
+        let s = random_scalar(rng);
+        if !s.is_zero() {
+            break s;
+        }
+    }
+}
+
+// 每个参与者一个线程：从上一棒收 SRS，贡献后交给下一棒，同时把贡献记录交给协调者
+// 返回 (按顺序的贡献记录, 每个人带出线程的秘密：诚实的人是 None)
+fn run_ceremony(initial: &Srs, honest: &[bool], seed: u64, verbose: bool) -> (Vec<Contribution>, Vec<Option<Scalar>>) {
+    let (log_tx, log_rx) = mpsc::channel::<Contribution>();
+    let (first_tx, mut upstream) = mpsc::channel::<Srs>();
+    let mut handles = Vec::with_capacity(PARTICIPANTS.len());
+    for (i, (&name, &is_honest)) in PARTICIPANTS.iter().zip(honest).enumerate() {
+        let (next_tx, next_rx) = mpsc::channel::<Srs>();
+        // 本线程拿走上游的接收端，把自己的接收端留给下一个人
+        let rx = mem::replace(&mut upstream, next_rx);
+        let log = log_tx.clone();
+        handles.push(thread::spawn(move || {
+            // 收到上一棒之前什么也做不了：仪式天然是串行的
+            let base = rx.recv().expect("上一棒的线程 panic");
+            let mut rng = SimpleRng::new(seed.wrapping_add(i as u64));
+            let secret = nonzero_scalar(&mut rng);
+            let contribution = Contribution::new(name, &base, secret, &mut rng);
+            if verbose {
+                println!("  #{} {:<5} 收到 SRS，乘上自己的 s，传给下一棒{}", i + 1, name, if is_honest { "" } else { "  (偷偷记下了 s)" });
+            }
+            // 最后一个人的下游没人接，发送失败也无所谓
+            let _ = next_tx.send(contribution.srs.clone());
+            log.send(contribution).expect("协调者一直在接收");
+            // 诚实的人：secret 随线程结束被丢弃，谁也拿不回来；留后门的人把它带出线程
+            if is_honest {
+                None
+            } else {
+                Some(secret)
+            }
+        }));
+    }
+    // 主线程自己的 log_tx 不丢掉，下面的 iter() 就永远等不到结束
+    drop(log_tx);
+    first_tx.send(initial.clone()).expect("第一个参与者在等待");
+    let contributions: Vec<Contribution> = log_rx.iter().collect();
+    let leaked = handles.into_iter().map(|h| h.join().expect("参与者线程 panic")).collect();
+    (contributions, leaked)
+}
+
+// 用猜出来的 τ 伪造 KZG 证明：p(z) 明明是 y，却"证明"它是 y + 1 (与 ex14 第 4 节相同)
+fn try_forge(srs: &Srs, tau_guess: Scalar) -> bool {
+    let poly = ScalarPoly::from_u64(&[5, 2, 0, 3]);
+    let commitment = srs.commit(&poly).expect("degree within SRS");
+    let z = tau_guess + Scalar::one(); // 保证 τ - z != 0
+    let lie = poly.evaluate(z) + Scalar::one();
+    let forged = KzgProof(GroupElement::generator().pow((poly.evaluate(tau_guess) - lie) / (tau_guess - z)));
+    srs.verify(&commitment, z, lie, &forged)
+}
+
+fn collude(srs: &Srs, leaked: &[Option<Scalar>]) {
+    let colluders: Vec<&str> = PARTICIPANTS.iter().zip(leaked).filter(|(_, s)| s.is_some()).map(|(&name, _)| name).collect();
+    let guess = leaked.iter().flatten().fold(Scalar::one(), |acc, &s| acc * s);
+    println!("  联手的人: {:?}，拼出 τ' = Π s_i = {}", colluders, guess);
+    let matches = GroupElement::generator().pow(guess) == srs.powers[1];
+    println!("  g^τ' == SRS[1]: {}", if matches { "✅ 拼出了真正的 τ" } else { "❌ 缺了诚实参与者那一份" });
+    println!("  用 τ' 伪造 KZG 证明: {}", if try_forge(srs, guess) { "✅ 通过 —— 参数被攻破" } else { "❌ 拒绝" });
+}
+
+pub fn run() {
+    println!("--- S05 Ex29: Powers of Tau 多方可信设置仪式 ---");
+    let mut rng = SimpleRng::from_time();
+    let initial = Srs::setup(MAX_DEGREE, Scalar::one());
+
+    // ==========================================
+    // 1. 仪式
+    // ==========================================
+    println!("\n参与者: {:?}", PARTICIPANTS);
+    let line = read_line("谁诚实地销毁了秘密？输入编号 1-5 (逗号分隔，直接回车默认 3，输入 0 表示没有人): ");
+    let chosen: Vec<usize> = if line.is_empty() {
+        vec![3]
+    } else {
+        line.split(',').filter_map(|s| s.trim().parse().ok()).collect()
+    };
+    let honest: Vec<bool> = (1..=PARTICIPANTS.len()).map(|i| chosen.contains(&i)).collect();
+
+    println!("\n[1] 起点 SRS (τ = 1) = [g, g, ..., g]，5 个线程接力");
+    let (contributions, leaked) = run_ceremony(&initial, &honest, rng.next_u64(), true);
+
+    // ==========================================
+    // 2. 协调者验证
+    // ==========================================
+    println!("\n[2] 协调者逐棒验证");
+    let mut previous = &initial;
+    for contribution in &contributions {
+        let verdict = match ceremony::verify_contribution(previous, contribution) {
+            Ok(()) => String::from("✅"),
+            Err(e) => format!("❌ {}", e),
+        };
+        println!("  {:<5} g^s = {:<4}  新 g^τ = {:<4}  {}", contribution.participant, contribution.public, contribution.srs.powers[1], verdict);
+        previous = &contribution.srs;
+    }
+    let srs = &contributions.last().expect("at least one participant").srs;
+    let shown: Vec<String> = srs.powers.iter().map(|p| p.to_string()).collect();
+    println!("  最终 SRS = [{}]", shown.join(", "));
+    let poly = ScalarPoly::from_u64(&[1, 4, 1, 5]);
+    let commitment = srs.commit(&poly).expect("degree within SRS");
+    let z = Scalar::new(9);
+    let (y, proof) = srs.open(&poly, z).expect("degree within SRS");
+    println!("  用它承诺 p(x) = {} 并在 z = {} 打开: {}", poly, z, if srs.verify(&commitment, z, y, &proof) { "✅" } else { "❌" });
+
+    // ==========================================
+    // 3. 留后门的人联手
+    // ==========================================
+    println!("\n[3] 留了后门的人联手");
+    if leaked.iter().all(Option::is_none) {
+        println!("  所有人都诚实，没有人手里有秘密");
+    } else {
+        collude(srs, &leaked);
+    }
+
+    // ==========================================
+    // 4. 对照：所有人都留了后门
+    // ==========================================
+    println!("\n[4] 对照：再办一次仪式，5 个人全部偷偷记下 s");
+    let (all_bad, all_leaked) = run_ceremony(&initial, &[false; PARTICIPANTS.len()], rng.next_u64(), false);
+    let ok = ceremony::verify_ceremony(&initial, &all_bad).is_ok();
+    println!("  仪式记录验证: {}  (后门是看不出来的：记下 s 不会在记录里留下任何痕迹)", if ok { "✅" } else { "❌" });
+    collude(&all_bad.last().expect("at least one participant").srs, &all_leaked);
+
+    // ==========================================
+    // 5. 恶意贡献
+    // ==========================================
+    println!("\n[5] ❌ 恶意贡献 (接在第 [1] 节仪式的最后一棒之后)");
+    let attempts = [
+        ("s = 0", Contribution::new("zero", srs, Scalar::zero(), &mut rng)),
+        // 不理会前面的人，从 τ = 1 重新开始，这样最终的 τ 就是 mallory 一个人的 s
+        ("另起炉灶", Contribution::new("mallory", &initial, nonzero_scalar(&mut rng), &mut rng)),
+        ("签名后篡改", {
+            let mut c = Contribution::new("tamper", srs, nonzero_scalar(&mut rng), &mut rng);
+            c.srs.powers[2] = c.srs.powers[2] * GroupElement::generator();
+            c
+        }),
+    ];
+    for (label, contribution) in &attempts {
+        match ceremony::verify_contribution(srs, contribution) {
+            Ok(()) => println!("  {}: ✅ 通过 (不应该发生)", label),
+            Err(e) => println!("  {}: ❌ {}", label, e),
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 1-of-n 信任：
+        τ = s_1 * s_2 * ... * s_n。少了任何一个 s_i，剩下的乘积与 τ 毫无关系 (第 3 节)。
+        所以参与者越多、越互不相干 (不同国家、不同机构、不同硬件)，越难全部串通。
+
+    2. 后门无法从记录中发现 (第 4 节)：
+        "记下 s" 和 "销毁 s" 留下的公开记录一模一样。验证只能保证每一棒都是正确的乘法，
+        不能保证秘密被销毁 —— 信任的来源是"至少有一个人诚实"，而不是验证本身。
+
+    3. 验证防的是破坏，不是偷看 (第 5 节)：
+        s = 0 或另起炉灶会把前面所有诚实参与者的贡献抹掉，这才是验证必须拦住的事。
+        Schnorr 签名证明贡献者知道 s，配对检查保证新 τ 是旧 τ 乘出来的、SRS 的每一项结构正确。
+
+    4. 仪式是串行的：
+        每一棒都依赖上一棒的输出，所以这里的线程不是为了加速，而是为了模拟"互不信任的独立参与者"：
+        每个线程只能通过 channel 拿到上一棒的 SRS，看不到别人的秘密 (所有权随消息转移，S04 Ex03)。
+        真实仪式里各方可能相隔几天、几千公里，顺序由协调者排队。
+*/
@@ -0,0 +1,233 @@
+// src/s05_zk_lab/ex30_inclusion_file.rs
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::common::input::read_line;
+use crate::common::json::{self, Json};
+
+use super::ex01_merkle::{hasher_by_name, Hasher, MerkleLeaf, MerkleProof, MerkleTree};
+
+/*
+业务场景：交易所核对充值
+    链上节点 (证明方) 手里有一整个区块的交易，交易所 (验证方) 只信任区块头里的 Merkle 根。
+    两边不在同一个进程、甚至不在同一台机器上，中间只传两个文件：
+        inclusion_root.json  —— 根 (相当于区块头，验证方通过别的可信渠道拿到)
+        inclusion_proof.json —— 某一笔交易 + 它的 Merkle 证明 (可以经过任何不可信的渠道)
+
+本练习分成两个菜单项，模拟两个独立的程序：
+    30 (证明方)：从 CSV 或 JSON 读交易、建树、为选中的交易写出根文件和证明文件
+    31 (验证方)：只读这两个文件，不碰原始交易数据，检查证明
+    ex25 序列化的是整棵树；这里验证方永远看不到其他交易 —— 这才是 Merkle 证明的用途。
+*/
+
+const DEFAULT_INPUT: &str = "data/transactions.csv";
+const ROOT_FILE: &str = "inclusion_root.json";
+const PROOF_FILE: &str = "inclusion_proof.json";
+
+struct Transaction {
+    id: u64,
+    from: String,
+    to: String,
+    amount: u64,
+}
+
+impl Transaction {
+    // 叶子用规范化的文本：同样的交易不管来自 CSV 还是 JSON，叶子 (以及根) 都完全一样
+    fn leaf(&self) -> String {
+        format!("#{} {} -> {}: {}", self.id, self.from, self.to, self.amount)
+    }
+}
+
+// 树直接保存 Transaction 结构体，需要哈希时才编码
+// 编码和 leaf() 的文本相同，所以验证方拿一行文本也能验证
+impl MerkleLeaf for Transaction {
+    fn leaf_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.leaf().into_bytes())
+    }
+}
+
+// CSV：第一行是表头 id,from,to,amount；不支持带引号的字段 (名字里不能有逗号)
+fn parse_csv(text: &str) -> Result<Vec<Transaction>, String> {
+    let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+    match lines.next() {
+        Some((_, header)) if header.trim() == "id,from,to,amount" => {}
+        _ => return Err(String::from("CSV 的第一行必须是表头 id,from,to,amount")),
+    }
+    lines
+        .map(|(n, line)| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [id, from, to, amount] = fields[..] else {
+                return Err(format!("第 {} 行应该有 4 列，实际 {} 列", n + 1, fields.len()));
+            };
+            Ok(Transaction {
+                id: id.parse().map_err(|_| format!("第 {} 行的 id 不是整数: {}", n + 1, id))?,
+                from: from.to_string(),
+                to: to.to_string(),
+                amount: amount.parse().map_err(|_| format!("第 {} 行的 amount 不是整数: {}", n + 1, amount))?,
+            })
+        })
+        .collect()
+}
+
+// JSON：[{"id": 1, "from": "alice", "to": "bob", "amount": 50}, ...]
+fn parse_json(text: &str) -> Result<Vec<Transaction>, String> {
+    json::parse(text)?
+        .as_array()
+        .ok_or("JSON 顶层必须是数组")?
+        .iter()
+        .map(|tx| {
+            Ok(Transaction {
+                id: tx.field("id", Json::as_u64)?,
+                from: tx.field("from", Json::as_str)?.to_string(),
+                to: tx.field("to", Json::as_str)?.to_string(),
+                amount: tx.field("amount", Json::as_u64)?,
+            })
+        })
+        .collect()
+}
+
+fn load_transactions(path: &str) -> Result<Vec<Transaction>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("读取 {} 失败: {}", path, e))?;
+    let txs = match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("csv") => parse_csv(&text)?,
+        Some("json") => parse_json(&text)?,
+        _ => return Err(String::from("只支持 .csv 和 .json 文件")),
+    };
+    if txs.is_empty() {
+        return Err(String::from("文件里没有交易"));
+    }
+    // 用 id 选交易，所以 id 必须唯一
+    let mut seen = HashSet::new();
+    if let Some(dup) = txs.iter().find(|tx| !seen.insert(tx.id)) {
+        return Err(format!("交易 id {} 重复", dup.id));
+    }
+    Ok(txs)
+}
+
+// ==========================================
+// 30. 证明方
+// ==========================================
+pub fn prove() {
+    println!("--- S05 Ex30: 离线包含证明 (证明方) ---");
+
+    let mut path = read_line(&format!("\n交易文件 (.csv / .json，直接回车使用 {}): ", DEFAULT_INPUT));
+    if path.is_empty() {
+        path = String::from(DEFAULT_INPUT);
+    }
+    let txs = match load_transactions(&path) {
+        Ok(txs) => txs,
+        Err(e) => {
+            println!("  ❌ {}", e);
+            return;
+        }
+    };
+    println!("\n[1] 读入 {} 笔交易", txs.len());
+    for tx in &txs {
+        println!("  {}", tx.leaf());
+    }
+
+    let default_id = txs[0].id;
+    let tree = MerkleTree::new_iterative(txs);
+    println!("\n[2] 建树 ({})，根 = {}", tree.hasher().name(), tree.root_hash());
+
+    let line = read_line(&format!("\n[3] 输入要证明的交易 id (直接回车默认 {}): ", default_id));
+    let id = line.parse().unwrap_or(default_id);
+    let Some(index) = tree.leaves.iter().position(|tx| tx.id == id) else {
+        println!("  ❌ 没有 id 为 {} 的交易", id);
+        return;
+    };
+    let proof = tree.prove(index).expect("index in range");
+
+    let root_json = Json::object(vec![
+        ("hasher", Json::Str(tree.hasher().name().to_string())),
+        ("root", Json::Str(tree.root_hash())),
+        ("count", Json::Number(tree.leaves.len() as i128)),
+    ]);
+    let proof_json = Json::object(vec![
+        ("leaf", Json::Str(hex::encode(tree.leaves[index].leaf_bytes()))),
+        ("proof", proof.to_json_value()),
+    ]);
+    for (file, value) in [(ROOT_FILE, root_json), (PROOF_FILE, proof_json)] {
+        match fs::write(file, value.to_string()) {
+            Ok(()) => println!("  ✅ 写入 {}", file),
+            Err(e) => println!("  ❌ 写入 {} 失败: {}", file, e),
+        }
+    }
+    println!("  证明包含 {} 个兄弟哈希；验证方不需要其余 {} 笔交易", proof.siblings.len(), tree.leaves.len() - 1);
+    println!("\n  下一步: 选 31 (验证方)，或者把两个文件拷到另一台机器上验证");
+}
+
+// ==========================================
+// 31. 验证方
+// ==========================================
+fn load_root(text: &str) -> Result<(Box<dyn Hasher>, String, usize), String> {
+    let value = json::parse(text)?;
+    let name = value.field("hasher", Json::as_str)?;
+    let hasher = hasher_by_name(name).ok_or_else(|| format!("未知的哈希函数 \"{}\"", name))?;
+    let root = value.field("root", Json::as_str)?.to_string();
+    let count = value.field("count", Json::as_u64)? as usize;
+    Ok((hasher, root, count))
+}
+
+fn load_proof(text: &str) -> Result<(String, MerkleProof), String> {
+    let value = json::parse(text)?;
+    let bytes = hex::decode(value.field("leaf", Json::as_str)?).map_err(|e| format!("叶子不是合法的 hex: {}", e))?;
+    let leaf = String::from_utf8(bytes).map_err(|_| String::from("叶子不是合法的 UTF-8"))?;
+    let proof = MerkleProof::from_json_value(value.field("proof", Some)?)?;
+    Ok((leaf, proof))
+}
+
+pub fn verify() {
+    println!("--- S05 Ex31: 离线包含证明 (验证方) ---");
+
+    let read = |file: &str| fs::read_to_string(file).map_err(|e| format!("读取 {} 失败: {} (先运行 30 生成)", file, e));
+    let loaded = read(ROOT_FILE)
+        .and_then(|text| load_root(&text).map_err(|e| format!("{} 无效: {}", ROOT_FILE, e)))
+        .and_then(|root| {
+            let proof = read(PROOF_FILE)?;
+            let proof = load_proof(&proof).map_err(|e| format!("{} 无效: {}", PROOF_FILE, e))?;
+            Ok((root, proof))
+        });
+    let ((hasher, root, count), (leaf, proof)) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            println!("  ❌ {}", e);
+            return;
+        }
+    };
+    println!("\n[1] 可信的根 ({}，共 {} 笔交易) = {}", hasher.name(), count, root);
+    println!("  证明文件: 下标 {} 的交易 \"{}\"，{} 个兄弟哈希", proof.index, leaf, proof.siblings.len());
+
+    // 验证方可以拿自己关心的那笔交易去核对，而不是照单全收文件里的叶子
+    let mut claimed = read_line("\n[2] 要核对的交易 (直接回车使用证明文件里的): ");
+    if claimed.is_empty() {
+        claimed = leaf;
+    }
+    if proof.index >= count {
+        println!("  ❌ 下标 {} 超出交易数 {}", proof.index, count);
+        return;
+    }
+    let ok = proof.verify_with(&claimed, &root, &hasher);
+    println!("  \"{}\" {}", claimed, if ok { "✅ 包含在这个根之下" } else { "❌ 证明与根不符" });
+}
+
+/*
+关键点总结：
+    1. 证明方和验证方只通过文件交流：
+        验证方 (31) 不读 data/ 里的原始交易，也不建树；它只需要根、一片叶子和 log2(n) 个兄弟哈希。
+        根必须来自可信渠道 (区块头、合约存储)；证明文件可以来自任何人 —— 造假会在验证时暴露。
+
+    2. 规范化叶子 (Transaction::leaf / MerkleLeaf)：
+        同一批交易的 CSV 和 JSON 版本得到同一个根 (试试两个示例文件)。
+        如果直接把 CSV 的原始行当叶子，多一个空格根就变了，两种格式的证明也不能互通。
+        树是 MerkleTree<Transaction>，按 id 查找、取叶子都直接用结构体；编码只在哈希时发生一次。
+
+    3. 输入校验放在边界上：
+        列数、整数格式、id 唯一、未知的哈希函数名、下标越界，全都在读文件时变成 Err，
+        后面的建树和验证代码可以假设数据是干净的。
+
+    4. 手写 CSV 解析的局限：
+        不支持带引号、带逗号的字段。真实项目应该用 csv crate，实验室坚持只用标准库。
+*/
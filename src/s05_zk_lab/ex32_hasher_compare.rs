@@ -0,0 +1,114 @@
+// src/s05_zk_lab/ex32_hasher_compare.rs
+use std::time::Instant;
+
+use crate::common::input::read_line;
+
+use super::ex01_merkle::{all_hashers, hasher_by_name, Hasher, MerkleTree};
+use super::hash::short_hex;
+
+/*
+业务场景：选型
+    同一份数据要上链 (Keccak，以太坊的 EVM 有预编译)、要进 ZK 电路 (Poseidon / MiMC)、
+    还要和比特币互通 (double SHA-256)。Merkle 树的逻辑完全一样，区别只在哈希后端。
+
+ex01 的 MerkleTree<L, H: Hasher> 在构造时选后端：
+    MerkleTree::with_hasher(leaves, Keccak256Hasher)
+    后端只需要实现 fn hash(&self, data: &[u8]) -> Digest，叶子 / 节点的域分离规则是 trait 的默认方法。
+
+本练习：
+    1. 同一批叶子，所有后端的根并排
+    2. 建树耗时 (CPU 上的快慢不代表电路里的成本)
+    3. ❌ 证明和根必须出自同一个后端
+    4. 按名字在运行时选后端 (和读 JSON 文件时一样)
+*/
+
+const BENCH_LEAVES: usize = 1024;
+
+fn root_with<H: Hasher>(leaves: &[String], hasher: H) -> String {
+    MerkleTree::with_hasher(leaves.to_vec(), hasher).root_hash()
+}
+
+pub fn run() {
+    println!("--- S05 Ex32: 可插拔的哈希后端 ---");
+
+    let line = read_line("\n输入叶子 (逗号分隔，直接回车使用 4 笔示例交易): ");
+    let leaves: Vec<String> = if line.is_empty() {
+        ["Tx1: Alice->Bob", "Tx2: Bob->Charlie", "Tx3: Charlie->Dave", "Tx4: Dave->Eve"].iter().map(|s| s.to_string()).collect()
+    } else {
+        line.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+    };
+    if leaves.is_empty() {
+        println!("  ❌ 没有叶子");
+        return;
+    }
+    let hashers = all_hashers();
+
+    // ==========================================
+    // 1. 并排比较根
+    // ==========================================
+    println!("\n[1] {} 片叶子，{} 个后端", leaves.len(), hashers.len());
+    for hasher in &hashers {
+        let tree = MerkleTree::with_hasher(leaves.clone(), hasher);
+        let proof = tree.prove(0).expect("tree is non-empty");
+        let ok = proof.verify_with(&leaves[0], &tree.root_hash(), hasher);
+        println!("  {:<16} H(\"abc\") = {}..  叶子 0 的证明 {}", hasher.name(), short_hex(&hasher.hash(b"abc")), if ok { "✅" } else { "❌" });
+        println!("  {:<16} 根 = {}", "", tree.root_hash());
+    }
+    println!("  (mock 后端只有 64 位，Poseidon / MiMC 的节点是一个 31 位的域元素，树里直接存十进制)");
+
+    // ==========================================
+    // 2. 建树耗时
+    // ==========================================
+    println!("\n[2] 建一棵 {} 片叶子的树", BENCH_LEAVES);
+    let bench: Vec<String> = (0..BENCH_LEAVES).map(|i| format!("Tx{}", i)).collect();
+    for hasher in &hashers {
+        let start = Instant::now();
+        root_with(&bench, hasher);
+        println!("  {:<16} {:?}", hasher.name(), start.elapsed());
+    }
+    println!("  => 这里的 Keccak 是没做任何优化的手写版本，debug 构建下的数字只看数量级");
+    println!("     真正的差别在电路里：Poseidon / MiMC 的约束数比 SHA-256 / Keccak 少几十倍 (见 ex18、ex23)");
+
+    // ==========================================
+    // 3. 混用后端
+    // ==========================================
+    println!("\n[3] ❌ 用一个后端的证明去配另一个后端的根");
+    for pair in hashers.windows(2) {
+        let (builder, verifier) = (&pair[0], &pair[1]);
+        let tree = MerkleTree::with_hasher(leaves.clone(), builder);
+        let proof = tree.prove(0).expect("tree is non-empty");
+        let ok = proof.verify_with(&leaves[0], &tree.root_hash(), verifier);
+        println!("  {} 的树，用 {} 验证: {}", builder.name(), verifier.name(), if ok { "✅ (不应该发生)" } else { "❌ 拒绝" });
+    }
+
+    // ==========================================
+    // 4. 按名字选
+    // ==========================================
+    let names: Vec<&str> = hashers.iter().map(|h| h.name()).collect();
+    let name = read_line(&format!("\n[4] 输入后端名字 {:?} (直接回车 keccak256): ", names));
+    let name = if name.is_empty() { String::from("keccak256") } else { name };
+    match hasher_by_name(&name) {
+        Some(hasher) => println!("  {} 的根 = {}", hasher.name(), root_with(&leaves, hasher)),
+        None => println!("  ❌ 没有叫 \"{}\" 的后端", name),
+    }
+}
+
+/*
+关键点总结：
+    1. trait 的默认方法承担"规则"，实现只提供"原料"：
+        Sha256Hasher、Keccak256Hasher 只写了 hash，域分离 (0x00 / 0x01 前缀) 由 Hasher::leaf / node 的默认实现负责。
+        比特币 (不加前缀)、Poseidon (标签 0 / 1 作为第一个域元素，直接压缩两个域元素) 这类特例重写 leaf / node 即可。
+
+    2. 静态分发和动态分发都能用：
+        MerkleTree<String, Keccak256Hasher> 在编译期确定后端，没有虚函数调用；
+        MerkleTree<String, &dyn Hasher> / MerkleTree<String, Box<dyn Hasher>> 在运行时选 (本练习的循环、ex25 读 JSON)。
+        ex01 为 &H 和 Box<H> 实现了 Hasher，两种写法共用同一份树的代码。
+
+    3. 泛型参数的默认值：
+        pub struct MerkleTree<L = String, H: Hasher = PrefixedHasher>。不关心哈希的代码 (FRI、基准测试) 照旧写 MerkleTree，
+        重构没有"传染"到它们。
+
+    4. 证明不带后端信息：
+        MerkleProof 只是一串哈希文本，验证者必须事先和证明者约定同一个后端 (第 3 节)。
+        所以 ex25 / ex30 的文件里都写了后端的名字。
+*/
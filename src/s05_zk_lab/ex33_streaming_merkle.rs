@@ -0,0 +1,137 @@
+// src/s05_zk_lab/ex33_streaming_merkle.rs
+use std::mem;
+use std::time::Instant;
+
+use crate::common::input::read_line;
+
+use super::ex01_merkle::{Keccak256Hasher, MerkleTree};
+use super::streaming_merkle::StreamingMerkleHasher;
+
+/*
+业务场景：给一个放不进内存的数据集算 Merkle 根
+    交易所每天导出几亿条流水，要公布一个 Merkle 根做储备证明；归档节点要给整条链的历史算承诺。
+    ex01 / ex04 的做法是先把全部叶子收进 Vec<String>，再分配 2n 个 Box<Node> ——
+    根只有几十字节，为了算它却要把整棵树放进内存。
+
+流式计算：
+    叶子一片一片地来 (读文件的一行、网络上的一条消息)，哈希完立刻丢掉。
+    状态只有每一层一个"等右兄弟"的节点，最多 log2(n) + 1 个：10 亿片叶子也只要 31 个哈希。
+
+本练习：
+    1. 和 MerkleTree 对拍：1..=17 片叶子 (覆盖各种奇数补齐)，换成 Keccak 后端再来一次
+    2. 山峰随叶子数的变化 (和 ex03 MMR 一样是二进制 +1)
+    3. 流式处理一个大数据流：耗时、状态大小
+    4. 内存对比：同样的数据先物化成 MerkleTree
+*/
+
+const DEFAULT_STREAM: usize = 1_000_000;
+// 物化整棵树的上限，再大就只跑流式的那一边
+const MATERIALIZE_LIMIT: usize = 1_000_000;
+
+fn stream_leaf(i: usize) -> String {
+    format!("Tx{}: payout {} coins", i, i % 1000)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex33: 常数内存的流式 Merkle 根 ---");
+
+    // ==========================================
+    // 1. 对拍
+    // ==========================================
+    println!("\n[1] 和 MerkleTree::new_iterative 对拍");
+    let mut mismatches = Vec::new();
+    for n in 1..=17 {
+        let leaves: Vec<String> = (0..n).map(stream_leaf).collect();
+        let mut stream = StreamingMerkleHasher::new();
+        leaves.iter().for_each(|leaf| stream.push(leaf));
+        let mut keccak = StreamingMerkleHasher::with_hasher(Keccak256Hasher);
+        leaves.iter().for_each(|leaf| keccak.push(leaf));
+        if stream.finalize() != MerkleTree::new_iterative(leaves.clone()).root_hash()
+            || keccak.finalize() != MerkleTree::with_hasher(leaves, Keccak256Hasher).root_hash()
+        {
+            mismatches.push(n);
+        }
+    }
+    if mismatches.is_empty() {
+        println!("  ✅ n = 1..=17，mock_hash+prefix 和 keccak256 两个后端的根全部一致");
+    } else {
+        println!("  ❌ 这些叶子数的根不一致: {:?}", mismatches);
+    }
+    println!("  空流的根: {:?} (和空树一样)", StreamingMerkleHasher::new().finalize());
+
+    // ==========================================
+    // 2. 山峰
+    // ==========================================
+    println!("\n[2] 每喂一片叶子后保存的山峰数");
+    let mut stream = StreamingMerkleHasher::new();
+    for i in 0..8 {
+        stream.push(&stream_leaf(i));
+        println!("  n = {} (0b{:04b})  山峰 {} 个", stream.len(), stream.len(), stream.peak_count());
+    }
+    println!("  => 山峰数 = n 的二进制里 1 的个数；n 是 2 的幂时只剩一座，它就是根");
+
+    // ==========================================
+    // 3. 大数据流
+    // ==========================================
+    let line = read_line(&format!("\n[3] 流的长度 (直接回车 {}): ", DEFAULT_STREAM));
+    let n = line.replace('_', "").parse().unwrap_or(DEFAULT_STREAM).max(1);
+    let start = Instant::now();
+    let mut stream = StreamingMerkleHasher::new();
+    let mut max_state = 0;
+    // 叶子是在循环里现造的，哈希完就随着这一轮迭代被 drop
+    for leaf in (0..n).map(stream_leaf) {
+        stream.push(&leaf);
+        max_state = max_state.max(stream.heap_bytes());
+    }
+    let peaks = stream.peak_count();
+    let streamed_root = stream.finalize();
+    println!("  {} 片叶子，耗时 {:.1?}", n, start.elapsed());
+    println!("  状态峰值: {} ({} 座山峰)", format_bytes(max_state), peaks);
+    println!("  根 = {}", streamed_root);
+
+    // ==========================================
+    // 4. 内存对比
+    // ==========================================
+    println!("\n[4] 对照：先把 {} 片叶子收进 Vec，再建整棵 Box<Node> 树", n);
+    if n > MATERIALIZE_LIMIT {
+        println!("  叶子数超过 {}，跳过物化 (按每片叶子约 2 个节点估算，这里会吃掉几百 MiB 以上)", MATERIALIZE_LIMIT);
+        return;
+    }
+    let start = Instant::now();
+    let leaves: Vec<String> = (0..n).map(stream_leaf).collect();
+    let leaf_bytes = leaves.capacity() * mem::size_of::<String>() + leaves.iter().map(String::capacity).sum::<usize>();
+    let tree = MerkleTree::new_iterative(leaves);
+    println!("  建树耗时 {:.1?}，{} 个 Box<Node>", start.elapsed(), tree.node_count());
+    println!("  叶子 Vec<String>: {}", format_bytes(leaf_bytes));
+    println!("  树的节点:         {}", format_bytes(tree.heap_bytes()));
+    println!("  流式的状态:       {}", format_bytes(max_state));
+    println!("  根一致: {}", if tree.root_hash() == streamed_root { "✅" } else { "❌" });
+}
+
+/*
+关键点总结：
+    1. 根只依赖"每层最右边那个未配对的节点"：
+        左兄弟一旦和右兄弟合并，它自己和它下面的整棵子树就再也用不到了。
+        所以流式计算只保存 peaks[h]，一层一个，内存 O(log n)，而物化整棵树是 O(n)。
+
+    2. 收尾规则必须和建树一致：
+        MMR (ex03) 用"装袋"把山峰折叠成根，得到的根和 MerkleTree 的不同；
+        这里 finalize 按 ex01 的规则让落单的节点和自己配对，所以两种算法可以对拍 (第 1 节)。
+        想要流式计算，就得挑一个"不需要回头看"的补齐规则 —— Bitcoin 的复制最后一个正好满足。
+
+    3. 借用而不是拿走：
+        push(&L) 只借用叶子，调用方可以从文件、网络、迭代器里边读边喂，每一片用完就 drop。
+        同一个 Hasher trait、同一个 MerkleLeaf trait，换后端 (with_hasher) 和换叶子类型都不需要改这里的代码。
+
+    4. 代价：
+        流式算法只能给出根，给不出证明 —— 证明需要兄弟节点，而它们已经被丢掉了。
+        需要证明时，要么只保留被证明叶子那一条路径上的兄弟 (边流边收集)，要么回到 MerkleTree。
+*/
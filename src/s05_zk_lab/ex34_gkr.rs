@@ -0,0 +1,202 @@
+// src/s05_zk_lab/ex34_gkr.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::gkr::{layer_final_value, layer_prover, line_point, restrict_to_line, LayerGate, LayeredCircuit};
+use super::math::field::F97;
+use super::math::multilinear::MultilinearPoly;
+use super::sumcheck::SumcheckVerifier;
+use super::transcript::session;
+
+/*
+业务场景：外包一整个电路，而不只是一个求和
+    ex13 的 sum-check 只能证明"一张表的和"。云服务器替你跑了一个多层的算术电路，
+    你手里只有输入和它声称的输出，怎么确认它真的逐层算对了？
+
+GKR 的思路：从输出往输入，一层一层地"归约声明"
+    1. 验证者拿到输出，在随机点 r 上算出 V_0(r) —— 这是关于第 0 层的一个声明
+    2. 每一层跑一次 sum-check，把"V_i(r) = m"变成关于下一层的两个声明 V_{i+1}(rx)、V_{i+1}(ry)
+    3. 沿直线 ℓ(t) 把两个声明合并成一个：V_{i+1}(ℓ(t*)) = q(t*)
+    4. 到了输入层，验证者自己用公开输入算 V_d(r)，对上了才接受
+    验证者从头到尾没有算过任何一个中间门。
+
+本练习 (F_97 上的 3 层电路，4 个输入)：
+    1. 逐层打印电路和取值
+    2. 选择证明者：诚实 / 谎报输出 / 偷换输入，跟着 transcript 看声明怎样一层层往下传
+    3. 统计：偷换输入的证明者侥幸过关的频率
+*/
+
+type Mle = MultilinearPoly<97>;
+
+const INPUTS: [u64; 4] = [3, 5, 2, 7];
+
+fn sample_circuit() -> LayeredCircuit {
+    LayeredCircuit::new(
+        INPUTS.len(),
+        vec![
+            vec![LayerGate::mul(0, 1), LayerGate::add(0, 1)],
+            vec![LayerGate::mul(0, 1), LayerGate::add(2, 3)],
+            vec![LayerGate::mul(0, 1), LayerGate::add(1, 2), LayerGate::mul(2, 3), LayerGate::add(3, 0)],
+        ],
+    )
+    .expect("sample circuit is well formed")
+}
+
+fn random_challenge(rng: &mut SimpleRng) -> F97 {
+    F97::new(rng.gen_range(F97::MODULUS))
+}
+
+fn show(values: &[F97]) -> String {
+    let shown: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    format!("({})", shown.join(", "))
+}
+
+// values 是证明者手里的逐层取值 (它可能撒谎)；public_inputs 是验证者自己知道的输入
+// verbose 时边记边打印 transcript。返回验证者是否接受
+fn run_protocol(circuit: &LayeredCircuit, public_inputs: &[F97], values: &[Vec<F97>], rng: &mut SimpleRng, verbose: bool) -> bool {
+    let (p_session, v_session) = session("GKR", verbose);
+    p_session.send("输出 y", show(&values[0]));
+
+    // 第 0 层的声明：验证者自己把输出做多线性扩展，在随机点上求值
+    let mut r: Vec<F97> = (0..circuit.layer_vars(0)).map(|_| random_challenge(rng)).collect();
+    v_session.send("r", show(&r));
+    let mut claim = Mle::new(values[0].clone()).evaluate(&r);
+    if verbose {
+        println!("  验证者自己算 V_0(r) = {}", claim);
+    }
+
+    for i in 0..circuit.depth() {
+        let k = circuit.layer_vars(i + 1);
+        if verbose {
+            println!("\n  === 第 {} 层 -> 第 {} 层：声明 V_{}{} = {}，sum-check 共 {} 轮 ===", i, i + 1, i, show(&r), claim, 2 * k);
+        }
+        let below = Mle::new(values[i + 1].clone());
+        let mut prover = layer_prover(circuit, i, &r, below.evals());
+        let mut verifier = SumcheckVerifier::new(claim);
+        for round in 1..=2 * k {
+            let poly = p_session.send(&format!("g_{}(X)", round), prover.round_poly());
+            let (g0, g1) = (poly.evaluate(F97::zero()), poly.evaluate(F97::one()));
+            let label = format!("g_{}(0) + g_{}(1) = {}  vs 声明值 {}", round, round, g0 + g1, verifier.claim());
+            if !v_session.check(&label, verifier.check_round(&poly)) {
+                return false;
+            }
+            let c = v_session.send(&format!("r_{}", round), random_challenge(rng));
+            verifier.bind(&poly, c);
+            prover.receive_challenge(&poly, c);
+        }
+
+        // sum-check 的终点是 g(rx, ry)：连线谓词验证者自己算，V_{i+1} 的两个值只能问证明者
+        let (rx, ry) = verifier.challenges().split_at(k);
+        let vx = p_session.send(&format!("V_{}(rx)", i + 1), below.evaluate(rx));
+        let vy = p_session.send(&format!("V_{}(ry)", i + 1), below.evaluate(ry));
+        let expected = layer_final_value(circuit, i, &r, rx, ry, vx, vy);
+        let label = format!("add~ * (vx + vy) + mul~ * vx * vy = {}  vs 声明值 {}", expected, verifier.claim());
+        if !v_session.check(&label, expected == verifier.claim()) {
+            return false;
+        }
+
+        // 两个声明 -> 一个：证明者发 q(t) = V_{i+1}(ℓ(t))，验证者检查两端后在直线上随机取一点
+        let q = p_session.send("q(t) = V(ℓ(t))", restrict_to_line(&below, rx, ry));
+        let ends_ok = q.evaluate(F97::zero()) == vx && q.evaluate(F97::one()) == vy;
+        if !v_session.check("q(0) == V(rx) 且 q(1) == V(ry)", ends_ok) {
+            return false;
+        }
+        let t = v_session.send("t*", random_challenge(rng));
+        r = line_point(rx, ry, t);
+        claim = q.evaluate(t);
+    }
+
+    // 输入层：唯一一次用到真实数据
+    let actual = Mle::new(public_inputs.to_vec()).evaluate(&r);
+    let label = format!("输入层 V_{}{} 用公开输入算 = {}  vs 声明值 {}", circuit.depth(), show(&r), actual, claim);
+    v_session.check(&label, actual == claim)
+}
+
+pub fn run() {
+    println!("--- S05 Ex34: GKR 协议 (分层电路，over F_97) ---");
+    let mut rng = SimpleRng::from_time();
+    let circuit = sample_circuit();
+    let inputs: Vec<F97> = INPUTS.iter().map(|&v| F97::new(v)).collect();
+    let values = circuit.evaluate(&inputs);
+
+    // ==========================================
+    // 1. 电路
+    // ==========================================
+    println!("\n[1] 电路 (第 0 层是输出，第 {} 层是输入)", circuit.depth());
+    println!("  第 {} 层 (输入): {}", circuit.depth(), show(&values[circuit.depth()]));
+    for i in (0..circuit.depth()).rev() {
+        let gates: Vec<String> = circuit.layer(i).iter().map(|g| format!("[{}]", g)).collect();
+        println!("  第 {} 层: {}  ->  {}", i, gates.join(" "), show(&values[i]));
+    }
+
+    // ==========================================
+    // 2. 交互
+    // ==========================================
+    let mode = loop {
+        match read_line("\n[2] 选择证明者: 1 = 诚实, 2 = 谎报输出 (y_0 + 1), 3 = 偷换输入 (x_0 + 1 后老实计算)，直接回车默认 1: ").as_str() {
+            // 空串也可能是 stdin 已经关闭 (EOF)，不能再问一遍，否则会一直刷提示
+            "" | "1" => break 1,
+            "2" => break 2,
+            "3" => break 3,
+            _ => println!("❌ 请输入 1、2 或 3"),
+        }
+    };
+    let claimed = match mode {
+        2 => {
+            let mut lied = values.clone();
+            lied[0][0] = lied[0][0] + F97::one();
+            lied
+        }
+        3 => {
+            let mut swapped = inputs.clone();
+            swapped[0] = swapped[0] + F97::one();
+            circuit.evaluate(&swapped)
+        }
+        _ => values.clone(),
+    };
+    let accepted = run_protocol(&circuit, &inputs, &claimed, &mut rng, true);
+    println!("\n  结论: {}", if accepted { "✅ 验证者接受" } else { "❌ 验证者拒绝" });
+    match (mode, accepted) {
+        (2, false) => println!("  谎报的输出和证明者手里的各层取值对不上，第 0 层的 sum-check 第一轮就露馅"),
+        (3, false) => println!("  每一层都自洽，sum-check 全部通过 —— 谎言一直被带到输入层，才和公开输入对不上"),
+        (2 | 3, true) => println!("  随机点恰好落在两个多项式的交点上，作弊者侥幸过关"),
+        _ => {}
+    }
+
+    // ==========================================
+    // 3. 统计
+    // ==========================================
+    println!("\n[3] 偷换输入的证明者自动跑 1000 次");
+    let trials = 1000;
+    let fooled = (0..trials)
+        .filter(|_| {
+            let mut swapped = inputs.clone();
+            swapped[0] = swapped[0] + F97::new(1 + rng.gen_range(F97::MODULUS - 1));
+            run_protocol(&circuit, &inputs, &circuit.evaluate(&swapped), &mut rng, false)
+        })
+        .count();
+    println!("  作弊成功 {} / {} 次", fooled, trials);
+    println!("  只有最后一个随机点 r 落在 V_x - V_x' 的零点上才会过关：概率 <= {} / 97 (Schwartz-Zippel)", circuit.layer_vars(circuit.depth()));
+}
+
+/*
+关键点总结：
+    1. GKR = 每层一次 sum-check：
+        V_i(r) = Σ add~(r,x,y)(V(x)+V(y)) + mul~(r,x,y)V(x)V(y)。
+        sum-check 把"对 2^(2k) 项求和"压成 2k 轮，每轮一个次数 <= 2 的单变量多项式，
+        所以 sumcheck.rs 只加了一个 sum_of_products 构造函数就能复用。
+
+    2. 声明一层层往下传：
+        sum-check 结束时验证者需要 V_{i+1}(rx) 和 V_{i+1}(ry) —— 它算不了，只能记下来作为对下一层的声明。
+        两个声明用直线 ℓ(t) 合并成一个，否则每往下一层声明数翻倍。
+        最后一层的声明由公开输入直接检查，这是整个协议里验证者唯一接触数据的地方。
+
+    3. 验证者的工作量：
+        每层 O(k) 轮 sum-check + 连线谓词 add~ / mul~ 在一个点上的求值。
+        对结构规整的电路 (数据并行、FFT) 连线谓词有简洁公式，验证者的总工作量远小于电路规模；
+        这里直接查 2^(k_i + 2k) 的表，只是为了演示。
+
+    4. 局限：
+        GKR 不是零知识的 (V(rx) 这些值会泄露中间结果)，也要求输入公开。
+        Libra、Virgo 等协议在 GKR 上加了掩码多项式和多项式承诺，才变成 zkSNARK。
+*/
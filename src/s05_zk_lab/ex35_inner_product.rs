@@ -0,0 +1,110 @@
+// src/s05_zk_lab/ex35_inner_product.rs
+use crate::common::input::read_line;
+
+use super::ipa::{self, inner_product, IpaGenerators};
+use super::math::group::Scalar;
+
+/*
+业务场景：证明"两个隐藏向量的内积等于 c"，而证明只有对数大小
+    范围证明 (ex22 第 5 节)、电路可满足性 (Bulletproofs 的算术电路版本)、Halo 的多项式承诺，
+    最后都归结为同一个问题：证明者承诺了 a、b，想让验证者相信 <a, b> = c。
+    直接打开要发 2n 个数；内积论证每轮把问题规模减半，只发 2 log2(n) + 2 个。
+
+本练习 (n = 8，群的阶 q = 1019)：
+    1. 逐轮打印折叠过程：向量怎样变短，L、R、挑战 x 是什么
+    2. ❌ 声称错误的内积
+    3. ❌ 篡改某一轮的 L
+    4. 证明大小随 n 的增长
+*/
+
+const N: usize = 8;
+const DEFAULT_A: [u64; N] = [1, 2, 3, 4, 5, 6, 7, 8];
+const DEFAULT_B: [u64; N] = [8, 7, 6, 5, 4, 3, 2, 1];
+
+fn read_vector(prompt: &str, default: [u64; N]) -> Vec<Scalar> {
+    let line = read_line(prompt);
+    let parsed: Vec<u64> = line.split(',').filter_map(|s| s.trim().parse().ok()).collect();
+    let values = if parsed.len() == N {
+        parsed
+    } else {
+        if !line.is_empty() {
+            println!("  需要恰好 {} 个整数，使用默认值", N);
+        }
+        default.to_vec()
+    };
+    values.into_iter().map(Scalar::new).collect()
+}
+
+pub fn run() {
+    println!("--- S05 Ex35: 内积论证 (Bulletproofs 的核心) ---");
+    let gens = IpaGenerators::setup(N);
+
+    // ==========================================
+    // 1. 折叠
+    // ==========================================
+    let a = read_vector(&format!("\n输入向量 a ({} 个整数，逗号分隔，直接回车 {:?}): ", N, DEFAULT_A), DEFAULT_A);
+    let b = read_vector(&format!("输入向量 b ({} 个整数，逗号分隔，直接回车 {:?}): ", N, DEFAULT_B), DEFAULT_B);
+    let c = inner_product(&a, &b);
+    let p = gens.commit(&a, &b);
+    println!("\n[1] <a, b> = {}，P = g^a * h^b * u^<a,b> = {}", c, p);
+    let proof = ipa::prove(&gens.g, &gens.h, gens.u, p, &a, &b, true);
+    match ipa::verify(&gens.g, &gens.h, gens.u, p, &proof) {
+        Ok(()) => println!("  验证者重放 {} 轮折叠后检查 P == g^a * h^b * u^(a*b): ✅", proof.l.len()),
+        Err(e) => println!("  验证: ❌ {}", e),
+    }
+    let (points, scalars) = proof.size();
+    println!("  证明: {} 个群元素 (L, R 各 {} 个) + {} 个标量；直接打开要 {} 个标量", points, proof.l.len(), scalars, 2 * N);
+
+    // ==========================================
+    // 2. 错误的内积
+    // ==========================================
+    println!("\n[2] ❌ 声称 <a, b> = {} (真实值 {})", c + Scalar::one(), c);
+    let lie = p * gens.u;
+    let forged = ipa::prove(&gens.g, &gens.h, gens.u, lie, &a, &b, false);
+    match ipa::verify(&gens.g, &gens.h, gens.u, lie, &forged) {
+        Ok(()) => println!("  验证: ✅ (不应该发生)"),
+        Err(e) => println!("  验证: ❌ {}", e),
+    }
+    println!("  多出来的 u 在每一轮都被带进 P'，最后那个 u^(a*b) 对不上");
+
+    // ==========================================
+    // 3. 篡改 L
+    // ==========================================
+    println!("\n[3] ❌ 把第 1 轮的 L 换成 L * g_0");
+    let mut tampered = proof.clone();
+    tampered.l[0] = tampered.l[0] * gens.g[0];
+    match ipa::verify(&gens.g, &gens.h, gens.u, p, &tampered) {
+        Ok(()) => println!("  验证: ✅ (不应该发生)"),
+        Err(e) => println!("  验证: ❌ {}", e),
+    }
+    println!("  挑战 x = H(P, L, R) 也跟着变了：证明者事先没法针对验证者的折叠方式凑答案");
+
+    // ==========================================
+    // 4. 证明大小
+    // ==========================================
+    println!("\n[4] 证明大小 (按元素个数)");
+    for log_n in [3u32, 6, 10, 20] {
+        let n = 1usize << log_n;
+        println!("  n = 2^{:<2}  直接打开 {:>8}   内积论证 {:>3}", log_n, 2 * n, 2 * log_n + 2);
+    }
+}
+
+/*
+关键点总结：
+    1. 折叠 = 用随机挑战把两半合成一半：
+        a' = a_lo * x + a_hi / x，g' = g_lo^(1/x) ∘ g_hi^x。g'^a' 展开后，"对角"项 g_lo^a_lo、g_hi^a_hi 原样保留，
+        交叉项 g_hi^(a_lo x^2)、g_lo^(a_hi / x^2) 由 L^(x^2)、R^(1/x^2) 补上 —— 这就是 L、R 的来历。
+
+    2. 挑战必须在 L、R 之后：
+        如果证明者先知道 x，就能挑 L、R 让任意错误的 P' 成立。Fiat-Shamir 把 x 绑定为 H(P, L, R)，
+        改动 L 会改变 x，整条折叠链都对不上 (第 3 节)。
+
+    3. 验证者的代价：
+        证明是对数大小，但验证者要自己折叠 g、h —— 每轮 O(n) 次幂运算，总共 O(n)。
+        所以 Bulletproofs 证明短、不需要可信设置，但验证比 Groth16 (常数时间) 慢；
+        Halo 等方案用递归把这部分"累积"起来，延迟到最后一次性检查。
+
+    4. 没有零知识：
+        这里的 IPA 最后发出的 a、b 会泄露信息。Bulletproofs 范围证明里 l、r 已经被 s_L、s_R 掩码过，
+        所以对它们做内积论证不会暴露金额 (bulletproof.rs)。
+*/
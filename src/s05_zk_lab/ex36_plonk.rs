@@ -0,0 +1,234 @@
+// src/s05_zk_lab/ex36_plonk.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::circuit::plonk::{PlonkCircuit, COLUMN_NAMES};
+use super::circuit::{Circuit, Wire};
+use super::math::field::F97;
+
+/*
+业务场景：同一个陈述，换一种"约束语言"
+    ex11 把 x^3 + x + 5 = 35 编译成了 R1CS。PLONK 系的证明系统 (PLONK、Halo2、Plonky2 ...)
+    用的是另一种格式：一张每行结构完全相同的门表，加上一个描述"哪些格子必须相等"的置换。
+
+本练习 (F_97，行数补齐到 8，定义域是 8 次单位根)：
+    1. 电路和它的 R1CS 编码 (ex11 的回顾)
+    2. 同一电路的 PLONK 门表：选择子 + 复制约束的环
+    3. 选择子多项式
+    4. 输入见证：逐行门检查、Z_H 整除检查、置换累乘
+    5. ❌ 改坏一个门的输出
+    6. ❌ 每个门都成立，但同一个 x 在不同行填了不同的值 —— 只有复制约束能抓住
+    7. 统计：小域上置换论证被骗过的频率
+*/
+
+// 和 ex11 完全相同的电路：x^3 + x + 5 == out
+fn cubic_circuit() -> Circuit<97> {
+    let mut c = Circuit::new();
+    let out = c.public_input("out");
+    let x = c.private_input("x");
+    let x2 = c.mul(x, x);
+    let x3 = c.mul(x2, x);
+    let sum = c.add(x3, x);
+    let five = c.constant(5);
+    let result = c.add(sum, five);
+    c.assert_eq(result, out);
+    c
+}
+
+// 选择子里的 96 其实是 -1，打印成有符号数更好读
+fn signed(v: F97) -> String {
+    if v.value() > F97::MODULUS / 2 {
+        format!("-{}", F97::MODULUS - v.value())
+    } else {
+        v.to_string()
+    }
+}
+
+fn row_with_output(plonk: &PlonkCircuit<97>, wire: Wire) -> usize {
+    plonk.rows.iter().position(|r| r.wires[2] == Some(wire)).expect("wire is some gate's output")
+}
+
+fn row_with_left(plonk: &PlonkCircuit<97>, wire: Wire) -> usize {
+    plonk.rows.iter().position(|r| r.wires[0] == Some(wire) && r.q_r == -F97::one()).expect("wire is asserted")
+}
+
+fn show_columns(columns: &[Vec<F97>; 3], pi: &[F97]) {
+    let show = |name: &str, column: &[F97]| {
+        let values: Vec<String> = column.iter().map(|v| format!("{:>3}", v)).collect();
+        println!("  {:<2} = [{}]", name, values.join(","));
+    };
+    for (name, column) in COLUMN_NAMES.iter().zip(columns) {
+        show(name, column);
+    }
+    show("PI", pi);
+}
+
+// 门检查 + 置换检查，打印结论，返回是否全部通过
+fn check_all(plonk: &PlonkCircuit<97>, columns: &[Vec<F97>; 3], pi: &[F97], rng: &mut SimpleRng) -> bool {
+    let gates_ok = match plonk.first_failing_gate(columns, pi) {
+        None => {
+            println!("  逐行门方程: ✅ 全部为 0");
+            true
+        }
+        Some(i) => {
+            println!("  逐行门方程: ❌ 第 {} 行 = {} ≠ 0", i, plonk.gate_value(i, columns, pi));
+            false
+        }
+    };
+    match plonk.gate_quotient(columns, pi) {
+        Some(t) => println!("  Z_H(X) = X^{} - 1 整除 G(X): ✅ 商 t(X) 次数 {}", plonk.size(), t.degree().unwrap_or(0)),
+        None => println!("  Z_H(X) = X^{} - 1 整除 G(X): ❌ 有余数", plonk.size()),
+    }
+
+    let (beta, gamma, z) = loop {
+        let (beta, gamma) = (F97::new(rng.gen_range(F97::MODULUS)), F97::new(rng.gen_range(F97::MODULUS)));
+        if let Some(z) = plonk.grand_product(columns, beta, gamma) {
+            break (beta, gamma, z);
+        }
+    };
+    let shown: Vec<String> = z.iter().map(|v| v.to_string()).collect();
+    println!("  置换累乘 (β = {}, γ = {}): Z = [{}]", beta, gamma, shown.join(", "));
+    let copies_ok = z[plonk.size()] == F97::one();
+    if copies_ok {
+        println!("  Z(ω^{}) == 1: ✅ 复制约束成立", plonk.size());
+    } else {
+        println!("  Z(ω^{}) = {} ≠ 1: ❌ 复制约束不成立", plonk.size(), z[plonk.size()]);
+    }
+    if let Some((cell, next)) = plonk.first_broken_copy(columns) {
+        println!("  (直接对照: 格子 {} 和 {} 本该相等)", plonk.cell_name(cell), plonk.cell_name(next));
+    }
+    gates_ok && copies_ok
+}
+
+pub fn run() {
+    println!("--- S05 Ex36: PLONK 风格的门约束 vs R1CS ---");
+    let mut rng = SimpleRng::from_time();
+    let circuit = cubic_circuit();
+    let name = |w: Wire| circuit.wire_name(w);
+
+    // ==========================================
+    // 1. R1CS 编码
+    // ==========================================
+    let r1cs = circuit.to_r1cs();
+    println!("\n[1] 电路 x^3 + x + 5 == out 的 R1CS 编码：{} 个全局变量，{} 条约束", r1cs.num_vars, r1cs.constraints.len());
+    for i in 0..r1cs.constraints.len() {
+        println!("  #{} {}", i, r1cs.format_constraint(i, |v| name(Wire(v))));
+    }
+    println!("  同一个变量 x 出现在好几条约束里 —— 它在 z 里只有一个位置，相等是\"免费\"的");
+
+    // ==========================================
+    // 2. PLONK 门表
+    // ==========================================
+    let plonk = match circuit.to_plonk() {
+        Ok(plonk) => plonk,
+        Err(e) => {
+            println!("❌ {}", e);
+            return;
+        }
+    };
+    println!("\n[2] PLONK 门表：{} 行 (补齐到 2 的幂)，每行都是 qL·a + qR·b + qM·a·b + qO·c + qC + PI = 0", plonk.size());
+    println!("  行 |  qL  qR  qM  qO  qC |   a      b      c");
+    for (i, row) in plonk.rows.iter().enumerate() {
+        let q: Vec<String> = [row.q_l, row.q_r, row.q_m, row.q_o, row.q_c].iter().map(|&v| format!("{:>3}", signed(v))).collect();
+        let w: Vec<String> = row.wires.iter().map(|w| format!("{:<6}", w.map_or(String::from("-"), name))).collect();
+        let tag = if plonk.public_rows.iter().any(|&(r, _)| r == i) { "  (公开输入, PI = -out)" } else { "" };
+        println!("  {:>2} | {} | {}{}", i, q.join(" "), w.join(" "), tag);
+    }
+    println!("  复制约束 (同一根导线的格子连成环，合起来就是置换 σ):");
+    for (wire, cells) in &plonk.copies {
+        let ring: Vec<String> = cells.iter().chain(cells.first()).map(|&c| plonk.cell_name(c)).collect();
+        println!("    {:<6}: {}", name(*wire), ring.join(" -> "));
+    }
+
+    // ==========================================
+    // 3. 选择子多项式
+    // ==========================================
+    let domain: Vec<String> = plonk.domain.iter().map(|w| w.to_string()).collect();
+    println!("\n[3] 定义域 H = {{ω^i}} = [{}] (ω = {})，陪集偏移 k = {:?}", domain.join(", "), plonk.domain[1], plonk.shifts.map(|k| k.value()));
+    for (label, poly) in plonk.selector_polys() {
+        println!("  {}(X) = {}", label, poly);
+    }
+    println!("  选择子只取决于电路，验证者可以预先承诺好；见证列 a(X)、b(X)、c(X) 由证明者承诺");
+
+    // ==========================================
+    // 4. 诚实见证
+    // ==========================================
+    let x: u64 = read_line("\n[4] 输入私有见证 x (直接回车默认 3): ").parse().unwrap_or(3);
+    let public = [F97::new(35)];
+    let pi = plonk.public_column(&public);
+    let wires = circuit.evaluate(&public, &[F97::new(x)]).expect("input counts match");
+    let columns = plonk.columns(&wires);
+    println!("  out = 35, x = {}", x);
+    show_columns(&columns, &pi);
+    let accepted = check_all(&plonk, &columns, &pi, &mut rng);
+    println!("  R1CS 对照: {}", if r1cs.is_satisfied(&wires) { "✅" } else { "❌" });
+    println!("  结论: {}", if accepted { "✅ 接受" } else { "❌ 拒绝" });
+
+    // ==========================================
+    // 5. 改坏一个门
+    // ==========================================
+    let mut broken = plonk.columns(&circuit.evaluate(&public, &[F97::new(3)]).expect("input counts match"));
+    let x3_row = row_with_output(&plonk, Wire(4));
+    println!("\n[5] ❌ x = 3，但把第 {} 行 (x^3 = x^2 * x) 的 c 从 27 改成 28", x3_row);
+    broken[2][x3_row] = F97::new(28);
+    check_all(&plonk, &broken, &pi, &mut rng);
+    println!("  一个格子变了，它所在的门方程和它参与的复制环一起露馅");
+
+    // ==========================================
+    // 6. 每个门都成立，但 x 不一致
+    // ==========================================
+    let (sum_row, result_row, assert_row) = (row_with_output(&plonk, Wire(5)), row_with_output(&plonk, Wire(7)), row_with_left(&plonk, Wire(7)));
+    println!("\n[6] ❌ 用 x = 4 作弊 (4^3 + 4 + 5 = 73 ≠ 35)：");
+    println!("  第 {} 行 x^3 + x 的 b 格不填 4，而填 30 - 64 = 63，让和变成 30，后面的行跟着改", sum_row);
+    let mut forged = plonk.columns(&circuit.evaluate(&public, &[F97::new(4)]).expect("input counts match"));
+    forged[1][sum_row] = F97::new(30) - forged[0][sum_row];
+    forged[2][sum_row] = F97::new(30);
+    forged[0][result_row] = F97::new(30);
+    forged[2][result_row] = F97::new(35);
+    forged[0][assert_row] = F97::new(35);
+    show_columns(&forged, &pi);
+    check_all(&plonk, &forged, &pi, &mut rng);
+    println!("  R1CS 里做不到这一步：x 只有一个变量位置，ex11 [4'] 的伪造在乘法约束上就露馅了");
+    println!("  PLONK 的每个格子都是独立的，\"b{} 就是 x\"只能靠置换论证来保证", sum_row);
+
+    // ==========================================
+    // 7. 统计
+    // ==========================================
+    println!("\n[7] 对第 6 节的伪造表，随机抽 1000 组 (β, γ)");
+    let trials = 1000;
+    let mut fooled = 0;
+    let mut done = 0;
+    while done < trials {
+        let (beta, gamma) = (F97::new(rng.gen_range(F97::MODULUS)), F97::new(rng.gen_range(F97::MODULUS)));
+        if let Some(z) = plonk.grand_product(&forged, beta, gamma) {
+            done += 1;
+            if z[plonk.size()] == F97::one() {
+                fooled += 1;
+            }
+        }
+    }
+    println!("  累乘恰好回到 1 (作弊成功) {} / {} 次", fooled, trials);
+    println!("  分子分母都是 β、γ 的低次多项式，不相等时撞上的概率不超过 3n / |F| = {} / 97 —— 真实系统用 ~2^255 的域", 3 * plonk.size());
+}
+
+/*
+关键点总结：
+    1. 一种门方程覆盖所有门：
+        R1CS 每条约束是 <A,z> * <B,z> = <C,z>，三个稀疏向量各不相同；
+        PLONK 每行都是 qL·a + qR·b + qM·a·b + qO·c + qC = 0，门的种类由 5 个选择子决定。
+        加法在 PLONK 里同样要占一行 (R1CS 也一样，见 ex11 的第 4 点)；Halo2 等系统再用自定义门把多步合成一行。
+
+    2. 相等关系的代价从"免费"变成"要证明"：
+        R1CS 的变量是全局的，PLONK 的格子是局部的。把同一根导线的所有格子连成环得到置换 σ，
+        证明"见证表在 σ 下不变"：用随机 β、γ 把 (值, 位置标签) 压成一个域元素，
+        比较两个乘积 Π (w + β·id + γ) 和 Π (w + β·σ(id) + γ)。第 6 节的伪造只有这一步能抓住。
+
+    3. 多项式化：
+        第 i 行对应 ω^i。"每行门方程都成立" ⇔ Z_H(X) = X^n - 1 整除 G(X)；
+        "累乘回到 1" ⇔ Z(ωX) * 分母(X) = Z(X) * 分子(X) 在 H 上成立，且 Z(1) = 1。
+        真实的 PLONK 把这两个整除关系合成一个商多项式，用 KZG (ex14) 承诺后只在一个随机点上打开。
+
+    4. 小域的风险：
+        F_97 上置换论证被骗过的频率肉眼可见 (第 7 节)，Schwartz-Zippel 的界 (次数 / 域大小)
+        在大域上才可以忽略。
+*/
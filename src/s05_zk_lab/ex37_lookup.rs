@@ -0,0 +1,171 @@
+// src/s05_zk_lab/ex37_lookup.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::circuit::Circuit;
+use super::lookup::{self, multiplicities, table_product, witness_product};
+use super::math::field::M31;
+
+/*
+业务场景：电路里到处都是范围检查
+    "这个数是一个字节"、"这个金额小于 2^64"、"这是一个合法的 ASCII 字符" ——
+    zkVM 和 zkEVM 里这类检查占了约束的大头。ex22 的办法是位分解：每一位一条布尔约束，
+    一个 8 位数就要 9 条约束。查找论证换个思路：把 0..256 做成一张公开表，
+    每个值只要证明"我在表里"，不管范围有多宽。
+
+本练习 (域 M31 = 2^31 - 1，表 T = {0, 1, ..., 255})：
+    1. 输入一组见证值，算出每个表项的重数 m_j，在随机 γ 上检查多重集等式
+    2. ❌ 见证里混进 300：诚实的证明者给不出重数，伪造的重数过不了等式
+    3. 统计：伪造的重数在 1000 个随机 γ 上侥幸过关的次数
+    4. 成本对比：位分解 vs 查表
+*/
+
+const TABLE_BITS: u32 = 8;
+const DEFAULT_WITNESS: [u64; 6] = [7, 200, 42, 42, 255, 0];
+
+fn byte_table() -> Vec<M31> {
+    (0..1u64 << TABLE_BITS).map(M31::new).collect()
+}
+
+fn random_gamma(rng: &mut SimpleRng) -> M31 {
+    M31::new(rng.gen_range(M31::MODULUS))
+}
+
+fn read_witness() -> Vec<M31> {
+    let line = read_line(&format!("\n[1] 输入见证值 (逗号分隔，直接回车 {:?}): ", DEFAULT_WITNESS));
+    let parsed: Vec<u64> = line.split(',').filter_map(|s| s.trim().parse().ok()).collect();
+    let values = if parsed.is_empty() { DEFAULT_WITNESS.to_vec() } else { parsed };
+    values.into_iter().map(M31::new).collect()
+}
+
+fn show_counts(table: &[M31], counts: &[u64]) -> String {
+    let used: Vec<String> = table
+        .iter()
+        .zip(counts)
+        .filter(|(_, &m)| m > 0)
+        .map(|(t, m)| format!("m[{}] = {}", t, m))
+        .collect();
+    format!("{}，其余 {} 项为 0", used.join(", "), counts.iter().filter(|&&m| m == 0).count())
+}
+
+// ex22 的做法在电路 DSL 里写出来：v = Σ b_i * 2^i，每一位 b_i * b_i == b_i
+fn bit_decomposition_circuit(bits: u32) -> Circuit<{ M31::MODULUS }> {
+    let mut c = Circuit::new();
+    let v = c.private_input("v");
+    let mut acc = None;
+    for i in 0..bits {
+        let b = c.private_input(&format!("b{}", i));
+        let bb = c.mul(b, b);
+        c.assert_eq(bb, b);
+        let weight = c.constant(1 << i);
+        let term = c.mul(b, weight);
+        acc = Some(match acc {
+            None => term,
+            Some(sum) => c.add(sum, term),
+        });
+    }
+    c.assert_eq(acc.expect("at least one bit"), v);
+    c
+}
+
+pub fn run() {
+    println!("--- S05 Ex37: 查找论证 (lookup) vs 位分解 ---");
+    let mut rng = SimpleRng::from_time();
+    let table = byte_table();
+
+    // ==========================================
+    // 1. 诚实的查表
+    // ==========================================
+    let witness = read_witness();
+    let shown: Vec<String> = witness.iter().map(|f| f.to_string()).collect();
+    println!("  见证 f = [{}]，表 T = {{0..{}}}", shown.join(", "), table.len() - 1);
+    match multiplicities(&table, &witness) {
+        Err(i) => println!("  ❌ f[{}] = {} 不在表里，证明者给不出重数", i, witness[i]),
+        Ok(counts) => {
+            println!("  重数: {}", show_counts(&table, &counts));
+            let gamma = random_gamma(&mut rng);
+            println!("  验证者的随机挑战 γ = {}", gamma);
+            println!("  Π (γ - f_i)        = {}", witness_product(&witness, gamma));
+            println!("  Π (γ - t_j)^(m_j)  = {}", table_product(&table, &counts, gamma));
+            match lookup::verify(&table, &witness, &counts, gamma) {
+                Ok(()) => println!("  验证: ✅ 两个多重集相等，每个见证值都在表里"),
+                Err(e) => println!("  验证: ❌ {}", e),
+            }
+        }
+    }
+
+    // ==========================================
+    // 2. 不在表里的值
+    // ==========================================
+    let bad: Vec<M31> = [7, 300, 42].iter().map(|&v| M31::new(v)).collect();
+    println!("\n[2] ❌ 见证 f = [7, 300, 42]，300 不是一个字节");
+    if let Err(i) = multiplicities(&table, &bad) {
+        println!("  诚实的证明者: f[{}] = {} 在表里找不到，算不出重数", i, bad[i]);
+    }
+    // 伪造：把 300 当成 44 (300 = 256 + 44) 记进重数，总数对得上
+    let mut forged = vec![0; table.len()];
+    for v in [7, 44, 42] {
+        forged[v] += 1;
+    }
+    println!("  作弊的证明者: 把 300 算成 44，给出 {}", show_counts(&table, &forged));
+    let gamma = random_gamma(&mut rng);
+    match lookup::verify(&table, &bad, &forged, gamma) {
+        Ok(()) => println!("  验证 (γ = {}): ✅ (不应该发生)", gamma),
+        Err(e) => println!("  验证 (γ = {}): ❌ {}", gamma, e),
+    }
+    println!("  左边有因子 (X - 300)，右边只有 (X - 44)：两个多项式不同，只有 γ 恰好是交点才会相等");
+
+    // ==========================================
+    // 3. 统计
+    // ==========================================
+    let trials = 1000;
+    let fooled = (0..trials)
+        .filter(|_| lookup::verify(&table, &bad, &forged, random_gamma(&mut rng)).is_ok())
+        .count();
+    println!("\n[3] 伪造的重数换 {} 个随机 γ: 侥幸过关 {} 次", trials, fooled);
+    println!("  两边次数都是 {}，最多 {} 个交点：单次过关概率 <= {} / {}", bad.len(), bad.len(), bad.len(), M31::MODULUS);
+
+    // ==========================================
+    // 4. 成本
+    // ==========================================
+    let dsl = bit_decomposition_circuit(TABLE_BITS).to_r1cs().constraints.len();
+    let bits = TABLE_BITS as usize;
+    println!("\n[4] 检查 \"v 是一个字节\" 要多少约束？");
+    println!("  位分解 (电路 DSL 直接编译): {} 条 —— 每一位 b*b == b，再加常数、乘权重、累加", dsl);
+    println!("  位分解 (手写 R1CS，线性部分并进约束): {} 条 —— 每位一条 b*(b-1) = 0，加一条重组", bits + 1);
+    println!("  查表: 每个值 1 次查找，外加整张表 {} 行 (只付一次，所有查找共用)", table.len());
+    println!("\n  要检查 k 个 8 位数 / 32 位数 (32 位拆成 4 个字节 limb 去查同一张表)：");
+    println!("  {:>6} | {:>10} {:>12} | {:>10} {:>12}", "k", "8 位:位分解", "查表", "32 位:位分解", "查表");
+    for k in [1usize, 10, 100, 1_000, 10_000] {
+        let table_rows = table.len();
+        println!(
+            "  {:>6} | {:>10} {:>12} | {:>10} {:>12}",
+            k,
+            k * (bits + 1),
+            k + table_rows,
+            k * 33,
+            k * 5 + table_rows
+        );
+    }
+    println!("  表的固定成本很快被摊薄：几十个以上的范围检查，查表就赢了，范围越宽赢得越多");
+}
+
+/*
+关键点总结：
+    1. 查找 = 多重集包含：
+        "f 的每个元素都在 T 里" ⇔ f 作为多重集等于"T 按重数 m 重复"。
+        多重集相等又等价于 Π (X - f_i) == Π (X - t_j)^(m_j)，在一个随机 γ 上比较就够了。
+
+    2. 为什么便宜：
+        位分解的成本和范围的位数成正比 (每一位一条约束)，查表的成本与范围无关 —— 每个值一行。
+        表本身要承诺一次，但 zkVM 里同一张字节表会被查成千上万次，固定成本几乎为零。
+        32 位数拆成 4 个字节 limb 查表，再用一条线性约束把它们拼回去。
+
+    3. 挑战必须在重数之后：
+        如果证明者事先知道 γ，它可以把一个不在表里的值"凑"成恰好满足等式的数。
+        所以 γ 来自验证者 (或 Fiat-Shamir 哈希 f、m 的承诺)，而且域要大 (这里用 M31，错误概率约 2^-29)。
+
+    4. 真实系统：
+        Plookup 用排序后的合并向量做置换论证；logUp 把乘积换成 Σ 1/(γ - f_i) = Σ m_j/(γ - t_j)，
+        同一个多重集等式，只是更适合 sum-check (ex13) 和多个表并行。
+*/
@@ -0,0 +1,225 @@
+// src/s05_zk_lab/ex38_commitment_bench.rs
+use std::time::{Duration, Instant};
+
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::commitments::kzg::{ScalarPoly, Srs};
+use super::ex01_merkle::MerkleTree;
+use super::ipa::{self, multi_exp, IpaGenerators};
+use super::math::group::{random_scalar, Scalar};
+
+/*
+业务场景：给证明系统选一个承诺方案
+    同一个向量 v (也可以看成多项式 p 在 0..n 上的取值)，要先承诺、之后在某个位置 i 打开 "v_i = y"。
+    Merkle 树 (STARK / FRI)、Pedersen 向量承诺 + 内积论证 (Bulletproofs / Halo)、KZG (PLONK / 以太坊 4844)
+    都能做这件事，差别在：要不要可信设置、承诺和证明多大、验证要花多少时间。
+
+本练习：
+    1. 同一个向量用三种方案承诺、打开、验证，并排打印耗时和大小
+    2. ❌ 在同一个位置声称一个错误的值，三种方案都拒绝
+    3. 证明大小随 n 的增长 (按真实参数折算成字节)
+
+⚠️ 计时只看数量级：
+    群是 q = 1019 的玩具群，KZG 的配对是 kzg.rs 里暴力求离散对数的 mock。
+    真实系统里一次配对约 1ms，一次群幂运算约 50µs，一次哈希约 0.5µs。
+*/
+
+const DEFAULT_N: usize = 64;
+const MAX_N: usize = 256;
+const REPS: u32 = 5;
+
+// 真实参数：SHA-256 摘要 32 字节；BLS12-381 G1 压缩点 48 字节；标量 32 字节
+const HASH_BYTES: usize = 32;
+const GROUP_ELEMENT_BYTES: usize = 48;
+const SCALAR_BYTES: usize = 32;
+
+struct BenchResult {
+    name: &'static str,
+    setup: &'static str,
+    commit: Duration,
+    open: Duration,
+    verify: Duration,
+    commitment_bytes: usize,
+    proof: String,
+    proof_bytes: usize,
+    ok: bool,
+    forged_rejected: bool,
+}
+
+// 跑 reps 次取平均，返回最后一次的结果
+fn time_it<T>(reps: u32, mut f: impl FnMut() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let mut last = f();
+    for _ in 1..reps {
+        last = f();
+    }
+    (last, start.elapsed() / reps)
+}
+
+fn bench_merkle(values: &[Scalar], index: usize) -> BenchResult {
+    let leaves: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    let (tree, commit) = time_it(REPS, || MerkleTree::new_iterative(leaves.clone()));
+    let root = tree.root_hash();
+    let (proof, open) = time_it(REPS, || tree.prove(index).expect("index is in range"));
+    let (ok, verify) = time_it(REPS, || proof.verify(&leaves[index], &root));
+    let forged = (values[index] + Scalar::one()).to_string();
+    BenchResult {
+        name: "Merkle (SHA-256)",
+        setup: "无 (透明)",
+        commit,
+        open,
+        verify,
+        commitment_bytes: HASH_BYTES,
+        proof: format!("{} 个哈希", proof.siblings.len()),
+        proof_bytes: proof.siblings.len() * HASH_BYTES,
+        ok,
+        forged_rejected: !proof.verify(&forged, &root),
+    }
+}
+
+// Pedersen 向量承诺 C = g^v；在位置 i 打开 = 用内积论证证明 <v, e_i> = y
+// P = C * h^(e_i) * u^y 正好是 ipa.rs 要的形状 g^a * h^b * u^<a,b>，其中 b = e_i 是公开的
+fn bench_pedersen(values: &[Scalar], index: usize) -> BenchResult {
+    let n = values.len();
+    let gens = IpaGenerators::setup(n);
+    let (commitment, commit) = time_it(REPS, || multi_exp(&gens.g, values));
+    let unit: Vec<Scalar> = (0..n).map(|j| if j == index { Scalar::one() } else { Scalar::zero() }).collect();
+    let statement = |y: Scalar| commitment * gens.h[index] * gens.u.pow(y);
+    let (proof, open) = time_it(REPS, || ipa::prove(&gens.g, &gens.h, gens.u, statement(values[index]), values, &unit, false));
+    let (ok, verify) = time_it(REPS, || ipa::verify(&gens.g, &gens.h, gens.u, statement(values[index]), &proof).is_ok());
+    let forged = statement(values[index] + Scalar::one());
+    let (points, scalars) = proof.size();
+    BenchResult {
+        name: "Pedersen + IPA",
+        setup: "无 (透明)",
+        commit,
+        open,
+        verify,
+        commitment_bytes: GROUP_ELEMENT_BYTES,
+        proof: format!("{} 群元素 + {} 标量", points, scalars),
+        proof_bytes: points * GROUP_ELEMENT_BYTES + scalars * SCALAR_BYTES,
+        ok,
+        forged_rejected: ipa::verify(&gens.g, &gens.h, gens.u, forged, &proof).is_err(),
+    }
+}
+
+// 把向量看成 p(0..n) 的取值：先插值出 p，再用 KZG 承诺；在 z = i 打开就得到 v_i
+fn bench_kzg(values: &[Scalar], index: usize, tau: Scalar) -> BenchResult {
+    let srs = Srs::setup(values.len() - 1, tau);
+    let points: Vec<(Scalar, Scalar)> = values.iter().enumerate().map(|(i, &v)| (Scalar::new(i as u64), v)).collect();
+    let (commitment, commit) = time_it(REPS, || {
+        let poly = ScalarPoly::interpolate(&points).expect("evaluation points are distinct");
+        (srs.commit(&poly).expect("degree fits the SRS"), poly)
+    });
+    let (commitment, poly) = commitment;
+    let z = Scalar::new(index as u64);
+    let ((y, proof), open) = time_it(REPS, || srs.open(&poly, z).expect("degree fits the SRS"));
+    let (ok, verify) = time_it(REPS, || y == values[index] && srs.verify(&commitment, z, y, &proof));
+    BenchResult {
+        name: "KZG (mock 配对)",
+        setup: "可信设置 (SRS)",
+        commit,
+        open,
+        verify,
+        commitment_bytes: GROUP_ELEMENT_BYTES,
+        proof: String::from("1 个群元素"),
+        proof_bytes: GROUP_ELEMENT_BYTES,
+        ok,
+        forged_rejected: !srs.verify(&commitment, z, y + Scalar::one(), &proof),
+    }
+}
+
+fn random_tau(rng: &mut SimpleRng) -> Scalar {
+    loop {
+        let t = random_scalar(rng);
+        if !t.is_zero() {
+            break t;
+        }
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex38: 承诺方案对比 (Merkle / Pedersen 向量 / KZG) ---");
+    println!("(提示：用 cargo run --release 运行，耗时对比更接近真实情况)");
+    let mut rng = SimpleRng::from_time();
+
+    let n = read_line(&format!("\n向量长度 n (2 的幂，最大 {}，直接回车默认 {}): ", MAX_N, DEFAULT_N))
+        .parse::<usize>()
+        .ok()
+        .filter(|n| n.is_power_of_two() && (2..=MAX_N).contains(n))
+        .unwrap_or(DEFAULT_N);
+    let values: Vec<Scalar> = (0..n).map(|_| random_scalar(&mut rng)).collect();
+    let index = n / 3;
+    println!("  随机向量 v (n = {})，在位置 i = {} 打开，v_i = {}", n, index, values[index]);
+
+    // ==========================================
+    // 1. 并排对比
+    // ==========================================
+    let results = [
+        bench_merkle(&values, index),
+        bench_pedersen(&values, index),
+        bench_kzg(&values, index, random_tau(&mut rng)),
+    ];
+    println!("\n[1] 承诺 -> 打开 -> 验证 (每步 {} 次取平均)", REPS);
+    println!("  {:<18} {:>12} {:>12} {:>12}   {:<16} {:>8}  {:<22} 验证", "方案", "承诺", "打开", "验证", "设置", "承诺大小", "证明");
+    for r in &results {
+        println!(
+            "  {:<18} {:>12.1?} {:>12.1?} {:>12.1?}   {:<16} {:>6} B  {:<14} {:>5} B  {}",
+            r.name,
+            r.commit,
+            r.open,
+            r.verify,
+            r.setup,
+            r.commitment_bytes,
+            r.proof,
+            r.proof_bytes,
+            if r.ok { "✅" } else { "❌" }
+        );
+    }
+    println!("  KZG 的\"承诺\"里包含了插值 (把取值变成系数)，它的\"验证\"是两次 mock 配对 = 暴力离散对数");
+
+    // ==========================================
+    // 2. 错误的值
+    // ==========================================
+    println!("\n[2] ❌ 声称 v_{} = {} (真实值 {})", index, values[index] + Scalar::one(), values[index]);
+    for r in &results {
+        println!("  {:<18} {}", r.name, if r.forged_rejected { "❌ 拒绝" } else { "✅ 接受 (不应该发生)" });
+    }
+
+    // ==========================================
+    // 3. 大小随 n 增长
+    // ==========================================
+    println!("\n[3] 单点打开的证明大小 (字节)");
+    println!("  {:>10} | {:>8} {:>10} {:>6}", "n", "Merkle", "Ped + IPA", "KZG");
+    for log_n in [4usize, 10, 20, 30] {
+        println!(
+            "  {:>10} | {:>8} {:>10} {:>6}",
+            format!("2^{}", log_n),
+            log_n * HASH_BYTES,
+            2 * log_n * GROUP_ELEMENT_BYTES + 2 * SCALAR_BYTES,
+            GROUP_ELEMENT_BYTES
+        );
+    }
+}
+
+/*
+关键点总结：
+    1. 三个维度的取舍：
+        Merkle   : 透明设置、只靠哈希 (抗量子)；证明 O(log n) 个哈希，验证 O(log n) 次哈希，都很快。
+                   但承诺没有代数结构，不能"相加"，证明 p(z) 需要 FRI 这样的额外协议 (ex16)。
+        Pedersen : 透明设置；证明 O(log n) 个群元素，但验证者要折叠生成元，O(n) 次群运算 (ex35)。
+        KZG      : 证明和验证都是常数 (1 个群元素、2 次配对)，代价是可信设置 (ex29) 和配对友好的曲线。
+
+    2. 同态：
+        Pedersen 和 KZG 的承诺可以相乘 —— Com(a) * Com(b) = Com(a + b)，批量打开、累积证明都靠这个；
+        Merkle 根之间没有任何关系。
+
+    3. "承诺大小"都是常数：
+        一个哈希或一个群元素，与 n 无关。真正随 n 变化的是证明大小和验证时间 (第 3 节)。
+
+    4. 工程上的选择：
+        以太坊 blob (EIP-4844) 用 KZG：证明要塞进区块，验证要便宜；
+        STARK 用 Merkle + FRI：不要可信设置，证明大一些但证明者快；
+        Zcash Orchard、Monero 用 Pedersen + IPA：不要可信设置，接受较慢的验证。
+*/
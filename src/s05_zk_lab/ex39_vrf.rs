@@ -0,0 +1,185 @@
+// src/s05_zk_lab/ex39_vrf.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::crypto::schnorr::{self, Keypair};
+use super::crypto::vrf::{self, VrfOutput};
+use super::hash::sha256;
+
+/*
+业务场景：权益证明 (PoS) 的出块人抽签
+    每个时隙 (slot) 要选出出块人，被选中的概率应该和质押量成正比。
+    用公开的随机数 (比如上一个区块哈希) 抽签，所有人都能提前算出下一个出块人 —— 攻击者可以提前 DDoS 它；
+    让每个人自己掷骰子，又没法证明自己没作弊。
+
+VRF (Algorand、Cardano Ouroboros Praos、Polkadot BABE)：
+    每个验证者用私钥对 "epoch / slot" 算 VRF，输出小于和质押量挂钩的阈值就当选。
+    没有私钥的人算不出别人的输出 (不可预测)；当选者出块时附上证明，所有人都能验证 (公开可验证)；
+    同一个 slot 只有一个合法输出，没法换着花样重抽 (唯一性)。
+
+本练习：
+    1. 对你输入的字符串求 VRF，验证证明；再跑一次，证明变了但输出不变
+    2. ❌ 篡改 Γ、换一把公钥
+    3. 为什么不用签名当随机数：Schnorr 签名可以反复重签来"刷"结果
+    4. 用 VRF 驱动一个 4 人的 PoS 出块抽签
+*/
+
+// Praos 的"活跃时隙系数" f：所有质押加起来，每个 slot 至少有一人当选的概率
+const ACTIVE_SLOT_COEFF: f64 = 0.5;
+const EPOCH: u64 = 7;
+const GRIND_TRIES: usize = 20;
+
+struct Validator {
+    name: &'static str,
+    stake: u64,
+    keys: Keypair,
+}
+
+// φ(α) = 1 - (1 - f)^α，α 是质押占比。φ 的形状保证"拆成多个账户"不会提高总当选概率
+fn threshold(stake: u64, total: u64) -> f64 {
+    1.0 - (1.0 - ACTIVE_SLOT_COEFF).powf(stake as f64 / total as f64)
+}
+
+fn slot_input(slot: u64) -> String {
+    format!("epoch-{}/slot-{}", EPOCH, slot)
+}
+
+// "用签名当随机数"：把签名哈希一下映射到 [0, 1)
+fn signature_lottery(keys: &Keypair, msg: &str, rng: &mut SimpleRng) -> f64 {
+    let sig = schnorr::sign(keys, msg.as_bytes(), rng);
+    let data: Vec<u8> = [sig.r.value().to_be_bytes(), sig.s.value().to_be_bytes()].concat();
+    VrfOutput(sha256(&data)).fraction()
+}
+
+pub fn run() {
+    println!("--- S05 Ex39: 可验证随机函数 (VRF) 与 PoS 出块抽签 ---");
+    let mut rng = SimpleRng::from_time();
+    let alice: Keypair = Keypair::generate(&mut rng);
+
+    // ==========================================
+    // 1. 求值与验证
+    // ==========================================
+    let alpha = read_line("\n[1] 输入 VRF 的输入 α (直接回车默认 \"epoch-7/slot-42\"): ");
+    let alpha = if alpha.is_empty() { slot_input(42) } else { alpha };
+    let (output, proof) = vrf::prove(&alice, &alpha, &mut rng);
+    println!("  Alice 的公钥 Y = {}", alice.public);
+    println!("  H = hash_to_group(Y, α) = {}，Γ = H^x = {}", vrf::hash_input(alice.public, &alpha), proof.gamma);
+    println!("  输出 β = {} (映射到 [0,1) = {:.4})", output, output.fraction());
+    println!("  证明 π = (Γ, a = {}, z = {})", proof.commitment, proof.response);
+    match vrf::verify(alice.public, &alpha, &proof) {
+        Ok(beta) => println!("  验证: ✅ DLEQ 成立，验证者自己从 Γ 算出 β = {}", beta),
+        Err(e) => println!("  验证: ❌ {}", e),
+    }
+    println!("  再证明 3 次 (nonce 不同):");
+    for _ in 0..3 {
+        let (again, proof) = vrf::prove(&alice, &alpha, &mut rng);
+        println!("    a = {:<12} z = {:<5} β = {} {}", proof.commitment.to_string(), proof.response, again, if again == output { "(相同)" } else { "(不同！)" });
+    }
+
+    // ==========================================
+    // 2. 篡改
+    // ==========================================
+    println!("\n[2] ❌ 篡改");
+    let mut forged = proof;
+    forged.gamma = forged.gamma * forged.gamma;
+    match vrf::verify(alice.public, &alpha, &forged) {
+        Ok(_) => println!("  把 Γ 换成 Γ^2 (想要另一个输出): ✅ (不应该发生)"),
+        Err(e) => println!("  把 Γ 换成 Γ^2 (想要另一个输出): ❌ {}", e),
+    }
+    let bob: Keypair = Keypair::generate(&mut rng);
+    match vrf::verify(bob.public, &alpha, &proof) {
+        Ok(_) => println!("  Bob 拿 Alice 的证明冒充自己的输出: ✅ (不应该发生)"),
+        Err(e) => println!("  Bob 拿 Alice 的证明冒充自己的输出: ❌ {}", e),
+    }
+
+    // ==========================================
+    // 3. 签名不是 VRF
+    // ==========================================
+    let p = 0.05;
+    println!("\n[3] 如果用 \"哈希(签名) < {}\" 抽签，一个 slot 试 {} 次签名：", p, GRIND_TRIES);
+    let slots = 500;
+    let (mut honest_wins, mut grind_wins) = (0, 0);
+    for slot in 0..slots {
+        let msg = slot_input(slot);
+        if vrf::prove(&alice, &msg, &mut rng).0.fraction() < p {
+            honest_wins += 1;
+        }
+        if (0..GRIND_TRIES).any(|_| signature_lottery(&alice, &msg, &mut rng) < p) {
+            grind_wins += 1;
+        }
+    }
+    println!("  VRF        : {} 个 slot 里当选 {} 次 (期望 {:.0})", slots, honest_wins, p * slots as f64);
+    println!("  签名 + 重签: {} 个 slot 里当选 {} 次 (期望 1 - (1 - p)^{} = {:.0}%)", slots, grind_wins, GRIND_TRIES, (1.0 - (1.0 - p).powi(GRIND_TRIES as i32)) * 100.0);
+    println!("  签名里的 nonce 由签名者随便选，每个都是合法签名；VRF 的 β 只由 (x, α) 决定，重算多少次都一样");
+
+    // ==========================================
+    // 4. PoS 出块抽签
+    // ==========================================
+    let validators: Vec<Validator> = [("Alice", 50), ("Bob", 30), ("Carol", 15), ("Dave", 5)]
+        .into_iter()
+        .map(|(name, stake)| Validator { name, stake, keys: Keypair::generate(&mut rng) })
+        .collect();
+    let total: u64 = validators.iter().map(|v| v.stake).sum();
+    println!("\n[4] PoS 抽签 (epoch {}，f = {})", EPOCH, ACTIVE_SLOT_COEFF);
+    for v in &validators {
+        println!("  {:<6} 质押 {:>3}  阈值 φ = {:.3}", v.name, v.stake, threshold(v.stake, total));
+    }
+    println!("\n  前 8 个 slot (每个验证者私下算自己的 VRF，只有当选者公布证明，其他人验证):");
+    for slot in 0..8 {
+        let alpha = slot_input(slot);
+        let leaders: Vec<String> = validators
+            .iter()
+            .filter_map(|v| {
+                let (output, proof) = vrf::prove(&v.keys, &alpha, &mut rng);
+                (output.fraction() < threshold(v.stake, total)).then(|| {
+                    let checked = vrf::verify(v.keys.public, &alpha, &proof).is_ok_and(|beta| beta.fraction() < threshold(v.stake, total));
+                    format!("{} (β = {:.3}, 证明 {})", v.name, output.fraction(), if checked { "✅" } else { "❌" })
+                })
+            })
+            .collect();
+        let shown = if leaders.is_empty() { String::from("无人当选 (空块)") } else { leaders.join(", ") };
+        println!("    slot {}: {}", slot, shown);
+    }
+
+    let slots = 2000;
+    let mut wins = vec![0u32; validators.len()];
+    let (mut empty, mut contested) = (0, 0);
+    for slot in 0..slots {
+        let alpha = slot_input(slot);
+        let winners: Vec<usize> = (0..validators.len())
+            .filter(|&i| vrf::prove(&validators[i].keys, &alpha, &mut rng).0.fraction() < threshold(validators[i].stake, total))
+            .collect();
+        for &i in &winners {
+            wins[i] += 1;
+        }
+        match winners.len() {
+            0 => empty += 1,
+            1 => {}
+            _ => contested += 1,
+        }
+    }
+    println!("\n  {} 个 slot 的统计:", slots);
+    for (v, &w) in validators.iter().zip(&wins) {
+        println!("    {:<6} 当选 {:>4} 次，期望 {:>4.0}", v.name, w, threshold(v.stake, total) * slots as f64);
+    }
+    println!("    空 slot {} 个，多人同时当选 {} 个 (Praos 靠最长链规则消解分叉)", empty, contested);
+    println!("  (玩具群只有 1019 个元素，Γ 也只有这么多种取值；统计上够用，安全上当然不够)");
+}
+
+/*
+关键点总结：
+    1. VRF = 确定性 + 可验证：
+        Γ = H(α)^x 对 (x, α) 唯一，β 从 Γ 导出；DLEQ 证明 Γ 和公钥 Y 用的是同一个 x。
+        和 BLS 签名 (本身就是确定性的) 做 VRF 是同一个思路，这里用的是 RFC 9381 ECVRF 的结构。
+
+    2. 不可预测 ≠ 保密：
+        出块前只有验证者自己知道是否当选，攻击者不知道该打谁；出块时公开证明，谁都能核对。
+
+    3. 唯一性防"刷"：
+        签名带随机 nonce，当选者可以重签到满意为止 (第 3 节)；VRF 的输出没有可调的自由度。
+        输入里还要混进 epoch 随机数，否则有人可以在质押前"挑"一把对未来 slot 有利的私钥。
+
+    4. 阈值 φ(α) = 1 - (1 - f)^α：
+        把质押拆成两个账户，至少一个当选的概率是 1 - (1-f)^α1 * (1-f)^α2 = φ(α1 + α2)，不多不少 ——
+        抽签对"拆分账户"是中立的。
+*/
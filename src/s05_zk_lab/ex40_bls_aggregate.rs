@@ -0,0 +1,145 @@
+// src/s05_zk_lab/ex40_bls_aggregate.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::crypto::bls::{self, AggregateSignature, BlsSignature};
+use super::crypto::schnorr::Keypair;
+use super::math::group::{random_scalar, GroupElement};
+
+/*
+业务场景：共识里的投票
+    以太坊每个 slot 有成千上万个验证者为同一个区块头投票。逐个附上签名，区块要多带几百 KB，
+    验证者还要做几千次验签。BLS 聚合把它们压成【一个】签名 + 一张"谁签了"的位图，
+    验证只需要把公钥乘起来，再做 2 次配对。
+
+本练习 (7 个验证者，法定人数 5 = ⌈2n/3⌉)：
+    1. 注册：每个验证者公布公钥和持有证明 (PoP)
+    2. 5 个人为区块头签名，聚合成一个签名，按位图验证
+    3. ❌ 有人签了另一个区块头 / 位图里多写了一个没签的人 / 人数不够
+    4. ❌ 恶意公钥攻击：没有 PoP 时，Mallory 一个人就能伪造"Alice 和 Mallory 都签了"
+    5. 大小和验证成本
+
+⚠️ 配对是模拟的 (kzg.rs 的 mock_pairing，暴力离散对数)，见 crypto/bls.rs 顶部的说明。
+*/
+
+const VALIDATORS: usize = 7;
+const SIGNED: [bool; VALIDATORS] = [true, true, false, true, true, false, true];
+
+// 真实参数 (BLS12-381)：签名 G1 压缩点 48 字节，Ed25519/Schnorr 签名 64 字节
+const BLS_SIGNATURE_BYTES: usize = 48;
+const SCHNORR_SIGNATURE_BYTES: usize = 64;
+
+fn show_bitfield(signers: &[bool]) -> String {
+    signers.iter().map(|&s| if s { '1' } else { '0' }).collect()
+}
+
+fn report(label: &str, result: Result<(), String>) {
+    match result {
+        Ok(()) => println!("  {}: ✅", label),
+        Err(e) => println!("  {}: ❌ {}", label, e),
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex40: BLS 签名聚合 (模拟配对) ---");
+    let mut rng = SimpleRng::from_time();
+    let quorum = (2 * VALIDATORS).div_ceil(3);
+
+    // ==========================================
+    // 1. 注册
+    // ==========================================
+    println!("\n[1] 注册 {} 个验证者 (公钥 + PoP)", VALIDATORS);
+    let keys: Vec<Keypair> = (0..VALIDATORS).map(|_| Keypair::generate(&mut rng)).collect();
+    let validator_set: Vec<GroupElement> = keys.iter().map(|k| k.public).collect();
+    for (i, k) in keys.iter().enumerate() {
+        let pop = bls::prove_possession(k);
+        println!("  V{}  pk = {:<5} PoP = {:<5} {}", i, k.public, pop.0, if bls::verify_possession(k.public, &pop) { "✅" } else { "❌" });
+    }
+
+    // ==========================================
+    // 2. 签名与聚合
+    // ==========================================
+    let header = read_line("\n[2] 输入要投票的区块头 (直接回车默认 \"block #1024, parent 0xabc\"): ");
+    let header = if header.is_empty() { String::from("block #1024, parent 0xabc") } else { header };
+    let votes: Vec<Option<BlsSignature>> = keys
+        .iter()
+        .zip(SIGNED)
+        .map(|(k, signed)| signed.then(|| bls::sign(k, &header)))
+        .collect();
+    for (i, vote) in votes.iter().enumerate() {
+        match vote {
+            Some(sig) => println!("  V{} 签名 σ = H(m)^x = {:<5} 单独验证 {}", i, sig.0, if bls::verify(keys[i].public, &header, sig) { "✅" } else { "❌" }),
+            None => println!("  V{} 离线，没有投票", i),
+        }
+    }
+    let agg = AggregateSignature::from_signatures(&votes);
+    println!("  聚合: σ_agg = Π σ_i = {}，位图 {}", agg.signature.0, show_bitfield(&agg.signers));
+    report(&format!("按位图验证 ({} / {} 人，法定人数 {})", agg.signer_count(), VALIDATORS, quorum), agg.verify(&validator_set, &header, quorum));
+    println!("  签名是确定性的：V0 再签一次得到 {} (和上面相同)", bls::sign(&keys[0], &header).0);
+
+    // ==========================================
+    // 3. 错误的聚合
+    // ==========================================
+    println!("\n[3] ❌ 三种错误的聚合");
+    let mut mixed = votes.clone();
+    mixed[1] = Some(bls::sign(&keys[1], "block #1024, parent 0xdef"));
+    report("V1 签的是另一个区块头", AggregateSignature::from_signatures(&mixed).verify(&validator_set, &header, quorum));
+
+    let mut padded = agg.clone();
+    padded.signers[2] = true;
+    report("位图里把没签的 V2 也标成 1", padded.verify(&validator_set, &header, quorum));
+
+    let few: Vec<Option<BlsSignature>> = votes.iter().enumerate().map(|(i, v)| if i < 3 { *v } else { None }).collect();
+    report("只有 V0、V1 两票", AggregateSignature::from_signatures(&few).verify(&validator_set, &header, quorum));
+
+    // ==========================================
+    // 4. 恶意公钥
+    // ==========================================
+    println!("\n[4] ❌ 恶意公钥攻击：Mallory 看到 Alice (V0) 的公钥后注册 pk_M = g^m / pk_A");
+    let m = random_scalar(&mut rng);
+    let rogue = GroupElement::generator().pow(m) * keys[0].public.inverse();
+    let forged = BlsSignature(bls::hash_message(&header).pow(m));
+    let pair = [keys[0].public, rogue];
+    println!("  pk_A * pk_M = g^m，Mallory 用 m 独自签出 σ = H(m)^m = {}", forged.0);
+    println!("  不检查 PoP: \"Alice 和 Mallory 都签了\" 的聚合验证 {}", if bls::verify_aggregate(&pair, &header, &forged) { "✅ 通过 —— Alice 根本没签！" } else { "❌" });
+    // Mallory 只知道 m，不知道 log_g(pk_M) = m - x_A，只能拿 m 硬签
+    let fake_pop = bls::prove_possession(&Keypair { secret: m, public: rogue });
+    println!("  检查 PoP: Mallory 交出的 PoP {}", if bls::verify_possession(rogue, &fake_pop) { "✅ (不应该发生)" } else { "❌ 无效，注册被拒绝" });
+    println!("  她不知道 pk_M 的私钥 (那需要 Alice 的私钥)，签不出自己的公钥");
+
+    // ==========================================
+    // 5. 成本
+    // ==========================================
+    println!("\n[5] 一个区块的投票带多少字节、验证者做多少次配对");
+    println!("  {:>8} | {:>12} {:>16} | {:>10} {:>10}", "验证者数", "逐个签名", "BLS 聚合+位图", "逐个验签", "聚合验签");
+    for n in [VALIDATORS, 128, 1_000, 100_000] {
+        println!(
+            "  {:>8} | {:>10} B {:>14} B | {:>6} 次配对 {:>6} 次配对",
+            n,
+            n * SCHNORR_SIGNATURE_BYTES,
+            BLS_SIGNATURE_BYTES + n.div_ceil(8),
+            2 * n,
+            2
+        );
+    }
+    println!("  聚合公钥要做 n - 1 次群乘法，但比配对便宜几个数量级；验证者集合不变时还可以缓存");
+}
+
+/*
+关键点总结：
+    1. 聚合为什么成立：
+        e 是双线性的：Π e(H(m), g)^(x_i) = e(H(m), g)^(Σ x_i) = e(H(m), Π pk_i)。
+        签名相乘 ⇔ 私钥相加 ⇔ 公钥相乘，所以聚合签名就是"聚合私钥"的普通签名。
+
+    2. 同一条消息才能这么便宜：
+        如果每人签的消息不同，验证变成 e(σ_agg, g) == Π e(H(m_i), pk_i)，仍然要 n + 1 次配对
+        (只省了带宽)。共识投票的优势在于所有人签的是同一个区块头。
+
+    3. PoP 是必需的：
+        聚合公钥只是乘法，谁都可以把自己的公钥"凑"成抵消别人的样子 (第 4 节)。
+        以太坊要求存款时提交 PoP；另一种办法是给每个公钥乘一个哈希系数 (MuSig 风格)。
+
+    4. 模拟的部分：
+        mock_pairing 靠暴力求离散对数 —— 这等于承认离散对数在玩具群里不难，所以这里的签名毫无安全性。
+        它只用来展示验证方程：真实配对在不知道指数的情况下算出 e(g^a, g^b) = e(g, g)^(ab)。
+*/
@@ -0,0 +1,379 @@
+// src/s05_zk_lab/ex41_zk_puzzle.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::commitments::hash_commit::{self, random_nonce, HashCommitment, Nonce};
+
+/*
+业务场景：不给你看答案，但让你相信我解出来了
+    "我解出了这道数独" / "这张地图能用 3 种颜色涂，相邻的国家颜色都不同"。
+    直接给出答案就泄露了答案；什么都不给，对方没理由相信。
+
+承诺-揭示 + 随机置换，重复很多轮 (Goldreich-Micali-Wigderson, 1986)：
+    1. 证明者把自己的解"换个名字" (颜色 / 数字随机置换)，对每一格单独做哈希承诺，全部发给验证者
+    2. 验证者随机挑一个局部：图里的一条边；数独里的一行 / 一列 / 一宫 / 题面给出的数字
+    3. 证明者只打开这个局部的承诺，验证者检查它是否合规
+    每一轮只看到"一条边两端颜色不同"或"一行是 1..9 的某个排列"，而置换每轮都重新选，拼不出答案。
+    不知道解的人，承诺里一定有某个局部是错的，每轮至少有 1/(局部总数) 的概率被抽中。
+
+本练习：
+    图 3-着色 (Petersen 图，15 条边) 和数独 (28 种挑战)，诚实 / 作弊各跑一遍，
+    看着"作弊者还能蒙混过关的概率"随轮数一点点降下去。
+*/
+
+// 每轮详细打印前几轮，之后每 REPORT_EVERY 轮报一次概率
+const SHOW_ROUNDS: usize = 3;
+const REPORT_EVERY: usize = 10;
+const COLORS: u8 = 3;
+const COLOR_NAMES: [&str; COLORS as usize] = ["红", "绿", "蓝"];
+
+// Petersen 图：外圈 0..5 是五边形，内圈 5..10 是五角星，i 和 i + 5 相连
+const VERTICES: usize = 10;
+const EDGES: [(usize, usize); 15] = [
+    (0, 1), (1, 2), (2, 3), (3, 4), (4, 0),
+    (5, 7), (7, 9), (9, 6), (6, 8), (8, 5),
+    (0, 5), (1, 6), (2, 7), (3, 8), (4, 9),
+];
+
+// 维基百科上的经典题目，'.' 是空格
+const PUZZLE: [&str; 9] = [
+    "53..7....", "6..195...", ".98....6.", "8...6...3", "4..8.3..1", "7...2...6", ".6....28.", "...419..5", "....8..79",
+];
+const SOLUTION: [&str; 9] = [
+    "534678912", "672195348", "198342567", "859761423", "426853791", "713924856", "961537284", "287419635", "345286179",
+];
+// 9 行 + 9 列 + 9 宫 + "题面" = 28 种挑战
+const SUDOKU_CHALLENGES: usize = 28;
+
+type Grid = [[u8; 9]; 9];
+
+// 一轮交互：Ok(这一轮验证者看到了什么) 或 Err(验证者发现的问题)
+type RoundResult = Result<String, String>;
+
+// 证明者发出的一格承诺，以及他私下留着、打开时才交出的 (值, nonce)
+struct CommittedCell {
+    commitment: HashCommitment,
+    value: u8,
+    nonce: Nonce,
+}
+
+fn commit_cell(value: u8, rng: &mut SimpleRng) -> CommittedCell {
+    let nonce = random_nonce(rng);
+    CommittedCell { commitment: hash_commit::commit(&[value], &nonce), value, nonce }
+}
+
+// 验证者打开一格：重算承诺，对得上才采信里面的值
+fn open_cell(cell: &CommittedCell) -> Result<u8, String> {
+    if hash_commit::verify(&cell.commitment, &[cell.value], &cell.nonce) {
+        Ok(cell.value)
+    } else {
+        Err(String::from("打开的值和承诺对不上"))
+    }
+}
+
+// Fisher-Yates：0..n 的一个随机排列
+fn random_permutation(n: usize, rng: &mut SimpleRng) -> Vec<u8> {
+    let mut perm: Vec<u8> = (0..n as u8).collect();
+    for i in (1..n).rev() {
+        let j = rng.gen_range(i as u64 + 1) as usize;
+        perm.swap(i, j);
+    }
+    perm
+}
+
+fn read_rounds(default: usize) -> usize {
+    read_line(&format!("  轮数 (直接回车默认 {}): ", default)).parse::<usize>().ok().filter(|&n| n > 0).unwrap_or(default)
+}
+
+// 多轮交互的公共部分：逐轮执行，打印"不知道解的人撑到现在的概率"
+// catch 是作弊者每轮至少被抓到的概率 (1 / 挑战总数)
+fn run_rounds(rounds: usize, catch: f64, catch_label: &str, rng: &mut SimpleRng, mut round: impl FnMut(&mut SimpleRng) -> RoundResult) {
+    for k in 1..=rounds {
+        match round(rng) {
+            Ok(seen) => {
+                if k <= SHOW_ROUNDS {
+                    println!("  第 {:>3} 轮: {} ✅", k, seen);
+                }
+                if k.is_multiple_of(REPORT_EVERY) || k == rounds {
+                    let survive = (1.0 - catch).powi(k as i32);
+                    println!(
+                        "  已通过 {:>3} 轮：不知道解的人撑到这里的概率 ≤ (1 - {})^{} = {:.6}，验证者的信心 {:.4}%",
+                        k,
+                        catch_label,
+                        k,
+                        survive,
+                        (1.0 - survive) * 100.0
+                    );
+                }
+            }
+            Err(e) => {
+                println!("  第 {:>3} 轮: ❌ {}", k, e);
+                println!("  验证者拒绝 —— 作弊者在第 {} 轮被抓住", k);
+                return;
+            }
+        }
+    }
+    println!("  验证者接受 ✅");
+}
+
+// ==========================================
+// 图 3-着色
+// ==========================================
+
+// 回溯找一个合法着色 (10 个顶点，瞬间出结果)
+fn find_coloring(colors: &mut [u8; VERTICES], v: usize) -> bool {
+    if v == VERTICES {
+        return true;
+    }
+    for c in 0..COLORS {
+        let clash = EDGES.iter().any(|&(a, b)| (a == v && b < v && colors[b] == c) || (b == v && a < v && colors[a] == c));
+        if !clash {
+            colors[v] = c;
+            if find_coloring(colors, v + 1) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn bad_edges(colors: &[u8; VERTICES]) -> usize {
+    EDGES.iter().filter(|&&(a, b)| colors[a] == colors[b]).count()
+}
+
+fn show_coloring(colors: &[u8; VERTICES]) -> String {
+    colors.iter().enumerate().map(|(v, &c)| format!("{}:{}", v, COLOR_NAMES[c as usize])).collect::<Vec<_>>().join(" ")
+}
+
+// 证明者：颜色随机置换后逐个顶点承诺
+fn commit_coloring(colors: &[u8; VERTICES], rng: &mut SimpleRng) -> Vec<CommittedCell> {
+    let perm = random_permutation(COLORS as usize, rng);
+    colors.iter().map(|&c| commit_cell(perm[c as usize], rng)).collect()
+}
+
+// 验证者：挑一条边，要求打开两端
+fn check_edge(committed: &[CommittedCell], edge: (usize, usize)) -> Result<(u8, u8), String> {
+    let (a, b) = (open_cell(&committed[edge.0])?, open_cell(&committed[edge.1])?);
+    if a >= COLORS || b >= COLORS {
+        return Err(format!("打开的颜色 {} / {} 不在 3 种颜色里", a, b));
+    }
+    if a == b {
+        return Err(format!("边 {}-{} 两端都是{}", edge.0, edge.1, COLOR_NAMES[a as usize]));
+    }
+    Ok((a, b))
+}
+
+fn coloring_round(colors: &[u8; VERTICES], rng: &mut SimpleRng) -> RoundResult {
+    let committed = commit_coloring(colors, rng);
+    let edge = EDGES[rng.gen_range(EDGES.len() as u64) as usize];
+    let (a, b) = check_edge(&committed, edge)?;
+    Ok(format!("承诺 {} 个顶点，挑战边 {}-{}，打开: {} / {}", VERTICES, edge.0, edge.1, COLOR_NAMES[a as usize], COLOR_NAMES[b as usize]))
+}
+
+fn run_coloring(colors: &[u8; VERTICES], rng: &mut SimpleRng) {
+    let rounds = read_rounds(3 * EDGES.len());
+    let catch = 1.0 / EDGES.len() as f64;
+    run_rounds(rounds, catch, &format!("1/{}", EDGES.len()), rng, |rng| coloring_round(colors, rng));
+}
+
+// ==========================================
+// 数独
+// ==========================================
+
+fn parse_grid(rows: &[&str; 9]) -> Grid {
+    let mut grid = [[0u8; 9]; 9];
+    for (r, row) in rows.iter().enumerate() {
+        for (c, ch) in row.chars().enumerate() {
+            grid[r][c] = ch.to_digit(10).map_or(0, |d| d as u8);
+        }
+    }
+    grid
+}
+
+// 第 i 个挑战覆盖的格子：0..9 行，9..18 列，18..27 宫，27 是题面给出的格子
+fn challenge_cells(challenge: usize, puzzle: &Grid) -> Vec<(usize, usize)> {
+    match challenge {
+        0..=8 => (0..9).map(|c| (challenge, c)).collect(),
+        9..=17 => (0..9).map(|r| (r, challenge - 9)).collect(),
+        18..=26 => {
+            let (br, bc) = ((challenge - 18) / 3 * 3, (challenge - 18) % 3 * 3);
+            (0..9).map(|i| (br + i / 3, bc + i % 3)).collect()
+        }
+        _ => (0..9).flat_map(|r| (0..9).map(move |c| (r, c))).filter(|&(r, c)| puzzle[r][c] != 0).collect(),
+    }
+}
+
+fn challenge_name(challenge: usize) -> String {
+    match challenge {
+        0..=8 => format!("第 {} 行", challenge + 1),
+        9..=17 => format!("第 {} 列", challenge - 8),
+        18..=26 => format!("第 {} 宫", challenge - 17),
+        _ => String::from("题面"),
+    }
+}
+
+// 不需要打开承诺也能算的"真相"：有多少种挑战会抓住这份填法
+fn bad_challenges(puzzle: &Grid, grid: &Grid) -> usize {
+    (0..SUDOKU_CHALLENGES)
+        .filter(|&ch| {
+            let cells = challenge_cells(ch, puzzle);
+            if ch == SUDOKU_CHALLENGES - 1 {
+                cells.iter().any(|&(r, c)| grid[r][c] != puzzle[r][c])
+            } else {
+                !is_permutation_of_digits(&cells.iter().map(|&(r, c)| grid[r][c]).collect::<Vec<u8>>())
+            }
+        })
+        .count()
+}
+
+fn is_permutation_of_digits(values: &[u8]) -> bool {
+    let mut seen = [false; 10];
+    values.len() == 9 && values.iter().all(|&v| (1..=9).contains(&v) && !std::mem::replace(&mut seen[v as usize], true))
+}
+
+// 证明者：数字 1..9 随机置换后逐格承诺
+fn commit_grid(grid: &Grid, rng: &mut SimpleRng) -> Vec<Vec<CommittedCell>> {
+    let perm = random_permutation(9, rng);
+    grid.iter().map(|row| row.iter().map(|&d| commit_cell(perm[(d - 1) as usize] + 1, rng)).collect()).collect()
+}
+
+// 验证者：行 / 列 / 宫必须是 1..9 的排列；题面的格子必须和题目"同构"
+// (同一个原数字打开成同一个新数字，不同的原数字打开成不同的新数字)
+fn check_challenge(committed: &[Vec<CommittedCell>], puzzle: &Grid, challenge: usize) -> Result<Vec<u8>, String> {
+    let cells = challenge_cells(challenge, puzzle);
+    let opened = cells.iter().map(|&(r, c)| open_cell(&committed[r][c])).collect::<Result<Vec<u8>, String>>()?;
+    if challenge == SUDOKU_CHALLENGES - 1 {
+        let mut mapping = [0u8; 10];
+        let mut used = [false; 10];
+        for (&(r, c), &v) in cells.iter().zip(&opened) {
+            let given = puzzle[r][c] as usize;
+            if mapping[given] == 0 {
+                if !(1..=9).contains(&v) || std::mem::replace(&mut used[v as usize], true) {
+                    return Err(format!("题面打开后不是一个置换 (({}, {}) 打开成 {})", r + 1, c + 1, v));
+                }
+                mapping[given] = v;
+            } else if mapping[given] != v {
+                return Err(format!("题面的 {} 一处打开成 {}，({}, {}) 却打开成 {}", given, mapping[given], r + 1, c + 1, v));
+            }
+        }
+    } else if !is_permutation_of_digits(&opened) {
+        return Err(format!("{} 打开后是 {:?}，有重复", challenge_name(challenge), opened));
+    }
+    Ok(opened)
+}
+
+fn sudoku_round(puzzle: &Grid, grid: &Grid, rng: &mut SimpleRng) -> RoundResult {
+    let committed = commit_grid(grid, rng);
+    let challenge = rng.gen_range(SUDOKU_CHALLENGES as u64) as usize;
+    let opened = check_challenge(&committed, puzzle, challenge)?;
+    let shown: String = opened.iter().take(9).map(|d| d.to_string()).collect();
+    let more = if opened.len() > 9 { format!(".. 共 {} 格", opened.len()) } else { String::new() };
+    Ok(format!("承诺 81 格，挑战{}，打开: {}{}", challenge_name(challenge), shown, more))
+}
+
+fn run_sudoku(puzzle: &Grid, grid: &Grid, rng: &mut SimpleRng) {
+    let rounds = read_rounds(100);
+    let catch = 1.0 / SUDOKU_CHALLENGES as f64;
+    run_rounds(rounds, catch, &format!("1/{}", SUDOKU_CHALLENGES), rng, |rng| sudoku_round(puzzle, grid, rng));
+}
+
+fn print_grid(grid: &Grid) {
+    for row in grid {
+        let line: String = row.iter().map(|&d| if d == 0 { '.' } else { (b'0' + d) as char }).collect();
+        println!("    {}", line);
+    }
+}
+
+pub fn run() {
+    println!("--- S05 Ex41: 零知识数独 / 图 3-着色 ---");
+    let mut rng = SimpleRng::from_time();
+
+    let mut coloring = [0u8; VERTICES];
+    assert!(find_coloring(&mut coloring, 0), "Petersen 图是 3-可着色的");
+    // 作弊者：把顶点 0 涂成和邻居 1 一样的颜色，其余照抄
+    let mut bad_coloring = coloring;
+    bad_coloring[0] = coloring[1];
+
+    let puzzle = parse_grid(&PUZZLE);
+    let solution = parse_grid(&SOLUTION);
+    // 作弊者：(1, 3) 是空格，真解是 4，他填了 5 —— 第 1 行、第 3 列、第 1 宫都出现两个 5
+    let mut bad_grid = solution;
+    bad_grid[0][2] = 5;
+
+    loop {
+        println!("\n选择场景:");
+        println!("  1. 图 3-着色：诚实的证明者");
+        println!("  2. ❌ 图 3-着色：作弊者 (有一条边两端同色)");
+        println!("  3. 数独：诚实的证明者");
+        println!("  4. ❌ 数独：作弊者 (有一格填错)");
+        println!("  5. 验证者学到了什么？");
+        println!("  0. 返回");
+        let choice = read_line("请输入: ");
+
+        match choice.as_str() {
+            "1" => {
+                println!("  Petersen 图：{} 个顶点、{} 条边；证明者手里的着色 (验证者看不到): {}", VERTICES, EDGES.len(), show_coloring(&coloring));
+                run_coloring(&coloring, &mut rng);
+            }
+            "2" => {
+                let bad = bad_edges(&bad_coloring);
+                println!("  作弊者的着色: {}", show_coloring(&bad_coloring));
+                println!("  {} / {} 条边两端同色，每轮被抓的概率 {:.1}%", bad, EDGES.len(), bad as f64 / EDGES.len() as f64 * 100.0);
+                run_coloring(&bad_coloring, &mut rng);
+            }
+            "3" => {
+                println!("  题目 ({} 个已知数字):", PUZZLE.iter().flat_map(|r| r.chars()).filter(|c| c.is_ascii_digit()).count());
+                print_grid(&puzzle);
+                println!("  证明者手里的解 {}", if bad_challenges(&puzzle, &solution) == 0 { "合法 ✅ (验证者看不到)" } else { "不合法 (不应该发生)" });
+                run_sudoku(&puzzle, &solution, &mut rng);
+            }
+            "4" => {
+                let bad = bad_challenges(&puzzle, &bad_grid);
+                println!("  作弊者的填法:");
+                print_grid(&bad_grid);
+                println!("  {} / {} 种挑战能抓住他，每轮被抓的概率 {:.1}%", bad, SUDOKU_CHALLENGES, bad as f64 / SUDOKU_CHALLENGES as f64 * 100.0);
+                run_sudoku(&puzzle, &bad_grid, &mut rng);
+            }
+            "5" => {
+                // 固定挑战同一条边 0-1，统计打开的颜色对：如果均匀分布，它就不带任何关于真实着色的信息
+                let trials = 6000;
+                let mut counts = [[0u32; COLORS as usize]; COLORS as usize];
+                for _ in 0..trials {
+                    let committed = commit_coloring(&coloring, &mut rng);
+                    let (a, b) = check_edge(&committed, (0, 1)).expect("honest coloring opens correctly");
+                    counts[a as usize][b as usize] += 1;
+                }
+                println!("  诚实证明者跑 {} 轮，每轮都挑战边 0-1，打开的颜色对:", trials);
+                for a in 0..COLORS as usize {
+                    for b in (0..COLORS as usize).filter(|&b| b != a) {
+                        println!("    ({}, {}): {:>5} 次", COLOR_NAMES[a], COLOR_NAMES[b], counts[a][b]);
+                    }
+                }
+                println!("  6 种不同色的组合各约 {} 次：验证者只知道\"两端不同色\"，自己掷骰子也能造出同样分布的记录", trials / 6);
+                println!("  数独同理：打开的一行是 1..9 的随机排列，每轮的置换都是新的，跨轮拼不出原来的数字");
+            }
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 完备性 / 可靠性 / 零知识：
+        诚实的证明者每轮都能通过。
+        不知道解的人，承诺里至少有一个局部不合规，每轮至少 1/k 的概率被抽中 (k = 边数或 28)，
+        n 轮后还没被抓的概率 ≤ (1 - 1/k)^n —— 轮数要到 k 的几倍，信心才够高。
+        验证者看到的只是一对随机的不同颜色 / 1..9 的随机排列，这些记录自己就能模拟出来 (场景 5)。
+
+    2. 每轮重新置换是零知识的关键：
+        如果颜色不换，验证者问遍所有边就拼出了整个着色；换了之后，不同轮次打开的颜色之间毫无关系。
+
+    3. 承诺必须先发：
+        证明者先锁定全部格子再看挑战；如果能看了挑战再决定填什么，他每次都能只在被问到的地方"凑"对。
+
+    4. 图 3-着色是 NP 完全的：
+        任何 NP 命题都能归约成 3-着色，所以这个看似玩具的协议说明了"所有 NP 命题都有零知识证明"。
+        实际系统不会这么用 (轮数太多、归约太大)，但 ex10 的 Sigma 协议、ex36 的 PLONK 都是同一个精神：
+        承诺 -> 随机挑战 -> 只打开一小部分。
+*/
@@ -0,0 +1,164 @@
+// src/s05_zk_lab/gkr.rs
+use std::fmt;
+
+use super::math::field::Fp;
+use super::math::multilinear::MultilinearPoly;
+use super::math::poly::Polynomial;
+use super::sumcheck::SumcheckProver;
+
+/*
+GKR 协议 (Goldwasser-Kalai-Rothblum, 2008) 用到的分层电路
+
+    分层算术电路：第 0 层是输出，最后一层是输入。第 i 层的每个门只从第 i+1 层取两个输入。
+    每层门的个数是 2 的幂，第 i 层的门用 k_i 个比特编号，这一层的取值表 V_i 就是一个 k_i 元多线性多项式。
+
+连线谓词 (wiring predicate)：
+    add_i(z, x, y) = 1  ⇔  第 i 层的 z 号门是加法门，输入是第 i+1 层的 x 号和 y 号
+    mul_i(z, x, y) 同理。于是对任意布尔的 z：
+        V_i(z) = Σ_{x, y ∈ {0,1}^k} add_i(z, x, y) * (V_{i+1}(x) + V_{i+1}(y)) + mul_i(z, x, y) * V_{i+1}(x) * V_{i+1}(y)
+    两边都是 z 的多线性函数，所以把 z 换成随机点 r 也成立 —— 这就是一次 sum-check 的输入。
+
+    把 z 固定为 r 之后，求和项是 (x, y) 上两个"多线性 × 多线性"之和：
+        add~(r, x, y) * [V(x) + V(y)]   +   mul~(r, x, y) * [V(x) * V(y)]
+    V(x) + V(y) 和 V(x) * V(y) 在 (x, y) 的 2k 个变量上仍然是多线性的 (x 和 y 是不同的变量)，
+    所以 sumcheck.rs 的 SumcheckProver::sum_of_products 可以直接复用。
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GateKind {
+    Add,
+    Mul,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayerGate {
+    pub kind: GateKind,
+    pub left: usize,
+    pub right: usize,
+}
+
+impl LayerGate {
+    pub fn add(left: usize, right: usize) -> Self {
+        LayerGate { kind: GateKind::Add, left, right }
+    }
+
+    pub fn mul(left: usize, right: usize) -> Self {
+        LayerGate { kind: GateKind::Mul, left, right }
+    }
+}
+
+impl fmt::Display for LayerGate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = match self.kind {
+            GateKind::Add => "+",
+            GateKind::Mul => "*",
+        };
+        write!(f, "{} {} {}", self.left, op, self.right)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LayeredCircuit {
+    // layers[0] 是输出层；layers[i] 的门从第 i+1 层取输入，最后一层的门直接读输入
+    layers: Vec<Vec<LayerGate>>,
+    num_inputs: usize,
+}
+
+impl LayeredCircuit {
+    pub fn new(num_inputs: usize, layers: Vec<Vec<LayerGate>>) -> Result<Self, String> {
+        if layers.is_empty() {
+            return Err(String::from("电路至少要有一层门"));
+        }
+        let sizes: Vec<usize> = layers.iter().map(Vec::len).chain([num_inputs]).collect();
+        if let Some(&bad) = sizes.iter().find(|n| !n.is_power_of_two()) {
+            return Err(format!("每层的大小必须是 2 的幂，实际有一层是 {}", bad));
+        }
+        for (i, layer) in layers.iter().enumerate() {
+            let below = sizes[i + 1];
+            if let Some(gate) = layer.iter().find(|g| g.left >= below || g.right >= below) {
+                return Err(format!("第 {} 层的门 {} 引用了不存在的下一层导线 (下一层只有 {} 个)", i, gate, below));
+            }
+        }
+        Ok(LayeredCircuit { layers, num_inputs })
+    }
+
+    // 门所在的层数；第 depth() 层是输入
+    pub fn depth(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn layer(&self, i: usize) -> &[LayerGate] {
+        &self.layers[i]
+    }
+
+    // 第 i 层的编号需要几个比特 (i == depth() 时是输入层)
+    pub fn layer_vars(&self, i: usize) -> usize {
+        self.layers.get(i).map_or(self.num_inputs, Vec::len).trailing_zeros() as usize
+    }
+
+    // 从输入往上逐层求值，返回 values[0] = 输出 ... values[depth] = 输入
+    pub fn evaluate<const P: u64>(&self, inputs: &[Fp<P>]) -> Vec<Vec<Fp<P>>> {
+        assert_eq!(inputs.len(), self.num_inputs, "input count mismatch");
+        let mut values = vec![inputs.to_vec()];
+        for layer in self.layers.iter().rev() {
+            let below = &values[values.len() - 1];
+            let current = layer
+                .iter()
+                .map(|g| match g.kind {
+                    GateKind::Add => below[g.left] + below[g.right],
+                    GateKind::Mul => below[g.left] * below[g.right],
+                })
+                .collect();
+            values.push(current);
+        }
+        values.reverse();
+        values
+    }
+
+    // add_i 或 mul_i 的取值表，变量顺序 (z, x, y)，z 在最高位
+    // 按 z 逐个 fix_first_variable 就得到 (x, y) 上的表
+    pub fn wiring<const P: u64>(&self, i: usize, kind: GateKind) -> MultilinearPoly<P> {
+        let (kz, k) = (self.layer_vars(i), self.layer_vars(i + 1));
+        let mut evals = vec![Fp::zero(); 1 << (kz + 2 * k)];
+        for (z, gate) in self.layers[i].iter().enumerate().filter(|(_, g)| g.kind == kind) {
+            evals[(z << (2 * k)) | (gate.left << k) | gate.right] = Fp::one();
+        }
+        MultilinearPoly::new(evals)
+    }
+}
+
+// 第 i 层的 sum-check：证明者要证明 V_i(r) = Σ_{x,y} add~(r,x,y)(V(x)+V(y)) + mul~(r,x,y) V(x)V(y)
+// below 是第 i+1 层的取值
+pub fn layer_prover<const P: u64>(circuit: &LayeredCircuit, i: usize, r: &[Fp<P>], below: &[Fp<P>]) -> SumcheckProver<P> {
+    let fix = |table: MultilinearPoly<P>| r.iter().fold(table, |t, &ri| t.fix_first_variable(ri));
+    let add = fix(circuit.wiring(i, GateKind::Add));
+    let mul = fix(circuit.wiring(i, GateKind::Mul));
+    let sums = below.iter().flat_map(|&vx| below.iter().map(move |&vy| vx + vy)).collect();
+    let products = below.iter().flat_map(|&vx| below.iter().map(move |&vy| vx * vy)).collect();
+    SumcheckProver::sum_of_products(vec![(add, MultilinearPoly::new(sums)), (mul, MultilinearPoly::new(products))])
+}
+
+// 验证者在 sum-check 结束时自己算连线谓词 (电路是公开的)，再用证明者给的 V(rx)、V(ry) 拼出 g 的值
+pub fn layer_final_value<const P: u64>(circuit: &LayeredCircuit, i: usize, r: &[Fp<P>], rx: &[Fp<P>], ry: &[Fp<P>], vx: Fp<P>, vy: Fp<P>) -> Fp<P> {
+    let point: Vec<Fp<P>> = [r, rx, ry].concat();
+    let add = circuit.wiring(i, GateKind::Add).evaluate(&point);
+    let mul = circuit.wiring(i, GateKind::Mul).evaluate(&point);
+    add * (vx + vy) + mul * vx * vy
+}
+
+// 两个点 a、b 连成的直线 ℓ(t) = a + t * (b - a)
+pub fn line_point<const P: u64>(a: &[Fp<P>], b: &[Fp<P>], t: Fp<P>) -> Vec<Fp<P>> {
+    a.iter().zip(b).map(|(&ai, &bi)| ai + t * (bi - ai)).collect()
+}
+
+// q(t) = V(ℓ(t))：多线性函数限制在直线上，次数 <= k，在 t = 0..=k 处求值再插值
+// q(0) = V(a)，q(1) = V(b)；把两个声明合并成一个：随机 t*，新点 ℓ(t*)，新声明值 q(t*)
+pub fn restrict_to_line<const P: u64>(v: &MultilinearPoly<P>, a: &[Fp<P>], b: &[Fp<P>]) -> Polynomial<P> {
+    let points: Vec<(Fp<P>, Fp<P>)> = (0..=a.len() as u64)
+        .map(|t| {
+            let t = Fp::new(t);
+            (t, v.evaluate(&line_point(a, b, t)))
+        })
+        .collect();
+    Polynomial::interpolate(&points).expect("0..=k are distinct")
+}
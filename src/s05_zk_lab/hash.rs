@@ -0,0 +1,126 @@
+// src/s05_zk_lab/hash.rs
+// S05 共用的哈希工具箱：教学用的 mock_hash + 真正的 SHA-256
+
+use sha2::{Digest as _, Sha256};
+
+// 32 字节摘要 (SHA-256 输出)
+// 用定长数组而不是 String：栈上分配、可以 Copy、比较时就是 32 字节逐个比
+pub type Digest = [u8; 32];
+
+// 引入一个简易的哈希模拟函数（在真实项目中我们会用 sha2/keccak）
+// 这里为了不引入外部 crate，我们用标准库模拟一个 "Hash"
+pub fn mock_hash(input: &str) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
+// 真正的 SHA-256：任意字节 -> 32 字节摘要
+pub fn sha256(data: &[u8]) -> Digest {
+    Sha256::digest(data).into()
+}
+
+// 比特币的 "double SHA-256"：SHA256(SHA256(data))，txid、区块哈希、Merkle 节点都用它
+pub fn sha256d(data: &[u8]) -> Digest {
+    sha256(&sha256(data))
+}
+
+// 父节点哈希：H(left || right)
+// 用一个 64 字节的栈数组拼接，避免为每次拼接分配堆内存 (对比 mock_hash 里的 format!)
+pub fn hash_pair(left: &Digest, right: &Digest) -> Digest {
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(left);
+    buf[32..].copy_from_slice(right);
+    sha256(&buf)
+}
+
+// 打印用：只显示前 8 个十六进制字符，终端里看得清
+pub fn short_hex(digest: &Digest) -> String {
+    hex::encode(&digest[..4])
+}
+
+// mock_hash 的字节版本，给 ex01 的 Hasher trait 用
+// 结果只有 64 位，放在 32 字节摘要的前 8 字节，其余补 0
+pub fn mock_digest(data: &[u8]) -> Digest {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+    let mut hasher = DefaultHasher::new();
+    hasher.write(data);
+    let mut digest = [0u8; 32];
+    digest[..8].copy_from_slice(&hasher.finish().to_be_bytes());
+    digest
+}
+
+// ==========================================
+// Keccak-256 (以太坊的哈希)
+// ==========================================
+// sha2 crate 里没有 Keccak，实验室不为它再加依赖，照着规范手写 Keccak-f[1600] 置换
+// 注意：以太坊的 keccak256 和 NIST 标准化后的 SHA3-256 只差一个填充字节 (0x01 vs 0x06)，输出完全不同
+const KECCAK_ROUND_CONSTANTS: [u64; 24] = [
+    0x0000_0000_0000_0001, 0x0000_0000_0000_8082, 0x8000_0000_0000_808A, 0x8000_0000_8000_8000,
+    0x0000_0000_0000_808B, 0x0000_0000_8000_0001, 0x8000_0000_8000_8081, 0x8000_0000_0000_8009,
+    0x0000_0000_0000_008A, 0x0000_0000_0000_0088, 0x0000_0000_8000_8009, 0x0000_0000_8000_000A,
+    0x0000_0000_8000_808B, 0x8000_0000_0000_008B, 0x8000_0000_0000_8089, 0x8000_0000_0000_8003,
+    0x8000_0000_0000_8002, 0x8000_0000_0000_0080, 0x0000_0000_0000_800A, 0x8000_0000_8000_000A,
+    0x8000_0000_8000_8081, 0x8000_0000_0000_8080, 0x0000_0000_8000_0001, 0x8000_0000_8000_8008,
+];
+// ρ 步骤的循环移位量，按 π 步骤访问 lane 的顺序排列
+const KECCAK_RHO: [u32; 24] = [1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44];
+const KECCAK_PI: [usize; 24] = [10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1];
+// 1600 位状态里每个块吸收 1088 位，剩下的 512 位是 capacity (安全性 256 位)
+const KECCAK_RATE: usize = 136;
+
+// 状态是 5 x 5 个 64 位 lane，下标 x + 5 * y
+fn keccak_f(state: &mut [u64; 25]) {
+    for rc in KECCAK_ROUND_CONSTANTS {
+        // θ：每个 lane 异或上相邻两列的列校验和
+        let mut columns = [0u64; 5];
+        for (i, lane) in state.iter().enumerate() {
+            columns[i % 5] ^= lane;
+        }
+        for (i, lane) in state.iter_mut().enumerate() {
+            let x = i % 5;
+            *lane ^= columns[(x + 4) % 5] ^ columns[(x + 1) % 5].rotate_left(1);
+        }
+        // ρ + π：lane 换位置，同时各自循环移位
+        let mut carried = state[1];
+        for (&target, &rotation) in KECCAK_PI.iter().zip(&KECCAK_RHO) {
+            let next = state[target];
+            state[target] = carried.rotate_left(rotation);
+            carried = next;
+        }
+        // χ：唯一的非线性步骤，按行做 a ^ (!b & c)
+        for row in state.chunks_exact_mut(5) {
+            let copy = [row[0], row[1], row[2], row[3], row[4]];
+            for (x, lane) in row.iter_mut().enumerate() {
+                *lane = copy[x] ^ (!copy[(x + 1) % 5] & copy[(x + 2) % 5]);
+            }
+        }
+        // ι：打破各轮之间的对称性
+        state[0] ^= rc;
+    }
+}
+
+pub fn keccak256(data: &[u8]) -> Digest {
+    // 填充：0x01 ... 0x80 (只差一个字节时合成 0x81)
+    let mut padded = data.to_vec();
+    padded.push(0x01);
+    padded.resize(padded.len().div_ceil(KECCAK_RATE) * KECCAK_RATE, 0);
+    let last = padded.len() - 1;
+    padded[last] |= 0x80;
+
+    let mut state = [0u64; 25];
+    for block in padded.chunks_exact(KECCAK_RATE) {
+        for (lane, bytes) in state.iter_mut().zip(block.chunks_exact(8)) {
+            *lane ^= u64::from_le_bytes(bytes.try_into().expect("8-byte chunk"));
+        }
+        keccak_f(&mut state);
+    }
+    let mut digest = [0u8; 32];
+    for (out, lane) in digest.chunks_exact_mut(8).zip(&state) {
+        out.copy_from_slice(&lane.to_le_bytes());
+    }
+    digest
+}
@@ -0,0 +1,158 @@
+// src/s05_zk_lab/ipa.rs
+use super::hash::sha256;
+use super::math::group::{GroupElement, Scalar};
+
+/*
+内积论证 (Inner-Product Argument, Bulletproofs 的核心, Bootle et al. 2016 / Bünz et al. 2018)
+
+陈述：我知道两个长度为 n 的向量 a、b，使得
+    P = g^a * h^b * u^<a, b>
+    (g^a 是 Π g_i^(a_i) 的简写，g、h 是两组谁都不知道彼此离散对数的生成元)
+直接把 a、b 发过去要 2n 个标量；内积论证只发 2 log2(n) 个群元素 + 2 个标量。
+
+每一轮把长度减半 ("折叠")：
+    把向量切成左右两半 a = (a_lo, a_hi)，b、g、h 同理
+    P 发送交叉项：
+        L = g_hi^(a_lo) * h_lo^(b_hi) * u^<a_lo, b_hi>
+        R = g_lo^(a_hi) * h_hi^(b_lo) * u^<a_hi, b_lo>
+    V 给挑战 x (这里用 Fiat-Shamir：x = H(P, L, R))，双方各自折叠：
+        g' = g_lo^(1/x) ∘ g_hi^x        h' = h_lo^x ∘ h_hi^(1/x)
+        P' = L^(x^2) * P * R^(1/x^2)
+    只有证明者折叠见证：
+        a' = a_lo * x + a_hi / x        b' = b_lo / x + b_hi * x
+    新的 (g', h', P', a', b') 满足同样形式的关系，长度减半。
+log2(n) 轮之后只剩一个数：证明者发 a、b，验证者检查 P == g^a * h^b * u^(a*b)。
+*/
+
+pub struct IpaGenerators {
+    pub g: Vec<GroupElement>,
+    pub h: Vec<GroupElement>,
+    pub u: GroupElement,
+}
+
+impl IpaGenerators {
+    // 所有生成元都由公开字符串哈希得到，没人知道它们之间的离散对数
+    pub fn setup(n: usize) -> Self {
+        assert!(n.is_power_of_two(), "vector length must be a power of two");
+        IpaGenerators {
+            g: (0..n).map(|i| GroupElement::hash_to_group(&format!("rust-zk-lab/ipa/g/{}", i))).collect(),
+            h: (0..n).map(|i| GroupElement::hash_to_group(&format!("rust-zk-lab/ipa/h/{}", i))).collect(),
+            u: GroupElement::hash_to_group("rust-zk-lab/ipa/u"),
+        }
+    }
+
+    // P = g^a * h^b * u^<a, b>
+    pub fn commit(&self, a: &[Scalar], b: &[Scalar]) -> GroupElement {
+        multi_exp(&self.g, a) * multi_exp(&self.h, b) * self.u.pow(inner_product(a, b))
+    }
+}
+
+pub fn inner_product(a: &[Scalar], b: &[Scalar]) -> Scalar {
+    a.iter().zip(b).fold(Scalar::zero(), |acc, (&x, &y)| acc + x * y)
+}
+
+// Π bases_i^(exps_i)
+pub fn multi_exp(bases: &[GroupElement], exps: &[Scalar]) -> GroupElement {
+    bases.iter().zip(exps).fold(GroupElement::identity(), |acc, (&base, &e)| acc * base.pow(e))
+}
+
+// Fiat-Shamir：把若干个公开值哈希成一个非零挑战 (挑战要求逆，0 不能用)
+pub fn challenge(parts: &[u64]) -> Scalar {
+    let data: Vec<u8> = parts.iter().flat_map(|x| x.to_be_bytes()).collect();
+    let digest = sha256(&data);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    let x = Scalar::new(u64::from_be_bytes(bytes));
+    if x.is_zero() {
+        Scalar::one()
+    } else {
+        x
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IpaProof {
+    pub l: Vec<GroupElement>,
+    pub r: Vec<GroupElement>,
+    pub a: Scalar,
+    pub b: Scalar,
+}
+
+impl IpaProof {
+    // (群元素个数, 标量个数)
+    pub fn size(&self) -> (usize, usize) {
+        (self.l.len() + self.r.len(), 2)
+    }
+}
+
+fn show(v: &[Scalar]) -> String {
+    let shown: Vec<String> = v.iter().map(|x| x.to_string()).collect();
+    format!("[{}]", shown.join(", "))
+}
+
+// g ∘ h 式的折叠：lo 部分乘 e_lo 次方，hi 部分乘 e_hi 次方
+fn fold_bases(bases: &[GroupElement], e_lo: Scalar, e_hi: Scalar) -> Vec<GroupElement> {
+    let (lo, hi) = bases.split_at(bases.len() / 2);
+    lo.iter().zip(hi).map(|(&l, &h)| l.pow(e_lo) * h.pow(e_hi)).collect()
+}
+
+fn fold_scalars(v: &[Scalar], e_lo: Scalar, e_hi: Scalar) -> Vec<Scalar> {
+    let (lo, hi) = v.split_at(v.len() / 2);
+    lo.iter().zip(hi).map(|(&l, &h)| l * e_lo + h * e_hi).collect()
+}
+
+// verbose 时逐轮打印向量的折叠过程
+pub fn prove(g: &[GroupElement], h: &[GroupElement], u: GroupElement, p: GroupElement, a: &[Scalar], b: &[Scalar], verbose: bool) -> IpaProof {
+    assert!(a.len().is_power_of_two() && [g.len(), h.len(), b.len()].iter().all(|&n| n == a.len()), "lengths must match and be a power of two");
+    let (mut g, mut h, mut p) = (g.to_vec(), h.to_vec(), p);
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    let (mut ls, mut rs) = (Vec::new(), Vec::new());
+    while a.len() > 1 {
+        let half = a.len() / 2;
+        let (a_lo, a_hi) = a.split_at(half);
+        let (b_lo, b_hi) = b.split_at(half);
+        let (c_l, c_r) = (inner_product(a_lo, b_hi), inner_product(a_hi, b_lo));
+        let l = multi_exp(&g[half..], a_lo) * multi_exp(&h[..half], b_hi) * u.pow(c_l);
+        let r = multi_exp(&g[..half], a_hi) * multi_exp(&h[half..], b_lo) * u.pow(c_r);
+        let x = challenge(&[p.value(), l.value(), r.value()]);
+        let x_inv = Scalar::one() / x;
+        if verbose {
+            println!("  第 {} 轮: n = {} -> {}", ls.len() + 1, a.len(), half);
+            println!("    a = {}  b = {}", show(&a), show(&b));
+            println!("    c_L = <a_lo, b_hi> = {}   c_R = <a_hi, b_lo> = {}", c_l, c_r);
+            println!("    L = {}   R = {}   挑战 x = H(P, L, R) = {}", l, r, x);
+        }
+        g = fold_bases(&g, x_inv, x);
+        h = fold_bases(&h, x, x_inv);
+        p = l.pow(x * x) * p * r.pow(x_inv * x_inv);
+        a = fold_scalars(&a, x, x_inv);
+        b = fold_scalars(&b, x_inv, x);
+        ls.push(l);
+        rs.push(r);
+    }
+    if verbose {
+        println!("  最后: a = {}  b = {}  (证明者直接发出这两个数)", a[0], b[0]);
+    }
+    IpaProof { l: ls, r: rs, a: a[0], b: b[0] }
+}
+
+// 验证者重放同样的折叠 (只折叠生成元和 P)，最后做一次检查
+pub fn verify(g: &[GroupElement], h: &[GroupElement], u: GroupElement, p: GroupElement, proof: &IpaProof) -> Result<(), String> {
+    let n = g.len();
+    if h.len() != n || proof.l.len() != proof.r.len() || 1usize.checked_shl(proof.l.len() as u32) != Some(n) {
+        return Err(format!("证明有 {} 轮，和向量长度 {} 不匹配", proof.l.len(), n));
+    }
+    let (mut g, mut h, mut p) = (g.to_vec(), h.to_vec(), p);
+    for (&l, &r) in proof.l.iter().zip(&proof.r) {
+        let x = challenge(&[p.value(), l.value(), r.value()]);
+        let x_inv = Scalar::one() / x;
+        g = fold_bases(&g, x_inv, x);
+        h = fold_bases(&h, x, x_inv);
+        p = l.pow(x * x) * p * r.pow(x_inv * x_inv);
+    }
+    if p == g[0].pow(proof.a) * h[0].pow(proof.b) * u.pow(proof.a * proof.b) {
+        Ok(())
+    } else {
+        Err(String::from("折叠到底后 P != g^a * h^b * u^(a*b)"))
+    }
+}
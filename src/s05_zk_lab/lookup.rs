@@ -0,0 +1,60 @@
+// src/s05_zk_lab/lookup.rs
+use std::collections::HashMap;
+
+use super::math::field::Fp;
+
+/*
+查找论证 (lookup argument) 的简化版：证明见证里的每个值 f_i 都出现在公开表 T 里
+
+思路 (多重集等式，和 logUp / cq 的出发点相同)：
+    证明者对每个表项 t_j 给出"被查了几次" m_j。如果每个 f_i 都在表里，那么
+        多重集 {f_1, ..., f_n}  ==  把 t_j 重复 m_j 次得到的多重集
+    两个多重集相等 ⇔ 两个多项式相等：
+        Π_i (X - f_i)  ==  Π_j (X - t_j)^(m_j)
+    验证者不逐项比较，而是在随机点 γ 上各算一次 (Schwartz-Zippel)：
+        左右两边次数都是 n，不相等时在随机 γ 上恰好相等的概率 <= n / |F|。
+
+只要有一个 f_i 不在表里，右边无论 m 怎么取都凑不出因子 (X - f_i)，两个多项式必然不同。
+
+⚠️ 玩具化：这里验证者直接拿着见证算左边。真实系统里两边的乘积都藏在承诺后面，
+    用 circuit/plonk.rs 那样的累乘多项式 Z 证明，验证者看不到 f_i。
+*/
+
+// 每个表项被查了几次。见证里有不在表里的值时返回它在见证里的下标
+pub fn multiplicities<const P: u64>(table: &[Fp<P>], witness: &[Fp<P>]) -> Result<Vec<u64>, usize> {
+    let mut index = HashMap::new();
+    for (j, t) in table.iter().enumerate() {
+        index.entry(*t).or_insert(j);
+    }
+    let mut counts = vec![0; table.len()];
+    for (i, f) in witness.iter().enumerate() {
+        counts[*index.get(f).ok_or(i)?] += 1;
+    }
+    Ok(counts)
+}
+
+// 左边：Π (γ - f_i)
+pub fn witness_product<const P: u64>(witness: &[Fp<P>], gamma: Fp<P>) -> Fp<P> {
+    witness.iter().fold(Fp::one(), |acc, &f| acc * (gamma - f))
+}
+
+// 右边：Π (γ - t_j)^(m_j)
+pub fn table_product<const P: u64>(table: &[Fp<P>], counts: &[u64], gamma: Fp<P>) -> Fp<P> {
+    table.iter().zip(counts).fold(Fp::one(), |acc, (&t, &m)| acc * (gamma - t).pow(m))
+}
+
+pub fn verify<const P: u64>(table: &[Fp<P>], witness: &[Fp<P>], counts: &[u64], gamma: Fp<P>) -> Result<(), String> {
+    if counts.len() != table.len() {
+        return Err(format!("给了 {} 个重数，表有 {} 项", counts.len(), table.len()));
+    }
+    let total: u64 = counts.iter().sum();
+    if total != witness.len() as u64 {
+        return Err(format!("重数之和 {} ≠ 见证个数 {}", total, witness.len()));
+    }
+    let (lhs, rhs) = (witness_product(witness, gamma), table_product(table, counts, gamma));
+    if lhs == rhs {
+        Ok(())
+    } else {
+        Err(format!("Π (γ - f_i) = {} ≠ Π (γ - t_j)^m_j = {}", lhs, rhs))
+    }
+}
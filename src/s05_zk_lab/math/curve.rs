@@ -0,0 +1,253 @@
+// src/s05_zk_lab/math/curve.rs
+use std::fmt;
+use std::ops::{Add, Neg};
+
+use crate::s05_zk_lab::hash::sha256;
+
+use super::field::Fp;
+use super::group::{Group, Scalar, GROUP_ORDER};
+
+/*
+玩具椭圆曲线：短 Weierstrass 形式  y^2 = x^3 + a x + b  (over F_p)
+
+    p = 971, a = 3, b = 10
+    这条曲线上恰好有 1019 个点 (含无穷远点 O) —— 和 group.rs 的 q 相同，而且是素数。
+    于是：
+        1. 除 O 以外每个点都是生成元
+        2. 标量依然活在 F_1019 里，group.rs 的 Scalar 可以原样复用
+    (这组参数是穷举小素数和小系数、挑出点数恰好为 1019 的曲线得到的)
+
+群运算 "弦切法" (Chord-and-tangent)：
+    P + Q：过 P、Q 作直线，交曲线于第三点 R，P + Q = -R (关于 x 轴翻转)
+        λ = (y2 - y1) / (x2 - x1)
+        x3 = λ^2 - x1 - x2,  y3 = λ (x1 - x3) - y1
+    P + P：用切线代替割线，λ = (3 x1^2 + a) / (2 y1)
+    单位元：无穷远点 O；逆元：-(x, y) = (x, -y)
+
+与 group.rs 的 mod p 乘法群相比：
+    同样安全强度下，椭圆曲线的元素小得多 (256 bit 对 3072 bit)，因为曲线上没有"指数演算"这类亚指数攻击。
+*/
+
+pub const FIELD_MODULUS: u64 = 971;
+pub const CURVE_A: u64 = 3;
+pub const CURVE_B: u64 = 10;
+
+pub type Fq = Fp<FIELD_MODULUS>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CurvePoint {
+    Infinity,
+    Affine { x: Fq, y: Fq },
+}
+
+// y^2 右边的 x^3 + a x + b
+fn rhs(x: Fq) -> Fq {
+    x * x * x + Fq::new(CURVE_A) * x + Fq::new(CURVE_B)
+}
+
+// p ≡ 3 (mod 4) 时平方根有公式：sqrt(v) = v^((p + 1) / 4)，算完要回代检查 v 是不是二次剩余
+fn sqrt(v: Fq) -> Option<Fq> {
+    let root = v.pow(FIELD_MODULUS.div_ceil(4));
+    (root * root == v).then_some(root)
+}
+
+impl CurvePoint {
+    pub fn is_on_curve(&self) -> bool {
+        match *self {
+            CurvePoint::Infinity => true,
+            CurvePoint::Affine { x, y } => y * y == rhs(x),
+        }
+    }
+
+    // 给定 x，取两个 y 里数值较小的那个 (没有对应的点就返回 None)
+    pub fn lift_x(x: Fq) -> Option<Self> {
+        sqrt(rhs(x)).map(|y| {
+            let y = if y.value() <= (-y).value() { y } else { -y };
+            CurvePoint::Affine { x, y }
+        })
+    }
+
+    // encode 的逆：从压缩编码恢复点，编码不合法 (x 越界、x 不在曲线上) 返回 None
+    pub fn decode(encoded: u64) -> Option<Self> {
+        let Some(v) = encoded.checked_sub(1) else {
+            return Some(CurvePoint::Infinity);
+        };
+        let (x, parity) = (v / 2, v & 1);
+        if x >= FIELD_MODULUS {
+            return None;
+        }
+        let point = Self::lift_x(Fq::new(x))?;
+        match point {
+            CurvePoint::Affine { y, .. } if y.value() & 1 != parity => Some(-point),
+            _ => Some(point),
+        }
+    }
+
+    // 暴力枚举曲线上所有的点 (p 很小才做得到)
+    pub fn all_points() -> Vec<Self> {
+        let mut points = vec![CurvePoint::Infinity];
+        for x in 0..FIELD_MODULUS {
+            if let Some(CurvePoint::Affine { x, y }) = Self::lift_x(Fq::new(x)) {
+                points.push(CurvePoint::Affine { x, y });
+                if !y.is_zero() {
+                    points.push(CurvePoint::Affine { x, y: -y });
+                }
+            }
+        }
+        points
+    }
+
+    pub fn double(self) -> Self {
+        match self {
+            CurvePoint::Infinity => CurvePoint::Infinity,
+            // 切线垂直：2P = O
+            CurvePoint::Affine { y, .. } if y.is_zero() => CurvePoint::Infinity,
+            CurvePoint::Affine { x, y } => {
+                let lambda = (Fq::new(3) * x * x + Fq::new(CURVE_A)) / (Fq::new(2) * y);
+                let x3 = lambda * lambda - x - x;
+                CurvePoint::Affine { x: x3, y: lambda * (x - x3) - y }
+            }
+        }
+    }
+
+    // 最朴素的标量乘：k 次点加。k 最多 1018，玩具曲线上还跑得动
+    pub fn mul_naive(self, k: Scalar) -> Self {
+        (0..k.value()).fold(CurvePoint::Infinity, |acc, _| acc + self)
+    }
+
+    // Double-and-add：从高位到低位扫 k，每一位先倍点，位是 1 再加一次 P —— O(log k) 次运算
+    // ❌ 运算次数 = 位长 + 汉明重量，耗时随私钥变化 (计时侧信道)
+    pub fn mul_double_and_add(self, k: Scalar) -> Self {
+        let k = k.value();
+        let mut acc = CurvePoint::Infinity;
+        for i in (0..u64::BITS - k.leading_zeros()).rev() {
+            acc = acc.double();
+            if (k >> i) & 1 == 1 {
+                acc = acc + self;
+            }
+        }
+        acc
+    }
+
+    // Montgomery ladder：始终保持 R1 = R0 + P，每一位都恰好做一次点加 + 一次倍点
+    // ✅ 运算序列与 k 的取值无关
+    pub fn mul_ladder(self, k: Scalar) -> Self {
+        // 固定位长：k' = k + q (不够长就 k + 2q)，k' * P = k * P，但 k' 的最高位恒在第 LADDER_BITS 位
+        // 否则 k 的前导 0 会让 R0 停在 O 上，O 参与的运算是"免费"的 —— 又把位长泄露出去了
+        let mut k = k.value() + GROUP_ORDER;
+        if k < 1 << (LADDER_BITS - 1) {
+            k += GROUP_ORDER;
+        }
+        let (mut r0, mut r1) = (self, self.double());
+        for i in (0..LADDER_BITS - 1).rev() {
+            let bit = (k >> i) & 1 == 1;
+            // bit = 0: R1 = R0 + R1, R0 = 2 R0
+            // bit = 1: R0 = R0 + R1, R1 = 2 R1   (交换一下就变成 bit = 0 的情形)
+            cswap(&mut r0, &mut r1, bit);
+            r1 = r0 + r1;
+            r0 = r0.double();
+            cswap(&mut r0, &mut r1, bit);
+        }
+        r0
+    }
+}
+
+// 2q < 2^11：加上 q 或 2q 之后的标量都恰好是 11 位
+const LADDER_BITS: u32 = 11;
+
+// 条件交换。⚠️ 这里的 if 本身就是一个分支：真实实现 (如 subtle crate) 用掩码做无分支的交换，
+// 并且点运算本身也要写成常数时间 —— 这里的仿射坐标加法遇到 O 会走捷径，做不到这一点
+fn cswap(a: &mut CurvePoint, b: &mut CurvePoint, swap: bool) {
+    if swap {
+        std::mem::swap(a, b);
+    }
+}
+
+impl Add for CurvePoint {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (CurvePoint::Infinity, p) | (p, CurvePoint::Infinity) => p,
+            (CurvePoint::Affine { x: x1, y: y1 }, CurvePoint::Affine { x: x2, y: y2 }) => {
+                if x1 == x2 {
+                    // 同一个 x：要么是同一个点 (切线)，要么互为逆元 (竖直线，交于 O)
+                    return if y1 == y2 { self.double() } else { CurvePoint::Infinity };
+                }
+                let lambda = (y2 - y1) / (x2 - x1);
+                let x3 = lambda * lambda - x1 - x2;
+                CurvePoint::Affine { x: x3, y: lambda * (x1 - x3) - y1 }
+            }
+        }
+    }
+}
+
+impl Neg for CurvePoint {
+    type Output = Self;
+    fn neg(self) -> Self {
+        match self {
+            CurvePoint::Infinity => CurvePoint::Infinity,
+            CurvePoint::Affine { x, y } => CurvePoint::Affine { x, y: -y },
+        }
+    }
+}
+
+impl fmt::Display for CurvePoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CurvePoint::Infinity => write!(f, "O"),
+            CurvePoint::Affine { x, y } => write!(f, "({}, {})", x, y),
+        }
+    }
+}
+
+impl Group for CurvePoint {
+    // x 最小的那个点；群的阶是素数，任何非 O 的点都能当生成元
+    fn generator() -> Self {
+        (0..FIELD_MODULUS)
+            .find_map(|x| Self::lift_x(Fq::new(x)))
+            .expect("curve has affine points")
+    }
+
+    fn identity() -> Self {
+        CurvePoint::Infinity
+    }
+
+    fn op(self, other: Self) -> Self {
+        self + other
+    }
+
+    // 协议里的标量往往是私钥或 nonce，用 Montgomery ladder (ex20)
+    fn pow(self, exp: Scalar) -> Self {
+        self.mul_ladder(exp)
+    }
+
+    fn inverse(self) -> Self {
+        -self
+    }
+
+    // Try-and-increment：哈希出一个 x，不在曲线上就换计数器重来 (大约一半的 x 有对应的点)
+    // 点数是素数、没有余因子 (cofactor = 1)，拿到的点直接就在 q 阶群里
+    fn hash_to_group(label: &str) -> Self {
+        (0u32..)
+            .find_map(|counter| {
+                let digest = sha256(format!("{}/{}", label, counter).as_bytes());
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&digest[..8]);
+                let point = Self::lift_x(Fq::new(u64::from_be_bytes(bytes)))?;
+                // 用哈希的最后一位决定取 y 还是 -y
+                Some(if digest[31] & 1 == 1 { -point } else { point })
+            })
+            .expect("about half of all x values lie on the curve")
+    }
+
+    // 压缩编码：O -> 0，其余 -> 1 + 2x + (y 的奇偶)
+    fn encode(&self) -> u64 {
+        match self {
+            CurvePoint::Infinity => 0,
+            CurvePoint::Affine { x, y } => 1 + 2 * x.value() + (y.value() & 1),
+        }
+    }
+}
+
+// 点数 = 群的阶 (见文件头)
+pub const CURVE_ORDER: u64 = GROUP_ORDER;
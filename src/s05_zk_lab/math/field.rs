@@ -0,0 +1,140 @@
+// src/s05_zk_lab/math/field.rs
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/*
+素数域 F_p：{0, 1, ..., p-1} 上的加减乘除，全部取模 p
+
+为什么用 const 泛型 Fp<const P: u64>？
+    素数 P 是类型的一部分：Fp<17> 和 Fp<97> 是两个不同的类型，
+    不小心把两个不同域的元素相加，编译器直接拦截 —— 而不是运行时算出一个错误的数。
+    想换一个素数，只需要换一个类型参数 (见文件末尾的类型别名)。
+
+约定：P 必须是素数且小于 2^63。乘法先升到 u128 再取模，不会溢出。
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fp<const P: u64>(u64);
+
+impl<const P: u64> Fp<P> {
+    pub const MODULUS: u64 = P;
+
+    // 任意整数 -> 域元素 (自动取模)
+    pub fn new(value: u64) -> Self {
+        Fp(value % P)
+    }
+
+    // 负数也能放进来：-1 ≡ p - 1 (mod p)
+    pub fn from_i64(value: i64) -> Self {
+        Fp(value.rem_euclid(P as i64) as u64)
+    }
+
+    pub fn zero() -> Self {
+        Fp(0)
+    }
+
+    pub fn one() -> Self {
+        Fp(1)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    // 快速幂 (Square-and-Multiply)：O(log exp) 次乘法
+    // 从低位到高位扫描 exp 的二进制：遇到 1 就把当前的 base 乘进结果，每一轮 base 自乘
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut result = Self::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        result
+    }
+
+    // 乘法逆元：找 x 使得 self * x ≡ 1 (mod p)
+    // 扩展欧几里得：求出 a*x + p*y = gcd(a, p) = 1，两边模 p 就得到 a*x ≡ 1
+    // 0 没有逆元，返回 None (就像整数不能除以 0)
+    pub fn inv(self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let (g, x, _) = ext_gcd(self.0 as i128, P as i128);
+        if g != 1 {
+            return None; // 只有 P 不是素数时才会走到这里
+        }
+        Some(Fp(x.rem_euclid(P as i128) as u64))
+    }
+}
+
+// 扩展欧几里得算法：返回 (g, x, y) 满足 a*x + b*y = g = gcd(a, b)
+// 用 i128 是因为中间的系数 x, y 可能为负
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+    // gcd(a, b) = gcd(b, a mod b)，然后把系数"倒推"回来
+    let (g, x1, y1) = ext_gcd(b, a % b);
+    (g, y1, x1 - (a / b) * y1)
+}
+
+// ==========================================
+// 运算符重载：让 a + b * c 读起来就像数学公式
+// ==========================================
+impl<const P: u64> Add for Fp<P> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Fp(((self.0 as u128 + rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Sub for Fp<P> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        // 先加上 P 再减，避免 u64 下溢
+        Fp(((self.0 as u128 + P as u128 - rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Mul for Fp<P> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Fp(((self.0 as u128 * rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Neg for Fp<P> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::zero() - self
+    }
+}
+
+// 除法 = 乘以逆元。除以 0 和整数一样直接 panic
+impl<const P: u64> Div for Fp<P> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let inv = rhs.inv().expect("division by zero in Fp");
+        self.mul(inv)
+    }
+}
+
+impl<const P: u64> fmt::Display for Fp<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// 常用的几个玩具域
+pub type F17 = Fp<17>;
+pub type F97 = Fp<97>;
+// Mersenne-31 (p = 2^31 - 1)：Plonky3 / Stwo 用的域；p - 1 不被 5 整除，x^5 是置换 (ex18 的 Poseidon)
+pub type M31 = Fp<2147483647>;
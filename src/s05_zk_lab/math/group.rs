@@ -0,0 +1,142 @@
+// src/s05_zk_lab/math/group.rs
+use std::fmt;
+use std::ops::Mul;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::sha256;
+
+use super::field::Fp;
+
+/*
+玩具素数阶群 (Schnorr Group)：承诺、签名、Sigma 协议的舞台
+
+构造：
+    取"安全素数" p = 2q + 1，其中 q 也是素数。这里 q = 1019, p = 2039。
+    乘法群 Z_p^* 有 p - 1 = 2q 个元素，其中"二次剩余" (某个数的平方) 恰好构成一个 q 阶子群。
+    q 是素数 => 子群里除了 1 以外的任何元素都是生成元。
+
+    - 群元素 GroupElement：Z_p^* 里的二次剩余，运算是 mod p 乘法
+    - 标量 Scalar：指数，活在 F_q 里 (因为 g^q = 1，指数只需 mod q)
+
+安全性：离散对数问题 —— 给定 g 和 g^x，求 x。
+    真实系统里 p 有 2048 bit 或者改用椭圆曲线 (ex 后续)，这里的 q = 1019 一秒就能暴力破解，
+    我们会在练习里故意这么做，看看"离散对数被破解"会带来什么后果。
+*/
+
+pub const GROUP_ORDER: u64 = 1019; // q
+pub const MODULUS: u64 = 2039; // p = 2q + 1
+
+pub type Scalar = Fp<GROUP_ORDER>;
+type Zp = Fp<MODULUS>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GroupElement(Zp);
+
+impl GroupElement {
+    // 生成元 g = 4 = 2^2：是二次剩余且不等于 1，所以阶恰好为 q
+    pub fn generator() -> Self {
+        GroupElement(Zp::new(4))
+    }
+
+    pub fn identity() -> Self {
+        GroupElement(Zp::one())
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0.value()
+    }
+
+    // 指数运算 g^x (写成乘法群的"标量乘")
+    pub fn pow(self, exp: Scalar) -> Self {
+        GroupElement(self.0.pow(exp.value()))
+    }
+
+    pub fn inverse(self) -> Self {
+        GroupElement(self.0.inv().expect("group elements are nonzero"))
+    }
+
+    // "Nothing up my sleeve"：从一个公开的字符串哈希出群元素
+    // 先把哈希值映射到 [1, p-1]，再平方，保证落在 q 阶子群里
+    // 这样谁都不知道它相对于 g 的离散对数 (除非暴力破解)
+    pub fn hash_to_group(label: &str) -> Self {
+        let digest = sha256(label.as_bytes());
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        let x = Zp::new(u64::from_be_bytes(bytes) % (MODULUS - 1) + 1);
+        GroupElement(x * x)
+    }
+
+    // 暴力求离散对数：只在玩具群里可行 (q = 1019 次尝试)
+    // 真实参数下这一步需要的时间比宇宙年龄还长
+    pub fn brute_force_log(self, base: Self) -> Option<Scalar> {
+        let mut acc = Self::identity();
+        for x in 0..GROUP_ORDER {
+            if acc == self {
+                return Some(Scalar::new(x));
+            }
+            acc = acc * base;
+        }
+        None
+    }
+}
+
+// 群运算：mod p 乘法
+impl Mul for GroupElement {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        GroupElement(self.0 * rhs.0)
+    }
+}
+
+impl fmt::Display for GroupElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// ==========================================
+// 抽象的素数阶群
+// ==========================================
+// Pedersen、Schnorr 只用到"群运算 + 标量乘 + 求逆"，不关心群长什么样：
+// 同一份代码既能跑在这里的 mod p 乘法群上，也能跑在 curve.rs 的椭圆曲线上 (阶同为 q，共用 Scalar)
+pub trait Group: Copy + Eq + fmt::Display {
+    fn generator() -> Self;
+    fn identity() -> Self;
+    // 群运算：乘法群里是模乘，椭圆曲线上是点加
+    fn op(self, other: Self) -> Self;
+    fn pow(self, exp: Scalar) -> Self;
+    fn inverse(self) -> Self;
+    fn hash_to_group(label: &str) -> Self;
+    // 喂给哈希函数 (Fiat-Shamir) 用的定长编码
+    fn encode(&self) -> u64;
+}
+
+// 直接转发给上面的固有方法 (固有方法优先于 trait 方法，不会递归)
+impl Group for GroupElement {
+    fn generator() -> Self {
+        GroupElement::generator()
+    }
+    fn identity() -> Self {
+        GroupElement::identity()
+    }
+    fn op(self, other: Self) -> Self {
+        self * other
+    }
+    fn pow(self, exp: Scalar) -> Self {
+        GroupElement::pow(self, exp)
+    }
+    fn inverse(self) -> Self {
+        GroupElement::inverse(self)
+    }
+    fn hash_to_group(label: &str) -> Self {
+        GroupElement::hash_to_group(label)
+    }
+    fn encode(&self) -> u64 {
+        self.value()
+    }
+}
+
+// 随机标量 (盲因子、随机挑战、私钥……)
+pub fn random_scalar(rng: &mut SimpleRng) -> Scalar {
+    Scalar::new(rng.gen_range(GROUP_ORDER))
+}
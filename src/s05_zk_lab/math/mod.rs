@@ -0,0 +1,8 @@
+// src/s05_zk_lab/math/mod.rs
+// ZK 练习共用的数学基础设施 (不是练习本身，练习在 exNN_*.rs 里)
+
+pub mod curve;
+pub mod field;
+pub mod group;
+pub mod multilinear;
+pub mod poly;
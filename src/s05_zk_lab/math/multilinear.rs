@@ -0,0 +1,63 @@
+// src/s05_zk_lab/math/multilinear.rs
+use super::field::Fp;
+
+/*
+多线性多项式 (Multilinear Polynomial)：n 个变量，每个变量的次数最多为 1
+    例：f(x1, x2) = 3 + 2*x1 + 5*x1*x2
+
+一个多线性多项式被它在布尔超立方体 {0,1}^n 上的 2^n 个取值【唯一】确定，
+所以直接存这张取值表 (evals)，而不是存系数。
+
+下标约定：x1 是最高位。
+    n = 2 时 evals = [f(0,0), f(0,1), f(1,0), f(1,1)]
+    前一半是 x1 = 0，后一半是 x1 = 1。
+
+这张表在布尔点之外的取值靠"多线性扩展" (MLE) 得到：
+    f(r, ...) = (1 - r) * f(0, ...) + r * f(1, ...)
+*/
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultilinearPoly<const P: u64> {
+    evals: Vec<Fp<P>>,
+}
+
+impl<const P: u64> MultilinearPoly<P> {
+    // evals 的长度必须是 2 的幂
+    pub fn new(evals: Vec<Fp<P>>) -> Self {
+        assert!(evals.len().is_power_of_two(), "evaluation table must have 2^n entries");
+        MultilinearPoly { evals }
+    }
+
+    pub fn from_u64(evals: &[u64]) -> Self {
+        Self::new(evals.iter().map(|&v| Fp::new(v)).collect())
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.evals.len().trailing_zeros() as usize
+    }
+
+    pub fn evals(&self) -> &[Fp<P>] {
+        &self.evals
+    }
+
+    // 固定第一个变量 x1 = r，得到一个 n-1 元的多线性多项式 (表的长度减半)
+    pub fn fix_first_variable(&self, r: Fp<P>) -> Self {
+        let half = self.evals.len() / 2;
+        let (lo, hi) = self.evals.split_at(half);
+        let evals = lo
+            .iter()
+            .zip(hi)
+            .map(|(&a, &b)| a + r * (b - a)) // (1 - r) * a + r * b
+            .collect();
+        MultilinearPoly { evals }
+    }
+
+    // 在任意点 (r1, ..., rn) 上求值：逐个固定变量，最后剩一个数
+    pub fn evaluate(&self, point: &[Fp<P>]) -> Fp<P> {
+        assert_eq!(point.len(), self.num_vars(), "point dimension mismatch");
+        point
+            .iter()
+            .fold(self.clone(), |poly, &r| poly.fix_first_variable(r))
+            .evals[0]
+    }
+}
@@ -0,0 +1,175 @@
+// src/s05_zk_lab/math/poly.rs
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+use super::field::Fp;
+
+/*
+域 F_p 上的单变量多项式  p(x) = c0 + c1*x + c2*x^2 + ...
+
+存储：coeffs[i] 是 x^i 的系数 (低次在前)。
+不变量：最高次系数非 0 (末尾的 0 会被 trim 掉)，零多项式就是空 Vec。
+    这样 degree() 直接看长度，两个多项式相等就是 Vec 相等。
+*/
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial<const P: u64> {
+    coeffs: Vec<Fp<P>>,
+}
+
+impl<const P: u64> Polynomial<P> {
+    pub fn new(mut coeffs: Vec<Fp<P>>) -> Self {
+        while coeffs.last().is_some_and(|c| c.is_zero()) {
+            coeffs.pop();
+        }
+        Polynomial { coeffs }
+    }
+
+    // 方便写字面量：from_u64(&[1, 3, 2]) = 1 + 3x + 2x^2
+    pub fn from_u64(coeffs: &[u64]) -> Self {
+        Self::new(coeffs.iter().map(|&c| Fp::new(c)).collect())
+    }
+
+    pub fn zero() -> Self {
+        Polynomial { coeffs: Vec::new() }
+    }
+
+    pub fn coeffs(&self) -> &[Fp<P>] {
+        &self.coeffs
+    }
+
+    // 零多项式没有次数 (数学上常记为 -∞)，用 None 表示
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    // 霍纳法则 (Horner)：c0 + x*(c1 + x*(c2 + ...))
+    // 从最高次往下折叠，n 次乘法 + n 次加法，不需要单独算 x^i
+    pub fn evaluate(&self, x: Fp<P>) -> Fp<P> {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(Fp::zero(), |acc, &c| acc * x + c)
+    }
+
+    // 第 i 个拉格朗日基多项式：
+    //   L_i(x) = Π_{j≠i} (x - x_j) / (x_i - x_j)
+    // 它在 x_i 处取 1，在其他所有 x_j 处取 0 —— 像一个"只对第 i 个点亮灯"的开关
+    pub fn lagrange_basis(xs: &[Fp<P>], i: usize) -> Self {
+        let mut basis = Self::new(vec![Fp::one()]);
+        let mut denominator = Fp::one();
+        for (j, &xj) in xs.iter().enumerate() {
+            if j == i {
+                continue;
+            }
+            // 乘上 (x - x_j)：系数是 [-x_j, 1]
+            basis = &basis * &Self::new(vec![-xj, Fp::one()]);
+            denominator = denominator * (xs[i] - xj);
+        }
+        // 整体除以分母 = 每个系数乘以分母的逆元
+        let scale = Fp::one() / denominator;
+        Self::new(basis.coeffs.iter().map(|&c| c * scale).collect())
+    }
+
+    // 拉格朗日插值：过 n 个点 (x 互不相同) 的唯一一条次数 < n 的多项式
+    //   p(x) = Σ y_i * L_i(x)
+    // 有重复的 x 时无解 (分母会是 0)，返回 None
+    pub fn interpolate(points: &[(Fp<P>, Fp<P>)]) -> Option<Self> {
+        let xs: Vec<Fp<P>> = points.iter().map(|(x, _)| *x).collect();
+        for (i, x) in xs.iter().enumerate() {
+            if xs[..i].contains(x) {
+                return None;
+            }
+        }
+
+        let mut result = Self::zero();
+        for (i, &(_, y)) in points.iter().enumerate() {
+            let term = Self::lagrange_basis(&xs, i);
+            let scaled = Self::new(term.coeffs.iter().map(|&c| c * y).collect());
+            result = &result + &scaled;
+        }
+        Some(result)
+    }
+
+    // 除以 (x - z)：综合除法 (Synthetic Division)，返回 (商, 余数)
+    // 余数恰好等于 p(z) (余数定理)；p(z) = 0 当且仅当 (x - z) 整除 p(x)
+    pub fn divide_by_linear(&self, z: Fp<P>) -> (Self, Fp<P>) {
+        let mut quotient = vec![Fp::zero(); self.coeffs.len().saturating_sub(1)];
+        let mut carry = Fp::zero();
+        for (i, &c) in self.coeffs.iter().enumerate().rev() {
+            carry = carry * z + c;
+            if i > 0 {
+                quotient[i - 1] = carry;
+            }
+        }
+        (Self::new(quotient), carry)
+    }
+}
+
+// ==========================================
+// 运算符：为引用实现，a + b 不会吃掉 a 和 b (Polynomial 内部有 Vec，不是 Copy)
+// ==========================================
+impl<const P: u64> Add for &Polynomial<P> {
+    type Output = Polynomial<P>;
+    fn add(self, rhs: Self) -> Polynomial<P> {
+        let len = self.coeffs.len().max(rhs.coeffs.len());
+        let coeffs = (0..len)
+            .map(|i| {
+                let a = self.coeffs.get(i).copied().unwrap_or_else(Fp::zero);
+                let b = rhs.coeffs.get(i).copied().unwrap_or_else(Fp::zero);
+                a + b
+            })
+            .collect();
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<const P: u64> Sub for &Polynomial<P> {
+    type Output = Polynomial<P>;
+    fn sub(self, rhs: Self) -> Polynomial<P> {
+        let negated = Polynomial::new(rhs.coeffs.iter().map(|&c| -c).collect());
+        self.add(&negated)
+    }
+}
+
+impl<const P: u64> Mul for &Polynomial<P> {
+    type Output = Polynomial<P>;
+    // 朴素卷积：(Σ a_i x^i)(Σ b_j x^j) = Σ a_i b_j x^(i+j)，O(n*m)
+    fn mul(self, rhs: Self) -> Polynomial<P> {
+        if self.coeffs.is_empty() || rhs.coeffs.is_empty() {
+            return Polynomial::zero();
+        }
+        let mut coeffs = vec![Fp::zero(); self.coeffs.len() + rhs.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in rhs.coeffs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j] + a * b;
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+// 打印成 2x^2 + 3x + 1 的样子 (高次在前，省略 0 系数)
+impl<const P: u64> fmt::Display for Polynomial<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let terms: Vec<String> = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| !c.is_zero())
+            .map(|(i, c)| match (i, c.value()) {
+                (0, v) => format!("{}", v),
+                (1, 1) => String::from("x"),
+                (1, v) => format!("{}x", v),
+                (_, 1) => format!("x^{}", i),
+                (_, v) => format!("{}x^{}", v, i),
+            })
+            .collect();
+        if terms.is_empty() {
+            write!(f, "0")
+        } else {
+            write!(f, "{}", terms.join(" + "))
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// src/s05_zk_lab/mimc.rs
+use std::sync::OnceLock;
+
+use super::ex01_merkle::{Hasher, LEAF_PREFIX, NODE_PREFIX};
+use super::hash::{sha256, Digest};
+use super::math::field::{ext_gcd, M31};
+use super::poseidon;
+
+/*
+MiMC：最早的"域原生"哈希之一 (Albrecht et al., 2016)，比 Poseidon 还简单
+
+分组密码 E_k(x)：
+    x_0 = x
+    x_{i+1} = (x_i + k + c_i)^d      共 ROUNDS 轮
+    E_k(x) = x_ROUNDS + k
+    每一轮只有"加常数 + 一个幂函数"，没有任何线性层 —— 状态只有 1 个域元素。
+
+为什么 d = 5？
+    x -> x^d 必须是域上的置换 (否则无法解密，也会丢失熵)，要求 gcd(d, p - 1) = 1。
+    M31 的 p - 1 = 2 * 3^2 * 7 * 11 * 31 * 151 * 331：x^3 和 x^7 都不行，x^5 可以。
+    (BN254 上的 circomlib 用的是 MiMC7，因为那里 5 不满足条件)
+
+轮数：
+    代数次数每轮乘 d，至少要让 d^ROUNDS >= p，才能挡住插值攻击：
+    ceil(31 / log2(5)) = 14，这里再多留 2 轮。
+
+哈希模式 (Miyaguchi-Preneel)：
+    h_0 = 0，每吸收一个元素 m：h <- E_h(m) + h + m
+    上一轮的哈希值当作密钥，所以压缩 n 个元素就要做 n 次加密。
+
+⚠️ 玩具化的地方：
+    安全余量远小于真实参数；输出只有 31 bit；哈希模式没有做长度填充 (下面用长度当初始值代替)。
+*/
+
+pub const EXPONENT: u64 = 5;
+pub const ROUNDS: usize = 16;
+
+// 与 Poseidon 一样："Nothing up my sleeve" 的轮常数，只算一次
+// 第 0 轮常数按惯例取 0
+fn round_constants() -> &'static [M31] {
+    static CONSTANTS: OnceLock<Vec<M31>> = OnceLock::new();
+    CONSTANTS.get_or_init(|| {
+        (0..ROUNDS)
+            .map(|r| {
+                if r == 0 {
+                    return M31::zero();
+                }
+                let digest = sha256(format!("rust-zk-lab/mimc/{}", r).as_bytes());
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&digest[..8]);
+                M31::new(u64::from_be_bytes(bytes))
+            })
+            .collect()
+    })
+}
+
+// x^5 的逆：x^(5^-1 mod (p - 1))，由费马小定理保证 (x^5)^e = x
+fn inverse_exponent() -> u64 {
+    let order = 2147483646i128; // p - 1
+    let (g, x, _) = ext_gcd(EXPONENT as i128, order);
+    assert_eq!(g, 1, "x^{} is not a permutation of the field", EXPONENT);
+    x.rem_euclid(order) as u64
+}
+
+pub fn encrypt(key: M31, plaintext: M31) -> M31 {
+    let x = round_constants().iter().fold(plaintext, |x, &c| (x + key + c).pow(EXPONENT));
+    x + key
+}
+
+// 解密要倒着走每一轮，并把 x^5 换成 x^(1/5)：一次开方要 30 多次乘法
+// 这正是 MiMC 的"不对称"：电路 (正向) 便宜，逆向贵 —— 但哈希从来不需要逆向
+pub fn decrypt(key: M31, ciphertext: M31) -> M31 {
+    let e = inverse_exponent();
+    round_constants().iter().rev().fold(ciphertext - key, |x, &c| x.pow(e) - key - c)
+}
+
+// 每轮 x^5 = 3 次乘法；一次加密 ROUNDS * 3 个乘法约束
+pub fn multiplications_per_block() -> usize {
+    ROUNDS * 3
+}
+
+pub fn hash(inputs: &[M31]) -> M31 {
+    // 初始值取输入长度，和 Poseidon 的 capacity 起同样的作用：[a] 和 [a, 0] 不撞车
+    inputs.iter().fold(M31::new(inputs.len() as u64), |h, &m| encrypt(h, m) + h + m)
+}
+
+// ==========================================
+// 接入 ex01 的 Merkle 树
+// ==========================================
+// 字节串打包成域元素的方式与 Poseidon 相同，直接复用：第一个元素是字节长度，"\x00\x01" 和 "\x01" 不会撞车
+pub fn hash_bytes(data: &[u8]) -> M31 {
+    hash(&poseidon::pack_bytes(data))
+}
+
+// 域分离和 ex01 的默认规则一致：叶子的第一个元素是 0x00，内部节点的第一个元素是 0x01
+fn merkle_leaf(data: &[u8]) -> String {
+    let mut inputs = vec![M31::new(LEAF_PREFIX as u64)];
+    inputs.extend(poseidon::pack_bytes(data));
+    hash(&inputs).to_string()
+}
+
+fn merkle_node(left: &str, right: &str) -> String {
+    let to_field = |s: &str| s.parse().map(M31::new).unwrap_or_else(|_| hash_bytes(s.as_bytes()));
+    hash(&[M31::new(NODE_PREFIX as u64), to_field(left), to_field(right)]).to_string()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MimcHasher;
+
+impl Hasher for MimcHasher {
+    fn name(&self) -> &'static str {
+        "mimc"
+    }
+    fn hash(&self, data: &[u8]) -> Digest {
+        poseidon::field_digest(hash_bytes(data))
+    }
+    fn leaf(&self, data: &[u8]) -> String {
+        merkle_leaf(data)
+    }
+    fn node(&self, left: &str, right: &str) -> String {
+        merkle_node(left, right)
+    }
+}
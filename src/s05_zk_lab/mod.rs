@@ -0,0 +1,164 @@
+// src/s05_zk_lab/mod.rs
+
+// 公共工具
+pub mod bitcoin_merkle;
+pub mod bulletproof;
+pub mod circuit;
+pub mod commitments;
+pub mod crypto;
+pub mod gkr;
+pub mod hash;
+pub mod ipa;
+pub mod lookup;
+pub mod math;
+pub mod mimc;
+pub mod poseidon;
+pub mod range_proof;
+pub mod sigma;
+pub mod snark;
+pub mod stark;
+pub mod streaming_merkle;
+pub mod sumcheck;
+pub mod transcript;
+
+// 练习
+pub mod ex01_merkle;
+pub mod ex02_smt;
+pub mod ex03_mmr;
+pub mod ex04_merkle_bench;
+pub mod ex05_parallel_merkle;
+pub mod ex06_field;
+pub mod ex07_poly;
+pub mod ex08_pedersen;
+pub mod ex09_schnorr;
+pub mod ex10_sigma;
+pub mod ex11_circuit;
+pub mod ex12_witness;
+pub mod ex13_sumcheck;
+pub mod ex14_kzg;
+pub mod ex15_snark;
+pub mod ex16_stark;
+pub mod ex17_verkle;
+pub mod ex18_poseidon;
+pub mod ex19_curve;
+pub mod ex20_scalar_mul;
+pub mod ex21_coin_flip;
+pub mod ex22_range_proof;
+pub mod ex23_mimc;
+pub mod ex24_second_preimage;
+pub mod ex25_merkle_json;
+pub mod ex26_bitcoin_merkle;
+pub mod ex27_rsa_accumulator;
+pub mod ex28_shielded_pool;
+pub mod ex29_ceremony;
+pub mod ex30_inclusion_file;
+pub mod ex32_hasher_compare;
+pub mod ex33_streaming_merkle;
+pub mod ex34_gkr;
+pub mod ex35_inner_product;
+pub mod ex36_plonk;
+pub mod ex37_lookup;
+pub mod ex38_commitment_bench;
+pub mod ex39_vrf;
+pub mod ex40_bls_aggregate;
+pub mod ex41_zk_puzzle;
+
+use std::io;
+
+pub fn run_experiments() {
+    loop {
+        println!("\n--- 🔐 S05 零知识证明实验室 (ZK Lab) ---");
+        println!("1. Merkle Tree (Box 递归构建)");
+        println!("2. 稀疏 Merkle 树 (非成员证明)");
+        println!("3. Merkle 山脉 (只追加累加器)");
+        println!("4. 迭代式构建 vs 递归构建 (Benchmark)");
+        println!("5. 多线程并行构建 (S04 x S05)");
+        println!("6. 素数域算术 (Finite Field)");
+        println!("7. 多项式与拉格朗日插值 (Polynomial)");
+        println!("8. Pedersen 承诺 (隐藏 & 绑定)");
+        println!("9. Schnorr 身份认证与签名");
+        println!("10. Sigma 协议框架 (AND / OR 组合)");
+        println!("11. 算术电路 DSL -> R1CS");
+        println!("12. 从电路自动生成见证 (Witness)");
+        println!("13. Sum-check 协议 (交互式)");
+        println!("14. KZG 多项式承诺 (模拟配对)");
+        println!("15. 端到端玩具 SNARK (毕业设计)");
+        println!("16. 玩具 STARK (斐波那契 + FRI)");
+        println!("17. Verkle 树 vs Merkle 树 (证明大小)");
+        println!("18. Poseidon 海绵哈希 (ZK 友好)");
+        println!("19. 玩具椭圆曲线 (群公理 & EC-Schnorr)");
+        println!("20. 标量乘与计时侧信道 (Montgomery ladder)");
+        println!("21. 承诺-揭示抛硬币 (作弊检测)");
+        println!("22. 位分解范围证明 (机密交易)");
+        println!("23. MiMC 哈希 (与 Poseidon / SHA-256 对比)");
+        println!("24. Merkle 第二原像攻击 (域分离前缀)");
+        println!("25. Merkle 证明存盘与跨会话验证 (JSON)");
+        println!("26. 比特币 Merkle 根 (对照真实区块)");
+        println!("27. RSA 累加器 vs Merkle 树 (常数大小证明)");
+        println!("28. 屏蔽池：票据承诺与作废符 (防双花)");
+        println!("29. Powers of Tau：多方可信设置仪式");
+        println!("30. 离线包含证明 (证明方)：读 CSV/JSON 交易，写出根和证明文件");
+        println!("31. 离线包含证明 (验证方)：只读文件并验证");
+        println!("32. 可插拔的哈希后端：同一批叶子比较各种根");
+        println!("33. 流式 Merkle 根：O(log n) 内存处理大数据流");
+        println!("34. GKR 协议：逐层 sum-check 把输出声明归约到输入");
+        println!("35. 内积论证 (Bulletproofs 核心)：逐轮折叠，对数大小的证明");
+        println!("36. PLONK 风格的门约束：选择子多项式 + 置换复制约束 (对照 R1CS)");
+        println!("37. 查找论证：用一张字节表做范围检查 (对比位分解)");
+        println!("38. 承诺方案对比：Merkle / Pedersen 向量 / KZG 的耗时与证明大小");
+        println!("39. 可验证随机函数 (VRF)：PoS 出块抽签");
+        println!("40. BLS 签名聚合：一个签名代表整个验证者集合的投票");
+        println!("41. 零知识数独 / 图 3-着色：一轮轮打开局部，攒出信心");
+        println!("0. 返回主菜单");
+        println!("请输入练习编号:");
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("读取失败");
+
+        match input.trim() {
+            "1" => ex01_merkle::run(),
+            "2" => ex02_smt::run(),
+            "3" => ex03_mmr::run(),
+            "4" => ex04_merkle_bench::run(),
+            "5" => ex05_parallel_merkle::run(),
+            "6" => ex06_field::run(),
+            "7" => ex07_poly::run(),
+            "8" => ex08_pedersen::run(),
+            "9" => ex09_schnorr::run(),
+            "10" => ex10_sigma::run(),
+            "11" => ex11_circuit::run(),
+            "12" => ex12_witness::run(),
+            "13" => ex13_sumcheck::run(),
+            "14" => ex14_kzg::run(),
+            "15" => ex15_snark::run(),
+            "16" => ex16_stark::run(),
+            "17" => ex17_verkle::run(),
+            "18" => ex18_poseidon::run(),
+            "19" => ex19_curve::run(),
+            "20" => ex20_scalar_mul::run(),
+            "21" => ex21_coin_flip::run(),
+            "22" => ex22_range_proof::run(),
+            "23" => ex23_mimc::run(),
+            "24" => ex24_second_preimage::run(),
+            "25" => ex25_merkle_json::run(),
+            "26" => ex26_bitcoin_merkle::run(),
+            "27" => ex27_rsa_accumulator::run(),
+            "28" => ex28_shielded_pool::run(),
+            "29" => ex29_ceremony::run(),
+            "30" => ex30_inclusion_file::prove(),
+            "31" => ex30_inclusion_file::verify(),
+            "32" => ex32_hasher_compare::run(),
+            "33" => ex33_streaming_merkle::run(),
+            "34" => ex34_gkr::run(),
+            "35" => ex35_inner_product::run(),
+            "36" => ex36_plonk::run(),
+            "37" => ex37_lookup::run(),
+            "38" => ex38_commitment_bench::run(),
+            "39" => ex39_vrf::run(),
+            "40" => ex40_bls_aggregate::run(),
+            "41" => ex41_zk_puzzle::run(),
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}
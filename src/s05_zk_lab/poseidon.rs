@@ -0,0 +1,157 @@
+// src/s05_zk_lab/poseidon.rs
+use std::sync::OnceLock;
+
+use super::ex01_merkle::{Hasher, LEAF_PREFIX, NODE_PREFIX};
+use super::hash::{sha256, Digest};
+use super::math::field::M31;
+
+/*
+简化版 Poseidon：为 ZK 电路设计的"域原生"哈希 (Grassi et al., 2019)
+
+海绵结构 (Sponge)：
+    状态 = WIDTH 个域元素，前 RATE 个是"吸收口"，剩下的 capacity 不对外暴露 (安全性来源)
+    吸收：把输入加进吸收口，做一次置换；重复直到输入用完
+    挤出：输出状态的第 0 个元素
+
+置换的每一轮 (Round)：
+    1. 加轮常数 (ARK)      state[i] += c[r][i]
+    2. S-box              x -> x^5 (非线性的唯一来源，也是唯一需要乘法的地方)
+    3. 线性混合 (MDS)      state = M * state
+
+    全轮 (Full round)：3 个元素都过 S-box；部分轮 (Partial round)：只有 state[0] 过 S-box
+    前后各 FULL_ROUNDS / 2 个全轮夹着中间的部分轮 —— 全轮抵抗统计攻击，部分轮便宜地堆高代数次数
+
+⚠️ 玩具化的地方：
+    轮数没有按论文的安全性公式计算；MDS 矩阵用的是最简单的 circ(2, 1, 1)；
+    输出只有 31 bit，生日攻击 2^16 次就能找到碰撞。
+*/
+
+pub const WIDTH: usize = 3;
+pub const RATE: usize = 2;
+pub const FULL_ROUNDS: usize = 8;
+pub const PARTIAL_ROUNDS: usize = 14;
+
+// "Nothing up my sleeve"：轮常数从公开字符串哈希出来，只算一次
+fn round_constants() -> &'static [[M31; WIDTH]] {
+    static CONSTANTS: OnceLock<Vec<[M31; WIDTH]>> = OnceLock::new();
+    CONSTANTS.get_or_init(|| {
+        (0..FULL_ROUNDS + PARTIAL_ROUNDS)
+            .map(|r| {
+                let mut row = [M31::zero(); WIDTH];
+                for (i, c) in row.iter_mut().enumerate() {
+                    let digest = sha256(format!("rust-zk-lab/poseidon/{}/{}", r, i).as_bytes());
+                    let mut bytes = [0u8; 8];
+                    bytes.copy_from_slice(&digest[..8]);
+                    *c = M31::new(u64::from_be_bytes(bytes));
+                }
+                row
+            })
+            .collect()
+    })
+}
+
+// x^5 = x * (x^2)^2：3 次乘法
+fn sbox(x: M31) -> M31 {
+    let x2 = x * x;
+    x2 * x2 * x
+}
+
+// M = circ(2, 1, 1)：y_i = x_i + (x_0 + x_1 + x_2)，只有加法，在电路里是免费的
+fn mix(state: &mut [M31; WIDTH]) {
+    let sum = state.iter().fold(M31::zero(), |acc, &x| acc + x);
+    for x in state.iter_mut() {
+        *x = *x + sum;
+    }
+}
+
+pub fn permute(state: &mut [M31; WIDTH]) {
+    let half_full = FULL_ROUNDS / 2;
+    for (r, constants) in round_constants().iter().enumerate() {
+        for (x, &c) in state.iter_mut().zip(constants) {
+            *x = *x + c;
+        }
+        if r < half_full || r >= half_full + PARTIAL_ROUNDS {
+            for x in state.iter_mut() {
+                *x = sbox(*x);
+            }
+        } else {
+            state[0] = sbox(state[0]);
+        }
+        mix(state);
+    }
+}
+
+// 一次置换里的乘法次数 —— 在 R1CS 里加法和乘常数都能并进线性组合，只有乘法才占约束
+pub fn multiplications_per_permutation() -> usize {
+    (FULL_ROUNDS * WIDTH + PARTIAL_ROUNDS) * 3
+}
+
+pub fn hash(inputs: &[M31]) -> M31 {
+    // capacity 初始化为输入长度 (域分离)：[a] 和 [a, 0] 不会得到同一个哈希
+    let mut state = [M31::zero(), M31::zero(), M31::new(inputs.len() as u64)];
+    if inputs.is_empty() {
+        permute(&mut state);
+    }
+    for chunk in inputs.chunks(RATE) {
+        for (x, &input) in state.iter_mut().zip(chunk) {
+            *x = *x + input;
+        }
+        permute(&mut state);
+    }
+    state[0]
+}
+
+// 字节串 -> 域元素：第一个元素是字节长度，之后每 3 个字节打包成一个元素 (2^24 < p，不会溢出取模)
+// 每块按大端整数折叠，开头的 0x00 字节不改变数值："\x00\x01" 和 "\x01" 都会打包成 [1]；
+// 先写下长度 ([2, 1] vs [1, 1])，两者才分得开 (长度超过 p 才会回绕，玩具实现不考虑)
+pub fn pack_bytes(data: &[u8]) -> Vec<M31> {
+    std::iter::once(M31::new(data.len() as u64))
+        .chain(data.chunks(3).map(|chunk| M31::new(chunk.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64))))
+        .collect()
+}
+
+pub fn hash_bytes(data: &[u8]) -> M31 {
+    hash(&pack_bytes(data))
+}
+
+// ==========================================
+// 接入 ex01 的 Merkle 树
+// ==========================================
+// 域分离和 ex01 的默认规则一致：叶子的第一个元素是 0x00，内部节点的第一个元素是 0x01
+fn merkle_leaf(data: &[u8]) -> String {
+    let mut inputs = vec![M31::new(LEAF_PREFIX as u64)];
+    inputs.extend(pack_bytes(data));
+    hash(&inputs).to_string()
+}
+
+// 节点哈希本身就是十进制的域元素，解析回来直接做 2 -> 1 压缩，不需要拼接字符串
+// (如果有人拿别的哈希函数的输出来喂，就退回到按字节哈希)
+fn merkle_node(left: &str, right: &str) -> String {
+    let to_field = |s: &str| s.parse().map(M31::new).unwrap_or_else(|_| hash_bytes(s.as_bytes()));
+    hash(&[M31::new(NODE_PREFIX as u64), to_field(left), to_field(right)]).to_string()
+}
+
+// 摘要只有一个 31 位的域元素，放在 32 字节的前 4 字节 (只在需要字节摘要时用，树里直接存十进制)
+pub fn field_digest(element: M31) -> Digest {
+    let mut digest = [0u8; 32];
+    digest[..4].copy_from_slice(&(element.value() as u32).to_be_bytes());
+    digest
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PoseidonHasher;
+
+impl Hasher for PoseidonHasher {
+    fn name(&self) -> &'static str {
+        "poseidon"
+    }
+    fn hash(&self, data: &[u8]) -> Digest {
+        field_digest(hash_bytes(data))
+    }
+    fn leaf(&self, data: &[u8]) -> String {
+        merkle_leaf(data)
+    }
+    fn node(&self, left: &str, right: &str) -> String {
+        merkle_node(left, right)
+    }
+}
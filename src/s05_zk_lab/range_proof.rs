@@ -0,0 +1,146 @@
+// src/s05_zk_lab/range_proof.rs
+use crate::common::rng::SimpleRng;
+
+use super::commitments::pedersen::{Commitment, PedersenParams};
+use super::hash::sha256;
+use super::math::group::{random_scalar, GroupElement, Scalar, GROUP_ORDER};
+use super::sigma::compose::{Either, Or, OrResponse};
+use super::sigma::dlog::{BaseDlogKnowledge, BaseDlogStatement};
+use super::sigma::{Pair, SigmaProtocol};
+
+/*
+位分解范围证明：在不打开 C = g^v * h^r 的前提下，证明 0 <= v < 2^n
+
+    1. 把 v 拆成二进制 v = Σ 2^i * b_i，对每一位单独做 Pedersen 承诺 C_i = g^(b_i) * h^(r_i)
+       盲因子满足 Σ 2^i * r_i = r (最后一位的 r 由前面的倒推出来)
+    2. 每一位证明 "b_i = 0 或 b_i = 1"：
+            b_i = 0  <=>  C_i = h^(r_i)        <=>  我知道 log_h(C_i)
+            b_i = 1  <=>  C_i / g = h^(r_i)    <=>  我知道 log_h(C_i / g)
+       正好是 Or<BaseDlogKnowledge, BaseDlogKnowledge> (ex10 的 OR 组合)，验证者分不出是哪一支
+    3. 验证者自己检查 Π C_i^(2^i) == C：同态性保证两边的 v 相同
+
+Fiat-Shamir：每一位的挑战 c_i = H(C, i, C_i, a_i)，整个证明是非交互的。
+
+⚠️ 玩具化的地方：
+    群的阶 q = 1019，2^n 必须小于 q，否则 Σ 2^i * b_i 会在 mod q 下"绕回来"，范围就失去意义 —— 所以 n <= 9。
+    证明大小是 O(n)；Bulletproofs 用内积论证把它压到 O(log n) (bulletproof.rs)。
+*/
+
+pub const MAX_BITS: usize = 9;
+
+type BitOr = Or<BaseDlogKnowledge, BaseDlogKnowledge>;
+
+#[derive(Debug, Clone)]
+pub struct BitProof {
+    pub commitment: Commitment,
+    pub a: Pair<GroupElement, GroupElement>,
+    pub z: OrResponse<Scalar, Scalar>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RangeProof {
+    pub bits: Vec<BitProof>,
+}
+
+impl RangeProof {
+    // (群元素个数, 标量个数)：每一位 1 个位承诺 + 2 个 OR 承诺，3 个标量 (c_left 和两支的响应)
+    pub fn size(&self) -> (usize, usize) {
+        (3 * self.bits.len(), 3 * self.bits.len())
+    }
+}
+
+// 第 i 位的两个陈述：(log_h(C_i), log_h(C_i / g))
+fn bit_statement(params: &PedersenParams, bit_commitment: &Commitment) -> (BaseDlogStatement, BaseDlogStatement) {
+    (
+        BaseDlogStatement { base: params.h, public: bit_commitment.0 },
+        BaseDlogStatement { base: params.h, public: bit_commitment.0 * params.g.inverse() },
+    )
+}
+
+fn challenge(value: &Commitment, index: usize, bit: &Commitment, a: &Pair<GroupElement, GroupElement>) -> Scalar {
+    let data: Vec<u8> = [value.0.value(), index as u64, bit.0.value(), a.0.value(), a.1.value()]
+        .iter()
+        .flat_map(|x| x.to_be_bytes())
+        .collect();
+    let digest = sha256(&data);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    Scalar::new(u64::from_be_bytes(bytes))
+}
+
+// 按给定的"位"生成证明。诚实的调用者只会传 0 / 1；
+// 传别的数 (作弊) 时没有见证，只能用模拟器伪造，Fiat-Shamir 的挑战对不上就会被拒绝
+pub fn prove_bits(params: &PedersenParams, bits: &[u64], blinding: Scalar, rng: &mut SimpleRng) -> RangeProof {
+    let n = bits.len();
+    // 前 n-1 位的盲因子随机选，最后一位倒推：r_(n-1) = (r - Σ 2^i r_i) / 2^(n-1)
+    let mut blindings: Vec<Scalar> = (0..n - 1).map(|_| random_scalar(rng)).collect();
+    let partial = blindings
+        .iter()
+        .enumerate()
+        .fold(Scalar::zero(), |acc, (i, &r)| acc + Scalar::new(1 << i) * r);
+    blindings.push((blinding - partial) / Scalar::new(1 << (n - 1)));
+
+    let value = bits
+        .iter()
+        .enumerate()
+        .fold(Scalar::zero(), |acc, (i, &b)| acc + Scalar::new(b << i));
+    let value_commitment = params.commit(value, blinding);
+
+    let proofs = bits
+        .iter()
+        .zip(&blindings)
+        .enumerate()
+        .map(|(i, (&b, &r))| {
+            let commitment = params.commit(Scalar::new(b), r);
+            let st = bit_statement(params, &commitment);
+            let witness = match b {
+                0 => Some(Either::Left(r)),
+                1 => Some(Either::Right(r)),
+                _ => None,
+            };
+            let (a, z) = match witness {
+                Some(w) => {
+                    let (state, a) = BitOr::commit(&st, &w, rng);
+                    let c = challenge(&value_commitment, i, &commitment, &a);
+                    (a, BitOr::respond(&st, &w, state, c))
+                }
+                None => BitOr::simulate(&st, random_scalar(rng), rng),
+            };
+            BitProof { commitment, a, z }
+        })
+        .collect();
+    RangeProof { bits: proofs }
+}
+
+// 诚实证明者：v >= 2^n 时根本拆不出 n 个 0/1 位
+pub fn prove(params: &PedersenParams, value: u64, blinding: Scalar, n: usize, rng: &mut SimpleRng) -> Option<RangeProof> {
+    assert!((1..=MAX_BITS).contains(&n), "2^n must stay below the group order");
+    if value >= 1 << n {
+        return None;
+    }
+    let bits: Vec<u64> = (0..n).map(|i| (value >> i) & 1).collect();
+    Some(prove_bits(params, &bits, blinding, rng))
+}
+
+pub fn verify(params: &PedersenParams, commitment: &Commitment, n: usize, proof: &RangeProof) -> Result<(), String> {
+    if proof.bits.len() != n || (1u64 << n) >= GROUP_ORDER {
+        return Err(format!("位数不对：期望 {} 位 (且 2^n < q)", n));
+    }
+    for (i, bit) in proof.bits.iter().enumerate() {
+        let st = bit_statement(params, &bit.commitment);
+        let c = challenge(commitment, i, &bit.commitment, &bit.a);
+        if !BitOr::verify(&st, &bit.a, c, &bit.z) {
+            return Err(format!("第 {} 位的 OR 证明无效 (位承诺不是 0/1，或者证明不属于这个位置)", i));
+        }
+    }
+    // Π C_i^(2^i) == C
+    let recombined = proof
+        .bits
+        .iter()
+        .enumerate()
+        .fold(GroupElement::identity(), |acc, (i, bit)| acc * bit.commitment.0.pow(Scalar::new(1 << i)));
+    if recombined != commitment.0 {
+        return Err(String::from("各位承诺加权相乘后不等于值的承诺"));
+    }
+    Ok(())
+}
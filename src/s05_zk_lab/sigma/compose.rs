@@ -0,0 +1,136 @@
+// src/s05_zk_lab/sigma/compose.rs
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::math::group::{random_scalar, Scalar};
+
+use super::{Pair, SigmaProtocol};
+
+/*
+组合子 (Combinator)：用两个 Sigma 协议拼出新的 Sigma 协议
+    And<A, B>：我同时知道 A 和 B 的见证
+    Or<A, B> ：我知道 A 或 B 其中之一的见证 (验证者分不出是哪一个)
+
+And / Or 本身也实现了 SigmaProtocol，所以可以无限嵌套：Or<And<A, B>, C> ……
+PhantomData<(A, B)>：结构体里不存 A、B 的值，只在类型层面"记住"它们
+*/
+
+pub struct And<A, B>(PhantomData<(A, B)>);
+
+impl<A: SigmaProtocol, B: SigmaProtocol> SigmaProtocol for And<A, B> {
+    const NAME: &'static str = "AND 组合";
+
+    type Statement = (A::Statement, B::Statement);
+    type Witness = (A::Witness, B::Witness);
+    type ProverState = (A::ProverState, B::ProverState);
+    type Commitment = Pair<A::Commitment, B::Commitment>;
+    type Response = Pair<A::Response, B::Response>;
+
+    fn commit(st: &Self::Statement, w: &Self::Witness, rng: &mut SimpleRng) -> (Self::ProverState, Self::Commitment) {
+        let (sa, ca) = A::commit(&st.0, &w.0, rng);
+        let (sb, cb) = B::commit(&st.1, &w.1, rng);
+        ((sa, sb), Pair(ca, cb))
+    }
+
+    // 两个子协议共用同一个挑战
+    fn respond(st: &Self::Statement, w: &Self::Witness, state: Self::ProverState, c: Scalar) -> Self::Response {
+        Pair(A::respond(&st.0, &w.0, state.0, c), B::respond(&st.1, &w.1, state.1, c))
+    }
+
+    fn verify(st: &Self::Statement, a: &Self::Commitment, c: Scalar, z: &Self::Response) -> bool {
+        A::verify(&st.0, &a.0, c, &z.0) && B::verify(&st.1, &a.1, c, &z.1)
+    }
+
+    fn simulate(st: &Self::Statement, c: Scalar, rng: &mut SimpleRng) -> (Self::Commitment, Self::Response) {
+        let (ca, za) = A::simulate(&st.0, c, rng);
+        let (cb, zb) = B::simulate(&st.1, c, rng);
+        (Pair(ca, cb), Pair(za, zb))
+    }
+}
+
+// ==========================================
+// OR 组合 (Cramer-Damgård-Schoenmakers)
+// ==========================================
+
+// 证明者只持有其中一边的见证
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+// 真实的一支正常走协议，另一支用模拟器提前伪造好 (挑战 c_sim 也是自己选的)
+pub enum OrState<A: SigmaProtocol, B: SigmaProtocol> {
+    Left { real: A::ProverState, sim_c: Scalar, sim_z: B::Response },
+    Right { real: B::ProverState, sim_c: Scalar, sim_z: A::Response },
+}
+
+// 响应里带上左边的挑战 c_left；右边的挑战由 c - c_left 推出
+#[derive(Debug, Clone)]
+pub struct OrResponse<ZA, ZB> {
+    pub c_left: Scalar,
+    pub left: ZA,
+    pub right: ZB,
+}
+
+impl<ZA: fmt::Display, ZB: fmt::Display> fmt::Display for OrResponse<ZA, ZB> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(c_left = {}, {}, {})", self.c_left, self.left, self.right)
+    }
+}
+
+pub struct Or<A, B>(PhantomData<(A, B)>);
+
+impl<A: SigmaProtocol, B: SigmaProtocol> SigmaProtocol for Or<A, B> {
+    const NAME: &'static str = "OR 组合";
+
+    type Statement = (A::Statement, B::Statement);
+    type Witness = Either<A::Witness, B::Witness>;
+    type ProverState = OrState<A, B>;
+    type Commitment = Pair<A::Commitment, B::Commitment>;
+    type Response = OrResponse<A::Response, B::Response>;
+
+    fn commit(st: &Self::Statement, w: &Self::Witness, rng: &mut SimpleRng) -> (Self::ProverState, Self::Commitment) {
+        let sim_c = random_scalar(rng);
+        match w {
+            Either::Left(wa) => {
+                let (real, ca) = A::commit(&st.0, wa, rng);
+                let (cb, sim_z) = B::simulate(&st.1, sim_c, rng);
+                (OrState::Left { real, sim_c, sim_z }, Pair(ca, cb))
+            }
+            Either::Right(wb) => {
+                let (ca, sim_z) = A::simulate(&st.0, sim_c, rng);
+                let (real, cb) = B::commit(&st.1, wb, rng);
+                (OrState::Right { real, sim_c, sim_z }, Pair(ca, cb))
+            }
+        }
+    }
+
+    // 验证者的挑战 c 到来后：真实那一支的挑战 = c - c_sim
+    // 证明者只能"预先选定"其中一支的挑战，另一支被 c 锁死 —— 所以至少有一支必须是真的
+    fn respond(st: &Self::Statement, w: &Self::Witness, state: Self::ProverState, c: Scalar) -> Self::Response {
+        match (w, state) {
+            (Either::Left(wa), OrState::Left { real, sim_c, sim_z }) => {
+                let c_left = c - sim_c;
+                OrResponse { c_left, left: A::respond(&st.0, wa, real, c_left), right: sim_z }
+            }
+            (Either::Right(wb), OrState::Right { real, sim_c, sim_z }) => {
+                let c_right = c - sim_c;
+                OrResponse { c_left: sim_c, left: sim_z, right: B::respond(&st.1, wb, real, c_right) }
+            }
+            _ => panic!("OR prover state does not match the witness branch"),
+        }
+    }
+
+    fn verify(st: &Self::Statement, a: &Self::Commitment, c: Scalar, z: &Self::Response) -> bool {
+        let c_right = c - z.c_left;
+        A::verify(&st.0, &a.0, z.c_left, &z.left) && B::verify(&st.1, &a.1, c_right, &z.right)
+    }
+
+    fn simulate(st: &Self::Statement, c: Scalar, rng: &mut SimpleRng) -> (Self::Commitment, Self::Response) {
+        let c_left = random_scalar(rng);
+        let (ca, za) = A::simulate(&st.0, c_left, rng);
+        let (cb, zb) = B::simulate(&st.1, c - c_left, rng);
+        (Pair(ca, cb), OrResponse { c_left, left: za, right: zb })
+    }
+}
@@ -0,0 +1,144 @@
+// src/s05_zk_lab/sigma/dlog.rs
+use std::fmt;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::crypto::schnorr::verify_response;
+use crate::s05_zk_lab::math::group::{random_scalar, GroupElement, Scalar};
+
+use super::{Pair, SigmaProtocol};
+
+// ==========================================
+// 1. 离散对数知识证明 (就是 ex09 的 Schnorr 身份认证)
+//    陈述：P = g^x      见证：x
+// ==========================================
+pub struct DlogKnowledge;
+
+impl SigmaProtocol for DlogKnowledge {
+    const NAME: &'static str = "DLOG: 我知道 x 使得 P = g^x";
+
+    type Statement = GroupElement;
+    type Witness = Scalar;
+    type ProverState = Scalar;
+    type Commitment = GroupElement;
+    type Response = Scalar;
+
+    fn commit(_: &GroupElement, _: &Scalar, rng: &mut SimpleRng) -> (Scalar, GroupElement) {
+        let k = random_scalar(rng);
+        (k, GroupElement::generator().pow(k))
+    }
+
+    fn respond(_: &GroupElement, x: &Scalar, k: Scalar, c: Scalar) -> Scalar {
+        k + c * *x
+    }
+
+    fn verify(public: &GroupElement, a: &GroupElement, c: Scalar, z: &Scalar) -> bool {
+        verify_response(*public, *a, c, *z)
+    }
+
+    // a = g^z * P^(-c)
+    fn simulate(public: &GroupElement, c: Scalar, rng: &mut SimpleRng) -> (GroupElement, Scalar) {
+        let z = random_scalar(rng);
+        (GroupElement::generator().pow(z) * public.pow(c).inverse(), z)
+    }
+}
+
+// ==========================================
+// 2. 离散对数相等证明 (Chaum-Pedersen, DLEQ)
+//    陈述：(g1, h1, g2, h2)      见证：x 使得 h1 = g1^x 且 h2 = g2^x
+//    用途：证明"两个公钥背后是同一把私钥"、VRF、可验证解密……
+// ==========================================
+#[derive(Debug, Clone, Copy)]
+pub struct DleqStatement {
+    pub g1: GroupElement,
+    pub h1: GroupElement,
+    pub g2: GroupElement,
+    pub h2: GroupElement,
+}
+
+impl fmt::Display for DleqStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "log_{}({}) == log_{}({})", self.g1, self.h1, self.g2, self.h2)
+    }
+}
+
+pub struct DlogEquality;
+
+impl SigmaProtocol for DlogEquality {
+    const NAME: &'static str = "DLEQ: log_g1(h1) == log_g2(h2)";
+
+    type Statement = DleqStatement;
+    type Witness = Scalar;
+    type ProverState = Scalar;
+    type Commitment = Pair<GroupElement, GroupElement>;
+    type Response = Scalar;
+
+    // 同一个 k 分别作用在两个底上：a1 = g1^k, a2 = g2^k
+    fn commit(st: &DleqStatement, _: &Scalar, rng: &mut SimpleRng) -> (Scalar, Self::Commitment) {
+        let k = random_scalar(rng);
+        (k, Pair(st.g1.pow(k), st.g2.pow(k)))
+    }
+
+    fn respond(_: &DleqStatement, x: &Scalar, k: Scalar, c: Scalar) -> Scalar {
+        k + c * *x
+    }
+
+    // 同一个 z 要同时满足两个等式：两边的 x 不一样的话，不可能用一个 z 凑出来
+    fn verify(st: &DleqStatement, a: &Self::Commitment, c: Scalar, z: &Scalar) -> bool {
+        st.g1.pow(*z) == a.0 * st.h1.pow(c) && st.g2.pow(*z) == a.1 * st.h2.pow(c)
+    }
+
+    fn simulate(st: &DleqStatement, c: Scalar, rng: &mut SimpleRng) -> (Self::Commitment, Scalar) {
+        let z = random_scalar(rng);
+        let a1 = st.g1.pow(z) * st.h1.pow(c).inverse();
+        let a2 = st.g2.pow(z) * st.h2.pow(c).inverse();
+        (Pair(a1, a2), z)
+    }
+}
+
+// ==========================================
+// 3. 任意底的离散对数知识
+//    陈述：(h, P)      见证：x 使得 P = h^x
+//    Pedersen 承诺 C = g^v * h^r 在 v 已知时，"我能打开 C" 就是 "我知道 log_h(C / g^v)"
+//    (范围证明的每一位都靠它：见 range_proof.rs)
+// ==========================================
+#[derive(Debug, Clone, Copy)]
+pub struct BaseDlogStatement {
+    pub base: GroupElement,
+    pub public: GroupElement,
+}
+
+impl fmt::Display for BaseDlogStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}^x", self.public, self.base)
+    }
+}
+
+pub struct BaseDlogKnowledge;
+
+impl SigmaProtocol for BaseDlogKnowledge {
+    const NAME: &'static str = "DLOG (任意底): 我知道 x 使得 P = h^x";
+
+    type Statement = BaseDlogStatement;
+    type Witness = Scalar;
+    type ProverState = Scalar;
+    type Commitment = GroupElement;
+    type Response = Scalar;
+
+    fn commit(st: &BaseDlogStatement, _: &Scalar, rng: &mut SimpleRng) -> (Scalar, GroupElement) {
+        let k = random_scalar(rng);
+        (k, st.base.pow(k))
+    }
+
+    fn respond(_: &BaseDlogStatement, x: &Scalar, k: Scalar, c: Scalar) -> Scalar {
+        k + c * *x
+    }
+
+    fn verify(st: &BaseDlogStatement, a: &GroupElement, c: Scalar, z: &Scalar) -> bool {
+        st.base.pow(*z) == *a * st.public.pow(c)
+    }
+
+    fn simulate(st: &BaseDlogStatement, c: Scalar, rng: &mut SimpleRng) -> (GroupElement, Scalar) {
+        let z = random_scalar(rng);
+        (st.base.pow(z) * st.public.pow(c).inverse(), z)
+    }
+}
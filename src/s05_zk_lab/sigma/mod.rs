@@ -0,0 +1,99 @@
+// src/s05_zk_lab/sigma/mod.rs
+use std::fmt;
+
+use crate::common::rng::SimpleRng;
+
+use super::math::group::{random_scalar, Scalar};
+use super::transcript::session;
+
+pub mod compose;
+pub mod dlog;
+
+/*
+Sigma 协议 (Σ-protocol)：三步走的交互式证明
+
+    证明者 P                         验证者 V
+        ----  承诺 a (commit)   ---->
+        <---  挑战 c (challenge) ----
+        ----  响应 z (respond)  ---->
+                                     verify(a, c, z)
+
+画出来像希腊字母 Σ，因此得名。Schnorr (ex09) 就是最经典的一个。
+
+把它抽象成 trait 之后：
+    - 新协议只需要实现这 5 个函数，通用的运行器 / AND / OR 组合自动可用
+    - simulate 是"零知识"的数学定义：不用见证，给定挑战就能造出合法的 (a, z)
+      OR 组合正是靠它来伪造"不知道的那一支"
+
+所有函数都是关联函数 (没有 self)：协议本身不带状态，
+状态放在 Statement (公开陈述)、Witness (秘密见证)、ProverState (两步之间的随机数) 里。
+*/
+
+pub trait SigmaProtocol {
+    const NAME: &'static str;
+
+    type Statement;
+    type Witness;
+    // 承诺与响应之间要记住的东西 (通常是随机数 k)，respond 会把它吃掉 (move)
+    type ProverState;
+    type Commitment: fmt::Display;
+    type Response: fmt::Display;
+
+    // 第 1 步 (P)
+    fn commit(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut SimpleRng,
+    ) -> (Self::ProverState, Self::Commitment);
+
+    // 第 2 步 (V)：默认就是均匀随机的标量
+    fn challenge(rng: &mut SimpleRng) -> Scalar {
+        random_scalar(rng)
+    }
+
+    // 第 3 步 (P)
+    fn respond(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::ProverState,
+        challenge: Scalar,
+    ) -> Self::Response;
+
+    // V 的最终判定
+    fn verify(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: Scalar,
+        response: &Self::Response,
+    ) -> bool;
+
+    // 模拟器：先有挑战，再倒推承诺 —— 不需要 witness
+    fn simulate(
+        statement: &Self::Statement,
+        challenge: Scalar,
+        rng: &mut SimpleRng,
+    ) -> (Self::Commitment, Self::Response);
+}
+
+// 两个消息并排放在一起 (AND 组合、DLEQ 的双承诺都用得到)
+// 单独定义而不是用元组，是为了能实现 Display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pair<X, Y>(pub X, pub Y);
+
+impl<X: fmt::Display, Y: fmt::Display> fmt::Display for Pair<X, Y> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+// 通用运行器：任何实现了 SigmaProtocol 的协议都能用它跑一遍完整会话，
+// 每一步都记进 transcript，打印格式与其他交互式协议 (ex09、ex13) 一致
+pub fn run_protocol<P: SigmaProtocol>(statement: &P::Statement, witness: &P::Witness, rng: &mut SimpleRng) -> bool {
+    println!("  [{}]", P::NAME);
+    let (prover, verifier) = session(P::NAME, true);
+    let (state, commitment) = P::commit(statement, witness, rng);
+    let commitment = prover.send("承诺 a", commitment);
+    let challenge = verifier.send("挑战 c", P::challenge(rng));
+    let response = prover.send("响应 z", P::respond(statement, witness, state, challenge));
+    verifier.check("verify(a, c, z)", P::verify(statement, &commitment, challenge, &response))
+}
@@ -0,0 +1,165 @@
+// src/s05_zk_lab/snark.rs
+use super::circuit::r1cs::{eval_lc, R1cs};
+use super::commitments::kzg::{KzgCommitment, KzgProof, ScalarPoly, Srs};
+use super::hash::sha256;
+use super::math::group::{Scalar, GROUP_ORDER};
+
+/*
+玩具 SNARK 流水线：把前面的积木串起来
+
+    电路 (ex11)  ->  R1CS (ex11)  ->  QAP 多项式编码 (本文件)  ->  KZG 承诺 (ex14)  ->  随机点验证
+
+QAP (Quadratic Arithmetic Program)：
+    R1CS 有 m 条约束，第 i 条是 <A_i, z> * <B_i, z> = <C_i, z>。
+    选 m 个点 x = 1..m，插值出 A(x) 使得 A(i) = <A_i, z>，B(x)、C(x) 同理。
+    "全部约束都满足"  <=>  A(x)B(x) - C(x) 在 1..m 上都为 0
+                     <=>  A(x)B(x) - C(x) = H(x) * Z(x)，其中 Z(x) = (x-1)(x-2)...(x-m)
+    m 个检查被压缩成了【一个】多项式整除关系，而多项式等式只需在一个随机点 s 上检查。
+
+公开输入的绑定：
+    A(x) 拆成 A_pub(x) + A_priv(x)，证明者只承诺私有部分，
+    公开部分由验证者根据公开输入自己插值计算 —— 换一个公开输入，验证就会失败。
+
+⚠️ 与真实 SNARK (Groth16 / PLONK) 的差距：
+    1. 没有检查 A_priv 真的是"同一组 z 的线性组合"：恶意证明者可以随便挑三个多项式凑出整除关系。
+       Groth16 用 SRS 里的 α、β 项强制这一点，PLONK 用置换论证 (copy constraint)。
+    2. 没有零知识：A(s)、B(s) 等取值直接暴露，真实系统会加随机盲化项。
+    3. KZG 的配对是模拟的 (见 commitments/kzg.rs)。
+*/
+
+// 第 i 条约束对应求值点 x = i + 1 (避开 0，教学上更直观)
+fn domain(m: usize) -> Vec<Scalar> {
+    (1..=m as u64).map(Scalar::new).collect()
+}
+
+// Z(x) = Π (x - i)
+pub fn vanishing_poly(m: usize) -> ScalarPoly {
+    domain(m).iter().fold(ScalarPoly::new(vec![Scalar::one()]), |acc, &i| {
+        &acc * &ScalarPoly::new(vec![-i, Scalar::one()])
+    })
+}
+
+// 把 R1CS 的三个矩阵 (只保留 keep(var) 为 true 的变量) 按行求值并插值成 (A, B, C)
+pub fn encode(r1cs: &R1cs<GROUP_ORDER>, z: &[Scalar], keep: impl Fn(usize) -> bool) -> [ScalarPoly; 3] {
+    let masked: Vec<Scalar> = z
+        .iter()
+        .enumerate()
+        .map(|(var, &v)| if keep(var) { v } else { Scalar::zero() })
+        .collect();
+    let xs = domain(r1cs.constraints.len());
+    let interpolate_rows = |row: &dyn Fn(usize) -> Scalar| {
+        let points: Vec<(Scalar, Scalar)> = xs.iter().enumerate().map(|(i, &x)| (x, row(i))).collect();
+        ScalarPoly::interpolate(&points).expect("domain points are distinct")
+    };
+    [
+        interpolate_rows(&|i| eval_lc(&r1cs.constraints[i].a, &masked)),
+        interpolate_rows(&|i| eval_lc(&r1cs.constraints[i].b, &masked)),
+        interpolate_rows(&|i| eval_lc(&r1cs.constraints[i].c, &masked)),
+    ]
+}
+
+fn is_public(r1cs: &R1cs<GROUP_ORDER>, var: usize) -> bool {
+    var == 0 || r1cs.public.contains(&var)
+}
+
+// 依次除以 (x - i)，任何一步有余数都说明某条约束不满足
+pub fn divide_by_vanishing(poly: &ScalarPoly, m: usize) -> Option<ScalarPoly> {
+    let mut quotient = poly.clone();
+    for x in domain(m) {
+        let (q, remainder) = quotient.divide_by_linear(x);
+        if !remainder.is_zero() {
+            return None;
+        }
+        quotient = q;
+    }
+    Some(quotient)
+}
+
+#[derive(Debug, Clone)]
+pub struct ToyProof {
+    // 对 A_priv、B_priv、C_priv、H 的承诺
+    pub commitments: [KzgCommitment; 4],
+    // 它们在挑战点 s 上的取值以及 KZG 打开证明
+    pub evals: [Scalar; 4],
+    pub openings: [KzgProof; 4],
+}
+
+// Fiat-Shamir：挑战点 s = H(四个承诺)，证明者在承诺之前无法预知 s
+fn challenge(commitments: &[KzgCommitment; 4]) -> Scalar {
+    let bytes: Vec<u8> = commitments.iter().flat_map(|c| c.0.value().to_be_bytes()).collect();
+    let digest = sha256(&bytes);
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&digest[..8]);
+    Scalar::new(u64::from_be_bytes(buf))
+}
+
+const NAMES: [&str; 4] = ["A_priv", "B_priv", "C_priv", "H"];
+
+// 证明者：z 是完整的见证向量 (ex12 的 generate_witness 产物)
+pub fn prove(srs: &Srs, r1cs: &R1cs<GROUP_ORDER>, z: &[Scalar]) -> Option<ToyProof> {
+    let m = r1cs.constraints.len();
+    println!("  [prove 1/4] QAP 编码：{} 条约束 -> 在 x = 1..{} 上插值", m, m);
+    let [a, b, c] = encode(r1cs, z, |_| true);
+    println!("    A(x) = {}", a);
+    println!("    B(x) = {}", b);
+    println!("    C(x) = {}", c);
+
+    let z_poly = vanishing_poly(m);
+    let p = &(&a * &b) - &c;
+    println!("  [prove 2/4] P(x) = A(x)B(x) - C(x) 除以 Z(x) = {}", z_poly);
+    let Some(h) = divide_by_vanishing(&p, m) else {
+        println!("    ❌ P(x) 不能被 Z(x) 整除：见证不满足 R1CS，无法生成证明");
+        return None;
+    };
+    println!("    H(x) = {}", h);
+
+    let [a_priv, b_priv, c_priv] = encode(r1cs, z, |var| !is_public(r1cs, var));
+    let polys = [a_priv, b_priv, c_priv, h];
+    let commitments = polys.clone().map(|poly| srs.commit(&poly).expect("SRS is large enough"));
+    println!("  [prove 3/4] KZG 承诺");
+    for (name, com) in NAMES.iter().zip(&commitments) {
+        println!("    Com({}) = {}", name, com.0);
+    }
+
+    let s = challenge(&commitments);
+    let mut evals = [Scalar::zero(); 4];
+    let mut openings = [KzgProof(srs.powers[0]); 4];
+    for (i, poly) in polys.iter().enumerate() {
+        let (y, pi) = srs.open(poly, s).expect("SRS is large enough");
+        evals[i] = y;
+        openings[i] = pi;
+    }
+    println!("  [prove 4/4] Fiat-Shamir 挑战 s = {}，在 s 处打开: {:?}", s, evals.map(|e| e.value()));
+    Some(ToyProof { commitments, evals, openings })
+}
+
+// 验证者：只知道 R1CS (电路)、公开输入的值和证明
+pub fn verify(srs: &Srs, r1cs: &R1cs<GROUP_ORDER>, public_values: &[Scalar], proof: &ToyProof) -> bool {
+    let m = r1cs.constraints.len();
+    let s = challenge(&proof.commitments);
+    println!("  [verify 1/3] 重算挑战 s = {}", s);
+
+    for (i, name) in NAMES.iter().enumerate() {
+        if !srs.verify(&proof.commitments[i], s, proof.evals[i], &proof.openings[i]) {
+            println!("    ❌ {} 的 KZG 打开证明无效", name);
+            return false;
+        }
+    }
+    println!("  [verify 2/3] 4 个 KZG 打开全部有效");
+
+    // 用公开输入自己构造公开部分的 z，其余位置填 0
+    let mut z_pub = vec![Scalar::zero(); r1cs.num_vars];
+    z_pub[0] = Scalar::one();
+    for (&var, &value) in r1cs.public.iter().zip(public_values) {
+        z_pub[var] = value;
+    }
+    let [a_pub, b_pub, c_pub] = encode(r1cs, &z_pub, |var| is_public(r1cs, var));
+    let a = a_pub.evaluate(s) + proof.evals[0];
+    let b = b_pub.evaluate(s) + proof.evals[1];
+    let c = c_pub.evaluate(s) + proof.evals[2];
+    let lhs = a * b - c;
+    let rhs = proof.evals[3] * vanishing_poly(m).evaluate(s);
+    let ok = lhs == rhs;
+    println!("  [verify 3/3] A(s)B(s) - C(s) = {}  vs  H(s)Z(s) = {}  {}", lhs, rhs, if ok { "✅" } else { "❌" });
+    ok
+}
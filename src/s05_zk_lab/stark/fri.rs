@@ -0,0 +1,145 @@
+// src/s05_zk_lab/stark/fri.rs
+use super::Channel;
+use crate::s05_zk_lab::ex01_merkle::{MerkleProof, MerkleTree};
+use crate::s05_zk_lab::math::field::F97;
+
+/*
+FRI (Fast Reed-Solomon IOP of Proximity)：证明"这张表是某个低次多项式的取值"
+
+    把 f(x) 拆成偶次项和奇次项：f(x) = f_e(x^2) + x * f_o(x^2)
+    验证者给一个随机数 β，折叠出 f'(y) = f_e(y) + β * f_o(y)，次数减半，定义域也减半 (y = x^2)
+    用 x 和 -x 两个点就能算出 f'(x^2)，不需要知道系数：
+        f_e(x^2) = (f(x) + f(-x)) / 2
+        f_o(x^2) = (f(x) - f(-x)) / (2x)
+
+    次数 < d 的多项式折叠 log2(d) 次后变成常数；
+    如果原来的表离任何低次多项式都很远，折叠后大概率仍然"很远"，最后一层就不是常数。
+
+定义域：陪集 D = {h * ω^j}，|D| 是 2 的幂。x 和 -x 在表里相隔 |D| / 2 (因为 ω^(|D|/2) = -1)。
+*/
+
+// 一层折叠：证明者手里的完整取值表和对它的 Merkle 承诺
+pub struct FriLayer {
+    pub evals: Vec<F97>,
+    pub tree: MerkleTree,
+}
+
+// 证明者的全部 FRI 数据
+pub struct FriCommitment {
+    pub layers: Vec<FriLayer>,
+    pub final_value: F97,
+}
+
+// 一次查询：每层打开一对 (x, -x)，连同 Merkle 证明
+#[derive(Debug, Clone)]
+pub struct FriQuery {
+    pub layers: Vec<[(F97, MerkleProof); 2]>,
+}
+
+pub fn commit_values(evals: &[F97]) -> MerkleTree {
+    MerkleTree::new_iterative(evals.iter().map(|v| v.to_string()).collect())
+}
+
+fn fold_pair(a: F97, b: F97, x: F97, beta: F97) -> F97 {
+    let two = F97::new(2);
+    (a + b) / two + beta * (a - b) / (two * x)
+}
+
+// f 在 {offset * gen^j} 上的取值表 -> f' 在 {offset^2 * gen^(2j)} 上的取值表
+fn fold(evals: &[F97], offset: F97, gen: F97, beta: F97) -> Vec<F97> {
+    let half = evals.len() / 2;
+    (0..half)
+        .map(|j| fold_pair(evals[j], evals[j + half], offset * gen.pow(j as u64), beta))
+        .collect()
+}
+
+// 证明者：逐层承诺 -> 从 channel 取 β -> 折叠，直到次数上界降到 1 (常数)
+pub fn commit(evals: Vec<F97>, degree_bound: usize, offset: F97, gen: F97, channel: &mut Channel) -> FriCommitment {
+    let mut layers = Vec::new();
+    let (mut evals, mut bound, mut offset, mut gen) = (evals, degree_bound, offset, gen);
+    while bound > 1 {
+        let tree = commit_values(&evals);
+        channel.absorb(&tree.root_hash());
+        let beta = channel.challenge();
+        let next = fold(&evals, offset, gen, beta);
+        layers.push(FriLayer { evals, tree });
+        evals = next;
+        bound /= 2;
+        offset = offset * offset;
+        gen = gen * gen;
+    }
+    // 诚实的证明者这里所有值都相同；作弊者只能发一个，其他位置会在查询时露馅
+    let final_value = evals[0];
+    channel.absorb(&final_value.to_string());
+    FriCommitment { layers, final_value }
+}
+
+impl FriCommitment {
+    pub fn roots(&self) -> Vec<String> {
+        self.layers.iter().map(|layer| layer.tree.root_hash()).collect()
+    }
+
+    // 第 0 层的 index 决定了之后每一层打开的位置：j = index mod (|D_k| / 2)
+    pub fn query(&self, index: usize) -> FriQuery {
+        let layers = self
+            .layers
+            .iter()
+            .map(|layer| {
+                let half = layer.evals.len() / 2;
+                let j = index % half;
+                [j, j + half].map(|i| (layer.evals[i], layer.tree.prove(i).expect("index within layer")))
+            })
+            .collect();
+        FriQuery { layers }
+    }
+}
+
+// 验证者重放 channel 得到的 FRI 参数
+pub struct FriParams<'a> {
+    pub roots: &'a [String],
+    pub betas: &'a [F97],
+    pub final_value: F97,
+    pub offset: F97,
+    pub gen: F97,
+    pub domain_size: usize,
+}
+
+// 检查一次查询：Merkle 证明有效 + 相邻两层折叠一致 + 最后落到常数上
+// 返回第 0 层在 index 处的取值 (调用者还要拿它和组合多项式比对)
+pub fn verify_query(params: &FriParams, index: usize, query: &FriQuery) -> Result<F97, String> {
+    if query.layers.len() != params.roots.len() {
+        return Err(format!("FRI 层数不对：期望 {}，收到 {}", params.roots.len(), query.layers.len()));
+    }
+    let (mut offset, mut gen, mut size) = (params.offset, params.gen, params.domain_size);
+    let mut first = None;
+    for (k, pair) in query.layers.iter().enumerate() {
+        let half = size / 2;
+        let j = index % half;
+        for (slot, (value, proof)) in pair.iter().enumerate() {
+            let expected_index = j + slot * half;
+            if proof.index != expected_index || !proof.verify(&value.to_string(), &params.roots[k]) {
+                return Err(format!("第 {} 层位置 {} 的 Merkle 证明无效", k, expected_index));
+            }
+        }
+        let (a, b) = (pair[0].0, pair[1].0);
+        if k == 0 {
+            first = Some(if index < half { a } else { b });
+        }
+
+        let folded = fold_pair(a, b, offset * gen.pow(j as u64), params.betas[k]);
+        // 下一层打开的一对里，j 要么是第一个位置，要么是第二个
+        let expected = match query.layers.get(k + 1) {
+            Some(next) => {
+                if j < half / 2 { next[0].0 } else { next[1].0 }
+            }
+            None => params.final_value,
+        };
+        if folded != expected {
+            return Err(format!("第 {} 层折叠结果 {} 与下一层的 {} 不一致", k, folded, expected));
+        }
+        offset = offset * offset;
+        gen = gen * gen;
+        size = half;
+    }
+    first.ok_or_else(|| String::from("没有任何 FRI 层"))
+}
@@ -0,0 +1,250 @@
+// src/s05_zk_lab/stark/mod.rs
+use super::ex01_merkle::MerkleProof;
+use super::hash::{sha256, Digest};
+use super::math::field::F97;
+use super::math::poly::Polynomial;
+
+pub mod fri;
+
+use fri::{FriParams, FriQuery};
+
+/*
+玩具 STARK：证明"斐波那契数列第 8 项是 21"，不需要可信设置，只用到哈希 (Merkle 树)
+
+算术化 (Arithmetization)：
+    执行轨迹 a_0 .. a_7 = 1, 1, 2, 3, 5, 8, 13, 21
+    取 F_97 里阶为 8 的 g，插值出轨迹多项式 f(g^i) = a_i
+    边界约束：f(1) = 1，f(g) = 1，f(g^7) = 21
+    转移约束：f(g^2 x) - f(g x) - f(x) = 0 对 x = g^0 .. g^5 成立
+
+    "约束在某些点上成立"  <=>  "约束多项式能被这些点的消失多项式整除"，于是得到 4 个商：
+        q0 = (f(x) - 1) / (x - 1)
+        q1 = (f(x) - 1) / (x - g)
+        q2 = (f(x) - 21) / (x - g^7)
+        q3 = (f(g^2 x) - f(g x) - f(x)) * (x - g^6)(x - g^7) / (x^8 - 1)
+    约束全部满足时它们都是低次多项式；否则至少有一个根本不是多项式。
+    用随机系数把它们合成一个组合多项式 CP = Σ α_i q_i，只需对 CP 做一次低次测试 (FRI)。
+
+低次扩展 (LDE)：
+    在 8 个点上插值，却在陪集 D = {5 * ω^j : j = 0..31} 上求值 (ω 的阶为 32，blowup = 4)。
+    D 与轨迹域不相交，所以商多项式在 D 上处处可以直接用除法计算；
+    查询落在 D 上，验证者看到的点不会直接暴露轨迹本身。
+
+与 ex15 的 SNARK 对比：
+    没有可信设置、没有群运算 —— 只有哈希和域运算，理论上抗量子。
+    代价是证明更大：每次查询都要附带几条 Merkle 路径。
+*/
+
+pub const TRACE_LEN: usize = 8;
+pub const BLOWUP: usize = 4;
+pub const LDE_SIZE: usize = TRACE_LEN * BLOWUP;
+pub const NUM_QUERIES: usize = 4;
+// 5 是 F_97 的原根，用它做陪集偏移
+const COSET_OFFSET: u64 = 5;
+
+// F_97^* 的阶是 96 = 2^5 * 3，所以 2 的幂阶子群最大到 32
+pub fn root_of_unity(order: usize) -> F97 {
+    F97::new(COSET_OFFSET).pow(96 / order as u64)
+}
+
+pub fn fibonacci_trace(a0: u64, a1: u64) -> Vec<F97> {
+    let mut trace = vec![F97::new(a0), F97::new(a1)];
+    while trace.len() < TRACE_LEN {
+        let n = trace.len();
+        trace.push(trace[n - 1] + trace[n - 2]);
+    }
+    trace
+}
+
+// ==========================================
+// Fiat-Shamir 信道
+// ==========================================
+// 证明者和验证者按同样的顺序 absorb 承诺，就会得到同样的"随机"挑战
+pub struct Channel {
+    state: Digest,
+}
+
+impl Channel {
+    pub fn new() -> Self {
+        Channel { state: sha256(b"rust-zk-lab/toy-stark") }
+    }
+
+    pub fn absorb(&mut self, data: &str) {
+        let mut buf = self.state.to_vec();
+        buf.extend_from_slice(data.as_bytes());
+        self.state = sha256(&buf);
+    }
+
+    pub fn challenge(&mut self) -> F97 {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&self.state[..8]);
+        self.absorb("challenge"); // 推进状态，下一次挑战不会重复
+        F97::new(u64::from_be_bytes(buf))
+    }
+
+    pub fn challenge_index(&mut self, bound: usize) -> usize {
+        self.challenge().value() as usize % bound
+    }
+}
+
+impl Default for Channel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ==========================================
+// 证明结构
+// ==========================================
+#[derive(Debug, Clone)]
+pub struct TraceOpening {
+    // f(x)、f(g x)、f(g^2 x) 三个位置的值和 Merkle 证明
+    pub values: [(F97, MerkleProof); 3],
+}
+
+#[derive(Debug, Clone)]
+pub struct StarkProof {
+    pub claimed: F97,
+    pub trace_root: String,
+    pub fri_roots: Vec<String>,
+    pub fri_final: F97,
+    pub queries: Vec<(TraceOpening, FriQuery)>,
+}
+
+impl StarkProof {
+    // 粗略的证明大小：一共包含多少个哈希
+    pub fn hash_count(&self) -> usize {
+        let per_query: usize = self
+            .queries
+            .iter()
+            .map(|(trace, fri)| {
+                trace.values.iter().map(|(_, p)| p.siblings.len()).sum::<usize>()
+                    + fri.layers.iter().flatten().map(|(_, p)| p.siblings.len()).sum::<usize>()
+            })
+            .sum();
+        1 + self.fri_roots.len() + per_query
+    }
+}
+
+// 在 D 的第 j 个点上，由 f(x)、f(gx)、f(g^2 x) 算出组合多项式的值 —— 证明者和验证者共用
+fn composition_at(x: F97, f: [F97; 3], claimed: F97, alphas: &[F97; 4]) -> F97 {
+    let g = root_of_unity(TRACE_LEN);
+    let one = F97::one();
+    let q0 = (f[0] - one) / (x - one);
+    let q1 = (f[0] - one) / (x - g);
+    let q2 = (f[0] - claimed) / (x - g.pow(7));
+    let q3 = (f[2] - f[1] - f[0]) * (x - g.pow(6)) * (x - g.pow(7)) / (x.pow(TRACE_LEN as u64) - one);
+    alphas[0] * q0 + alphas[1] * q1 + alphas[2] * q2 + alphas[3] * q3
+}
+
+fn lde_point(j: usize) -> F97 {
+    F97::new(COSET_OFFSET) * root_of_unity(LDE_SIZE).pow(j as u64)
+}
+
+// 在 D 上 x 的下标是 j，g x 的下标就是 j + BLOWUP (g = ω^BLOWUP)
+fn shifted(j: usize, steps: usize) -> usize {
+    (j + steps * BLOWUP) % LDE_SIZE
+}
+
+fn draw_alphas(channel: &mut Channel) -> [F97; 4] {
+    [(); 4].map(|_| channel.challenge())
+}
+
+// 证明者：trace 是完整的执行轨迹，claimed 是要公开的最后一项
+pub fn prove(trace: &[F97], claimed: F97) -> StarkProof {
+    let g = root_of_unity(TRACE_LEN);
+    let mut channel = Channel::new();
+    channel.absorb(&claimed.to_string());
+
+    // [1] 轨迹多项式 + 低次扩展
+    let points: Vec<(F97, F97)> = trace.iter().enumerate().map(|(i, &a)| (g.pow(i as u64), a)).collect();
+    let f = Polynomial::interpolate(&points).expect("trace domain points are distinct");
+    let lde: Vec<F97> = (0..LDE_SIZE).map(|j| f.evaluate(lde_point(j))).collect();
+    let trace_tree = fri::commit_values(&lde);
+    println!("  [prove 1/4] 轨迹多项式 f(x) = {}", f);
+    println!("    在 {} 个点上做低次扩展，Merkle 根 = {}", LDE_SIZE, trace_tree.root_hash());
+    channel.absorb(&trace_tree.root_hash());
+
+    // [2] 组合多项式
+    let alphas = draw_alphas(&mut channel);
+    let cp: Vec<F97> = (0..LDE_SIZE)
+        .map(|j| composition_at(lde_point(j), [lde[j], lde[shifted(j, 1)], lde[shifted(j, 2)]], claimed, &alphas))
+        .collect();
+    println!("  [prove 2/4] 随机系数 α = {:?}，组合多项式 CP 在 D 上求值", alphas.map(|a| a.value()));
+
+    // [3] FRI
+    let fri = fri::commit(cp, TRACE_LEN, F97::new(COSET_OFFSET), root_of_unity(LDE_SIZE), &mut channel);
+    println!("  [prove 3/4] FRI：{} 层折叠，最后的常数 = {}", fri.layers.len(), fri.final_value);
+    for (k, layer) in fri.layers.iter().enumerate() {
+        println!("    第 {} 层：{} 个值，根 = {}", k, layer.evals.len(), layer.tree.root_hash());
+    }
+
+    // [4] 查询
+    let queries = (0..NUM_QUERIES)
+        .map(|_| {
+            let j = channel.challenge_index(LDE_SIZE);
+            let values = [0, 1, 2].map(|s| {
+                let i = shifted(j, s);
+                (lde[i], trace_tree.prove(i).expect("index within LDE"))
+            });
+            (TraceOpening { values }, fri.query(j))
+        })
+        .collect::<Vec<_>>();
+    let indices: Vec<usize> = queries.iter().map(|(t, _)| t.values[0].1.index).collect();
+    println!("  [prove 4/4] Fiat-Shamir 抽出查询位置 {:?}", indices);
+
+    StarkProof {
+        claimed,
+        trace_root: trace_tree.root_hash(),
+        fri_roots: fri.roots(),
+        fri_final: fri.final_value,
+        queries,
+    }
+}
+
+// 验证者：只看证明本身，重放 channel 得到同样的挑战
+pub fn verify(proof: &StarkProof) -> Result<(), String> {
+    let mut channel = Channel::new();
+    channel.absorb(&proof.claimed.to_string());
+    channel.absorb(&proof.trace_root);
+    let alphas = draw_alphas(&mut channel);
+    let betas: Vec<F97> = proof
+        .fri_roots
+        .iter()
+        .map(|root| {
+            channel.absorb(root);
+            channel.challenge()
+        })
+        .collect();
+    channel.absorb(&proof.fri_final.to_string());
+
+    let params = FriParams {
+        roots: &proof.fri_roots,
+        betas: &betas,
+        final_value: proof.fri_final,
+        offset: F97::new(COSET_OFFSET),
+        gen: root_of_unity(LDE_SIZE),
+        domain_size: LDE_SIZE,
+    };
+    if proof.queries.len() != NUM_QUERIES {
+        return Err(format!("查询次数不对：期望 {}，收到 {}", NUM_QUERIES, proof.queries.len()));
+    }
+    for (trace, fri_query) in &proof.queries {
+        let j = channel.challenge_index(LDE_SIZE);
+        for (s, (value, merkle)) in trace.values.iter().enumerate() {
+            let i = shifted(j, s);
+            if merkle.index != i || !merkle.verify(&value.to_string(), &proof.trace_root) {
+                return Err(format!("轨迹在位置 {} 的 Merkle 证明无效", i));
+            }
+        }
+        let f = trace.values.clone().map(|(v, _)| v);
+        let expected = composition_at(lde_point(j), f, proof.claimed, &alphas);
+        let cp = fri::verify_query(&params, j, fri_query)?;
+        if cp != expected {
+            return Err(format!("位置 {}：由轨迹算出的 CP = {}，FRI 第 0 层却是 {}", j, expected, cp));
+        }
+        println!("    查询 {:>2}: f = {:?}，CP = {}，FRI 折叠一致 ✅", j, f.map(|v| v.value()), cp);
+    }
+    Ok(())
+}
+
@@ -0,0 +1,92 @@
+// src/s05_zk_lab/streaming_merkle.rs
+use std::mem;
+
+use super::ex01_merkle::{Hasher, MerkleLeaf, PrefixedHasher};
+
+// ==========================================
+// 流式计算 Merkle 根
+// ==========================================
+// 叶子一片一片地喂进来，只保留每一层"还没等到右兄弟"的那个节点 (山峰)，最多 log2(n) + 1 个。
+// 喂叶子的过程和 ex03 MMR 的 push 一样是二进制 +1：本层已有山峰就合并、进位到上一层。
+// 和 MMR 不同的是 finalize 不"装袋"，而是按 ex01 的奇数规则 (落单的节点和自己配对) 收尾，
+// 所以算出的根和 MerkleTree::with_hasher 建整棵树得到的根完全相同。
+pub struct StreamingMerkleHasher<H: Hasher = PrefixedHasher> {
+    // peaks[h]：高度为 h、还在等右兄弟的节点哈希
+    peaks: Vec<Option<String>>,
+    count: usize,
+    hasher: H,
+}
+
+impl StreamingMerkleHasher {
+    pub fn new() -> Self {
+        Self::with_hasher(PrefixedHasher)
+    }
+}
+
+impl Default for StreamingMerkleHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Hasher> StreamingMerkleHasher<H> {
+    pub fn with_hasher(hasher: H) -> Self {
+        StreamingMerkleHasher { peaks: Vec::new(), count: 0, hasher }
+    }
+
+    // 叶子只被借用一下：哈希完就可以丢掉，流的来源不需要留在内存里
+    pub fn push<L: MerkleLeaf + ?Sized>(&mut self, leaf: &L) {
+        let mut carry = self.hasher.leaf(&leaf.leaf_bytes());
+        let mut h = 0;
+        // 本层已经有一个左兄弟：合并，结果进位到上一层
+        while let Some(left) = self.peaks.get_mut(h).and_then(Option::take) {
+            carry = self.hasher.node(&left, &carry);
+            h += 1;
+        }
+        if h == self.peaks.len() {
+            self.peaks.push(None);
+        }
+        self.peaks[h] = Some(carry);
+        self.count += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    // 当前保存的山峰数 = count 的二进制里 1 的个数
+    pub fn peak_count(&self) -> usize {
+        self.peaks.iter().flatten().count()
+    }
+
+    // 状态占用的堆内存：山峰数组本身 + 每个哈希字符串
+    pub fn heap_bytes(&self) -> usize {
+        self.peaks.capacity() * mem::size_of::<Option<String>>() + self.peaks.iter().flatten().map(String::capacity).sum::<usize>()
+    }
+
+    // 从最低层往上收尾：树高 = ceil(log2 n)，每一层最多只有一个落单的节点
+    //   本层有山峰、下面有进位 -> 山峰是左孩子，进位是右孩子
+    //   只有其中一个        -> 落单，和自己配对 (与 MerkleTree 的 new_promoted 相同)
+    // 空流返回空字符串，和 MerkleTree::root_hash 一致
+    pub fn finalize(self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let height = self.count.next_power_of_two().trailing_zeros() as usize;
+        let mut peaks = self.peaks;
+        let mut carry: Option<String> = None;
+        for peak in peaks.iter_mut().take(height) {
+            carry = match (peak.take(), carry) {
+                (Some(left), Some(right)) => Some(self.hasher.node(&left, &right)),
+                (Some(lone), None) | (None, Some(lone)) => Some(self.hasher.node(&lone, &lone)),
+                (None, None) => None,
+            };
+        }
+        // 叶子数是 2 的幂时整棵树就是最高的那座山峰，否则最后一次合并的结果就是根
+        carry.or_else(|| peaks.get_mut(height).and_then(Option::take)).expect("non-empty stream has a root")
+    }
+}
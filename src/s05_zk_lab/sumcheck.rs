@@ -0,0 +1,143 @@
+// src/s05_zk_lab/sumcheck.rs
+use super::math::field::Fp;
+use super::math::multilinear::MultilinearPoly;
+use super::math::poly::Polynomial;
+
+/*
+Sum-check 协议 (Lund-Fortnow-Karloff-Nisan, 1990)
+
+证明者声称：H = Σ_{b ∈ {0,1}^n} g(b)
+验证者自己算要 2^n 次求值，sum-check 把它降到 n 轮、每轮只做常数次运算，最后只在【一个】随机点上求一次 g。
+
+这里 g = f1 * f2 是两个多线性多项式的乘积 (GKR、Spartan 里的标准形态)，每个变量的次数最多为 2。
+更一般地，g 可以是若干个这样的乘积之和 Σ_k f1_k * f2_k，次数不变 (GKR 每一层是 add * (V+V) + mul * (V*V)，见 gkr.rs)。
+
+第 i 轮：
+    P：发送单变量多项式 g_i(X) = Σ_{剩余变量取布尔值} g(r1, ..., r_{i-1}, X, ...)
+    V：检查 g_i(0) + g_i(1) == 当前声明值 (第一轮就是 H)，且 deg(g_i) <= 2
+       然后随机选 r_i，新的声明值 = g_i(r_i)
+最后：V 拿到 (r1, ..., rn) 和声明值 c，只需检查 g(r1, ..., rn) == c (对 g 的一次"预言机"访问)
+*/
+
+const MAX_DEGREE: usize = 2;
+
+pub struct SumcheckProver<const P: u64> {
+    // g = Σ f1 * f2，每一项的两个表一起折半
+    terms: Vec<(MultilinearPoly<P>, MultilinearPoly<P>)>,
+    claim: Fp<P>,
+    // 作弊者押注的点：验证者恰好选中它时，谎言就"洗白"了
+    cheat_guess: Option<Fp<P>>,
+}
+
+impl<const P: u64> SumcheckProver<P> {
+    pub fn true_sum(f1: &MultilinearPoly<P>, f2: &MultilinearPoly<P>) -> Fp<P> {
+        f1.evals()
+            .iter()
+            .zip(f2.evals())
+            .fold(Fp::zero(), |acc, (&a, &b)| acc + a * b)
+    }
+
+    pub fn honest(f1: MultilinearPoly<P>, f2: MultilinearPoly<P>) -> Self {
+        Self::sum_of_products(vec![(f1, f2)])
+    }
+
+    // 所有表的变量数必须相同
+    pub fn sum_of_products(terms: Vec<(MultilinearPoly<P>, MultilinearPoly<P>)>) -> Self {
+        let claim = terms.iter().fold(Fp::zero(), |acc, (f1, f2)| acc + Self::true_sum(f1, f2));
+        SumcheckProver { terms, claim, cheat_guess: None }
+    }
+
+    // 作弊者：声称一个错误的和，每一轮都调整多项式让 g_i(0) + g_i(1) 凑上自己的声明值
+    pub fn cheating(f1: MultilinearPoly<P>, f2: MultilinearPoly<P>, claim: Fp<P>, guess: Fp<P>) -> Self {
+        SumcheckProver { terms: vec![(f1, f2)], claim, cheat_guess: Some(guess) }
+    }
+
+    pub fn claim(&self) -> Fp<P> {
+        self.claim
+    }
+
+    // 诚实的 g_i：在 X = 0, 1, 2 三个点上求和，再插值出次数 <= 2 的多项式
+    fn honest_round_poly(&self) -> Polynomial<P> {
+        let points: Vec<(Fp<P>, Fp<P>)> = (0..=MAX_DEGREE as u64)
+            .map(|t| {
+                let t = Fp::new(t);
+                let sum = self.terms.iter().fold(Fp::zero(), |acc, (f1, f2)| {
+                    let half = f1.evals().len() / 2;
+                    let (f1_lo, f1_hi) = f1.evals().split_at(half);
+                    let (f2_lo, f2_hi) = f2.evals().split_at(half);
+                    (0..half).fold(acc, |acc, i| {
+                        let a = f1_lo[i] + t * (f1_hi[i] - f1_lo[i]);
+                        let b = f2_lo[i] + t * (f2_hi[i] - f2_lo[i]);
+                        acc + a * b
+                    })
+                });
+                (t, sum)
+            })
+            .collect();
+        Polynomial::interpolate(&points).expect("0, 1, 2 are distinct")
+    }
+
+    pub fn round_poly(&self) -> Polynomial<P> {
+        let honest = self.honest_round_poly();
+        let real = honest.evaluate(Fp::zero()) + honest.evaluate(Fp::one());
+        let diff = self.claim - real;
+        let Some(guess) = self.cheat_guess.filter(|_| !diff.is_zero()) else {
+            return honest;
+        };
+        // 加上 e(X) = k * (X - a)：e(0) + e(1) = k * (1 - 2a) = diff，并且 e(a) = 0
+        // 验证者若恰好选中 r = a，新的声明值就等于真实值，之后诚实作答即可过关
+        // (a = 1/2 时 1 - 2a = 0，只能退化成常数平移，谎言永远洗不白)
+        let denom = Fp::one() - Fp::new(2) * guess;
+        let fix = if denom.is_zero() {
+            Polynomial::new(vec![diff / Fp::new(2)])
+        } else {
+            let k = diff / denom;
+            Polynomial::new(vec![-(k * guess), k])
+        };
+        &honest + &fix
+    }
+
+    // 收到挑战 r：固定第一个变量，声明值更新为刚才发出的 g_i(r)
+    pub fn receive_challenge(&mut self, sent: &Polynomial<P>, r: Fp<P>) {
+        self.claim = sent.evaluate(r);
+        for (f1, f2) in &mut self.terms {
+            *f1 = f1.fix_first_variable(r);
+            *f2 = f2.fix_first_variable(r);
+        }
+    }
+}
+
+pub struct SumcheckVerifier<const P: u64> {
+    claim: Fp<P>,
+    challenges: Vec<Fp<P>>,
+}
+
+impl<const P: u64> SumcheckVerifier<P> {
+    pub fn new(claimed_sum: Fp<P>) -> Self {
+        SumcheckVerifier { claim: claimed_sum, challenges: Vec::new() }
+    }
+
+    pub fn claim(&self) -> Fp<P> {
+        self.claim
+    }
+
+    pub fn challenges(&self) -> &[Fp<P>] {
+        &self.challenges
+    }
+
+    // 每轮的检查：次数不超标，且 g_i(0) + g_i(1) 等于当前声明值
+    pub fn check_round(&self, poly: &Polynomial<P>) -> bool {
+        let degree_ok = poly.degree().is_none_or(|d| d <= MAX_DEGREE);
+        degree_ok && poly.evaluate(Fp::zero()) + poly.evaluate(Fp::one()) == self.claim
+    }
+
+    pub fn bind(&mut self, poly: &Polynomial<P>, r: Fp<P>) {
+        self.claim = poly.evaluate(r);
+        self.challenges.push(r);
+    }
+
+    // 最终检查：唯一一次真正去碰 g 的地方
+    pub fn final_check(&self, f1: &MultilinearPoly<P>, f2: &MultilinearPoly<P>) -> bool {
+        f1.evaluate(&self.challenges) * f2.evaluate(&self.challenges) == self.claim
+    }
+}
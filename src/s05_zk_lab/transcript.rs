@@ -0,0 +1,113 @@
+// src/s05_zk_lab/transcript.rs
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/*
+交互式证明的"对话记录" (Transcript)
+
+    交互式协议的全部公开信息就是双方按顺序交换的消息。
+    零知识的定义、Fiat-Shamir 的哈希输入、审计与复现，说的都是这份记录。
+
+两个会话对象共享同一份记录：
+    ProverSession   —— 证明者只能以 P 的身份发消息
+    VerifierSession —— 验证者发挑战、记录自己的检查，最后把整份记录拿走
+    共享用的是 Rc<RefCell<Transcript>> (S03 的内部可变性)：两个会话都要往里写，但只在单线程里用。
+
+live = true 时每条消息在记录的同时就打印出来，所有协议的输出格式因此完全一致；
+live = false 时只记录 (例如批量统计时)，事后可以用 dump 整份打印。
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Party {
+    Prover,
+    Verifier,
+}
+
+#[derive(Debug, Clone)]
+enum Entry {
+    // 发给对方的消息
+    Message { from: Party, label: String, value: String },
+    // 验证者在本地做的检查 (不发给任何人，但属于协议的一部分)
+    Check { label: String, ok: bool },
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Entry::Message { from: Party::Prover, label, value } => write!(f, "P -> V  {} = {}", label, value),
+            Entry::Message { from: Party::Verifier, label, value } => write!(f, "V -> P  {} = {}", label, value),
+            Entry::Check { label, ok } => write!(f, "V 检查  {}  {}", label, if *ok { "✅" } else { "❌" }),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    protocol: String,
+    entries: Vec<Entry>,
+    live: bool,
+}
+
+impl Transcript {
+    fn record(&mut self, entry: Entry) {
+        if self.live {
+            println!("  [{:02}] {}", self.entries.len() + 1, entry);
+        }
+        self.entries.push(entry);
+    }
+
+    // 按顺序重放整份记录，格式与 live 打印相同
+    pub fn dump(&self) {
+        println!("  --- transcript: {} ({} 条) ---", self.protocol, self.entries.len());
+        for (i, entry) in self.entries.iter().enumerate() {
+            println!("  [{:02}] {}", i + 1, entry);
+        }
+    }
+}
+
+pub struct ProverSession {
+    transcript: Rc<RefCell<Transcript>>,
+}
+
+impl ProverSession {
+    // 记录一条 P -> V 的消息，并把值原样还给调用者，方便写成 let r = prover.send("R", r)
+    pub fn send<T: fmt::Display>(&self, label: &str, value: T) -> T {
+        self.transcript.borrow_mut().record(Entry::Message {
+            from: Party::Prover,
+            label: label.to_string(),
+            value: value.to_string(),
+        });
+        value
+    }
+}
+
+pub struct VerifierSession {
+    transcript: Rc<RefCell<Transcript>>,
+}
+
+impl VerifierSession {
+    pub fn send<T: fmt::Display>(&self, label: &str, value: T) -> T {
+        self.transcript.borrow_mut().record(Entry::Message {
+            from: Party::Verifier,
+            label: label.to_string(),
+            value: value.to_string(),
+        });
+        value
+    }
+
+    pub fn check(&self, label: &str, ok: bool) -> bool {
+        self.transcript.borrow_mut().record(Entry::Check { label: label.to_string(), ok });
+        ok
+    }
+
+    // 会话结束：拿走一份记录的副本 (ProverSession 可能还活着，所以是 clone 而不是 Rc::try_unwrap)
+    pub fn finish(self) -> Transcript {
+        self.transcript.borrow().clone()
+    }
+}
+
+pub fn session(protocol: &str, live: bool) -> (ProverSession, VerifierSession) {
+    let transcript = Rc::new(RefCell::new(Transcript { protocol: protocol.to_string(), entries: Vec::new(), live }));
+    (ProverSession { transcript: Rc::clone(&transcript) }, VerifierSession { transcript })
+}
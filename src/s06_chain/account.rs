@@ -0,0 +1,273 @@
+// src/s06_chain/account.rs
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::s05_zk_lab::ex01_merkle::MerkleLeaf;
+use crate::s05_zk_lab::hash::{sha256, short_hex, Digest};
+
+use super::block::Block;
+use super::gas::{self, GasMeter};
+use super::trie::PatriciaTrie;
+
+/*
+账户模型 (以太坊的记账方式)，对照 utxo.rs
+
+    状态 = 地址 -> 账户 { 余额, nonce, 存储 }。交易直接改账户：
+        nonce   : 这个账户发过多少笔交易，交易必须带上"下一个" nonce —— 防重放，也决定同一账户交易的顺序
+        storage : 账户自己的键值存储 (合约的状态就放在这里)
+    交易不是一次转账，而是一串操作 (Op)，每一步都要按 gas.rs 的价格付 gas。
+
+执行一笔交易 (execute)：
+    1. 检查：nonce 对得上、gas_limit 至少够固定开销、余额付得起 gas_limit × gas_price
+       —— 不满足的交易根本不能上链，不收费 (Err)
+    2. 预扣 gas_limit × gas_price，nonce + 1
+    3. 逐个执行 Op：先扣 gas 再执行；out of gas 或者执行失败 (比如余额不够转账) 就撤销所有 Op 的改动
+    4. 结算：没用完的 gas 退回，用掉的付给矿工 (coinbase)。2、4 步的改动不会被撤销
+
+状态根 (state_root)：地址 -> 账户编码 放进一棵 Patricia 树 (trie.rs)，树根写进区块头。
+    账户编码 = 余额 + nonce + 存储树的根 —— 存储本身也是一棵 Patricia 树，和以太坊一样两层。
+*/
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Account {
+    pub balance: u64,
+    pub nonce: u64,
+    pub storage: BTreeMap<String, u64>,
+}
+
+impl Account {
+    pub fn storage_root(&self) -> Digest {
+        let mut trie = PatriciaTrie::new();
+        for (key, value) in &self.storage {
+            trie.insert(key.as_bytes(), value.to_be_bytes().to_vec());
+        }
+        trie.root_hash()
+    }
+
+    // 状态树叶子里存的值：定长 8 + 8 + 32 字节
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(48);
+        data.extend_from_slice(&self.balance.to_be_bytes());
+        data.extend_from_slice(&self.nonce.to_be_bytes());
+        data.extend_from_slice(&self.storage_root());
+        data
+    }
+
+    // 轻节点拿到的只有编码：解出 (余额, nonce, 存储根)
+    pub fn decode(data: &[u8]) -> Option<(u64, u64, Digest)> {
+        if data.len() != 48 {
+            return None;
+        }
+        let balance = u64::from_be_bytes(data[0..8].try_into().ok()?);
+        let nonce = u64::from_be_bytes(data[8..16].try_into().ok()?);
+        Some((balance, nonce, data[16..48].try_into().ok()?))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op {
+    Transfer { to: String, amount: u64 },
+    // 写发送者自己的存储
+    Store { key: String, value: u64 },
+    Load { key: String },
+    // 对 data 反复做 rounds 次 SHA-256：代表"纯计算"
+    Hash { data: String, rounds: u32 },
+    Log(String),
+}
+
+impl Op {
+    // 价格可能取决于当前状态：Store 写新槽和改旧槽不一样
+    pub fn gas_cost(&self, sender: &Account) -> u64 {
+        match self {
+            Op::Transfer { .. } => gas::TRANSFER,
+            Op::Store { key, .. } if sender.storage.contains_key(key) => gas::STORE_UPDATE,
+            Op::Store { .. } => gas::STORE_NEW,
+            Op::Load { .. } => gas::LOAD,
+            Op::Hash { rounds, .. } => gas::HASH_ROUND * *rounds as u64,
+            Op::Log(text) => gas::LOG_BASE + gas::LOG_BYTE * text.len() as u64,
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Op::Transfer { to, amount } => write!(f, "Transfer {} -> {}", amount, to),
+            Op::Store { key, value } => write!(f, "Store {} = {}", key, value),
+            Op::Load { key } => write!(f, "Load {}", key),
+            Op::Hash { rounds, .. } => write!(f, "Hash x{}", rounds),
+            Op::Log(text) => write!(f, "Log \"{}\"", text),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountTx {
+    pub from: String,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub gas_price: u64,
+    pub ops: Vec<Op>,
+}
+
+impl AccountTx {
+    // 只用来算区块的 Merkle 根：Debug 输出对同样的交易是确定的，玩具里够用
+    pub fn encode(&self) -> Vec<u8> {
+        format!("{}|{}|{}|{}|{:?}", self.from, self.nonce, self.gas_limit, self.gas_price, self.ops).into_bytes()
+    }
+}
+
+impl MerkleLeaf for AccountTx {
+    fn leaf_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.encode())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxStatus {
+    Success,
+    // op 是停在第几个操作 (从 0 开始)
+    OutOfGas { op: usize },
+    Reverted { op: usize, reason: String },
+}
+
+// 交易上链后的结果：不管成功与否都有收据，gas 都照收
+#[derive(Debug, Clone)]
+pub struct Receipt {
+    pub status: TxStatus,
+    pub gas_used: u64,
+    pub fee: u64,
+    pub logs: Vec<String>,
+}
+
+// BTreeMap 而不是 HashMap：遍历顺序固定，以后对整个状态做承诺 (状态根) 时每个节点算出来的一样
+#[derive(Debug, Clone, Default)]
+pub struct AccountState {
+    accounts: BTreeMap<String, Account>,
+}
+
+impl AccountState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 创世分配
+    pub fn credit(&mut self, address: &str, amount: u64) {
+        self.accounts.entry(address.to_string()).or_default().balance += amount;
+    }
+
+    pub fn account(&self, address: &str) -> Option<&Account> {
+        self.accounts.get(address)
+    }
+
+    pub fn balance(&self, address: &str) -> u64 {
+        self.account(address).map_or(0, |a| a.balance)
+    }
+
+    pub fn trie(&self) -> PatriciaTrie {
+        let mut trie = PatriciaTrie::new();
+        for (address, account) in &self.accounts {
+            trie.insert(address.as_bytes(), account.encode());
+        }
+        trie
+    }
+
+    // 每次从头建树 (玩具规模)；真实节点只更新改动过的账户那几条路径
+    pub fn state_root(&self) -> Digest {
+        self.trie().root_hash()
+    }
+
+    // 执行整个区块：任何一笔交易不合法、或者执行结果和区块头里的状态根不一致，整块拒绝，状态不变
+    pub fn apply_block(&mut self, block: &Block<AccountTx>, coinbase: &str) -> Result<Vec<Receipt>, String> {
+        let mut next = self.clone();
+        let mut receipts = Vec::with_capacity(block.txs.len());
+        for (i, tx) in block.txs.iter().enumerate() {
+            receipts.push(next.execute(tx, coinbase).map_err(|e| format!("第 {} 笔交易: {}", i, e))?);
+        }
+        let root = next.state_root();
+        if root != block.header.state_root {
+            return Err(format!("状态根不一致：区块头写的是 {}..，执行结果是 {}..", short_hex(&block.header.state_root), short_hex(&root)));
+        }
+        *self = next;
+        Ok(receipts)
+    }
+
+    // Err：交易不合法，不能上链，状态不变；Ok：交易上链了，收据里写着执行结果
+    pub fn execute(&mut self, tx: &AccountTx, coinbase: &str) -> Result<Receipt, String> {
+        let sender = self.accounts.get(&tx.from).cloned().unwrap_or_default();
+        if tx.nonce != sender.nonce {
+            return Err(format!("{} 的 nonce 应该是 {}，交易写的是 {}", tx.from, sender.nonce, tx.nonce));
+        }
+        if tx.gas_limit < gas::TX_BASE {
+            return Err(format!("gas_limit {} 连固定开销 {} 都不够", tx.gas_limit, gas::TX_BASE));
+        }
+        let max_fee = tx.gas_limit * tx.gas_price;
+        if sender.balance < max_fee {
+            return Err(format!("{} 余额 {} 付不起 gas_limit × gas_price = {}", tx.from, sender.balance, max_fee));
+        }
+
+        // 预扣 + nonce：不管后面执行成不成功都生效
+        let account = self.accounts.entry(tx.from.clone()).or_default();
+        account.balance -= max_fee;
+        account.nonce += 1;
+
+        let mut meter = GasMeter::new(tx.gas_limit);
+        meter.charge(gas::TX_BASE).expect("gas_limit checked above");
+        let checkpoint = self.accounts.clone();
+        let mut logs = Vec::new();
+        let mut status = TxStatus::Success;
+        for (i, op) in tx.ops.iter().enumerate() {
+            let cost = op.gas_cost(&self.accounts[&tx.from]);
+            if let Err(e) = meter.charge(cost) {
+                // 以太坊的规则：out of gas 把 gas_limit 全部烧掉
+                meter.used = meter.limit;
+                logs.push(e.to_string());
+                status = TxStatus::OutOfGas { op: i };
+                break;
+            }
+            if let Err(reason) = self.apply_op(&tx.from, op, &mut logs) {
+                status = TxStatus::Reverted { op: i, reason };
+                break;
+            }
+        }
+        if status != TxStatus::Success {
+            self.accounts = checkpoint;
+            logs.clear();
+        }
+
+        let fee = meter.used * tx.gas_price;
+        self.accounts.get_mut(&tx.from).expect("sender exists").balance += max_fee - fee;
+        self.credit(coinbase, fee);
+        Ok(Receipt { status, gas_used: meter.used, fee, logs })
+    }
+
+    fn apply_op(&mut self, from: &str, op: &Op, logs: &mut Vec<String>) -> Result<(), String> {
+        match op {
+            Op::Transfer { to, amount } => {
+                let sender = self.accounts.get_mut(from).expect("sender exists");
+                if sender.balance < *amount {
+                    return Err(format!("余额 {} 不够转 {}", sender.balance, amount));
+                }
+                sender.balance -= amount;
+                self.credit(to, *amount);
+            }
+            Op::Store { key, value } => {
+                self.accounts.get_mut(from).expect("sender exists").storage.insert(key.clone(), *value);
+            }
+            Op::Load { key } => {
+                let value = self.accounts[from].storage.get(key).copied();
+                logs.push(format!("{} = {}", key, value.map_or(String::from("(空)"), |v| v.to_string())));
+            }
+            Op::Hash { data, rounds } => {
+                let mut digest = sha256(data.as_bytes());
+                for _ in 1..*rounds {
+                    digest = sha256(&digest);
+                }
+                logs.push(format!("hash^{}({}) = {}..", rounds, data, short_hex(&digest)));
+            }
+            Op::Log(text) => logs.push(text.clone()),
+        }
+        Ok(())
+    }
+}
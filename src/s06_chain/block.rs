@@ -0,0 +1,68 @@
+// src/s06_chain/block.rs
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::s05_zk_lab::ex01_merkle::{MerkleLeaf, MerkleTree};
+use crate::s05_zk_lab::hash::{sha256, Digest};
+
+/*
+区块 = 区块头 + 交易列表
+
+    区块头只有几十字节，却通过两个哈希把整条链"钉"住：
+        prev_hash   : 上一个区块头的哈希 —— 改了历史上任何一个区块，它后面那个区块的 prev_hash 就对不上
+        merkle_root : 交易列表的 Merkle 根 (S05 ex01 的 MerkleTree) —— 改了任何一笔交易，根就变了
+    state_root：执行完本块之后整个状态的 Patricia 树根 (见 trie.rs) —— 账户模型是账户表，全节点 (node.rs) 是 UTXO 集合；
+        只承诺交易的练习链 (Block<String>) 上全 0。
+    PoW 只对区块头做哈希 (见 pow.rs)，nonce 是矿工唯一可以随便改的字段。
+
+Block<T> 的交易类型是泛型：只要能编码成 Merkle 叶子 (MerkleLeaf) 就行。
+默认是 String，写 Block 就是 Block<String>，和 MerkleTree 的默认参数一个思路。
+*/
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub height: u64,
+    pub prev_hash: Digest,
+    pub merkle_root: String,
+    pub state_root: Digest,
+    pub timestamp: u64,
+    // 难度 = 区块哈希至少要有多少个前导 0 比特
+    pub difficulty: u32,
+    pub nonce: u64,
+}
+
+impl BlockHeader {
+    // 字段按固定顺序拼接；除 merkle_root (定长 64 个十六进制字符) 外都是定长整数，不会有拼接歧义
+    pub fn hash(&self) -> Digest {
+        let mut data = Vec::with_capacity(8 + 32 + self.merkle_root.len() + 32 + 8 + 4 + 8);
+        data.extend_from_slice(&self.height.to_be_bytes());
+        data.extend_from_slice(&self.prev_hash);
+        data.extend_from_slice(self.merkle_root.as_bytes());
+        data.extend_from_slice(&self.state_root);
+        data.extend_from_slice(&self.timestamp.to_be_bytes());
+        data.extend_from_slice(&self.difficulty.to_be_bytes());
+        data.extend_from_slice(&self.nonce.to_be_bytes());
+        sha256(&data)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Block<T: MerkleLeaf = String> {
+    pub header: BlockHeader,
+    pub txs: Vec<T>,
+}
+
+impl<T: MerkleLeaf> Block<T> {
+    pub fn hash(&self) -> Digest {
+        self.header.hash()
+    }
+}
+
+// 交易列表的 Merkle 根：先把每笔交易编码成字节，再交给 S05 的 MerkleTree
+pub fn merkle_root<T: MerkleLeaf>(txs: &[T]) -> String {
+    let leaves: Vec<Vec<u8>> = txs.iter().map(|tx| tx.leaf_bytes().into_owned()).collect();
+    MerkleTree::new_iterative(leaves).root_hash()
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
@@ -0,0 +1,97 @@
+// src/s06_chain/bridge.rs
+use std::collections::{BTreeMap, HashSet};
+
+use crate::s05_zk_lab::ex01_merkle::MerkleProof;
+use crate::s05_zk_lab::hash::Digest;
+
+use super::block::BlockHeader;
+use super::spv::HeaderChain;
+use super::validation::BlockError;
+
+/*
+跨链桥：把链 A 上的资产"搬"到链 B
+
+    资产不会真的离开链 A。用户在 A 上把币锁进桥的金库 (lock)，B 上的桥合约确认锁定发生了，
+    再给用户铸造等量的包装币 (wrapped)。B 上的包装币总量必须永远等于 A 上金库的余额。
+
+    B 怎么知道 A 上发生了什么？B 的合约里跑着一个 A 的轻节点 (spv::HeaderChain)：
+        1. 中继者 (relayer) 把 A 的区块头一个个交给合约，合约自己验证 PoW 和链接
+        2. 中继者提交 lock 交易 + Merkle 证明 + 区块哈希；合约对照自己手里的区块头验证
+        3. 区块要压够 confirmations 个块才认 (防止 A 上的重组把锁定撤销掉)
+        4. 每个 lock 只能铸造一次 (防重放)
+    中继者不需要被信任：它只能搬运 A 上真实发生过的事，证明不对合约就拒绝。
+*/
+
+const LOCK_PREFIX: &str = "lock";
+
+// 链 A 上的一笔锁定：owner 在 A 上锁币，recipient 在 B 上收包装币
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lock {
+    pub id: u64,
+    pub owner: String,
+    pub recipient: String,
+    pub amount: u64,
+}
+
+impl Lock {
+    // 链 A 是 Chain<String>，锁定编码成一行文本
+    pub fn to_tx(&self) -> String {
+        format!("{}|{}|{}|{}|{}", LOCK_PREFIX, self.id, self.owner, self.recipient, self.amount)
+    }
+
+    pub fn parse(tx: &str) -> Option<Self> {
+        let [prefix, id, owner, recipient, amount] = tx.split('|').collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        if prefix != LOCK_PREFIX {
+            return None;
+        }
+        Some(Lock { id: id.parse().ok()?, owner: owner.to_string(), recipient: recipient.to_string(), amount: amount.parse().ok()? })
+    }
+}
+
+// 链 B 上的桥合约
+#[derive(Debug, Clone)]
+pub struct BridgeContract {
+    source: HeaderChain,
+    confirmations: u64,
+    minted: HashSet<u64>,
+    wrapped: BTreeMap<String, u64>,
+}
+
+impl BridgeContract {
+    // source_genesis 是部署合约时写死的链 A 创世区块头
+    pub fn new(source_genesis: BlockHeader, difficulty: u32, confirmations: u64) -> Self {
+        BridgeContract { source: HeaderChain::new(source_genesis, difficulty), confirmations, minted: HashSet::new(), wrapped: BTreeMap::new() }
+    }
+
+    pub fn add_header(&mut self, header: BlockHeader) -> Result<bool, BlockError> {
+        self.source.add(header)
+    }
+
+    pub fn source_height(&self) -> u64 {
+        self.source.best().height
+    }
+
+    // 验证通过就铸造，返回这笔锁定
+    pub fn claim(&mut self, tx: &str, proof: &MerkleProof, block_hash: &Digest) -> Result<Lock, String> {
+        let lock = Lock::parse(tx).ok_or_else(|| format!("\"{}\" 不是锁定交易", tx))?;
+        let confirmations = self.source.verify_inclusion(&tx.to_string(), proof, block_hash)?;
+        if confirmations < self.confirmations {
+            return Err(format!("锁定 #{} 只有 {} 个确认，需要 {} 个", lock.id, confirmations, self.confirmations));
+        }
+        if !self.minted.insert(lock.id) {
+            return Err(format!("锁定 #{} 已经铸造过了 (重放)", lock.id));
+        }
+        *self.wrapped.entry(lock.recipient.clone()).or_insert(0) += lock.amount;
+        Ok(lock)
+    }
+
+    pub fn balance(&self, name: &str) -> u64 {
+        self.wrapped.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn total_supply(&self) -> u64 {
+        self.wrapped.values().sum()
+    }
+}
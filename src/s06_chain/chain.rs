@@ -0,0 +1,86 @@
+// src/s06_chain/chain.rs
+use crate::s05_zk_lab::ex01_merkle::MerkleLeaf;
+
+use super::block::{merkle_root, now_secs, Block, BlockHeader};
+use super::pow::{self, MiningResult};
+use super::validation::{check_header, BlockError};
+
+/*
+链 = 一串首尾相连的区块
+
+    append   : 只接受"接在当前最高块后面、PoW 合格、Merkle 根对得上"的区块
+    validate : 从创世块开始把整条链重新检查一遍 —— 新节点同步、或者怀疑本地数据被改过时都要做
+    两者用同一套规则 (validation::check_header)，只是 validate 对每一对相邻区块都跑一遍。
+    这里只管区块头和 Merkle 根，交易本身对不对 (验签、UTXO) 见 validation::validate_block。
+
+blocks 是公开字段 (和 MerkleTree::leaves 一样)：练习里要直接改历史数据，看 validate 怎么发现。
+*/
+
+#[derive(Debug, Clone)]
+pub struct Chain<T: MerkleLeaf = String> {
+    pub blocks: Vec<Block<T>>,
+    pub difficulty: u32,
+}
+
+impl<T: MerkleLeaf> Chain<T> {
+    // 创世块没有父块：prev_hash 全 0，高度 0，同样要挖
+    pub fn new(difficulty: u32, genesis_txs: Vec<T>) -> Self {
+        let header = BlockHeader {
+            height: 0,
+            prev_hash: [0u8; 32],
+            merkle_root: merkle_root(&genesis_txs),
+            state_root: [0u8; 32],
+            timestamp: now_secs(),
+            difficulty,
+            nonce: 0,
+        };
+        let genesis = Block { header: pow::mine(header).header, txs: genesis_txs };
+        Chain { blocks: vec![genesis], difficulty }
+    }
+
+    pub fn tip(&self) -> &Block<T> {
+        self.blocks.last().expect("chain always has a genesis block")
+    }
+
+    pub fn height(&self) -> u64 {
+        self.tip().header.height
+    }
+
+    // 接在当前最高块后面的候选区块，nonce 还没找
+    pub fn candidate(&self, txs: Vec<T>) -> Block<T> {
+        let header = BlockHeader {
+            height: self.height() + 1,
+            prev_hash: self.tip().hash(),
+            merkle_root: merkle_root(&txs),
+            state_root: [0u8; 32],
+            timestamp: now_secs(),
+            difficulty: self.difficulty,
+            nonce: 0,
+        };
+        Block { header, txs }
+    }
+
+    pub fn append(&mut self, block: Block<T>) -> Result<(), BlockError> {
+        check_header(Some(self.tip()), &block, self.difficulty)?;
+        self.blocks.push(block);
+        Ok(())
+    }
+
+    // 打包 -> 挖矿 -> 上链
+    pub fn mine_block(&mut self, txs: Vec<T>) -> Result<MiningResult, BlockError> {
+        let mut block = self.candidate(txs);
+        let result = pow::mine(block.header.clone());
+        block.header = result.header.clone();
+        self.append(block)?;
+        Ok(result)
+    }
+
+    pub fn validate(&self) -> Result<(), BlockError> {
+        let mut prev = None;
+        for block in &self.blocks {
+            check_header(prev, block, self.difficulty)?;
+            prev = Some(block);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+// src/s06_chain/da.rs
+use std::borrow::Cow;
+
+use crate::s05_zk_lab::ex01_merkle::{MerkleLeaf, MerkleProof, MerkleTree};
+use crate::s05_zk_lab::math::field::M31;
+use crate::s05_zk_lab::math::poly::Polynomial;
+
+/*
+数据可用性 (Data Availability)：区块头发出来了，区块里的数据真的有人能拿到吗？
+
+    出块人可以只发区块头、扣住一部分数据 —— 全节点发现不了问题在哪 (没数据就没法验证)，轻客户端更不行。
+    办法是纠删码 + 随机抽样：
+        1. 把数据切成 k 块，用 Reed-Solomon 扩展成 2k 块：任意 k 块就能还原全部数据
+        2. 对 2k 块建 Merkle 树，根放进区块头 (data_root)
+        3. 出块人想让数据还原不出来，至少要扣住 k + 1 块 —— 超过一半！
+           轻客户端随机要 s 块 (带 Merkle 证明)，每一次都躲开被扣的块的概率 < 1/2，s 次都躲开 < (1/2)^s
+
+    Reed-Solomon 在 M31 上做：每 3 个字节是一个域元素 (< 2^24 < p)。
+    每个块有 m 个符号；第 j 列的 k 个原始符号看成多项式在 x = 0..k-1 上的取值，
+    插值出这个次数 < k 的多项式，再在 x = k..2k-1 上求值，就是校验块的第 j 个符号。
+    前 k 块就是原始数据本身 (系统码)，不需要解码就能直接读。
+*/
+
+const SYMBOL_BYTES: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    pub index: usize,
+    pub symbols: Vec<M31>,
+}
+
+impl MerkleLeaf for Chunk {
+    // 下标也编进叶子：同一份内容放到别的位置，证明就对不上
+    fn leaf_bytes(&self) -> Cow<'_, [u8]> {
+        let mut data = (self.index as u32).to_be_bytes().to_vec();
+        for symbol in &self.symbols {
+            data.extend_from_slice(&(symbol.value() as u32).to_be_bytes());
+        }
+        Cow::Owned(data)
+    }
+}
+
+// 放进区块头的东西：根 + 还原时需要的参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaHeader {
+    pub data_root: String,
+    pub k: usize,
+    pub len: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExtendedData {
+    pub header: DaHeader,
+    pub chunks: Vec<Chunk>,
+}
+
+// 同一列上的点：(x = 块下标, y = 这一列的符号)
+fn column(chunks: &[&Chunk], j: usize) -> Vec<(M31, M31)> {
+    chunks.iter().map(|c| (M31::new(c.index as u64), c.symbols[j])).collect()
+}
+
+impl ExtendedData {
+    pub fn encode(data: &[u8], k: usize) -> Self {
+        let m = data.len().div_ceil(SYMBOL_BYTES * k).max(1);
+        let mut padded = data.to_vec();
+        padded.resize(k * m * SYMBOL_BYTES, 0);
+        let symbols: Vec<M31> = padded.chunks(SYMBOL_BYTES).map(|b| M31::new(u64::from_be_bytes([0, 0, 0, 0, 0, b[0], b[1], b[2]]))).collect();
+        let mut chunks: Vec<Chunk> = symbols.chunks(m).enumerate().map(|(index, s)| Chunk { index, symbols: s.to_vec() }).collect();
+
+        let originals: Vec<&Chunk> = chunks.iter().collect();
+        let polys: Vec<Polynomial<_>> = (0..m).map(|j| Polynomial::interpolate(&column(&originals, j)).expect("chunk indices are distinct")).collect();
+        let parity: Vec<Chunk> = (k..2 * k).map(|index| Chunk { index, symbols: polys.iter().map(|p| p.evaluate(M31::new(index as u64))).collect() }).collect();
+        chunks.extend(parity);
+
+        let data_root = MerkleTree::new_iterative(chunks.clone()).root_hash();
+        ExtendedData { header: DaHeader { data_root, k, len: data.len() }, chunks }
+    }
+
+    pub fn tree(&self) -> MerkleTree<Chunk> {
+        MerkleTree::new_iterative(self.chunks.clone())
+    }
+}
+
+// 单个块对照区块头检查：下标在范围内，Merkle 证明对得上 data_root
+pub fn verify_chunk(header: &DaHeader, chunk: &Chunk, proof: &MerkleProof) -> Result<(), String> {
+    if chunk.index >= 2 * header.k {
+        return Err(format!("块下标 {} 超出范围 (一共 {} 块)", chunk.index, 2 * header.k));
+    }
+    if proof.index != chunk.index || !proof.verify(chunk, &header.data_root) {
+        return Err(format!("块 {} 的 Merkle 证明和 data_root 对不上", chunk.index));
+    }
+    Ok(())
+}
+
+// 任意 k 个不同的块 -> 原始数据
+// 调用方应该先用 verify_chunk 检查过每一块；这里只管解码
+pub fn reconstruct(header: &DaHeader, available: &[Chunk]) -> Result<Vec<u8>, String> {
+    let mut picked: Vec<&Chunk> = Vec::new();
+    for chunk in available {
+        if !picked.iter().any(|c| c.index == chunk.index) {
+            picked.push(chunk);
+        }
+    }
+    if picked.len() < header.k {
+        return Err(format!("只有 {} 个不同的块，至少要 {} 个", picked.len(), header.k));
+    }
+    picked.truncate(header.k);
+    let m = picked[0].symbols.len();
+    if picked.iter().any(|c| c.symbols.len() != m) {
+        return Err(String::from("块的长度不一致"));
+    }
+
+    let mut originals = vec![Vec::with_capacity(m); header.k];
+    for j in 0..m {
+        let poly = Polynomial::interpolate(&column(&picked, j)).ok_or("块下标重复")?;
+        for (x, original) in originals.iter_mut().enumerate() {
+            original.push(poly.evaluate(M31::new(x as u64)));
+        }
+    }
+    let mut data: Vec<u8> = originals.iter().flatten().flat_map(|s| (s.value() as u32).to_be_bytes()[1..].to_vec()).collect();
+    if data.len() < header.len {
+        return Err(String::from("还原出来的数据比区块头里记录的长度短"));
+    }
+    data.truncate(header.len);
+    Ok(data)
+}
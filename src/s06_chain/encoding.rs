@@ -0,0 +1,171 @@
+// src/s06_chain/encoding.rs
+use crate::s05_zk_lab::hash::sha256;
+
+/*
+地址编码：把公钥哈希这串字节变成"人抄得对、抄错了能发现"的字符串
+
+Base58Check (比特币传统地址)：
+    数据 = 版本字节 || 载荷；校验和 = SHA-256(SHA-256(数据)) 的前 4 字节
+    把 数据 || 校验和 当成一个大整数，反复除以 58 转成 58 进制
+    字母表去掉了容易看混的 0 O I l；开头每个 0x00 字节写成一个 '1' (大整数转换会把前导 0 吃掉)
+    抄错一个字符 -> 校验和对不上的概率 1 - 2^-32
+
+Bech32 (隔离见证地址，BIP-173，这里省掉了见证版本)：
+    人类可读前缀 (hrp) + '1' + 数据 (每个字符 5 bit) + 6 个字符的 BCH 校验码
+    全小写、字母表 32 个字符；校验码保证任意不超过 4 个字符的错误一定能发现
+*/
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32_ALPHABET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+
+fn checksum(data: &[u8]) -> [u8; 4] {
+    let digest = sha256(&sha256(data));
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+pub fn base58_encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    // 58 进制的数字，低位在前；每读入一个字节：整个数 × 256 + 字节
+    let mut digits: Vec<u8> = Vec::new();
+    for &byte in &bytes[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let mut text = "1".repeat(zeros);
+    text.extend(digits.iter().rev().map(|&d| BASE58_ALPHABET[d as usize] as char));
+    text
+}
+
+pub fn base58_decode(text: &str) -> Result<Vec<u8>, String> {
+    let zeros = text.chars().take_while(|&c| c == '1').count();
+    // 256 进制的数字，低位在前；每读入一个字符：整个数 × 58 + 字符的值
+    let mut bytes: Vec<u8> = Vec::new();
+    for (i, c) in text.chars().enumerate().skip(zeros) {
+        let value = BASE58_ALPHABET.iter().position(|&a| a as char == c).ok_or_else(|| format!("第 {} 个字符 {:?} 不在 Base58 字母表里", i + 1, c))?;
+        let mut carry = value as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    let mut decoded = vec![0u8; zeros];
+    decoded.extend(bytes.iter().rev());
+    Ok(decoded)
+}
+
+pub fn base58check_encode(version: u8, payload: &[u8]) -> String {
+    let mut data = vec![version];
+    data.extend_from_slice(payload);
+    let sum = checksum(&data);
+    data.extend_from_slice(&sum);
+    base58_encode(&data)
+}
+
+// 返回 (版本, 载荷)
+pub fn base58check_decode(text: &str) -> Result<(u8, Vec<u8>), String> {
+    let data = base58_decode(text)?;
+    if data.len() < 5 {
+        return Err(format!("解码后只有 {} 字节，放不下版本和 4 字节校验和", data.len()));
+    }
+    let (body, sum) = data.split_at(data.len() - 4);
+    if checksum(body) != sum {
+        return Err(format!("校验和不对：算出来 {}，地址里写的是 {}", hex::encode(checksum(body)), hex::encode(sum)));
+    }
+    Ok((body[0], body[1..].to_vec()))
+}
+
+fn polymod(values: &[u8]) -> u32 {
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ v as u32;
+        for (i, g) in BECH32_GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+// hrp 的每个字符拆成高 3 位和低 5 位参与校验，改了前缀校验码也会变
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut values: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|b| b & 31));
+    values
+}
+
+// 8 bit 一组 <-> 5 bit 一组；pad 为 true 时末尾不足的位补 0
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, String> {
+    let (mut acc, mut bits) = (0u32, 0u32);
+    let max = (1u32 << to) - 1;
+    let mut out = Vec::new();
+    for &value in data {
+        acc = (acc << from) | value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return Err(String::from("末尾的填充位不是 0"));
+    }
+    Ok(out)
+}
+
+pub fn bech32_encode(hrp: &str, payload: &[u8]) -> String {
+    let data = convert_bits(payload, 8, 5, true).expect("padding never fails");
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(&data);
+    values.extend_from_slice(&[0; 6]);
+    let check = polymod(&values) ^ 1;
+    let mut text = format!("{}1", hrp);
+    text.extend(data.iter().map(|&d| BECH32_ALPHABET[d as usize] as char));
+    text.extend((0..6).map(|i| BECH32_ALPHABET[((check >> (5 * (5 - i))) & 31) as usize] as char));
+    text
+}
+
+// 返回 (hrp, 载荷)
+pub fn bech32_decode(text: &str) -> Result<(String, Vec<u8>), String> {
+    if text.chars().any(|c| c.is_ascii_uppercase()) && text.chars().any(|c| c.is_ascii_lowercase()) {
+        return Err(String::from("大小写混用"));
+    }
+    let text = text.to_ascii_lowercase();
+    let split = text.rfind('1').ok_or("没有分隔符 '1'")?;
+    let (hrp, rest) = (&text[..split], &text[split + 1..]);
+    if hrp.is_empty() || rest.len() < 6 {
+        return Err(String::from("前缀为空或者数据部分太短"));
+    }
+    let mut data = Vec::with_capacity(rest.len());
+    for (i, c) in rest.chars().enumerate() {
+        let value = BECH32_ALPHABET.iter().position(|&a| a as char == c).ok_or_else(|| format!("第 {} 个字符 {:?} 不在 Bech32 字母表里", split + 2 + i, c))?;
+        data.push(value as u8);
+    }
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(&data);
+    if polymod(&values) != 1 {
+        return Err(String::from("校验码不对"));
+    }
+    let payload = convert_bits(&data[..data.len() - 6], 5, 8, false)?;
+    Ok((hrp.to_string(), payload))
+}
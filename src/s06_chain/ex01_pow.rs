@@ -0,0 +1,147 @@
+// src/s06_chain/ex01_pow.rs
+use crate::common::input::read_line;
+
+use super::block::merkle_root;
+use super::chain::Chain;
+use super::pow::{self, MiningResult};
+
+/*
+业务场景：从零搭一条 PoW 链
+    S04 ex01 的矿工线程 sleep 两秒就宣布"挖矿成功，Hash: 000abc..."；S05 ex01 算出了交易的 Merkle 根。
+    这里把两者接起来：区块头里放 Merkle 根和上一个区块的哈希，矿工真的去搜一个让哈希够小的 nonce。
+
+本练习：
+    1. 选一个难度，挖创世块和 3 个区块，看每个块试了多少次
+    2. 整条链验证通过
+    3. ❌ 篡改第 1 个区块里的一笔交易，看攻击者每"修补"一步，验证在哪里失败
+    4. 实测：难度每 +1，平均尝试次数翻倍
+*/
+
+const DEFAULT_DIFFICULTY: u32 = 16;
+const MAX_DIFFICULTY: u32 = 24;
+
+fn show_mined(label: &str, result: &MiningResult) {
+    println!(
+        "  {:<6} nonce = {:<8} 尝试 {:>8} 次  {:>10.1?}  哈希 {}",
+        label,
+        result.header.nonce,
+        result.attempts,
+        result.elapsed,
+        &hex::encode(result.header.hash())[..24]
+    );
+}
+
+fn report(label: &str, chain: &Chain) {
+    match chain.validate() {
+        Ok(()) => println!("  {}: ✅ 整条链验证通过", label),
+        Err(e) => println!("  {}: ❌ {}", label, e),
+    }
+}
+
+pub fn run() {
+    println!("--- S06 Ex01: 区块、链与工作量证明 (PoW) ---");
+
+    let difficulty = read_line(&format!("\n难度 (前导 0 比特数，最大 {}，直接回车默认 {}): ", MAX_DIFFICULTY, DEFAULT_DIFFICULTY))
+        .parse::<u32>()
+        .ok()
+        .filter(|d| *d <= MAX_DIFFICULTY)
+        .unwrap_or(DEFAULT_DIFFICULTY);
+    println!("  每次尝试成功的概率 1 / 2^{}，期望尝试 {:.0} 次", difficulty, pow::expected_attempts(difficulty));
+
+    // ==========================================
+    // 1. 挖矿
+    // ==========================================
+    println!("\n[1] 挖矿");
+    let mut chain = Chain::new(difficulty, vec![String::from("coinbase -> Satoshi: 50")]);
+    let genesis = &chain.blocks[0].header;
+    println!("  {:<6} nonce = {:<8} 尝试 {:>8} 次  {:>10}  哈希 {}", "创世块", genesis.nonce, genesis.nonce + 1, "-", &hex::encode(genesis.hash())[..24]);
+    let batches = [
+        vec!["coinbase -> Alice: 50", "Satoshi -> Bob: 10"],
+        vec!["coinbase -> Bob: 50", "Alice -> Carol: 5", "Bob -> Dave: 7"],
+        vec!["coinbase -> Carol: 50"],
+    ];
+    for txs in batches {
+        let txs: Vec<String> = txs.into_iter().map(String::from).collect();
+        match chain.mine_block(txs) {
+            Ok(result) => show_mined(&format!("#{}", result.header.height), &result),
+            Err(e) => println!("  ❌ 出块失败 (不应该发生): {}", e),
+        }
+    }
+    for block in &chain.blocks {
+        println!(
+            "    #{} prev = {}..  merkle_root = {}..  {} 笔交易",
+            block.header.height,
+            &hex::encode(block.header.prev_hash)[..12],
+            &block.header.merkle_root[..12],
+            block.txs.len()
+        );
+    }
+
+    // ==========================================
+    // 2. 验证
+    // ==========================================
+    println!("\n[2] 从创世块开始验证");
+    report("诚实的链", &chain);
+
+    // ==========================================
+    // 3. 篡改历史
+    // ==========================================
+    println!("\n[3] ❌ 攻击者想把区块 #1 里的 \"Satoshi -> Bob: 10\" 改成 100");
+    let mut forged = chain.clone();
+    forged.blocks[1].txs[1] = String::from("Satoshi -> Bob: 100");
+    report("只改交易", &forged);
+
+    forged.blocks[1].header.merkle_root = merkle_root(&forged.blocks[1].txs);
+    report("再重算 Merkle 根", &forged);
+
+    let remined = pow::mine(forged.blocks[1].header.clone());
+    println!("  重新挖区块 #1: 又试了 {} 次", remined.attempts);
+    forged.blocks[1].header = remined.header;
+    report("再重新挖 #1", &forged);
+
+    let mut extra = 0;
+    for i in 2..forged.blocks.len() {
+        forged.blocks[i].header.prev_hash = forged.blocks[i - 1].hash();
+        let remined = pow::mine(forged.blocks[i].header.clone());
+        extra += remined.attempts;
+        forged.blocks[i].header = remined.header;
+    }
+    println!("  把 #2、#3 也重新挖一遍: 又试了 {} 次", extra);
+    report("重挖后面所有区块", &forged);
+    println!("  伪造成功了，但要重做被改区块之后的【全部】工作量，而诚实的矿工同时还在往前挖 ——");
+    println!("  这就是\"确认数越多越安全\"的原因 (谁的链更长、累计工作量更大，见后续的分叉选择练习)");
+
+    // ==========================================
+    // 4. 难度与尝试次数
+    // ==========================================
+    println!("\n[4] 每个难度挖 20 个块，平均尝试次数");
+    let mut sample = Chain::new(0, vec![String::from("sample")]);
+    for d in [4, 8, 10, 12] {
+        sample.difficulty = d;
+        let mut header = sample.candidate(vec![String::from("sample")]).header;
+        let mut total = 0;
+        for i in 0..20 {
+            header.timestamp += i;
+            total += pow::mine(header.clone()).attempts;
+        }
+        println!("  难度 {:>2}: 平均 {:>7.0} 次，期望 2^{} = {:>5.0}", d, total as f64 / 20.0, d, pow::expected_attempts(d));
+    }
+}
+
+/*
+关键点总结：
+    1. 区块头的两个哈希指针：
+        prev_hash 把区块串成链，merkle_root 把交易钉进区块头。
+        只需要保存最新区块的哈希，就等于承诺了整段历史。
+
+    2. PoW 的不对称：
+        找 nonce 平均 2^d 次哈希，验证只要 1 次。难度是一个概率门槛，不是"算出某个答案"。
+
+    3. 改历史的代价：
+        改一笔交易 -> Merkle 根变 -> 区块哈希变 -> 要重新挖 -> 下一块的 prev_hash 对不上 -> 后面全部重挖。
+        攻击者必须在算力上追上并超过整个诚实网络。
+
+    4. 和 S04 ex01 对照：
+        那里的矿工线程只是 sleep；这里的 pow::mine 是真正的 CPU 密集循环，
+        后面的并发练习会把 nonce 空间分给多个线程一起搜。
+*/
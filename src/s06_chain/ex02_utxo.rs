@@ -0,0 +1,188 @@
+// src/s06_chain/ex02_utxo.rs
+use crate::common::input::read_line;
+
+use super::chain::Chain;
+use super::utxo::{OutPoint, Transaction, TxIn, TxOut, UtxoSet, BLOCK_REWARD};
+
+/*
+业务场景：比特币怎么记账
+    S01 的 Account 有一个 balance 字段，转账就是 balance -= amount。
+    比特币里没有这个字段：每一笔钱都是某笔交易的某个输出，花钱 = 把旧输出整个消耗掉、再创建新输出。
+
+本练习：
+    1. 创世块把 50 发给 Alice，看 UTXO 集合
+    2. Alice 给 Bob 转账 (金额可以自己输入)，看输入怎么被消耗、找零怎么产生；矿工拿走手续费
+    3. ❌ 五种不合法的交易 / 区块
+    4. 对照账户模型
+*/
+
+// 练习不需要等挖矿，难度调低
+const DIFFICULTY: u32 = 8;
+const FEE: u64 = 1;
+
+fn show_utxos(utxos: &UtxoSet, people: &[&str]) {
+    if utxos.is_empty() {
+        println!("  UTXO 集合为空");
+        return;
+    }
+    println!("  UTXO 集合 ({} 个未花费输出):", utxos.len());
+    for &name in people {
+        let outputs: Vec<String> = utxos.outputs_of(name).iter().map(|(op, out)| format!("{} = {}", op, out.amount)).collect();
+        println!("    {:<6} 余额 {:>3}  [{}]", name, utxos.balance(name), outputs.join(", "));
+    }
+}
+
+fn mine_and_apply(chain: &mut Chain<Transaction>, utxos: &mut UtxoSet, miner: &str, txs: Vec<Transaction>) -> Result<(), String> {
+    let fees: u64 = txs.iter().map(|tx| utxos.validate_tx(tx)).sum::<Result<u64, String>>()?;
+    let mut all = vec![Transaction::coinbase(miner, BLOCK_REWARD + fees, chain.height() + 1)];
+    all.extend(txs);
+    let candidate = chain.candidate(all);
+    // 先在 UTXO 上试执行，通过了才挖矿上链
+    let mut trial = utxos.clone();
+    trial.apply_block(&candidate)?;
+    chain.mine_block(candidate.txs).map_err(|e| e.to_string())?;
+    *utxos = trial;
+    Ok(())
+}
+
+fn report(label: &str, result: Result<u64, String>) {
+    match result {
+        Ok(fee) => println!("  {}: ✅ 合法，手续费 {} (不应该发生)", label, fee),
+        Err(e) => println!("  {}: ❌ {}", label, e),
+    }
+}
+
+pub fn run() {
+    println!("--- S06 Ex02: UTXO 交易模型 ---");
+    let people = ["Alice", "Bob", "Miner"];
+
+    // ==========================================
+    // 1. 创世
+    // ==========================================
+    println!("\n[1] 创世块：coinbase 给 Alice {}", BLOCK_REWARD);
+    let genesis_cb = Transaction::coinbase("Alice", BLOCK_REWARD, 0);
+    let mut chain: Chain<Transaction> = Chain::new(DIFFICULTY, vec![genesis_cb.clone()]);
+    let mut utxos = UtxoSet::new();
+    if let Err(e) = utxos.apply_block(&chain.blocks[0]) {
+        println!("  ❌ 创世块无法执行 (不应该发生): {}", e);
+        return;
+    }
+    println!("  {}", genesis_cb);
+    show_utxos(&utxos, &people);
+
+    // ==========================================
+    // 2. 转账
+    // ==========================================
+    let amount = read_line(&format!("\n[2] Alice 给 Bob 转多少 (1..{}，直接回车默认 30): ", BLOCK_REWARD - FEE))
+        .parse::<u64>()
+        .ok()
+        .filter(|a| (1..=BLOCK_REWARD - FEE).contains(a))
+        .unwrap_or(30);
+    let pay = match utxos.build_transfer("Alice", "Bob", amount, FEE) {
+        Ok(tx) => tx,
+        Err(e) => {
+            println!("  ❌ {}", e);
+            return;
+        }
+    };
+    println!("  交易: {}", pay);
+    println!("  输入 {} 整个被消耗；输出给 Bob {}，找零给 Alice {}，差额 {} 是手续费", pay.inputs[0].prev, amount, BLOCK_REWARD - amount - FEE, FEE);
+    let old_coin = pay.inputs[0].prev;
+    match mine_and_apply(&mut chain, &mut utxos, "Miner", vec![pay.clone()]) {
+        Ok(()) => println!("  区块 #{} 已上链，Miner 的 coinbase = 奖励 {} + 手续费 {}", chain.height(), BLOCK_REWARD, FEE),
+        Err(e) => println!("  ❌ 出块失败 (不应该发生): {}", e),
+    }
+    show_utxos(&utxos, &people);
+
+    // Bob 把刚收到的钱再转一部分给 Alice：花的是上一个区块里创建的输出
+    if let Ok(back) = utxos.build_transfer("Bob", "Alice", amount / 2, FEE) {
+        println!("  Bob 转回 Alice {}: {}", amount / 2, back);
+        if let Err(e) = mine_and_apply(&mut chain, &mut utxos, "Miner", vec![back]) {
+            println!("  ❌ 出块失败 (不应该发生): {}", e);
+        }
+        show_utxos(&utxos, &people);
+    }
+    match chain.validate() {
+        Ok(()) => println!("  整条链 (高度 {}) PoW / Merkle 验证 ✅", chain.height()),
+        Err(e) => println!("  ❌ {}", e),
+    }
+
+    // ==========================================
+    // 3. 不合法的交易
+    // ==========================================
+    println!("\n[3] ❌ 不合法的交易");
+    let fake = OutPoint { txid: [0xab; 32], index: 0 };
+    let to_bob = |amount| vec![TxOut { owner: String::from("Bob"), amount }];
+    report(
+        "花一个不存在的输出",
+        utxos.validate_tx(&Transaction { inputs: vec![TxIn::unsigned(fake)], outputs: to_bob(10), lock_time: 0 }),
+    );
+    report(
+        "Alice 重放创世块里那张已经花掉的 50",
+        utxos.validate_tx(&Transaction { inputs: vec![TxIn::unsigned(old_coin)], outputs: to_bob(10), lock_time: 0 }),
+    );
+    let (alice_coin, alice_out) = utxos.outputs_of("Alice")[0].clone();
+    report(
+        &format!("Alice 用 {} 的输出付 {}", alice_out.amount, alice_out.amount + 5),
+        utxos.validate_tx(&Transaction { inputs: vec![TxIn::unsigned(alice_coin)], outputs: to_bob(alice_out.amount + 5), lock_time: 0 }),
+    );
+    report(
+        "同一个输入写两次，想算成双倍",
+        utxos.validate_tx(&Transaction { inputs: vec![TxIn::unsigned(alice_coin), TxIn::unsigned(alice_coin)], outputs: to_bob(alice_out.amount * 2), lock_time: 0 }),
+    );
+    let mut wrap = to_bob(u64::MAX);
+    wrap.extend(to_bob(2));
+    report(
+        "输出 u64::MAX + 2，想让总额回绕成 1",
+        utxos.validate_tx(&Transaction { inputs: vec![TxIn::unsigned(alice_coin)], outputs: wrap, lock_time: 0 }),
+    );
+
+    // 两笔交易单独看都合法，放进同一个区块就是双花
+    let spend_a = Transaction { inputs: vec![TxIn::unsigned(alice_coin)], outputs: to_bob(alice_out.amount), lock_time: 0 };
+    let spend_b = Transaction {
+        inputs: vec![TxIn::unsigned(alice_coin)],
+        outputs: vec![TxOut { owner: String::from("Alice"), amount: alice_out.amount }],
+        lock_time: 0,
+    };
+    println!("  两笔都花 {}: 单独验证 {} / {}", alice_coin, utxos.validate_tx(&spend_a).is_ok(), utxos.validate_tx(&spend_b).is_ok());
+    let block = chain.candidate(vec![Transaction::coinbase("Miner", BLOCK_REWARD, chain.height() + 1), spend_a, spend_b]);
+    match utxos.clone().apply_block(&block) {
+        Ok(()) => println!("  放进同一个区块: ✅ (不应该发生)"),
+        Err(e) => println!("  放进同一个区块: ❌ {}", e),
+    }
+
+    // ==========================================
+    // 4. 对照账户模型
+    // ==========================================
+    println!("\n[4] 账户模型 (S01 Account) vs UTXO");
+    let rows = [
+        ("", "账户模型", "UTXO"),
+        ("状态", "每个地址一个 balance", "一堆未花费输出"),
+        ("转账", "balance -= x; balance += x", "消耗旧输出，创建新输出 + 找零"),
+        ("防重放", "账户 nonce 递增", "输出只能花一次，天然防重放"),
+        ("并行验证", "同一账户的交易必须排队", "不冲突的交易互不影响"),
+        ("智能合约", "方便 (以太坊)", "困难 (状态散落在输出里)"),
+    ];
+    for (topic, account, utxo) in rows {
+        println!("  {:<12} | {:<30} | {}", topic, account, utxo);
+    }
+}
+
+/*
+关键点总结：
+    1. 余额是派生量：
+        UTXO 集合里属于你的输出加起来就是余额。钱包的工作就是"选币"(build_transfer) 和找零。
+
+    2. 双花检查就是集合操作：
+        输入必须在未花费集合里；花掉就从集合里删掉。同一区块里的交易按顺序执行 (apply_block)，
+        所以第二笔花同一个输出的交易看到的集合里已经没有它了。
+
+    3. 区块原子性：
+        apply_block 先在 clone 上执行，全部成功才替换 —— 任何一笔失败，UTXO 集合原样不动。
+
+    4. coinbase：
+        唯一凭空造币的交易，金额上限是 出块奖励 + 区块里所有交易的手续费；
+        输入里编码区块高度，保证每个 coinbase 的 txid 不同。
+
+    5. 这里还没有签名：谁都能写一个花 Alice 输出的交易。下一步给输入加上签名。
+*/
@@ -0,0 +1,158 @@
+// src/s06_chain/ex03_signatures.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::crypto::schnorr::{self, Keypair};
+
+use super::chain::Chain;
+use super::mempool::{Accepted, Mempool};
+use super::utxo::{Transaction, TxIn, TxOut, UtxoSet, Witness, BLOCK_REWARD};
+use super::wallet::Wallet;
+
+/*
+业务场景：凭什么只有 Alice 能花 Alice 的钱
+    ex02 的 UTXO 只写了主人的名字，谁都能写一笔"花 Alice 的输出"的交易，账本照单全收。
+    现在输出上写的是地址 (公钥哈希)，花它要出示公钥 + 签名；Mempool::add 在交易进池之前就检查。
+
+本练习：
+    1. 三个钱包 (Alice、Bob、Mallory)，创世块给 Alice 50
+    2. Alice 签名转账给 Bob，进入交易池
+    3. ❌ Mallory 的五种尝试，全部被 Mempool::add 拒绝
+    4. 打包出块；已经上链的交易再提交一次
+*/
+
+const DIFFICULTY: u32 = 8;
+const FEE: u64 = 1;
+
+fn report(label: &str, result: Result<Accepted, String>) {
+    match result {
+        Ok(Accepted { fee, .. }) => println!("  {}: ✅ 进入交易池，手续费 {}", label, fee),
+        Err(e) => println!("  {}: ❌ {}", label, e),
+    }
+}
+
+fn show_balances(utxos: &UtxoSet, wallets: &[&Wallet]) {
+    for w in wallets {
+        println!("    {:<8} {}  余额 {}", w.name, w.address(), utxos.balance(&w.address()));
+    }
+}
+
+pub fn run() {
+    println!("--- S06 Ex03: 交易签名与交易池 (EC-Schnorr) ---");
+    let mut rng = SimpleRng::from_time();
+
+    // ==========================================
+    // 1. 钱包与创世块
+    // ==========================================
+    println!("\n[1] 钱包 (私钥在玩具曲线上，地址 = 公钥哈希)");
+    let alice = Wallet::generate("Alice", &mut rng);
+    let bob = Wallet::generate("Bob", &mut rng);
+    let mallory = Wallet::generate("Mallory", &mut rng);
+    let miner = Wallet::generate("Miner", &mut rng);
+    for w in [&alice, &bob, &mallory] {
+        println!("  {:<8} 公钥 {:<12} 地址 {}", w.name, w.keys.public.to_string(), w.address());
+    }
+    let mut chain: Chain<Transaction> = Chain::new(DIFFICULTY, vec![Transaction::coinbase(&alice.address(), BLOCK_REWARD, 0)]);
+    let mut utxos = UtxoSet::new();
+    if let Err(e) = utxos.apply_block(&chain.blocks[0]) {
+        println!("  ❌ 创世块无法执行 (不应该发生): {}", e);
+        return;
+    }
+    let (alice_coin, _) = utxos.outputs_of(&alice.address())[0].clone();
+    println!("  创世块: Alice 得到 {} = {}", alice_coin, BLOCK_REWARD);
+
+    // ==========================================
+    // 2. 正常转账
+    // ==========================================
+    let amount = read_line(&format!("\n[2] Alice 给 Bob 转多少 (1..{}，直接回车默认 20): ", BLOCK_REWARD - FEE))
+        .parse::<u64>()
+        .ok()
+        .filter(|a| (1..=BLOCK_REWARD - FEE).contains(a))
+        .unwrap_or(20);
+    let mut pay = match utxos.build_transfer(&alice.address(), &bob.address(), amount, FEE) {
+        Ok(tx) => tx,
+        Err(e) => {
+            println!("  ❌ {}", e);
+            return;
+        }
+    };
+    let unsigned_txid = pay.txid();
+    alice.sign(&mut pay, &utxos, &mut rng);
+    let witness = pay.inputs[0].witness.expect("Alice signed her own input");
+    println!("  交易 {}", pay);
+    println!("  输入的见证: 公钥 {}，签名 (R = {}, s = {})", witness.public, witness.signature.r, witness.signature.s);
+    println!("  签名前后 txid 不变: {}", if pay.txid() == unsigned_txid { "✅ (txid 不含签名)" } else { "❌" });
+    let mut pool = Mempool::new();
+    report("Alice 的交易", pool.add(pay.clone(), &utxos));
+
+    // ==========================================
+    // 3. Mallory 的尝试
+    // ==========================================
+    println!("\n[3] ❌ Mallory 想花 Alice 的 {}", alice_coin);
+    let steal = Transaction {
+        inputs: vec![TxIn::unsigned(alice_coin)],
+        outputs: vec![TxOut { owner: mallory.address(), amount: BLOCK_REWARD - FEE }],
+        lock_time: 0,
+    };
+    report("不签名", pool.add(steal.clone(), &utxos));
+
+    // Wallet::sign 只签属于自己的输入，Mallory 只能手动塞见证
+    let mut own_key = steal.clone();
+    own_key.inputs[0].witness = Some(Witness { public: mallory.keys.public, signature: schnorr::sign(&mallory.keys, &steal.encode(), &mut rng) });
+    report("用自己的私钥签", pool.add(own_key, &utxos));
+
+    let mut borrowed = steal.clone();
+    let pretend = Keypair { secret: mallory.keys.secret, public: alice.keys.public };
+    borrowed.inputs[0].witness = Some(Witness { public: alice.keys.public, signature: schnorr::sign(&pretend, &steal.encode(), &mut rng) });
+    report("出示 Alice 的公钥，签名用自己的私钥", pool.add(borrowed, &utxos));
+
+    let mut redirected = pay.clone();
+    redirected.outputs[0].owner = mallory.address();
+    report("截获 Alice 的交易，把收款人改成自己 (签名原样保留)", pool.add(redirected, &utxos));
+
+    // Alice 自己签的第二笔交易：签名完全合法，但和池子里的第一笔花的是同一个输出，手续费也没多付 (RBF 替换不了)
+    let mut second = Transaction {
+        inputs: vec![TxIn::unsigned(alice_coin)],
+        outputs: vec![TxOut { owner: mallory.address(), amount: 10 }, TxOut { owner: alice.address(), amount: BLOCK_REWARD - 10 - FEE }],
+        lock_time: 0,
+    };
+    alice.sign(&mut second, &utxos, &mut rng);
+    report("骗 Alice 再签一笔花同一个输出的交易", pool.add(second, &utxos));
+    println!("  交易池里现在有 {} 笔交易", pool.len());
+
+    // ==========================================
+    // 4. 打包
+    // ==========================================
+    println!("\n[4] 矿工打包交易池");
+    let mut txs = pool.take_all();
+    let fees: u64 = txs.iter().map(|tx| utxos.validate_tx(tx).unwrap_or(0)).sum();
+    txs.insert(0, Transaction::coinbase(&miner.address(), BLOCK_REWARD + fees, chain.height() + 1));
+    let mut next = utxos.clone();
+    match next.apply_block(&chain.candidate(txs.clone())).and_then(|_| chain.mine_block(txs).map_err(|e| e.to_string())) {
+        Ok(result) => {
+            utxos = next;
+            println!("  区块 #{} 上链 (尝试 {} 次)，交易池剩 {} 笔{}", result.header.height, result.attempts, pool.len(), if pool.is_empty() { "，已清空" } else { "" });
+        }
+        Err(e) => println!("  ❌ 出块失败 (不应该发生): {}", e),
+    }
+    show_balances(&utxos, &[&alice, &bob, &mallory, &miner]);
+    report("把已经上链的 Alice 的交易再提交一次", pool.add(pay, &utxos));
+}
+
+/*
+关键点总结：
+    1. 锁和钥匙：
+        输出上的地址是"锁"，花它时出示的 (公钥, 签名) 是"钥匙"。
+        address(公钥) == 地址 证明钥匙配这把锁；签名有效证明真的持有私钥。
+
+    2. 签名签的是整笔交易：
+        改一个收款地址、一个金额，encode() 就变了，原签名失效 (第 3 节第 4 种)。
+        所以中间人截获交易也没法改；只能原样转发或者丢弃。
+
+    3. txid 不含签名 (SegWit 的做法)：
+        签名是在 txid 确定之后才加上去的，也不会因为签名编码被改动而换 txid；
+        但区块的 Merkle 根用的是带签名的编码，签名同样被区块头承诺。
+
+    4. 交易池是第一道关：
+        进池前就检查签名、余额和池内冲突，垃圾交易不会占空间、也不会被广播出去。
+        出块时还要再检查一遍 (下一步的区块验证流水线)，因为别的矿工打包的区块不一定经过你的交易池。
+*/
@@ -0,0 +1,161 @@
+// src/s06_chain/ex04_validation.rs
+use crate::common::rng::SimpleRng;
+
+use super::block::{merkle_root, Block};
+use super::chain::Chain;
+use super::pow;
+use super::utxo::{Transaction, TxIn, TxOut, UtxoSet, BLOCK_REWARD};
+use super::validation::{validate_block, BlockError};
+use super::wallet::Wallet;
+
+/*
+业务场景：别的矿工广播了一个区块，收不收？
+    ex03 的交易池只管"进池"的交易；但区块可以是别人打包的，里面的交易从没经过你的交易池。
+    节点收到区块要自己从头验一遍，任何一道关没过就整个丢掉。
+
+本练习：
+    1. 准备一个诚实的区块 #1 (Alice 签名转账给 Bob)，validate_block 通过
+    2. ❌ 九个故意弄坏的区块，每个只坏一处，看它卡在流水线的哪一道关
+    3. 诚实的区块上链；同一个区块再发一次
+*/
+
+const DIFFICULTY: u32 = 8;
+const FEE: u64 = 1;
+
+fn report(label: &str, result: Result<(), BlockError>) {
+    match result {
+        Ok(()) => println!("  {:<36} ✅ 通过", label),
+        Err(e) => println!("  {:<36} ❌ [{}] {}", label, e.stage(), e),
+    }
+}
+
+// 改完区块内容后重算 Merkle 根、重新挖矿：保证除了故意弄坏的那一处，其它检查都能过
+fn remine(mut block: Block<Transaction>) -> Block<Transaction> {
+    block.header.merkle_root = merkle_root(&block.txs);
+    block.header = pow::mine(block.header).header;
+    block
+}
+
+pub fn run() {
+    println!("--- S06 Ex04: 区块验证流水线 (BlockError) ---");
+    let mut rng = SimpleRng::from_time();
+
+    // ==========================================
+    // 1. 诚实的区块
+    // ==========================================
+    println!("\n[1] 诚实的区块：Alice 转 20 给 Bob，Miner 领 奖励 {} + 手续费 {}", BLOCK_REWARD, FEE);
+    let alice = Wallet::generate("Alice", &mut rng);
+    let bob = Wallet::generate("Bob", &mut rng);
+    let miner = Wallet::generate("Miner", &mut rng);
+    let mut chain: Chain<Transaction> = Chain::new(DIFFICULTY, vec![Transaction::coinbase(&alice.address(), BLOCK_REWARD, 0)]);
+    let utxos = match UtxoSet::from_chain(&chain) {
+        Ok(utxos) => utxos,
+        Err(e) => {
+            println!("  ❌ 创世块无法执行 (不应该发生): {}", e);
+            return;
+        }
+    };
+    let mut pay = match utxos.build_transfer(&alice.address(), &bob.address(), 20, FEE) {
+        Ok(tx) => tx,
+        Err(e) => {
+            println!("  ❌ {}", e);
+            return;
+        }
+    };
+    alice.sign(&mut pay, &utxos, &mut rng);
+    let coinbase = Transaction::coinbase(&miner.address(), BLOCK_REWARD + FEE, chain.height() + 1);
+    let honest = remine(chain.candidate(vec![coinbase.clone(), pay.clone()]));
+    println!("  区块 #{} 哈希 {}.. ({} 笔交易)", honest.header.height, &hex::encode(honest.hash())[..16], honest.txs.len());
+    report("诚实的区块", validate_block(&chain, &honest));
+
+    // ==========================================
+    // 2. 弄坏的区块
+    // ==========================================
+    println!("\n[2] ❌ 每个区块只坏一处 (除特别说明外都重新挖过矿，PoW 是合格的)");
+
+    // 链接
+    let mut b = honest.clone();
+    b.header.height = 5;
+    report("高度跳到 #5", validate_block(&chain, &remine(b)));
+
+    let mut b = honest.clone();
+    b.header.prev_hash = [0xab; 32];
+    report("prev_hash 指向不存在的区块", validate_block(&chain, &remine(b)));
+
+    // 工作量
+    let mut b = honest.clone();
+    b.header.difficulty = 2;
+    report("自己把难度改成 2 再挖", validate_block(&chain, &remine(b)));
+
+    // 挖好之后改 nonce (不重新挖)；万一改完碰巧仍然合格就继续往后改
+    let mut b = honest.clone();
+    b.header.nonce += 1;
+    while pow::meets_target(&b.header) {
+        b.header.nonce += 1;
+    }
+    report("挖好后改了 nonce，没重新挖", validate_block(&chain, &b));
+
+    // Merkle：区块头原样保留，只改交易
+    let mut b = honest.clone();
+    b.txs[1].outputs[0].amount = 45;
+    report("挖好后把 Bob 收的 20 改成 45", validate_block(&chain, &b));
+
+    // 交易
+    let mut b = honest.clone();
+    b.txs.remove(0);
+    report("没有 coinbase", validate_block(&chain, &remine(b)));
+
+    let mut b = honest.clone();
+    b.txs[1].inputs[0].witness = None;
+    report("Alice 的交易去掉签名", validate_block(&chain, &remine(b)));
+
+    // 第二笔同样是 Alice 亲手签的，单独看完全合法
+    let mut again = Transaction {
+        inputs: vec![TxIn::unsigned(pay.inputs[0].prev)],
+        outputs: vec![TxOut { owner: alice.address(), amount: BLOCK_REWARD - FEE }],
+        lock_time: 0,
+    };
+    alice.sign(&mut again, &utxos, &mut rng);
+    let mut b = honest.clone();
+    b.txs.push(again);
+    report("同一个输出在区块里花两次", validate_block(&chain, &remine(b)));
+
+    let mut b = honest.clone();
+    b.txs[0] = Transaction::coinbase(&miner.address(), 100, chain.height() + 1);
+    report("coinbase 给自己发 100", validate_block(&chain, &remine(b)));
+
+    // ==========================================
+    // 3. 上链
+    // ==========================================
+    println!("\n[3] 诚实的区块上链");
+    match chain.append(honest.clone()) {
+        Ok(()) => println!("  高度 {}，整条链 {}", chain.height(), if chain.validate().is_ok() { "✅" } else { "❌" }),
+        Err(e) => println!("  ❌ 上链失败 (不应该发生): {}", e),
+    }
+    if let Ok(utxos) = UtxoSet::from_chain(&chain) {
+        for w in [&alice, &bob, &miner] {
+            println!("    {:<6} 余额 {}", w.name, utxos.balance(&w.address()));
+        }
+    }
+    report("同一个区块再广播一次", validate_block(&chain, &honest));
+}
+
+/*
+关键点总结：
+    1. 流水线按成本排序：
+        链接、工作量只看区块头，一次哈希就能判断；Merkle 要哈希全部交易；交易检查要验签、查 UTXO。
+        伪造 PoW 的垃圾区块几乎不花验证者的算力。
+
+    2. 每道关防的东西不同：
+        链接   —— 区块接错了位置 (或者是旧区块重放)
+        工作量 —— 没花算力；"自己降低难度"同样不行，难度是链规定的，不是区块自己说了算
+        Merkle —— 挖好之后偷换交易 (区块头没变，PoW 还是合格的)
+        交易   —— 区块头全对，但内容违反记账规则：没签名、双花、多领奖励
+
+    3. 类型化的错误：
+        BlockError 是枚举，调用方可以 match：哪道关没过、具体哪笔交易出错都带在错误里，
+        不用去解析错误字符串。Display 负责给人看，stage() 负责给程序分类。
+
+    4. 区块和交易池是两套关卡：
+        Mempool::add 只拦住经过自己的交易；别人打包的区块里的每一笔都要在 validate_block 里重新验。
+*/
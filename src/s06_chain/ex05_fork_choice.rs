@@ -0,0 +1,172 @@
+// src/s06_chain/ex05_fork_choice.rs
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::s05_zk_lab::hash::Digest;
+
+use super::block::{merkle_root, now_secs, Block, BlockHeader};
+use super::chain::Chain;
+use super::fork::{BlockTree, ForkChoice};
+use super::pow;
+
+/*
+业务场景：两个矿工同时挖出了下一个块
+    网络延迟让一部分节点先看到 A 的块，另一部分先看到 B 的块，链就分叉了。
+    每个节点都要独立地决定"哪条是主链"，而且所有诚实节点最终要得出同一个答案。
+
+本练习：
+    1. 手工造一个分叉：两个同高度的块 (先到先得)，再让其中一支变长
+    2. 难度不同的分支：最长链和最重链给出不同答案
+    3. 两个挖矿线程赛跑，各挖各的分支，看主链头什么时候切换
+*/
+
+const DIFFICULTY: u32 = 8;
+
+// 在 parent 后面挖一个块，交易只有一笔写着矿工名字的 coinbase
+fn extend(parent: &Block, miner: &str, difficulty: u32) -> Block {
+    let txs = vec![format!("coinbase -> {}: 50 (高度 {})", miner, parent.header.height + 1)];
+    let header = BlockHeader {
+        height: parent.header.height + 1,
+        prev_hash: parent.hash(),
+        merkle_root: merkle_root(&txs),
+        state_root: [0u8; 32],
+        timestamp: now_secs(),
+        difficulty,
+        nonce: 0,
+    };
+    Block { header: pow::mine(header).header, txs }
+}
+
+fn name(labels: &HashMap<Digest, String>, block: &Block) -> String {
+    labels.get(&block.hash()).cloned().unwrap_or_else(|| format!("#{}", block.header.height))
+}
+
+fn show_heads(tree: &BlockTree, labels: &HashMap<Digest, String>) {
+    let tips: Vec<String> = tree.tips().iter().map(|tip| name(labels, tip)).collect();
+    let longest = tree.head(ForkChoice::LongestChain);
+    let heaviest = tree.head(ForkChoice::MostWork);
+    println!("    tips = [{}]", tips.join(", "));
+    println!(
+        "    最长链头 {} (高度 {})   最重链头 {} (累计工作量 {})",
+        name(labels, longest),
+        longest.header.height,
+        name(labels, heaviest),
+        tree.chain_work(&heaviest.hash())
+    );
+}
+
+pub fn run() {
+    println!("--- S06 Ex05: 分叉选择 (最长链 vs 最重链) ---");
+    let chain = Chain::new(DIFFICULTY, vec![String::from("coinbase -> Satoshi: 50")]);
+    let genesis = chain.blocks[0].clone();
+    let mut labels = HashMap::new();
+    labels.insert(genesis.hash(), String::from("创世"));
+
+    // ==========================================
+    // 1. 同难度分叉
+    // ==========================================
+    println!("\n[1] 同难度 ({}) 的分叉", DIFFICULTY);
+    let mut tree = BlockTree::new(genesis.clone(), DIFFICULTY);
+    let a1 = extend(&genesis, "A", DIFFICULTY);
+    let b1 = extend(&genesis, "B", DIFFICULTY);
+    for (label, block) in [("A1", &a1), ("B1", &b1)] {
+        labels.insert(block.hash(), label.to_string());
+        if let Err(e) = tree.insert(block.clone()) {
+            println!("  ❌ {} (不应该发生)", e);
+        }
+    }
+    println!("  A1、B1 都接在创世块后面，高度一样；A1 先到");
+    show_heads(&tree, &labels);
+
+    let b2 = extend(&b1, "B", DIFFICULTY);
+    labels.insert(b2.hash(), String::from("B2"));
+    if let Err(e) = tree.insert(b2.clone()) {
+        println!("  ❌ {} (不应该发生)", e);
+    }
+    println!("  B 的支持者在 B1 后面又挖出 B2 —— 两条规则都切到 B");
+    show_heads(&tree, &labels);
+    let path: Vec<String> = tree.branch(&b2.hash()).iter().map(|b| name(&labels, b)).collect();
+    println!("    主链: {}  (A1 成了孤立的分支，里面的交易要回到交易池 —— 见下一个练习)", path.join(" -> "));
+
+    // ==========================================
+    // 2. 难度不同的分支
+    // ==========================================
+    let heavy = DIFFICULTY + 4;
+    println!("\n[2] C 在创世块后面挖了一个难度 {} 的块 (工作量 2^{} = {}，B 的每个块只有 {})", heavy, heavy, pow::block_work(heavy), pow::block_work(DIFFICULTY));
+    let c1 = extend(&genesis, "C", heavy);
+    labels.insert(c1.hash(), String::from("C1"));
+    if let Err(e) = tree.insert(c1) {
+        println!("  ❌ {} (不应该发生)", e);
+    }
+    show_heads(&tree, &labels);
+    println!("  最长链只数块数，挖一串低难度块就能抢主链；比特币比的是累计工作量");
+
+    let cheap = extend(&b2, "Mallory", DIFFICULTY - 4);
+    match tree.insert(cheap) {
+        Ok(()) => println!("  ❌ 接受了低于难度下限的块 (不应该发生)"),
+        Err(e) => println!("  Mallory 用难度 {} 接在 B2 后面: ❌ {}", DIFFICULTY - 4, e),
+    }
+
+    // ==========================================
+    // 3. 两个矿工线程赛跑
+    // ==========================================
+    let (fast, slow) = (DIFFICULTY + 2, DIFFICULTY + 6);
+    println!("\n[3] 两个挖矿线程：A 难度 {} 挖 12 个块，B 难度 {} 挖 3 个块，各自只延长自己的分支", fast, slow);
+    let mut tree = BlockTree::new(genesis.clone(), DIFFICULTY);
+    let (tx, rx) = mpsc::channel();
+    for (miner, difficulty, count) in [("A", fast, 12), ("B", slow, 3)] {
+        let tx = tx.clone();
+        let mut parent = genesis.clone();
+        thread::spawn(move || {
+            for _ in 0..count {
+                let block = extend(&parent, miner, difficulty);
+                // 区块的所有权随消息转移给节点，矿工自己留一份副本继续往后挖
+                tx.send((miner, block.clone())).unwrap();
+                parent = block;
+            }
+        });
+    }
+    drop(tx);
+
+    let mut heads = (genesis.hash(), genesis.hash());
+    for (miner, block) in rx {
+        let label = format!("{}{}", miner, block.header.height);
+        labels.insert(block.hash(), label.clone());
+        if let Err(e) = tree.insert(block) {
+            println!("  ❌ {} (不应该发生)", e);
+            continue;
+        }
+        let longest = tree.head(ForkChoice::LongestChain);
+        let heaviest = tree.head(ForkChoice::MostWork);
+        let switched = |old: Digest, new: &Block| if old != new.hash() && new.header.prev_hash != old { " ⇐ 切换" } else { "" };
+        println!(
+            "  收到 {:<4} 最长链头 {:<4}{:<6} 最重链头 {:<4} (工作量 {:>6}){}",
+            label,
+            name(&labels, longest),
+            switched(heads.0, longest),
+            name(&labels, heaviest),
+            tree.chain_work(&heaviest.hash()),
+            switched(heads.1, heaviest)
+        );
+        heads = (longest.hash(), heaviest.hash());
+    }
+    println!("  最长链: A 的分支 (块多)；最重链: B 的分支 (每个块的工作量是 A 的 {} 倍)", pow::block_work(slow) / pow::block_work(fast));
+}
+
+/*
+关键点总结：
+    1. 链其实是一棵树：
+        BlockTree 用 HashMap<哈希, 区块> 存所有见过的块，每个块通过 prev_hash 指向父块。
+        没有子块的块是 tip；分叉选择就是在 tips 里挑一个。
+
+    2. 最长链 vs 最重链：
+        难度固定时两者等价；难度会变 (见后面的难度调整练习) 时只有累计工作量有意义，
+        否则攻击者在自己的私有分支上把难度调低，就能快速挖出一条"更长"的链。
+
+    3. 先到先得：
+        平局时不切换。否则两个同样好的分支来回切，节点的状态 (UTXO、交易池) 会一直抖动。
+
+    4. 切换主链 = 重组 (reorg)：
+        旧主链上不在新主链里的块要回滚，里面的交易退回交易池 —— 下一个练习。
+*/
@@ -0,0 +1,271 @@
+// src/s06_chain/ex06_reorg.rs
+use std::collections::HashMap;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::short_hex;
+
+use super::block::merkle_root;
+use super::node::{Node, Snapshot};
+use super::pow;
+use super::utxo::{OutPoint, Transaction, TxId, TxIn, TxOut, BLOCK_REWARD};
+use super::wallet::Wallet;
+
+/*
+业务场景：Bob 收到了 Alice 的钱，也看到了一个确认，结果钱没了
+    Alice 付钱给 Bob 的交易进了区块 #3；与此同时她自己偷偷从 #2 后面挖了一条分支，
+    分支里同一枚币转回给了她自己。等私有分支比公开的主链长，她把它广播出去 ——
+    所有节点按最长链规则切换过去，#3、#4 被回滚，Bob 收到的钱凭空消失。
+
+本练习：
+    1. 公共历史 #1、#2，然后主链 #3 (三笔转账)、#4 (Bob 花刚收到的钱)
+    2. Alice 的私有分支 #3'、#4'、#5'
+    3. 节点收到更长的分支：回滚了哪些块、哪些 UTXO 被撤销 / 恢复、旧交易各自去了哪里
+    4. ❌ 中间夹着一个不合法区块的更长分支：重组整体失败，旧链原样保留
+    每个区块头都带着状态根；分叉点记一个快照 (高度 + 哈希 + 状态根)，
+    要回到分叉点时 revert_to 按撤销数据往回拆，拆完和快照对一下就知道状态恢复得对不对。
+*/
+
+const DIFFICULTY: u32 = 8;
+const FEE: u64 = 1;
+
+fn who<'a>(address: &str, wallets: &[&'a Wallet]) -> &'a str {
+    wallets.iter().find(|w| w.address() == address).map_or("?", |w| w.name.as_str())
+}
+
+fn show_balances(node: &Node, wallets: &[&Wallet]) {
+    let line: Vec<String> = wallets.iter().map(|w| format!("{} {}", w.name, node.utxos().balance(&w.address()))).collect();
+    let root = node.chain().tip().header.state_root;
+    println!("    高度 {}  状态根 {}..  余额: {}", node.chain().height(), short_hex(&root), line.join("  "));
+}
+
+// 复制一份节点，退回到快照的高度；状态根要和快照里记的一样
+fn fork_from(node: &Node, snapshot: &Snapshot) -> Option<Node> {
+    let mut fork = node.clone();
+    match fork.revert_to(snapshot.height) {
+        Ok(removed) if fork.snapshot() == *snapshot => {
+            println!("  退回 #{}：拆掉 {} 个块，状态根 {}.. 和快照一致 ✅", snapshot.height, removed.len(), short_hex(&snapshot.state_root));
+            Some(fork)
+        }
+        Ok(_) => {
+            println!("  ❌ 退回 #{} 之后和快照对不上 (不应该发生)", snapshot.height);
+            None
+        }
+        Err(e) => {
+            println!("  ❌ {} (不应该发生)", e);
+            None
+        }
+    }
+}
+
+// 转账 + 签名 + 进交易池，返回交易的副本 (要广播给别的节点)
+fn pay(node: &mut Node, from: &Wallet, to: &Wallet, amount: u64, rng: &mut SimpleRng) -> Result<Transaction, String> {
+    let mut tx = node.utxos().build_transfer(&from.address(), &to.address(), amount, FEE)?;
+    from.sign(&mut tx, node.utxos(), rng);
+    node.submit(tx.clone())?;
+    Ok(tx)
+}
+
+pub fn run() {
+    println!("--- S06 Ex06: 链重组 (Reorg) ---");
+    let mut rng = SimpleRng::from_time();
+    let alice = Wallet::generate("Alice", &mut rng);
+    let bob = Wallet::generate("Bob", &mut rng);
+    let carol = Wallet::generate("Carol", &mut rng);
+    let miner = Wallet::generate("Miner", &mut rng);
+    let wallets = [&alice, &bob, &carol, &miner];
+    let mut labels: HashMap<TxId, String> = HashMap::new();
+
+    // ==========================================
+    // 1. 公共历史 + 主链
+    // ==========================================
+    println!("\n[1] 公共历史：创世块给 Alice {}，#1 Miner 挖，#2 Carol 挖", BLOCK_REWARD);
+    let mut node = match Node::new(DIFFICULTY, vec![Transaction::coinbase(&alice.address(), BLOCK_REWARD, 0)]) {
+        Ok(node) => node,
+        Err(e) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    let common = node.mine(&miner.address()).and_then(|_| node.mine(&carol.address()));
+    if let Err(e) = common {
+        println!("  ❌ 出块失败 (不应该发生): {}", e);
+        return;
+    }
+    show_balances(&node, &wallets);
+    // Alice 在这里分叉出去：记下快照就够了，不用复制整个节点
+    let fork_point = node.snapshot();
+
+    println!("  主链 #3: Alice -> Bob 20，Miner -> Bob 10，Carol -> Bob 15");
+    let transfers = [(&alice, 20, "Alice -> Bob 20"), (&miner, 10, "Miner -> Bob 10"), (&carol, 15, "Carol -> Bob 15")];
+    let mut broadcast = Vec::new();
+    for (from, amount, label) in transfers {
+        match pay(&mut node, from, &bob, amount, &mut rng) {
+            Ok(tx) => {
+                labels.insert(tx.txid(), label.to_string());
+                broadcast.push(tx);
+            }
+            Err(e) => println!("  ❌ {}: {} (不应该发生)", label, e),
+        }
+    }
+    if let Err(e) = node.mine(&miner.address()) {
+        println!("  ❌ 出块失败 (不应该发生): {}", e);
+        return;
+    }
+    println!("  主链 #4: Bob 从 Alice 给的那 20 里转 5 给 Carol (花的是 #3 里创建的输出)");
+    let Some(from_alice) = broadcast.first() else {
+        return;
+    };
+    let mut spend = Transaction {
+        inputs: vec![TxIn::unsigned(OutPoint { txid: from_alice.txid(), index: 0 })],
+        outputs: vec![TxOut { owner: carol.address(), amount: 5 }, TxOut { owner: bob.address(), amount: 20 - 5 - FEE }],
+        lock_time: 0,
+    };
+    bob.sign(&mut spend, node.utxos(), &mut rng);
+    labels.insert(spend.txid(), String::from("Bob -> Carol 5"));
+    if let Err(e) = node.submit(spend) {
+        println!("  ❌ {} (不应该发生)", e);
+    }
+    if let Err(e) = node.mine(&miner.address()) {
+        println!("  ❌ 出块失败 (不应该发生): {}", e);
+        return;
+    }
+    for block in &node.chain().blocks[3..] {
+        labels.insert(block.txs[0].txid(), format!("#{} 的 coinbase", block.header.height));
+    }
+    show_balances(&node, &wallets);
+
+    // ==========================================
+    // 2. Alice 的私有分支
+    // ==========================================
+    println!("\n[2] Alice 从 #2 后面私下挖：#3' 把同一枚币转回给自己，并顺手打包 Miner 那笔公开交易");
+    let Some(mut attacker) = fork_from(&node, &fork_point) else {
+        return;
+    };
+    match pay(&mut attacker, &alice, &alice, BLOCK_REWARD - FEE, &mut rng) {
+        Ok(tx) => {
+            labels.insert(tx.txid(), String::from("Alice -> Alice 49 (双花)"));
+        }
+        Err(e) => println!("  ❌ {} (不应该发生)", e),
+    }
+    // Miner 的转账在网络里广播过，Alice 的节点也收到了 (broadcast[1])
+    if let Some(miner_tx) = broadcast.get(1) {
+        if let Err(e) = attacker.submit(miner_tx.clone()) {
+            println!("  ❌ {} (不应该发生)", e);
+        }
+    }
+    for _ in 0..3 {
+        if let Err(e) = attacker.mine(&alice.address()) {
+            println!("  ❌ 出块失败 (不应该发生): {}", e);
+            return;
+        }
+    }
+    show_balances(&attacker, &wallets);
+
+    // ==========================================
+    // 3. 重组
+    // ==========================================
+    println!("\n[3] 节点收到 Alice 的分支 (高度 {} > 主链高度 {})", attacker.chain().height(), node.chain().height());
+    let too_short = attacker.chain().blocks[3..4].to_vec();
+    if let Err(e) = node.reorg(too_short) {
+        println!("  先只收到 #3': {}", e);
+    }
+    let name = |txid: &TxId| labels.get(txid).cloned().unwrap_or_else(|| short_hex(txid));
+    match node.reorg(attacker.chain().blocks[3..].to_vec()) {
+        Ok(report) => {
+            println!("  分叉点 #{}", report.fork_height);
+            for (height, hash, undo) in &report.rolled_back {
+                let created: Vec<String> = undo.created.iter().map(|op| op.to_string()).collect();
+                let restored: Vec<String> =
+                    undo.spent.iter().map(|(op, out)| format!("{} ({} {})", op, who(&out.owner, &wallets), out.amount)).collect();
+                println!("  回滚 #{} {}..", height, short_hex(hash));
+                println!("      撤销它创建的输出: [{}]", created.join(", "));
+                println!("      恢复它花掉的输出: [{}]", restored.join(", "));
+            }
+            let connected: Vec<String> = report.connected.iter().map(|(h, hash)| format!("#{}' {}..", h, short_hex(hash))).collect();
+            println!("  接上: {}", connected.join(", "));
+            println!("  旧链上的交易:");
+            for txid in &report.already_confirmed {
+                println!("      {:<22} 新链上也有，不用管", name(txid));
+            }
+            for txid in &report.reinjected {
+                println!("      {:<22} ✅ 回到交易池，等下一个块", name(txid));
+            }
+            for (txid, reason) in &report.dropped {
+                println!("      {:<22} ❌ 丢弃: {}", name(txid), reason);
+            }
+        }
+        Err(e) => println!("  ❌ 重组失败 (不应该发生): {}", e),
+    }
+    show_balances(&node, &wallets);
+    println!("  Bob 在 #3 收到的 20 没了：那枚币在新链上已经转回给 Alice。一个确认不够");
+    match node.mine(&miner.address()) {
+        Ok(block) => println!("  Miner 挖出 #{}，打包了交易池里的 {} 笔交易", block.header.height, block.txs.len() - 1),
+        Err(e) => println!("  ❌ 出块失败 (不应该发生): {}", e),
+    }
+    show_balances(&node, &wallets);
+
+    // ==========================================
+    // 4. 不合法的更长分支
+    // ==========================================
+    println!("\n[4] ❌ 又一条从 #2 分出去、更长的分支，但最后一个块的 coinbase 多领了钱");
+    let fork_height = fork_point.height as usize;
+    let Some(mut evil) = fork_from(&node, &fork_point) else {
+        return;
+    };
+    while evil.chain().height() <= node.chain().height() {
+        if let Err(e) = evil.mine(&carol.address()) {
+            println!("  ❌ 出块失败 (不应该发生): {}", e);
+            return;
+        }
+    }
+    let honest = evil.chain().blocks[fork_height + 1..].to_vec();
+    let mut branch = honest.clone();
+    let last = branch.last_mut().expect("branch is longer than the main chain");
+    last.txs[0] = Transaction::coinbase(&carol.address(), 1000, last.header.height);
+    last.header.merkle_root = merkle_root(&last.txs);
+    last.header = pow::mine(last.header.clone()).header;
+    let before = node.snapshot();
+    match node.reorg(branch) {
+        Ok(_) => println!("  ✅ 切换了 (不应该发生)"),
+        Err(e) => println!("  {}", e),
+    }
+    println!("  节点状态和重组之前一样 (高度、最高块、状态根): {}", if node.snapshot() == before { "✅" } else { "❌" });
+
+    println!("  同一条分支，交易都没改，只是最后一个块头里的状态根是编的");
+    let mut branch = honest;
+    let last = branch.last_mut().expect("branch is longer than the main chain");
+    last.header.state_root = [0xab; 32];
+    last.header = pow::mine(last.header.clone()).header;
+    match node.reorg(branch) {
+        Ok(_) => println!("  ✅ 切换了 (不应该发生)"),
+        Err(e) => println!("  {}", e),
+    }
+    println!("  节点状态和重组之前一样 (高度、最高块、状态根): {}", if node.snapshot() == before { "✅" } else { "❌" });
+    show_balances(&node, &wallets);
+}
+
+/*
+关键点总结：
+    1. 回滚需要撤销数据：
+        区块只记录"花了哪个 OutPoint"，被花掉的输出 (主人、金额) 执行后就从 UTXO 集合里删了。
+        connect_block 时把它们存进 BlockUndo，disconnect 时原样放回，同时删掉区块创建的输出。
+
+    2. 状态根和快照：
+        区块头里的状态根给每个高度的状态一个指纹；接块时重算一遍，对不上就拒绝。
+        快照只记 (高度, 哈希, 状态根)，回滚靠撤销数据 —— 只碰这几个块改过的输出，不用复制也不用从创世块重放。
+
+    3. 所有权的流动：
+        disconnect_tip 把 Block 从 chain.blocks 里 pop 出来交还给调用方；
+        reorg 再把其中的交易 move 进交易池 (或者丢弃)。没有任何一笔交易被复制出两份。
+
+    4. 旧交易的三种去向：
+        新链上已经有 —— 不用管；仍然有效 —— 回到交易池，在新链上重新打包；
+        和新链冲突 (被双花) 或者依赖被双花的交易 —— 永远作废；coinbase 随区块作废。
+
+    5. 原子性：
+        新分支中间任何一个块不合法，就先拆掉已经接上的部分、再把旧块按顺序接回去。
+        节点的状态要么是完整的旧链，要么是完整的新链。
+
+    6. 为什么要等确认：
+        确认数 = 交易所在区块之后又挖了多少块。攻击者要追上这么多块的工作量，概率随确认数指数下降。
+*/
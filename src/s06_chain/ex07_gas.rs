@@ -0,0 +1,162 @@
+// src/s06_chain/ex07_gas.rs
+use crate::common::input::read_line;
+
+use super::account::{AccountState, AccountTx, Op, TxStatus};
+use super::gas;
+
+/*
+业务场景：以太坊的交易可以做任意计算，矿工凭什么替你算？
+    每一步操作都标了 gas 价格，交易自己写上最多愿意付多少 gas (gas_limit) 和每单位 gas 的价格 (gas_price)。
+    算到一半 gas 用完了：所有改动撤销，但已经烧掉的 gas 不退 —— 矿工的算力不能白费。
+
+本练习：
+    1. gas 价格表
+    2. 便宜的转账 vs 昂贵的存储 + 计算 (gas_limit 可以自己输入)，对比 gas 和手续费
+    3. ❌ out of gas：停在哪一步、状态有没有变、钱扣了多少
+    4. ❌ 执行失败 (余额不够转账) 和"死循环"
+    5. ❌ 根本不能上链的交易：不收费
+*/
+
+const GAS_PRICE: u64 = 2;
+const MINER: &str = "Miner";
+
+fn describe(status: &TxStatus) -> String {
+    match status {
+        TxStatus::Success => String::from("✅ 成功"),
+        TxStatus::OutOfGas { op } => format!("❌ out of gas (第 {} 个操作)", op + 1),
+        TxStatus::Reverted { op, reason } => format!("❌ 回滚 (第 {} 个操作: {})", op + 1, reason),
+    }
+}
+
+fn run_tx(state: &mut AccountState, label: &str, tx: &AccountTx) {
+    let before = state.balance(&tx.from);
+    match state.execute(tx, MINER) {
+        Ok(receipt) => {
+            println!(
+                "  {:<16} limit {:>7}  used {:>7}  手续费 {:>7}  {} 余额 {} -> {}  {}",
+                label,
+                tx.gas_limit,
+                receipt.gas_used,
+                receipt.fee,
+                tx.from,
+                before,
+                state.balance(&tx.from),
+                describe(&receipt.status)
+            );
+            for log in &receipt.logs {
+                println!("      log: {}", log);
+            }
+        }
+        Err(e) => println!("  {:<16} ❌ 不能上链，不收费: {}", label, e),
+    }
+}
+
+fn storage_of(state: &AccountState, address: &str) -> String {
+    let entries: Vec<String> = state.account(address).map_or(Vec::new(), |a| a.storage.iter().map(|(k, v)| format!("{}={}", k, v)).collect());
+    format!("{{{}}}", entries.join(", "))
+}
+
+pub fn run() {
+    println!("--- S06 Ex07: Gas 计量 ---");
+    let mut state = AccountState::new();
+    state.credit("Alice", 1_000_000);
+    state.credit("Bob", 50_000);
+    let mut nonce = 0;
+    let mut next_tx = |gas_limit, ops| {
+        nonce += 1;
+        AccountTx { from: String::from("Alice"), nonce: nonce - 1, gas_limit, gas_price: GAS_PRICE, ops }
+    };
+
+    // ==========================================
+    // 1. 价格表
+    // ==========================================
+    println!("\n[1] gas 价格表 (gas_price = {}，手续费 = gas_used × gas_price)", GAS_PRICE);
+    let table = [
+        ("交易固定开销", gas::TX_BASE.to_string()),
+        ("Transfer", gas::TRANSFER.to_string()),
+        ("Store 新槽", gas::STORE_NEW.to_string()),
+        ("Store 改旧槽", gas::STORE_UPDATE.to_string()),
+        ("Load", gas::LOAD.to_string()),
+        ("Hash", format!("{} / 轮", gas::HASH_ROUND)),
+        ("Log", format!("{} + {} / 字节", gas::LOG_BASE, gas::LOG_BYTE)),
+    ];
+    for (op, price) in table {
+        println!("  {:<14} {}", op, price);
+    }
+
+    // ==========================================
+    // 2. 便宜 vs 昂贵
+    // ==========================================
+    let expensive_ops = vec![
+        Op::Store { key: String::from("a"), value: 1 },
+        Op::Store { key: String::from("b"), value: 2 },
+        Op::Store { key: String::from("c"), value: 3 },
+        Op::Hash { data: String::from("alice"), rounds: 2000 },
+        Op::Log(String::from("stored a, b, c")),
+    ];
+    let needed: u64 = gas::TX_BASE + 3 * gas::STORE_NEW + 2000 * gas::HASH_ROUND + gas::LOG_BASE + gas::LOG_BYTE * 14;
+    let limit = read_line(&format!("\n[2] 昂贵交易的 gas_limit (需要 {}，直接回车默认 200000): ", needed))
+        .parse::<u64>()
+        .unwrap_or(200_000);
+    run_tx(&mut state, "转账 100 给 Bob", &next_tx(50_000, vec![Op::Transfer { to: String::from("Bob"), amount: 100 }]));
+    run_tx(&mut state, "存 3 个槽 + 计算", &next_tx(limit, expensive_ops.clone()));
+    let updates = vec![Op::Store { key: String::from("a"), value: 10 }, Op::Store { key: String::from("b"), value: 20 }, Op::Load { key: String::from("c") }];
+    run_tx(&mut state, "改写 2 个旧槽", &next_tx(100_000, updates));
+    println!("  Alice 的存储: {}", storage_of(&state, "Alice"));
+    println!("  固定开销就占了转账的 70%；存储最贵，因为它让每个节点永久多存一份数据");
+
+    // ==========================================
+    // 3. out of gas
+    // ==========================================
+    println!("\n[3] ❌ 同样的昂贵交易 (另外 3 个新槽)，gas_limit 只给 70000");
+    let renamed: Vec<Op> = expensive_ops
+        .into_iter()
+        .map(|op| match op {
+            Op::Store { key, value } => Op::Store { key: format!("{}2", key), value },
+            other => other,
+        })
+        .collect();
+    let before = storage_of(&state, "Alice");
+    run_tx(&mut state, "存 3 个槽 + 计算", &next_tx(70_000, renamed));
+    println!("  存储 {} -> {} (前两次 Store 也被撤销了)", before, storage_of(&state, "Alice"));
+    println!("  gas_limit 全部烧掉给了矿工；nonce 照样 +1，这笔交易不能重放");
+
+    // ==========================================
+    // 4. 执行失败 / 死循环
+    // ==========================================
+    println!("\n[4] ❌ 执行到一半失败");
+    let overdraw = vec![Op::Store { key: String::from("d"), value: 4 }, Op::Transfer { to: String::from("Bob"), amount: 10_000_000 }];
+    run_tx(&mut state, "先存再超额转账", &next_tx(100_000, overdraw));
+    println!("  (报错里的余额已经预扣了 gas) 回滚只收用掉的 gas，剩下的退回；存储 {}", storage_of(&state, "Alice"));
+    let forever = vec![Op::Hash { data: String::from("loop"), rounds: u32::MAX }];
+    run_tx(&mut state, "算 2^32 轮哈希", &next_tx(100_000, forever));
+    println!("  先扣 gas 再执行：扣不动就一步都不算，节点不会被卡住");
+
+    // ==========================================
+    // 5. 不能上链
+    // ==========================================
+    println!("\n[5] ❌ 不合法的交易");
+    let transfer = |amount| vec![Op::Transfer { to: String::from("Alice"), amount }];
+    run_tx(&mut state, "nonce 用旧的", &AccountTx { from: String::from("Alice"), nonce: 0, gas_limit: 50_000, gas_price: GAS_PRICE, ops: transfer(1) });
+    run_tx(&mut state, "gas_limit 20000", &AccountTx { from: String::from("Bob"), nonce: 0, gas_limit: 20_000, gas_price: GAS_PRICE, ops: transfer(1) });
+    run_tx(&mut state, "付不起 gas", &AccountTx { from: String::from("Bob"), nonce: 0, gas_limit: 30_000, gas_price: 10, ops: transfer(1) });
+    println!("\n  矿工收到的手续费合计 {}", state.balance(MINER));
+}
+
+/*
+关键点总结：
+    1. 先扣 gas 再执行：
+        GasMeter::charge 在每一步执行之前调用。gas 不够就停，停下来之前没做任何事 ——
+        所以哪怕是 2^32 轮的哈希，节点最多只做了 gas_limit 允许的那么多工作。
+
+    2. 失败也要付钱：
+        预扣的 gas_limit × gas_price 和 nonce + 1 在执行 Op 之前就生效，不在回滚范围内。
+        否则攻击者可以不停地发"执行到最后一步故意失败"的交易，让全网免费陪他计算。
+
+    3. 两种"失败"要分清：
+        不合法 (nonce 不对、付不起 gas) —— 根本不能进区块，不收费 (Err)；
+        执行失败 (out of gas、余额不够转账) —— 交易上链了，状态回滚，收费 (Receipt 里写着失败)。
+
+    4. 价格反映的是全网成本：
+        新开存储槽最贵，因为每个全节点都要永久保存它；纯计算按轮收费；读比写便宜。
+*/
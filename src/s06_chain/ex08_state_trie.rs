@@ -0,0 +1,212 @@
+// src/s06_chain/ex08_state_trie.rs
+use crate::s05_zk_lab::ex01_merkle::MerkleTree;
+use crate::s05_zk_lab::hash::{sha256, short_hex};
+
+use super::account::{Account, AccountState, AccountTx, Op};
+use super::block::{Block, BlockHeader};
+use super::chain::Chain;
+use super::pow;
+use super::trie::{verify_proof, PatriciaTrie};
+
+/*
+业务场景：轻节点想知道 "Bob 现在有多少钱"，但它只下载了区块头。
+    UTXO 链的区块头只承诺了"这个块里有哪些交易"；账户模型的余额是所有历史交易执行出来的结果，
+    必须再承诺一个"执行完之后的整个状态"：state_root。
+    状态每个块都在变、要能按地址查找、还要能证明"某个地址不存在" —— 这就是 Merkle-Patricia 树。
+
+本练习：
+    1. 用几个账户建树，看 Leaf / Extension / Branch 长什么样
+    2. 根只由 (键, 值) 集合决定，和插入顺序无关；改一个余额根就变 (对照二叉 MerkleTree)
+    3. ✅ 存在证明、✅ 不存在证明、❌ 篡改过的证明、❌ 拿新证明对旧根
+    4. 账户模型的链：区块头带 state_root，轻节点只凭区块头验证余额；❌ 状态根作假的区块被拒绝
+    5. 证明大小：Patricia 树 vs 二叉 Merkle 树
+*/
+
+const DIFFICULTY: u32 = 8;
+const MINER: &str = "Miner";
+
+fn show_proof(root: &[u8; 32], key: &str, proof: &[Vec<u8>]) -> String {
+    match verify_proof(root, key.as_bytes(), proof) {
+        Ok(Some(value)) => match Account::decode(&value) {
+            Some((balance, nonce, _)) => format!("✅ {} 存在：余额 {}，nonce {}", key, balance, nonce),
+            None => format!("✅ {} 存在，值 {} 字节", key, value.len()),
+        },
+        Ok(None) => format!("✅ {} 不存在 (这一点同样被证明了)", key),
+        Err(e) => format!("❌ 证明不成立：{}", e),
+    }
+}
+
+fn proof_bytes(proof: &[Vec<u8>]) -> usize {
+    proof.iter().map(|node| node.len()).sum()
+}
+
+fn transfer(from: &str, nonce: u64, to: &str, amount: u64) -> AccountTx {
+    AccountTx { from: String::from(from), nonce, gas_limit: 50_000, gas_price: 1, ops: vec![Op::Transfer { to: String::from(to), amount }] }
+}
+
+// 全节点出块：先在状态副本上执行交易，把得到的状态根写进区块头，再挖矿
+fn produce(chain: &mut Chain<AccountTx>, state: &mut AccountState, txs: Vec<AccountTx>) -> Result<(), String> {
+    let mut block = chain.candidate(txs);
+    let mut next = state.clone();
+    for tx in &block.txs {
+        next.execute(tx, MINER)?;
+    }
+    block.header.state_root = next.state_root();
+    block.header = pow::mine(block.header).header;
+    state.apply_block(&block, MINER)?;
+    chain.append(block).map_err(|e| e.to_string())
+}
+
+pub fn run() {
+    println!("--- S06 Ex08: Merkle-Patricia 状态树 ---");
+
+    // ==========================================
+    // 1. 树的形状
+    // ==========================================
+    let mut state = AccountState::new();
+    let genesis = [("Alice", 1_000_000), ("Alan", 300), ("Bob", 50_000), ("Bobby", 7), ("Carol", 80_000)];
+    for (address, amount) in genesis {
+        state.credit(address, amount);
+    }
+    let trie = state.trie();
+    println!("\n[1] {} 个账户的状态树 (键 = 地址的字节，按十六进制 nibble 展开)", genesis.len());
+    for (address, _) in genesis {
+        println!("  {:<6} -> {}", address, hex::encode(address));
+    }
+    for line in trie.describe() {
+        println!("  {}", line);
+    }
+    for key in ["Bob", "Bo"] {
+        let found = trie.get(key.as_bytes()).and_then(Account::decode).map_or(String::from("没有这个账户"), |(balance, _, _)| format!("余额 {}", balance));
+        println!("  get({:?}) -> {}", key, found);
+    }
+    println!("  所有地址都以 nibble 4 开头 -> 根是 Extension；\"Alice\"/\"Alan\" 再共享 6c6；\"Bob\" 是 \"Bobby\" 的前缀 -> 它的值放在 Branch 的值槽里");
+
+    // ==========================================
+    // 2. 顺序无关 / 改一个余额
+    // ==========================================
+    println!("\n[2] 同样的账户，反过来插入");
+    let mut reversed = PatriciaTrie::new();
+    for (address, _) in genesis.iter().rev() {
+        let account = state.account(address).expect("credited above");
+        reversed.insert(address.as_bytes(), account.encode());
+    }
+    println!("  Patricia 根: 正序 {}..  倒序 {}..  {}", short_hex(&trie.root_hash()), short_hex(&reversed.root_hash()), if trie.root_hash() == reversed.root_hash() { "✅ 一样" } else { "❌ 不一样" });
+    let leaves: Vec<Vec<u8>> = genesis.iter().map(|(address, _)| state.account(address).expect("credited above").encode()).collect();
+    let forward_root = MerkleTree::new(leaves.clone()).root_hash();
+    let backward_root = MerkleTree::new(leaves.into_iter().rev().collect()).root_hash();
+    println!("  二叉 Merkle 根: 正序 {}..  倒序 {}..  {}", &forward_root[..8], &backward_root[..8], if forward_root == backward_root { "一样" } else { "不一样 (叶子按位置排)" });
+    let mut changed = state.clone();
+    changed.credit("Bobby", 1);
+    println!("  Bobby 余额 +1：状态根 {}.. -> {}..", short_hex(&state.state_root()), short_hex(&changed.state_root()));
+
+    // ==========================================
+    // 3. 证明
+    // ==========================================
+    let root = trie.root_hash();
+    println!("\n[3] 验证者只有状态根 {}..", short_hex(&root));
+    for key in ["Alice", "Bob", "Alex", "Dave"] {
+        let proof = trie.prove(key.as_bytes());
+        println!("  {:<6} 证明 {} 个节点 {:>4} 字节  {}", key, proof.len(), proof_bytes(&proof), show_proof(&root, key, &proof));
+    }
+
+    println!("  ❌ 全节点把 Alice 的余额改成 9,999,999 再发过来");
+    let mut forged = trie.clone();
+    let rich = Account { balance: 9_999_999, ..state.account("Alice").expect("credited above").clone() };
+    forged.insert(b"Alice", rich.encode());
+    println!("     {}", show_proof(&root, "Alice", &forged.prove(b"Alice")));
+    println!("  ❌ 直接改证明最后一个节点的一个字节");
+    let mut tampered = trie.prove(b"Alice");
+    if let Some(byte) = tampered.last_mut().and_then(|leaf| leaf.last_mut()) {
+        *byte ^= 1;
+    }
+    println!("     {}", show_proof(&root, "Alice", &tampered));
+    println!("  ❌ Bobby 改过余额之后的证明，拿去对旧根");
+    println!("     {}", show_proof(&root, "Bobby", &changed.trie().prove(b"Bobby")));
+
+    // ==========================================
+    // 4. 区块头里的状态根
+    // ==========================================
+    println!("\n[4] 账户模型的链 (难度 {})：每个区块头带执行完之后的 state_root", DIFFICULTY);
+    // 创世分配不经过交易，这里不给创世块写状态根 (全 0)
+    let mut chain: Chain<AccountTx> = Chain::new(DIFFICULTY, vec![]);
+    let rounds = [
+        vec![transfer("Alice", 0, "Bob", 1_000), transfer("Carol", 0, "Dave", 10)],
+        vec![transfer("Bob", 0, "Carol", 20_000), transfer("Alice", 1, "Dave", 50_000)],
+        vec![transfer("Dave", 0, "Alan", 1_000)],
+    ];
+    for txs in rounds {
+        if let Err(e) = produce(&mut chain, &mut state, txs) {
+            println!("  ❌ 出块失败 (不应该发生): {}", e);
+            return;
+        }
+        let header = &chain.tip().header;
+        println!("  #{} hash {}..  state_root {}..  ({} 笔交易)", header.height, short_hex(&chain.tip().hash()), short_hex(&header.state_root), chain.tip().txs.len());
+    }
+
+    // 轻节点：只存区块头，链接关系和 PoW 自己检查 (这里省略)，余额靠全节点给的证明
+    let headers: Vec<BlockHeader> = chain.blocks.iter().map(|block| block.header.clone()).collect();
+    let latest = headers.last().expect("chain has blocks");
+    println!("  轻节点只存了 {} 个区块头 (每个几十字节)，问全节点最新状态：", headers.len());
+    let full_trie = state.trie();
+    for key in ["Bob", "Dave", "Eve"] {
+        println!("    {}", show_proof(&latest.state_root, key, &full_trie.prove(key.as_bytes())));
+    }
+    let stale = headers[1].state_root;
+    println!("    ❌ 拿 #1 的区块头验证最新的 Bob 证明: {}", show_proof(&stale, "Bob", &full_trie.prove(b"Bob")));
+
+    println!("  ❌ 矿工在状态里给自己多加 1000，按这个状态算根写进区块头");
+    let mut block: Block<AccountTx> = chain.candidate(vec![transfer("Alice", 2, "Carol", 1)]);
+    let mut cheat = state.clone();
+    for tx in &block.txs {
+        let _ = cheat.execute(tx, MINER);
+    }
+    cheat.credit(MINER, 1_000);
+    block.header.state_root = cheat.state_root();
+    block.header = pow::mine(block.header).header;
+    match state.apply_block(&block, MINER) {
+        Ok(_) => println!("     ✅ 接受了 (不应该发生)"),
+        Err(e) => println!("     其他节点自己执行一遍，被拒绝: {}", e),
+    }
+    println!("     PoW 和 Merkle 根都没问题 —— 只有重新执行交易才能发现状态根是假的");
+
+    // ==========================================
+    // 5. 证明大小
+    // ==========================================
+    println!("\n[5] 证明大小：Patricia 树 (键是 20 字节的哈希地址) vs 二叉 Merkle 树");
+    println!("  {:>6}  {:>14}  {:>10}  {:>14}  {:>10}", "账户数", "Patricia 节点", "字节", "Merkle 兄弟", "字节");
+    for n in [16u32, 256, 4096] {
+        let accounts: Vec<(Vec<u8>, Vec<u8>)> = (0..n).map(|i| (sha256(&i.to_be_bytes())[..20].to_vec(), Account { balance: i as u64, ..Account::default() }.encode())).collect();
+        let mut trie = PatriciaTrie::new();
+        for (key, value) in &accounts {
+            trie.insert(key, value.clone());
+        }
+        let target = n as usize / 2;
+        let proof = trie.prove(&accounts[target].0);
+        let verified = verify_proof(&trie.root_hash(), &accounts[target].0, &proof).ok().flatten().as_deref() == Some(accounts[target].1.as_slice());
+        let merkle = MerkleTree::new(accounts.iter().map(|(_, value)| value.clone()).collect::<Vec<_>>());
+        let siblings = merkle.prove(target).map_or(0, |p| p.siblings.len());
+        println!("  {:>6}  {:>14}  {:>10}  {:>14}  {:>10}  {}", n, proof.len(), proof_bytes(&proof), siblings, siblings * 32, if verified { "✅" } else { "❌" });
+    }
+    println!("  Patricia 每层是 16 叉：层数少 (log16 n)，但每个分支节点要带上最多 16 个孩子哈希，总字节反而更多");
+    println!("  二叉树每层只要 1 个兄弟哈希，最省字节；可它不能按地址查找、不能证明不存在，改一个账户还可能打乱所有下标");
+}
+
+/*
+关键点总结：
+    1. 状态承诺的三个要求，二叉 Merkle 树都做不到：
+        确定性 (同样的状态同样的根，不管更新的顺序)、按键查找、不存在证明。
+        Patricia 树的形状只由键集合决定，三点都满足。
+
+    2. 三种节点各有分工：
+        Branch 负责分叉 (16 个槽 + 值槽)，Extension 压缩没有分叉的公共前缀，Leaf 把剩下的路径一次走完。
+        插入时 Leaf / Extension 可能被"劈开"成 Branch (insert_at 里的 split)。
+
+    3. 证明 = 根到键路径上的节点编码：
+        验证者一路检查"这个节点的哈希 == 上一层记的孩子哈希"，走到键结束或者路断了为止。
+        路断了 (槽是空的 / 路径不匹配) 本身就是不存在的证明。
+
+    4. state_root 把执行结果绑进区块头：
+        轻节点只靠区块头 + 证明就能查余额；全节点必须重新执行交易，算出来的根对不上就拒绝整块。
+        代价：每个块都要更新状态树，证明也比二叉树大 —— 以太坊正在研究用 Verkle 树缩小证明。
+*/
@@ -0,0 +1,213 @@
+// src/s06_chain/ex09_light_client.rs
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::ex01_merkle::{MerkleProof, MerkleTree};
+use crate::s05_zk_lab::hash::{sha256, short_hex, Digest};
+
+use super::block::{Block, BlockHeader};
+use super::node::Node;
+use super::spv::{HeaderChain, HEADER_BYTES};
+use super::utxo::{Transaction, TxId, BLOCK_REWARD};
+use super::wallet::Wallet;
+
+/*
+业务场景：Bob 在手机钱包上收款，手机存不下整条链，也执行不了所有交易。
+    钱包 (轻节点) 只同步区块头；Alice 说"我付过了，txid 是 ..."，
+    钱包就找一个全节点要这笔交易的 Merkle 证明，对照自己的区块头验证。
+
+本练习 (两个线程，只通过 channel 通信)：
+    1. 全节点线程挖矿，每出一个块只把区块头发给轻节点；中途出现一个分叉，还混进一个坏头和一个孤立的头
+    2. 轻节点线程验证每个头的 PoW 和链接，按累计工作量跟踪最好的头链
+    3. 同步完之后，轻节点请求 SPV 证明：
+        ✅ 主链上的付款 (数确认数)、❌ 只在被甩掉的分支上的付款、❌ 全节点根本没有的交易、❌ 被改过金额的交易
+*/
+
+const DIFFICULTY: u32 = 8;
+const FEE: u64 = 1;
+// 低于这个确认数，钱包显示"等待中"
+const CONFIRMATIONS: u64 = 3;
+
+// 全节点 -> 轻节点
+enum Message {
+    Header(BlockHeader),
+    // 付款方 (通过网络) 告诉收款方的交易号
+    Claim { label: String, txid: TxId },
+    Synced,
+    Proof { txid: TxId, found: Option<(Digest, Transaction, MerkleProof)> },
+}
+
+fn pay(node: &mut Node, from: &Wallet, to: &str, amount: u64, rng: &mut SimpleRng) -> Result<Transaction, String> {
+    let mut tx = node.utxos().build_transfer(&from.address(), to, amount, FEE)?;
+    from.sign(&mut tx, node.utxos(), rng);
+    node.submit(tx.clone())?;
+    Ok(tx)
+}
+
+// 全节点线程：挖矿、广播区块头，然后回答证明请求。返回回答了多少个请求
+fn full_node(mut node: Node, alice: Wallet, miner: String, bob: String, carol: String, to_light: Sender<Message>, requests: Receiver<TxId>) -> Result<usize, String> {
+    let mut rng = SimpleRng::from_time();
+    let mut store: HashMap<Digest, Block<Transaction>> = HashMap::new();
+    let send = |message| to_light.send(message).map_err(|_| String::from("轻节点断开了"));
+    let mine = |node: &mut Node, store: &mut HashMap<_, _>| -> Result<BlockHeader, String> {
+        let block = node.mine(&miner).map_err(|e| e.to_string())?;
+        let header = block.header.clone();
+        store.insert(block.hash(), block);
+        Ok(header)
+    };
+
+    // #1 带着 Alice -> Bob 的付款，#2 空块
+    let payment = pay(&mut node, &alice, &bob, 30, &mut rng)?;
+    send(Message::Claim { label: String::from("Alice -> Bob 30"), txid: payment.txid() })?;
+    for _ in 0..2 {
+        send(Message::Header(mine(&mut node, &mut store)?))?;
+    }
+
+    // 另一个矿工在 #2 上挖出 #3'，里面有 Alice -> Carol；它比主链的 #3 先到
+    let mut rival = node.clone();
+    let side_payment = pay(&mut rival, &alice, &carol, 10, &mut rng)?;
+    send(Message::Claim { label: String::from("Alice -> Carol 10"), txid: side_payment.txid() })?;
+    send(Message::Header(mine(&mut rival, &mut store)?))?;
+    send(Message::Header(mine(&mut node, &mut store)?))?;
+    let tip = mine(&mut node, &mut store)?;
+    send(Message::Header(tip.clone()))?;
+
+    // 两个坏头：难度字段被改低了 (哈希也跟着变了)、父块谁也没见过
+    send(Message::Header(BlockHeader { difficulty: DIFFICULTY - 1, ..tip.clone() }))?;
+    send(Message::Header(BlockHeader { prev_hash: sha256(b"nowhere"), height: tip.height + 5, ..tip }))?;
+    send(Message::Header(mine(&mut node, &mut store)?))?;
+    send(Message::Synced)?;
+
+    let mut served = 0;
+    for txid in requests {
+        let found = store.iter().find_map(|(hash, block)| {
+            let index = block.txs.iter().position(|tx| tx.txid() == txid)?;
+            let proof = MerkleTree::new_iterative(block.txs.clone()).prove(index)?;
+            Some((*hash, block.txs[index].clone(), proof))
+        });
+        send(Message::Proof { txid, found })?;
+        served += 1;
+    }
+    Ok(served)
+}
+
+// 轻节点线程：只有创世块的头 (检查点) 和 Bob 的地址
+fn light_client(genesis: BlockHeader, bob: String, from_full: Receiver<Message>, requests: Sender<TxId>) {
+    let mut headers = HeaderChain::new(genesis, DIFFICULTY);
+    let mut claims = Vec::new();
+    let mut received = 1;
+
+    println!("\n[1] 同步区块头 (轻节点线程)");
+    for message in from_full.iter() {
+        match message {
+            Message::Header(header) => {
+                received += 1;
+                let (height, hash) = (header.height, header.hash());
+                match headers.add(header) {
+                    Ok(true) => println!("  #{} {}.. ✅ 新的最好链头", height, short_hex(&hash)),
+                    Ok(false) => println!("  #{} {}.. ✅ 合法，但不比当前链头 #{} 重，先不换", height, short_hex(&hash), headers.best().height),
+                    Err(e) => println!("  #{} {}.. ❌ 拒绝 ({}): {}", height, short_hex(&hash), e.stage(), e),
+                }
+            }
+            Message::Claim { label, txid } => {
+                println!("  (Alice 发来消息: \"{}\" 付过了，txid {}..)", label, short_hex(&txid));
+                claims.push((label, txid));
+            }
+            Message::Synced => break,
+            Message::Proof { .. } => println!("  ❌ 还没请求就收到了证明 (不应该发生)"),
+        }
+    }
+    let best = headers.best();
+    println!("  最好链头 #{} {}..，一共收了 {} 个头 (含创世检查点和被拒绝的) ≈ {} 字节", best.height, short_hex(&best.hash()), received, received * HEADER_BYTES);
+
+    println!("\n[2] 向全节点要 SPV 证明 (需要 {} 个确认)", CONFIRMATIONS);
+    claims.push((String::from("Mallory -> Bob 500 (瞎编的)"), sha256(b"mallory never paid")));
+    let mut genuine = None;
+    for (label, txid) in claims {
+        if requests.send(txid).is_err() {
+            println!("  ❌ 全节点断开了 (不应该发生)");
+            return;
+        }
+        let Ok(Message::Proof { txid: answered, found }) = from_full.recv() else {
+            println!("  ❌ 全节点的回答不对 (不应该发生)");
+            return;
+        };
+        let Some((block_hash, tx, proof)) = found.filter(|_| answered == txid) else {
+            println!("  {:<28} ❌ 全节点说没有这笔交易", label);
+            continue;
+        };
+        let to_bob: u64 = tx.outputs.iter().filter(|out| out.owner == bob).map(|out| out.amount).sum();
+        match headers.verify_inclusion(&tx, &proof, &block_hash) {
+            Ok(confirmations) if confirmations >= CONFIRMATIONS => println!("  {:<28} ✅ 上链了，{} 个确认，给 Bob {}", label, confirmations, to_bob),
+            Ok(confirmations) => println!("  {:<28} ⏳ 上链了，但只有 {} 个确认", label, confirmations),
+            Err(e) => println!("  {:<28} ❌ {} (证明 {} 个兄弟哈希)", label, e, proof.siblings.len()),
+        }
+        if to_bob > 0 {
+            genuine = Some((block_hash, tx, proof));
+        }
+    }
+
+    println!("\n[3] ❌ 中间人把真交易的金额改成 3000，证明原样转发");
+    if let Some((block_hash, mut tx, proof)) = genuine {
+        if let Some(out) = tx.outputs.iter_mut().find(|out| out.owner == bob) {
+            out.amount = 3000;
+        }
+        match headers.verify_inclusion(&tx, &proof, &block_hash) {
+            Ok(_) => println!("  ✅ 通过了 (不应该发生)"),
+            Err(e) => println!("  {}", e),
+        }
+    }
+}
+
+pub fn run() {
+    println!("--- S06 Ex09: 轻节点同步区块头 + SPV 付款验证 ---");
+    let mut rng = SimpleRng::from_time();
+    let alice = Wallet::generate("Alice", &mut rng);
+    let (bob, carol, miner) = ["Bob", "Carol", "Miner"].map(|name| Wallet::generate(name, &mut rng).address()).into();
+    let node = match Node::new(DIFFICULTY, vec![Transaction::coinbase(&alice.address(), BLOCK_REWARD, 0)]) {
+        Ok(node) => node,
+        Err(e) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    let genesis = node.chain().tip().header.clone();
+    println!("创世块给 Alice {}；全节点难度 {}，轻节点只带着创世块的头", BLOCK_REWARD, DIFFICULTY);
+
+    let (to_light, from_full) = mpsc::channel();
+    let (to_full, requests) = mpsc::channel();
+    let light_bob = bob.clone();
+    let full = thread::spawn(move || full_node(node, alice, miner, bob, carol, to_light, requests));
+    let light = thread::spawn(move || light_client(genesis, light_bob, from_full, to_full));
+
+    if light.join().is_err() {
+        println!("  ❌ 轻节点线程 panic 了 (不应该发生)");
+    }
+    // 轻节点线程结束时 drop 了请求的 Sender，全节点的 for 循环随之结束
+    match full.join() {
+        Ok(Ok(served)) => println!("\n全节点一共回答了 {} 个证明请求", served),
+        Ok(Err(e)) => println!("\n  ❌ 全节点出错 (不应该发生): {}", e),
+        Err(_) => println!("\n  ❌ 全节点线程 panic 了 (不应该发生)"),
+    }
+}
+
+/*
+关键点总结：
+    1. 区块头自己可以验证：
+        链接和 PoW 只看区块头 (check_link_and_work 是从 check_header 里拆出来的前两道关)，
+        所以轻节点和全节点对"哪条链最重"的判断完全一样，伪造一条更重的头链需要真实的算力。
+
+    2. SPV 证明 = 交易 + Merkle 兄弟哈希 + 区块哈希：
+        对照的是轻节点自己验证过的区块头里的 merkle_root，全节点撒谎 (改交易、编交易) 都会被发现；
+        但全节点可以"装作没有" —— 所以真实的钱包会同时连好几个全节点。
+
+    3. 在链上 ≠ 在最好的链上：
+        被甩掉的分支上的区块头同样合法、证明同样成立，只有确认数能区分 (confirmations 返回 None)。
+        确认数越多，攻击者要重写的工作量越大 (S06 ex06 的重组)。
+
+    4. 线程之间只有消息：
+        全节点和轻节点各自拥有自己的数据 (Node / HeaderChain)，Sender 被 drop 就是"连接断开"的信号，
+        for txid in requests 自然结束，不需要额外的退出标志。
+*/
@@ -0,0 +1,282 @@
+// src/s06_chain/ex10_pbft.rs
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::s05_zk_lab::hash::{sha256, short_hex, Digest};
+
+/*
+业务场景：联盟链 / PoS 链的验证者集合是固定的 N 个，不挖矿，靠投票对下一个区块达成一致 (PBFT，Castro & Liskov 1999)
+
+    N = 3f + 1 个验证者，最多容忍 f 个拜占庭 (任意作恶) 的验证者。法定人数 (quorum) = 2f + 1。
+    一轮 (view v, 序号 seq) 分三个阶段：
+        PRE-PREPARE : 主节点 (leader = v mod N) 提议一个区块
+        PREPARE     : 每个验证者收到提议后广播"我看到了这个区块"；凑够 2f+1 个 PREPARE -> prepared
+        COMMIT      : prepared 之后广播 COMMIT；凑够 2f+1 个 COMMIT -> committed，区块最终确定，不会回滚
+    为什么是 2f+1：任意两个 2f+1 的集合至少重叠 f+1 个验证者，其中至少 1 个诚实 ——
+    诚实验证者不会给两个不同的区块都投票，所以不可能有两个冲突的区块同时凑够法定人数。
+
+和 PoW 的区别：PoW 的区块永远只是"概率上"确定 (ex06 的重组)；PBFT 一旦 committed 就是最终的。
+代价：每轮 O(N²) 条消息，验证者集合必须事先知道。
+
+本练习 (每个验证者一个线程，消息全部走 channel)：
+    1. 全部诚实
+    2. 1 个验证者同时给两个区块投票 (冲突投票) -> 被发现，其余 3 个照样达成一致
+    3. 主节点作恶，给不同的人发不同的区块 -> 安全性保住了，但有人卡住 (要靠 view change 换主节点，这里不实现)
+    4. ❌ 作恶的超过 f 个 -> 两个诚实验证者提交了冲突的区块
+简化：消息没有签名 (假设 channel 认证了发送者)；真实实现里每条投票都要签名，冲突投票就是可以拿去罚款的证据。
+*/
+
+const VALIDATORS: usize = 4;
+// 一轮的时限：到点还没提交就放弃 (真实系统会在这里发起 view change)
+const TIMEOUT: Duration = Duration::from_millis(300);
+const VIEW: u64 = 0;
+const SEQ: u64 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Phase {
+    PrePrepare,
+    Prepare,
+    Commit,
+}
+
+impl Phase {
+    fn name(&self) -> &'static str {
+        match self {
+            Phase::PrePrepare => "PRE-PREPARE",
+            Phase::Prepare => "PREPARE",
+            Phase::Commit => "COMMIT",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Vote {
+    phase: Phase,
+    view: u64,
+    seq: u64,
+    digest: Digest,
+    from: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Behavior {
+    Honest,
+    // 每个阶段都把两个区块的票一起发给所有人
+    DoubleVote,
+    // to_a 里的验证者收到区块 A 的票，其他人收到区块 B 的票 —— 谁也看不到它投了两种票
+    Split { to_a: Vec<usize> },
+}
+
+// 验证者 -> 主线程的日志
+struct Event {
+    node: usize,
+    text: String,
+}
+
+fn quorum(n: usize) -> usize {
+    2 * ((n - 1) / 3) + 1
+}
+
+fn leader(view: u64, n: usize) -> usize {
+    (view % n as u64) as usize
+}
+
+// ==========================================
+// 1. 拜占庭验证者：不等任何消息，一上来就把所有 (冲突的) 票发出去
+// ==========================================
+fn byzantine(me: usize, behavior: Behavior, peers: Vec<Sender<Vote>>, blocks: [Digest; 2]) {
+    let mut phases = vec![Phase::Prepare, Phase::Commit];
+    if leader(VIEW, peers.len()) == me {
+        phases.insert(0, Phase::PrePrepare);
+    }
+    for phase in phases {
+        for (to, peer) in peers.iter().enumerate() {
+            let digests = match &behavior {
+                Behavior::DoubleVote => blocks.to_vec(),
+                Behavior::Split { to_a } => vec![if to_a.contains(&to) { blocks[0] } else { blocks[1] }],
+                Behavior::Honest => unreachable!("honest validators run `honest`"),
+            };
+            for digest in digests {
+                let _ = peer.send(Vote { phase, view: VIEW, seq: SEQ, digest, from: me });
+            }
+        }
+    }
+}
+
+// ==========================================
+// 2. 诚实验证者：三阶段状态机
+// ==========================================
+fn honest(me: usize, inbox: Receiver<Vote>, peers: Vec<Sender<Vote>>, events: Sender<Event>, proposal: Digest) -> Option<Digest> {
+    let n = peers.len();
+    let q = quorum(n);
+    let log = |text: String| {
+        let _ = events.send(Event { node: me, text });
+    };
+    // 广播包括自己：自己的票也从收件箱里走一遍，计数逻辑只有一份
+    let broadcast = |phase, digest| {
+        for peer in &peers {
+            let _ = peer.send(Vote { phase, view: VIEW, seq: SEQ, digest, from: me });
+        }
+    };
+    if leader(VIEW, n) == me {
+        log(format!("我是主节点，提议区块 {}..", short_hex(&proposal)));
+        broadcast(Phase::PrePrepare, proposal);
+    }
+
+    let mut accepted: Option<Digest> = None;
+    let mut first_vote: HashMap<(Phase, usize), Digest> = HashMap::new();
+    let mut prepares: HashMap<Digest, HashSet<usize>> = HashMap::new();
+    let mut commits: HashMap<Digest, HashSet<usize>> = HashMap::new();
+    let mut sent_commit = false;
+    let mut committed = None;
+    // 提交之后也不马上退出：验证者一直在线，晚到的消息 (比如冲突的票) 照样要看
+    let deadline = Instant::now() + TIMEOUT;
+
+    loop {
+        let vote = match inbox.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(vote) => vote,
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => {
+                if committed.is_none() {
+                    let best = prepares.values().map(HashSet::len).max().unwrap_or(0);
+                    log(format!("⏳ 超时：PREPARE 最多只凑到 {}/{}，需要 view change", best, q));
+                }
+                return committed;
+            }
+        };
+        if vote.view != VIEW || vote.seq != SEQ {
+            continue;
+        }
+        // 同一个验证者、同一个阶段投了两个不同的值：记下来 (证据)，只认第一票
+        match first_vote.get(&(vote.phase, vote.from)) {
+            Some(first) if *first != vote.digest => {
+                log(format!("⚠️ 验证者 {} 的 {} 投了两个区块 {}.. 和 {}..，第二票作废", vote.from, vote.phase.name(), short_hex(first), short_hex(&vote.digest)));
+                continue;
+            }
+            Some(_) => continue,
+            None => {
+                first_vote.insert((vote.phase, vote.from), vote.digest);
+            }
+        }
+        match vote.phase {
+            Phase::PrePrepare if vote.from == leader(VIEW, n) && accepted.is_none() => {
+                accepted = Some(vote.digest);
+                broadcast(Phase::Prepare, vote.digest);
+            }
+            Phase::PrePrepare => {}
+            Phase::Prepare => {
+                prepares.entry(vote.digest).or_default().insert(vote.from);
+            }
+            Phase::Commit => {
+                commits.entry(vote.digest).or_default().insert(vote.from);
+            }
+        }
+
+        // PREPARE 可能比 PRE-PREPARE 先到，所以每收到一条消息都重新检查一遍
+        let Some(digest) = accepted else {
+            continue;
+        };
+        let count = |votes: &HashMap<Digest, HashSet<usize>>| votes.get(&digest).map_or(0, HashSet::len);
+        if !sent_commit && count(&prepares) >= q {
+            sent_commit = true;
+            log(format!("prepared：{}/{} 个 PREPARE 投给 {}..，广播 COMMIT", count(&prepares), n, short_hex(&digest)));
+            broadcast(Phase::Commit, digest);
+        }
+        if sent_commit && committed.is_none() && count(&commits) >= q {
+            log(format!("✅ committed：{}/{} 个 COMMIT，区块 {}.. 最终确定", count(&commits), n, short_hex(&digest)));
+            committed = Some(digest);
+        }
+    }
+}
+
+// ==========================================
+// 3. 跑一轮：N 个线程 + 全连接的 channel
+// ==========================================
+fn round(title: &str, behaviors: Vec<Behavior>, blocks: [Digest; 2], names: &HashMap<Digest, &str>) {
+    let n = behaviors.len();
+    let faulty = behaviors.iter().filter(|b| !matches!(b, Behavior::Honest)).count();
+    println!("\n{} (N = {}，作恶 {} 个，最多容忍 f = {}，quorum = {})", title, n, faulty, (n - 1) / 3, quorum(n));
+
+    let (senders, inboxes): (Vec<Sender<Vote>>, Vec<Receiver<Vote>>) = (0..n).map(|_| mpsc::channel()).unzip();
+    let (event_sender, events) = mpsc::channel();
+    let mut handles = Vec::new();
+    for (me, (behavior, inbox)) in behaviors.into_iter().zip(inboxes).enumerate() {
+        let peers = senders.clone();
+        let events = event_sender.clone();
+        handles.push(thread::spawn(move || match behavior {
+            Behavior::Honest => (true, honest(me, inbox, peers, events, blocks[0])),
+            other => {
+                byzantine(me, other, peers, blocks);
+                (false, None)
+            }
+        }));
+    }
+    // 主线程手里的 Sender 都得 drop：所有验证者退出后 events 才会结束
+    drop(event_sender);
+    drop(senders);
+    for event in events {
+        println!("  [验证者 {}] {}", event.node, event.text);
+    }
+
+    let results: Vec<(bool, Option<Digest>)> = handles.into_iter().map(|h| h.join().expect("validator thread panicked")).collect();
+    let committed: HashSet<Digest> = results.iter().filter(|(honest, _)| *honest).filter_map(|(_, d)| *d).collect();
+    let line: Vec<String> = results
+        .iter()
+        .enumerate()
+        .map(|(i, (honest, digest))| match (honest, digest) {
+            (false, _) => format!("{}: 作恶", i),
+            (true, Some(d)) => format!("{}: {}", i, names[d]),
+            (true, None) => format!("{}: 卡住", i),
+        })
+        .collect();
+    println!("  结果 {}", line.join(" | "));
+    match committed.len() {
+        0 => println!("  安全 ✅ 没有人提交；活性 ❌ 这一轮没出块"),
+        1 => println!("  安全 ✅ 所有提交了的诚实验证者结果一致"),
+        _ => println!("  安全 ❌ 诚实验证者提交了冲突的区块 —— 作恶的超过了 f 个"),
+    }
+}
+
+pub fn run() {
+    println!("--- S06 Ex10: PBFT 共识 (三阶段投票) ---");
+    let a = sha256("区块 #1: Alice -> Bob 10".as_bytes());
+    let b = sha256("区块 #1: Alice -> Carol 10 (同一笔钱)".as_bytes());
+    let names = HashMap::from([(a, "区块 A"), (b, "区块 B")]);
+    println!("区块 A = {}..  区块 B = {}.. (花同一笔钱，只能有一个上链)", short_hex(&a), short_hex(&b));
+    let honest = || vec![Behavior::Honest; VALIDATORS];
+
+    round("[1] 全部诚实", honest(), [a, b], &names);
+
+    let mut behaviors = honest();
+    behaviors[3] = Behavior::DoubleVote;
+    round("[2] 验证者 3 冲突投票 (A、B 都投)", behaviors, [a, b], &names);
+
+    // 主节点 0 给验证者 1 发 A，给 2、3 发 B
+    let mut behaviors = honest();
+    behaviors[0] = Behavior::Split { to_a: vec![1] };
+    round("[3] 主节点 0 作恶：A 发给 1，B 发给 2、3", behaviors, [a, b], &names);
+
+    let mut behaviors = honest();
+    behaviors[0] = Behavior::Split { to_a: vec![1] };
+    behaviors[3] = Behavior::Split { to_a: vec![1] };
+    round("[4] ❌ 主节点 0 和验证者 3 串通：对 1 说 A，对 2 说 B", behaviors, [a, b], &names);
+}
+
+/*
+关键点总结：
+    1. 法定人数的交集：
+        quorum = 2f+1，两个 quorum 至少重叠 f+1 个，其中至少 1 个诚实验证者，它只会给一个区块投票 ——
+        所以 f 个以内的作恶者怎么投票都造不出两个冲突的 quorum ([2]、[3])。超过 f 个，交集可能全是作恶者 ([4])。
+
+    2. 安全性 vs 活性：
+        [3] 里验证者 1 凑不够票卡住了，但它不会提交错误的区块；PBFT 用 view change (换主节点) 恢复活性。
+        异步网络里两者不可兼得 (FLP 不可能定理)：PBFT 选择永远安全、网络恢复正常后才保证出块。
+
+    3. 为什么要两轮投票 (PREPARE + COMMIT)：
+        prepared 只说明"我知道 2f+1 个人看到了同一个区块"；COMMIT 再确认"2f+1 个人都知道这件事"，
+        这样即使换了主节点，新主节点也一定能从 2f+1 个验证者里问出已经 prepared 的区块，不会推翻它。
+
+    4. 冲突投票就是证据：
+        [2] 里验证者 3 的两张票同时出现在一个诚实验证者手里。票上有签名的话，这就是 PoS 罚没 (slashing) 的依据。
+*/
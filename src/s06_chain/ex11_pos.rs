@@ -0,0 +1,126 @@
+// src/s06_chain/ex11_pos.rs
+use std::collections::HashMap;
+
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::crypto::schnorr::Keypair;
+use crate::s05_zk_lab::hash::{sha256, Digest};
+use crate::s05_zk_lab::math::curve::CurvePoint;
+
+use super::pos::ValidatorSet;
+
+/*
+业务场景：PoS 链不挖矿，每一轮按质押量抽一个出块人。抽得公平吗？谁都能验证吗？
+
+本练习：
+    1. 4 个验证者，跑很多轮 (轮数可以自己输入)，对比"实际当选频率"和"质押占比"
+    2. 同一个种子 -> 所有人算出同一张出块表；换个种子 -> 完全不同
+    3. 把质押拆成很多个小验证者，总当选概率不变 (拆分没有好处，也没有坏处)
+    4. ❌ 空集合、质押为 0、重复注册
+*/
+
+const GENESIS: [(&str, u64); 4] = [("Alice", 5_000), ("Bob", 3_000), ("Carol", 1_500), ("Dave", 500)];
+
+// 抽签只看质押，不看公钥；这里用固定种子生成公钥，两个节点建出来的集合一模一样
+fn genesis_set() -> ValidatorSet {
+    let mut rng = SimpleRng::new(1);
+    let mut set = ValidatorSet::new();
+    for (name, stake) in GENESIS {
+        set.add(name, Keypair::<CurvePoint>::generate(&mut rng).public, stake).expect("genesis validators are valid");
+    }
+    set
+}
+
+fn tally(set: &ValidatorSet, seed: &Digest, rounds: u64) -> HashMap<String, u64> {
+    let mut counts = HashMap::new();
+    for round in 0..rounds {
+        if let Some(v) = set.proposer(seed, round) {
+            *counts.entry(v.name.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+fn schedule(set: &ValidatorSet, seed: &Digest, rounds: u64) -> String {
+    let names: Vec<&str> = (0..rounds).filter_map(|round| set.proposer(seed, round)).map(|v| &v.name[..1]).collect();
+    names.join(" ")
+}
+
+pub fn run() {
+    println!("--- S06 Ex11: 权益证明 (按质押抽出块人) ---");
+    let set = genesis_set();
+    let seed = sha256(b"epoch-1 randomness");
+    let total = set.total_stake();
+
+    // ==========================================
+    // 1. 频率 vs 质押占比
+    // ==========================================
+    let rounds = read_line("\n[1] 抽多少轮 (直接回车默认 10000): ").parse::<u64>().unwrap_or(10_000).max(1);
+    let counts = tally(&set, &seed, rounds);
+    println!("  {:<6} {:>6} {:>8} {:>8} {:>8}", "验证者", "质押", "占比", "当选", "频率");
+    for v in set.validators() {
+        let share = v.stake as f64 / total as f64;
+        let hits = counts.get(&v.name).copied().unwrap_or(0);
+        let freq = hits as f64 / rounds as f64;
+        let bar = "#".repeat((freq * 50.0).round() as usize);
+        println!("  {:<9} {:>6} {:>7.1}% {:>8} {:>7.1}%  {}", v.name, v.stake, share * 100.0, hits, freq * 100.0, bar);
+    }
+    println!("  轮数越多，频率越接近占比 (大数定律)；少量轮次时偏差可能很大 —— 试试输入 20");
+
+    // ==========================================
+    // 2. 确定性
+    // ==========================================
+    println!("\n[2] 出块表只由 (验证者集合, 种子) 决定");
+    println!("  节点 X 算的前 20 轮 : {}", schedule(&set, &seed, 20));
+    println!("  节点 Y 算的前 20 轮 : {}", schedule(&genesis_set(), &seed, 20));
+    println!("  换一个种子         : {}", schedule(&set, &sha256(b"epoch-2 randomness"), 20));
+    println!("  谁都能提前算出下一个出块人 —— 也意味着攻击者知道该去 DDoS 谁 (VRF 抽签没有这个问题，见 S05 ex39)");
+
+    // ==========================================
+    // 3. 拆分质押
+    // ==========================================
+    println!("\n[3] Dave 把 500 拆成 5 个 100 的验证者");
+    let mut rng = SimpleRng::new(2);
+    let mut split = ValidatorSet::new();
+    for v in set.validators().iter().take(3) {
+        split.add(&v.name, v.public, v.stake).expect("genesis validators are valid");
+    }
+    for i in 0..5 {
+        split.add(&format!("Dave#{}", i), Keypair::generate(&mut rng).public, 100).expect("distinct names");
+    }
+    let split_rounds = rounds.max(10_000);
+    let before = tally(&set, &seed, split_rounds).get("Dave").copied().unwrap_or(0);
+    let after: u64 = tally(&split, &seed, split_rounds).iter().filter(|(name, _)| name.starts_with("Dave")).map(|(_, hits)| hits).sum();
+    println!("  {} 轮里 Dave 一个账户当选 {} 次，5 个账户合计 {} 次 (期望都是 {:.0})", split_rounds, before, after, split_rounds as f64 * 500.0 / total as f64);
+    println!("  数轴上 Dave 的总长度没变，所以概率不变 —— 抽签规则必须满足这一点，否则大户会被迫拆账户");
+
+    // ==========================================
+    // 4. 边界情况
+    // ==========================================
+    println!("\n[4] ❌ 不合法的验证者集合");
+    let empty = ValidatorSet::new();
+    println!("  空集合出块人: {}", empty.proposer(&seed, 0).map_or(String::from("没有 (None)"), |v| v.name.clone()));
+    let mut set = genesis_set();
+    let public = set.validators()[0].public;
+    for (name, stake) in [("Eve", 0), ("Alice", 10)] {
+        if let Err(e) = set.add(name, public, stake) {
+            println!("  add({}, {}) -> {}", name, stake, e);
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. follow-the-satoshi：
+        每个质押单位 (satoshi) 被抽中的概率一样，验证者的概率就是它的质押占比。
+        实现只是"随机数 mod 总质押，再在前缀和里找位置"。
+
+    2. 确定性是共识的前提：
+        每个节点必须独立算出同一个出块人，才能判断收到的区块该不该由他出。
+        所以随机数只能来自链上公开的数据 (种子)，不能是本地的 rand()。
+
+    3. 种子本身的安全：
+        出块人能影响种子，就能"刷"出对自己有利的下一轮 (grinding)。RANDAO / VRF 是两种常见的补救办法。
+
+    4. 下一步：出块人签名的区块如果在同一高度出现两个，就是作恶的证据 —— 罚没 (slashing)。
+*/
@@ -0,0 +1,188 @@
+// src/s06_chain/ex12_slashing.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::{sha256, short_hex, Digest};
+
+use super::block::{merkle_root, now_secs, BlockHeader};
+use super::pos::{EquivocationDetector, Evidence, SignedHeader, ValidatorSet, SLASH_PERCENT};
+use super::wallet::Wallet;
+
+/*
+业务场景：PoS 出块几乎不花钱，出块人完全可以在同一高度签两个块 —— 一个给交易所看 (Alice 付了钱)，一个给别人看 (没付)。
+    这就是 equivocation (双签)，PoW 里要付两倍算力，PoS 里只要多签一次名。
+    对策：每个节点都记着"谁在哪个高度签过哪个头"，发现同一高度两个不同的头，签名就是证据，提交上链罚没。
+
+本练习：
+    1. 固定剧本：Dave 在 #1 双签 -> 被监视器抓到 -> 烧掉 SLASH_PERCENT% 质押、踢出活跃集合 -> 抽签概率重新分配
+    2. 交互：一轮一轮出块，你来决定轮到的出块人是诚实出块还是双签
+    3. ❌ 不成立的证据：栽赃 (伪造签名)、同一个块转发两次、不同高度、同一份证据罚两次
+*/
+
+const GENESIS: [(&str, u64); 4] = [("Alice", 5_000), ("Bob", 3_000), ("Carol", 1_500), ("Dave", 500)];
+const ROUNDS: u64 = 8;
+
+struct Network {
+    wallets: Vec<Wallet>,
+    set: ValidatorSet,
+    detector: EquivocationDetector,
+    seed: Digest,
+    tip: Digest,
+    rng: SimpleRng,
+}
+
+impl Network {
+    fn new() -> Self {
+        let mut rng = SimpleRng::from_time();
+        let wallets: Vec<Wallet> = GENESIS.iter().map(|(name, _)| Wallet::generate(name, &mut rng)).collect();
+        let mut set = ValidatorSet::new();
+        for (wallet, (_, stake)) in wallets.iter().zip(GENESIS) {
+            set.add(&wallet.name, wallet.keys.public, stake).expect("genesis validators are valid");
+        }
+        Network { wallets, set, detector: EquivocationDetector::new(), seed: sha256(b"slashing epoch"), tip: sha256(b"genesis"), rng }
+    }
+
+    fn wallet(&self, name: &str) -> &Wallet {
+        self.wallets.iter().find(|w| w.name == name).expect("every validator has a wallet")
+    }
+
+    // 区块内容只是一行字符串，够让两个头的 merkle_root 不一样
+    fn sign(&mut self, name: &str, height: u64, content: &str) -> SignedHeader {
+        let header = BlockHeader {
+            height,
+            prev_hash: self.tip,
+            merkle_root: merkle_root(&[content.to_string()]),
+            state_root: [0u8; 32],
+            timestamp: now_secs(),
+            difficulty: 0,
+            nonce: 0,
+        };
+        let keys = self.wallet(name).keys;
+        SignedHeader::sign(name, header, &keys, &mut self.rng)
+    }
+
+    // 广播给监视器；抓到双签就当场提交证据罚没
+    fn broadcast(&mut self, signed: SignedHeader) {
+        let (name, height, hash) = (signed.validator.clone(), signed.header.height, signed.header.hash());
+        match self.detector.observe(&self.set, signed) {
+            Ok(None) => println!("    监视器: {} 在 #{} 签的 {}.. 没有冲突", name, height, short_hex(&hash)),
+            Ok(Some(evidence)) => {
+                println!("    ⚠️ {} 在 #{} 又签了 {}.. (之前是 {}..)，提交证据", name, height, short_hex(&hash), short_hex(&evidence.first.header.hash()));
+                self.punish(&evidence);
+            }
+            Err(e) => println!("    ❌ {}", e),
+        }
+    }
+
+    fn punish(&mut self, evidence: &Evidence) {
+        let before = self.set.get(&evidence.first.validator).map_or(0, |v| v.stake);
+        match self.set.slash(evidence) {
+            Ok(burned) => println!("    🔥 罚没 {}：质押 {} -> {} (烧掉 {})，移出活跃集合", evidence.first.validator, before, before - burned, burned),
+            Err(e) => println!("    ❌ 证据不成立: {}", e),
+        }
+    }
+
+    // 诚实出块：签一个头，链头前进
+    fn propose(&mut self, name: &str, height: u64) {
+        let signed = self.sign(name, height, &format!("{} 的第 {} 块", name, height));
+        self.tip = signed.header.hash();
+        self.broadcast(signed);
+    }
+
+    // 双签：同一高度两个内容不同的头；链沿着第一个继续
+    fn equivocate(&mut self, name: &str, height: u64) {
+        let first = self.sign(name, height, "Alice 付给交易所 1000");
+        let second = self.sign(name, height, "Alice 把 1000 转给自己");
+        self.tip = first.header.hash();
+        self.broadcast(first);
+        self.broadcast(second);
+    }
+
+    fn print_set(&self) {
+        let total = self.set.total_stake();
+        for v in self.set.validators() {
+            let share = if v.active && total > 0 { format!("{:.1}%", v.stake as f64 / total as f64 * 100.0) } else { String::from("-") };
+            println!("    {:<6} 质押 {:>5}  {}  出块概率 {}", v.name, v.stake, if v.active { "活跃  " } else { "已罚没" }, share);
+        }
+    }
+}
+
+pub fn run() {
+    println!("--- S06 Ex12: 罚没 (双签被抓，质押被烧) ---");
+
+    // ==========================================
+    // 1. 固定剧本
+    // ==========================================
+    println!("\n[1] Dave 在 #1 签了两个不同的块 (罚没比例 {}%)", SLASH_PERCENT);
+    let mut net = Network::new();
+    net.print_set();
+    net.equivocate("Dave", 1);
+    println!("  罚没之后，抽签只在剩下的活跃验证者里进行：");
+    net.print_set();
+
+    // ==========================================
+    // 2. 交互：轮到谁，你来决定他怎么出块
+    // ==========================================
+    println!("\n[2] 新的网络，逐轮出块 (回车 = 诚实出块，d = 双签，q = 结束)");
+    let mut net = Network::new();
+    for height in 1..=ROUNDS {
+        let Some(proposer) = net.set.proposer(&net.seed, height) else {
+            println!("  没有活跃验证者了，链停了");
+            break;
+        };
+        let (name, stake) = (proposer.name.clone(), proposer.stake);
+        match read_line(&format!("  #{} 出块人 {} (质押 {}) > ", height, name, stake)).as_str() {
+            "q" => break,
+            "d" => net.equivocate(&name, height),
+            _ => net.propose(&name, height),
+        }
+    }
+    println!("  最终的验证者集合：");
+    net.print_set();
+
+    // ==========================================
+    // 3. 不成立的证据
+    // ==========================================
+    println!("\n[3] ❌ 不成立的证据");
+    let mut net = Network::new();
+    let honest = net.sign("Bob", 1, "Bob 的第 1 块");
+    net.broadcast(honest.clone());
+
+    println!("  (a) Mallory 用自己的钥匙伪造一个\"Bob 签的\" #1，想栽赃");
+    let mallory = Wallet::generate("Mallory", &mut net.rng);
+    let mut forged = net.sign("Bob", 1, "Bob 没签过的块");
+    forged.signature = SignedHeader::sign("Bob", forged.header.clone(), &mallory.keys, &mut net.rng).signature;
+    net.broadcast(forged.clone());
+    net.punish(&Evidence { first: honest.clone(), second: forged });
+
+    println!("  (b) 同一个块被 gossip 转发了两次");
+    net.broadcast(honest.clone());
+    net.punish(&Evidence { first: honest.clone(), second: honest.clone() });
+
+    println!("  (c) Bob 在 #1 和 #2 各出了一个块");
+    let next = net.sign("Bob", 2, "Bob 的第 2 块");
+    net.punish(&Evidence { first: honest.clone(), second: next });
+
+    println!("  (d) Bob 真的双签了，证据被提交两次");
+    let conflicting = net.sign("Bob", 1, "Bob 的另一个第 1 块");
+    let evidence = Evidence { first: honest, second: conflicting };
+    net.punish(&evidence);
+    net.punish(&evidence);
+    net.print_set();
+}
+
+/*
+关键点总结：
+    1. 证据是自证的：
+        两个区块头 + 两份签名，任何人都能核对 (同一个人、同一高度、内容不同、签名都对)，
+        不需要信任提交证据的人 —— 所以伪造签名的栽赃、重复转发、不同高度都会被 check_evidence 拒绝。
+
+    2. 罚没 = 烧质押 + 踢出集合：
+        烧掉的钱让双签的收益变成负的；踢出集合让它以后抽不到出块权，
+        剩下验证者的出块概率自动按新的总质押重新分配 (total_stake 只算活跃的)。
+
+    3. 监视器先验签再登记：
+        如果不验签就记录"Bob 在 #1 签了 X"，攻击者抢先塞一个伪造的 X，之后 Bob 真的双签时反而对不上号。
+
+    4. 同一份证据只罚一次：
+        被罚过的验证者已经不活跃，slash 直接拒绝，防止同一个错误被反复扣钱。
+*/
@@ -0,0 +1,224 @@
+// src/s06_chain/ex13_double_spend.rs
+use std::collections::HashMap;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::{short_hex, Digest};
+
+use super::block::Block;
+use super::node::Node;
+use super::utxo::{Transaction, TxId, BLOCK_REWARD};
+use super::wallet::Wallet;
+
+/*
+业务场景：Alice 手里只有一枚 50 的币，却想同时买 Bob 和 Carol 的东西
+    她用同一个 UTXO 签了两笔交易：tx_A 付给 Bob，tx_B 付给 Carol，
+    分别发给离 Bob 近的节点和离 Carol 近的节点。两个节点各自只见过一笔，都觉得"合法"，
+    又恰好各自挖出了一个块 —— 网络里同时存在两个 #1，Bob 和 Carol 都看到了 1 个确认。
+    最终只有一条分支能活下来，另一笔交易连同它所在的区块一起被重组掉。
+
+本练习 (三个节点，消息按顺序手动投递)：
+    1. 节点 B (Bob 的商店)、节点 C (Carol 的商店)、节点 X (交易所，只看不挖)，共享创世块
+    2. 两笔冲突交易分别 gossip 到 B 和 C，X 两笔都收到 —— 谁先到谁进池子
+    3. B 和 C 同时出块，区块互相传播：同样高的分支先到先得，各节点的视图开始分裂
+    4. 随机一方先挖出 #2，另外两个节点重组，输的那笔交易被丢弃
+    每一步都打印每个节点眼里的两笔交易的状态和余额
+*/
+
+const DIFFICULTY: u32 = 8;
+const FEE: u64 = 1;
+const PRICE: u64 = 40;
+
+// 一个节点 + 它收到的、暂时不在主链上的区块 (侧链)
+struct Peer {
+    name: &'static str,
+    node: Node,
+    miner: String,
+    side: HashMap<Digest, Block<Transaction>>,
+}
+
+impl Peer {
+    fn receive_tx(&mut self, tx: &Transaction, label: &str) {
+        match self.node.submit(tx.clone()) {
+            Ok(_) => println!("    {} 收到 {}: ✅ 进交易池", self.name, label),
+            Err(e) => println!("    {} 收到 {}: ❌ {}", self.name, label, e),
+        }
+    }
+
+    // 接在链头上就直接 connect；否则先放进侧链，再看侧链上能不能拼出一条更长的分支
+    fn receive_block(&mut self, block: &Block<Transaction>) {
+        let (height, hash) = (block.header.height, block.hash());
+        print!("    {} 收到 #{} {}..: ", self.name, height, short_hex(&hash));
+        if block.header.prev_hash == self.node.chain().tip().hash() {
+            match self.node.connect(block.clone()) {
+                Ok(()) => println!("✅ 接在链头上"),
+                Err(e) => println!("❌ {}", e),
+            }
+            return;
+        }
+        self.side.insert(hash, block.clone());
+        let mut branch = Vec::new();
+        let mut cursor = hash;
+        while let Some(block) = self.side.get(&cursor) {
+            branch.push(block.clone());
+            cursor = block.header.prev_hash;
+        }
+        branch.reverse();
+        match self.node.reorg(branch) {
+            Ok(report) => {
+                println!("⚠️ 重组：回滚 {} 个块，接上 {} 个块", report.rolled_back.len(), report.connected.len());
+                for (_, hash) in &report.connected {
+                    self.side.remove(hash);
+                }
+                for (txid, reason) in &report.dropped {
+                    println!("        丢弃 {}..: {}", short_hex(txid), reason);
+                }
+            }
+            Err(e) => println!("放进侧链 ({})", e),
+        }
+    }
+
+    fn mine(&mut self) -> Option<Block<Transaction>> {
+        match self.node.mine(&self.miner) {
+            Ok(block) => Some(block),
+            Err(e) => {
+                println!("  ❌ {} 出块失败 (不应该发生): {}", self.name, e);
+                None
+            }
+        }
+    }
+
+    // 某笔交易在这个节点眼里的状态
+    fn status(&self, txid: &TxId) -> String {
+        let chain = self.node.chain();
+        if let Some(block) = chain.blocks.iter().find(|b| b.txs.iter().any(|tx| tx.txid() == *txid)) {
+            return format!("✅ {} 确认", chain.height() - block.header.height + 1);
+        }
+        if self.node.is_pending(txid) {
+            return String::from("⏳ 交易池");
+        }
+        String::from("-")
+    }
+}
+
+// 按终端显示宽度补空格：中文和 emoji 占两格，format! 的宽度只数字符个数
+fn pad(text: &str, width: usize) -> String {
+    let shown: usize = text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum();
+    format!("{}{}", text, " ".repeat(width.saturating_sub(shown)))
+}
+
+fn show_views(step: &str, peers: &[Peer], txs: &[(TxId, &str)], wallets: &[&Wallet]) {
+    println!("  [{}]", step);
+    println!("    {}{}{}{}余额", pad("节点", 6), pad("链头", 17), pad(txs[0].1, 18), pad(txs[1].1, 18));
+    for peer in peers {
+        let tip = peer.node.chain().tip();
+        let balances: Vec<String> = wallets.iter().map(|w| format!("{} {}", w.name, peer.node.utxos().balance(&w.address()))).collect();
+        let head = format!("#{} {}..", tip.header.height, short_hex(&tip.hash()));
+        println!("    {}{}{}{}{}", pad(peer.name, 6), pad(&head, 17), pad(&peer.status(&txs[0].0), 18), pad(&peer.status(&txs[1].0), 18), balances.join("  "));
+    }
+}
+
+pub fn run() {
+    println!("--- S06 Ex13: 双花赛跑 (两笔冲突交易、两条分支、只有一个赢家) ---");
+    let mut rng = SimpleRng::from_time();
+    let alice = Wallet::generate("Alice", &mut rng);
+    let bob = Wallet::generate("Bob", &mut rng);
+    let carol = Wallet::generate("Carol", &mut rng);
+    let wallets = [&alice, &bob, &carol];
+
+    // ==========================================
+    // 1. 三个节点，共享创世块
+    // ==========================================
+    println!("\n[1] 创世块给 Alice {}；节点 B 给 MinerB 挖矿，节点 C 给 MinerC 挖矿，节点 X 只转发", BLOCK_REWARD);
+    let genesis = match Node::new(DIFFICULTY, vec![Transaction::coinbase(&alice.address(), BLOCK_REWARD, 0)]) {
+        Ok(node) => node,
+        Err(e) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    let peer = |name, miner: &str| Peer { name, node: genesis.clone(), miner: miner.to_string(), side: HashMap::new() };
+    let mut peers = [peer("B", "MinerB"), peer("C", "MinerC"), peer("X", "MinerX")];
+
+    // ==========================================
+    // 2. 同一个 UTXO，两笔交易
+    // ==========================================
+    println!("\n[2] Alice 用同一枚币签了两笔交易，分头发出去");
+    let utxos = genesis.utxos();
+    let signed = |to: &Wallet, rng: &mut SimpleRng| -> Result<Transaction, String> {
+        let mut tx = utxos.build_transfer(&alice.address(), &to.address(), PRICE, FEE)?;
+        alice.sign(&mut tx, utxos, rng);
+        Ok(tx)
+    };
+    let (tx_a, tx_b) = match (signed(&bob, &mut rng), signed(&carol, &mut rng)) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(e), _) | (_, Err(e)) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    let labels = [(tx_a.txid(), "tx_A (给 Bob)"), (tx_b.txid(), "tx_B (给 Carol)")];
+    println!("    tx_A = {}..，tx_B = {}..，花的都是 {}", short_hex(&tx_a.txid()), short_hex(&tx_b.txid()), tx_a.inputs[0].prev);
+    let [b, c, x] = &mut peers;
+    b.receive_tx(&tx_a, labels[0].1);
+    c.receive_tx(&tx_b, labels[1].1);
+    x.receive_tx(&tx_a, labels[0].1);
+    x.receive_tx(&tx_b, labels[1].1);
+    b.receive_tx(&tx_b, labels[1].1);
+    c.receive_tx(&tx_a, labels[0].1);
+    show_views("交易传播之后", &peers, &labels, &wallets);
+    println!("    每个节点的交易池都只有一笔 —— 但哪一笔取决于谁先到");
+
+    // ==========================================
+    // 3. 同时出块
+    // ==========================================
+    println!("\n[3] B 和 C 几乎同时挖出 #1，各自打包了自己池子里的那一笔");
+    let [b, c, x] = &mut peers;
+    let (Some(block_b), Some(block_c)) = (b.mine(), c.mine()) else {
+        return;
+    };
+    x.receive_block(&block_b);
+    x.receive_block(&block_c);
+    b.receive_block(&block_c);
+    c.receive_block(&block_b);
+    show_views("第一轮出块之后", &peers, &labels, &wallets);
+    println!("    Bob 和 Carol 都看到了 1 个确认，都以为收到了钱");
+
+    // ==========================================
+    // 4. 下一个块决定胜负
+    // ==========================================
+    let winner = rng.gen_range(2) as usize;
+    println!("\n[4] 下一个块被 {} 先挖出来 (随机)", peers[winner].name);
+    let Some(next) = peers[winner].mine() else {
+        return;
+    };
+    for (i, peer) in peers.iter_mut().enumerate() {
+        if i != winner {
+            peer.receive_block(&next);
+        }
+    }
+    show_views("第二轮出块之后", &peers, &labels, &wallets);
+
+    let (kept, lost, victim) = if winner == 0 { (labels[0].1, labels[1].1, &carol) } else { (labels[1].1, labels[0].1, &bob) };
+    let agreed = peers.windows(2).all(|w| w[0].node.chain().tip().hash() == w[1].node.chain().tip().hash());
+    if agreed {
+        println!("  ✅ 三个节点的链头一致：{} 留下，{} 随着被甩掉的 #1 一起消失", kept, lost);
+    } else {
+        println!("  ❌ 节点的链头还不一致 (不应该发生)");
+    }
+    println!("  {} 在 1 个确认时就发了货，现在钱没了；{} 也回不到交易池 —— 它花的币已经被另一笔花掉了", victim.name, lost);
+}
+
+/*
+关键点总结：
+    1. 双花不需要算力优势：
+        只要两笔冲突交易先到了不同的节点，两边的矿工就会各自打包一笔，网络里自然出现两个同样高的分支。
+        每个节点在各自的分支上都是"对的"，交易池和区块验证都挡不住。
+
+    2. 先到先得 + 最长链：
+        同样高时各节点保持先看到的分支 (视图分裂)；谁先多出一个块，所有人就都切到那条分支。
+        输掉的交易在重组时被 mempool 拒绝 (输入已经被花了)，永远不会再上链。
+
+    3. 1 个确认 ≠ 到账：
+        分裂期间每个商户看到的都是"1 个确认"，完全没法区分自己是不是在会输的那条分支上。
+        等的确认越多，"另一条分支恰好更长"的概率越小 —— 这就是比特币建议等 6 个确认的原因。
+*/
@@ -0,0 +1,254 @@
+// src/s06_chain/ex14_explorer.rs
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::short_hex;
+
+use super::block::merkle_root;
+use super::chain::Chain;
+use super::explorer::Explorer;
+use super::node::Node;
+use super::utxo::{Transaction, BLOCK_REWARD};
+use super::wallet::Wallet;
+
+/*
+业务场景：链上数据是公开的，但一串区块和交易对人并不友好 —— 区块浏览器把它变成可以查的网页。
+
+本练习：先从 toy_chain.json 读回上次的链 (没有存档就挖一条有几笔转账的小链并保存)，然后进入浏览器子菜单
+    1. 区块列表：高度、哈希、交易数、时间
+    2. 查看区块：区块头字段，重新计算 Merkle 根对照区块头，逐笔列出交易的输入 / 输出
+    3. 按 txid 查交易 (十六进制前缀就行)
+    4. 地址的余额历史 (输入名字或地址)
+    5. 再挖一个块并保存 —— 退出程序再进来，链还在
+    6. 删除存档
+    所有查询都只读内存里的 Chain，UTXO 集合只在最后用来对账
+*/
+
+const DIFFICULTY: u32 = 8;
+const FEE: u64 = 1;
+const CHAIN_FILE: &str = "toy_chain.json";
+// 钥匙用固定种子生成：重启之后地址不变，存档里的地址才对得上名字 (⚠️ 真实钱包绝对不能这么做)
+const KEY_SEED: u64 = 14;
+
+// 地址 -> 名字，只是为了打印好看；链上只有地址
+struct Names(HashMap<String, String>);
+
+impl Names {
+    fn show(&self, address: &str) -> String {
+        self.0.get(address).cloned().unwrap_or_else(|| address.to_string())
+    }
+
+    // 先当名字查 (不分大小写)，查不到就当地址
+    fn resolve(&self, input: &str) -> String {
+        self.0.iter().find(|(_, name)| name.eq_ignore_ascii_case(input)).map_or_else(|| input.to_string(), |(address, _)| address.clone())
+    }
+}
+
+fn pay(node: &mut Node, from: &Wallet, to: &Wallet, amount: u64, rng: &mut SimpleRng) -> Result<(), String> {
+    let mut tx = node.utxos().build_transfer(&from.address(), &to.address(), amount, FEE)?;
+    from.sign(&mut tx, node.utxos(), rng);
+    node.submit(tx)?;
+    Ok(())
+}
+
+// 一小段历史：每个块里放几笔转账，让余额有涨有跌
+fn build_chain(wallets: &[Wallet; 4], rng: &mut SimpleRng) -> Result<Node, String> {
+    let [alice, bob, carol, miner] = wallets;
+    let mut node = Node::new(DIFFICULTY, vec![Transaction::coinbase(&alice.address(), BLOCK_REWARD, 0)])?;
+    let rounds: [&[(&Wallet, &Wallet, u64)]; 4] = [
+        &[(alice, bob, 20)],
+        &[(bob, carol, 5), (alice, carol, 10)],
+        &[(miner, alice, 30)],
+        &[(carol, bob, 12), (alice, bob, 25)],
+    ];
+    for transfers in rounds {
+        for &(from, to, amount) in transfers {
+            pay(&mut node, from, to, amount, rng).map_err(|e| format!("{} -> {} {}: {}", from.name, to.name, amount, e))?;
+        }
+        node.mine(&miner.address()).map_err(|e| e.to_string())?;
+    }
+    Ok(node)
+}
+
+fn list_blocks(explorer: &Explorer) {
+    println!("  {:>4}  {:<10} {:<10} {:>4}  时间戳", "高度", "哈希", "父块", "交易");
+    for block in explorer.chain().blocks.iter().rev() {
+        let h = &block.header;
+        println!("  {:>6}  {}.. {}.. {:>6}  {}", h.height, short_hex(&block.hash()), short_hex(&h.prev_hash), block.txs.len(), h.timestamp);
+    }
+}
+
+fn print_tx(explorer: &Explorer, names: &Names, tx: &Transaction) {
+    println!("    tx {}", hex::encode(tx.txid()));
+    if tx.is_coinbase() {
+        println!("      输入: coinbase (新发行 + 手续费)");
+    } else {
+        for input in &tx.inputs {
+            match explorer.spent_output(&input.prev) {
+                Some(out) => println!("      输入: {}  {} {}", input.prev, names.show(&out.owner), out.amount),
+                None => println!("      输入: {}  (链上找不到，不应该发生)", input.prev),
+            }
+        }
+    }
+    for (index, out) in tx.outputs.iter().enumerate() {
+        println!("      输出 {}: {} {}", index, names.show(&out.owner), out.amount);
+    }
+}
+
+fn show_block(explorer: &Explorer, names: &Names) {
+    let input = read_line("  区块高度: ");
+    let Some(block) = input.parse::<usize>().ok().and_then(|h| explorer.chain().blocks.get(h)) else {
+        println!("  ❌ 没有高度为 {:?} 的区块 (链高 {})", input, explorer.chain().height());
+        return;
+    };
+    let h = &block.header;
+    println!("  区块 #{} {}", h.height, hex::encode(block.hash()));
+    println!("    prev_hash   {}", hex::encode(h.prev_hash));
+    println!("    merkle_root {}", h.merkle_root);
+    let recomputed = merkle_root(&block.txs);
+    println!("    重新计算    {} {}", recomputed, if recomputed == h.merkle_root { "✅" } else { "❌" });
+    println!("    state_root  {}", hex::encode(h.state_root));
+    println!("    难度 {}  nonce {}  时间戳 {}", h.difficulty, h.nonce, h.timestamp);
+    for tx in &block.txs {
+        print_tx(explorer, names, tx);
+    }
+}
+
+fn lookup_tx(explorer: &Explorer, names: &Names) {
+    let prefix = read_line("  txid (十六进制，前几位就行): ");
+    if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+        println!("  ❌ 不是十六进制: {:?}", prefix);
+        return;
+    }
+    let found = explorer.find_tx(&prefix);
+    match found.as_slice() {
+        [] => println!("  ❌ 链上没有以 {} 开头的交易", prefix),
+        [(location, tx)] => {
+            let confirmations = explorer.chain().height() - location.height + 1;
+            println!("  在区块 #{} 的第 {} 笔，{} 个确认", location.height, location.index, confirmations);
+            print_tx(explorer, names, tx);
+        }
+        many => {
+            println!("  前缀 {} 命中 {} 笔交易，再多输几位:", prefix, many.len());
+            for (location, tx) in many {
+                println!("    #{}[{}] {}", location.height, location.index, hex::encode(tx.txid()));
+            }
+        }
+    }
+}
+
+fn show_history(explorer: &Explorer, names: &Names, node: &Node) {
+    let address = names.resolve(&read_line("  名字或地址: "));
+    let history = explorer.history(&address);
+    if history.is_empty() {
+        println!("  {} 在链上没有任何记录", names.show(&address));
+        return;
+    }
+    println!("  {} ({})", names.show(&address), address);
+    println!("  {:>4}  {:<10} {:>6} {:>6} {:>6}", "高度", "交易", "收入", "支出", "余额");
+    for entry in &history {
+        println!("  {:>6}  {}.. {:>8} {:>8} {:>8}", entry.height, short_hex(&entry.txid), entry.received, entry.spent, entry.balance);
+    }
+    let last = history.last().map_or(0, |e| e.balance);
+    let utxo = node.utxos().balance(&address);
+    println!("  历史算出的余额 {}，UTXO 集合里的余额 {} {}", last, utxo, if last == utxo { "✅" } else { "❌ (不应该发生)" });
+}
+
+// 有存档就读存档 (读回来要重新验证整条链)，存档坏了或者没有就重新挖一条并保存
+fn open_chain(wallets: &[Wallet; 4], rng: &mut SimpleRng) -> Result<Node, String> {
+    if Path::new(CHAIN_FILE).exists() {
+        match Chain::load(CHAIN_FILE).and_then(Node::from_chain) {
+            Ok(node) => {
+                println!("从 {} 读回 {} 个区块 (含创世块)，全部重新验证通过 ✅", CHAIN_FILE, node.chain().blocks.len());
+                return Ok(node);
+            }
+            Err(e) => println!("❌ {} 不能用: {}\n重新挖一条", CHAIN_FILE, e),
+        }
+    }
+    let node = build_chain(wallets, rng)?;
+    node.chain().save(CHAIN_FILE)?;
+    println!("挖好 {} 个区块 (含创世块)，保存到 {}", node.chain().blocks.len(), CHAIN_FILE);
+    Ok(node)
+}
+
+// Alice 给 Bob 转 1 (钱不够就只有 coinbase)，Miner 出块，然后立刻存盘
+fn mine_and_save(node: &mut Node, wallets: &[Wallet; 4], rng: &mut SimpleRng) -> Result<u64, String> {
+    let [alice, bob, _, miner] = wallets;
+    if let Err(e) = pay(node, alice, bob, 1, rng) {
+        println!("  Alice -> Bob 1 没进交易池: {}", e);
+    }
+    let block = node.mine(&miner.address()).map_err(|e| e.to_string())?;
+    node.chain().save(CHAIN_FILE)?;
+    Ok(block.header.height)
+}
+
+pub fn run() {
+    println!("--- S06 Ex14: 区块浏览器 ---");
+    let mut key_rng = SimpleRng::new(KEY_SEED);
+    let wallets = ["Alice", "Bob", "Carol", "Miner"].map(|name| Wallet::generate(name, &mut key_rng));
+    let mut rng = SimpleRng::from_time();
+    let mut node = match open_chain(&wallets, &mut rng) {
+        Ok(node) => node,
+        Err(e) => {
+            println!("  ❌ 建链失败 (不应该发生): {}", e);
+            return;
+        }
+    };
+    let names = Names(wallets.iter().map(|w| (w.address(), w.name.clone())).collect());
+    println!("地址:");
+    for w in &wallets {
+        println!("  {:<6} {}", w.name, w.address());
+    }
+
+    loop {
+        println!("\n浏览器 (链高 {}):", node.chain().height());
+        println!("  1. 区块列表");
+        println!("  2. 查看区块 (区块头、Merkle 根、交易)");
+        println!("  3. 按 txid 查交易");
+        println!("  4. 地址余额历史");
+        println!("  5. 再挖一个块并保存");
+        println!("  6. 删除存档 (下次进来重新挖)");
+        println!("  0. 返回");
+        let choice = read_line("请输入: ");
+        let explorer = Explorer::new(node.chain());
+        match choice.as_str() {
+            "1" => list_blocks(&explorer),
+            "2" => show_block(&explorer, &names),
+            "3" => lookup_tx(&explorer, &names),
+            "4" => show_history(&explorer, &names, &node),
+            "5" => match mine_and_save(&mut node, &wallets, &mut rng) {
+                Ok(height) => println!("  ✅ 挖出 #{}，已保存到 {}", height, CHAIN_FILE),
+                Err(e) => println!("  ❌ {}", e),
+            },
+            "6" => match fs::remove_file(CHAIN_FILE) {
+                Ok(()) => println!("  已删除 {}", CHAIN_FILE),
+                Err(e) => println!("  ❌ 删除 {} 失败: {}", CHAIN_FILE, e),
+            },
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 余额是算出来的：
+        UTXO 链上没有余额字段。浏览器把每个输出登记下来，看到输入时回查它花的是谁的多少钱，
+        顺着时间累加，就得到"余额历史"；最后一行必须和 UtxoSet::balance 对得上。
+
+    2. 输入本身不带金额：
+        TxIn 里只有 OutPoint，要显示"花了多少"就得找到那个旧输出 —— 这也是为什么验证交易必须有 UTXO 集合。
+
+    3. Merkle 根可以重新算：
+        浏览器 (和任何全节点) 拿到交易列表后都能重算 Merkle 根，对照区块头里的那个，确认交易没被篡改。
+
+    4. 索引是全节点之外的东西：
+        按地址查历史需要额外的索引 (比特币核心默认不建)，浏览器为此要维护自己的数据库。
+
+    5. 存档不可信：
+        读回来的链要重新验证 (Chain::validate 查区块头，Node::from_chain 重放所有交易和签名)，
+        手动改过 toy_chain.json 里的任何一个金额，下次进来都会被发现。
+*/
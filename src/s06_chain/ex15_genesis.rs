@@ -0,0 +1,147 @@
+// src/s06_chain/ex15_genesis.rs
+use crate::common::input::read_line;
+use crate::s05_zk_lab::hash::short_hex;
+
+use super::genesis::{handshake, GenesisConfig};
+use super::node::Node;
+
+/*
+业务场景：启动一个新网络 (或者加入一个已有的网络)，节点从哪知道"链的参数"和"第一块长什么样"？
+    比特币把创世块写死在代码里；以太坊和大多数新链用一个 genesis.json，节点启动时读它。
+
+本练习：
+    1. 读 data/genesis.json (路径可以自己输入)，解析成 GenesisConfig / ConsensusConfig
+    2. 两个节点各自读同一份文件：创世哈希一样，握手成功；在上面挖一个块
+    3. ❌ 握手失败：换了魔数的测试网、偷偷多给自己分了钱的配置
+    4. ❌ 写错的配置文件：所有问题一次报出来，带字段路径
+*/
+
+const DEFAULT_PATH: &str = "data/genesis.json";
+
+const BROKEN: [(&str, &str); 4] = [
+    ("不是 JSON", r#"{"chain_id": "devnet", "magic": "#),
+    ("缺字段", r#"{"chain_id": "devnet", "consensus": {}, "balances": []}"#),
+    (
+        "字段都在，但值不对",
+        r#"{"chain_id": "dev net!", "magic": "7a6b", "timestamp": 1700000000, "consensus": {"difficulty": 64},
+            "balances": [{"owner": "Alice", "amount": 10}, {"owner": "Alice", "amount": 0}, {"owner": "Bob"}]}"#,
+    ),
+    (
+        "总发行量溢出",
+        r#"{"chain_id": "devnet", "magic": "7a6b6c31", "timestamp": 1, "consensus": {"difficulty": 8},
+            "balances": [{"owner": "Alice", "amount": 9223372036854775807}, {"owner": "Bob", "amount": 9223372036854775807}, {"owner": "Carol", "amount": 9}]}"#,
+    ),
+];
+
+fn print_problems(problems: &[String]) {
+    println!("  ❌ {} 个问题:", problems.len());
+    for problem in problems {
+        println!("      - {}", problem);
+    }
+}
+
+fn show_balances(node: &Node, config: &GenesisConfig) {
+    let line: Vec<String> = config.balances.iter().map(|(owner, _)| format!("{} {}", owner, node.utxos().balance(owner))).collect();
+    println!("    高度 {}  余额: {}", node.chain().height(), line.join("  "));
+}
+
+pub fn run() {
+    println!("--- S06 Ex15: 从 genesis.json 启动链 ---");
+
+    // ==========================================
+    // 1. 读配置
+    // ==========================================
+    let input = read_line(&format!("\n[1] 创世配置文件 (直接回车用 {}): ", DEFAULT_PATH));
+    let path = if input.is_empty() { DEFAULT_PATH } else { input.as_str() };
+    let config = match GenesisConfig::load(path) {
+        Ok(config) => config,
+        Err(problems) => {
+            print_problems(&problems);
+            return;
+        }
+    };
+    let consensus = &config.consensus;
+    println!("  chain_id   {}", consensus.chain_id);
+    println!("  magic      {}", hex::encode(consensus.magic));
+    println!("  difficulty {}", consensus.difficulty);
+    println!("  timestamp  {}", config.timestamp);
+    for (owner, amount) in &config.balances {
+        println!("  初始分配   {:<8} {}", owner, amount);
+    }
+    println!("  总发行量   {}", config.total_supply());
+
+    // ==========================================
+    // 2. 两个节点，同一份配置
+    // ==========================================
+    println!("\n[2] 节点 A 和节点 B 各自读同一份文件");
+    let (mut node_a, mut node_b) = match (config.build_node(), GenesisConfig::load(path).map_err(|p| p.join("; ")).and_then(|c| c.build_node())) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(e), _) | (_, Err(e)) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    let (genesis_a, genesis_b) = (node_a.chain().tip().hash(), node_b.chain().tip().hash());
+    println!("  A 的创世块 {}..，B 的创世块 {}..", short_hex(&genesis_a), short_hex(&genesis_b));
+    match handshake((consensus, &genesis_a), (consensus, &genesis_b)) {
+        Ok(()) => println!("  ✅ 握手成功：同一条链"),
+        Err(e) => println!("  ❌ 握手失败 (不应该发生): {}", e),
+    }
+    show_balances(&node_a, &config);
+    // 只要创世块一样，A 挖的块对 B 也合法
+    let relayed = node_a.mine("Miner").and_then(|block| {
+        let height = block.header.height;
+        node_b.connect(block).map(|()| height)
+    });
+    match relayed {
+        Ok(height) => println!("  A 挖出 #{}，B 验证通过并接上 ✅", height),
+        Err(e) => println!("  ❌ {} (不应该发生)", e),
+    }
+
+    // ==========================================
+    // 3. 握手失败
+    // ==========================================
+    println!("\n[3] ❌ 和配置不同的节点握手");
+    let mut testnet = config.clone();
+    testnet.consensus.magic[3] ^= 0xff;
+    testnet.consensus.chain_id.push_str("-test");
+    let mut greedy = config.clone();
+    if let Some((_, amount)) = greedy.balances.first_mut() {
+        *amount += 1_000_000;
+    }
+    for (label, other) in [("测试网 (换了魔数)", &testnet), ("偷偷改了初始分配", &greedy)] {
+        let genesis = other.genesis_block().hash();
+        match handshake((consensus, &genesis_a), (&other.consensus, &genesis)) {
+            Ok(()) => println!("  {}: ✅ 握手成功 (不应该发生)", label),
+            Err(e) => println!("  {}: {}", label, e),
+        }
+    }
+
+    // ==========================================
+    // 4. 写错的配置文件
+    // ==========================================
+    println!("\n[4] ❌ 写错的配置文件");
+    for (label, text) in BROKEN {
+        println!("  {}", label);
+        match GenesisConfig::parse(text) {
+            Ok(_) => println!("  ✅ 解析成功 (不应该发生)"),
+            Err(problems) => print_problems(&problems),
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 创世块必须是确定的：
+        时间戳、难度、初始分配全部来自配置文件，挖矿 (从 nonce 0 开始) 也是确定的，
+        所以两个节点各自造出来的创世块哈希一模一样 —— 它就是"这条链"的身份证。
+
+    2. 握手比较三样东西：
+        魔数挡住别的网络的消息，chain_id 挡住跨链重放，创世哈希挡住"参数一样、初始分配被改过"的配置。
+
+    3. 配置错误一次报完：
+        Problems 收集每个字段的问题并带上路径 (balances[1].amount)，缺字段、值越界、重复、溢出都能同时看到。
+
+    4. 创世分配不受出块奖励限制：
+        UtxoSet::apply_block 对高度 0 的 coinbase 不检查上限；别的高度的块仍然只能领 出块奖励 + 手续费。
+*/
@@ -0,0 +1,291 @@
+// src/s06_chain/ex16_rollup.rs
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::s05_zk_lab::ex01_merkle::MerkleProof;
+use crate::s05_zk_lab::hash::{short_hex, Digest};
+
+use super::chain::Chain;
+use super::rollup::{Batch, BatchCommitment, L2State, L2Tx};
+
+/*
+业务场景：L1 每个块只能放几笔交易，手续费很贵。Rollup 把成百上千笔 L2 交易压成 L1 上的一笔"承诺"。
+
+本练习 (全部通过 channel 通信)：
+    1. 排序器线程：从 channel 收 L2 交易，按 nonce 和余额检查，攒够 BATCH_SIZE 笔，或者最早的一笔等了 BATCH_TIMEOUT，就封一个批次，
+       算出批次的 Merkle 根，挖一个 L1 块把承诺发上去
+    2. 用户线程 (Alice / Bob / Carol)：各自发几笔转账，拿到"软确认"；然后要包含证明，
+       自己去 L1 上找到那个批次的 tx_root 验证 —— 不信任排序器
+    3. Mallory 线程：透支、重放 Alice 的交易，都被排序器拒绝
+    4. ❌ 把证明里的交易金额改掉，对不上 L1 上的根
+*/
+
+const L1_DIFFICULTY: u32 = 8;
+const BATCH_SIZE: usize = 4;
+const BATCH_TIMEOUT: Duration = Duration::from_millis(40);
+const POLL: Duration = Duration::from_millis(20);
+const GENESIS: [(&str, u64); 4] = [("Alice", 100), ("Bob", 50), ("Carol", 20), ("Mallory", 5)];
+
+enum ProofReply {
+    // 排序器收了，但还没封进批次 (软确认)
+    Pending,
+    Included { batch: u64, tx: L2Tx, proof: MerkleProof },
+    Unknown,
+}
+
+enum Request {
+    Submit { tx: L2Tx, reply: Sender<Result<(), String>> },
+    Prove { hash: Digest, reply: Sender<ProofReply> },
+}
+
+struct SequencerSummary {
+    state: L2State,
+    batches: Vec<Batch>,
+    rejected: usize,
+}
+
+// ==========================================
+// 1. 排序器
+// ==========================================
+fn post(l1: &Mutex<Chain<String>>, state: &L2State, batches: &mut Vec<Batch>, pending: &mut Vec<L2Tx>) {
+    let batch = Batch::seal(batches.len() as u64, std::mem::take(pending), state);
+    let mut chain = l1.lock().expect("l1 lock poisoned");
+    match chain.mine_block(vec![batch.commitment.to_l1_tx()]) {
+        Ok(result) => println!(
+            "  [排序器] 批次 #{}: {} 笔，tx_root {}，state_root {}.. -> L1 #{}",
+            batch.commitment.batch,
+            batch.txs.len(),
+            batch.commitment.tx_root,
+            short_hex(&batch.commitment.state_root),
+            result.header.height
+        ),
+        Err(e) => println!("  [排序器] ❌ L1 出块失败 (不应该发生): {}", e),
+    }
+    batches.push(batch);
+}
+
+fn sequencer(inbox: Receiver<Request>, l1: Arc<Mutex<Chain<String>>>) -> SequencerSummary {
+    let mut state = L2State::new(&GENESIS);
+    let mut pending = Vec::new();
+    let mut batches = Vec::new();
+    let mut rejected = 0;
+    // 批次里第一笔交易进来时开始计时；证明请求再频繁也不会推迟封批
+    let mut deadline: Option<Instant> = None;
+    loop {
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            post(&l1, &state, &mut batches, &mut pending);
+            deadline = None;
+        }
+        let received = match deadline {
+            Some(d) => inbox.recv_timeout(d.saturating_duration_since(Instant::now())),
+            None => inbox.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        let request = match received {
+            Ok(request) => request,
+            // 到点了，回到循环开头封批
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        match request {
+            Request::Submit { tx, reply } => {
+                let result = state.apply(&tx);
+                match &result {
+                    Ok(()) => {
+                        pending.push(tx);
+                        deadline.get_or_insert_with(|| Instant::now() + BATCH_TIMEOUT);
+                    }
+                    Err(_) => rejected += 1,
+                }
+                let _ = reply.send(result);
+                if pending.len() == BATCH_SIZE {
+                    post(&l1, &state, &mut batches, &mut pending);
+                    deadline = None;
+                }
+            }
+            Request::Prove { hash, reply } => {
+                let answer = if pending.iter().any(|tx| tx.hash() == hash) {
+                    ProofReply::Pending
+                } else {
+                    batches
+                        .iter()
+                        .find_map(|b| b.prove(&hash).map(|(tx, proof)| ProofReply::Included { batch: b.commitment.batch, tx, proof }))
+                        .unwrap_or(ProofReply::Unknown)
+                };
+                let _ = reply.send(answer);
+            }
+        }
+    }
+    // 所有用户都走了：剩下的也要发出去
+    if !pending.is_empty() {
+        post(&l1, &state, &mut batches, &mut pending);
+    }
+    SequencerSummary { state, batches, rejected }
+}
+
+// ==========================================
+// 2. 用户
+// ==========================================
+fn submit(to_sequencer: &Sender<Request>, tx: L2Tx) -> Result<(), String> {
+    let (reply, answer) = mpsc::channel();
+    to_sequencer.send(Request::Submit { tx, reply }).map_err(|_| String::from("排序器下线了"))?;
+    answer.recv().map_err(|_| String::from("排序器没有回答"))?
+}
+
+// 轮询直到交易被封进批次
+fn fetch_proof(to_sequencer: &Sender<Request>, hash: Digest) -> Result<(u64, L2Tx, MerkleProof), String> {
+    loop {
+        let (reply, answer) = mpsc::channel();
+        to_sequencer.send(Request::Prove { hash, reply }).map_err(|_| String::from("排序器下线了"))?;
+        match answer.recv().map_err(|_| String::from("排序器没有回答"))? {
+            ProofReply::Included { batch, tx, proof } => return Ok((batch, tx, proof)),
+            ProofReply::Pending => thread::sleep(POLL),
+            ProofReply::Unknown => return Err(String::from("排序器说没见过这笔交易")),
+        }
+    }
+}
+
+// 只信 L1：在 L1 上找到这个批次的承诺，用它的 tx_root 验证
+fn verify_on_l1(l1: &Mutex<Chain<String>>, batch: u64, tx: &L2Tx, proof: &MerkleProof) -> Result<u64, String> {
+    let chain = l1.lock().expect("l1 lock poisoned");
+    let (height, commitment) =
+        BatchCommitment::scan(&chain).into_iter().find(|(_, c)| c.batch == batch).ok_or_else(|| format!("L1 上没有批次 #{} 的承诺", batch))?;
+    if !proof.verify(tx, &commitment.tx_root) {
+        return Err(format!("证明和 L1 #{} 上批次 #{} 的 tx_root {} 对不上", height, batch, commitment.tx_root));
+    }
+    Ok(height)
+}
+
+fn user(name: &'static str, transfers: Vec<(&'static str, u64)>, to_sequencer: Sender<Request>, l1: Arc<Mutex<Chain<String>>>) -> Vec<String> {
+    let mut log = Vec::new();
+    let mut accepted = Vec::new();
+    for (nonce, (to, amount)) in transfers.into_iter().enumerate() {
+        let tx = L2Tx { from: name.to_string(), to: to.to_string(), amount, nonce: nonce as u64 };
+        match submit(&to_sequencer, tx.clone()) {
+            Ok(()) => {
+                log.push(format!("{} ✅ 软确认", tx));
+                accepted.push(tx);
+            }
+            Err(e) => log.push(format!("{} ❌ {}", tx, e)),
+        }
+    }
+    for tx in accepted {
+        let checked = fetch_proof(&to_sequencer, tx.hash()).and_then(|(batch, tx, proof)| {
+            let height = verify_on_l1(&l1, batch, &tx, &proof)?;
+            Ok((batch, height, proof.siblings.len()))
+        });
+        match checked {
+            Ok((batch, height, siblings)) => log.push(format!("{} ✅ 在批次 #{} 里 (L1 #{}，证明 {} 个兄弟哈希)", tx, batch, height, siblings)),
+            Err(e) => log.push(format!("{} ❌ {}", tx, e)),
+        }
+    }
+    log
+}
+
+// Mallory：先透支，再把 Alice 的第一笔交易原样重放一遍
+fn mallory(to_sequencer: Sender<Request>) -> Vec<String> {
+    let attempts = [
+        L2Tx { from: String::from("Mallory"), to: String::from("Mallory"), amount: 1_000, nonce: 0 },
+        L2Tx { from: String::from("Alice"), to: String::from("Bob"), amount: 30, nonce: 0 },
+    ];
+    attempts
+        .into_iter()
+        .map(|tx| match submit(&to_sequencer, tx.clone()) {
+            Ok(()) => format!("{} ✅ 被接受了 (不应该发生)", tx),
+            Err(e) => format!("{} ❌ {}", tx, e),
+        })
+        .collect()
+}
+
+pub fn run() {
+    println!("--- S06 Ex16: Rollup 排序器 (L2 批次 -> L1 承诺) ---");
+    let l1 = Arc::new(Mutex::new(Chain::new(L1_DIFFICULTY, vec![String::from("L1 genesis")])));
+    let initial: Vec<String> = GENESIS.iter().map(|(name, balance)| format!("{} {}", name, balance)).collect();
+    println!("\n[1] L2 初始余额: {}；每批最多 {} 笔，最早一笔等满 {:?} 也封批", initial.join("  "), BATCH_SIZE, BATCH_TIMEOUT);
+
+    let (to_sequencer, inbox) = mpsc::channel();
+    let sequencer_l1 = Arc::clone(&l1);
+    let sequencer = thread::spawn(move || sequencer(inbox, sequencer_l1));
+
+    let plans: [(&str, Vec<(&str, u64)>); 3] =
+        [("Alice", vec![("Bob", 30), ("Carol", 10), ("Bob", 5)]), ("Bob", vec![("Carol", 20), ("Alice", 15)]), ("Carol", vec![("Alice", 25), ("Bob", 1)])];
+    let mut users = Vec::new();
+    for (name, transfers) in plans {
+        let (sender, l1) = (to_sequencer.clone(), Arc::clone(&l1));
+        users.push((name, thread::spawn(move || user(name, transfers, sender, l1))));
+    }
+    let attacker = {
+        let sender = to_sequencer.clone();
+        thread::spawn(move || mallory(sender))
+    };
+    // 主线程不再发请求：所有用户线程结束后 channel 断开，排序器收尾退出
+    drop(to_sequencer);
+
+    let mut logs = Vec::new();
+    for (name, handle) in users {
+        logs.push((name, handle.join().unwrap_or_else(|_| vec![String::from("❌ 线程 panic 了 (不应该发生)")])));
+    }
+    logs.push(("Mallory", attacker.join().unwrap_or_else(|_| vec![String::from("❌ 线程 panic 了 (不应该发生)")])));
+    let Ok(summary) = sequencer.join() else {
+        println!("  ❌ 排序器线程 panic 了 (不应该发生)");
+        return;
+    };
+
+    println!("\n[2] 各用户看到的结果 (线程结束后统一打印)");
+    for (name, lines) in &logs {
+        println!("  {}:", name);
+        for line in lines {
+            println!("    {}", line);
+        }
+    }
+
+    println!("\n[3] L1 上的承诺 vs L2 的实际数据");
+    let chain = l1.lock().expect("l1 lock poisoned");
+    let commitments = BatchCommitment::scan(&chain);
+    let l2_bytes: usize = summary.batches.iter().flat_map(|b| &b.txs).map(|tx| tx.encode().len()).sum();
+    let l1_bytes: usize = commitments.iter().map(|(_, c)| c.to_l1_tx().len()).sum();
+    let total: usize = commitments.iter().map(|(_, c)| c.tx_count).sum();
+    println!("  L1 高度 {}，{} 个批次承诺，承载 {} 笔 L2 交易 (拒绝 {} 笔)", chain.height(), commitments.len(), total, summary.rejected);
+    println!("  L2 交易 {} 字节 -> L1 上 {} 字节的承诺 (真实 rollup 还要把压缩后的交易数据也发上去，见数据可用性)", l2_bytes, l1_bytes);
+    let balances: Vec<String> = summary.state.accounts().map(|(name, balance, nonce)| format!("{} {} (nonce {})", name, balance, nonce)).collect();
+    println!("  L2 最终状态: {}", balances.join("  "));
+    let latest = commitments.last().map(|(_, c)| c.state_root);
+    if latest == Some(summary.state.root()) {
+        println!("  ✅ L1 上最后一个 state_root 和排序器的状态一致");
+    } else {
+        println!("  ❌ L1 上最后一个 state_root 和排序器的状态不一致 (不应该发生)");
+    }
+    drop(chain);
+
+    println!("\n[4] ❌ 中间人把一份真证明里的金额改成 999");
+    let Some(batch) = summary.batches.first() else {
+        return;
+    };
+    let Some((mut tx, proof)) = batch.txs.first().and_then(|tx| batch.prove(&tx.hash())) else {
+        return;
+    };
+    tx.amount = 999;
+    match verify_on_l1(&l1, batch.commitment.batch, &tx, &proof) {
+        Ok(_) => println!("  ✅ 通过了 (不应该发生)"),
+        Err(e) => println!("  {}: {}", tx, e),
+    }
+}
+
+/*
+关键点总结：
+    1. 信任从排序器转移到 L1：
+        软确认只是排序器的一句话；交易真正"定下来"是承诺上了 L1。
+        用户拿到的证明只对照 L1 上的 tx_root，排序器改不了已经发布的承诺。
+
+    2. 批次 = 攒一批 + 超时兜底：
+        只靠"攒够 N 笔"的话，交易少的时候最后几笔永远等不到。
+        超时要从批次里第一笔交易算起，而不是"多久没收到消息" —— 用户不停地轮询证明，后者永远不会超时。
+
+    3. 排序器只能拒绝，不能伪造：
+        它可以不回答证明请求 (审查)，但给出的证明一定要对得上 L1 的根 —— 改金额、编交易都会被发现。
+        真实 rollup 还要求把交易数据发到 L1 (数据可用性)，排序器跑路之后别人也能重建状态。
+
+    4. state_root 也上了 L1：
+        下一个练习里排序器会发一个错的 state_root，观察者重新执行批次就能发现，进入欺诈证明流程。
+*/
@@ -0,0 +1,337 @@
+// src/s06_chain/ex17_fraud_proof.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::{sha256, short_hex, Digest};
+
+use super::chain::Chain;
+use super::rollup::{one_step, state_at, trace, Batch, BatchCommitment, Bisection, L2State, L2Tx, Verdict};
+
+/*
+业务场景：乐观 rollup 的 L1 合约不执行 L2 交易，排序器说状态根是什么就先记什么。
+    安全性全靠一个假设：挑战窗口结束之前，至少有一个诚实的观察者在线，重新执行了批次并且在发现问题时挑战。
+
+本练习：
+    1. 固定剧本：
+        (a) 诚实批次：观察者重新执行一致，窗口过后最终确认
+        (b) 排序器在某一步给自己凭空加钱：观察者发现最终的根不对，二分定位到那一步，L1 只执行这一笔就判定欺诈，批次回滚
+        (c) 恶意挑战者挑战一个诚实批次：二分到底，L1 执行结果和排序器一致，挑战失败
+    2. 交互：排序器自己决定要不要作恶 (1/CHEAT_ODDS 的概率)，你控制观察者上线下线、推进 L1 区块；
+       观察者离线太久，作恶的批次就会熬过挑战窗口被最终确认
+*/
+
+const L1_DIFFICULTY: u32 = 8;
+const BATCH_TXS: usize = 8;
+const CHALLENGE_WINDOW: u64 = 3;
+const BOND: u64 = 100;
+const CHEAT_ODDS: u64 = 3;
+const STOLEN: u64 = 1_000;
+const SEQUENCER: &str = "Sequencer";
+const GENESIS: [(&str, u64); 3] = [("Alice", 100), ("Bob", 100), ("Carol", 100)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pending,
+    Finalized,
+}
+
+// L1 合约里记录的一个批次
+// 真实的乐观 rollup 会把交易数据也发到 L1 (数据可用性)，观察者才能重新执行；这里直接放在一起
+struct Posted {
+    commitment: BatchCommitment,
+    txs: Vec<L2Tx>,
+    // 排序器自己的执行轨迹：二分时它从这里回答
+    claimed: Vec<Digest>,
+    // 排序器发这个批次之前的状态，回滚时退回这里
+    pre: L2State,
+    l1_height: u64,
+    status: Status,
+    // 只有旁白知道：第几笔之后作了恶
+    forged_at: Option<usize>,
+}
+
+struct Game {
+    l1: Chain<String>,
+    sequencer: L2State,
+    posted: Vec<Posted>,
+    watcher_online: bool,
+    // 观察者自己执行出来的状态，以及检查到了第几个批次
+    verified: L2State,
+    checked: usize,
+    frauds: usize,
+    broken: bool,
+    rng: SimpleRng,
+}
+
+impl Game {
+    fn new(rng: SimpleRng) -> Self {
+        let genesis = L2State::new(&GENESIS);
+        Game {
+            l1: Chain::new(L1_DIFFICULTY, vec![String::from("L1 genesis")]),
+            sequencer: genesis.clone(),
+            posted: Vec::new(),
+            watcher_online: true,
+            verified: genesis,
+            checked: 0,
+            frauds: 0,
+            broken: false,
+            rng,
+        }
+    }
+
+    // 随机生成 BATCH_TXS 笔在排序器当前状态下合法的转账
+    fn collect_txs(&mut self) -> Vec<L2Tx> {
+        let mut scratch = self.sequencer.clone();
+        let mut txs = Vec::new();
+        while txs.len() < BATCH_TXS {
+            let from = GENESIS[self.rng.gen_range(GENESIS.len() as u64) as usize].0;
+            let to = GENESIS[self.rng.gen_range(GENESIS.len() as u64) as usize].0;
+            let tx = L2Tx { from: from.to_string(), to: to.to_string(), amount: 1 + self.rng.gen_range(20), nonce: scratch.nonce(from) };
+            if from != to && scratch.apply(&tx).is_ok() {
+                txs.push(tx);
+            }
+        }
+        txs
+    }
+
+    fn mine(&mut self, tx: String) {
+        if let Err(e) = self.l1.mine_block(vec![tx]) {
+            println!("    ❌ L1 出块失败 (不应该发生): {}", e);
+        }
+    }
+
+    // 排序器执行并发布一个批次；forge 是 Some(k) 时在第 k 笔之后给自己加钱
+    fn post_batch(&mut self, forge: Option<usize>) {
+        let txs = self.collect_txs();
+        let pre = self.sequencer.clone();
+        let mut claimed = vec![pre.root()];
+        for (i, tx) in txs.iter().enumerate() {
+            let _ = self.sequencer.apply(tx);
+            if forge == Some(i) {
+                self.sequencer.credit(SEQUENCER, STOLEN);
+            }
+            claimed.push(self.sequencer.root());
+        }
+        let batch = Batch::seal(self.posted.len() as u64, txs, &self.sequencer);
+        let commitment = batch.commitment;
+        self.mine(commitment.to_l1_tx());
+        println!("  L1 #{}: 排序器发布批次 #{} ({} 笔，state_root {}..)", self.l1.height(), commitment.batch, commitment.tx_count, short_hex(&commitment.state_root));
+        self.posted.push(Posted { commitment, txs: batch.txs, claimed, pre, l1_height: self.l1.height(), status: Status::Pending, forged_at: forge });
+        self.settle();
+    }
+
+    // 排序器自己决定要不要作恶
+    fn post_random(&mut self) {
+        let forge = (self.rng.gen_range(CHEAT_ODDS) == 0).then(|| self.rng.gen_range(BATCH_TXS as u64) as usize);
+        self.post_batch(forge);
+    }
+
+    // 一个和 rollup 无关的 L1 区块，只是让时间往前走
+    fn idle_block(&mut self) {
+        self.mine(String::from("其它 L1 交易"));
+        println!("  L1 #{}: 普通区块", self.l1.height());
+        self.settle();
+    }
+
+    // 每出一个 L1 块：观察者 (在线的话) 先检查，然后窗口到期的批次最终确认
+    fn settle(&mut self) {
+        if self.watcher_online {
+            self.watch();
+        }
+        let height = self.l1.height();
+        for posted in self.posted.iter_mut().filter(|p| p.status == Status::Pending && height >= p.l1_height + CHALLENGE_WINDOW) {
+            posted.status = Status::Finalized;
+            match posted.forged_at {
+                None => println!("    ✅ 批次 #{} 挑战窗口结束，最终确认", posted.commitment.batch),
+                Some(step) => {
+                    println!("    ❌ 批次 #{} 挑战窗口结束，最终确认 —— 但它在第 {} 笔之后给 {} 凭空加了 {}！", posted.commitment.batch, step, SEQUENCER, STOLEN);
+                    println!("    ❌ 窗口里没有人挑战，错误的状态根成了事实，乐观假设被打破");
+                    self.broken = true;
+                }
+            }
+        }
+    }
+
+    // 按顺序重新执行还没检查过的批次
+    fn watch(&mut self) {
+        while self.checked < self.posted.len() {
+            let posted = &self.posted[self.checked];
+            let honest = trace(&self.verified, &posted.txs);
+            let ours = *honest.last().expect("trace always has the pre-state root");
+            if ours == posted.commitment.state_root {
+                println!("    观察者: 重新执行批次 #{}，状态根一致 ✅", posted.commitment.batch);
+                self.verified = state_at(&self.verified, &posted.txs, posted.txs.len());
+                self.checked += 1;
+            } else {
+                println!("    ⚠️ 观察者: 批次 #{} 重新执行得到 {}..，排序器声称 {}..，发起挑战", posted.commitment.batch, short_hex(&ours), short_hex(&posted.commitment.state_root));
+                self.challenge(self.checked);
+                return;
+            }
+        }
+    }
+
+    // 观察者用自己的执行轨迹和排序器二分
+    fn challenge(&mut self, index: usize) {
+        let posted = &self.posted[index];
+        let honest = trace(&self.verified, &posted.txs);
+        let step = bisect(&posted.claimed, &honest);
+        let pre = state_at(&self.verified, &posted.txs, step);
+        match judge(posted, step, &pre) {
+            Ok(Verdict::Fraud { .. }) => {
+                let reverted = self.posted.len() - index;
+                println!("      🔥 欺诈成立：排序器押金 {} 罚没 (一半奖励观察者)，批次 #{} 起共 {} 个批次回滚", BOND, index, reverted);
+                self.mine(format!("rollup-fraud|{}|{}", index, step));
+                self.sequencer = self.posted[index].pre.clone();
+                self.posted.truncate(index);
+                self.frauds += 1;
+            }
+            Ok(Verdict::Honest) => println!("      ✅ 排序器这一步是对的，挑战失败 (不应该发生)"),
+            Err(e) => println!("      ❌ {}", e),
+        }
+    }
+
+    // 恶意挑战者：对每个中点都说"不同意"，想把诚实的批次拖下水
+    fn false_challenge(&mut self, index: usize) {
+        let posted = &self.posted[index];
+        let fake: Vec<Digest> = posted.claimed.iter().enumerate().map(|(i, root)| if i == 0 { *root } else { sha256(root) }).collect();
+        println!("    Mallory 挑战批次 #{}，声称最终的根应该是 {}..", posted.commitment.batch, short_hex(&fake[fake.len() - 1]));
+        let step = bisect(&posted.claimed, &fake);
+        // 一步证明里 Mallory 必须交出真实的前状态，否则连根都对不上
+        let pre = state_at(&posted.pre, &posted.txs, step);
+        match judge(posted, step, &pre) {
+            Ok(Verdict::Honest) => println!("      ✅ L1 执行结果和排序器一致：挑战失败，Mallory 的挑战押金 {} 被罚没，批次不受影响", BOND),
+            Ok(Verdict::Fraud { .. }) => println!("      ❌ 诚实批次被判欺诈 (不应该发生)"),
+            Err(e) => println!("      ❌ {}", e),
+        }
+    }
+
+    fn print_status(&self) {
+        let pending = self.posted.iter().filter(|p| p.status == Status::Pending).count();
+        println!(
+            "  L1 高度 {}，观察者{}，{} 个批次 ({} 个待确认)，抓到 {} 次欺诈",
+            self.l1.height(),
+            if self.watcher_online { "在线" } else { "离线" },
+            self.posted.len(),
+            pending,
+            self.frauds
+        );
+        for posted in &self.posted {
+            let status = match posted.status {
+                Status::Finalized => String::from("已确认"),
+                Status::Pending => format!("待确认 (还剩 {} 个块)", posted.l1_height + CHALLENGE_WINDOW - self.l1.height()),
+            };
+            let secret = posted.forged_at.map_or(String::new(), |step| format!("  ⚠️ 第 {} 笔之后作了恶", step));
+            println!("    #{} L1 #{} state_root {}.. {}{}", posted.commitment.batch, posted.l1_height, short_hex(&posted.commitment.state_root), status, secret);
+        }
+    }
+}
+
+// 二分：双方同意 trace[0]，不同意最后一个根；返回有分歧的那一步 (第几笔交易)
+fn bisect(claimed: &[Digest], challenger: &[Digest]) -> usize {
+    let mut game = Bisection::new(claimed.len() - 1);
+    let mut round = 1;
+    while let Some(mid) = game.mid() {
+        let agree = claimed[mid] == challenger[mid];
+        println!(
+            "      第 {} 轮 [{}, {}]: 排序器公布 trace[{}] = {}..，挑战者算出 {}.. -> {}",
+            round,
+            game.lo,
+            game.hi,
+            mid,
+            short_hex(&claimed[mid]),
+            short_hex(&challenger[mid]),
+            if agree { "同意，问题在后半段" } else { "不同意，问题在前半段" }
+        );
+        game.narrow(agree);
+        round += 1;
+    }
+    game.lo
+}
+
+// L1 只执行第 step 笔交易
+fn judge(posted: &Posted, step: usize, pre: &L2State) -> Result<Verdict, String> {
+    let (agreed, claimed) = (&posted.claimed[step], &posted.claimed[step + 1]);
+    println!("      定位到第 {} 笔 ({})：双方同意执行前是 {}..，排序器声称执行后是 {}..", step, posted.txs[step], short_hex(agreed), short_hex(claimed));
+    let verdict = one_step(pre, agreed, &posted.txs[step], claimed)?;
+    if let Verdict::Fraud { actual } = &verdict {
+        println!("      L1 执行这一笔得到 {}.. ≠ {}..", short_hex(actual), short_hex(claimed));
+    }
+    Ok(verdict)
+}
+
+pub fn run() {
+    println!("--- S06 Ex17: 乐观 rollup 欺诈证明 (挑战窗口 + 二分) ---");
+    println!("每个批次 {} 笔交易，挑战窗口 {} 个 L1 区块，排序器押金 {}", BATCH_TXS, CHALLENGE_WINDOW, BOND);
+
+    // ==========================================
+    // 1. 固定剧本
+    // ==========================================
+    println!("\n[1a] 诚实批次");
+    let mut game = Game::new(SimpleRng::new(17));
+    game.post_batch(None);
+    for _ in 0..CHALLENGE_WINDOW {
+        game.idle_block();
+    }
+
+    println!("\n[1b] 排序器在第 5 笔之后给自己加了 {}", STOLEN);
+    game.post_batch(Some(5));
+    game.print_status();
+
+    println!("\n[1c] 恶意挑战者");
+    game.post_batch(None);
+    let last = game.posted.len() - 1;
+    game.false_challenge(last);
+
+    // ==========================================
+    // 2. 交互
+    // ==========================================
+    println!("\n[2] 新的 rollup：排序器有 1/{} 的概率作恶，你来控制观察者", CHEAT_ODDS);
+    let mut game = Game::new(SimpleRng::from_time());
+    loop {
+        println!();
+        game.print_status();
+        println!("  1. 排序器发布下一个批次");
+        println!("  2. 出一个普通 L1 区块 (挑战窗口往前走)");
+        println!("  3. 观察者{}", if game.watcher_online { "下线" } else { "上线" });
+        println!("  0. 返回");
+        match read_line("请输入: ").as_str() {
+            "1" => game.post_random(),
+            "2" => game.idle_block(),
+            "3" => {
+                game.watcher_online = !game.watcher_online;
+                if game.watcher_online {
+                    println!("  观察者上线，补查错过的批次");
+                    game.watch();
+                } else {
+                    println!("  观察者下线了");
+                }
+            }
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+        if game.broken {
+            println!("\n  {} 在 L2 上凭空多出 {}，而且再也挑战不了了", SEQUENCER, STOLEN);
+            break;
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 乐观 = 先相信，再给时间反驳：
+        L1 不执行任何 L2 交易，直接记下排序器的状态根；挑战窗口 (真实网络里一般是 7 天) 结束才算最终确认。
+        安全假设只有一条：窗口内至少一个诚实观察者在线，并且拿得到交易数据 —— 离线太久，错的根照样被确认。
+
+    2. 二分把争议缩到一步：
+        n 笔交易只需要 log2(n) 轮，每轮排序器公布一个中间根，挑战者表态。
+        最后 L1 只执行一笔交易，成本和批次大小无关。
+
+    3. 一步证明不信任任何一方：
+        挑战者必须交出和双方同意的根对得上的完整前状态，L1 自己执行、自己比较；
+        恶意挑战诚实批次的人二分到底也只会得到"排序器是对的"，自己的押金被罚。
+
+    4. 回滚是连锁的：
+        后面的批次都建立在错误的状态上，所以从出错的批次开始全部回滚，排序器退回发布之前的状态。
+
+    5. 执行规则必须确定：
+        非法交易一律当空操作 (state_at / trace / one_step 用的是同一套 apply)，否则双方连"正确答案"都对不上。
+*/
@@ -0,0 +1,208 @@
+// src/s06_chain/ex18_da_sampling.rs
+use std::collections::HashSet;
+
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::ex01_merkle::{MerkleProof, MerkleTree};
+use crate::s05_zk_lab::math::field::M31;
+
+use super::block::merkle_root;
+use super::chain::Chain;
+use super::da::{reconstruct, verify_chunk, Chunk, DaHeader, ExtendedData};
+
+/*
+业务场景：轻客户端不下载区块，只看区块头。出块人要是扣住一部分数据不发，谁也没法验证这个块 ——
+    轻客户端怎么用很少的下载量确认"数据确实公布了"？
+
+本练习：
+    1. 挖一个有十几笔交易的块，把交易数据切成 K 块、纠删码扩展成 2K 块，data_root 对所有块建 Merkle 树
+    2. 随便丢掉一半：剩下 K 块照样还原出交易，Merkle 根和区块头对得上；只剩 K - 1 块就还原不了
+    3. 出块人扣住 K + 1 块 (让数据还原不出来的最少数量)，轻客户端随机抽样 (抽几块可以自己输入)，
+       再跑很多次统计"被发现的概率"，和理论值、和不做纠删码的情况对比
+    4. 很多轻客户端各抽几块，合起来就够还原整个块 —— 出块人下线也不怕
+    5. ❌ 出块人发一个被改过的块：Merkle 证明对不上
+*/
+
+const L1_DIFFICULTY: u32 = 8;
+const K: usize = 8;
+const TRIALS: u32 = 2_000;
+const CLIENTS: usize = 6;
+
+// 出块人：手里有全部 2K 块，但可以扣住一些，也可以篡改某一块
+struct Producer {
+    data: ExtendedData,
+    tree: MerkleTree<Chunk>,
+    withheld: HashSet<usize>,
+    tampered: Option<usize>,
+}
+
+impl Producer {
+    fn new(data: ExtendedData) -> Self {
+        let tree = data.tree();
+        Producer { data, tree, withheld: HashSet::new(), tampered: None }
+    }
+
+    fn serve(&self, index: usize) -> Option<(Chunk, MerkleProof)> {
+        if self.withheld.contains(&index) {
+            return None;
+        }
+        let mut chunk = self.data.chunks.get(index)?.clone();
+        if self.tampered == Some(index) {
+            chunk.symbols[0] = chunk.symbols[0] + M31::one();
+        }
+        Some((chunk, self.tree.prove(index)?))
+    }
+}
+
+// 不放回地抽 s 个下标 (Fisher-Yates 洗前 s 个)
+fn sample_indices(rng: &mut SimpleRng, n: usize, s: usize) -> Vec<usize> {
+    let mut all: Vec<usize> = (0..n).collect();
+    for i in 0..s.min(n) {
+        let j = i + rng.gen_range((n - i) as u64) as usize;
+        all.swap(i, j);
+    }
+    all.truncate(s);
+    all
+}
+
+// 轻客户端：抽到的每一块都要拿得到、证明要对；返回拿到的块
+fn light_client(producer: &Producer, header: &DaHeader, indices: &[usize]) -> Result<Vec<Chunk>, String> {
+    let mut got = Vec::new();
+    for &index in indices {
+        let (chunk, proof) = producer.serve(index).ok_or_else(|| format!("块 {} 要不到", index))?;
+        verify_chunk(header, &chunk, &proof)?;
+        got.push(chunk);
+    }
+    Ok(got)
+}
+
+// 被扣住 w 块 (共 n 块) 时，抽 s 块全部躲开的概率 = Π (n - w - i) / (n - i)；s 超过 n 就是全抽了
+fn miss_probability(n: usize, w: usize, s: usize) -> f64 {
+    (0..s.min(n)).map(|i| n.saturating_sub(w + i) as f64 / (n - i) as f64).product()
+}
+
+fn decode_txs(header: &DaHeader, chunks: &[Chunk]) -> Result<Vec<String>, String> {
+    let bytes = reconstruct(header, chunks)?;
+    let text = String::from_utf8(bytes).map_err(|_| String::from("还原出来的不是 UTF-8"))?;
+    Ok(text.lines().map(str::to_string).collect())
+}
+
+fn check_against_block(label: &str, header: &DaHeader, chunks: &[Chunk], block_root: &str) {
+    match decode_txs(header, chunks) {
+        Ok(txs) if merkle_root(&txs) == block_root => println!("  {}: ✅ 还原出 {} 笔交易，Merkle 根和区块头一致", label, txs.len()),
+        Ok(_) => println!("  {}: ❌ 还原出来的交易和区块头对不上 (不应该发生)", label),
+        Err(e) => println!("  {}: ❌ {}", label, e),
+    }
+}
+
+pub fn run() {
+    println!("--- S06 Ex18: 数据可用性抽样 (纠删码 + Merkle + 随机抽样) ---");
+    let mut rng = SimpleRng::from_time();
+
+    // ==========================================
+    // 1. 编码
+    // ==========================================
+    let txs: Vec<String> = (1..=14).map(|i| format!("tx{:02}: user{} -> user{} {}", i, i % 5, (i * 3) % 7, i * 11)).collect();
+    let mut chain = Chain::new(L1_DIFFICULTY, vec![String::from("genesis")]);
+    if let Err(e) = chain.mine_block(txs.clone()) {
+        println!("  ❌ 出块失败 (不应该发生): {}", e);
+        return;
+    }
+    let block_root = chain.tip().header.merkle_root.clone();
+    let data = txs.join("\n");
+    let extended = ExtendedData::encode(data.as_bytes(), K);
+    let header = extended.header.clone();
+    let per_chunk = extended.chunks[0].symbols.len();
+    println!("\n[1] 区块 #{}：{} 笔交易，{} 字节", chain.height(), txs.len(), data.len());
+    println!("  切成 {} 块原始数据 + {} 块校验数据，每块 {} 个 M31 符号 ({} 字节)", K, K, per_chunk, per_chunk * 3);
+    for chunk in &extended.chunks {
+        let preview: Vec<String> = chunk.symbols.iter().take(4).map(|s| format!("{:08}", s.value())).collect();
+        println!("    块 {:>2} {} {} ..", chunk.index, if chunk.index < K { "原始" } else { "校验" }, preview.join(" "));
+    }
+    println!("  data_root {}", header.data_root);
+
+    // ==========================================
+    // 2. 任意 K 块都能还原
+    // ==========================================
+    println!("\n[2] 随机丢掉一半");
+    let keep = sample_indices(&mut rng, 2 * K, K);
+    let mut sorted = keep.clone();
+    sorted.sort();
+    let survivors: Vec<Chunk> = keep.iter().map(|&i| extended.chunks[i].clone()).collect();
+    check_against_block(&format!("留下块 {:?}", sorted), &header, &survivors, &block_root);
+    let parity_only: Vec<Chunk> = extended.chunks[K..].to_vec();
+    check_against_block("只留校验块", &header, &parity_only, &block_root);
+    check_against_block(&format!("只剩 {} 块", K - 1), &header, &survivors[..K - 1], &block_root);
+
+    // ==========================================
+    // 3. 扣住数据 vs 随机抽样
+    // ==========================================
+    let mut producer = Producer::new(extended.clone());
+    producer.withheld = sample_indices(&mut rng, 2 * K, K + 1).into_iter().collect();
+    println!("\n[3] 出块人扣住 {} / {} 块 —— 剩下 {} 块，不够还原", K + 1, 2 * K, K - 1);
+    let s = read_line(&format!("  你的轻客户端抽几块 (1..={}，直接回车默认 4): ", 2 * K)).parse::<usize>().unwrap_or(4).clamp(1, 2 * K);
+    let indices = sample_indices(&mut rng, 2 * K, s);
+    match light_client(&producer, &header, &indices) {
+        Ok(_) => println!("  抽了 {:?}：全部拿到，证明都对 —— 这次运气不好，没发现 ⚠️", indices),
+        Err(e) => println!("  抽了 {:?}：{} -> 拒绝这个区块 ✅", indices, e),
+    }
+
+    println!("\n  每种抽样数跑 {} 次，统计没发现的比例：", TRIALS);
+    println!("  {:>4} {:>10} {:>10} {:>10}", "抽样", "实测漏检", "理论漏检", "不做纠删码的理论漏检");
+    for s in 1..K {
+        let missed = (0..TRIALS).filter(|_| light_client(&producer, &header, &sample_indices(&mut rng, 2 * K, s)).is_ok()).count();
+        // 不做纠删码：只有 K 块，扣住 1 块就还原不了
+        println!(
+            "  {:>6} {:>13.2}% {:>13.2}% {:>19.2}%",
+            s,
+            missed as f64 / TRIALS as f64 * 100.0,
+            miss_probability(2 * K, K + 1, s) * 100.0,
+            miss_probability(K, 1, s) * 100.0
+        );
+    }
+
+    // ==========================================
+    // 4. 大家一起抽，合起来还原
+    // ==========================================
+    println!("\n[4] 诚实的出块人，{} 个轻客户端各抽 4 块，然后出块人下线", CLIENTS);
+    let honest = Producer::new(extended.clone());
+    let mut pooled: Vec<Chunk> = Vec::new();
+    for client in 1..=CLIENTS {
+        let indices = sample_indices(&mut rng, 2 * K, 4);
+        match light_client(&honest, &header, &indices) {
+            Ok(chunks) => pooled.extend(chunks),
+            Err(e) => println!("  客户端 {}: ❌ {} (不应该发生)", client, e),
+        }
+    }
+    let distinct: HashSet<usize> = pooled.iter().map(|c| c.index).collect();
+    println!("  合起来拿到 {} 个不同的块 (需要 {} 个)", distinct.len(), K);
+    check_against_block("用轻客户端手里的块还原", &header, &pooled, &block_root);
+
+    // ==========================================
+    // 5. 篡改
+    // ==========================================
+    println!("\n[5] ❌ 出块人把块 3 的内容改了一个符号，证明还是原来的");
+    let mut cheater = Producer::new(extended);
+    cheater.tampered = Some(3);
+    match light_client(&cheater, &header, &[3]) {
+        Ok(_) => println!("  ✅ 通过了 (不应该发生)"),
+        Err(e) => println!("  {}", e),
+    }
+}
+
+/*
+关键点总结：
+    1. 纠删码把"扣一点"变成"扣一半"：
+        不做纠删码，扣住 K 块里的 1 块数据就不完整，抽 s 块发现的概率只有 s / K。
+        扩展成 2K 块之后，任意 K 块就能还原，出块人至少要扣 K + 1 块 —— 每抽一次就有一半以上的概率撞上。
+
+    2. 漏检概率指数下降：
+        抽 s 块全部躲开的概率 < (1/2)^s，抽 10 块就不到千分之一，而下载量和区块大小无关。
+
+    3. 抽样的人多了，数据就"存"在网络里：
+        每个轻客户端拿到的块都带 Merkle 证明，合起来只要有 K 个不同的块，就能还原整个区块。
+
+    4. 这里没解决的问题：
+        出块人可能把校验块算错 (编码本身不对)。以太坊的 danksharding 用 KZG 承诺保证每一块都在同一个多项式上，
+        Celestia 用欺诈证明 —— 这个练习只假设编码是对的，只检查"拿不拿得到"和"有没有被改"。
+*/
@@ -0,0 +1,198 @@
+// src/s06_chain/ex19_fee_market.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::fee_market::{next_base_fee, FeePool, FeeTx, BASE_FEE_MAX_CHANGE_DENOMINATOR, ELASTICITY};
+
+/*
+业务场景：一个热门 NFT 开卖，几个区块之内交易量翻几倍。手续费应该怎么变？出价低的交易会怎样？
+
+本练习：
+    1. 公式本身：上一个块满 / 空 / 正好目标 / 3/4 满时，base fee 怎么变
+    2. 模拟几十个块 (块数可以自己输入)：平静 -> 抢购 -> 冷却，每个块新来一批交易，
+       钱包按"当前 base fee 的若干倍"设 max_fee，有人出价偏低；出块人按小费排序装满区块
+    3. ASCII 图：base fee 的走势 + 每个块的满空程度
+    4. 统计：烧掉多少、出块人拿到多少小费、出价低的交易等了多久
+*/
+
+const GWEI: u64 = 1_000_000_000;
+const GAS_LIMIT: u64 = 3_000_000;
+const INITIAL_BASE_FEE: u64 = 20 * GWEI;
+const TX_GAS: [u64; 3] = [21_000, 50_000, 120_000];
+const DEFAULT_BLOCKS: u64 = 30;
+const CHART_ROWS: usize = 12;
+
+// 三个阶段：(名字, 每块新来的交易数范围, 钱包出价倍数范围 (百分比))
+#[derive(Clone, Copy)]
+struct Phase {
+    name: &'static str,
+    arrivals: (u64, u64),
+    bid_percent: (u64, u64),
+}
+
+const CALM: Phase = Phase { name: "平静", arrivals: (18, 28), bid_percent: (70, 250) };
+const RUSH: Phase = Phase { name: "抢购", arrivals: (60, 90), bid_percent: (100, 300) };
+const COOL: Phase = Phase { name: "冷却", arrivals: (4, 12), bid_percent: (70, 200) };
+
+fn phase_of(height: u64, blocks: u64) -> Phase {
+    match height * 10 / blocks {
+        0..=2 => CALM,
+        3..=5 => RUSH,
+        _ => COOL,
+    }
+}
+
+fn between(rng: &mut SimpleRng, (lo, hi): (u64, u64)) -> u64 {
+    lo + rng.gen_range(hi - lo + 1)
+}
+
+fn gwei(wei: u64) -> String {
+    format!("{:.2}", wei as f64 / GWEI as f64)
+}
+
+struct Record {
+    base_fee: u64,
+    gas_used: u64,
+    included: usize,
+    waiting: usize,
+    priced_out: usize,
+}
+
+// 竖着的柱状图：每个块一列，柱高 = base fee；下面一行是用量 (0-9 表示 0%-90%，F 表示满)
+fn chart(records: &[Record]) {
+    let max = records.iter().map(|r| r.base_fee).max().unwrap_or(1);
+    let min = records.iter().map(|r| r.base_fee).min().unwrap_or(0);
+    let span = (max - min).max(1);
+    // 最低的那个块也至少画一格
+    let level = |fee: u64| 1 + ((fee - min) as u128 * (CHART_ROWS as u128 - 1) / span as u128) as usize;
+    for row in (1..=CHART_ROWS).rev() {
+        let label = min + span * (row as u64 - 1) / (CHART_ROWS as u64 - 1);
+        let bars: String = records.iter().map(|r| if level(r.base_fee) >= row { "# " } else { "  " }).collect();
+        println!("  {:>7} | {}", gwei(label), bars);
+    }
+    println!("  {:>7} +-{}", "gwei", "--".repeat(records.len()));
+    let fullness: String = records
+        .iter()
+        .map(|r| {
+            let tenth = r.gas_used * 10 / GAS_LIMIT;
+            if tenth >= 10 { String::from("F ") } else { format!("{} ", tenth) }
+        })
+        .collect();
+    // 中文标签显示宽度是字数的两倍，{:>5} 才和上面的 {:>7} 对齐
+    println!("  {:>5}   {}", "用量", fullness);
+    let ticks: String = (1..=records.len()).map(|h| if h % 5 == 0 { format!("{:<10}", h) } else { String::new() }).collect();
+    println!("  {:>5}   {}{}", "高度", " ".repeat(8), ticks);
+}
+
+pub fn run() {
+    println!("--- S06 Ex19: EIP-1559 手续费市场 ---");
+
+    // ==========================================
+    // 1. 公式
+    // ==========================================
+    let target = GAS_LIMIT / ELASTICITY;
+    println!("\n[1] gas 上限 {}，目标 {}，每块最多变化 1/{}", GAS_LIMIT, target, BASE_FEE_MAX_CHANGE_DENOMINATOR);
+    for (label, used) in [("满块", GAS_LIMIT), ("3/4 满", GAS_LIMIT * 3 / 4), ("正好目标", target), ("1/4 满", GAS_LIMIT / 4), ("空块", 0)] {
+        let next = next_base_fee(INITIAL_BASE_FEE, used, GAS_LIMIT);
+        let change = (next as f64 / INITIAL_BASE_FEE as f64 - 1.0) * 100.0;
+        println!("  上一块{:<6} {} gwei -> {} gwei ({:+.2}%)", label, gwei(INITIAL_BASE_FEE), gwei(next), change);
+    }
+
+    // ==========================================
+    // 2. 模拟
+    // ==========================================
+    let blocks = read_line(&format!("\n[2] 模拟多少个块 (直接回车默认 {}): ", DEFAULT_BLOCKS)).parse::<u64>().unwrap_or(DEFAULT_BLOCKS).clamp(10, 60);
+    println!("  前 30% 平静，接着 30% 抢购，剩下的冷却；钱包按当前 base fee 的若干倍出价，小费 1-3 gwei");
+    let mut rng = SimpleRng::from_time();
+    let mut pool = FeePool::new();
+    let mut base_fee = INITIAL_BASE_FEE;
+    let mut next_id = 0;
+    let mut records = Vec::new();
+    let (mut burned, mut tips) = (0u128, 0u128);
+    let mut waits: Vec<u64> = Vec::new();
+    let mut longest: Option<(FeeTx, u64)> = None;
+
+    for height in 1..=blocks {
+        let phase = phase_of(height - 1, blocks);
+        for _ in 0..between(&mut rng, phase.arrivals) {
+            let bid = base_fee / 100 * between(&mut rng, phase.bid_percent);
+            let tx = FeeTx {
+                id: next_id,
+                gas: TX_GAS[rng.gen_range(TX_GAS.len() as u64) as usize],
+                max_fee: bid,
+                priority_fee: between(&mut rng, (1, 3)) * GWEI,
+                arrived: height,
+            };
+            pool.add(tx);
+            next_id += 1;
+        }
+
+        let (included, fees) = pool.build_block(base_fee, GAS_LIMIT);
+        burned += fees.burned as u128;
+        tips += fees.tips as u128;
+        for tx in included.iter() {
+            let wait = height - tx.arrived;
+            waits.push(wait);
+            if longest.as_ref().is_none_or(|(_, w)| wait > *w) {
+                longest = Some((tx.clone(), wait));
+            }
+        }
+        let record = Record { base_fee, gas_used: fees.gas_used, included: included.len(), waiting: pool.len(), priced_out: pool.priced_out(base_fee) };
+        println!(
+            "  #{:<3} {} base {:>7} gwei  用量 {:>3}%  打包 {:>3}  池里剩 {:>3} (出价不够 {:>3})",
+            height,
+            phase.name,
+            gwei(base_fee),
+            fees.gas_used * 100 / GAS_LIMIT,
+            record.included,
+            record.waiting,
+            record.priced_out
+        );
+        records.push(record);
+        base_fee = next_base_fee(base_fee, fees.gas_used, GAS_LIMIT);
+    }
+
+    // ==========================================
+    // 3. 图
+    // ==========================================
+    println!("\n[3] base fee 走势");
+    chart(&records);
+
+    // ==========================================
+    // 4. 统计
+    // ==========================================
+    println!("\n[4] 统计");
+    let total_included: usize = records.iter().map(|r| r.included).sum();
+    println!("  一共来了 {} 笔交易，打包 {} 笔，还在池里 {} 笔 (出价低于现在的 base fee {} gwei 的有 {} 笔)", next_id, total_included, pool.len(), gwei(base_fee), pool.priced_out(base_fee));
+    println!("  烧掉 {:.4} ETH，出块人拿到小费 {:.4} ETH", burned as f64 / 1e18, tips as f64 / 1e18);
+    if !waits.is_empty() {
+        let average = waits.iter().sum::<u64>() as f64 / waits.len() as f64;
+        let immediate = waits.iter().filter(|&&w| w == 0).count();
+        println!("  平均等 {:.2} 个块；{} 笔进池当块就被打包", average, immediate);
+    }
+    if let Some((tx, wait)) = longest {
+        let base_then = records[(tx.arrived - 1) as usize].base_fee;
+        let reason = if tx.max_fee < base_then { "进池时出价不够，等 base fee 降下来" } else { "出价够，但区块被小费更高的交易占满" };
+        println!("  等得最久的：第 {} 笔，#{} 进池 (当时 base fee {} gwei)，max_fee {} gwei，等了 {} 个块 —— {}", tx.id, tx.arrived, gwei(base_then), gwei(tx.max_fee), wait, reason);
+    }
+    if let Some(stuck) = pool.pending().iter().min_by_key(|tx| tx.max_fee) {
+        println!("  池里出价最低的：第 {} 笔，max_fee {} gwei，从 #{} 一直等到现在", stuck.id, gwei(stuck.max_fee), stuck.arrived);
+    }
+}
+
+/*
+关键点总结：
+    1. base fee 是一个反馈控制器：
+        块比目标满就涨，比目标空就跌，每块最多 12.5%。抢购开始后连续满块，base fee 几个块就翻倍，
+        把出价不够的交易挤出去，直到需求降到区块装得下为止。
+
+    2. 用户不用再猜价：
+        下一个块的 base fee 是确定的 (任何人都能按公式算)，钱包直接出 2 倍 base fee + 一点小费就行，
+        多出来的部分不会被收走 —— 实际只付 base fee + min(小费, max_fee - base fee)。
+
+    3. 出价低的交易不会丢，只会等：
+        max_fee < base fee 时进不了块，留在池里；冷却阶段 base fee 一路跌下来，它们一批批被打包。
+
+    4. base fee 烧掉而不是给矿工：
+        如果 base fee 归出块人，出块人就有动机塞满自己的假交易把价格抬上去；烧掉之后，出块人只关心小费。
+*/
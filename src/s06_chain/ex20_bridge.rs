@@ -0,0 +1,317 @@
+// src/s06_chain/ex20_bridge.rs
+use std::collections::BTreeMap;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::s05_zk_lab::ex01_merkle::{MerkleProof, MerkleTree};
+use crate::s05_zk_lab::hash::{short_hex, Digest};
+
+use super::block::{Block, BlockHeader};
+use super::bridge::{BridgeContract, Lock};
+use super::chain::Chain;
+
+/*
+业务场景：Alice 的币在链 A 上，她想在链 B 上用。两条链互相看不见对方的状态，只能靠"证明"。
+
+本练习 (三个线程，只通过 channel 通信)：
+    链 A 线程：定时出块，处理锁定请求 (币转进桥的金库)
+    链 B 线程：定时出块，桥合约里跑着 A 的轻节点，验证中继者交上来的证明，铸造包装币
+    中继线程：收 A 的每个新块，把区块头交给 B；看到锁定就等 CONFIRMATIONS 个确认，然后提交 Merkle 证明
+    1. Alice 和 Carol 各锁一笔，等中继者搬到 B 上
+    2. ❌ 攻击：锁超过余额、改证明里的金额、同一个证明重放、Mallory 自己挖一条链伪造锁定
+    3. 停掉所有线程：A 上金库的余额 = B 上包装币的总量
+*/
+
+const DIFFICULTY: u32 = 8;
+const CONFIRMATIONS: u64 = 3;
+const A_BLOCK_TIME: Duration = Duration::from_millis(30);
+const B_BLOCK_TIME: Duration = Duration::from_millis(45);
+const WAIT: Duration = Duration::from_secs(10);
+const VAULT: &str = "bridge-vault";
+
+// 谁 -> 链 A
+enum ToA {
+    Lock { owner: String, recipient: String, amount: u64, reply: Sender<Result<u64, String>> },
+    Prove { id: u64, reply: Sender<Option<(String, MerkleProof, Digest)>> },
+}
+
+// 谁 -> 链 B
+enum ToB {
+    Header(BlockHeader),
+    Claim { tx: String, proof: MerkleProof, block_hash: Digest, reply: Sender<Result<Lock, String>> },
+}
+
+// 两条链都用同一个出块节奏：到点就出块，中间处理请求
+fn until(deadline: Instant) -> Duration {
+    deadline.saturating_duration_since(Instant::now())
+}
+
+// 每个块都有一笔 coinbase，空块也有交易可以算 Merkle 根；返回打包了几笔 pending
+fn produce(chain: &mut Chain<String>, name: &str, pending: &mut Vec<String>) -> usize {
+    let mut txs = vec![format!("{}#{} coinbase", name, chain.height() + 1)];
+    let count = pending.len();
+    txs.append(pending);
+    match chain.mine_block(txs) {
+        Ok(_) => count,
+        Err(e) => {
+            println!("  [{}] ❌ 出块失败 (不应该发生): {}", name, e);
+            0
+        }
+    }
+}
+
+fn chain_a(mut chain: Chain<String>, inbox: Receiver<ToA>, relayer: Sender<Block<String>>) -> (Chain<String>, BTreeMap<String, u64>) {
+    let mut balances = BTreeMap::from([(String::from("Alice"), 100), (String::from("Carol"), 30)]);
+    let mut pending = Vec::new();
+    let mut next_id = 1;
+    let mut deadline = Instant::now() + A_BLOCK_TIME;
+    loop {
+        match inbox.recv_timeout(until(deadline)) {
+            Ok(ToA::Lock { owner, recipient, amount, reply }) => {
+                let balance = balances.get(&owner).copied().unwrap_or(0);
+                if amount == 0 || amount > balance {
+                    let _ = reply.send(Err(format!("{} 余额 {}，锁不了 {}", owner, balance, amount)));
+                    continue;
+                }
+                balances.insert(owner.clone(), balance - amount);
+                *balances.entry(VAULT.to_string()).or_insert(0) += amount;
+                pending.push(Lock { id: next_id, owner, recipient, amount }.to_tx());
+                let _ = reply.send(Ok(next_id));
+                next_id += 1;
+            }
+            Ok(ToA::Prove { id, reply }) => {
+                let found = chain.blocks.iter().find_map(|block| {
+                    let index = block.txs.iter().position(|tx| Lock::parse(tx).is_some_and(|lock| lock.id == id))?;
+                    let proof = MerkleTree::new_iterative(block.txs.clone()).prove(index)?;
+                    Some((block.txs[index].clone(), proof, block.hash()))
+                });
+                let _ = reply.send(found);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if Instant::now() < deadline {
+            continue;
+        }
+        deadline = Instant::now() + A_BLOCK_TIME;
+        let locks = produce(&mut chain, "A", &mut pending);
+        if locks > 0 {
+            println!("  [A] #{} 打包 {} 笔锁定", chain.height(), locks);
+        }
+        let _ = relayer.send(chain.tip().clone());
+    }
+    // 停机前已经扣了余额的锁定也要上链，金库才对得上
+    if !pending.is_empty() {
+        let locks = produce(&mut chain, "A", &mut pending);
+        println!("  [A] 停机前 #{} 打包最后 {} 笔锁定", chain.height(), locks);
+    }
+    (chain, balances)
+}
+
+fn chain_b(mut chain: Chain<String>, mut contract: BridgeContract, inbox: Receiver<ToB>) -> (Chain<String>, BridgeContract) {
+    let mut pending = Vec::new();
+    let mut deadline = Instant::now() + B_BLOCK_TIME;
+    loop {
+        match inbox.recv_timeout(until(deadline)) {
+            Ok(ToB::Header(header)) => {
+                let height = header.height;
+                if let Err(e) = contract.add_header(header) {
+                    println!("  [B] 桥合约拒绝区块头 #{}: {}", height, e);
+                }
+            }
+            Ok(ToB::Claim { tx, proof, block_hash, reply }) => {
+                let result = contract.claim(&tx, &proof, &block_hash);
+                if let Ok(lock) = &result {
+                    pending.push(format!("mint|{}|{}|{}", lock.id, lock.recipient, lock.amount));
+                }
+                let _ = reply.send(result);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if Instant::now() < deadline {
+            continue;
+        }
+        deadline = Instant::now() + B_BLOCK_TIME;
+        let mints = produce(&mut chain, "B", &mut pending);
+        if mints > 0 {
+            println!("  [B] #{} 打包 {} 笔铸造 (合约已同步到 A 的 #{})", chain.height(), mints, contract.source_height());
+        }
+    }
+    if !pending.is_empty() {
+        let mints = produce(&mut chain, "B", &mut pending);
+        println!("  [B] 停机前 #{} 打包最后 {} 笔铸造", chain.height(), mints);
+    }
+    (chain, contract)
+}
+
+// 中继者：A 的每个块都转给 B；锁定压够确认数就提交证明
+fn relayer(blocks: Receiver<Block<String>>, to_b: Sender<ToB>, minted: Sender<Lock>) {
+    let mut waiting: Vec<(Block<String>, usize)> = Vec::new();
+    for block in blocks {
+        let height = block.header.height;
+        for (index, tx) in block.txs.iter().enumerate() {
+            if let Some(lock) = Lock::parse(tx) {
+                println!("  [中继] 看到 A #{} 里的锁定 #{} ({} -> {} {})，等 {} 个确认", height, lock.id, lock.owner, lock.recipient, lock.amount, CONFIRMATIONS);
+                waiting.push((block.clone(), index));
+            }
+        }
+        if to_b.send(ToB::Header(block.header.clone())).is_err() {
+            return;
+        }
+        let (ready, rest): (Vec<_>, Vec<_>) = waiting.into_iter().partition(|(b, _)| height + 1 - b.header.height >= CONFIRMATIONS);
+        waiting = rest;
+        for (b, index) in ready {
+            let Some(proof) = MerkleTree::new_iterative(b.txs.clone()).prove(index) else {
+                continue;
+            };
+            let (reply, answer) = mpsc::channel();
+            let claim = ToB::Claim { tx: b.txs[index].clone(), proof, block_hash: b.hash(), reply };
+            if to_b.send(claim).is_err() {
+                return;
+            }
+            match answer.recv() {
+                Ok(Ok(lock)) => {
+                    println!("  [中继] ✅ A #{} 已有 {} 个确认，证明提交成功：B 上给 {} 铸造 {}", b.header.height, height + 1 - b.header.height, lock.recipient, lock.amount);
+                    let _ = minted.send(lock);
+                }
+                Ok(Err(e)) => println!("  [中继] ❌ {}", e),
+                Err(_) => return,
+            }
+        }
+    }
+}
+
+fn lock(to_a: &Sender<ToA>, owner: &str, recipient: &str, amount: u64) -> Result<u64, String> {
+    let (reply, answer) = mpsc::channel();
+    to_a.send(ToA::Lock { owner: owner.to_string(), recipient: recipient.to_string(), amount, reply }).map_err(|_| String::from("链 A 停了"))?;
+    answer.recv().map_err(|_| String::from("链 A 没有回答"))?
+}
+
+fn claim(to_b: &Sender<ToB>, tx: String, proof: MerkleProof, block_hash: Digest) -> Result<Lock, String> {
+    let (reply, answer) = mpsc::channel();
+    to_b.send(ToB::Claim { tx, proof, block_hash, reply }).map_err(|_| String::from("链 B 停了"))?;
+    answer.recv().map_err(|_| String::from("链 B 没有回答"))?
+}
+
+fn report(label: &str, result: Result<Lock, String>) {
+    match result {
+        Ok(lock) => println!("  {}: ✅ 铸造了 {} (不应该发生)", label, lock.amount),
+        Err(e) => println!("  {}: ❌ {}", label, e),
+    }
+}
+
+pub fn run() {
+    println!("--- S06 Ex20: 跨链桥 (链 A 锁定 -> Merkle 证明 -> 链 B 铸造) ---");
+    let a = Chain::new(DIFFICULTY, vec![String::from("chain A genesis")]);
+    let contract = BridgeContract::new(a.tip().header.clone(), DIFFICULTY, CONFIRMATIONS);
+    let b = Chain::new(DIFFICULTY, vec![String::from("chain B genesis")]);
+    println!("链 A 创世 {}..，链 B 创世 {}..，桥合约部署在 B 上，要求 {} 个确认", short_hex(&a.tip().hash()), short_hex(&b.tip().hash()), CONFIRMATIONS);
+
+    let (to_a, a_inbox) = mpsc::channel();
+    let (to_b, b_inbox) = mpsc::channel();
+    let (to_relayer, relayer_inbox) = mpsc::channel();
+    let (minted_tx, minted) = mpsc::channel();
+    let a_thread = thread::spawn(move || chain_a(a, a_inbox, to_relayer));
+    let b_thread = thread::spawn(move || chain_b(b, contract, b_inbox));
+    let relayer_to_b = to_b.clone();
+    let relayer_thread = thread::spawn(move || relayer(relayer_inbox, relayer_to_b, minted_tx));
+
+    // ==========================================
+    // 1. 正常跨链
+    // ==========================================
+    println!("\n[1] Alice 锁 40 给 B 上的 Bob，Carol 锁 15 给 B 上的自己");
+    let mut first = None;
+    for (owner, recipient, amount) in [("Alice", "Bob", 40), ("Carol", "Carol", 15)] {
+        match lock(&to_a, owner, recipient, amount) {
+            Ok(id) => {
+                println!("  链 A 接受锁定 #{}: {} -> {} {}", id, owner, recipient, amount);
+                first.get_or_insert(id);
+            }
+            Err(e) => println!("  ❌ {} (不应该发生)", e),
+        }
+    }
+    for _ in 0..2 {
+        if minted.recv_timeout(WAIT).is_err() {
+            println!("  ❌ {:?} 内没等到铸造 (不应该发生)", WAIT);
+            break;
+        }
+    }
+
+    // ==========================================
+    // 2. 攻击
+    // ==========================================
+    println!("\n[2] ❌ 攻击");
+    match lock(&to_a, "Alice", "Mallory", 1_000) {
+        Ok(id) => println!("  Alice 锁 1000: ✅ 锁定 #{} (不应该发生)", id),
+        Err(e) => println!("  Alice 锁 1000: ❌ 链 A 拒绝: {}", e),
+    }
+
+    let (reply, answer) = mpsc::channel();
+    let proven = first.and_then(|id| to_a.send(ToA::Prove { id, reply }).ok()).and_then(|()| answer.recv().ok().flatten());
+    if let Some((tx, proof, block_hash)) = proven {
+        let mut inflated = Lock::parse(&tx).expect("chain A only proves lock transactions");
+        inflated.amount *= 10;
+        report(&format!("把证明里的 {} 改成 {}", tx, inflated.to_tx()), claim(&to_b, inflated.to_tx(), proof.clone(), block_hash));
+        report("把锁定 #1 的真证明再交一次", claim(&to_b, tx, proof, block_hash));
+    } else {
+        println!("  ❌ 链 A 找不到锁定 #1 (不应该发生)");
+    }
+
+    // Mallory 自己挖一条链，里面有一笔"锁定"：PoW 和 Merkle 证明都是真的，但桥合约不认识这条链
+    let fake = Lock { id: 99, owner: String::from("Mallory"), recipient: String::from("Mallory"), amount: 1_000 };
+    let mut forged = Chain::new(DIFFICULTY, vec![String::from("Mallory genesis")]);
+    match forged.mine_block(vec![String::from("A#1 coinbase"), fake.to_tx()]) {
+        Ok(_) => {
+            let block = forged.tip().clone();
+            let proof = MerkleTree::new_iterative(block.txs.clone()).prove(1).expect("index 1 exists");
+            // 先把她的区块头塞给合约：父块不在 A 的链上，合约的轻节点直接拒绝
+            let _ = to_b.send(ToB::Header(block.header.clone()));
+            report("Mallory 用自己挖的链伪造锁定 #99", claim(&to_b, fake.to_tx(), proof, block.hash()));
+        }
+        Err(e) => println!("  ❌ Mallory 出块失败 (不应该发生): {}", e),
+    }
+
+    // ==========================================
+    // 3. 停机对账
+    // ==========================================
+    println!("\n[3] 停掉所有线程，对账");
+    // 先停 A：中继者收不到新块就退出，B 的所有发送端都关了之后 B 也退出
+    drop(to_a);
+    drop(to_b);
+    let joined = (a_thread.join(), relayer_thread.join(), b_thread.join());
+    let (Ok((a, balances)), Ok(()), Ok((b, contract))) = joined else {
+        println!("  ❌ 有线程 panic 了 (不应该发生)");
+        return;
+    };
+    println!("  链 A 高度 {}，链 B 高度 {}，桥合约同步到 A 的 #{}", a.height(), b.height(), contract.source_height());
+    let line: Vec<String> = balances.iter().map(|(name, amount)| format!("{} {}", name, amount)).collect();
+    println!("  链 A 余额: {}", line.join("  "));
+    println!("  链 B 包装币: Bob {}  Carol {}  Mallory {}", contract.balance("Bob"), contract.balance("Carol"), contract.balance("Mallory"));
+    let vault = balances.get(VAULT).copied().unwrap_or(0);
+    let supply = contract.total_supply();
+    if vault == supply {
+        println!("  ✅ A 上金库 {} = B 上包装币总量 {}", vault, supply);
+    } else {
+        println!("  ⏳ A 上金库 {}，B 上包装币总量 {}：有锁定还没等够确认就停机了", vault, supply);
+    }
+}
+
+/*
+关键点总结：
+    1. 锁定 + 铸造，而不是"转移"：
+        币一直在链 A 的金库里，B 上的包装币只是对它的债权。要回去就反过来：B 上销毁，A 上凭证明解锁。
+        桥的不变量：金库余额 = 包装币总量；一旦有人能不锁就铸造，包装币就变成了空头支票。
+
+    2. 合约里跑的是 A 的轻节点：
+        B 不信任中继者，只信任自己验证过的区块头 (PoW + 链接) 和 Merkle 证明。
+        改金额 -> Merkle 根对不上；自己挖的链 -> 父块不在合约认识的链上；同一个证明交两次 -> 重放检查拦下。
+
+    3. 确认数是安全参数：
+        A 上发生重组，锁定可能被撤销，但 B 上已经铸造的币收不回来 —— 所以要等足够多的确认。
+        这个参数只和 A 的算力一样可靠：这里的难度只有 8 位，任何人都能挖出 3 个块，真实的桥要求高得多。
+
+    4. 中继者只是搬运工：
+        谁都可以当中继者，它最多"不搬" (延迟)，搬不了假的东西。
+*/
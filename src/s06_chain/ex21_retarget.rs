@@ -0,0 +1,174 @@
+// src/s06_chain/ex21_retarget.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::retarget::{Algorithm, Retargeter};
+
+/*
+业务场景：新一代矿机上线，全网算力几周内翻了一倍多；接着某地矿场断电，一夜之间走掉大半。
+    出块间隔会变成什么样？难度调整算法要多久才能把它拉回 10 分钟？
+
+本练习：
+    1. 模拟 100 天的出块：每个块的用时服从指数分布，均值 = 难度 / 当时的算力
+       算力按阶段变化：平稳 -> 新矿机涌入 -> 矿场断电 -> 慢慢恢复
+    2. 同一份算力曲线、同一串随机数，分别用三种算法跑：不调整 / 比特币 2016 块窗口 / 每块 EMA
+       (EMA 的平滑块数可以自己输入)
+    3. 按周列出平均出块间隔，再统计每种算法的均值、标准差、最长间隔、超过 1 小时的块数
+*/
+
+const TARGET: f64 = 600.0;
+const DAY: f64 = 86_400.0;
+const DAYS: f64 = 100.0;
+const BITCOIN_WINDOW: u64 = 2016;
+const DEFAULT_SMOOTHING: u64 = 100;
+
+// (阶段名, 从第几天开始, 全网算力)
+const SCHEDULE: [(&str, f64, f64); 4] = [("平稳", 0.0, 100.0), ("新矿机涌入", 20.0, 250.0), ("矿场断电", 45.0, 60.0), ("慢慢恢复", 70.0, 120.0)];
+
+fn hashrate_at(time: f64) -> (&'static str, f64) {
+    let day = time / DAY;
+    let (name, _, rate) = SCHEDULE.iter().rev().find(|(_, start, _)| day >= *start).copied().unwrap_or(SCHEDULE[0]);
+    (name, rate)
+}
+
+// (0, 1] 上的均匀分布，再变成均值为 mean 的指数分布
+fn exponential(rng: &mut SimpleRng, mean: f64) -> f64 {
+    let uniform = ((rng.next_u64() >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+    -uniform.ln() * mean
+}
+
+// 一次模拟：每个块 (出块时刻, 用时, 出块时的难度)
+struct Run {
+    name: String,
+    blocks: Vec<(f64, f64, f64)>,
+}
+
+fn simulate(algorithm: Algorithm, seed: u64) -> Run {
+    let mut rng = SimpleRng::new(seed);
+    // 一开始难度正好配得上第一阶段的算力
+    let mut retargeter = Retargeter::new(algorithm, TARGET, SCHEDULE[0].2 * TARGET);
+    let mut time = 0.0;
+    let mut blocks = Vec::new();
+    while time < DAYS * DAY {
+        // 简化：一个块挖到一半算力变了不管，按开始挖时的算力算
+        let difficulty = retargeter.difficulty();
+        let interval = exponential(&mut rng, difficulty / hashrate_at(time).1);
+        time += interval;
+        blocks.push((time, interval, difficulty));
+        retargeter.on_block(interval);
+    }
+    Run { name: algorithm.name(), blocks }
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+// 按终端显示宽度补空格：中文占两格，format! 的宽度只数字符个数
+fn pad(text: &str, width: usize) -> String {
+    let shown: usize = text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum();
+    format!("{}{}", text, " ".repeat(width.saturating_sub(shown)))
+}
+
+fn minutes(seconds: Option<f64>) -> String {
+    seconds.map_or(String::from("-"), |s| format!("{:.1}", s / 60.0))
+}
+
+pub fn run() {
+    println!("--- S06 Ex21: 难度调整 (算力变化时，比特币窗口 vs 每块 EMA) ---");
+
+    // ==========================================
+    // 1. 算力曲线
+    // ==========================================
+    println!("\n[1] 目标出块间隔 {} 分钟，模拟 {} 天 (期望 {} 个块)", TARGET / 60.0, DAYS, (DAYS * DAY / TARGET) as u64);
+    for (name, start, rate) in SCHEDULE {
+        println!("  第 {:>3} 天起  算力 {:>3}  {}", start, rate, name);
+    }
+
+    // ==========================================
+    // 2. 三种算法各跑一遍
+    // ==========================================
+    let smoothing = read_line(&format!("\n[2] EMA 平滑多少个块 (直接回车默认 {}): ", DEFAULT_SMOOTHING)).parse::<u64>().unwrap_or(DEFAULT_SMOOTHING).clamp(2, BITCOIN_WINDOW);
+    // 三次模拟用同一个 seed：第 i 个块的"运气"一样，差别只来自算法
+    let seed = SimpleRng::from_time().next_u64();
+    let runs: Vec<Run> = [Algorithm::Fixed, Algorithm::Window { window: BITCOIN_WINDOW }, Algorithm::Ema { smoothing }].into_iter().map(|a| simulate(a, seed)).collect();
+    for run in &runs {
+        let adjustments = run.blocks.windows(2).filter(|pair| pair[0].2 != pair[1].2).count();
+        println!("  {} 出了 {:>5} 个块，难度变了 {:>5} 次", pad(&run.name, 10), run.blocks.len(), adjustments);
+    }
+
+    // ==========================================
+    // 3. 按周看平均出块间隔 (分钟)
+    // ==========================================
+    println!("\n[3] 每周平均出块间隔 (分钟，目标 {})", TARGET / 60.0);
+    let header: String = runs.iter().map(|run| pad(&run.name, 12)).collect();
+    println!("  {}{}{}", pad("周", 5), pad("算力", 11), header.trim_end());
+    let weeks = (DAYS / 7.0).ceil() as usize;
+    for week in 0..weeks {
+        let (start, end) = (week as f64 * 7.0 * DAY, (week + 1) as f64 * 7.0 * DAY);
+        // 这一周中间换了阶段就写成 "旧 -> 新"
+        let (first, last) = (hashrate_at(start), hashrate_at(end.min(DAYS * DAY) - 1.0));
+        let rate = if first == last { format!("{}", first.1) } else { format!("{} -> {}", first.1, last.1) };
+        let columns: String = runs
+            .iter()
+            .map(|run| {
+                let intervals: Vec<f64> = run.blocks.iter().filter(|(t, _, _)| *t >= start && *t < end).map(|(_, i, _)| *i).collect();
+                format!("{:<12}", minutes(mean(&intervals)))
+            })
+            .collect();
+        println!("  {:<5}{:<11}{}{}", week + 1, rate, columns, last.0);
+    }
+
+    // ==========================================
+    // 4. 统计
+    // ==========================================
+    println!("\n[4] 统计 (分钟)");
+    for run in &runs {
+        let intervals: Vec<f64> = run.blocks.iter().map(|(_, i, _)| *i).collect();
+        let average = mean(&intervals).unwrap_or(0.0);
+        let deviation = (intervals.iter().map(|i| (i - average).powi(2)).sum::<f64>() / intervals.len() as f64).sqrt();
+        let longest = intervals.iter().copied().fold(0.0, f64::max);
+        let slow = intervals.iter().filter(|&&i| i > 3_600.0).count();
+        // 每个阶段里平均间隔离目标最远的那个
+        let worst = SCHEDULE
+            .iter()
+            .map(|(name, _, _)| {
+                let phase: Vec<f64> = run.blocks.iter().filter(|(t, i, _)| hashrate_at(t - i).0 == *name).map(|(_, i, _)| *i).collect();
+                (*name, mean(&phase))
+            })
+            .filter_map(|(name, m)| m.map(|m| (name, m)))
+            .max_by(|a, b| (a.1 - TARGET).abs().total_cmp(&(b.1 - TARGET).abs()));
+        println!(
+            "  {} 平均 {:>5}  标准差 {:>5}  最长 {:>6}  超过 1 小时 {:>4} 块  最差阶段: {}",
+            pad(&run.name, 10),
+            minutes(Some(average)),
+            minutes(Some(deviation)),
+            minutes(Some(longest)),
+            slow,
+            worst.map_or(String::from("-"), |(name, m)| format!("{} ({} 分钟)", name, minutes(Some(m))))
+        );
+    }
+}
+
+/*
+关键点总结：
+    1. 难度调整是一个只看得到"过去"的反馈控制：
+        协议只知道出块时间，算力是估出来的。估得越快 (窗口越短 / smoothing 越小)，跟得越紧，
+        但单个块的用时本身就是指数分布 (标准差 = 均值)，估得太快会被运气带着来回晃。
+
+    2. 比特币窗口的问题是滞后：
+        算力翻倍后，剩下的窗口按 5 分钟一个块跑完才调；算力走掉大半时更糟 ——
+        2016 个块每个都要等很久，窗口迟迟跑不完，出块一直慢。2017 年 BCH 分叉后就碰到过，
+        后来改成了每块调整 (先是 cw-144，后来 ASERT)。
+
+    3. 每块 EMA 几十个块就跟上：
+        算力变化后的几个小时内间隔就回到 10 分钟附近，代价是难度本身在小幅抖动。
+
+    4. 这里没模拟的：
+        时间戳是矿工自己填的，每块调整的算法更容易被"谎报时间戳"操纵 (比特币的窗口 + 4 倍上限更保守)；
+        真实网络还有矿工在几条链之间跳来跳去 (算力随收益切换)，会放大振荡。
+*/
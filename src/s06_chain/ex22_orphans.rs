@@ -0,0 +1,212 @@
+// src/s06_chain/ex22_orphans.rs
+use std::collections::HashMap;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::Digest;
+
+use super::block::{merkle_root, now_secs, Block, BlockHeader};
+use super::chain::Chain;
+use super::fork::{BlockTree, ForkChoice};
+use super::orphan::{Arrival, OrphanPool};
+use super::pow;
+
+/*
+业务场景：新节点从好几个对等节点同时下载区块，网络延迟让区块完全乱序地到达 ——
+    #7 比 #3 先到，分叉上的块夹在中间。节点不能把先到的子块扔掉 (还得重新下载)，也不能无限制地存。
+
+本练习：
+    1. 一条 8 个块的主链 + 从 M3 分出去的 2 个块，打乱顺序交给新节点：
+       父块没到的进孤块池，父块一到，等它的整棵子树一起接上；最后和按顺序收到的结果一样
+    2. ❌ 坏块：孤块池里的块接上时才发现 Merkle 根不对，它的子孙一起作废；没挖过的假孤块进不了池
+    3. 孤块池只有 3 个位置：被挤掉的孤块要重新下载
+*/
+
+const DIFFICULTY: u32 = 8;
+const POOL_CAPACITY: usize = 16;
+
+// 在 parent 后面挖一个块，交易只有一笔写着矿工名字的 coinbase
+fn extend(parent: &Block, miner: &str) -> Block {
+    let txs = vec![format!("coinbase -> {}: 50 (高度 {})", miner, parent.header.height + 1)];
+    let header = BlockHeader {
+        height: parent.header.height + 1,
+        prev_hash: parent.hash(),
+        merkle_root: merkle_root(&txs),
+        state_root: [0u8; 32],
+        timestamp: now_secs(),
+        difficulty: DIFFICULTY,
+        nonce: 0,
+    };
+    Block { header: pow::mine(header).header, txs }
+}
+
+struct Labels(HashMap<Digest, String>);
+
+impl Labels {
+    fn add(&mut self, label: &str, block: &Block) {
+        self.0.insert(block.hash(), label.to_string());
+    }
+
+    fn get(&self, hash: &Digest) -> String {
+        self.0.get(hash).cloned().unwrap_or_else(|| String::from("?"))
+    }
+
+    fn list(&self, hashes: impl IntoIterator<Item = Digest>) -> String {
+        hashes.into_iter().map(|h| self.get(&h)).collect::<Vec<_>>().join(", ")
+    }
+}
+
+fn deliver(tree: &mut BlockTree, pool: &mut OrphanPool, labels: &Labels, label: &str, block: Block) {
+    let outcome = match pool.receive(tree, block) {
+        Arrival::Connected { connected, rejected, discarded } => {
+            let mut text = format!("接上 {}", labels.list(connected.into_iter().map(|(_, h)| h)));
+            for (hash, error) in rejected {
+                text += &format!("；❌ 孤块 {} 不合法: {}", labels.get(&hash), error);
+            }
+            if !discarded.is_empty() {
+                text += &format!("；它的子孙 {} 一起作废", labels.list(discarded));
+            }
+            text
+        }
+        Arrival::Orphaned { missing, evicted } => {
+            let mut text = format!("孤块，缺父块 {}", labels.get(&missing));
+            if let Some(evicted) = evicted {
+                text += &format!("；池子满了，挤掉最早的 {}", labels.get(&evicted));
+            }
+            text
+        }
+        Arrival::Rejected { error, discarded } => {
+            let mut text = format!("❌ 拒绝: {}", error);
+            if !discarded.is_empty() {
+                text += &format!("；在等它的 {} 一起作废", labels.list(discarded));
+            }
+            text
+        }
+        Arrival::Duplicate => String::from("重复，忽略"),
+    };
+    println!("  收到 {:<3} -> {}", label, outcome);
+    if !pool.is_empty() {
+        println!("  {:<9}池里 {} 个，要向对等节点要: [{}]", "", pool.len(), labels.list(pool.wanted()));
+    }
+}
+
+pub fn run() {
+    println!("--- S06 Ex22: 孤块池 (区块乱序到达，父块一到整棵子树接上) ---");
+    let chain = Chain::new(DIFFICULTY, vec![String::from("coinbase -> Satoshi: 50")]);
+    let genesis = chain.blocks[0].clone();
+    let mut labels = Labels(HashMap::new());
+    labels.add("创世", &genesis);
+
+    // ==========================================
+    // 1. 乱序到达
+    // ==========================================
+    let mut main = vec![genesis.clone()];
+    for i in 1..=8 {
+        let block = extend(&main[i - 1], "Miner");
+        labels.add(&format!("M{}", i), &block);
+        main.push(block);
+    }
+    let f4 = extend(&main[3], "Rival");
+    let f5 = extend(&f4, "Rival");
+    labels.add("F4", &f4);
+    labels.add("F5", &f5);
+    let mut arrivals: Vec<(String, Block)> = main[1..].iter().enumerate().map(|(i, b)| (format!("M{}", i + 1), b.clone())).collect();
+    arrivals.push((String::from("F4"), f4));
+    arrivals.push((String::from("F5"), f5));
+
+    // 按顺序收一遍，当作标准答案
+    let mut expected = BlockTree::new(genesis.clone(), DIFFICULTY);
+    for (_, block) in &arrivals {
+        if let Err(e) = expected.insert(block.clone()) {
+            println!("  ❌ {} (不应该发生)", e);
+        }
+    }
+
+    let mut rng = SimpleRng::from_time();
+    for i in (1..arrivals.len()).rev() {
+        arrivals.swap(i, rng.gen_range(i as u64 + 1) as usize);
+    }
+    let order: Vec<&str> = arrivals.iter().map(|(label, _)| label.as_str()).collect();
+    println!("\n[1] 主链 M1..M8，F4、F5 从 M3 分叉；到达顺序: {}", order.join(" "));
+    let mut tree = BlockTree::new(genesis.clone(), DIFFICULTY);
+    let mut pool = OrphanPool::new(POOL_CAPACITY);
+    for (label, block) in arrivals.iter().cloned() {
+        deliver(&mut tree, &mut pool, &labels, &label, block);
+    }
+    // 同一个块再来一次
+    let (label, block) = arrivals[0].clone();
+    deliver(&mut tree, &mut pool, &labels, &label, block);
+
+    let head = tree.head(ForkChoice::MostWork).hash();
+    let mut tips: Vec<String> = tree.tips().iter().map(|b| labels.get(&b.hash())).collect();
+    tips.sort();
+    println!("  主链头 {}，tips [{}]，孤块池剩 {} 个", labels.get(&head), tips.join(", "), pool.len());
+    if head == expected.head(ForkChoice::MostWork).hash() && tree.tips().len() == expected.tips().len() {
+        println!("  ✅ 和按顺序收到的结果一样");
+    } else {
+        println!("  ❌ 和按顺序收到的结果不一样 (不应该发生)");
+    }
+
+    // ==========================================
+    // 2. 坏块
+    // ==========================================
+    println!("\n[2] ❌ 坏块和假孤块");
+    let p = extend(&main[8], "Miner");
+    let mut z = extend(&p, "Mallory");
+    // 挖完之后偷偷改交易：区块头 (和哈希) 没变，Merkle 根对不上了
+    z.txs[0] = String::from("coinbase -> Mallory: 5000");
+    let z2 = extend(&z, "Mallory");
+    for (label, block) in [("P", &p), ("Z", &z), ("Z2", &z2)] {
+        labels.add(label, block);
+    }
+    println!("  Z 接在 P 后面，交易被改过；Z2 接在 Z 后面。到达顺序: Z2 Z P");
+    deliver(&mut tree, &mut pool, &labels, "Z2", z2);
+    deliver(&mut tree, &mut pool, &labels, "Z", z);
+    deliver(&mut tree, &mut pool, &labels, "P", p.clone());
+
+    println!("  S 是没挖过的假孤块：随便写一个父块，nonce 也不找");
+    let mut fake = extend(&p, "Spammer");
+    fake.header.prev_hash = [0xAB; 32];
+    while pow::meets_target(&fake.header) {
+        fake.header.nonce += 1;
+    }
+    labels.add("S", &fake);
+    deliver(&mut tree, &mut pool, &labels, "S", fake);
+
+    // ==========================================
+    // 3. 池子满了
+    // ==========================================
+    println!("\n[3] 孤块池只有 3 个位置，Q2..Q6 先到，Q1 最后到");
+    let mut small = OrphanPool::new(3);
+    let mut q = vec![p];
+    for i in 1..=6 {
+        let block = extend(&q[i - 1], "Miner");
+        labels.add(&format!("Q{}", i), &block);
+        q.push(block);
+    }
+    for i in [2, 3, 4, 5, 6, 1] {
+        deliver(&mut tree, &mut small, &labels, &format!("Q{}", i), q[i].clone());
+    }
+    println!("  被挤掉的 Q2、Q3 要重新下载:");
+    for i in [2, 3] {
+        deliver(&mut tree, &mut small, &labels, &format!("Q{}", i), q[i].clone());
+    }
+    println!("  主链头 {} (高度 {})", labels.get(&tree.head(ForkChoice::MostWork).hash()), tree.head(ForkChoice::MostWork).header.height);
+}
+
+/*
+关键点总结：
+    1. 按"缺的父块"建索引：
+        HashMap<父块哈希, Vec<孤块>>，父块一到就 remove 整组 —— 不用扫描整个池子，
+        而且 remove 直接把 Vec 的所有权交出来，块从池子移进树里，一次复制都没有。
+
+    2. 一层层往下接：
+        接上一个孤块之后，它自己可能就是别人的父块，所以用一个队列继续找；
+        分叉 (同一个父块有两个子块) 自然也一起接上，最后的树和按顺序收到的完全一样。
+
+    3. 孤块也要先过一道便宜的检查：
+        不知道父块，交易和链接都验不了，但工作量能验 —— 没挖过的块不让进池，造假孤块的成本就和挖矿一样。
+
+    4. 容量上限和作废：
+        池子满了扔最早的 (比特币是随机扔)，被扔的以后再下载；父块不合法，等它的整棵子树都作废，
+        否则它们会永远占着位置。
+*/
@@ -0,0 +1,212 @@
+// src/s06_chain/ex23_rbf.rs
+use std::collections::HashMap;
+
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::short_hex;
+
+use super::mempool::MIN_FEE_BUMP;
+use super::node::Node;
+use super::utxo::{OutPoint, Transaction, TxId, TxIn, TxOut};
+use super::wallet::Wallet;
+
+/*
+业务场景：Alice 给 Bob 转账，手续费给少了，交易在池子里一直没被打包。
+    她能不能"加价"让它快点上链？能不能干脆撤回？池子凭什么接受一笔和已有交易冲突的新交易？
+
+本练习：
+    1. 手续费替换 (RBF) 的几种结果：
+       同样的手续费换个收款人 -> 拒绝；多付 MIN_FEE_BUMP -> 替换；
+       一笔交易同时和池子里两笔冲突 -> 手续费要超过两笔之和才能把它们一起踢掉
+    2. 交互：Alice 的一笔转账卡在池子里，你来决定加价、撤回 (转回给自己) 还是出块，
+       出块之后再替换就是双花，池子直接拒绝
+*/
+
+const DIFFICULTY: u32 = 8;
+
+struct Demo {
+    node: Node,
+    labels: HashMap<TxId, String>,
+    rng: SimpleRng,
+}
+
+impl Demo {
+    // 花掉 from 的 coins：amount 给 to，找零给 from，剩下的是手续费；签好名
+    fn transfer(&mut self, from: &Wallet, coins: &[OutPoint], to: &Wallet, amount: u64, fee: u64) -> Result<Transaction, String> {
+        let owned: HashMap<OutPoint, TxOut> = self.node.utxos().outputs_of(&from.address()).into_iter().collect();
+        let mut gathered = 0;
+        for coin in coins {
+            gathered += owned.get(coin).ok_or_else(|| format!("{} 已经不在 UTXO 集合里 (上链的交易把它花掉了)", coin))?.amount;
+        }
+        if gathered < amount + fee {
+            return Err(format!("{} 只有 {}，不够付 {} + 手续费 {}", from.name, gathered, amount, fee));
+        }
+        let mut outputs = vec![TxOut { owner: to.address(), amount }];
+        if gathered > amount + fee {
+            outputs.push(TxOut { owner: from.address(), amount: gathered - amount - fee });
+        }
+        let mut tx = Transaction { inputs: coins.iter().map(|&coin| TxIn::unsigned(coin)).collect(), outputs, lock_time: 0 };
+        from.sign(&mut tx, self.node.utxos(), &mut self.rng);
+        Ok(tx)
+    }
+
+    fn submit(&mut self, label: &str, tx: Result<Transaction, String>) {
+        let tx = match tx {
+            Ok(tx) => tx,
+            Err(e) => {
+                println!("  {}: ❌ {}", label, e);
+                return;
+            }
+        };
+        let txid = tx.txid();
+        match self.node.submit(tx) {
+            Ok(accepted) => {
+                self.labels.insert(txid, label.to_string());
+                if accepted.replaced.is_empty() {
+                    println!("  {}: ✅ 进入交易池，手续费 {}", label, accepted.fee);
+                } else {
+                    let replaced: Vec<String> = accepted.replaced.iter().map(|id| self.label(id)).collect();
+                    println!("  {}: ✅ 手续费 {}，替换掉 {}", label, accepted.fee, replaced.join("、"));
+                }
+            }
+            Err(e) => println!("  {}: ❌ {}", label, e),
+        }
+    }
+
+    fn label(&self, txid: &TxId) -> String {
+        self.labels.get(txid).cloned().unwrap_or_else(|| format!("{}..", short_hex(txid)))
+    }
+
+    fn show_pool(&self) {
+        let pool = self.node.mempool().txs();
+        let utxos = self.node.utxos();
+        let items: Vec<String> = pool.iter().map(|tx| format!("{} (手续费 {})", self.label(&tx.txid()), utxos.validate_tx(tx).unwrap_or(0))).collect();
+        println!("    交易池 [{}]", items.join(", "));
+    }
+
+    fn mine(&mut self, miner: &Wallet, wallets: &[&Wallet]) {
+        let included: Vec<String> = self.node.mempool().txs().iter().map(|tx| self.label(&tx.txid())).collect();
+        match self.node.mine(&miner.address()) {
+            Ok(block) => println!("  Miner 挖出 #{}，打包 [{}]", block.header.height, included.join(", ")),
+            Err(e) => println!("  ❌ 出块失败 (不应该发生): {}", e),
+        }
+        let line: Vec<String> = wallets.iter().map(|w| format!("{} {}", w.name, self.node.utxos().balance(&w.address()))).collect();
+        println!("    余额: {}", line.join("  "));
+    }
+}
+
+fn read_fee(prompt: &str) -> Option<u64> {
+    read_line(prompt).parse::<u64>().ok()
+}
+
+pub fn run() {
+    println!("--- S06 Ex23: 手续费替换 (RBF：加价替换池子里卡住的交易) ---");
+    let mut rng = SimpleRng::from_time();
+    let alice = Wallet::generate("Alice", &mut rng);
+    let bob = Wallet::generate("Bob", &mut rng);
+    let carol = Wallet::generate("Carol", &mut rng);
+    let miner = Wallet::generate("Miner", &mut rng);
+    let wallets = [&alice, &bob, &carol, &miner];
+
+    // 创世块给 Alice 两枚币：30 和 20
+    let mut genesis = Transaction::coinbase(&alice.address(), 30, 0);
+    genesis.outputs.push(TxOut { owner: alice.address(), amount: 20 });
+    let node = match Node::new(DIFFICULTY, vec![genesis]) {
+        Ok(node) => node,
+        Err(e) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    let mut demo = Demo { node, labels: HashMap::new(), rng };
+    let coins = demo.node.utxos().outputs_of(&alice.address());
+    let coin_of = |amount: u64| coins.iter().find(|(_, out)| out.amount == amount).map(|(op, _)| *op);
+    let (Some(coin30), Some(coin20)) = (coin_of(30), coin_of(20)) else {
+        println!("  ❌ 创世块里没有 Alice 的两枚币 (不应该发生)");
+        return;
+    };
+
+    // ==========================================
+    // 1. 替换规则
+    // ==========================================
+    println!("\n[1] Alice 有两枚币 (30、20)；替换规则: 新手续费 >= 被替换的手续费之和 + {}", MIN_FEE_BUMP);
+    let tx = demo.transfer(&alice, &[coin30], &bob, 10, 1);
+    demo.submit("tx1 (30 -> Bob 10)", tx);
+    let tx = demo.transfer(&alice, &[coin30], &carol, 10, 1);
+    demo.submit("tx1' (同一枚币改给 Carol，手续费一样)", tx);
+    demo.show_pool();
+    let tx = demo.transfer(&alice, &[coin30], &bob, 10, 1 + MIN_FEE_BUMP);
+    demo.submit("tx2 (给 Bob，加价)", tx);
+    let tx = demo.transfer(&alice, &[coin20], &carol, 15, 1);
+    demo.submit("tx3 (20 -> Carol 15)", tx);
+    demo.show_pool();
+
+    println!("  Alice 想把两枚币一起转回给自己，撤回 tx2 和 tx3:");
+    let both = [coin30, coin20];
+    let tx = demo.transfer(&alice, &both, &alice, 50 - 3, 3);
+    demo.submit("tx4 (合并)", tx);
+    let tx = demo.transfer(&alice, &both, &alice, 50 - 4, 4);
+    demo.submit("tx5 (合并)", tx);
+    demo.show_pool();
+    demo.mine(&miner, &wallets);
+
+    // ==========================================
+    // 2. 交互
+    // ==========================================
+    let Some((coin, _)) = demo.node.utxos().outputs_of(&alice.address()).into_iter().next() else {
+        println!("  ❌ Alice 没有币了 (不应该发生)");
+        return;
+    };
+    println!("\n[2] Alice 用剩下的 {} 给 Bob 转 20，手续费只给了 1", demo.node.utxos().balance(&alice.address()));
+    let tx = demo.transfer(&alice, &[coin], &bob, 20, 1);
+    demo.submit("原交易", tx);
+    let mut attempt = 0;
+    loop {
+        demo.show_pool();
+        println!("  1. 加价重发给 Bob");
+        println!("  2. 撤回 (整枚币转回给 Alice 自己)");
+        println!("  3. Miner 出块");
+        println!("  0. 返回");
+        match read_line("  选择: ").as_str() {
+            "1" => {
+                let Some(fee) = read_fee("  新的手续费: ") else {
+                    println!("❌ 手续费要是非负整数");
+                    continue;
+                };
+                attempt += 1;
+                let tx = demo.transfer(&alice, &[coin], &bob, 20, fee);
+                demo.submit(&format!("加价 #{}", attempt), tx);
+            }
+            "2" => {
+                let Some(fee) = read_fee("  手续费: ") else {
+                    println!("❌ 手续费要是非负整数");
+                    continue;
+                };
+                attempt += 1;
+                let total = demo.node.utxos().outputs_of(&alice.address()).into_iter().find(|(op, _)| *op == coin).map_or(0, |(_, out)| out.amount);
+                let tx = demo.transfer(&alice, &[coin], &alice, total.saturating_sub(fee), fee);
+                demo.submit(&format!("撤回 #{}", attempt), tx);
+            }
+            "3" => demo.mine(&miner, &wallets),
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 池子里的冲突不是"先到先得"：
+        同一个输入的两笔交易，手续费高出足够多的那笔赢。节点按这个规则替换，矿工也乐意 (多赚手续费)。
+
+    2. 为什么要"之和 + 增量"：
+        被替换的交易已经被全网转发过一次，替换要为那次转发买单 (>= 之和)，还要为自己的转发再付一点 (增量)；
+        否则攻击者可以用同样的手续费反复替换，白白消耗全网带宽。
+
+    3. 撤回也是替换：
+        把钱转回给自己的交易和原交易冲突，手续费够高就能把原交易挤掉 —— 所以 0 确认的交易随时可能消失，
+        收款方要等上链 (最好再等几个确认) 才能发货。
+
+    4. 上链之后就不能替换了：
+        原输入已经在链上被花掉，新交易在 validate_tx 那一步就失败，再高的手续费也没用 —— 替换只发生在交易池里。
+*/
@@ -0,0 +1,204 @@
+// src/s06_chain/ex24_multisig.rs
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::crypto::schnorr::Keypair;
+use crate::s05_zk_lab::math::curve::CurvePoint;
+
+use super::multisig::{Approval, MultisigWallet, PendingSpend, Proposal};
+
+/*
+业务场景：团队金库不能只靠一把私钥 —— 丢了钱就没了，被偷了钱也没了。
+    5 个保管人各拿一把钥匙，任意 3 个同意才能花钱 (3-of-5)：丢两把、被偷两把都不要紧。
+
+本练习：
+    1. 每个保管人一个线程，协调者用 channel 把提案发给他们，签名从一个共享的回信 channel 陆续回来 (到达顺序随机)
+       保管人里有正常签名的、拒绝的、手抖发了两遍的、签错了金额的，还有一个不在名单里的 Mallory 也来凑热闹
+       协调者逐个检查，凑够 3 个有效签名立刻执行，之后再到的签名一律作废
+    2. 重放：把第 1 轮执行过的提案和它的签名原样再交一次 —— nonce 已经变了，拒绝
+    3. 三个人拒绝，只凑到 2 个签名：达不到门槛，钱不动
+*/
+
+const THRESHOLD: usize = 3;
+const INITIAL_BALANCE: u64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Behavior {
+    Honest,
+    Decline,
+    // 同一个签名发两遍
+    DoubleSend,
+    // 签的是改了金额的提案
+    WrongAmount,
+}
+
+enum Reply {
+    Approve(Approval),
+    Decline,
+}
+
+struct Holder {
+    name: &'static str,
+    keys: Keypair<CurvePoint>,
+}
+
+// 玩具曲线只有 1019 个点，随机生成的几把钥匙可能撞上，撞了就重抽
+fn distinct_holders(names: &[&'static str], rng: &mut SimpleRng) -> Vec<Holder> {
+    let mut holders: Vec<Holder> = Vec::new();
+    for name in names {
+        let keys = loop {
+            let keys = Keypair::generate(rng);
+            if holders.iter().all(|h| h.keys.public != keys.public) {
+                break keys;
+            }
+        };
+        holders.push(Holder { name, keys });
+    }
+    holders
+}
+
+// 保管人线程：等提案，"考虑"一会儿，按自己的行为回信
+fn spawn_holder(holder: &Holder, behavior: Behavior, seed: u64, replies: Sender<(&'static str, Reply)>) -> (Sender<Proposal>, thread::JoinHandle<()>) {
+    let (tx, rx) = mpsc::channel::<Proposal>();
+    let (name, keys) = (holder.name, holder.keys);
+    let handle = thread::spawn(move || {
+        let mut rng = SimpleRng::new(seed);
+        let Ok(proposal) = rx.recv() else {
+            return;
+        };
+        thread::sleep(Duration::from_millis(10 + rng.gen_range(80)));
+        let reply = match behavior {
+            Behavior::Honest | Behavior::DoubleSend => Reply::Approve(Approval::sign(&keys, &proposal, &mut rng)),
+            Behavior::Decline => Reply::Decline,
+            Behavior::WrongAmount => {
+                let tampered = Proposal { amount: proposal.amount * 10, ..proposal };
+                Reply::Approve(Approval::sign(&keys, &tampered, &mut rng))
+            }
+        };
+        if let (Behavior::DoubleSend, Reply::Approve(approval)) = (behavior, &reply) {
+            let _ = replies.send((name, Reply::Approve(*approval)));
+        }
+        let _ = replies.send((name, reply));
+    });
+    (tx, handle)
+}
+
+// 一轮收集：把提案发给所有人，边收边验，够门槛就执行。返回被接受的签名 (给重放用)
+fn round(wallet: &mut MultisigWallet, pending: &mut PendingSpend, participants: &[(&Holder, Behavior)], rng: &mut SimpleRng) -> Vec<Approval> {
+    let (reply_tx, reply_rx) = mpsc::channel();
+    let mut handles = Vec::new();
+    for (holder, behavior) in participants {
+        let (inbox, handle) = spawn_holder(holder, *behavior, rng.next_u64(), reply_tx.clone());
+        if inbox.send(pending.proposal.clone()).is_err() {
+            println!("  ❌ {} 的线程已经退出 (不应该发生)", holder.name);
+        }
+        handles.push(handle);
+    }
+    // 协调者自己不回信：丢掉这份 Sender，所有保管人线程结束后 reply_rx 才会关闭
+    drop(reply_tx);
+
+    let mut accepted = Vec::new();
+    for (name, reply) in reply_rx {
+        let approval = match reply {
+            Reply::Decline => {
+                println!("    {:<8} 拒绝签名", name);
+                continue;
+            }
+            Reply::Approve(approval) => approval,
+        };
+        match pending.collect(wallet, &approval) {
+            Ok(count) => {
+                accepted.push(approval);
+                println!("    {:<8} ✅ 有效签名 {}/{}", name, count, wallet.threshold());
+                if count == wallet.threshold() {
+                    match wallet.execute(pending) {
+                        Ok(()) => println!("    ==> 凑够门槛，执行 {}，金库余额 {}", pending.proposal, wallet.balance),
+                        Err(e) => println!("    ==> ❌ 凑够门槛但执行失败: {}", e),
+                    }
+                }
+            }
+            Err(e) => println!("    {:<8} ❌ {}", name, e),
+        }
+    }
+    for handle in handles {
+        let _ = handle.join();
+    }
+    accepted
+}
+
+pub fn run() {
+    println!("--- S06 Ex24: 多签钱包 ({}-of-5，保管人线程通过 channel 回传签名) ---", THRESHOLD);
+    let mut rng = SimpleRng::from_time();
+    let holders = distinct_holders(&["Alice", "Bob", "Carol", "Dave", "Erin", "Mallory"], &mut rng);
+    let (members, outsider) = holders.split_at(5);
+    let mut wallet = match MultisigWallet::new(members.iter().map(|h| h.keys.public).collect(), THRESHOLD, INITIAL_BALANCE) {
+        Ok(wallet) => wallet,
+        Err(e) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    let names: Vec<String> = members.iter().enumerate().map(|(i, h)| format!("#{} {}", i, h.name)).collect();
+    println!("  名单 ({} 人): {}；Mallory 不在名单里", wallet.signer_count(), names.join("  "));
+
+    // ==========================================
+    // 1. 收集签名
+    // ==========================================
+    let proposal = wallet.propose("Vendor", 300);
+    println!("\n[1] 提案 {}；金库余额 {}", proposal, wallet.balance);
+    println!("  行为: Alice、Erin 正常签；Bob 发两遍；Carol 拒绝；Dave 签成了 10 倍金额；Mallory 也签了一份");
+    let behaviors = [Behavior::Honest, Behavior::DoubleSend, Behavior::Decline, Behavior::WrongAmount, Behavior::Honest];
+    let mut participants: Vec<(&Holder, Behavior)> = members.iter().zip(behaviors).collect();
+    participants.push((&outsider[0], Behavior::Honest));
+    let mut pending = PendingSpend::new(proposal.clone());
+    let accepted = round(&mut wallet, &mut pending, &participants, &mut rng);
+    println!("  计入的签名者: {:?}", pending.signers());
+
+    // ==========================================
+    // 2. 重放
+    // ==========================================
+    println!("\n[2] 把提案 {} 和它的 {} 个签名原样再交一次", proposal, accepted.len());
+    let mut replay = PendingSpend::new(proposal);
+    for approval in &accepted {
+        if let Err(e) = replay.collect(&wallet, approval) {
+            println!("  ❌ 签名居然不被接受 (不应该发生): {}", e);
+        }
+    }
+    match wallet.execute(&mut replay) {
+        Ok(()) => println!("  ✅ 又执行了一次 (不应该发生)，金库余额 {}", wallet.balance),
+        Err(e) => println!("  ❌ {}", e),
+    }
+
+    // ==========================================
+    // 3. 达不到门槛
+    // ==========================================
+    let proposal = wallet.propose("Mallory", 600);
+    println!("\n[3] 提案 {}：只有 Alice、Dave 同意，其他三人拒绝", proposal);
+    let behaviors = [Behavior::Honest, Behavior::Decline, Behavior::Decline, Behavior::Honest, Behavior::Decline];
+    let participants: Vec<(&Holder, Behavior)> = members.iter().zip(behaviors).collect();
+    let mut pending = PendingSpend::new(proposal);
+    round(&mut wallet, &mut pending, &participants, &mut rng);
+    match wallet.execute(&mut pending) {
+        Ok(()) => println!("  ✅ 执行了 (不应该发生)"),
+        Err(e) => println!("  ❌ 不能执行: {}；金库余额还是 {}", e, wallet.balance),
+    }
+}
+
+/*
+关键点总结：
+    1. 三种无效签名，三种检查：
+        不在名单 (查公钥表)、重复 (按签名者下标去重，而不是按签名 —— 同一个人换个随机数能签出无数个不同的签名)、
+        签错内容 (验签失败)。只要有一个检查缺失，门槛就能被一个人凑够。
+
+    2. channel 天然适合"收集"：
+        每个保管人线程拿一份 Sender 的克隆，协调者只从一个 Receiver 读；
+        协调者丢掉自己那份 Sender 之后，所有线程结束、channel 关闭，for 循环自然退出。
+
+    3. 门槛一到就执行，之后的签名作废：
+        执行只能发生一次 (executed 标志)；金库的 nonce + 1 让这份提案连同它的签名一起失效，重放不了。
+
+    4. 签名签的是整份提案：
+        收款人、金额、nonce 任何一项不同，签名就对不上。Dave 签了 10 倍金额的版本，这个签名对真正的提案毫无用处。
+*/
@@ -0,0 +1,137 @@
+// src/s06_chain/ex25_hd_wallet.rs
+use crate::common::input::read_line;
+use crate::s05_zk_lab::crypto::schnorr::Keypair;
+
+use super::hd::{parse_path, path_string, ExtendedKey, HARDENED};
+use super::wallet::{base58_address, bech32_address};
+
+/*
+业务场景：每收一笔款换一个新地址 (隐私)，钱包里很快就有几百把私钥 —— 每把都要备份？
+    HD 钱包只备份一个种子：所有私钥都从种子按路径算出来，丢了手机，用同一个种子在新设备上恢复出一模一样的钱包。
+
+本练习：
+    1. 从种子派生一棵钥匙树：账户 m/44'/0'/0'，收款地址 .../0/i，找零地址 .../1/i
+    2. 同一个种子再派生一遍 -> 地址完全一样；种子改一个字 -> 全变了
+    3. 观察钱包：只拿 .../0 的扩展公钥，算出同样的收款地址，但没有私钥；强化派生它算不了
+    4. ⚠️ 扩展公钥 + 一个普通子私钥泄露 = 父私钥泄露，所有兄弟钥匙跟着完蛋 (所以账户这一层用强化派生)
+    5. 自己输入路径，看对应的地址
+*/
+
+const DEFAULT_SEED: &str = "correct horse battery staple";
+const RECEIVE: usize = 5;
+const CHANGE: usize = 2;
+
+// 账户 m/44'/0'/0' 下的收款、找零地址
+fn addresses(master: &ExtendedKey) -> Vec<(String, String)> {
+    let account = master.derive(&[44 + HARDENED, HARDENED, HARDENED]);
+    let mut list = Vec::new();
+    for (branch, count) in [(0, RECEIVE), (1, CHANGE)] {
+        let node = account.child(branch);
+        for i in 0..count as u32 {
+            let key = node.child(i);
+            list.push((path_string(&key.path), base58_address(&key.keys.public)));
+        }
+    }
+    list
+}
+
+pub fn run() {
+    println!("--- S06 Ex25: HD 钱包 (一个种子派生整棵钥匙树) ---");
+    let input = read_line(&format!("  种子短语 (回车用 {:?}): ", DEFAULT_SEED));
+    let seed = if input.is_empty() { DEFAULT_SEED.to_string() } else { input };
+
+    // ==========================================
+    // 1. 派生钥匙树
+    // ==========================================
+    let master = ExtendedKey::master(seed.as_bytes());
+    println!("\n[1] 种子 {:?}", seed);
+    println!("  主钥匙 m: 公钥 {}，链码 {}..", master.keys.public, hex::encode(&master.chain_code[..8]));
+    let list = addresses(&master);
+    for (path, addr) in &list {
+        println!("    {:<20} {}", path, addr);
+    }
+
+    // ==========================================
+    // 2. 同一个种子恢复
+    // ==========================================
+    println!("\n[2] 恢复");
+    let restored = addresses(&ExtendedKey::master(seed.as_bytes()));
+    println!("  同一个种子在\"新设备\"上再派生: {}", if restored == list { "✅ 地址完全一样" } else { "❌ 不一样 (不应该发生)" });
+    let other_seed = format!("{}!", seed);
+    let other = addresses(&ExtendedKey::master(other_seed.as_bytes()));
+    let same = list.iter().zip(&other).filter(|(a, b)| a.1 == b.1).count();
+    println!("  种子 {:?}: {} 个地址里有 {} 个相同 (玩具曲线只有 1019 个点，偶尔撞上)", other_seed, other.len(), same);
+
+    // ==========================================
+    // 3. 观察钱包
+    // ==========================================
+    let receive_node = master.derive(&[44 + HARDENED, HARDENED, HARDENED, 0]);
+    let xpub = receive_node.neuter();
+    println!("\n[3] 观察钱包只拿到 {} 的扩展公钥 (公钥 {} + 链码)", path_string(&xpub.path), xpub.public);
+    for i in 0..RECEIVE as u32 {
+        match xpub.child(i) {
+            Ok(child) => {
+                let expected = base58_address(&receive_node.child(i).keys.public);
+                let addr = base58_address(&child.public);
+                println!("    {:<20} {} {}", path_string(&child.path), addr, if addr == expected { "✅" } else { "❌ (不应该发生)" });
+            }
+            Err(e) => println!("    ❌ {} (不应该发生)", e),
+        }
+    }
+    match xpub.child(HARDENED) {
+        Ok(_) => println!("  ✅ 居然能做强化派生 (不应该发生)"),
+        Err(e) => println!("  ❌ {}", e),
+    }
+
+    // ==========================================
+    // 4. 泄露
+    // ==========================================
+    println!("\n[4] ⚠️ 观察钱包的扩展公钥 + {} 的私钥一起泄露", path_string(&receive_node.child(3).path));
+    let leaked = receive_node.child(3).keys.secret;
+    match xpub.recover_parent_secret(3, leaked) {
+        Ok(parent) => {
+            let ok = parent == receive_node.keys.secret;
+            println!("  推出 {} 的私钥 {}: {}", path_string(&xpub.path), parent, if ok { "✅ 和真的一样" } else { "❌ 不对 (不应该发生)" });
+            let sibling = ExtendedKey { keys: Keypair::from_secret(parent), chain_code: xpub.chain_code, path: xpub.path.clone() }.child(0);
+            println!("  再推出兄弟 {} 的私钥: {}", path_string(&sibling.path), if sibling.keys.secret == receive_node.child(0).keys.secret { "✅ 所有收款地址的钱都能花了" } else { "❌ (不应该发生)" });
+        }
+        Err(e) => println!("  ❌ {} (不应该发生)", e),
+    }
+    let account = master.derive(&[44 + HARDENED, HARDENED]);
+    match account.neuter().recover_parent_secret(HARDENED, account.child(HARDENED).keys.secret) {
+        Ok(_) => println!("  ✅ 强化子钥匙也推回去了 (不应该发生)"),
+        Err(e) => println!("  换成强化派生的 {}: ❌ {}", path_string(&account.child(HARDENED).path), e),
+    }
+
+    // ==========================================
+    // 5. 自己输入路径
+    // ==========================================
+    let text = read_line("\n[5] 输入派生路径 (例如 m/44'/0'/0'/0/7，回车跳过): ");
+    if text.is_empty() {
+        return;
+    }
+    match parse_path(&text) {
+        Ok(path) => {
+            let key = master.derive(&path);
+            println!("  {}: 公钥 {}", path_string(&key.path), key.keys.public);
+            println!("    Base58Check {}", base58_address(&key.keys.public));
+            println!("    Bech32      {}", bech32_address(&key.keys.public));
+        }
+        Err(e) => println!("  ❌ {}", e),
+    }
+}
+
+/*
+关键点总结：
+    1. 确定性：
+        钥匙树只由种子决定，备份一次种子 = 备份了所有过去和将来的私钥。种子本身就是全部秘密，要像私钥一样保管。
+
+    2. 链码：
+        只用父公钥派生子钥匙的话，任何人看到公钥就能算出所有子公钥 —— 隐私全无。
+        链码是额外的 32 字节秘密，有了它才能派生；扩展公钥 = 公钥 + 链码，给谁就等于让谁看到整棵子树的地址。
+
+    3. 普通 vs 强化：
+        普通派生的 tweak 只依赖公开信息，所以观察钱包能派生地址；代价是 子私钥 - tweak = 父私钥。
+        强化派生把父私钥放进哈希，子私钥泄露也推不回父私钥，但也就没法只用公钥派生。
+        BIP-44 的约定：m/44'/币种'/账户' 这几层强化，账户下面的 收款/找零/序号 用普通派生。
+*/
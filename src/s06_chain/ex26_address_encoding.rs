@@ -0,0 +1,123 @@
+// src/s06_chain/ex26_address_encoding.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::encoding::{base58_decode, base58_encode, base58check_encode};
+use super::wallet::{address_hash, base58_address, bech32_address, parse_address, Wallet, ADDRESS_HRP, ADDRESS_VERSION};
+
+/*
+业务场景：Bob 把收款地址念给 Alice，Alice 手抄的时候抄错了一个字符。
+    如果地址没有校验，钱就转到了一个谁也没有私钥的地址 —— 永远拿不回来。
+    所以给人看的地址都带校验和：钱包在发交易之前先解码，抄错了直接拒绝。
+
+本练习：
+    1. 同一个公钥哈希的三种写法：链内部的 addr_十六进制、Base58Check、Bech32；Base58 的前导 0 字节变成 '1'
+    2. 抄错一个字符 / 相邻两个字符抄反：对每个位置都试一遍，数一数能发现多少
+       没有校验和的十六进制写法：抄错了照样"合法"，钱就丢了
+    3. 交互：粘贴一个地址 (可以故意改错几个字符)，钱包告诉你它属于谁，或者哪里不对
+*/
+
+// 每个位置换成字母表里的下一个字符 / 和下一个字符交换，看看 parse_address 能不能报错
+// (没报错就是解析成了另一个"合法"地址，钱会转给别人)
+fn corruption_stats(text: &str, alphabet: &str, skip: usize) -> (usize, usize, usize, usize) {
+    let chars: Vec<char> = text.chars().collect();
+    let alpha: Vec<char> = alphabet.chars().collect();
+    let (mut typo_total, mut typo_caught, mut swap_total, mut swap_caught) = (0, 0, 0, 0);
+    for i in skip..chars.len() {
+        let Some(pos) = alpha.iter().position(|&c| c == chars[i]) else {
+            continue;
+        };
+        let mut typo = chars.clone();
+        typo[i] = alpha[(pos + 1) % alpha.len()];
+        typo_total += 1;
+        if parse_address(&typo.iter().collect::<String>()).is_err() {
+            typo_caught += 1;
+        }
+        if i + 1 < chars.len() && chars[i] != chars[i + 1] {
+            let mut swapped = chars.clone();
+            swapped.swap(i, i + 1);
+            swap_total += 1;
+            if parse_address(&swapped.iter().collect::<String>()).is_err() {
+                swap_caught += 1;
+            }
+        }
+    }
+    (typo_total, typo_caught, swap_total, swap_caught)
+}
+
+pub fn run() {
+    println!("--- S06 Ex26: 地址编码 (Base58Check / Bech32，抄错一个字符也能发现) ---");
+    let mut rng = SimpleRng::from_time();
+    let wallets = [Wallet::generate("Alice", &mut rng), Wallet::generate("Bob", &mut rng)];
+
+    // ==========================================
+    // 1. 三种写法
+    // ==========================================
+    println!("\n[1] 同一个公钥哈希的三种写法 (版本字节 0x{:02x})", ADDRESS_VERSION);
+    for wallet in &wallets {
+        println!("  {} 公钥哈希 {}", wallet.name, hex::encode(address_hash(&wallet.keys.public)));
+        println!("    内部        {}", wallet.address());
+        println!("    Base58Check {}", base58_address(&wallet.keys.public));
+        println!("    Bech32      {}", bech32_address(&wallet.keys.public));
+    }
+    let bytes = [0u8, 0, 0, 1, 2, 3];
+    let encoded = base58_encode(&bytes);
+    println!("  前导 0 字节: {} -> {:?} -> {:?}", hex::encode(bytes), encoded, base58_decode(&encoded).map(hex::encode));
+    println!("  版本字节不同，开头的字符就不同: 0x05 -> {}", base58check_encode(0x05, &address_hash(&wallets[0].keys.public)));
+
+    // ==========================================
+    // 2. 抄错
+    // ==========================================
+    println!("\n[2] 抄错的地址能不能被发现 (每个位置都试一遍，十六进制写法没有校验)");
+    let alice = &wallets[0];
+    let cases = [
+        ("十六进制", alice.address(), "0123456789abcdef", "addr_".len()),
+        ("Base58Check", base58_address(&alice.keys.public), "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz", 0),
+        ("Bech32", bech32_address(&alice.keys.public), "qpzry9x8gf2tvdw0s3jn54khce6mua7l", ADDRESS_HRP.len() + 1),
+    ];
+    for (name, text, alphabet, skip) in cases {
+        let (typo_total, typo_caught, swap_total, swap_caught) = corruption_stats(&text, alphabet, skip);
+        println!("  {:<12} 改一个字符: 发现 {}/{}   相邻互换: 发现 {}/{}", name, typo_caught, typo_total, swap_caught, swap_total);
+    }
+    let mut typo: Vec<char> = base58_address(&alice.keys.public).chars().collect();
+    let last = typo.len() - 1;
+    typo[last] = if typo[last] == 'z' { 'y' } else { 'z' };
+    let typo: String = typo.into_iter().collect();
+    match parse_address(&typo) {
+        Ok(addr) => println!("  {} -> ✅ {} (不应该发生)", typo, addr),
+        Err(e) => println!("  例: {} -> ❌ {}", typo, e),
+    }
+
+    // ==========================================
+    // 3. 交互
+    // ==========================================
+    println!("\n[3] 粘贴一个地址 (三种写法都行，可以故意改错几个字符)，回车结束");
+    loop {
+        let text = read_line("  地址: ");
+        if text.is_empty() {
+            break;
+        }
+        match parse_address(&text) {
+            Ok(addr) => match wallets.iter().find(|w| w.address() == addr) {
+                Some(wallet) => println!("  ✅ 属于 {} ({})", wallet.name, addr),
+                None => println!("  ✅ 格式正确 ({})，但不是这里的钱包 —— 转过去之前再确认一下", addr),
+            },
+            Err(e) => println!("  ❌ {}", e),
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 编码不是加密：
+        三种写法是同一串字节，互相转换不需要任何秘密。区别只在"给人用"的体验：长度、易混字符、校验能力。
+
+    2. 校验和挡住手误：
+        Base58Check 的 4 字节校验和让随机错误漏过去的概率是 2^-32；
+        Bech32 的 BCH 码更进一步：不超过 4 个字符的错误保证能发现，而且全小写、不会因为大小写抄错。
+        没有校验的十六进制写法，每一个抄错的版本都是"合法地址"。
+
+    3. 前缀和版本：
+        Base58Check 的版本字节决定了开头的字符 (比特币 P2PKH 是 1，P2SH 是 3)，Bech32 的前缀直接写在明面上 (bc1、tb1)；
+        钱包据此拒绝"别的链 / 别的网络"的地址。
+*/
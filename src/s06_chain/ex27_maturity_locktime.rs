@@ -0,0 +1,221 @@
+// src/s06_chain/ex27_maturity_locktime.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::hash::short_hex;
+
+use super::node::Node;
+use super::pow;
+use super::utxo::{OutPoint, Transaction, TxIn, TxOut, BLOCK_REWARD};
+use super::wallet::Wallet;
+
+/*
+业务场景：Miner 刚挖出一个块，马上想拿这 50 块奖励去付账 —— 节点不让。
+    如果这个块过一会儿被重组掉，coinbase 就凭空消失了，花了它的交易、以及后面花这些交易输出的交易，全都跟着作废。
+    所以 coinbase 要"晾"几个块 (比特币 100 个) 才能花。
+    另一个方向：Alice 想签一笔"到某个高度之后才生效"的交易 (遗嘱、分期付款)，在那之前谁拿到它都广播不出去。
+
+本练习 (成熟期 MATURITY 个块)：
+    1. 成熟期：创世块给 Alice 的币、Miner 刚挖到的币都还不能花；交易池拒绝，直接塞进区块也被节点拒绝
+    2. 锁定时间：Alice 签一笔锁定到 3 个块之后的交易，一个块一个块地重试，看它什么时候能进交易池
+    3. 交互：你来出块、花 Miner 最新/最老的 coinbase、发锁定交易
+*/
+
+const DIFFICULTY: u32 = 8;
+const MATURITY: u64 = 3;
+const FEE: u64 = 1;
+
+struct Demo {
+    node: Node,
+    rng: SimpleRng,
+}
+
+impl Demo {
+    // 下一个块的高度：交易按它检查成熟期和锁定时间
+    fn next_height(&self) -> u64 {
+        self.node.chain().height() + 1
+    }
+
+    // 把 coin 整枚花掉：amount 给 to，找零给 from，签好名
+    fn spend(&mut self, from: &Wallet, coin: OutPoint, to: &Wallet, amount: u64, lock_time: u64) -> Result<Transaction, String> {
+        let owned = self.node.utxos().outputs_of(&from.address());
+        let (_, out) = owned.iter().find(|(op, _)| *op == coin).ok_or_else(|| format!("{} 不是 {} 的币", coin, from.name))?;
+        if out.amount < amount + FEE {
+            return Err(format!("{} 只有 {}，不够付 {} + 手续费 {}", coin, out.amount, amount, FEE));
+        }
+        let mut outputs = vec![TxOut { owner: to.address(), amount }];
+        if out.amount > amount + FEE {
+            outputs.push(TxOut { owner: from.address(), amount: out.amount - amount - FEE });
+        }
+        let mut tx = Transaction { inputs: vec![TxIn::unsigned(coin)], outputs, lock_time };
+        from.sign(&mut tx, self.node.utxos(), &mut self.rng);
+        Ok(tx)
+    }
+
+    fn submit(&mut self, label: &str, tx: Result<Transaction, String>) -> bool {
+        match tx.and_then(|tx| self.node.submit(tx)) {
+            Ok(accepted) => {
+                println!("  {}: ✅ 进入交易池，手续费 {}", label, accepted.fee);
+                true
+            }
+            Err(e) => {
+                println!("  {}: ❌ {}", label, e);
+                false
+            }
+        }
+    }
+
+    fn mine(&mut self, miner: &Wallet) {
+        let pending = self.node.mempool().len();
+        match self.node.mine(&miner.address()) {
+            Ok(block) => println!("  Miner 挖出 #{}，打包 {} 笔交易", block.header.height, pending),
+            Err(e) => println!("  ❌ 出块失败 (不应该发生): {}", e),
+        }
+    }
+
+    // 按创建高度排好的币：(币, 金额, 最早能花的高度)；普通输出的高度记 0
+    fn coins(&self, wallet: &Wallet) -> Vec<(OutPoint, u64, u64)> {
+        let utxos = self.node.utxos();
+        let mut coins: Vec<_> = utxos.outputs_of(&wallet.address()).into_iter().map(|(op, out)| (op, out.amount, utxos.spendable_from(&op).unwrap_or(0))).collect();
+        coins.sort_by_key(|&(_, _, from)| from);
+        coins
+    }
+
+    fn show(&self, wallets: &[&Wallet]) {
+        let next = self.next_height();
+        println!("    链高 #{}，下一个块 #{}", next - 1, next);
+        for wallet in wallets {
+            let coins = self.coins(wallet);
+            let total: u64 = coins.iter().map(|&(_, amount, _)| amount).sum();
+            let ready: u64 = coins.iter().filter(|&&(_, _, from)| from <= next).map(|&(_, amount, _)| amount).sum();
+            let items: Vec<String> = coins
+                .iter()
+                .map(|&(op, amount, from)| match from {
+                    0 => format!("{} {}", short_hex(&op.txid), amount),
+                    from if from <= next => format!("{} {} ✅", short_hex(&op.txid), amount),
+                    from => format!("{} {} ⏳#{}", short_hex(&op.txid), amount, from),
+                })
+                .collect();
+            println!("    {:<6} 余额 {:>3} (能花 {:>3})  [{}]", wallet.name, total, ready, items.join(", "));
+        }
+    }
+}
+
+pub fn run() {
+    println!("--- S06 Ex27: coinbase 成熟期与锁定时间 (刚挖到的币不能马上花，交易可以约定最早上链的高度) ---");
+    let mut rng = SimpleRng::from_time();
+    let alice = Wallet::generate("Alice", &mut rng);
+    let bob = Wallet::generate("Bob", &mut rng);
+    let miner = Wallet::generate("Miner", &mut rng);
+    let wallets = [&alice, &bob, &miner];
+
+    let node = match Node::new(DIFFICULTY, vec![Transaction::coinbase(&alice.address(), BLOCK_REWARD, 0)]) {
+        Ok(node) => node.with_coinbase_maturity(MATURITY),
+        Err(e) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    let mut demo = Demo { node, rng };
+
+    // ==========================================
+    // 1. 成熟期
+    // ==========================================
+    println!("\n[1] coinbase 要等 {} 个块才能花 (#h 的 coinbase 最早进 #h+{})；⏳ 后面是最早能花的高度", demo.node.utxos().coinbase_maturity(), MATURITY);
+    demo.mine(&miner);
+    demo.show(&wallets);
+    let fresh = demo.coins(&miner)[0].0;
+    let tx = demo.spend(&miner, fresh, &alice, 10, 0);
+    demo.submit("Miner 马上花 #1 的 coinbase", tx.clone());
+    let alice_coin = demo.coins(&alice)[0].0;
+    let tx_alice = demo.spend(&alice, alice_coin, &bob, 10, 0);
+    demo.submit("Alice 花创世块的 coinbase", tx_alice);
+
+    // 交易池不收，Miner 干脆自己出块，把这笔交易直接打包进去
+    if let Ok(tx) = tx {
+        let before = demo.node.snapshot();
+        let next = demo.next_height();
+        let mut block = demo.node.chain().candidate(vec![Transaction::coinbase(&miner.address(), BLOCK_REWARD + FEE, next), tx]);
+        block.header = pow::mine(block.header).header;
+        match demo.node.connect(block) {
+            Ok(()) => println!("  Miner 自己打包: ✅ 上链了 (不应该发生)"),
+            Err(e) => println!("  Miner 自己打包: ❌ 节点拒绝 #{}: {}", next, e),
+        }
+        let unchanged = demo.node.snapshot() == before;
+        println!("  节点状态{}", if unchanged { "没变 ✅" } else { "被改了 ❌ (不应该发生)" });
+    }
+
+    // ==========================================
+    // 2. 锁定时间
+    // ==========================================
+    demo.mine(&miner);
+    let lock_time = demo.next_height() + 2;
+    println!("\n[2] Alice 的币成熟了；她签一笔锁定到 #{} 的交易给 Bob，每出一个块重试一次", lock_time);
+    let alice_coin = demo.coins(&alice)[0].0;
+    let locked = demo.spend(&alice, alice_coin, &bob, 20, lock_time);
+    if let Ok(tx) = &locked {
+        println!("    {}", tx);
+    }
+    for _ in 0..4 {
+        let label = format!("下一个块 #{}", demo.next_height());
+        if demo.submit(&label, locked.clone()) {
+            break;
+        }
+        demo.mine(&miner);
+    }
+    demo.mine(&miner);
+
+    // ==========================================
+    // 3. 交互
+    // ==========================================
+    println!("\n[3] 交互：自己试试什么时候能花");
+    loop {
+        demo.show(&wallets);
+        println!("  1. Miner 出块");
+        println!("  2. Miner 把最新的 coinbase 转 10 给 Alice");
+        println!("  3. Miner 把最老的 coinbase 转 10 给 Alice");
+        println!("  4. Alice 给 Bob 转 5，锁定到指定高度");
+        println!("  0. 返回");
+        match read_line("  选择: ").as_str() {
+            "1" => demo.mine(&miner),
+            choice @ ("2" | "3") => {
+                let coinbases: Vec<_> = demo.coins(&miner).into_iter().filter(|&(_, _, from)| from > 0).collect();
+                let coin = if choice == "2" { coinbases.last() } else { coinbases.first() };
+                let Some(&(coin, _, from)) = coin else {
+                    println!("  ❌ Miner 没有 coinbase 了");
+                    continue;
+                };
+                let tx = demo.spend(&miner, coin, &alice, 10, 0);
+                demo.submit(&format!("花 {} (最早 #{})", short_hex(&coin.txid), from), tx);
+            }
+            "4" => {
+                let Some(lock_time) = read_line("  锁定到高度 (0 表示不锁): ").parse::<u64>().ok() else {
+                    println!("❌ 高度要是非负整数");
+                    continue;
+                };
+                let Some(&(coin, _, _)) = demo.coins(&alice).first() else {
+                    println!("  ❌ Alice 没有币了");
+                    continue;
+                };
+                let tx = demo.spend(&alice, coin, &bob, 5, lock_time);
+                demo.submit(&format!("锁定到 #{}", lock_time), tx);
+            }
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 成熟期是共识规则，不只是交易池的策略：
+        交易池拒绝只能挡住"好好广播"的交易；矿工自己打包的块，每个节点 connect 的时候还要再查一遍。
+        所以规则写在 UtxoSet::validate_tx 里，交易池和区块验证走的是同一段代码。
+
+    2. 为什么要等：
+        coinbase 不来自任何输入，区块被重组掉它就彻底消失，没有"回到交易池重新打包"这回事。
+        等几个块再让它流通，被回滚时牵连的交易就少得多。UTXO 集合要记住每枚 coinbase 的高度，回滚时一起删掉。
+
+    3. 锁定时间让交易"约定未来"：
+        签名覆盖了 lock_time (它在 encode 里，改了 txid 就变)，谁也不能把锁提前；
+        高度没到之前交易池不收，区块也不能包含它；这是支付通道、HTLC 这些"到期退款"合约的基础。
+*/
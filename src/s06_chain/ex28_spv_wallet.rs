@@ -0,0 +1,224 @@
+// src/s06_chain/ex28_spv_wallet.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::ex01_merkle::{MerkleProof, MerkleTree};
+use crate::s05_zk_lab::hash::{short_hex, Digest};
+
+use super::block::Block;
+use super::node::Node;
+use super::spv::{SpvWallet, HEADER_BYTES};
+use super::utxo::{Transaction, BLOCK_REWARD};
+use super::wallet::Wallet;
+
+/*
+业务场景：Bob 的手机钱包只存区块头和"自己收到的钱"的证明，不存任何别人的交易。
+    Alice 付款之后把 交易 + Merkle 证明 + 区块哈希 发给 Bob；钱包对照自己的区块头验证，验证过的证明存下来。
+    之后链发生重组，那个区块不在最好的链上了 —— 钱包要能自己发现"这笔钱不算数了"。
+
+本练习 (余额只算至少 CONFIRMATIONS 个确认的收款)：
+    1. 同步区块头、收下一笔真实付款，对比钱包和全节点要存的字节数
+    2. 伪造的证明：改过金额、指向钱包没见过的区块、付给别人的交易、重复提交
+    3. 分叉：主链和分支上各有一笔付给 Bob 的钱，分支变重之后，钱包自动把主链上那笔标成"不在最好的链上"
+    4. 交互：你来决定哪条链出块、在哪条链上付款
+*/
+
+const DIFFICULTY: u32 = 8;
+const FEE: u64 = 1;
+const CONFIRMATIONS: u64 = 2;
+const BRANCH_NAMES: [&str; 2] = ["主链", "分支"];
+
+struct Demo {
+    // 两个全节点：一开始 branches[1] 只是占位，分叉时才复制主链
+    branches: [Node; 2],
+    wallet: SpvWallet,
+    alice: Wallet,
+    miner: String,
+    rng: SimpleRng,
+}
+
+fn chain_bytes(node: &Node) -> usize {
+    node.chain().blocks.iter().map(|b| HEADER_BYTES + b.txs.iter().map(|tx| tx.encode_with_witness().len()).sum::<usize>()).sum()
+}
+
+fn prove(block: &Block<Transaction>, tx: &Transaction) -> Option<MerkleProof> {
+    let index = block.txs.iter().position(|t| t.txid() == tx.txid())?;
+    MerkleTree::new_iterative(block.txs.clone()).prove(index)
+}
+
+impl Demo {
+    // 在第 branch 条链上出块，amount > 0 时先让 Alice 付给 Bob；钱包收区块头，再收付款的证明
+    fn mine(&mut self, branch: usize, amount: u64) {
+        let name = BRANCH_NAMES[branch];
+        let node = &mut self.branches[branch];
+        let mut payment = None;
+        if amount > 0 {
+            let tx = node.utxos().build_transfer(&self.alice.address(), &self.wallet.owner, amount, FEE).and_then(|mut tx| {
+                self.alice.sign(&mut tx, node.utxos(), &mut self.rng);
+                node.submit(tx.clone()).map(|_| tx)
+            });
+            match tx {
+                Ok(tx) => payment = Some(tx),
+                Err(e) => println!("  {}: Alice 付不了 {}: ❌ {}", name, amount, e),
+            }
+        }
+        let block = match node.mine(&self.miner) {
+            Ok(block) => block,
+            Err(e) => {
+                println!("  ❌ {}出块失败 (不应该发生): {}", name, e);
+                return;
+            }
+        };
+        let (height, hash) = (block.header.height, block.hash());
+        match self.wallet.add_header(block.header.clone()) {
+            Ok(true) => println!("  {}挖出 #{} {}..，钱包: 新的最好链头", name, height, short_hex(&hash)),
+            Ok(false) => println!("  {}挖出 #{} {}..，钱包: 收下了，但不比 #{} 重", name, height, short_hex(&hash), self.wallet.headers().best().height),
+            Err(e) => println!("  {}挖出 #{} {}..，钱包: ❌ {} (不应该发生)", name, height, short_hex(&hash), e),
+        }
+        if let Some(tx) = payment {
+            let proof = prove(&block, &tx);
+            self.deliver(&format!("Alice 在{}上付 {}", name, amount), tx, proof, hash);
+        }
+    }
+
+    fn deliver(&mut self, label: &str, tx: Transaction, proof: Option<MerkleProof>, block_hash: Digest) {
+        let Some(proof) = proof else {
+            println!("  {:<26} ❌ 区块里找不到这笔交易 (不应该发生)", label);
+            return;
+        };
+        match self.wallet.receive(tx, proof, block_hash) {
+            Ok(Some(confirmations)) => println!("  {:<26} ✅ 证明成立，存下，{} 个确认", label, confirmations),
+            Ok(None) => println!("  {:<26} ⚠️ 证明成立，但区块不在最好的链上：存下，不算进余额", label),
+            Err(e) => println!("  {:<26} ❌ {}", label, e),
+        }
+    }
+
+    fn show(&self) {
+        let best = self.wallet.headers().best();
+        println!("    钱包: {} 个区块头，最好链头 #{} {}..", self.wallet.headers().count(), best.height, short_hex(&best.hash()));
+        for (payment, confirmations) in self.wallet.payments() {
+            let status = match confirmations {
+                Some(c) if c >= CONFIRMATIONS => format!("✅ {} 个确认", c),
+                Some(c) => format!("⏳ {} 个确认", c),
+                None => String::from("⚠️ 不在最好的链上"),
+            };
+            println!("      收 {:>3}  交易 {}..  区块 #{} {}..  {}", payment.amount, short_hex(&payment.tx.txid()), payment.height, short_hex(&payment.block_hash), status);
+        }
+        println!("    余额 ({} 个确认以上) {}", CONFIRMATIONS, self.wallet.balance(CONFIRMATIONS));
+        println!("    存储: 钱包 {} 字节，主链全节点 {} 字节", self.wallet.storage_bytes(), chain_bytes(&self.branches[0]));
+    }
+}
+
+fn read_amount() -> Option<u64> {
+    read_line("  金额: ").parse::<u64>().ok().filter(|&a| a > 0)
+}
+
+pub fn run() {
+    println!("--- S06 Ex28: SPV 钱包 (只存区块头和自己的付款证明) ---");
+    let mut rng = SimpleRng::from_time();
+    let alice = Wallet::generate("Alice", &mut rng);
+    let bob = Wallet::generate("Bob", &mut rng);
+    let miner = Wallet::generate("Miner", &mut rng).address();
+    let node = match Node::new(DIFFICULTY, vec![Transaction::coinbase(&alice.address(), BLOCK_REWARD, 0)]) {
+        Ok(node) => node,
+        Err(e) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    let wallet = SpvWallet::new(&bob.address(), node.chain().tip().header.clone(), DIFFICULTY);
+    let mut demo = Demo { branches: [node.clone(), node], wallet, alice, miner, rng };
+
+    // ==========================================
+    // 1. 同步 + 真实付款
+    // ==========================================
+    println!("\n[1] Bob 的钱包从创世块的头开始；主链上 Alice 付 20，再压一个块");
+    demo.mine(0, 20);
+    demo.mine(0, 0);
+    demo.show();
+
+    // ==========================================
+    // 2. 伪造的证明
+    // ==========================================
+    println!("\n[2] 伪造的证明");
+    if let Some((payment, _)) = demo.wallet.payments().first() {
+        let payment = (*payment).clone();
+        let mut tampered = payment.tx.clone();
+        if let Some(out) = tampered.outputs.iter_mut().find(|out| out.owner == demo.wallet.owner) {
+            out.amount = 3000;
+        }
+        demo.deliver("金额改成 3000", tampered, Some(payment.proof.clone()), payment.block_hash);
+        demo.deliver("同一个证明再交一次", payment.tx.clone(), Some(payment.proof.clone()), payment.block_hash);
+    }
+    // 全节点私下挖了一个块，没有广播头：钱包对不上任何一个它知道的区块
+    let mut hidden = demo.branches[0].clone();
+    let secret = hidden.utxos().build_transfer(&demo.alice.address(), &demo.wallet.owner, 5, FEE).and_then(|mut tx| {
+        demo.alice.sign(&mut tx, hidden.utxos(), &mut demo.rng);
+        hidden.submit(tx.clone()).map(|_| tx)
+    });
+    match (secret, hidden.mine(&demo.miner)) {
+        (Ok(tx), Ok(block)) => demo.deliver("没广播过的区块里的付款", tx.clone(), prove(&block, &tx), block.hash()),
+        (Err(e), _) => println!("  ❌ {} (不应该发生)", e),
+        (_, Err(e)) => println!("  ❌ {} (不应该发生)", e),
+    }
+    let tip = demo.branches[0].chain().tip().clone();
+    let coinbase = tip.txs[0].clone();
+    demo.deliver("给 Miner 的 coinbase", coinbase.clone(), prove(&tip, &coinbase), tip.hash());
+
+    // ==========================================
+    // 3. 分叉
+    // ==========================================
+    println!("\n[3] 在 #{} 分叉：主链上 Alice 付 10，分支上同一枚币付 7 (两边互为双花)", demo.branches[0].chain().height());
+    demo.branches[1] = demo.branches[0].clone();
+    demo.mine(0, 10);
+    demo.mine(1, 7);
+    demo.show();
+    println!("  分支再出一个块，比主链重了:");
+    demo.mine(1, 0);
+    demo.show();
+
+    // ==========================================
+    // 4. 交互
+    // ==========================================
+    println!("\n[4] 交互：你来决定哪条链出块");
+    loop {
+        println!("  1. 主链出块");
+        println!("  2. 分支出块");
+        println!("  3. Alice 在主链上给 Bob 付款 (立刻出块)");
+        println!("  4. Alice 在分支上给 Bob 付款 (立刻出块)");
+        println!("  5. 看钱包");
+        println!("  0. 返回");
+        match read_line("  选择: ").as_str() {
+            "1" => demo.mine(0, 0),
+            "2" => demo.mine(1, 0),
+            choice @ ("3" | "4") => {
+                let Some(amount) = read_amount() else {
+                    println!("❌ 金额要是正整数");
+                    continue;
+                };
+                demo.mine(if choice == "3" { 0 } else { 1 }, amount);
+            }
+            "5" => demo.show(),
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 钱包只存和自己有关的东西：
+        区块头 (每个 156 字节) + 自己收款的交易和证明 (兄弟哈希是 log2(n) 个)，
+        这里的链上几乎只有付给 Bob 的交易，还看不出差距 (钱包还多存了分支的头)；
+        真实的区块里有几千笔别人的交易，全节点的存储随之增长，钱包只多一个区块头。
+
+    2. 证明要对得上"自己验证过的"区块头：
+        改过的交易 Merkle 根对不上；指向没见过的区块，钱包无从判断那个区块有没有算力背书，一律拒绝。
+        付给别人的交易证明再真也不存 —— 钱包只关心自己的钱。
+
+    3. 确认数是现算的，不是存下来的：
+        存的是 (交易, 证明, 区块哈希)，每次显示都对着当前最好的链重新数确认。
+        分支变重时主链上的收款自动变成 ⚠️，再换回来又自动恢复；钱包不需要专门处理"重组事件"。
+
+    4. 轻节点信任的边界：
+        两条分支上的付款互为双花，钱包看不出来 (它没有 UTXO 集合)，只能靠"最好的链 + 足够的确认数"。
+*/
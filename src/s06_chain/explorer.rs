@@ -0,0 +1,95 @@
+// src/s06_chain/explorer.rs
+use std::collections::HashMap;
+
+use super::chain::Chain;
+use super::utxo::{OutPoint, Transaction, TxId, TxOut};
+
+/*
+区块浏览器：对一条 UTXO 链做只读查询
+
+    链上只存交易，不存"余额"，也不存"谁在什么时候收了多少钱"。浏览器 (etherscan / mempool.space) 的做法：
+        从创世块开始把每个输出登记下来 (OutPoint -> TxOut)，
+        再看到一个输入时回头查它花的是哪个输出、属于谁、多少钱。
+    这里每次查询都从头扫一遍，真实的浏览器会把这些索引存进数据库，随新区块增量更新。
+*/
+
+// 交易在链上的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxLocation {
+    pub height: u64,
+    pub index: usize,
+}
+
+// 地址历史的一行：某笔交易让这个地址收到 / 花掉了多少，之后的余额是多少
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub height: u64,
+    pub txid: TxId,
+    pub received: u64,
+    pub spent: u64,
+    pub balance: u64,
+}
+
+pub struct Explorer<'a> {
+    chain: &'a Chain<Transaction>,
+    // 链上出现过的所有输出，花没花掉都在
+    outputs: HashMap<OutPoint, &'a TxOut>,
+}
+
+impl<'a> Explorer<'a> {
+    pub fn new(chain: &'a Chain<Transaction>) -> Self {
+        let mut outputs = HashMap::new();
+        for tx in chain.blocks.iter().flat_map(|b| &b.txs) {
+            let txid = tx.txid();
+            for (index, out) in tx.outputs.iter().enumerate() {
+                outputs.insert(OutPoint { txid, index: index as u32 }, out);
+            }
+        }
+        Explorer { chain, outputs }
+    }
+
+    pub fn chain(&self) -> &'a Chain<Transaction> {
+        self.chain
+    }
+
+    // 输入花的是哪个输出 (coinbase 的占位输入查不到，返回 None)
+    pub fn spent_output(&self, prev: &OutPoint) -> Option<&'a TxOut> {
+        self.outputs.get(prev).copied()
+    }
+
+    // 十六进制前缀查交易：前缀太短可能命中好几笔，全部返回
+    pub fn find_tx(&self, prefix: &str) -> Vec<(TxLocation, &'a Transaction)> {
+        let prefix = prefix.to_lowercase();
+        let mut found = Vec::new();
+        for block in &self.chain.blocks {
+            for (index, tx) in block.txs.iter().enumerate() {
+                if hex::encode(tx.txid()).starts_with(&prefix) {
+                    found.push((TxLocation { height: block.header.height, index }, tx));
+                }
+            }
+        }
+        found
+    }
+
+    // 按上链顺序列出和这个地址有关的每一笔交易
+    pub fn history(&self, address: &str) -> Vec<HistoryEntry> {
+        let mut balance = 0;
+        let mut entries = Vec::new();
+        for block in &self.chain.blocks {
+            for tx in &block.txs {
+                let received: u64 = tx.outputs.iter().filter(|out| out.owner == address).map(|out| out.amount).sum();
+                let spent: u64 = if tx.is_coinbase() {
+                    0
+                } else {
+                    tx.inputs.iter().filter_map(|input| self.spent_output(&input.prev)).filter(|out| out.owner == address).map(|out| out.amount).sum()
+                };
+                if received == 0 && spent == 0 {
+                    continue;
+                }
+                balance = balance + received - spent;
+                entries.push(HistoryEntry { height: block.header.height, txid: tx.txid(), received, spent, balance });
+            }
+        }
+        entries
+    }
+}
@@ -0,0 +1,117 @@
+// src/s06_chain/fee_market.rs
+
+/*
+EIP-1559 手续费市场：base fee 由协议按区块的满空程度自动调整
+
+    以前的以太坊是"第一价格拍卖"：用户自己猜出价，拥堵时大家互相抬价，猜错了不是多付就是一直卡着。
+    EIP-1559 把手续费拆成两部分：
+        base fee      每个区块一个值，协议算出来的，全部烧掉 (矿工拿不到，没动机去操纵它)
+        priority fee  给出块人的小费，用来在同一个区块里排队
+    区块的 gas 上限是 目标 × ELASTICITY：
+        上一个块正好用了目标那么多 -> base fee 不变
+        用满了 (2 倍目标) -> 涨 1/BASE_FEE_MAX_CHANGE_DENOMINATOR = 12.5%
+        空块 -> 跌 12.5%；中间按比例
+    交易写 max_fee (最多愿意付多少/gas) 和 max_priority_fee，实际付 base_fee + min(priority, max_fee - base_fee)。
+    max_fee < base_fee 的交易这个块进不去，留在交易池里等 base fee 降下来。
+*/
+
+pub const ELASTICITY: u64 = 2;
+pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+// 按 EIP-1559 的公式算下一个块的 base fee (整数运算，和规范一致)
+pub fn next_base_fee(parent_base_fee: u64, parent_gas_used: u64, gas_limit: u64) -> u64 {
+    let target = gas_limit / ELASTICITY;
+    if parent_gas_used == target {
+        return parent_base_fee;
+    }
+    if parent_gas_used > target {
+        let delta = parent_base_fee as u128 * (parent_gas_used - target) as u128 / target as u128 / BASE_FEE_MAX_CHANGE_DENOMINATOR as u128;
+        // 涨价至少涨 1：base fee 很低时也能涨起来
+        parent_base_fee + (delta as u64).max(1)
+    } else {
+        let delta = parent_base_fee as u128 * (target - parent_gas_used) as u128 / target as u128 / BASE_FEE_MAX_CHANGE_DENOMINATOR as u128;
+        parent_base_fee - delta as u64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeTx {
+    pub id: u64,
+    pub gas: u64,
+    pub max_fee: u64,
+    pub priority_fee: u64,
+    // 进交易池时的区块高度，用来算等了多久
+    pub arrived: u64,
+}
+
+impl FeeTx {
+    // 出块人实际拿到的小费 (每单位 gas)；max_fee 付不起 base fee 时是 None
+    pub fn effective_tip(&self, base_fee: u64) -> Option<u64> {
+        self.max_fee.checked_sub(base_fee).map(|room| room.min(self.priority_fee))
+    }
+}
+
+// 一个块的收支
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockFees {
+    pub gas_used: u64,
+    pub burned: u64,
+    pub tips: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct FeePool {
+    pending: Vec<FeeTx>,
+}
+
+impl FeePool {
+    pub fn new() -> Self {
+        FeePool { pending: Vec::new() }
+    }
+
+    pub fn add(&mut self, tx: FeeTx) {
+        self.pending.push(tx);
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn pending(&self) -> &[FeeTx] {
+        &self.pending
+    }
+
+    // max_fee 付不起当前 base fee 的交易数
+    pub fn priced_out(&self, base_fee: u64) -> usize {
+        self.pending.iter().filter(|tx| tx.max_fee < base_fee).count()
+    }
+
+    // 出块人按小费从高到低装，装到 gas 上限为止；付不起 base fee 的留在池里
+    pub fn build_block(&mut self, base_fee: u64, gas_limit: u64) -> (Vec<FeeTx>, BlockFees) {
+        let mut candidates: Vec<(u64, FeeTx)> = Vec::new();
+        let mut waiting = Vec::new();
+        for tx in self.pending.drain(..) {
+            match tx.effective_tip(base_fee) {
+                Some(tip) => candidates.push((tip, tx)),
+                None => waiting.push(tx),
+            }
+        }
+        // 小费一样时先来的先装
+        candidates.sort_by(|(a, x), (b, y)| b.cmp(a).then(x.arrived.cmp(&y.arrived)));
+
+        let mut included = Vec::new();
+        let mut fees = BlockFees { gas_used: 0, burned: 0, tips: 0 };
+        for (tip, tx) in candidates {
+            if fees.gas_used + tx.gas > gas_limit {
+                waiting.push(tx);
+                continue;
+            }
+            fees.gas_used += tx.gas;
+            fees.burned += base_fee * tx.gas;
+            fees.tips += tip * tx.gas;
+            included.push(tx);
+        }
+        self.pending = waiting;
+        (included, fees)
+    }
+}
@@ -0,0 +1,116 @@
+// src/s06_chain/fork.rs
+use std::collections::HashMap;
+
+use crate::s05_zk_lab::ex01_merkle::MerkleLeaf;
+use crate::s05_zk_lab::hash::Digest;
+
+use super::block::Block;
+use super::pow;
+use super::validation::{check_header, BlockError};
+
+/*
+区块树：两个矿工几乎同时挖出块，网络里就会同时存在两条链
+
+    Chain 是一个 Vec，只有一个"最高块"。真实节点收到的区块会长成一棵树：
+        每个区块记着父块；没有子块的区块叫 tip (分支的末端)，同一时刻可以有好几个。
+    节点必须从这些 tip 里挑一个当"主链头" —— 这就是分叉选择规则 (fork choice)：
+        LongestChain : 高度最高的分支 (最初的直觉，也是"最长链"这个说法的来源)
+        MostWork     : 累计工作量最大的分支 (比特币真正用的规则)；每个块的工作量 = 2^难度
+    两者在所有区块难度相同时一样；难度不同时，几个高难度的块可以胜过一串低难度的块。
+
+    平局 (高度 / 工作量相同) 时先到先得：节点不会因为看到一条一样好的分支就来回切换。
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForkChoice {
+    LongestChain,
+    MostWork,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T: MerkleLeaf> {
+    block: Block<T>,
+    // 从创世块到这个块的累计工作量
+    work: u128,
+    // 第几个收到的，平局时比较
+    arrival: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockTree<T: MerkleLeaf = String> {
+    nodes: HashMap<Digest, Node<T>>,
+    tips: Vec<Digest>,
+    // 每个块的难度可以不同，但不能低于这个下限 (否则随手就能挖出一长串块)
+    pub min_difficulty: u32,
+}
+
+impl<T: MerkleLeaf> BlockTree<T> {
+    pub fn new(genesis: Block<T>, min_difficulty: u32) -> Self {
+        let hash = genesis.hash();
+        let work = pow::block_work(genesis.header.difficulty);
+        let mut nodes = HashMap::new();
+        nodes.insert(hash, Node { block: genesis, work, arrival: 0 });
+        BlockTree { nodes, tips: vec![hash], min_difficulty }
+    }
+
+    // 父块必须已经在树里 (先到的子块交给 orphan::OrphanPool)；重复收到同一个块直接忽略
+    pub fn insert(&mut self, block: Block<T>) -> Result<(), BlockError> {
+        let hash = block.hash();
+        if self.nodes.contains_key(&hash) {
+            return Ok(());
+        }
+        let h = &block.header;
+        let Some(parent) = self.nodes.get(&h.prev_hash) else {
+            return Err(BlockError::UnknownParent { height: h.height, prev_hash: h.prev_hash });
+        };
+        if h.difficulty < self.min_difficulty {
+            return Err(BlockError::WrongDifficulty { height: h.height, expected: self.min_difficulty, found: h.difficulty });
+        }
+        check_header(Some(&parent.block), &block, h.difficulty)?;
+        let work = parent.work + pow::block_work(h.difficulty);
+        self.tips.retain(|tip| *tip != h.prev_hash);
+        self.tips.push(hash);
+        let arrival = self.nodes.len();
+        self.nodes.insert(hash, Node { block, work, arrival });
+        Ok(())
+    }
+
+    pub fn contains(&self, hash: &Digest) -> bool {
+        self.nodes.contains_key(hash)
+    }
+
+    pub fn tips(&self) -> Vec<&Block<T>> {
+        self.tips.iter().map(|tip| &self.nodes[tip].block).collect()
+    }
+
+    pub fn chain_work(&self, hash: &Digest) -> u128 {
+        self.nodes.get(hash).map_or(0, |node| node.work)
+    }
+
+    // 按规则打分，分数相同比谁先到 (arrival 小的赢)
+    pub fn head(&self, rule: ForkChoice) -> &Block<T> {
+        let score = |node: &Node<T>| match rule {
+            ForkChoice::LongestChain => node.block.header.height as u128,
+            ForkChoice::MostWork => node.work,
+        };
+        let best = self
+            .tips
+            .iter()
+            .map(|tip| &self.nodes[tip])
+            .max_by(|a, b| score(a).cmp(&score(b)).then(b.arrival.cmp(&a.arrival)))
+            .expect("tree always has at least the genesis tip");
+        &best.block
+    }
+
+    // 从创世块走到 hash 这个块的整条分支 (创世块在前)
+    pub fn branch(&self, hash: &Digest) -> Vec<&Block<T>> {
+        let mut path = Vec::new();
+        let mut cursor = self.nodes.get(hash);
+        while let Some(node) = cursor {
+            path.push(&node.block);
+            cursor = if node.block.header.height == 0 { None } else { self.nodes.get(&node.block.header.prev_hash) };
+        }
+        path.reverse();
+        path
+    }
+}
@@ -0,0 +1,63 @@
+// src/s06_chain/gas.rs
+use std::fmt;
+
+/*
+Gas：执行要按步骤收费
+
+    比特币的脚本不能循环，执行时间有上限；以太坊的交易可以做任意计算，就必须给计算标价，
+    否则一笔"死循环"交易就能让全网节点卡住。
+        1. 每种操作有固定的 gas 价格 (下面的常量，数值参照以太坊的量级)
+        2. 交易自带 gas_limit：最多愿意消耗多少 gas；执行前先按 gas_limit × gas_price 预扣
+        3. 每执行一步先扣 gas，扣不动就停 (out of gas)：状态改动全部撤销，但 gas 照收
+        4. 没用完的 gas 退回发送者，用掉的给矿工
+
+    第 3 点是关键：如果 out of gas 不收费，攻击者就可以免费让矿工白算一通。
+*/
+
+// 每笔交易的固定开销 (验签、读写账户、nonce)
+pub const TX_BASE: u64 = 21_000;
+pub const TRANSFER: u64 = 9_000;
+// 新开一个存储槽比改写已有的贵得多：它永久增大了所有节点要保存的状态
+pub const STORE_NEW: u64 = 20_000;
+pub const STORE_UPDATE: u64 = 5_000;
+pub const LOAD: u64 = 800;
+pub const HASH_ROUND: u64 = 30;
+pub const LOG_BASE: u64 = 375;
+pub const LOG_BYTE: u64 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfGas {
+    pub needed: u64,
+    pub remaining: u64,
+}
+
+impl fmt::Display for OutOfGas {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "out of gas：这一步要 {}，只剩 {}", self.needed, self.remaining)
+    }
+}
+
+// 计量表：只增不减，超过 limit 的那一步不执行
+#[derive(Debug, Clone, Copy)]
+pub struct GasMeter {
+    pub limit: u64,
+    pub used: u64,
+}
+
+impl GasMeter {
+    pub fn new(limit: u64) -> Self {
+        GasMeter { limit, used: 0 }
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.limit - self.used
+    }
+
+    pub fn charge(&mut self, gas: u64) -> Result<(), OutOfGas> {
+        if gas > self.remaining() {
+            return Err(OutOfGas { needed: gas, remaining: self.remaining() });
+        }
+        self.used += gas;
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+// src/s06_chain/genesis.rs
+use std::collections::HashSet;
+use std::fs;
+
+use crate::common::json::{self, Json};
+use crate::s05_zk_lab::hash::{short_hex, Digest};
+
+use super::block::{merkle_root, Block, BlockHeader};
+use super::node::Node;
+use super::pow;
+use super::utxo::{OutPoint, Transaction, TxIn, TxOut, UtxoSet};
+
+/*
+创世配置：一条链的"出厂设置"写在 genesis.json 里，而不是散落在代码的常量中
+
+    chain_id    : 链的名字，防止把测试网的交易拿到主网重放
+    magic       : 4 字节网络魔数 (比特币主网是 f9beb4d9)，每条 P2P 消息的开头；对不上的连接直接断开
+    timestamp   : 创世块的时间戳 —— 必须写死，否则每个节点造出来的创世块哈希都不一样
+    difficulty  : 挖矿难度 (前导 0 比特数)
+    balances    : 初始分配，全部放进创世块的那一笔 coinbase
+
+    同一份文件 -> 同一个创世块哈希 -> 同一个网络。节点握手时比较 (chain_id, magic, 创世哈希)。
+    配置是人手写的，错误要一次全部报出来 (带上字段路径)，而不是改一个、跑一次、再报下一个。
+*/
+
+// 难度上限：再高玩具链就挖不动了
+pub const MAX_DIFFICULTY: u32 = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsensusConfig {
+    pub chain_id: String,
+    pub magic: [u8; 4],
+    pub difficulty: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenesisConfig {
+    pub consensus: ConsensusConfig,
+    pub timestamp: u64,
+    pub balances: Vec<(String, u64)>,
+}
+
+// 取字段时把出错的地方记下来，继续往下检查；最后一起返回
+struct Problems(Vec<String>);
+
+impl Problems {
+    fn take<T>(&mut self, result: Result<T, String>, path: &str) -> Option<T> {
+        result.map_err(|e| self.0.push(format!("{}: {}", path, e))).ok()
+    }
+
+    fn check(&mut self, ok: bool, message: impl FnOnce() -> String) {
+        if !ok {
+            self.0.push(message());
+        }
+    }
+}
+
+fn parse_magic(text: &str) -> Result<[u8; 4], String> {
+    let bytes = hex::decode(text).map_err(|_| format!("\"{}\" 不是十六进制", text))?;
+    bytes.try_into().map_err(|b: Vec<u8>| format!("必须正好 4 字节，实际 {} 字节", b.len()))
+}
+
+impl GenesisConfig {
+    // 解析 + 校验；Err 里是所有问题，每条一行
+    pub fn from_json(value: &Json) -> Result<Self, Vec<String>> {
+        let mut problems = Problems(Vec::new());
+        let chain_id = problems.take(value.field("chain_id", Json::as_str), "chain_id");
+        let magic = problems.take(value.field("magic", Json::as_str).and_then(parse_magic), "magic");
+        let timestamp = problems.take(value.field("timestamp", Json::as_u64), "timestamp");
+        let difficulty = problems.take(value.field("consensus", Some).and_then(|c| c.field("difficulty", Json::as_u64)), "consensus.difficulty");
+        let entries = problems.take(value.field("balances", Json::as_array), "balances").unwrap_or_default();
+
+        if let Some(id) = chain_id {
+            problems.check(!id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'), || format!("chain_id: \"{}\" 只能由字母、数字和 - 组成，且不能为空", id));
+        }
+        if let Some(d) = difficulty {
+            problems.check((1..=MAX_DIFFICULTY as u64).contains(&d), || format!("consensus.difficulty: {} 不在 1..={} 范围内", d, MAX_DIFFICULTY));
+        }
+        problems.check(value.get("balances").is_none() || !entries.is_empty(), || String::from("balances: 至少要有一个初始账户"));
+
+        let mut balances = Vec::new();
+        let mut owners = HashSet::new();
+        for (i, entry) in entries.iter().enumerate() {
+            let owner = problems.take(entry.field("owner", Json::as_str), &format!("balances[{}].owner", i));
+            let amount = problems.take(entry.field("amount", Json::as_u64), &format!("balances[{}].amount", i));
+            let (Some(owner), Some(amount)) = (owner, amount) else {
+                continue;
+            };
+            problems.check(amount > 0, || format!("balances[{}].amount: {} 的初始余额是 0，不用写进创世块", i, owner));
+            problems.check(owners.insert(owner), || format!("balances[{}].owner: {} 出现了两次", i, owner));
+            balances.push((owner.to_string(), amount));
+        }
+        let supply = balances.iter().try_fold(0u64, |sum, (_, amount)| sum.checked_add(*amount));
+        problems.check(supply.is_some(), || String::from("balances: 总发行量超过了 u64"));
+
+        match (chain_id, magic, timestamp, difficulty) {
+            (Some(chain_id), Some(magic), Some(timestamp), Some(difficulty)) if problems.0.is_empty() => Ok(GenesisConfig {
+                consensus: ConsensusConfig { chain_id: chain_id.to_string(), magic, difficulty: difficulty as u32 },
+                timestamp,
+                balances,
+            }),
+            _ => Err(problems.0),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Vec<String>> {
+        let value = json::parse(text).map_err(|e| vec![format!("JSON 语法错误: {}", e)])?;
+        Self::from_json(&value)
+    }
+
+    pub fn load(path: &str) -> Result<Self, Vec<String>> {
+        let text = fs::read_to_string(path).map_err(|e| vec![format!("读取 {} 失败: {}", path, e)])?;
+        Self::parse(&text)
+    }
+
+    pub fn total_supply(&self) -> u64 {
+        self.balances.iter().map(|(_, amount)| amount).sum()
+    }
+
+    // 创世块：一笔 coinbase，每个初始账户一个输出；状态根就是这些输出的树根。所有字段都来自配置，挖矿结果也就确定了
+    pub fn genesis_block(&self) -> Block<Transaction> {
+        let coinbase = Transaction {
+            inputs: vec![TxIn::unsigned(OutPoint { txid: [0u8; 32], index: 0 })],
+            outputs: self.balances.iter().map(|(owner, amount)| TxOut { owner: owner.clone(), amount: *amount }).collect(),
+            lock_time: 0,
+        };
+        let mut utxos = UtxoSet::new();
+        utxos.apply_tx(&coinbase);
+        let txs = vec![coinbase];
+        let header = BlockHeader {
+            height: 0,
+            prev_hash: [0u8; 32],
+            merkle_root: merkle_root(&txs),
+            state_root: utxos.state_root(),
+            timestamp: self.timestamp,
+            difficulty: self.consensus.difficulty,
+            nonce: 0,
+        };
+        Block { header: pow::mine(header).header, txs }
+    }
+
+    pub fn build_node(&self) -> Result<Node, String> {
+        Node::from_genesis(self.genesis_block(), self.consensus.difficulty)
+    }
+}
+
+// 握手：两个节点必须在同一条链上才交换区块
+pub fn handshake(a: (&ConsensusConfig, &Digest), b: (&ConsensusConfig, &Digest)) -> Result<(), String> {
+    let ((ca, ga), (cb, gb)) = (a, b);
+    if ca.magic != cb.magic {
+        return Err(format!("魔数不同 ({} vs {})，不是同一个网络的消息", hex::encode(ca.magic), hex::encode(cb.magic)));
+    }
+    if ca.chain_id != cb.chain_id {
+        return Err(format!("chain_id 不同 ({} vs {})", ca.chain_id, cb.chain_id));
+    }
+    if ga != gb {
+        return Err(format!("创世块不同 ({}.. vs {}..)，配置文件有差别", short_hex(ga), short_hex(gb)));
+    }
+    Ok(())
+}
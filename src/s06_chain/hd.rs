@@ -0,0 +1,152 @@
+// src/s06_chain/hd.rs
+use crate::s05_zk_lab::crypto::schnorr::Keypair;
+use crate::s05_zk_lab::hash::sha256;
+use crate::s05_zk_lab::math::curve::CurvePoint;
+use crate::s05_zk_lab::math::group::{Group, Scalar};
+
+/*
+分层确定性 (HD) 钥匙树：一个种子派生出整棵树的私钥 (BIP-32 的简化版)
+
+    主钥匙：I = SHA-256("toy-hd-seed" || 种子)
+        私钥 = I 的前 8 字节 (mod q)，链码 (chain code) = SHA-256(I || "chain")
+    子钥匙 i：I = SHA-256(链码 || 数据 || i)
+        普通子钥匙   (i < 2^31)：数据 = 父公钥        —— 只有公钥和链码也能算出子公钥
+        强化子钥匙 (i >= 2^31)：数据 = 0x00 || 父私钥 —— 必须有父私钥
+        子私钥 = 父私钥 + tweak (tweak = I 的前 8 字节)，子链码 = SHA-256(I || "chain")
+    普通派生的子公钥 = 父公钥 + tweak·G，和用子私钥算出来的一样：这就是"只看不花"的观察钱包。
+
+路径写法：m/44'/0'/0'/0/3，带 ' 的是强化派生。
+
+⚠️ 玩具曲线的私钥只有 1019 种，不同路径撞到同一把钥匙很常见；真实系统用 256 位的私钥和 HMAC-SHA512。
+*/
+
+pub const HARDENED: u32 = 1 << 31;
+
+#[derive(Debug, Clone)]
+pub struct ExtendedKey {
+    pub keys: Keypair<CurvePoint>,
+    pub chain_code: [u8; 32],
+    pub path: Vec<u32>,
+}
+
+// 观察钱包拿到的"扩展公钥" (xpub)：公钥 + 链码，没有私钥
+#[derive(Debug, Clone)]
+pub struct ExtendedPublicKey {
+    pub public: CurvePoint,
+    pub chain_code: [u8; 32],
+    pub path: Vec<u32>,
+}
+
+fn scalar_of(digest: &[u8; 32]) -> Scalar {
+    Scalar::new(u64::from_be_bytes(digest[..8].try_into().expect("digest has 32 bytes")))
+}
+
+fn next_chain_code(digest: &[u8; 32]) -> [u8; 32] {
+    let mut data = digest.to_vec();
+    data.extend_from_slice(b"chain");
+    sha256(&data)
+}
+
+// 子钥匙 index 的 I；hardened 时 data 是私钥，否则是公钥
+fn child_digest(chain_code: &[u8; 32], data: &[u8], index: u32) -> [u8; 32] {
+    let mut buf = chain_code.to_vec();
+    buf.extend_from_slice(data);
+    buf.extend_from_slice(&index.to_be_bytes());
+    sha256(&buf)
+}
+
+fn public_data(public: &CurvePoint) -> Vec<u8> {
+    public.encode().to_be_bytes().to_vec()
+}
+
+pub fn path_string(path: &[u32]) -> String {
+    let mut text = String::from("m");
+    for &index in path {
+        if index >= HARDENED {
+            text.push_str(&format!("/{}'", index - HARDENED));
+        } else {
+            text.push_str(&format!("/{}", index));
+        }
+    }
+    text
+}
+
+pub fn parse_path(text: &str) -> Result<Vec<u32>, String> {
+    let mut parts = text.trim().split('/');
+    if parts.next() != Some("m") {
+        return Err(format!("路径 {:?} 要以 m 开头", text));
+    }
+    parts
+        .map(|part| {
+            let (digits, hardened) = match part.strip_suffix('\'') {
+                Some(digits) => (digits, true),
+                None => (part, false),
+            };
+            let index: u32 = digits.parse().ok().filter(|i| *i < HARDENED).ok_or_else(|| format!("{:?} 不是 0..2^31 的整数", part))?;
+            Ok(if hardened { index + HARDENED } else { index })
+        })
+        .collect()
+}
+
+impl ExtendedKey {
+    pub fn master(seed: &[u8]) -> Self {
+        let mut data = b"toy-hd-seed".to_vec();
+        data.extend_from_slice(seed);
+        let digest = sha256(&data);
+        // 私钥不能是 0：抽到 0 就把种子再哈希一次
+        let mut secret = scalar_of(&digest);
+        let mut retry = digest;
+        while secret.is_zero() {
+            retry = sha256(&retry);
+            secret = scalar_of(&retry);
+        }
+        ExtendedKey { keys: Keypair::from_secret(secret), chain_code: next_chain_code(&retry), path: Vec::new() }
+    }
+
+    pub fn child(&self, index: u32) -> Self {
+        let data = if index >= HARDENED {
+            let mut data = vec![0u8];
+            data.extend_from_slice(&self.keys.secret.value().to_be_bytes());
+            data
+        } else {
+            public_data(&self.keys.public)
+        };
+        let digest = child_digest(&self.chain_code, &data, index);
+        let mut path = self.path.clone();
+        path.push(index);
+        // 子私钥是 0 的概率 1/q，BIP-32 规定跳过这个 index；这里不处理，保证和扩展公钥的派生结果一致
+        let secret = self.keys.secret + scalar_of(&digest);
+        ExtendedKey { keys: Keypair::from_secret(secret), chain_code: next_chain_code(&digest), path }
+    }
+
+    pub fn derive(&self, path: &[u32]) -> Self {
+        path.iter().fold(self.clone(), |key, &index| key.child(index))
+    }
+
+    pub fn neuter(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey { public: self.keys.public, chain_code: self.chain_code, path: self.path.clone() }
+    }
+}
+
+impl ExtendedPublicKey {
+    // 只能做普通派生；强化派生需要私钥
+    pub fn child(&self, index: u32) -> Result<Self, String> {
+        if index >= HARDENED {
+            return Err(format!("子钥匙 {}' 是强化派生，扩展公钥算不出来", index - HARDENED));
+        }
+        let digest = child_digest(&self.chain_code, &public_data(&self.public), index);
+        let mut path = self.path.clone();
+        path.push(index);
+        let public = self.public.op(CurvePoint::generator().pow(scalar_of(&digest)));
+        Ok(ExtendedPublicKey { public, chain_code: next_chain_code(&digest), path })
+    }
+
+    // ⚠️ 扩展公钥 + 任意一个普通子钥匙的私钥 -> 父私钥：父私钥 = 子私钥 - tweak，tweak 只依赖公开信息
+    pub fn recover_parent_secret(&self, index: u32, child_secret: Scalar) -> Result<Scalar, String> {
+        if index >= HARDENED {
+            return Err(String::from("强化子钥匙的 tweak 依赖父私钥，推不回去"));
+        }
+        let digest = child_digest(&self.chain_code, &public_data(&self.public), index);
+        Ok(child_secret - scalar_of(&digest))
+    }
+}
@@ -0,0 +1,102 @@
+// src/s06_chain/mempool.rs
+use crate::s05_zk_lab::hash::short_hex;
+
+use super::utxo::{Transaction, TxId, UtxoSet};
+
+/*
+交易池：还没打包进区块的交易
+
+    S01 ex02 的 Mempool 只是一个 Vec<Transaction>，add 什么都收。
+    真实节点在 add 时就要把关，垃圾交易不能进池子、更不能被转发给别的节点：
+        1. 签名：每个输入都由被花输出的主人签了名 (UtxoSet::verify_signatures)
+        2. 记账：输入存在、没花过、金额守恒 (UtxoSet::validate_tx)
+        3. 池内冲突：和池子里已有的交易花同一个输出时，按 RBF 规则决定替换还是拒绝
+    检查都是对照"当前链上的 UTXO 集合"做的，所以 add 要借用它。
+
+手续费替换 (Replace-By-Fee, RBF)：
+    交易发出去之后卡在池子里 (手续费给少了)，发送方可以用同样的输入重新签一笔、多付点手续费。
+    新交易可能同时和池子里好几笔冲突 (花了它们各自的一个输入)，要替换就得把它们全部踢掉，所以：
+        新交易的手续费 >= 被替换的那些交易的手续费之和 + MIN_FEE_BUMP
+    不满足就拒绝 —— 否则谁都能用同样 (或更低) 的手续费反复替换，让全网白白转发，矿工还少赚。
+    这也意味着：池子里的交易在上链之前都不算数，收款方不能把 0 确认当成已收到。
+*/
+
+// 替换时至少要多付的手续费 (比特币按新交易的大小 × incremental relay fee 算)
+pub const MIN_FEE_BUMP: u64 = 1;
+
+// 进池成功：这笔交易的手续费，和被它替换掉的交易
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accepted {
+    pub fee: u64,
+    pub replaced: Vec<TxId>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Mempool {
+    txs: Vec<Transaction>,
+}
+
+impl Mempool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.txs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.txs.is_empty()
+    }
+
+    pub fn contains(&self, txid: &TxId) -> bool {
+        self.txs.iter().any(|tx| tx.txid() == *txid)
+    }
+
+    pub fn txs(&self) -> &[Transaction] {
+        &self.txs
+    }
+
+    // 和池子里的交易冲突时按 RBF 规则：手续费够高就替换它们，不够就拒绝
+    pub fn add(&mut self, tx: Transaction, utxos: &UtxoSet) -> Result<Accepted, String> {
+        utxos.verify_signatures(&tx)?;
+        let fee = utxos.validate_tx(&tx)?;
+        if self.contains(&tx.txid()) {
+            return Err(format!("交易 {} 已经在池子里了", short_hex(&tx.txid())));
+        }
+        let conflicts = |pending: &Transaction| tx.inputs.iter().any(|i| pending.inputs.iter().any(|p| p.prev == i.prev));
+        let replaced: Vec<&Transaction> = self.txs.iter().filter(|pending| conflicts(pending)).collect();
+        if !replaced.is_empty() {
+            // 池子里的交易进池时都验过，对当前 UTXO 集合一定有效
+            let replaced_fees: u64 = replaced.iter().map(|pending| utxos.validate_tx(pending).unwrap_or(0)).sum();
+            if fee < replaced_fees + MIN_FEE_BUMP {
+                let ids: Vec<String> = replaced.iter().map(|pending| short_hex(&pending.txid())).collect();
+                return Err(format!(
+                    "和池子里的交易 {} 花同一个输出；替换要付至少 {} 手续费 (它们共 {} + {})，这笔只付了 {}",
+                    ids.join(", "),
+                    replaced_fees + MIN_FEE_BUMP,
+                    replaced_fees,
+                    MIN_FEE_BUMP,
+                    fee
+                ));
+            }
+        }
+        let replaced: Vec<TxId> = replaced.iter().map(|pending| pending.txid()).collect();
+        self.txs.retain(|pending| !conflicts(pending));
+        self.txs.push(tx);
+        Ok(Accepted { fee, replaced })
+    }
+
+    // 链变了 (出了新块、或者重组) 之后，把池子里的交易对照新的 UTXO 集合重新过一遍：
+    // 已经上链的、和新块冲突的都会被 add 拒绝，留下的仍然按原来的顺序排队
+    pub fn revalidate(&mut self, utxos: &UtxoSet) {
+        for tx in self.take_all() {
+            let _ = self.add(tx, utxos);
+        }
+    }
+
+    // 矿工打包：把池子里的交易全部拿走 (所有权移交给新区块)
+    pub fn take_all(&mut self) -> Vec<Transaction> {
+        std::mem::take(&mut self.txs)
+    }
+}
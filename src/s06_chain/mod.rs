@@ -0,0 +1,133 @@
+// src/s06_chain/mod.rs
+
+// 公共工具
+pub mod account;
+pub mod block;
+pub mod bridge;
+pub mod chain;
+pub mod da;
+pub mod encoding;
+pub mod explorer;
+pub mod fee_market;
+pub mod fork;
+pub mod genesis;
+pub mod gas;
+pub mod hd;
+pub mod mempool;
+pub mod multisig;
+pub mod node;
+pub mod orphan;
+pub mod persist;
+pub mod pos;
+pub mod pow;
+pub mod retarget;
+pub mod rollup;
+pub mod spv;
+pub mod trie;
+pub mod utxo;
+pub mod validation;
+pub mod wallet;
+
+// 练习
+pub mod ex01_pow;
+pub mod ex02_utxo;
+pub mod ex03_signatures;
+pub mod ex04_validation;
+pub mod ex05_fork_choice;
+pub mod ex06_reorg;
+pub mod ex07_gas;
+pub mod ex08_state_trie;
+pub mod ex09_light_client;
+pub mod ex10_pbft;
+pub mod ex11_pos;
+pub mod ex12_slashing;
+pub mod ex13_double_spend;
+pub mod ex14_explorer;
+pub mod ex15_genesis;
+pub mod ex16_rollup;
+pub mod ex17_fraud_proof;
+pub mod ex18_da_sampling;
+pub mod ex19_fee_market;
+pub mod ex20_bridge;
+pub mod ex21_retarget;
+pub mod ex22_orphans;
+pub mod ex23_rbf;
+pub mod ex24_multisig;
+pub mod ex25_hd_wallet;
+pub mod ex26_address_encoding;
+pub mod ex27_maturity_locktime;
+pub mod ex28_spv_wallet;
+
+use std::io;
+
+pub fn run_experiments() {
+    loop {
+        println!("\n--- ⛓️ S06 玩具区块链 (Toy Chain) ---");
+        println!("1. 区块、链与工作量证明 (PoW 挖矿)");
+        println!("2. UTXO 交易模型 (对照 S01 的账户余额)");
+        println!("3. 交易签名与交易池 (Mempool::add 验签)");
+        println!("4. 区块验证流水线 (BlockError: 故意弄坏的区块)");
+        println!("5. 分叉选择 (最长链 vs 最重链，两个挖矿线程赛跑)");
+        println!("6. 链重组 (回滚区块、撤销 UTXO、交易回到交易池)");
+        println!("7. Gas 计量 (账户模型：便宜 vs 昂贵的交易，out of gas 照样收费)");
+        println!("8. Merkle-Patricia 状态树 (state_root、存在/不存在证明，对照二叉 Merkle)");
+        println!("9. 轻节点 (线程间只传区块头，向全节点要 SPV 证明验证收款)");
+        println!("10. PBFT 共识 (验证者线程三阶段投票，容忍 f 个作恶者)");
+        println!("11. 权益证明 (按质押抽出块人，频率 vs 质押占比)");
+        println!("12. 罚没 (同一高度双签被抓，烧质押、踢出验证者集合)");
+        println!("13. 双花赛跑 (冲突交易进了两条分支，分叉选择淘汰其中一笔)");
+        println!("14. 区块浏览器 (区块列表、交易查询、地址余额历史，链存盘跨会话保留)");
+        println!("15. 创世配置 (从 genesis.json 启动链，配置错误一次报完)");
+        println!("16. Rollup 排序器 (线程收集 L2 交易、打包批次、承诺上 L1、包含证明)");
+        println!("17. 欺诈证明 (乐观 rollup：挑战窗口、二分定位错误的一步)");
+        println!("18. 数据可用性抽样 (纠删码扩展区块数据，轻客户端随机抽样发现扣留)");
+        println!("19. EIP-1559 手续费市场 (base fee 随区块满空调整，出价低的交易在池里等)");
+        println!("20. 跨链桥 (两条链各一个线程，中继者提交锁定的 Merkle 证明，B 上铸造包装币)");
+        println!("21. 难度调整 (算力忽高忽低，比特币 2016 块窗口 vs 每块 EMA 的出块间隔)");
+        println!("22. 孤块池 (区块乱序到达，按缺的父块归类，父块一到整棵子树接上)");
+        println!("23. 手续费替换 (RBF：加价替换卡住的交易，手续费不够就拒绝)");
+        println!("24. 多签钱包 (3-of-5：保管人线程通过 channel 回传签名，拒绝重复和无效签名)");
+        println!("25. HD 钱包 (一个种子派生钥匙树，观察钱包只用扩展公钥生成收款地址)");
+        println!("26. 地址编码 (Base58Check 校验和、Bech32，抄错一个字符就能发现)");
+        println!("27. coinbase 成熟期与锁定时间 (刚挖到的币不能马上花，交易约定最早上链的高度)");
+        println!("28. SPV 钱包 (只存区块头和自己的付款证明，重组后自动发现付款失效)");
+        println!("0. 返回主菜单");
+        println!("请输入练习编号:");
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("读取失败");
+
+        match input.trim() {
+            "1" => ex01_pow::run(),
+            "2" => ex02_utxo::run(),
+            "3" => ex03_signatures::run(),
+            "4" => ex04_validation::run(),
+            "5" => ex05_fork_choice::run(),
+            "6" => ex06_reorg::run(),
+            "7" => ex07_gas::run(),
+            "8" => ex08_state_trie::run(),
+            "9" => ex09_light_client::run(),
+            "10" => ex10_pbft::run(),
+            "11" => ex11_pos::run(),
+            "12" => ex12_slashing::run(),
+            "13" => ex13_double_spend::run(),
+            "14" => ex14_explorer::run(),
+            "15" => ex15_genesis::run(),
+            "16" => ex16_rollup::run(),
+            "17" => ex17_fraud_proof::run(),
+            "18" => ex18_da_sampling::run(),
+            "19" => ex19_fee_market::run(),
+            "20" => ex20_bridge::run(),
+            "21" => ex21_retarget::run(),
+            "22" => ex22_orphans::run(),
+            "23" => ex23_rbf::run(),
+            "24" => ex24_multisig::run(),
+            "25" => ex25_hd_wallet::run(),
+            "26" => ex26_address_encoding::run(),
+            "27" => ex27_maturity_locktime::run(),
+            "28" => ex28_spv_wallet::run(),
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// src/s06_chain/multisig.rs
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::crypto::schnorr::{self, Keypair, Signature};
+use crate::s05_zk_lab::math::curve::CurvePoint;
+
+/*
+m-of-n 多签钱包：n 把公钥共同管一笔钱，一笔支出要凑齐其中 m 个签名才能执行
+
+    提案 (Proposal)：付给谁、多少钱、钱包当前的 nonce。签名签的是提案的编码。
+    收集 (PendingSpend::collect)：每收到一个签名当场检查
+        签名者必须在 n 把公钥里、同一个签名者只算一次、签名必须对得上这份提案
+    执行 (MultisigWallet::execute)：够 m 个就扣钱，nonce + 1
+        nonce 防重放：同一份提案 (连同它收集到的签名) 执行过一次，nonce 就变了，再交一次不会被接受
+*/
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proposal {
+    pub nonce: u64,
+    pub to: String,
+    pub amount: u64,
+}
+
+impl Proposal {
+    pub fn message(&self) -> Vec<u8> {
+        format!("multisig|{}|{}|{}", self.nonce, self.to, self.amount).into_bytes()
+    }
+}
+
+impl fmt::Display for Proposal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{} 付 {} 给 {}", self.nonce, self.amount, self.to)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Approval {
+    pub signer: CurvePoint,
+    pub signature: Signature<CurvePoint>,
+}
+
+impl Approval {
+    pub fn sign(keys: &Keypair<CurvePoint>, proposal: &Proposal, rng: &mut SimpleRng) -> Self {
+        Approval { signer: keys.public, signature: schnorr::sign(keys, &proposal.message(), rng) }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalError {
+    UnknownSigner,
+    Duplicate { index: usize },
+    BadSignature { index: usize },
+    AlreadyExecuted,
+}
+
+impl fmt::Display for ApprovalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ApprovalError::UnknownSigner => write!(f, "签名者不在多签名单里"),
+            ApprovalError::Duplicate { index } => write!(f, "签名者 #{} 已经签过了，不重复计数", index),
+            ApprovalError::BadSignature { index } => write!(f, "签名者 #{} 的签名和这份提案对不上", index),
+            ApprovalError::AlreadyExecuted => write!(f, "提案已经执行了"),
+        }
+    }
+}
+
+pub struct MultisigWallet {
+    signers: Vec<CurvePoint>,
+    threshold: usize,
+    pub balance: u64,
+    nonce: u64,
+}
+
+impl MultisigWallet {
+    pub fn new(signers: Vec<CurvePoint>, threshold: usize, balance: u64) -> Result<Self, String> {
+        if threshold == 0 || threshold > signers.len() {
+            return Err(format!("门槛 {} 不在 1..={}", threshold, signers.len()));
+        }
+        if signers.iter().enumerate().any(|(i, s)| signers[..i].contains(s)) {
+            return Err(String::from("名单里有重复的公钥 (同一把钥匙会被算两次)"));
+        }
+        Ok(MultisigWallet { signers, threshold, balance, nonce: 0 })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn signer_count(&self) -> usize {
+        self.signers.len()
+    }
+
+    pub fn signer_index(&self, public: &CurvePoint) -> Option<usize> {
+        self.signers.iter().position(|s| s == public)
+    }
+
+    pub fn propose(&self, to: &str, amount: u64) -> Proposal {
+        Proposal { nonce: self.nonce, to: to.to_string(), amount }
+    }
+
+    pub fn execute(&mut self, pending: &mut PendingSpend) -> Result<(), String> {
+        if pending.executed {
+            return Err(ApprovalError::AlreadyExecuted.to_string());
+        }
+        let proposal = &pending.proposal;
+        if proposal.nonce != self.nonce {
+            return Err(format!("提案的 nonce 是 {}，钱包当前是 {} (重放或者过期的提案)", proposal.nonce, self.nonce));
+        }
+        if pending.approvals.len() < self.threshold {
+            return Err(format!("只有 {} 个签名，门槛是 {}", pending.approvals.len(), self.threshold));
+        }
+        // collect 时已经验过；执行前再验一遍，不信任中间状态
+        let message = proposal.message();
+        if let Some(index) = pending.approvals.iter().find(|(i, sig)| !schnorr::verify(self.signers[**i], &message, sig)).map(|(i, _)| *i) {
+            return Err(ApprovalError::BadSignature { index }.to_string());
+        }
+        if self.balance < proposal.amount {
+            return Err(format!("余额 {} 不够付 {}", self.balance, proposal.amount));
+        }
+        self.balance -= proposal.amount;
+        self.nonce += 1;
+        pending.executed = true;
+        Ok(())
+    }
+}
+
+// 一份提案和它已经收集到的签名 (签名者下标 -> 签名)
+pub struct PendingSpend {
+    pub proposal: Proposal,
+    approvals: BTreeMap<usize, Signature<CurvePoint>>,
+    executed: bool,
+}
+
+impl PendingSpend {
+    pub fn new(proposal: Proposal) -> Self {
+        PendingSpend { proposal, approvals: BTreeMap::new(), executed: false }
+    }
+
+    // 成功时返回目前有效签名的个数
+    pub fn collect(&mut self, wallet: &MultisigWallet, approval: &Approval) -> Result<usize, ApprovalError> {
+        if self.executed {
+            return Err(ApprovalError::AlreadyExecuted);
+        }
+        let index = wallet.signer_index(&approval.signer).ok_or(ApprovalError::UnknownSigner)?;
+        if self.approvals.contains_key(&index) {
+            return Err(ApprovalError::Duplicate { index });
+        }
+        if !schnorr::verify(approval.signer, &self.proposal.message(), &approval.signature) {
+            return Err(ApprovalError::BadSignature { index });
+        }
+        self.approvals.insert(index, approval.signature);
+        Ok(self.approvals.len())
+    }
+
+    pub fn signers(&self) -> Vec<usize> {
+        self.approvals.keys().copied().collect()
+    }
+}
@@ -0,0 +1,275 @@
+// src/s06_chain/node.rs
+use crate::s05_zk_lab::hash::Digest;
+
+use super::block::Block;
+use super::chain::Chain;
+use super::mempool::{Accepted, Mempool};
+use super::pow;
+use super::utxo::{BlockUndo, Transaction, TxId, UtxoSet, BLOCK_REWARD};
+use super::validation::{check_header, check_transactions, BlockError};
+
+/*
+全节点的本地状态：主链 + UTXO 集合 + 每个区块的撤销数据 + 交易池
+
+    四样东西必须始终一致：UTXO 集合 = 从创世块执行到 chain 的最高块的结果，
+    undo[i] 是 chain.blocks[i] 的撤销数据，交易池里的交易对当前 UTXO 集合都有效。
+    所以字段都不公开，只能通过 submit / connect / disconnect_tip / revert_to / reorg 一起变。
+
+状态根：每个区块头都写着"执行完本块之后 UTXO 集合的 Patricia 树根"
+    矿工挖矿前先在副本上执行一遍，把根写进区块头；别的节点接块时自己执行，根对不上就拒绝。
+    于是"高度 h 的状态"有了一个 32 字节的指纹，写在高度 h 的区块头里。
+
+快照 (snapshot / revert_to)：
+    快照只是 (高度, 区块哈希, 状态根) 三个字段 —— 不用复制 UTXO 集合，因为撤销数据已经记下了每个块改了什么。
+    revert_to(h) 从最高块往回按撤销数据拆到高度 h，只碰这些块改过的输出，不用从创世块重放；
+    拆完之后的状态根必须等于高度 h 的区块头里写的那个；对不上就把拆下来的块接回去再报错，节点不会停在半路。
+
+重组 (reorg)：收到一条从历史某处分出去、而且更长的分支
+    1. 从最高块往回 disconnect，直到分叉点 —— 区块的所有权从 chain 交还给 reorg
+    2. 依次 connect 新分支的区块；任何一个不合法就全部撤回，恢复旧链
+    3. 旧区块里的交易：新链上已经有的丢掉；其余的重新交给交易池，池子会拒绝和新链冲突的 (被双花了)
+       coinbase 随旧区块一起作废
+*/
+
+#[derive(Debug)]
+pub struct ReorgReport {
+    pub fork_height: u64,
+    // 回滚的区块 (按高度从低到高)：高度、哈希、撤销数据
+    pub rolled_back: Vec<(u64, Digest, BlockUndo)>,
+    pub connected: Vec<(u64, Digest)>,
+    // 旧链上的交易的去向
+    pub reinjected: Vec<TxId>,
+    pub already_confirmed: Vec<TxId>,
+    pub dropped: Vec<(TxId, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot {
+    pub height: u64,
+    pub tip: Digest,
+    pub state_root: Digest,
+}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    chain: Chain<Transaction>,
+    utxos: UtxoSet,
+    undo: Vec<BlockUndo>,
+    mempool: Mempool,
+}
+
+impl Node {
+    pub fn new(difficulty: u32, genesis_txs: Vec<Transaction>) -> Result<Self, String> {
+        let mut genesis = Chain::new(difficulty, genesis_txs).blocks.remove(0);
+        genesis.header.state_root = UtxoSet::new().root_after(&genesis).map_err(|e| format!("创世块: {}", e))?;
+        genesis.header = pow::mine(genesis.header).header;
+        Self::from_genesis(genesis, difficulty)
+    }
+
+    // 从一个现成的创世块启动 (genesis.rs 按配置文件造的)：同一份配置 -> 同一个创世哈希 -> 同一个网络
+    pub fn from_genesis(genesis: Block<Transaction>, difficulty: u32) -> Result<Self, String> {
+        let mut utxos = UtxoSet::new();
+        let undo = utxos.connect_block(&genesis).map_err(|e| format!("创世块: {}", e))?;
+        let root = utxos.state_root();
+        if root != genesis.header.state_root {
+            return Err(format!("创世块: {}", BlockError::StateRootMismatch { height: 0, claimed: genesis.header.state_root, computed: root }));
+        }
+        let chain = Chain { blocks: vec![genesis], difficulty };
+        Ok(Node { chain, utxos, undo: vec![undo], mempool: Mempool::new() })
+    }
+
+    // coinbase 成熟期是共识规则，整个网络要一致；创世块的 coinbase 也算在内
+    pub fn with_coinbase_maturity(mut self, blocks: u64) -> Self {
+        self.utxos = self.utxos.with_maturity(blocks);
+        self
+    }
+
+    // 从存档恢复 (persist.rs)：创世块之后的每个块重新走一遍 connect，交易和签名全部重新验证
+    pub fn from_chain(chain: Chain<Transaction>) -> Result<Self, String> {
+        let difficulty = chain.difficulty;
+        let mut blocks = chain.blocks.into_iter();
+        let genesis = blocks.next().ok_or("链里没有创世块")?;
+        let mut node = Self::from_genesis(genesis, difficulty)?;
+        for block in blocks {
+            let height = block.header.height;
+            node.connect(block).map_err(|e| format!("区块 #{}: {}", height, e))?;
+        }
+        Ok(node)
+    }
+
+    pub fn chain(&self) -> &Chain<Transaction> {
+        &self.chain
+    }
+
+    pub fn utxos(&self) -> &UtxoSet {
+        &self.utxos
+    }
+
+    pub fn mempool(&self) -> &Mempool {
+        &self.mempool
+    }
+
+    // 交易还在本节点的交易池里排队 (没上链)
+    pub fn is_pending(&self, txid: &TxId) -> bool {
+        self.mempool.contains(txid)
+    }
+
+    // 交易进池，对照的是本节点当前的 UTXO 集合；冲突的交易按 RBF 规则替换
+    pub fn submit(&mut self, tx: Transaction) -> Result<Accepted, String> {
+        self.mempool.add(tx, &self.utxos)
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        let tip = self.chain.tip();
+        Snapshot { height: tip.header.height, tip: tip.hash(), state_root: self.utxos.state_root() }
+    }
+
+    // 不碰交易池：reorg 要等整条新分支都接上之后才统一处理交易池
+    fn connect_block(&mut self, block: Block<Transaction>) -> Result<(), BlockError> {
+        check_header(Some(self.chain.tip()), &block, self.chain.difficulty)?;
+        check_transactions(&self.utxos, &block)?;
+        let undo = self.utxos.connect_block(&block).expect("checked by check_transactions");
+        let root = self.utxos.state_root();
+        if root != block.header.state_root {
+            self.utxos.disconnect_block(&undo);
+            return Err(BlockError::StateRootMismatch { height: block.header.height, claimed: block.header.state_root, computed: root });
+        }
+        self.chain.blocks.push(block);
+        self.undo.push(undo);
+        Ok(())
+    }
+
+    pub fn connect(&mut self, block: Block<Transaction>) -> Result<(), BlockError> {
+        self.connect_block(block)?;
+        self.mempool.revalidate(&self.utxos);
+        Ok(())
+    }
+
+    // 打包交易池里的全部交易，coinbase 给 miner (地址)，挖矿并接到自己的链上；返回区块的副本用来广播
+    pub fn mine(&mut self, miner: &str) -> Result<Block<Transaction>, BlockError> {
+        let height = self.chain.height() + 1;
+        let overflow = |what| BlockError::AmountOverflow { height, what };
+        // 先算奖励再取交易：溢出时直接报错，交易池原样留着
+        let pending = self.mempool.txs().iter();
+        let fees = pending.map(|tx| self.utxos.validate_tx(tx).unwrap_or(0)).try_fold(0u64, u64::checked_add).ok_or(overflow("手续费总额"))?;
+        let reward = BLOCK_REWARD.checked_add(fees).ok_or(overflow("出块奖励 + 手续费"))?;
+        let mut txs = self.mempool.take_all();
+        txs.insert(0, Transaction::coinbase(miner, reward, height));
+        let mut block = self.chain.candidate(txs);
+        // 执行失败的话不写根，connect 会报出具体是哪笔交易的问题
+        if let Ok(root) = self.utxos.root_after(&block) {
+            block.header.state_root = root;
+        }
+        block.header = pow::mine(block.header).header;
+        self.connect(block.clone())?;
+        Ok(block)
+    }
+
+    // 回滚最高块 (创世块不能回滚)：区块连同里面的交易一起还给调用方
+    pub fn disconnect_tip(&mut self) -> Option<(Block<Transaction>, BlockUndo)> {
+        if self.chain.height() == 0 {
+            return None;
+        }
+        let block = self.chain.blocks.pop()?;
+        let undo = self.undo.pop()?;
+        self.utxos.disconnect_block(&undo);
+        Some((block, undo))
+    }
+
+    // 拆到高度 height，返回拆下来的区块 (从高到低)；不碰交易池
+    fn rewind(&mut self, height: u64) -> Vec<(Block<Transaction>, BlockUndo)> {
+        let mut removed = Vec::new();
+        while self.chain.height() > height {
+            removed.extend(self.disconnect_tip());
+        }
+        removed
+    }
+
+    // 回到高度 height 时的状态：拆下来的区块里的交易不回交易池 (那是 reorg 的事)，池子里依赖它们的交易一起作废
+    pub fn revert_to(&mut self, height: u64) -> Result<Vec<(Block<Transaction>, BlockUndo)>, String> {
+        if height > self.chain.height() {
+            return Err(format!("高度 {} 还没到 (当前高度 {})", height, self.chain.height()));
+        }
+        let removed = self.rewind(height);
+        let snapshot = self.snapshot();
+        let claimed = self.chain.tip().header.state_root;
+        if snapshot.state_root != claimed {
+            // 回滚出来的状态对不上区块头：把拆下来的块按原顺序接回去，节点保持调用前的样子 (交易池还没动过)
+            // 不走 connect_block —— 状态已经和区块头对不上，再校验一遍状态根只会失败；
+            // UtxoSet::connect_block 是 disconnect_block 的逆操作，原样重做一遍就回到了调用前的 UTXO 集合
+            for (block, _) in removed.into_iter().rev() {
+                let undo = self.utxos.connect_block(&block).expect("removed blocks were valid on this chain");
+                self.chain.blocks.push(block);
+                self.undo.push(undo);
+            }
+            return Err(BlockError::StateRootMismatch { height, claimed, computed: snapshot.state_root }.to_string());
+        }
+        self.mempool.revalidate(&self.utxos);
+        Ok(removed)
+    }
+
+    // branch 从分叉点的下一个块开始，按高度排好；只有比当前主链长才切换
+    pub fn reorg(&mut self, branch: Vec<Block<Transaction>>) -> Result<ReorgReport, String> {
+        let Some(first) = branch.first() else {
+            return Err(String::from("新分支是空的"));
+        };
+        let Some(fork) = self.chain.blocks.iter().position(|b| b.hash() == first.header.prev_hash) else {
+            return Err(BlockError::UnknownParent { height: first.header.height, prev_hash: first.header.prev_hash }.to_string());
+        };
+        let fork_height = fork as u64;
+        let new_height = fork_height + branch.len() as u64;
+        if new_height <= self.chain.height() {
+            return Err(format!("新分支到高度 {}，不比当前主链 (高度 {}) 长，不切换", new_height, self.chain.height()));
+        }
+
+        let old = self.rewind(fork_height);
+        let mut connected = Vec::new();
+        for block in branch {
+            let (height, hash) = (block.header.height, block.hash());
+            if let Err(e) = self.connect_block(block) {
+                // 全部撤回：先拆掉新分支已经接上的块，再按原顺序把旧块接回去
+                self.rewind(fork_height);
+                for (block, _) in old.into_iter().rev() {
+                    self.connect_block(block).expect("old blocks were valid on this chain");
+                }
+                return Err(format!("新分支的区块 #{} 不合法，保持旧链: {}", height, e));
+            }
+            connected.push((height, hash));
+        }
+
+        // 旧区块里的交易按原来的上链顺序交回交易池，排在池子里原有的交易前面
+        let confirmed: Vec<TxId> =
+            self.chain.blocks[fork + 1..].iter().flat_map(|b| b.txs.iter().map(|tx| tx.txid())).collect();
+        let pending = self.mempool.take_all();
+        let mut report = ReorgReport {
+            fork_height,
+            rolled_back: Vec::new(),
+            connected,
+            reinjected: Vec::new(),
+            already_confirmed: Vec::new(),
+            dropped: Vec::new(),
+        };
+        let mut orphaned = Vec::new();
+        for (block, undo) in old.into_iter().rev() {
+            report.rolled_back.push((block.header.height, block.hash(), undo));
+            orphaned.extend(block.txs);
+        }
+        for tx in orphaned {
+            let txid = tx.txid();
+            if tx.is_coinbase() {
+                report.dropped.push((txid, String::from("coinbase 随旧区块一起作废")));
+            } else if confirmed.contains(&txid) {
+                report.already_confirmed.push(txid);
+            } else {
+                match self.mempool.add(tx, &self.utxos) {
+                    Ok(_) => report.reinjected.push(txid),
+                    Err(e) => report.dropped.push((txid, e)),
+                }
+            }
+        }
+        for tx in pending {
+            let _ = self.mempool.add(tx, &self.utxos);
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,170 @@
+// src/s06_chain/orphan.rs
+use std::collections::{HashMap, VecDeque};
+
+use crate::s05_zk_lab::ex01_merkle::MerkleLeaf;
+use crate::s05_zk_lab::hash::Digest;
+
+use super::block::Block;
+use super::fork::BlockTree;
+use super::pow;
+use super::validation::BlockError;
+
+/*
+孤块池 (orphan pool)：子块比父块先到怎么办
+
+    网络不保证顺序：节点可能先收到 #5，再收到 #4。BlockTree::insert 要求父块已经在树里，
+    #5 只能先放一边 —— 这样的块叫孤块。孤块按"缺的父块哈希"归类：
+        by_parent : 缺的父块 -> 在等它的孤块 (同一个父块可以有好几个子块，分叉)
+        parent_of : 孤块自己的哈希 -> 它缺的父块 (查重、淘汰时反查用)
+    父块到了之后，by_parent.remove 把等它的那一组孤块整个拿出来 (所有权从池子转给调用方)，
+    逐个 insert 进树 (所有权再转给树)；每接上一个，又去看有没有等它的 —— 一整棵子树一层层接上。
+
+    孤块没法完整验证 (不知道父块)，但工作量不需要父块就能查：没挖过的块直接拒绝，
+    否则谁都能随手造一堆假孤块把内存撑爆。池子还有容量上限，满了就扔最早进来的。
+    父块被拒绝时，等它的孤块 (以及它们的子孙) 也全部作废。
+*/
+
+#[derive(Debug)]
+pub enum Arrival {
+    // 接上了：这个块自己 + 因为它接上的孤块 (按接上的顺序)；
+    // rejected 是接的时候验证失败的孤块，discarded 是它们的子孙
+    Connected { connected: Vec<(u64, Digest)>, rejected: Vec<(Digest, BlockError)>, discarded: Vec<Digest> },
+    // 放进孤块池等父块；池子满了会挤掉最早的一个
+    Orphaned { missing: Digest, evicted: Option<Digest> },
+    // 这个块本身不合法，在等它的孤块一起作废
+    Rejected { error: BlockError, discarded: Vec<Digest> },
+    // 树里或池里已经有了
+    Duplicate,
+}
+
+#[derive(Debug, Clone)]
+pub struct OrphanPool<T: MerkleLeaf = String> {
+    by_parent: HashMap<Digest, Vec<Block<T>>>,
+    parent_of: HashMap<Digest, Digest>,
+    // 进池顺序，淘汰用；已经离开池子的哈希懒得删，淘汰时跳过
+    order: VecDeque<Digest>,
+    capacity: usize,
+}
+
+impl<T: MerkleLeaf> OrphanPool<T> {
+    pub fn new(capacity: usize) -> Self {
+        OrphanPool { by_parent: HashMap::new(), parent_of: HashMap::new(), order: VecDeque::new(), capacity }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent_of.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent_of.is_empty()
+    }
+
+    // 该向别的节点要的块：孤块缺的父块里，本身不在池里的那些 (在池里的只是在等更早的块)
+    pub fn wanted(&self) -> Vec<Digest> {
+        let mut wanted: Vec<Digest> = self.by_parent.keys().filter(|parent| !self.parent_of.contains_key(*parent)).copied().collect();
+        wanted.sort();
+        wanted
+    }
+
+    fn add(&mut self, block: Block<T>) -> Option<Digest> {
+        let mut evicted = None;
+        if self.len() >= self.capacity {
+            while let Some(oldest) = self.order.pop_front() {
+                if self.remove(&oldest) {
+                    evicted = Some(oldest);
+                    break;
+                }
+            }
+        }
+        let (hash, parent) = (block.hash(), block.header.prev_hash);
+        self.parent_of.insert(hash, parent);
+        self.order.push_back(hash);
+        self.by_parent.entry(parent).or_default().push(block);
+        evicted
+    }
+
+    // 只拿掉这一个孤块；它自己的子块还留在池里，等它再来一次
+    fn remove(&mut self, hash: &Digest) -> bool {
+        let Some(parent) = self.parent_of.remove(hash) else {
+            return false;
+        };
+        if let Some(siblings) = self.by_parent.get_mut(&parent) {
+            siblings.retain(|b| b.hash() != *hash);
+            if siblings.is_empty() {
+                self.by_parent.remove(&parent);
+            }
+        }
+        true
+    }
+
+    // 把等 parent 的孤块整组拿出来，所有权交给调用方
+    fn take_children(&mut self, parent: &Digest) -> Vec<Block<T>> {
+        let children = self.by_parent.remove(parent).unwrap_or_default();
+        for child in &children {
+            self.parent_of.remove(&child.hash());
+        }
+        children
+    }
+
+    // hash 作废了：等它的孤块和它们的子孙一起扔掉
+    fn discard_descendants(&mut self, hash: Digest) -> Vec<Digest> {
+        let mut discarded = Vec::new();
+        let mut stack = vec![hash];
+        while let Some(parent) = stack.pop() {
+            for child in self.take_children(&parent) {
+                let child_hash = child.hash();
+                discarded.push(child_hash);
+                stack.push(child_hash);
+            }
+        }
+        discarded
+    }
+
+    // 收到一个块：能接就接 (连带接上等它的孤块)，父块没见过就进池
+    pub fn receive(&mut self, tree: &mut BlockTree<T>, block: Block<T>) -> Arrival {
+        let hash = block.hash();
+        if tree.contains(&hash) || self.parent_of.contains_key(&hash) {
+            return Arrival::Duplicate;
+        }
+        let h = &block.header;
+        if !tree.contains(&h.prev_hash) {
+            // 不知道父块也能查的：难度够不够、哈希满不满足难度
+            if h.difficulty < tree.min_difficulty {
+                let error = BlockError::WrongDifficulty { height: h.height, expected: tree.min_difficulty, found: h.difficulty };
+                return Arrival::Rejected { error, discarded: self.discard_descendants(hash) };
+            }
+            if !pow::meets_target(h) {
+                let error = BlockError::InsufficientWork { height: h.height, hash, zero_bits: pow::leading_zero_bits(&hash), required: h.difficulty };
+                return Arrival::Rejected { error, discarded: self.discard_descendants(hash) };
+            }
+            let missing = h.prev_hash;
+            let evicted = self.add(block);
+            return Arrival::Orphaned { missing, evicted };
+        }
+
+        let height = h.height;
+        if let Err(error) = tree.insert(block) {
+            return Arrival::Rejected { error, discarded: self.discard_descendants(hash) };
+        }
+        let mut connected = vec![(height, hash)];
+        let (mut rejected, mut discarded) = (Vec::new(), Vec::new());
+        // 广度优先：先接完同一层 (分叉的兄弟块)，再接下一层
+        let mut queue = VecDeque::from([hash]);
+        while let Some(parent) = queue.pop_front() {
+            for child in self.take_children(&parent) {
+                let (height, child_hash) = (child.header.height, child.hash());
+                match tree.insert(child) {
+                    Ok(()) => {
+                        connected.push((height, child_hash));
+                        queue.push_back(child_hash);
+                    }
+                    Err(error) => {
+                        rejected.push((child_hash, error));
+                        discarded.extend(self.discard_descendants(child_hash));
+                    }
+                }
+            }
+        }
+        Arrival::Connected { connected, rejected, discarded }
+    }
+}
@@ -0,0 +1,184 @@
+// src/s06_chain/persist.rs
+use std::fs;
+
+use crate::common::json::{self, Json};
+use crate::s05_zk_lab::crypto::schnorr::Signature;
+use crate::s05_zk_lab::ex01_merkle::MerkleLeaf;
+use crate::s05_zk_lab::hash::Digest;
+use crate::s05_zk_lab::math::curve::CurvePoint;
+use crate::s05_zk_lab::math::group::{Group, Scalar};
+
+use super::block::{Block, BlockHeader};
+use super::chain::Chain;
+use super::utxo::{OutPoint, Transaction, TxIn, TxOut, Witness};
+
+/*
+链的持久化：把 Chain 存成 JSON 文件，下次启动读回来
+
+    格式和 S05 ex01 的 MerkleTree::to_json 一个思路：用 common::json，哈希存成 hex，整数原样存 (Json::Number 是 i128，u64 的金额、nonce 不会被截断)。
+        {"format": "toy-chain-v1", "difficulty": 8, "blocks": [{"header": {...}, "txs": [...]}, ...]}
+    交易类型是泛型，只要实现了 JsonCodec 就能存；String 和 UTXO 的 Transaction 都实现了。
+
+    文件不可信：读回来之后跑一遍 Chain::validate (链接、PoW、Merkle 根)，
+    改过任何一笔交易或区块头都会被发现。写文件时先写临时文件再 rename，写到一半崩溃不会留下半个文件。
+*/
+
+const FORMAT: &str = "toy-chain-v1";
+
+pub trait JsonCodec: Sized {
+    fn to_json(&self) -> Json;
+    fn from_json(value: &Json) -> Result<Self, String>;
+}
+
+fn digest_from_hex(text: &str) -> Result<Digest, String> {
+    let bytes = hex::decode(text).map_err(|_| format!("\"{}\" 不是十六进制", text))?;
+    bytes.try_into().map_err(|b: Vec<u8>| format!("哈希应该是 32 字节，实际 {} 字节", b.len()))
+}
+
+fn point_from_json(value: &Json, key: &str) -> Result<CurvePoint, String> {
+    let encoded = value.field(key, Json::as_u64)?;
+    CurvePoint::decode(encoded).ok_or_else(|| format!("\"{}\": {} 不是曲线上的点", key, encoded))
+}
+
+impl JsonCodec for String {
+    fn to_json(&self) -> Json {
+        Json::Str(self.clone())
+    }
+
+    fn from_json(value: &Json) -> Result<Self, String> {
+        value.as_str().map(str::to_string).ok_or_else(|| String::from("交易应该是字符串"))
+    }
+}
+
+impl JsonCodec for BlockHeader {
+    fn to_json(&self) -> Json {
+        Json::object(vec![
+            ("height", Json::Number(self.height.into())),
+            ("prev_hash", Json::Str(hex::encode(self.prev_hash))),
+            ("merkle_root", Json::Str(self.merkle_root.clone())),
+            ("state_root", Json::Str(hex::encode(self.state_root))),
+            ("timestamp", Json::Number(self.timestamp.into())),
+            ("difficulty", Json::Number(self.difficulty.into())),
+            ("nonce", Json::Number(self.nonce.into())),
+        ])
+    }
+
+    fn from_json(value: &Json) -> Result<Self, String> {
+        Ok(BlockHeader {
+            height: value.field("height", Json::as_u64)?,
+            prev_hash: digest_from_hex(value.field("prev_hash", Json::as_str)?)?,
+            merkle_root: value.field("merkle_root", Json::as_str)?.to_string(),
+            state_root: digest_from_hex(value.field("state_root", Json::as_str)?)?,
+            timestamp: value.field("timestamp", Json::as_u64)?,
+            difficulty: value.field("difficulty", Json::as_u32)?,
+            nonce: value.field("nonce", Json::as_u64)?,
+        })
+    }
+}
+
+// 签名和公钥存 Group::encode 的压缩编码，读回来用 CurvePoint::decode 还原
+impl JsonCodec for Transaction {
+    fn to_json(&self) -> Json {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| {
+                let witness = match &input.witness {
+                    Some(w) => Json::object(vec![
+                        ("public", Json::Number(w.public.encode().into())),
+                        ("r", Json::Number(w.signature.r.encode().into())),
+                        ("s", Json::Number(w.signature.s.value().into())),
+                    ]),
+                    None => Json::Null,
+                };
+                Json::object(vec![
+                    ("txid", Json::Str(hex::encode(input.prev.txid))),
+                    ("index", Json::Number(input.prev.index.into())),
+                    ("witness", witness),
+                ])
+            })
+            .collect();
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|out| Json::object(vec![("owner", Json::Str(out.owner.clone())), ("amount", Json::Number(out.amount.into()))]))
+            .collect();
+        Json::object(vec![("inputs", Json::Array(inputs)), ("outputs", Json::Array(outputs)), ("lock_time", Json::Number(self.lock_time.into()))])
+    }
+
+    fn from_json(value: &Json) -> Result<Self, String> {
+        let inputs = value
+            .field("inputs", Json::as_array)?
+            .iter()
+            .map(|input| {
+                let prev = OutPoint { txid: digest_from_hex(input.field("txid", Json::as_str)?)?, index: input.field("index", Json::as_u32)? };
+                let witness = match input.get("witness") {
+                    None | Some(Json::Null) => None,
+                    Some(w) => Some(Witness {
+                        public: point_from_json(w, "public")?,
+                        signature: Signature { r: point_from_json(w, "r")?, s: Scalar::new(w.field("s", Json::as_u64)?) },
+                    }),
+                };
+                Ok(TxIn { prev, witness })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let outputs = value
+            .field("outputs", Json::as_array)?
+            .iter()
+            .map(|out| Ok(TxOut { owner: out.field("owner", Json::as_str)?.to_string(), amount: out.field("amount", Json::as_u64)? }))
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Transaction { inputs, outputs, lock_time: value.field("lock_time", Json::as_u64)? })
+    }
+}
+
+impl<T: MerkleLeaf + JsonCodec> Chain<T> {
+    pub fn to_json(&self) -> String {
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|block| Json::object(vec![("header", block.header.to_json()), ("txs", Json::Array(block.txs.iter().map(T::to_json).collect()))]))
+            .collect();
+        Json::object(vec![("format", Json::Str(FORMAT.to_string())), ("difficulty", Json::Number(self.difficulty.into())), ("blocks", Json::Array(blocks))])
+            .to_string()
+    }
+
+    // 结构读对了还不够：validate 通过才算一条合法的链
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let value = json::parse(text)?;
+        let format = value.field("format", Json::as_str)?;
+        if format != FORMAT {
+            return Err(format!("不认识的文件格式 \"{}\" (需要 \"{}\")", format, FORMAT));
+        }
+        let difficulty = value.field("difficulty", Json::as_u32)?;
+        let blocks = value
+            .field("blocks", Json::as_array)?
+            .iter()
+            .enumerate()
+            .map(|(i, block)| {
+                let parse = || -> Result<Block<T>, String> {
+                    let header = BlockHeader::from_json(block.field("header", Some)?)?;
+                    let txs = block.field("txs", Json::as_array)?.iter().map(T::from_json).collect::<Result<Vec<_>, _>>()?;
+                    Ok(Block { header, txs })
+                };
+                parse().map_err(|e| format!("区块 {}: {}", i, e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        if blocks.is_empty() {
+            return Err(String::from("文件里没有区块 (至少要有创世块)"));
+        }
+        let chain = Chain { blocks, difficulty };
+        chain.validate().map_err(|e| format!("链不合法: {}", e))?;
+        Ok(chain)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let tmp = format!("{}.tmp", path);
+        fs::write(&tmp, self.to_json()).map_err(|e| format!("写入 {} 失败: {}", tmp, e))?;
+        fs::rename(&tmp, path).map_err(|e| format!("重命名 {} -> {} 失败: {}", tmp, path, e))
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("读取 {} 失败: {}", path, e))?;
+        Self::from_json(&text)
+    }
+}
@@ -0,0 +1,190 @@
+// src/s06_chain/pos.rs
+use std::collections::HashMap;
+
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::crypto::schnorr::{self, Keypair, Signature};
+use crate::s05_zk_lab::hash::{sha256, short_hex, Digest};
+use crate::s05_zk_lab::math::curve::CurvePoint;
+
+use super::block::BlockHeader;
+
+/*
+权益证明 (Proof of Stake)：出块权按质押量分配，而不是按算力
+
+    PoW 里"谁先算出 nonce 谁出块"，概率 ∝ 算力；PoS 里每一轮 (slot) 用一个公开的随机种子抽出一个出块人，概率 ∝ 质押量。
+    抽签方法 (follow-the-satoshi)：
+        把所有质押排成一条 [0, 总质押) 的数轴，每个验证者占一段，长度 = 它的质押
+        r = H(种子 || 轮次) mod 总质押，r 落在谁的那一段，谁就是这一轮的出块人
+    种子对所有人公开，所以任何人都能算出整个 epoch 的出块顺序并验证"这个块该不该由他出"。
+
+    种子从哪来是真正的难点：如果是上一个区块的哈希，出块人可以反复改区块内容"刷"出对自己有利的种子 (grinding)。
+    以太坊用 RANDAO (所有出块人轮流混入自己揭示的随机数)，Cardano / Algorand 用 VRF (S05 ex39)。
+
+罚没 (slashing)：PoS 出块几乎没有成本，"两边都签"也不花钱 —— 所以必须让作恶有代价
+    同一个验证者在同一高度签了两个不同的区块头 (equivocation)，两份签名放在一起就是无法抵赖的证据。
+    任何人都可以提交证据；链上核对签名之后，烧掉一部分质押，并把它踢出活跃集合 (以后不再被抽中)。
+*/
+
+// 罚没时烧掉的质押比例 (百分比)
+pub const SLASH_PERCENT: u64 = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validator {
+    pub name: String,
+    pub public: CurvePoint,
+    pub stake: u64,
+    // 被罚没后为 false：剩下的质押还在，但不再参与抽签
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ValidatorSet {
+    validators: Vec<Validator>,
+}
+
+// 每一轮的随机数：epoch 种子和轮次一起哈希，轮与轮之间互不相关，但都是确定的
+pub fn round_seed(epoch_seed: &Digest, round: u64) -> Digest {
+    let data: Vec<u8> = [epoch_seed.as_slice(), &round.to_be_bytes()].concat();
+    sha256(&data)
+}
+
+impl ValidatorSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, name: &str, public: CurvePoint, stake: u64) -> Result<(), String> {
+        if stake == 0 {
+            return Err(format!("{} 的质押是 0，不能成为验证者", name));
+        }
+        if self.validators.iter().any(|v| v.name == name) {
+            return Err(format!("验证者 {} 已经在集合里了", name));
+        }
+        self.validators.push(Validator { name: name.to_string(), public, stake, active: true });
+        Ok(())
+    }
+
+    pub fn validators(&self) -> &[Validator] {
+        &self.validators
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Validator> {
+        self.validators.iter().find(|v| v.name == name)
+    }
+
+    pub fn active(&self) -> impl Iterator<Item = &Validator> {
+        self.validators.iter().filter(|v| v.active)
+    }
+
+    // 只算活跃验证者：被罚没的人剩下的质押不参与抽签
+    pub fn total_stake(&self) -> u64 {
+        self.active().map(|v| v.stake).sum()
+    }
+
+    // 没有活跃验证者时没有出块人。取模有一点偏差 (2^64 不是总质押的整数倍)，总质押远小于 2^64 时可以忽略
+    pub fn proposer(&self, epoch_seed: &Digest, round: u64) -> Option<&Validator> {
+        let total = self.total_stake();
+        if total == 0 {
+            return None;
+        }
+        let seed = round_seed(epoch_seed, round);
+        let mut r = u64::from_be_bytes(seed[..8].try_into().expect("digest has 32 bytes")) % total;
+        for validator in self.active() {
+            if r < validator.stake {
+                return Some(validator);
+            }
+            r -= validator.stake;
+        }
+        unreachable!("r < total stake always lands in some validator's range")
+    }
+
+    // 核对证据：同一个人、同一高度、两个不同的区块头、两份签名都能用登记的公钥验过
+    pub fn check_evidence(&self, evidence: &Evidence) -> Result<&Validator, String> {
+        let (a, b) = (&evidence.first, &evidence.second);
+        if a.validator != b.validator {
+            return Err(format!("两个区块头分别是 {} 和 {} 签的，不是同一个人", a.validator, b.validator));
+        }
+        if a.header.height != b.header.height {
+            return Err(format!("高度 #{} 和 #{} 不同，在不同高度各出一个块是正常的", a.header.height, b.header.height));
+        }
+        if a.header.hash() == b.header.hash() {
+            return Err(String::from("两个区块头完全一样，只是同一个块被转发了两次"));
+        }
+        let validator = self.get(&a.validator).ok_or_else(|| format!("{} 不是验证者", a.validator))?;
+        for signed in [a, b] {
+            if !signed.verify(validator.public) {
+                return Err(format!("区块头 {}.. 上的签名不是 {} 的，可能是有人栽赃", short_hex(&signed.header.hash()), validator.name));
+            }
+        }
+        Ok(validator)
+    }
+
+    // 证据成立：烧掉 SLASH_PERCENT% 的质押并踢出活跃集合，返回烧掉了多少
+    pub fn slash(&mut self, evidence: &Evidence) -> Result<u64, String> {
+        let name = self.check_evidence(evidence)?.name.clone();
+        let validator = self.validators.iter_mut().find(|v| v.name == name).expect("check_evidence found this validator");
+        if !validator.active {
+            return Err(format!("{} 已经被罚没过了，同一份证据不能罚两次", name));
+        }
+        let burned = validator.stake * SLASH_PERCENT / 100;
+        validator.stake -= burned;
+        validator.active = false;
+        Ok(burned)
+    }
+}
+
+// 验证者对区块头哈希的签名：出块人把它和区块一起广播
+#[derive(Debug, Clone)]
+pub struct SignedHeader {
+    pub validator: String,
+    pub header: BlockHeader,
+    pub signature: Signature<CurvePoint>,
+}
+
+impl SignedHeader {
+    pub fn sign(validator: &str, header: BlockHeader, keys: &Keypair<CurvePoint>, rng: &mut SimpleRng) -> Self {
+        let signature = schnorr::sign(keys, &header.hash(), rng);
+        SignedHeader { validator: validator.to_string(), header, signature }
+    }
+
+    pub fn verify(&self, public: CurvePoint) -> bool {
+        schnorr::verify(public, &self.header.hash(), &self.signature)
+    }
+}
+
+// 双签的证据：同一个验证者在同一高度签过的两个区块头
+#[derive(Debug, Clone)]
+pub struct Evidence {
+    pub first: SignedHeader,
+    pub second: SignedHeader,
+}
+
+// 每个节点都在跑的"监视器"：记住每个 (验证者, 高度) 第一次见到的区块头
+#[derive(Debug, Default)]
+pub struct EquivocationDetector {
+    seen: HashMap<(String, u64), SignedHeader>,
+}
+
+impl EquivocationDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 签名先验过再记：否则别人伪造一个头抢先登记，真正的双签反而对不上
+    // 返回 Ok(Some(证据)) = 这个人在这个高度已经签过另一个头了
+    pub fn observe(&mut self, set: &ValidatorSet, signed: SignedHeader) -> Result<Option<Evidence>, String> {
+        let validator = set.get(&signed.validator).ok_or_else(|| format!("{} 不是验证者", signed.validator))?;
+        if !signed.verify(validator.public) {
+            return Err(format!("{} 的签名不对，丢弃", signed.validator));
+        }
+        let key = (signed.validator.clone(), signed.header.height);
+        match self.seen.get(&key) {
+            Some(first) if first.header.hash() != signed.header.hash() => Ok(Some(Evidence { first: first.clone(), second: signed })),
+            Some(_) => Ok(None),
+            None => {
+                self.seen.insert(key, signed);
+                Ok(None)
+            }
+        }
+    }
+}
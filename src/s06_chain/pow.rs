@@ -0,0 +1,60 @@
+// src/s06_chain/pow.rs
+use std::time::{Duration, Instant};
+
+use crate::s05_zk_lab::hash::Digest;
+
+use super::block::BlockHeader;
+
+/*
+工作量证明 (Proof of Work)
+
+    目标：找一个 nonce，使 SHA-256(区块头) 的前 difficulty 个比特全是 0。
+    SHA-256 的输出可以看成均匀随机的，每试一次成功的概率是 1 / 2^difficulty，
+    除了一个一个试没有捷径 —— 平均要试 2^difficulty 次，验证却只要算 1 次哈希。
+
+    S04 ex01 的"矿工线程"只是 sleep 两秒假装在算，这里是真的在算。
+    难度每加 1，期望耗时翻倍。比特币现在大约是 2^78 次哈希出一个块。
+*/
+
+pub struct MiningResult {
+    pub header: BlockHeader,
+    pub attempts: u64,
+    pub elapsed: Duration,
+}
+
+pub fn leading_zero_bits(digest: &Digest) -> u32 {
+    let mut bits = 0;
+    for &byte in digest {
+        if byte != 0 {
+            return bits + byte.leading_zeros();
+        }
+        bits += 8;
+    }
+    bits
+}
+
+pub fn meets_target(header: &BlockHeader) -> bool {
+    leading_zero_bits(&header.hash()) >= header.difficulty
+}
+
+pub fn expected_attempts(difficulty: u32) -> f64 {
+    2f64.powi(difficulty as i32)
+}
+
+// 一个区块代表的工作量 = 期望哈希次数 2^difficulty (整数版，分叉选择时累加比较用)
+pub fn block_work(difficulty: u32) -> u128 {
+    1u128 << difficulty
+}
+
+// 从 nonce = 0 开始逐个尝试。u64 有 2^64 个 nonce，玩具难度下不可能试完；
+// 真实比特币的 nonce 只有 32 位，试完了还要改 coinbase 里的 extra nonce 换一个 Merkle 根
+pub fn mine(mut header: BlockHeader) -> MiningResult {
+    let start = Instant::now();
+    header.nonce = 0;
+    let mut attempts = 1;
+    while !meets_target(&header) {
+        header.nonce += 1;
+        attempts += 1;
+    }
+    MiningResult { header, attempts, elapsed: start.elapsed() }
+}
@@ -0,0 +1,96 @@
+// src/s06_chain/retarget.rs
+
+/*
+难度调整 (retarget)：算力在变，出块间隔要稳住
+
+    出块间隔 ≈ 难度 / 全网算力。矿工加入，块就出得快；矿工离开，块就出得慢。
+    协议看不到算力，只看得到区块头里的时间戳，所以只能"看过去的出块时间，猜现在的算力"。
+
+    这里的难度是一个连续的数 = 平均要算多少次哈希才出一个块。
+    (玩具链的 difficulty 是前导零比特数，只能 ×2 / ÷2，太粗了；比特币用的是 256 位的 target，效果等价于连续值)
+
+    两种典型做法：
+        Window  比特币：每 window (2016) 个块调整一次，新难度 = 旧难度 × 期望用时 / 实际用时，
+                单次最多 4 倍。窗口里难度不变，算力变了要等到窗口结束才跟上。
+        Ema     每个块都调：对"每块难度"和"每块用时"分别做指数移动平均，
+                估出来的算力 = 平均难度 / 平均用时，新难度 = 估计算力 × 目标间隔。
+                smoothing 越大越平稳，越小跟得越快但越容易被运气带偏。
+*/
+
+// 比特币单次调整的上限
+const MAX_WINDOW_FACTOR: f64 = 4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Algorithm {
+    // 从不调整，当作对照
+    Fixed,
+    Window { window: u64 },
+    Ema { smoothing: u64 },
+}
+
+impl Algorithm {
+    pub fn name(&self) -> String {
+        match self {
+            Algorithm::Fixed => String::from("不调整"),
+            Algorithm::Window { window } => format!("窗口 {}", window),
+            Algorithm::Ema { smoothing } => format!("EMA {}", smoothing),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Retargeter {
+    algorithm: Algorithm,
+    target_time: f64,
+    difficulty: f64,
+    // Window：当前窗口里已经出了几个块、用了多久
+    window_blocks: u64,
+    window_time: f64,
+    // Ema：每块难度和每块用时的移动平均
+    ema_work: f64,
+    ema_time: f64,
+}
+
+impl Retargeter {
+    pub fn new(algorithm: Algorithm, target_time: f64, initial_difficulty: f64) -> Self {
+        Retargeter {
+            algorithm,
+            target_time,
+            difficulty: initial_difficulty,
+            window_blocks: 0,
+            window_time: 0.0,
+            ema_work: initial_difficulty,
+            ema_time: target_time,
+        }
+    }
+
+    // 下一个块要用的难度
+    pub fn difficulty(&self) -> f64 {
+        self.difficulty
+    }
+
+    // 一个块出来了，用了 block_time 秒；返回调整后的难度
+    pub fn on_block(&mut self, block_time: f64) -> f64 {
+        match self.algorithm {
+            Algorithm::Fixed => {}
+            Algorithm::Window { window } => {
+                self.window_blocks += 1;
+                self.window_time += block_time;
+                if self.window_blocks == window {
+                    let expected = window as f64 * self.target_time;
+                    let actual = self.window_time.clamp(expected / MAX_WINDOW_FACTOR, expected * MAX_WINDOW_FACTOR);
+                    self.difficulty *= expected / actual;
+                    self.window_blocks = 0;
+                    self.window_time = 0.0;
+                }
+            }
+            Algorithm::Ema { smoothing } => {
+                let n = smoothing as f64;
+                self.ema_work += (self.difficulty - self.ema_work) / n;
+                self.ema_time += (block_time - self.ema_time) / n;
+                self.difficulty = self.target_time * self.ema_work / self.ema_time;
+            }
+        }
+        self.difficulty
+    }
+}
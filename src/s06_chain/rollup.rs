@@ -0,0 +1,253 @@
+// src/s06_chain/rollup.rs
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::s05_zk_lab::ex01_merkle::{MerkleLeaf, MerkleProof, MerkleTree};
+use crate::s05_zk_lab::hash::{sha256, Digest};
+
+use super::chain::Chain;
+
+/*
+Rollup：交易在 L2 执行，L1 只存"承诺"
+
+    L1 的区块空间贵。Rollup 的排序器 (sequencer) 收集一批 L2 交易，在自己那里执行，
+    然后只往 L1 发一笔很小的交易：
+        批次号 + 交易数 + 这批交易的 Merkle 根 (tx_root) + 执行完之后的 L2 状态根 (state_root)
+    用户想证明"我的交易被打包了"：向排序器要 Merkle 证明，对照的却是 L1 上的 tx_root ——
+    排序器可以拒绝回答，但没法伪造一个对得上 L1 承诺的证明。
+
+    L2 用最简单的账户模型：名字 -> (余额, nonce)，转账要求 nonce 连续 (防重放)。
+
+乐观 rollup 的欺诈证明 (fraud proof)
+
+    L1 默认相信排序器发的 state_root，但给一个挑战窗口：窗口内任何人都可以重新执行批次，发现不对就挑战。
+    L1 自己不想执行整个批次 (太贵)，于是双方二分：
+        执行轨迹 trace[i] = 执行完前 i 笔交易之后的状态根；trace[0] 双方都同意 (上一个批次的根)，trace[n] 有分歧。
+        每一轮排序器公布中点的根，挑战者说同意还是不同意，区间减半，直到只剩一步 (第 lo 笔交易)。
+        L1 只执行这一笔：挑战者给出第 lo 步之前的完整状态 (根必须是双方同意的那个)，
+        L1 执行后和排序器声称的根比较，不一样就是欺诈。
+    重新执行的规则必须确定：非法交易 (nonce 或余额不对) 当作空操作，状态不变。
+*/
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L2Tx {
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub nonce: u64,
+}
+
+impl L2Tx {
+    // 名字前面加长度，避免拼接歧义 (和 utxo::Transaction::encode 同一个做法)
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        for name in [&self.from, &self.to] {
+            data.extend_from_slice(&(name.len() as u32).to_be_bytes());
+            data.extend_from_slice(name.as_bytes());
+        }
+        data.extend_from_slice(&self.amount.to_be_bytes());
+        data.extend_from_slice(&self.nonce.to_be_bytes());
+        data
+    }
+
+    pub fn hash(&self) -> Digest {
+        sha256(&self.encode())
+    }
+}
+
+impl MerkleLeaf for L2Tx {
+    fn leaf_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.encode())
+    }
+}
+
+impl fmt::Display for L2Tx {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} -> {} {} (nonce {})", self.from, self.to, self.amount, self.nonce)
+    }
+}
+
+// BTreeMap：遍历顺序固定，状态根才是确定的
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct L2State {
+    accounts: BTreeMap<String, (u64, u64)>,
+}
+
+impl L2State {
+    pub fn new(balances: &[(&str, u64)]) -> Self {
+        L2State { accounts: balances.iter().map(|(name, balance)| (name.to_string(), (*balance, 0))).collect() }
+    }
+
+    pub fn balance(&self, name: &str) -> u64 {
+        self.accounts.get(name).map_or(0, |(balance, _)| *balance)
+    }
+
+    pub fn nonce(&self, name: &str) -> u64 {
+        self.accounts.get(name).map_or(0, |(_, nonce)| *nonce)
+    }
+
+    pub fn accounts(&self) -> impl Iterator<Item = (&str, u64, u64)> {
+        self.accounts.iter().map(|(name, (balance, nonce))| (name.as_str(), *balance, *nonce))
+    }
+
+    // 凭空给某个账户加钱 —— 正常流程里只有创世分配会这么做，这里留给"作恶的排序器"用
+    pub fn credit(&mut self, name: &str, amount: u64) {
+        self.accounts.entry(name.to_string()).or_insert((0, 0)).0 += amount;
+    }
+
+    // 不合法就什么都不改
+    pub fn apply(&mut self, tx: &L2Tx) -> Result<(), String> {
+        if tx.amount == 0 {
+            return Err(String::from("金额不能是 0"));
+        }
+        let (balance, nonce) = (self.balance(&tx.from), self.nonce(&tx.from));
+        if tx.nonce != nonce {
+            return Err(format!("{} 的 nonce 应该是 {}，交易里是 {}", tx.from, nonce, tx.nonce));
+        }
+        if balance < tx.amount {
+            return Err(format!("{} 余额 {}，不够转 {}", tx.from, balance, tx.amount));
+        }
+        self.accounts.insert(tx.from.clone(), (balance - tx.amount, nonce + 1));
+        self.accounts.entry(tx.to.clone()).or_insert((0, 0)).0 += tx.amount;
+        Ok(())
+    }
+
+    pub fn root(&self) -> Digest {
+        let mut data = Vec::new();
+        for (name, (balance, nonce)) in &self.accounts {
+            data.extend_from_slice(&(name.len() as u32).to_be_bytes());
+            data.extend_from_slice(name.as_bytes());
+            data.extend_from_slice(&balance.to_be_bytes());
+            data.extend_from_slice(&nonce.to_be_bytes());
+        }
+        sha256(&data)
+    }
+}
+
+// 发到 L1 的那一笔交易的内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchCommitment {
+    pub batch: u64,
+    pub tx_count: usize,
+    pub tx_root: String,
+    pub state_root: Digest,
+}
+
+const L1_PREFIX: &str = "rollup-batch";
+
+impl BatchCommitment {
+    // L1 是 Chain<String>，承诺编码成一行文本
+    pub fn to_l1_tx(&self) -> String {
+        format!("{}|{}|{}|{}|{}", L1_PREFIX, self.batch, self.tx_count, self.tx_root, hex::encode(self.state_root))
+    }
+
+    pub fn from_l1_tx(tx: &str) -> Option<Self> {
+        let [prefix, batch, count, tx_root, state_root] = tx.split('|').collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        if prefix != L1_PREFIX {
+            return None;
+        }
+        Some(BatchCommitment {
+            batch: batch.parse().ok()?,
+            tx_count: count.parse().ok()?,
+            tx_root: tx_root.to_string(),
+            state_root: hex::decode(state_root).ok()?.try_into().ok()?,
+        })
+    }
+
+    // 扫一遍 L1，找出所有 rollup 承诺和它们所在的高度；L1 上别的交易跳过
+    pub fn scan(l1: &Chain<String>) -> Vec<(u64, BatchCommitment)> {
+        l1.blocks.iter().flat_map(|b| b.txs.iter().filter_map(|tx| Self::from_l1_tx(tx)).map(|c| (b.header.height, c))).collect()
+    }
+}
+
+// 排序器手里的一个完整批次：交易本身 (L1 上没有) + 承诺
+#[derive(Debug, Clone)]
+pub struct Batch {
+    pub txs: Vec<L2Tx>,
+    pub commitment: BatchCommitment,
+}
+
+impl Batch {
+    pub fn seal(batch: u64, txs: Vec<L2Tx>, state_after: &L2State) -> Self {
+        let tx_root = MerkleTree::new_iterative(txs.clone()).root_hash();
+        let commitment = BatchCommitment { batch, tx_count: txs.len(), tx_root, state_root: state_after.root() };
+        Batch { txs, commitment }
+    }
+
+    pub fn prove(&self, hash: &Digest) -> Option<(L2Tx, MerkleProof)> {
+        let index = self.txs.iter().position(|tx| tx.hash() == *hash)?;
+        let proof = MerkleTree::new_iterative(self.txs.clone()).prove(index)?;
+        Some((self.txs[index].clone(), proof))
+    }
+}
+
+// 执行完前 step 笔交易之后的状态
+pub fn state_at(pre: &L2State, txs: &[L2Tx], step: usize) -> L2State {
+    let mut state = pre.clone();
+    for tx in &txs[..step] {
+        // 非法交易是空操作：apply 失败时状态不变
+        let _ = state.apply(tx);
+    }
+    state
+}
+
+// trace[i] 是执行完前 i 笔交易之后的状态根，长度 txs.len() + 1
+pub fn trace(pre: &L2State, txs: &[L2Tx]) -> Vec<Digest> {
+    let mut state = pre.clone();
+    let mut roots = vec![state.root()];
+    for tx in txs {
+        let _ = state.apply(tx);
+        roots.push(state.root());
+    }
+    roots
+}
+
+// 二分游戏的当前区间：双方同意 trace[lo]，不同意 trace[hi]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bisection {
+    pub lo: usize,
+    pub hi: usize,
+}
+
+impl Bisection {
+    pub fn new(steps: usize) -> Self {
+        Bisection { lo: 0, hi: steps }
+    }
+
+    // 下一轮要排序器公布的位置；只剩一步时返回 None
+    pub fn mid(&self) -> Option<usize> {
+        (self.hi - self.lo > 1).then_some((self.lo + self.hi) / 2)
+    }
+
+    // 挑战者对中点的根表态：同意就往后找，不同意就往前找
+    pub fn narrow(&mut self, agree: bool) {
+        if let Some(mid) = self.mid() {
+            if agree {
+                self.lo = mid;
+            } else {
+                self.hi = mid;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Honest,
+    Fraud { actual: Digest },
+}
+
+// 一步证明：L1 只执行一笔交易
+// pre 由挑战者提供，必须对得上双方同意的根，否则挑战者在撒谎
+pub fn one_step(pre: &L2State, agreed: &Digest, tx: &L2Tx, claimed: &Digest) -> Result<Verdict, String> {
+    if pre.root() != *agreed {
+        return Err(String::from("挑战者给的前状态和双方同意的根对不上"));
+    }
+    let mut state = pre.clone();
+    let _ = state.apply(tx);
+    let actual = state.root();
+    Ok(if actual == *claimed { Verdict::Honest } else { Verdict::Fraud { actual } })
+}
@@ -0,0 +1,179 @@
+// src/s06_chain/spv.rs
+use std::collections::HashMap;
+
+use crate::s05_zk_lab::ex01_merkle::{MerkleLeaf, MerkleProof};
+use crate::s05_zk_lab::hash::{short_hex, Digest};
+
+use super::block::BlockHeader;
+use super::pow;
+use super::utxo::{Transaction, TxId};
+use super::validation::{check_link_and_work, BlockError};
+
+/*
+轻节点 (SPV，Simplified Payment Verification，比特币白皮书第 8 节)
+
+    全节点下载每个区块、执行每笔交易；轻节点只下载区块头 (每个一百多字节)：
+        1. 区块头自己就能验证链接和 PoW (validation::check_link_and_work)，不需要交易
+        2. 和 BlockTree 一样按累计工作量选最好的头链 —— 伪造一条更重的头链和伪造区块一样贵
+        3. 想确认"某笔付款上链了"：向全节点要这笔交易 + 它在区块里的 Merkle 证明，
+           对照自己手里那个区块头的 merkle_root 验证；区块头在最好的链上，并且后面压了足够多的块
+
+    轻节点验证不了交易本身合不合法 (它没有 UTXO 集合)：它相信的是"算力多数不会把无效交易打包进去"。
+
+SPV 钱包 (SpvWallet)：HeaderChain + 自己收到的付款 (交易 + Merkle 证明 + 区块哈希)，别的什么都不存
+    收到证明时：没见过的区块头、Merkle 对不上、不是付给自己的 -> 直接拒绝
+    证明成立但区块头不在最好的链上 -> 存下来但标出来，不算进余额；重组回来之后又会算数
+    确认数每次都对着当前最好的链现算，所以最好的链换了，已经存下的付款会自动"失效"或"复活"
+*/
+
+// 区块头编码的字节数：高度 8 + prev_hash 32 + merkle_root 64 + state_root 32 + 时间戳 8 + 难度 4 + nonce 8
+pub const HEADER_BYTES: usize = 156;
+
+#[derive(Debug, Clone)]
+pub struct HeaderChain {
+    // 区块头 + 从创世块到它的累计工作量
+    headers: HashMap<Digest, (BlockHeader, u128)>,
+    best: Digest,
+    pub difficulty: u32,
+}
+
+impl HeaderChain {
+    // 创世块的头是写死在软件里的检查点，不需要验证
+    pub fn new(genesis: BlockHeader, difficulty: u32) -> Self {
+        let hash = genesis.hash();
+        let work = pow::block_work(genesis.difficulty);
+        HeaderChain { headers: HashMap::from([(hash, (genesis, work))]), best: hash, difficulty }
+    }
+
+    // Ok(true)：最好的链头换成了这个头
+    pub fn add(&mut self, header: BlockHeader) -> Result<bool, BlockError> {
+        let hash = header.hash();
+        if self.headers.contains_key(&hash) {
+            return Ok(false);
+        }
+        let Some((parent, parent_work)) = self.headers.get(&header.prev_hash) else {
+            return Err(BlockError::UnknownParent { height: header.height, prev_hash: header.prev_hash });
+        };
+        check_link_and_work(Some(parent), &header, self.difficulty)?;
+        let work = parent_work + pow::block_work(header.difficulty);
+        self.headers.insert(hash, (header, work));
+        // 严格大于才换：一样重的分支先到先得
+        if work > self.headers[&self.best].1 {
+            self.best = hash;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    // 收下的区块头个数，包括不在最好的链上的
+    pub fn count(&self) -> usize {
+        self.headers.len()
+    }
+
+    pub fn best(&self) -> &BlockHeader {
+        &self.headers[&self.best].0
+    }
+
+    // 区块在最好的链上时返回确认数 (它自己算 1)；在别的分支上或者没见过返回 None
+    pub fn confirmations(&self, hash: &Digest) -> Option<u64> {
+        let (target, _) = self.headers.get(hash)?;
+        let mut cursor = self.best();
+        while cursor.height > target.height {
+            cursor = &self.headers[&cursor.prev_hash].0;
+        }
+        (cursor.hash() == *hash).then(|| self.best().height - target.height + 1)
+    }
+
+    // 证明和区块头的 merkle_root 对得上时返回这个区块头，不管它在不在最好的链上
+    pub fn check_proof<T: MerkleLeaf>(&self, tx: &T, proof: &MerkleProof, block_hash: &Digest) -> Result<&BlockHeader, String> {
+        let Some((header, _)) = self.headers.get(block_hash) else {
+            return Err(format!("没见过区块 {}..", short_hex(block_hash)));
+        };
+        if !proof.verify(tx, &header.merkle_root) {
+            return Err(format!("Merkle 证明和区块 #{} 的 merkle_root 对不上", header.height));
+        }
+        Ok(header)
+    }
+
+    // 全节点给的 (交易, 证明, 区块哈希)：成立时返回确认数
+    pub fn verify_inclusion<T: MerkleLeaf>(&self, tx: &T, proof: &MerkleProof, block_hash: &Digest) -> Result<u64, String> {
+        let header = self.check_proof(tx, proof, block_hash)?;
+        self.confirmations(block_hash).ok_or_else(|| format!("区块 #{} 不在最好的链上 (被甩掉的分支)", header.height))
+    }
+}
+
+// 钱包存下的一笔收款：付给自己的金额，和证明它上链的全部材料
+#[derive(Debug, Clone)]
+pub struct Payment {
+    pub tx: Transaction,
+    pub proof: MerkleProof,
+    pub block_hash: Digest,
+    pub height: u64,
+    pub amount: u64,
+}
+
+impl Payment {
+    // 交易 (带签名) + 兄弟哈希 (32 字节 + 1 字节方向) + 区块哈希
+    pub fn bytes(&self) -> usize {
+        self.tx.encode_with_witness().len() + self.proof.siblings.len() * 33 + 32
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpvWallet {
+    pub owner: String,
+    headers: HeaderChain,
+    payments: Vec<Payment>,
+}
+
+impl SpvWallet {
+    pub fn new(owner: &str, genesis: BlockHeader, difficulty: u32) -> Self {
+        SpvWallet { owner: owner.to_string(), headers: HeaderChain::new(genesis, difficulty), payments: Vec::new() }
+    }
+
+    pub fn headers(&self) -> &HeaderChain {
+        &self.headers
+    }
+
+    pub fn add_header(&mut self, header: BlockHeader) -> Result<bool, BlockError> {
+        self.headers.add(header)
+    }
+
+    // 存下一笔收款的证明，返回当前的确认数；None 表示区块头不在最好的链上 (存下了，但不算数)
+    pub fn receive(&mut self, tx: Transaction, proof: MerkleProof, block_hash: Digest) -> Result<Option<u64>, String> {
+        let txid = tx.txid();
+        if self.payments.iter().any(|p| p.tx.txid() == txid && p.block_hash == block_hash) {
+            return Err(format!("交易 {}.. 在这个区块里的证明已经存过了", short_hex(&txid)));
+        }
+        let amount: u64 = tx.outputs.iter().filter(|out| out.owner == self.owner).map(|out| out.amount).sum();
+        if amount == 0 {
+            return Err(format!("交易 {}.. 没有付给 {} 的输出", short_hex(&txid), self.owner));
+        }
+        let height = self.headers.check_proof(&tx, &proof, &block_hash)?.height;
+        self.payments.push(Payment { tx, proof, block_hash, height, amount });
+        Ok(self.headers.confirmations(&block_hash))
+    }
+
+    // 每笔收款和它现在的确认数 (对着当前最好的链算)
+    pub fn payments(&self) -> Vec<(&Payment, Option<u64>)> {
+        self.payments.iter().map(|p| (p, self.headers.confirmations(&p.block_hash))).collect()
+    }
+
+    // 确认数够 min_confirmations 的收款之和；同一笔交易在两条分支上各存了一份时只算一次
+    pub fn balance(&self, min_confirmations: u64) -> u64 {
+        let mut counted: Vec<TxId> = Vec::new();
+        let mut total = 0;
+        for (payment, confirmations) in self.payments() {
+            let txid = payment.tx.txid();
+            if confirmations.is_some_and(|c| c >= min_confirmations) && !counted.contains(&txid) {
+                counted.push(txid);
+                total += payment.amount;
+            }
+        }
+        total
+    }
+
+    pub fn storage_bytes(&self) -> usize {
+        self.headers.count() * HEADER_BYTES + self.payments.iter().map(Payment::bytes).sum::<usize>()
+    }
+}
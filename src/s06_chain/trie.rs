@@ -0,0 +1,362 @@
+// src/s06_chain/trie.rs
+use crate::s05_zk_lab::hash::{sha256, Digest};
+
+/*
+Merkle-Patricia 树 (简化版的以太坊状态树)
+
+    键按 4 比特 (nibble) 拆开，一个 nibble 一层，所以每个分支节点最多 16 个孩子 (hexary)。
+    三种节点：
+        Leaf      : 剩下的整段路径 + 值 (后面没有分叉了，一次走完)
+        Extension : 一段所有键共享的路径 + 一个孩子 (压缩掉"只有一个孩子"的长链)
+        Branch    : 16 个孩子槽 + 一个值槽 (某个键恰好在这里结束，比如 "Bob" 是 "Bobby" 的前缀)
+    父节点只保存孩子的哈希 (Encoded)，所以根哈希承诺了整棵树。
+
+和 S05 ex01 的二叉 MerkleTree 的区别：
+    MerkleTree 的叶子按数组下标排列，插入顺序不同根就不同；也没法按键查找。
+    Patricia 树的形状只由键集合决定：同样的 (键, 值) 不管按什么顺序插入，根都一样；
+    改一个值只需要重算它到根这一条路径。这正是"状态"需要的：账户不断更新，每个块都要一个新的状态根。
+
+简化：以太坊用 RLP 编码、短节点内联、键先做 keccak (防止攻击者构造长公共前缀让树变深)；这里都省了，键就是地址的字节。
+*/
+
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+fn nibbles_hex(path: &[u8]) -> String {
+    path.iter().map(|n| format!("{:x}", n)).collect()
+}
+
+fn common_prefix(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+pub fn empty_root() -> Digest {
+    sha256(&[])
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf { path: Vec<u8>, value: Vec<u8> },
+    Extension { path: Vec<u8>, child: Box<Node> },
+    Branch { children: [Option<Box<Node>>; 16], value: Option<Vec<u8>> },
+}
+
+// 节点的编码形式：孩子只写哈希。证明里传的就是它的字节
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Encoded {
+    Leaf { path: Vec<u8>, value: Vec<u8> },
+    Extension { path: Vec<u8>, child: Digest },
+    // 16 个哈希有 500 多字节，装箱让另外两种节点不用跟着变大
+    Branch { children: Box<[Option<Digest>; 16]>, value: Option<Vec<u8>> },
+}
+
+impl Encoded {
+    // 标签 (0 叶子 / 1 扩展 / 2 分支) + 字段；变长字段前面写长度
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        match self {
+            Encoded::Leaf { path, value } => {
+                data.push(0);
+                data.extend_from_slice(&(path.len() as u16).to_be_bytes());
+                data.extend_from_slice(path);
+                data.extend_from_slice(&(value.len() as u32).to_be_bytes());
+                data.extend_from_slice(value);
+            }
+            Encoded::Extension { path, child } => {
+                data.push(1);
+                data.extend_from_slice(&(path.len() as u16).to_be_bytes());
+                data.extend_from_slice(path);
+                data.extend_from_slice(child);
+            }
+            Encoded::Branch { children, value } => {
+                data.push(2);
+                // 位图：第 i 位为 1 表示第 i 个孩子存在，空槽不占字节
+                let bitmap = children.iter().enumerate().fold(0u16, |acc, (i, c)| if c.is_some() { acc | 1 << i } else { acc });
+                data.extend_from_slice(&bitmap.to_be_bytes());
+                for child in children.iter().flatten() {
+                    data.extend_from_slice(child);
+                }
+                match value {
+                    Some(value) => {
+                        data.push(1);
+                        data.extend_from_slice(&(value.len() as u32).to_be_bytes());
+                        data.extend_from_slice(value);
+                    }
+                    None => data.push(0),
+                }
+            }
+        }
+        data
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader { data, pos: 0 };
+        let node = match reader.take(1)?[0] {
+            0 => {
+                let len = u16::from_be_bytes(reader.array()?) as usize;
+                let path = reader.take(len)?.to_vec();
+                let len = u32::from_be_bytes(reader.array()?) as usize;
+                Encoded::Leaf { path, value: reader.take(len)?.to_vec() }
+            }
+            1 => {
+                let len = u16::from_be_bytes(reader.array()?) as usize;
+                let path = reader.take(len)?.to_vec();
+                Encoded::Extension { path, child: reader.array()? }
+            }
+            2 => {
+                let bitmap = u16::from_be_bytes(reader.array()?);
+                let mut children = Box::new([None; 16]);
+                for (i, child) in children.iter_mut().enumerate() {
+                    if bitmap & (1 << i) != 0 {
+                        *child = Some(reader.array()?);
+                    }
+                }
+                let value = match reader.take(1)?[0] {
+                    0 => None,
+                    _ => {
+                        let len = u32::from_be_bytes(reader.array()?) as usize;
+                        Some(reader.take(len)?.to_vec())
+                    }
+                };
+                Encoded::Branch { children, value }
+            }
+            tag => return Err(format!("未知的节点类型 {}", tag)),
+        };
+        if reader.pos != data.len() {
+            return Err(String::from("节点编码后面有多余的字节"));
+        }
+        Ok(node)
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let slice = self.data.get(self.pos..self.pos + n).ok_or_else(|| String::from("节点编码被截断了"))?;
+        self.pos += n;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        Ok(self.take(N)?.try_into().expect("take returns exactly N bytes"))
+    }
+}
+
+impl Node {
+    fn encode(&self) -> Encoded {
+        match self {
+            Node::Leaf { path, value } => Encoded::Leaf { path: path.clone(), value: value.clone() },
+            Node::Extension { path, child } => Encoded::Extension { path: path.clone(), child: child.hash() },
+            Node::Branch { children, value } => Encoded::Branch {
+                children: Box::new(std::array::from_fn(|i| children[i].as_ref().map(|c| c.hash()))),
+                value: value.clone(),
+            },
+        }
+    }
+
+    // 不缓存：每次都递归重算 (玩具规模够用；真实实现会把哈希存在节点里，只在修改时作废)
+    fn hash(&self) -> Digest {
+        sha256(&self.encode().to_bytes())
+    }
+}
+
+// (剩下的路径, 放进分支槽的子树, 路径刚好走完时放进值槽的值)
+type Entry<'a> = (&'a [u8], Box<Node>, Option<Vec<u8>>);
+
+// 两段路径在 common 处分开：建一个分支节点放下两者，前面共享的部分 (如果有) 用扩展节点接上
+fn split(prefix: &[u8], entries: [Entry; 2]) -> Box<Node> {
+    let mut children: [Option<Box<Node>>; 16] = Default::default();
+    let mut branch_value = None;
+    for (rest, subtree, value) in entries {
+        match (rest.split_first(), value) {
+            (None, Some(value)) => branch_value = Some(value),
+            (Some((&nibble, _)), _) => children[nibble as usize] = Some(subtree),
+            (None, None) => unreachable!("only leaves can end exactly at a branch"),
+        }
+    }
+    let branch = Box::new(Node::Branch { children, value: branch_value });
+    if prefix.is_empty() {
+        branch
+    } else {
+        Box::new(Node::Extension { path: prefix.to_vec(), child: branch })
+    }
+}
+
+fn insert_at(node: Option<Box<Node>>, path: &[u8], value: Vec<u8>) -> Box<Node> {
+    let Some(node) = node else {
+        return Box::new(Node::Leaf { path: path.to_vec(), value });
+    };
+    match *node {
+        Node::Leaf { path: leaf_path, value: leaf_value } => {
+            if leaf_path == path {
+                return Box::new(Node::Leaf { path: leaf_path, value });
+            }
+            let common = common_prefix(&leaf_path, path);
+            let leaf = |rest: &[u8], value: Vec<u8>| Box::new(Node::Leaf { path: rest.get(1..).unwrap_or_default().to_vec(), value });
+            let (old_rest, new_rest) = (&leaf_path[common..], &path[common..]);
+            split(
+                &path[..common],
+                [
+                    (old_rest, leaf(old_rest, leaf_value.clone()), Some(leaf_value)),
+                    (new_rest, leaf(new_rest, value.clone()), Some(value)),
+                ],
+            )
+        }
+        Node::Extension { path: ext_path, child } => {
+            let common = common_prefix(&ext_path, path);
+            if common == ext_path.len() {
+                let child = insert_at(Some(child), &path[common..], value);
+                return Box::new(Node::Extension { path: ext_path, child });
+            }
+            // 扩展节点在 common 处被劈开：分叉 nibble 之后剩下的部分 (如果有) 还是一个扩展节点
+            let ext_rest = &ext_path[common..];
+            let subtree = if ext_rest.len() == 1 { child } else { Box::new(Node::Extension { path: ext_rest[1..].to_vec(), child }) };
+            let new_rest = &path[common..];
+            let new_leaf = Box::new(Node::Leaf { path: new_rest.get(1..).unwrap_or_default().to_vec(), value: value.clone() });
+            split(&path[..common], [(ext_rest, subtree, None), (new_rest, new_leaf, Some(value))])
+        }
+        Node::Branch { mut children, value: branch_value } => match path.split_first() {
+            None => Box::new(Node::Branch { children, value: Some(value) }),
+            Some((&nibble, rest)) => {
+                let slot = children[nibble as usize].take();
+                children[nibble as usize] = Some(insert_at(slot, rest, value));
+                Box::new(Node::Branch { children, value: branch_value })
+            }
+        },
+    }
+}
+
+fn describe_node(node: &Node, depth: usize, label: &str, out: &mut Vec<String>) {
+    let pad = "    ".repeat(depth);
+    match node {
+        Node::Leaf { path, value } => out.push(format!("{}{}Leaf [{}] 值 {} 字节", pad, label, nibbles_hex(path), value.len())),
+        Node::Extension { path, child } => {
+            out.push(format!("{}{}Extension [{}]", pad, label, nibbles_hex(path)));
+            describe_node(child, depth + 1, "", out);
+        }
+        Node::Branch { children, value } => {
+            let value = value.as_ref().map_or(String::new(), |v| format!("，值 {} 字节", v.len()));
+            out.push(format!("{}{}Branch{}", pad, label, value));
+            for (i, child) in children.iter().enumerate() {
+                if let Some(child) = child {
+                    describe_node(child, depth + 1, &format!("{:x}: ", i), out);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PatriciaTrie {
+    root: Option<Box<Node>>,
+}
+
+impl PatriciaTrie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, key: &[u8], value: Vec<u8>) {
+        self.root = Some(insert_at(self.root.take(), &to_nibbles(key), value));
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        let path = to_nibbles(key);
+        let mut rest = path.as_slice();
+        let mut node = self.root.as_deref()?;
+        loop {
+            match node {
+                Node::Leaf { path, value } => return (path.as_slice() == rest).then_some(value.as_slice()),
+                Node::Extension { path, child } => {
+                    rest = rest.strip_prefix(path.as_slice())?;
+                    node = child;
+                }
+                Node::Branch { children, value } => match rest.split_first() {
+                    None => return value.as_deref(),
+                    Some((&nibble, tail)) => {
+                        node = children[nibble as usize].as_deref()?;
+                        rest = tail;
+                    }
+                },
+            }
+        }
+    }
+
+    pub fn root_hash(&self) -> Digest {
+        self.root.as_ref().map_or_else(empty_root, |root| root.hash())
+    }
+
+    // 证明 = 从根往下沿着键走过的每个节点的编码。键不存在时同样有证明 (走到断掉的地方为止)
+    pub fn prove(&self, key: &[u8]) -> Vec<Vec<u8>> {
+        let path = to_nibbles(key);
+        let mut rest = path.as_slice();
+        let mut proof = Vec::new();
+        let mut cursor = self.root.as_deref();
+        while let Some(node) = cursor {
+            proof.push(node.encode().to_bytes());
+            cursor = match node {
+                Node::Leaf { .. } => None,
+                Node::Extension { path, child } => rest.strip_prefix(path.as_slice()).map(|tail| {
+                    rest = tail;
+                    child.as_ref()
+                }),
+                Node::Branch { children, .. } => rest.split_first().and_then(|(&nibble, tail)| {
+                    rest = tail;
+                    children[nibble as usize].as_deref()
+                }),
+            };
+        }
+        proof
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        match &self.root {
+            Some(root) => describe_node(root, 0, "", &mut out),
+            None => out.push(String::from("(空树)")),
+        }
+        out
+    }
+}
+
+// 验证者只有根哈希：每个节点的哈希必须等于上一个节点里记着的孩子哈希，一路走到键结束的地方
+// Ok(Some(值)) 键存在；Ok(None) 键不存在 (同样被证明了)；Err 证明本身不成立
+pub fn verify_proof(root: &Digest, key: &[u8], proof: &[Vec<u8>]) -> Result<Option<Vec<u8>>, String> {
+    if proof.is_empty() {
+        return if *root == empty_root() { Ok(None) } else { Err(String::from("证明是空的，但树不是空树")) };
+    }
+    let path = to_nibbles(key);
+    let mut rest = path.as_slice();
+    let mut expected = *root;
+    for (i, bytes) in proof.iter().enumerate() {
+        if sha256(bytes) != expected {
+            return Err(format!("证明第 {} 个节点的哈希和上一层记录的对不上", i + 1));
+        }
+        match Encoded::from_bytes(bytes)? {
+            Encoded::Leaf { path, value } => return Ok((path.as_slice() == rest).then_some(value)),
+            Encoded::Extension { path, child } => match rest.strip_prefix(path.as_slice()) {
+                Some(tail) => {
+                    rest = tail;
+                    expected = child;
+                }
+                None => return Ok(None),
+            },
+            Encoded::Branch { children, value } => match rest.split_first() {
+                None => return Ok(value),
+                Some((&nibble, tail)) => match children[nibble as usize] {
+                    Some(child) => {
+                        rest = tail;
+                        expected = child;
+                    }
+                    None => return Ok(None),
+                },
+            },
+        }
+    }
+    Err(String::from("证明不完整：还没走到键的位置就结束了"))
+}
@@ -0,0 +1,432 @@
+// src/s06_chain/utxo.rs
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::s05_zk_lab::crypto::schnorr::{self, Signature};
+use crate::s05_zk_lab::ex01_merkle::MerkleLeaf;
+use crate::s05_zk_lab::hash::{sha256, short_hex, Digest};
+use crate::s05_zk_lab::math::curve::CurvePoint;
+use crate::s05_zk_lab::math::group::Group;
+
+use super::block::Block;
+use super::chain::Chain;
+use super::trie::PatriciaTrie;
+use super::wallet::address;
+
+/*
+UTXO 模型 (Unspent Transaction Output，比特币的记账方式)
+
+    没有"账户余额"这个字段。账本是一堆还没花掉的输出 (UTXO)，每个输出写着"谁的、多少钱"。
+    交易 = 消耗若干旧输出 (inputs) + 创建若干新输出 (outputs)：
+        输入必须存在且没被花过；输入总额 >= 输出总额，差额是给矿工的手续费。
+    一个输出要么整个花掉，要么原封不动 —— 付 30 块但手里是一张 50 的，就要给自己找零 20。
+
+    "余额"只是一个派生量：把属于某人的 UTXO 加起来。
+
+两条和高度有关的规则：
+    coinbase 成熟期：coinbase 的输出要等 coinbase_maturity 个块之后才能花 (比特币是 100)。
+        区块被重组掉，它的 coinbase 就凭空消失；等一段时间再让它流通，被回滚时牵连的交易就少。
+        默认 0 (不限制)，需要的链用 with_maturity 打开。
+    锁定时间 (lock_time)：交易写明"最早进哪个高度的区块"，之前连交易池都进不去；0 表示不锁。
+        比特币的 nLockTime 还可以是时间戳，这里只支持高度。
+
+对照 S01 的 Account { balance }：
+    账户模型直接改 balance，交易之间通过余额互相影响，必须按顺序执行；
+    UTXO 之间互不相干，只要不花同一个输出，交易就可以并行验证。
+*/
+
+pub type TxId = Digest;
+
+// 出块奖励 (比特币最初是 50 BTC)
+pub const BLOCK_REWARD: u64 = 50;
+
+// 一个输出的坐标：哪笔交易的第几个输出
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OutPoint {
+    pub txid: TxId,
+    pub index: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOut {
+    pub owner: String,
+    pub amount: u64,
+}
+
+// 花一个输出要出示的"钥匙"：公钥 (哈希后必须等于输出上的地址) + 用对应私钥对交易的签名
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Witness {
+    pub public: CurvePoint,
+    pub signature: Signature<CurvePoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxIn {
+    pub prev: OutPoint,
+    pub witness: Option<Witness>,
+}
+
+impl TxIn {
+    // 先搭好交易，再由钱包逐个输入签名 (wallet.rs)
+    pub fn unsigned(prev: OutPoint) -> Self {
+        TxIn { prev, witness: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub inputs: Vec<TxIn>,
+    pub outputs: Vec<TxOut>,
+    // 最早能进的区块高度，0 表示不锁
+    pub lock_time: u64,
+}
+
+impl Transaction {
+    // coinbase 没有真正的输入：比特币用一个全 0 的 txid 占位，并把区块高度写进去 (BIP 34)，
+    // 否则两个区块里"给同一个人发 50"的 coinbase 会得到相同的 txid
+    pub fn coinbase(owner: &str, amount: u64, height: u64) -> Self {
+        Transaction {
+            inputs: vec![TxIn::unsigned(OutPoint { txid: [0u8; 32], index: height as u32 })],
+            outputs: vec![TxOut { owner: owner.to_string(), amount }],
+            lock_time: 0,
+        }
+    }
+
+    pub fn is_coinbase(&self) -> bool {
+        self.inputs.len() == 1 && self.inputs[0].prev.txid == [0u8; 32]
+    }
+
+    // 定长字段直接拼，owner 前面加长度，避免 "ab" + "c" 和 "a" + "bc" 的歧义
+    // 不含签名：签名签的就是这段字节 (签名不能签自己)；txid 也不含签名，见 txid()
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(self.inputs.len() as u32).to_be_bytes());
+        for input in &self.inputs {
+            data.extend_from_slice(&input.prev.txid);
+            data.extend_from_slice(&input.prev.index.to_be_bytes());
+        }
+        data.extend_from_slice(&(self.outputs.len() as u32).to_be_bytes());
+        for output in &self.outputs {
+            data.extend_from_slice(&(output.owner.len() as u32).to_be_bytes());
+            data.extend_from_slice(output.owner.as_bytes());
+            data.extend_from_slice(&output.amount.to_be_bytes());
+        }
+        data.extend_from_slice(&self.lock_time.to_be_bytes());
+        data
+    }
+
+    // 带签名的完整编码：区块的 Merkle 根承诺的是它，签名也被钉进区块头
+    pub fn encode_with_witness(&self) -> Vec<u8> {
+        let mut data = self.encode();
+        for input in &self.inputs {
+            match &input.witness {
+                Some(w) => {
+                    data.push(1);
+                    data.extend_from_slice(&w.public.encode().to_be_bytes());
+                    data.extend_from_slice(&w.signature.r.encode().to_be_bytes());
+                    data.extend_from_slice(&w.signature.s.value().to_be_bytes());
+                }
+                None => data.push(0),
+            }
+        }
+        data
+    }
+
+    // 和隔离见证 (SegWit) 一样，txid 不含签名：
+    // 否则第三方改一下签名的编码 (签名延展性)，同一笔交易就换了 txid，依赖它的子交易全部失效
+    pub fn txid(&self) -> TxId {
+        sha256(&self.encode())
+    }
+
+    // 金额都是 u64：输出 u64::MAX 和 2 直接相加会回绕成 1，"输出不超过输入"的检查就被绕过去凭空造币了
+    // 所以一律 checked_add，溢出返回 None，由调用方拒绝整笔交易
+    pub fn output_total(&self) -> Option<u64> {
+        self.outputs.iter().try_fold(0u64, |total, o| total.checked_add(o.amount))
+    }
+}
+
+// 区块里的 Merkle 叶子就是交易的编码，Block<Transaction> 由此成立
+impl MerkleLeaf for Transaction {
+    fn leaf_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.encode_with_witness())
+    }
+}
+
+impl fmt::Display for OutPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", short_hex(&self.txid), self.index)
+    }
+}
+
+impl fmt::Display for Transaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let outputs: Vec<String> = self.outputs.iter().map(|o| format!("{} {}", o.owner, o.amount)).collect();
+        if self.is_coinbase() {
+            write!(f, "{} coinbase -> [{}]", short_hex(&self.txid()), outputs.join(", "))
+        } else {
+            let inputs: Vec<String> = self.inputs.iter().map(|i| i.prev.to_string()).collect();
+            write!(f, "{} [{}] -> [{}]", short_hex(&self.txid()), inputs.join(", "), outputs.join(", "))?;
+            if self.lock_time > 0 {
+                write!(f, " (锁定到 #{})", self.lock_time)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+// 撤销数据 (比特币的 rev*.dat)：区块里的输入只写了 OutPoint，被花掉的输出 (主人、金额) 执行完就从集合里删了，
+// 回滚时没处找 —— 所以上链时顺手记下来
+#[derive(Debug, Clone, Default)]
+pub struct BlockUndo {
+    // 这个区块创建的输出，回滚时删掉
+    pub created: Vec<OutPoint>,
+    // 这个区块花掉的、在它之前就存在的输出，回滚时放回去
+    pub spent: Vec<(OutPoint, TxOut)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UtxoSet {
+    unspent: HashMap<OutPoint, TxOut>,
+    // 花掉的输出记一笔"被谁花的"：只为了报错时能区分"不存在"和"已经花过"
+    spent: HashMap<OutPoint, TxId>,
+    // coinbase 输出 -> 它所在区块的高度；花掉之后也留着，回滚创建它的区块时才删
+    coinbase_heights: HashMap<OutPoint, u64>,
+    // 最后执行的区块的高度；交易按"下一个块" (height + 1) 检查成熟期和锁定时间
+    height: u64,
+    coinbase_maturity: u64,
+}
+
+impl UtxoSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_maturity(mut self, blocks: u64) -> Self {
+        self.coinbase_maturity = blocks;
+        self
+    }
+
+    pub fn coinbase_maturity(&self) -> u64 {
+        self.coinbase_maturity
+    }
+
+    // coinbase 输出最早能在哪个高度被花；普通输出返回 None
+    pub fn spendable_from(&self, op: &OutPoint) -> Option<u64> {
+        self.coinbase_heights.get(op).map(|h| h + self.coinbase_maturity)
+    }
+
+    pub fn len(&self) -> usize {
+        self.unspent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.unspent.is_empty()
+    }
+
+    // 按 (txid, index) 排序，打印出来的顺序稳定
+    pub fn outputs_of(&self, owner: &str) -> Vec<(OutPoint, TxOut)> {
+        let mut outputs: Vec<(OutPoint, TxOut)> = self
+            .unspent
+            .iter()
+            .filter(|(_, out)| out.owner == owner)
+            .map(|(op, out)| (*op, out.clone()))
+            .collect();
+        outputs.sort_by_key(|(op, _)| (op.txid, op.index));
+        outputs
+    }
+
+    pub fn balance(&self, owner: &str) -> u64 {
+        self.outputs_of(owner).iter().map(|(_, out)| out.amount).sum()
+    }
+
+    // 普通交易的检查，返回手续费 (输入 - 输出)
+    pub fn validate_tx(&self, tx: &Transaction) -> Result<u64, String> {
+        if tx.is_coinbase() {
+            return Err(String::from("coinbase 只能是区块的第一笔交易"));
+        }
+        if tx.inputs.is_empty() || tx.outputs.is_empty() {
+            return Err(String::from("交易至少要有一个输入和一个输出"));
+        }
+        let next = self.height + 1;
+        if tx.lock_time > next {
+            return Err(format!("锁定到高度 {}，下一个块才 #{}", tx.lock_time, next));
+        }
+        let mut input_total = 0u64;
+        for (i, input) in tx.inputs.iter().enumerate() {
+            if tx.inputs[..i].iter().any(|earlier| earlier.prev == input.prev) {
+                return Err(format!("同一笔交易里两次花 {}", input.prev));
+            }
+            match (self.unspent.get(&input.prev), self.spent.get(&input.prev)) {
+                (Some(out), _) => input_total = input_total.checked_add(out.amount).ok_or("输入总额超出 u64 范围")?,
+                (None, Some(by)) => return Err(format!("{} 已经被交易 {} 花掉了 (双花)", input.prev, short_hex(by))),
+                (None, None) => return Err(format!("{} 不存在", input.prev)),
+            }
+            if let Some(from) = self.spendable_from(&input.prev).filter(|&from| from > next) {
+                let created = self.coinbase_heights[&input.prev];
+                return Err(format!("{} 是 #{} 的 coinbase，要到 #{} 才成熟 (下一个块才 #{})", input.prev, created, from, next));
+            }
+        }
+        let output_total = tx.output_total().ok_or("输出总额超出 u64 范围")?;
+        if output_total > input_total {
+            return Err(format!("输出总额 {} 超过输入总额 {}", output_total, input_total));
+        }
+        Ok(input_total - output_total)
+    }
+
+    // 授权检查：每个输入都要出示公钥和签名
+    //   1. address(公钥) == 被花输出上的地址 —— 证明"钥匙"配这把"锁"
+    //   2. 签名对 encode() 有效 —— 证明持有私钥，而且签的正是这笔交易 (改了金额或收款人，签名就失效)
+    pub fn verify_signatures(&self, tx: &Transaction) -> Result<(), String> {
+        let sighash = tx.encode();
+        for input in &tx.inputs {
+            let owner = match self.unspent.get(&input.prev) {
+                Some(out) => &out.owner,
+                None => return Err(format!("{} 不存在或已花费", input.prev)),
+            };
+            let Some(witness) = &input.witness else {
+                return Err(format!("输入 {} 没有签名", input.prev));
+            };
+            if address(&witness.public) != *owner {
+                return Err(format!("输入 {} 属于 {}，出示的公钥对应的是 {}", input.prev, owner, address(&witness.public)));
+            }
+            if !schnorr::verify(witness.public, &sighash, &witness.signature) {
+                return Err(format!("输入 {} 的签名无效", input.prev));
+            }
+        }
+        Ok(())
+    }
+
+    // 不做检查，调用方先 validate_tx
+    pub fn apply_tx(&mut self, tx: &Transaction) {
+        let txid = tx.txid();
+        if !tx.is_coinbase() {
+            for input in &tx.inputs {
+                self.unspent.remove(&input.prev);
+                self.spent.insert(input.prev, txid);
+            }
+        }
+        for (index, output) in tx.outputs.iter().enumerate() {
+            self.unspent.insert(OutPoint { txid, index: index as u32 }, output.clone());
+        }
+    }
+
+    // 一个区块要么全部生效，要么什么都不改：先在副本上执行，成功了再换回来
+    // 区块内的交易按顺序执行，后面的交易可以花前面交易刚创建的输出
+    pub fn apply_block(&mut self, block: &Block<Transaction>) -> Result<(), String> {
+        let Some((coinbase, txs)) = block.txs.split_first() else {
+            return Err(String::from("区块里没有交易 (至少要有 coinbase)"));
+        };
+        if !coinbase.is_coinbase() {
+            return Err(String::from("区块的第一笔交易必须是 coinbase"));
+        }
+        let mut next = self.clone();
+        let mut fees = 0u64;
+        for (i, tx) in txs.iter().enumerate() {
+            let fee = next.validate_tx(tx).map_err(|e| format!("第 {} 笔交易 {}: {}", i + 1, short_hex(&tx.txid()), e))?;
+            fees = fees.checked_add(fee).ok_or("手续费总额超出 u64 范围")?;
+            next.apply_tx(tx);
+        }
+        let claimed = coinbase.output_total().ok_or("coinbase 输出总额超出 u64 范围")?;
+        let allowed = BLOCK_REWARD.checked_add(fees).ok_or("出块奖励 + 手续费超出 u64 范围")?;
+        // 创世块的初始分配由配置决定 (genesis.rs)，不受出块奖励限制；check_header 保证只有链的第一个块能是高度 0
+        if block.header.height > 0 && claimed > allowed {
+            return Err(format!("coinbase 领了 {}，最多只能领 出块奖励 {} + 手续费 {}", claimed, BLOCK_REWARD, fees));
+        }
+        next.apply_tx(coinbase);
+        let txid = coinbase.txid();
+        for index in 0..coinbase.outputs.len() as u32 {
+            next.coinbase_heights.insert(OutPoint { txid, index }, block.header.height);
+        }
+        next.height = block.header.height;
+        *self = next;
+        Ok(())
+    }
+
+    // apply_block + 记下撤销数据
+    // 区块内"先创建、再被后面的交易花掉"的输出不用恢复：回滚后它本来就不该存在
+    pub fn connect_block(&mut self, block: &Block<Transaction>) -> Result<BlockUndo, String> {
+        let spent = block
+            .txs
+            .iter()
+            .filter(|tx| !tx.is_coinbase())
+            .flat_map(|tx| &tx.inputs)
+            .filter_map(|input| self.unspent.get(&input.prev).map(|out| (input.prev, out.clone())))
+            .collect();
+        self.apply_block(block)?;
+        let created = block
+            .txs
+            .iter()
+            .flat_map(|tx| {
+                let txid = tx.txid();
+                (0..tx.outputs.len() as u32).map(move |index| OutPoint { txid, index })
+            })
+            .collect();
+        Ok(BlockUndo { created, spent })
+    }
+
+    // connect_block 的逆操作，必须按上链的相反顺序 (从最高块往回) 调用
+    pub fn disconnect_block(&mut self, undo: &BlockUndo) {
+        for op in &undo.created {
+            self.unspent.remove(op);
+            self.spent.remove(op);
+            self.coinbase_heights.remove(op);
+        }
+        self.height = self.height.saturating_sub(1);
+        for (op, out) in &undo.spent {
+            self.spent.remove(op);
+            self.unspent.insert(*op, out.clone());
+        }
+    }
+
+    // 状态根：OutPoint -> (主人, 金额) 放进 Patricia 树 (trie.rs)，树根写进区块头
+    // Patricia 树的根只由内容决定，HashMap 的遍历顺序不影响结果
+    pub fn state_root(&self) -> Digest {
+        let mut trie = PatriciaTrie::new();
+        for (op, out) in &self.unspent {
+            let mut key = op.txid.to_vec();
+            key.extend_from_slice(&op.index.to_be_bytes());
+            let mut value = out.owner.as_bytes().to_vec();
+            value.extend_from_slice(&out.amount.to_be_bytes());
+            trie.insert(&key, value);
+        }
+        trie.root_hash()
+    }
+
+    // 执行完 block 之后的状态根，自己不变：矿工在挖矿之前就要把它写进区块头
+    pub fn root_after(&self, block: &Block<Transaction>) -> Result<Digest, String> {
+        let mut next = self.clone();
+        next.apply_block(block)?;
+        Ok(next.state_root())
+    }
+
+    // 从创世块开始重放整条链得到当前的 UTXO 集合
+    pub fn from_chain(chain: &Chain<Transaction>) -> Result<Self, String> {
+        let mut utxos = UtxoSet::new();
+        for block in &chain.blocks {
+            utxos.apply_block(block).map_err(|e| format!("区块 #{}: {}", block.header.height, e))?;
+        }
+        Ok(utxos)
+    }
+
+    // 钱包的活：从 from 的 UTXO 里凑够 amount + fee，多出来的找零给自己
+    pub fn build_transfer(&self, from: &str, to: &str, amount: u64, fee: u64) -> Result<Transaction, String> {
+        let mut inputs = Vec::new();
+        let mut gathered = 0;
+        for (op, out) in self.outputs_of(from) {
+            if gathered >= amount + fee {
+                break;
+            }
+            inputs.push(TxIn::unsigned(op));
+            gathered += out.amount;
+        }
+        if gathered < amount + fee {
+            return Err(format!("{} 只有 {}，不够付 {} + 手续费 {}", from, gathered, amount, fee));
+        }
+        let mut outputs = vec![TxOut { owner: to.to_string(), amount }];
+        if gathered > amount + fee {
+            outputs.push(TxOut { owner: from.to_string(), amount: gathered - amount - fee });
+        }
+        Ok(Transaction { inputs, outputs, lock_time: 0 })
+    }
+}
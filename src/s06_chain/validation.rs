@@ -0,0 +1,164 @@
+// src/s06_chain/validation.rs
+use std::fmt;
+
+use crate::s05_zk_lab::ex01_merkle::MerkleLeaf;
+use crate::s05_zk_lab::hash::{short_hex, Digest};
+
+use super::block::{merkle_root, Block, BlockHeader};
+use super::chain::Chain;
+use super::pow;
+use super::utxo::{Transaction, TxId, UtxoSet, BLOCK_REWARD};
+
+/*
+区块验证流水线：别的矿工发来一个区块，收之前按顺序过四道关
+
+    1. 链接   : 高度 = 父块高度 + 1，prev_hash = 父块哈希
+    2. 工作量 : 难度字段等于链的难度，区块头哈希满足难度
+    3. Merkle : 区块头里的 merkle_root 等于按交易列表重算的根
+    4. 交易   : 第一笔是 coinbase；其余每笔验签 + 记账 (按顺序对 UTXO 集合执行)；coinbase 不超过 奖励 + 手续费
+    5. 状态根 : 执行完之后 UTXO 集合的根等于区块头里的 state_root (全节点接块时检查，见 node.rs)
+
+    便宜的检查放前面：1、2 只看区块头 (一次哈希)，3 要哈希全部交易，4 要验签、查 UTXO。
+    垃圾区块大多在前两道关就被丢掉，不会浪费验签的算力。
+
+错误类型是 BlockError 而不是 String：调用方可以按"哪道关没过"分别处理
+(比如 PoW 不合格的区块直接拉黑发送者，交易无效的区块可能只是自己的 UTXO 集合落后了)。
+*/
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockError {
+    BadHeight { expected: u64, found: u64 },
+    BrokenLink { height: u64, expected: Digest, found: Digest },
+    UnknownParent { height: u64, prev_hash: Digest },
+    WrongDifficulty { height: u64, expected: u32, found: u32 },
+    InsufficientWork { height: u64, hash: Digest, zero_bits: u32, required: u32 },
+    MerkleMismatch { height: u64 },
+    NoCoinbase,
+    BadTransaction { index: usize, txid: TxId, reason: String },
+    CoinbaseTooLarge { claimed: u64, allowed: u64 },
+    // 金额累加超出 u64 (what 说明是哪一项)：回绕之后的小数字会骗过"不超过"的检查，只能整块拒绝
+    AmountOverflow { height: u64, what: &'static str },
+    StateRootMismatch { height: u64, claimed: Digest, computed: Digest },
+}
+
+impl BlockError {
+    // 流水线的第几道关
+    pub fn stage(&self) -> &'static str {
+        match self {
+            BlockError::BadHeight { .. } | BlockError::BrokenLink { .. } | BlockError::UnknownParent { .. } => "链接",
+            BlockError::WrongDifficulty { .. } | BlockError::InsufficientWork { .. } => "工作量",
+            BlockError::MerkleMismatch { .. } => "Merkle",
+            BlockError::NoCoinbase
+            | BlockError::BadTransaction { .. }
+            | BlockError::CoinbaseTooLarge { .. }
+            | BlockError::AmountOverflow { .. } => "交易",
+            BlockError::StateRootMismatch { .. } => "状态根",
+        }
+    }
+}
+
+impl fmt::Display for BlockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BlockError::BadHeight { expected, found } => write!(f, "区块高度是 {}，应该是 {}", found, expected),
+            BlockError::BrokenLink { height, expected, found } => write!(
+                f,
+                "区块 #{} 的 prev_hash = {}..，但上一个区块的哈希是 {}..",
+                height,
+                short_hex(found),
+                short_hex(expected)
+            ),
+            BlockError::UnknownParent { height, prev_hash } => write!(f, "区块 #{} 的父块 {}.. 没见过", height, short_hex(prev_hash)),
+            BlockError::WrongDifficulty { height, expected, found } => write!(f, "区块 #{} 的难度是 {}，链要求 {}", height, found, expected),
+            BlockError::InsufficientWork { height, hash, zero_bits, required } => write!(
+                f,
+                "区块 #{} 的哈希 {}.. 只有 {} 个前导 0 比特，不满足难度 {}",
+                height,
+                short_hex(hash),
+                zero_bits,
+                required
+            ),
+            BlockError::MerkleMismatch { height } => write!(f, "区块 #{} 的 Merkle 根和交易列表对不上", height),
+            BlockError::NoCoinbase => write!(f, "区块的第一笔交易必须是 coinbase"),
+            BlockError::BadTransaction { index, txid, reason } => write!(f, "第 {} 笔交易 {}: {}", index, short_hex(txid), reason),
+            BlockError::CoinbaseTooLarge { claimed, allowed } => {
+                write!(f, "coinbase 领了 {}，最多只能领 出块奖励 + 手续费 = {}", claimed, allowed)
+            }
+            BlockError::AmountOverflow { height, what } => write!(f, "区块 #{}: {}超出 u64 范围", height, what),
+            BlockError::StateRootMismatch { height, claimed, computed } => write!(
+                f,
+                "区块 #{} 的 state_root 是 {}..，执行完得到的是 {}..",
+                height,
+                short_hex(claimed),
+                short_hex(computed)
+            ),
+        }
+    }
+}
+
+// 第 1~2 道关：只看区块头。轻节点没有交易列表，只能做到这一步 (见 spv.rs)
+// prev 为 None 表示 header 应该是创世块
+pub fn check_link_and_work(prev: Option<&BlockHeader>, h: &BlockHeader, difficulty: u32) -> Result<(), BlockError> {
+    let (expected_height, expected_prev) = match prev {
+        Some(p) => (p.height + 1, p.hash()),
+        None => (0, [0u8; 32]),
+    };
+    if h.height != expected_height {
+        return Err(BlockError::BadHeight { expected: expected_height, found: h.height });
+    }
+    if h.prev_hash != expected_prev {
+        return Err(BlockError::BrokenLink { height: h.height, expected: expected_prev, found: h.prev_hash });
+    }
+    if h.difficulty != difficulty {
+        return Err(BlockError::WrongDifficulty { height: h.height, expected: difficulty, found: h.difficulty });
+    }
+    if !pow::meets_target(h) {
+        let hash = h.hash();
+        return Err(BlockError::InsufficientWork { height: h.height, hash, zero_bits: pow::leading_zero_bits(&hash), required: h.difficulty });
+    }
+    Ok(())
+}
+
+// 第 1~3 道关：只依赖区块本身和父块，任何交易类型都适用 (Chain::append / validate 也用它)
+pub fn check_header<T: MerkleLeaf>(prev: Option<&Block<T>>, block: &Block<T>, difficulty: u32) -> Result<(), BlockError> {
+    check_link_and_work(prev.map(|p| &p.header), &block.header, difficulty)?;
+    if block.header.merkle_root != merkle_root(&block.txs) {
+        return Err(BlockError::MerkleMismatch { height: block.header.height });
+    }
+    Ok(())
+}
+
+// 第 4 道关：对照父块之后的 UTXO 集合，按顺序执行区块里的交易 (在副本上，不改 utxos)
+pub fn check_transactions(utxos: &UtxoSet, block: &Block<Transaction>) -> Result<(), BlockError> {
+    let Some((coinbase, txs)) = block.txs.split_first() else {
+        return Err(BlockError::NoCoinbase);
+    };
+    if !coinbase.is_coinbase() {
+        return Err(BlockError::NoCoinbase);
+    }
+    let height = block.header.height;
+    let overflow = |what| BlockError::AmountOverflow { height, what };
+    let mut next = utxos.clone();
+    let mut fees = 0u64;
+    for (i, tx) in txs.iter().enumerate() {
+        let bad = |reason| BlockError::BadTransaction { index: i + 1, txid: tx.txid(), reason };
+        next.verify_signatures(tx).map_err(bad)?;
+        let fee = next.validate_tx(tx).map_err(bad)?;
+        fees = fees.checked_add(fee).ok_or(overflow("手续费总额"))?;
+        next.apply_tx(tx);
+    }
+    let claimed = coinbase.output_total().ok_or(overflow("coinbase 输出总额"))?;
+    let allowed = BLOCK_REWARD.checked_add(fees).ok_or(overflow("出块奖励 + 手续费"))?;
+    if claimed > allowed {
+        return Err(BlockError::CoinbaseTooLarge { claimed, allowed });
+    }
+    Ok(())
+}
+
+// 完整流水线：block 要接在 chain 的最高块后面
+// 链上已有的区块默认是验证过的，从创世块重放一遍得到当前的 UTXO 集合 (玩具链只有几个块，真实节点会一直维护着它)
+pub fn validate_block(chain: &Chain<Transaction>, block: &Block<Transaction>) -> Result<(), BlockError> {
+    check_header(Some(chain.tip()), block, chain.difficulty)?;
+    let utxos = UtxoSet::from_chain(chain).expect("blocks already on the chain are valid");
+    check_transactions(&utxos, block)
+}
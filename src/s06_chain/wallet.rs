@@ -0,0 +1,91 @@
+// src/s06_chain/wallet.rs
+use crate::common::rng::SimpleRng;
+use crate::s05_zk_lab::crypto::schnorr::{self, Keypair};
+use crate::s05_zk_lab::hash::sha256;
+use crate::s05_zk_lab::math::curve::CurvePoint;
+use crate::s05_zk_lab::math::group::Group;
+
+use super::encoding;
+use super::utxo::{Transaction, UtxoSet, Witness};
+
+/*
+钱包 = 一把 EC-Schnorr 私钥 (S05 crypto::schnorr，跑在 math::curve 的玩具曲线上) + 它派生出的地址
+
+    地址 = 公钥哈希的前 10 字节 (比特币 P2PKH 的思路)，链内部写成 addr_<十六进制>，给人看时用 Base58Check / Bech32：
+        输出上只写地址，不写公钥；花的时候才把公钥亮出来，验证者检查 address(公钥) == 地址。
+    签名：对交易不含签名的编码 (Transaction::encode) 签 Schnorr 签名。
+
+⚠️ 玩具曲线只有 1019 个点，私钥暴力一下就出来了，这里只演示流程。
+*/
+
+// Base58Check 的版本字节和 Bech32 的前缀 (见 encoding.rs)
+pub const ADDRESS_VERSION: u8 = 0x00;
+pub const ADDRESS_HRP: &str = "toy";
+
+// 公钥哈希的前 10 字节：地址的原始字节，下面几种写法都是它的不同编码
+pub fn address_hash(public: &CurvePoint) -> Vec<u8> {
+    sha256(&public.encode().to_be_bytes())[..10].to_vec()
+}
+
+// 链内部用的写法 (UTXO 的 owner、交易池、浏览器都认这个)
+pub fn address(public: &CurvePoint) -> String {
+    format!("addr_{}", hex::encode(address_hash(public)))
+}
+
+// 给人看、给人抄的写法：带校验和，抄错了能发现
+pub fn base58_address(public: &CurvePoint) -> String {
+    encoding::base58check_encode(ADDRESS_VERSION, &address_hash(public))
+}
+
+pub fn bech32_address(public: &CurvePoint) -> String {
+    encoding::bech32_encode(ADDRESS_HRP, &address_hash(public))
+}
+
+// 三种写法都接受，统一换成内部写法；校验和、版本、前缀、长度任何一项不对都报错
+pub fn parse_address(text: &str) -> Result<String, String> {
+    let text = text.trim();
+    let hash = if let Some(hex_part) = text.strip_prefix("addr_") {
+        hex::decode(hex_part).map_err(|e| format!("十六进制不合法: {}", e))?
+    } else if text.to_ascii_lowercase().starts_with(&format!("{}1", ADDRESS_HRP)) {
+        let (hrp, payload) = encoding::bech32_decode(text)?;
+        if hrp != ADDRESS_HRP {
+            return Err(format!("前缀是 {}，应该是 {}", hrp, ADDRESS_HRP));
+        }
+        payload
+    } else {
+        let (version, payload) = encoding::base58check_decode(text)?;
+        if version != ADDRESS_VERSION {
+            return Err(format!("版本字节是 0x{:02x}，应该是 0x{:02x}", version, ADDRESS_VERSION));
+        }
+        payload
+    };
+    if hash.len() != 10 {
+        return Err(format!("公钥哈希应该是 10 字节，这里是 {} 字节", hash.len()));
+    }
+    Ok(format!("addr_{}", hex::encode(hash)))
+}
+
+pub struct Wallet {
+    pub name: String,
+    pub keys: Keypair<CurvePoint>,
+}
+
+impl Wallet {
+    pub fn generate(name: &str, rng: &mut SimpleRng) -> Self {
+        Wallet { name: name.to_string(), keys: Keypair::generate(rng) }
+    }
+
+    pub fn address(&self) -> String {
+        address(&self.keys.public)
+    }
+
+    // 给交易里所有"花我的钱"的输入签名；别人的输入不碰 (多方合资的交易各签各的)
+    pub fn sign(&self, tx: &mut Transaction, utxos: &UtxoSet, rng: &mut SimpleRng) {
+        let mine: Vec<_> = utxos.outputs_of(&self.address()).into_iter().map(|(op, _)| op).collect();
+        let sighash = tx.encode();
+        for input in tx.inputs.iter_mut().filter(|input| mine.contains(&input.prev)) {
+            let signature = schnorr::sign(&self.keys, &sighash, rng);
+            input.witness = Some(Witness { public: self.keys.public, signature });
+        }
+    }
+}
@@ -0,0 +1,148 @@
+// src/s07_vm/asm.rs
+use std::collections::{BTreeSet, HashMap};
+
+use super::opcode::{decode, encode, Instr};
+
+/*
+汇编器 / 反汇编器：助记符文本 <-> 字节码
+
+    源码格式，一行一条：
+        ; 分号后面是注释
+        loop:              标签 = 下一条指令的字节偏移
+        PUSH 42            十进制
+        PUSH 0xff          十六进制
+        PUSH loop          标签引用，汇编时换成偏移
+        DUP 2 / SWAP 1     深度 1..=16
+        ADD                其它指令没有操作数；大小写都行
+    PUSH 的编码长度固定 (9 字节)，所以第一遍就能算出每个标签的偏移，第二遍再把标签换成数字。
+
+    反汇编：字节码 -> 源码。紧跟在 JUMP / JUMPI 前面、并且落在指令开头的 PUSH 当作跳转目标，
+    换成 L<偏移> 标签，这样反汇编的结果可以直接改了再汇编回去。
+*/
+
+// 没有操作数的指令，按助记符查
+const SIMPLE: [Instr; 24] = [
+    Instr::Stop,
+    Instr::Pop,
+    Instr::Add,
+    Instr::Sub,
+    Instr::Mul,
+    Instr::Div,
+    Instr::Lt,
+    Instr::Gt,
+    Instr::Eq,
+    Instr::IsZero,
+    Instr::Jump,
+    Instr::JumpI,
+    Instr::Load,
+    Instr::Store,
+    Instr::Caller,
+    Instr::CallValue,
+    Instr::Address,
+    Instr::Arg,
+    Instr::Balance,
+    Instr::Call,
+    Instr::Log,
+    Instr::Emit,
+    Instr::Return,
+    Instr::Revert,
+];
+
+fn parse_number(text: &str) -> Option<u64> {
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+fn is_label(text: &str) -> bool {
+    text.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_') && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+pub fn assemble(source: &str) -> Result<Vec<u8>, String> {
+    // 第一遍：解析每一行，记下标签的偏移
+    let mut labels: HashMap<&str, u64> = HashMap::new();
+    // (行号, 指令, PUSH 引用的标签——等第二遍再换成偏移)
+    let mut lines: Vec<(usize, Instr, Option<&str>)> = Vec::new();
+    let mut offset = 0u64;
+    for (index, raw) in source.lines().enumerate() {
+        let line_no = index + 1;
+        let line = raw.split(';').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(name) = line.strip_suffix(':') {
+            let name = name.trim();
+            if !is_label(name) {
+                return Err(format!("第 {} 行: 标签名 {:?} 只能用字母、数字、下划线，且不能以数字开头", line_no, name));
+            }
+            if labels.insert(name, offset).is_some() {
+                return Err(format!("第 {} 行: 标签 {} 重复定义", line_no, name));
+            }
+            continue;
+        }
+        let mut words = line.split_whitespace();
+        let mnemonic = words.next().unwrap_or("").to_uppercase();
+        let operand = words.next();
+        if let Some(extra) = words.next() {
+            return Err(format!("第 {} 行: 多余的 {:?}", line_no, extra));
+        }
+        let (instr, label) = match (mnemonic.as_str(), operand) {
+            ("PUSH", Some(text)) => match parse_number(text) {
+                Some(value) => (Instr::Push(value), None),
+                None if is_label(text) => (Instr::Push(0), Some(text)),
+                None => return Err(format!("第 {} 行: PUSH 的操作数 {:?} 既不是数字也不是标签", line_no, text)),
+            },
+            ("DUP" | "SWAP", Some(text)) => {
+                let depth = text.parse::<u8>().ok().filter(|n| (1..=16).contains(n)).ok_or_else(|| format!("第 {} 行: {} 的深度 {:?} 不在 1..=16", line_no, mnemonic, text))?;
+                (if mnemonic == "DUP" { Instr::Dup(depth) } else { Instr::Swap(depth) }, None)
+            }
+            ("PUSH" | "DUP" | "SWAP", None) => return Err(format!("第 {} 行: {} 缺操作数", line_no, mnemonic)),
+            (name, operand) => {
+                let instr = SIMPLE.iter().find(|instr| instr.to_string() == name).ok_or_else(|| format!("第 {} 行: 不认识的指令 {}", line_no, name))?;
+                if let Some(text) = operand {
+                    return Err(format!("第 {} 行: {} 没有操作数，多了 {:?}", line_no, name, text));
+                }
+                (*instr, None)
+            }
+        };
+        offset += instr.size() as u64;
+        lines.push((line_no, instr, label));
+    }
+
+    // 第二遍：标签换成偏移
+    let mut program = Vec::with_capacity(lines.len());
+    for (line_no, instr, label) in lines {
+        program.push(match label {
+            Some(name) => Instr::Push(*labels.get(name).ok_or_else(|| format!("第 {} 行: 标签 {} 没有定义", line_no, name))?),
+            None => instr,
+        });
+    }
+    Ok(encode(&program))
+}
+
+pub fn disassemble(code: &[u8]) -> Result<String, String> {
+    let program = decode(code)?;
+    let starts: BTreeSet<usize> = program.iter().map(|(pc, _)| *pc).collect();
+    // 跳转目标：PUSH x; JUMP/JUMPI，并且 x 是某条指令的开头
+    let targets: BTreeSet<usize> = program
+        .windows(2)
+        .filter_map(|pair| match pair {
+            [(_, Instr::Push(target)), (_, Instr::Jump | Instr::JumpI)] => usize::try_from(*target).ok().filter(|t| starts.contains(t)),
+            _ => None,
+        })
+        .collect();
+
+    let mut source = String::new();
+    for (i, (pc, instr)) in program.iter().enumerate() {
+        if targets.contains(pc) {
+            source.push_str(&format!("L{}:\n", pc));
+        }
+        let jumps_next = matches!(program.get(i + 1), Some((_, Instr::Jump | Instr::JumpI)));
+        match instr {
+            Instr::Push(target) if jumps_next && usize::try_from(*target).is_ok_and(|t| targets.contains(&t)) => source.push_str(&format!("    PUSH L{}\n", target)),
+            other => source.push_str(&format!("    {}\n", other)),
+        }
+    }
+    Ok(source)
+}
@@ -0,0 +1,181 @@
+// src/s07_vm/chain.rs
+use std::borrow::Cow;
+
+use crate::s05_zk_lab::ex01_merkle::MerkleLeaf;
+use crate::s05_zk_lab::hash::short_hex;
+use crate::s06_chain::block::Block;
+use crate::s06_chain::chain::Chain;
+use crate::s06_chain::gas::{self, GasMeter};
+use crate::s06_chain::pow;
+
+use super::receipts::{self, BlockReceipts, LogFilter, LogQuery};
+use super::vm::{LogEntry, Vm, VmError};
+use super::world::{Address, World};
+
+/*
+合约链：S06 的账户模型链 + 虚拟机
+
+    交易有三种：转账、部署合约、调用合约。执行流程和 S06 account.rs 的 execute 一模一样：
+        1. 检查 nonce、gas_limit 够不够固定开销、余额付不付得起 gas_limit × gas_price (不满足不能上链)
+        2. 预扣 gas 费，nonce + 1
+        3. 交给 Vm 执行；失败 (out of gas、Revert……) 就撤销执行的改动，但 gas 照收
+        4. 没用完的 gas 退回，用掉的给矿工
+    区块还是 S06 的 Block<T> / Chain<T>，区块头里的 state_root 是执行完之后的世界状态根。
+    每个区块的收据 (连同日志的 Bloom 过滤器) 按高度存在 VmChain::receipts 里，浏览器按主题查日志用 (见 receipts.rs)。
+*/
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxKind {
+    Transfer { to: Address, amount: u64 },
+    Deploy { code: Vec<u8>, value: u64 },
+    Call { to: Address, value: u64, input: Vec<u64> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmTx {
+    pub from: Address,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub gas_price: u64,
+    pub kind: TxKind,
+}
+
+impl VmTx {
+    // 和 AccountTx 一样，只用来算 Merkle 根
+    pub fn encode(&self) -> Vec<u8> {
+        format!("{}|{}|{}|{}|{:?}", self.from, self.nonce, self.gas_limit, self.gas_price, self.kind).into_bytes()
+    }
+}
+
+impl MerkleLeaf for VmTx {
+    fn leaf_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.encode())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxStatus {
+    // 调用的返回值；部署成功时是 0
+    Success { ret: u64 },
+    Failed(VmError),
+}
+
+#[derive(Debug, Clone)]
+pub struct Receipt {
+    pub status: TxStatus,
+    pub gas_used: u64,
+    pub fee: u64,
+    pub logs: Vec<LogEntry>,
+    pub created: Option<Address>,
+}
+
+// Err：交易不合法，不能上链，状态不变；Ok：交易上链了，收据里写着执行结果
+pub fn execute(world: &mut World, tx: &VmTx, coinbase: Address) -> Result<Receipt, String> {
+    if tx.nonce != world.nonce(tx.from) {
+        return Err(format!("nonce 应该是 {}，交易写的是 {}", world.nonce(tx.from), tx.nonce));
+    }
+    if tx.gas_limit < gas::TX_BASE {
+        return Err(format!("gas_limit {} 连固定开销 {} 都不够", tx.gas_limit, gas::TX_BASE));
+    }
+    let max_fee = tx.gas_limit * tx.gas_price;
+    if world.balance(tx.from) < max_fee {
+        return Err(format!("余额 {} 付不起 gas_limit × gas_price = {}", world.balance(tx.from), max_fee));
+    }
+
+    // 预扣 + nonce：不管后面执行成不成功都生效
+    let sender = world.account_mut(tx.from);
+    sender.balance -= max_fee;
+    sender.nonce += 1;
+
+    let mut meter = GasMeter::new(tx.gas_limit);
+    meter.charge(gas::TX_BASE).expect("gas_limit checked above");
+    let checkpoint = world.clone();
+    let mut vm = Vm::new(world, meter);
+    let mut created = None;
+    let result = match &tx.kind {
+        TxKind::Transfer { to, amount } => vm.call(tx.from, *to, *amount, Vec::new()),
+        TxKind::Deploy { code, value } => vm.deploy(tx.from, tx.nonce, code, *value).map(|address| {
+            created = Some(address);
+            0
+        }),
+        TxKind::Call { to, value, input } => vm.call(tx.from, *to, *value, input.clone()),
+    };
+    let (mut meter, mut logs) = (vm.meter, std::mem::take(&mut vm.logs));
+    let status = match result {
+        Ok(ret) => TxStatus::Success { ret },
+        Err(e) => {
+            // 和 S06 一样：out of gas 把 gas_limit 全部烧掉
+            if let VmError::OutOfGas(_) = e {
+                meter.used = meter.limit;
+            }
+            *world = checkpoint;
+            logs.clear();
+            created = None;
+            TxStatus::Failed(e)
+        }
+    };
+
+    let fee = meter.used * tx.gas_price;
+    world.account_mut(tx.from).balance += max_fee - fee;
+    world.credit(coinbase, fee);
+    Ok(Receipt { status, gas_used: meter.used, fee, logs, created })
+}
+
+// 任何一笔交易不合法、或者执行结果和区块头里的状态根不一致，整块拒绝，状态不变
+pub fn apply_block(world: &mut World, block: &Block<VmTx>, coinbase: Address) -> Result<Vec<Receipt>, String> {
+    let mut next = world.clone();
+    let mut receipts = Vec::with_capacity(block.txs.len());
+    for (i, tx) in block.txs.iter().enumerate() {
+        receipts.push(execute(&mut next, tx, coinbase).map_err(|e| format!("第 {} 笔交易: {}", i, e))?);
+    }
+    let root = next.state_root();
+    if root != block.header.state_root {
+        return Err(format!("状态根不一致：区块头写的是 {}..，执行结果是 {}..", short_hex(&block.header.state_root), short_hex(&root)));
+    }
+    *world = next;
+    Ok(receipts)
+}
+
+// 一个全节点：链 + 世界状态 + 每个区块的收据，自己出块
+pub struct VmChain {
+    pub chain: Chain<VmTx>,
+    pub world: World,
+    pub coinbase: Address,
+    // receipts[h] 是区块 #h 的收据 (创世块没有交易，收据为空)
+    pub receipts: Vec<BlockReceipts>,
+}
+
+impl VmChain {
+    pub fn new(difficulty: u32, coinbase: Address, allocations: &[(Address, u64)]) -> Self {
+        let mut world = World::new();
+        for &(address, amount) in allocations {
+            world.credit(address, amount);
+        }
+        VmChain { chain: Chain::new(difficulty, vec![]), world, coinbase, receipts: vec![BlockReceipts::new(0, Vec::new())] }
+    }
+
+    // 先在副本上执行拿到状态根，写进区块头、挖矿，再按"收到别人的区块"的流程验证并上链
+    pub fn produce(&mut self, txs: Vec<VmTx>) -> Result<Vec<Receipt>, String> {
+        let mut block = self.chain.candidate(txs);
+        let mut next = self.world.clone();
+        for tx in &block.txs {
+            execute(&mut next, tx, self.coinbase)?;
+        }
+        block.header.state_root = next.state_root();
+        block.header = pow::mine(block.header).header;
+        let receipts = apply_block(&mut self.world, &block, self.coinbase)?;
+        self.chain.append(block).map_err(|e| e.to_string())?;
+        self.receipts.push(BlockReceipts::new(self.chain.height(), receipts.clone()));
+        Ok(receipts)
+    }
+
+    // 区块 #height 的第 index 笔交易和它的收据
+    pub fn receipt(&self, height: u64, index: usize) -> Option<(&VmTx, &Receipt)> {
+        let tx = self.chain.blocks.get(height as usize)?.txs.get(index)?;
+        Some((tx, self.receipts.get(height as usize)?.receipts.get(index)?))
+    }
+
+    pub fn logs(&self, filter: &LogFilter) -> LogQuery {
+        receipts::query(&self.receipts, filter)
+    }
+}
@@ -0,0 +1,277 @@
+// src/s07_vm/ex01_stack_vm.rs
+use crate::s05_zk_lab::hash::short_hex;
+use crate::s06_chain::gas::GasMeter;
+
+use super::chain::{Receipt, TxKind, TxStatus, VmChain, VmTx};
+use super::opcode::{decode, encode, Instr};
+use super::vm::Vm;
+use super::world::{address_of, short_address, Address, World};
+
+/*
+业务场景：S06 的账户链只会转账。要让链上跑"程序" (计数器、代币、拍卖……)，
+    每个节点都得用完全相同的规则执行同一段代码，得到完全相同的状态 —— 这就是合约虚拟机。
+
+本练习：
+    1. 一个计数器合约：用 Instr 写出来，编码成字节码，再反汇编回来；带跟踪地执行一次，看每一步的栈
+    2. 上链：Alice 部署计数器，Alice、Bob 发交易调用，打印收据 (返回值、gas、日志) 和状态根
+    3. 合约调合约：代理合约用 CALL 转发给计数器，计数器看到的 CALLER 是代理合约
+    4. ❌ 失败的交易：死循环 out of gas、未知选择子 REVERT、跳进立即数、坏字节码、余额不够
+       失败的执行被撤销，但交易照样上链、gas 照样收
+*/
+
+const DIFFICULTY: u32 = 8;
+const GAS_LIMIT: u64 = 200_000;
+const GAS_PRICE: u64 = 1;
+
+// 计数器的选择子 (input[0])
+const INC: u64 = 0;
+const GET: u64 = 1;
+
+// 一段指令编码后的长度 = 下一条指令的字节偏移，用来回填跳转目标
+fn offset(program: &[Instr]) -> u64 {
+    program.iter().map(|instr| instr.size() as u64).sum()
+}
+
+// storage[0] = 计数，storage[1] = 最后一次 +1 的调用者
+fn counter_code() -> Vec<u8> {
+    let mut program = vec![
+        Instr::Push(0),
+        Instr::Arg,
+        // 选择子 == INC (也就是 0)
+        Instr::Dup(1),
+        Instr::IsZero,
+        Instr::Push(0), // -> inc，下面回填
+        Instr::JumpI,
+        // 选择子 == GET
+        Instr::Dup(1),
+        Instr::Push(GET),
+        Instr::Eq,
+        Instr::Push(0), // -> get，下面回填
+        Instr::JumpI,
+        Instr::Revert,
+    ];
+    program[4] = Instr::Push(offset(&program));
+    program.extend([
+        Instr::Pop,
+        Instr::Push(0),
+        Instr::Load,
+        Instr::Push(1),
+        Instr::Add,
+        Instr::Dup(1),
+        Instr::Push(0),
+        Instr::Store,
+        Instr::Caller,
+        Instr::Push(1),
+        Instr::Store,
+        Instr::Dup(1),
+        Instr::Log,
+        Instr::Return,
+    ]);
+    program[9] = Instr::Push(offset(&program));
+    program.extend([Instr::Pop, Instr::Push(0), Instr::Load, Instr::Return]);
+    encode(&program)
+}
+
+// 把 input[0] 原样转发给 target；调用失败自己也 REVERT
+fn proxy_code(target: Address) -> Vec<u8> {
+    let mut program = vec![
+        Instr::Push(0),
+        Instr::Arg,
+        Instr::Push(0),
+        Instr::Push(target),
+        Instr::Call,
+        Instr::IsZero,
+        Instr::Push(0), // -> fail，下面回填
+        Instr::JumpI,
+        Instr::Return,
+    ];
+    program[6] = Instr::Push(offset(&program));
+    program.push(Instr::Revert);
+    encode(&program)
+}
+
+fn show_code(code: &[u8]) {
+    let hex = hex::encode(code);
+    println!("  字节码 ({} 字节): {}{}", code.len(), &hex[..hex.len().min(64)], if hex.len() > 64 { ".." } else { "" });
+    match decode(code) {
+        Ok(program) => {
+            for (pc, instr) in program {
+                println!("    {:>4}  {}", pc, instr);
+            }
+        }
+        Err(e) => println!("  ❌ 反汇编失败 (不应该发生): {}", e),
+    }
+}
+
+fn show_receipt(label: &str, receipt: &Receipt) {
+    let result = match &receipt.status {
+        TxStatus::Success { ret } => format!("✅ 返回 {}", ret),
+        TxStatus::Failed(e) => format!("❌ {}", e),
+    };
+    println!("  {}: {}  (gas {}，手续费 {})", label, result, receipt.gas_used, receipt.fee);
+    if let Some(address) = receipt.created {
+        println!("      新合约地址 {}", short_address(address));
+    }
+    for log in &receipt.logs {
+        println!("      日志 {}", log);
+    }
+}
+
+struct Sender {
+    name: &'static str,
+    address: Address,
+}
+
+impl Sender {
+    fn new(name: &'static str) -> Self {
+        Sender { name, address: address_of(name) }
+    }
+
+    fn tx(&self, chain: &VmChain, offset: u64, kind: TxKind) -> VmTx {
+        VmTx { from: self.address, nonce: chain.world.nonce(self.address) + offset, gas_limit: GAS_LIMIT, gas_price: GAS_PRICE, kind }
+    }
+}
+
+// 打包一块，逐笔打印收据
+fn mine(chain: &mut VmChain, labels: &[&str], txs: Vec<VmTx>) -> Vec<Receipt> {
+    match chain.produce(txs) {
+        Ok(receipts) => {
+            println!("  ⛏️ #{} state_root {}..", chain.chain.height(), short_hex(&chain.chain.tip().header.state_root));
+            for (label, receipt) in labels.iter().zip(&receipts) {
+                show_receipt(label, receipt);
+            }
+            receipts
+        }
+        Err(e) => {
+            println!("  ❌ 出块失败 (不应该发生): {}", e);
+            Vec::new()
+        }
+    }
+}
+
+pub fn run() {
+    println!("--- S07 Ex01: 栈式虚拟机 (字节码合约) ---");
+    let alice = Sender::new("Alice");
+    let bob = Sender::new("Bob");
+    let miner = Sender::new("Miner");
+    let code = counter_code();
+
+    // ==========================================
+    // 1. 计数器合约：字节码与执行跟踪
+    // ==========================================
+    println!("\n[1] 计数器合约：input[0] = {} 加一并返回，= {} 读取，其它 REVERT", INC, GET);
+    show_code(&code);
+    let mut scratch = World::new();
+    scratch.credit(alice.address, 1_000_000);
+    let mut vm = Vm::new(&mut scratch, GasMeter::new(GAS_LIMIT)).with_trace();
+    let deployed = vm.deploy(alice.address, 0, &code, 0).and_then(|counter| {
+        let deploy_gas = vm.meter.used;
+        vm.take_trace();
+        let ret = vm.call(alice.address, counter, 0, vec![INC])?;
+        Ok((deploy_gas, ret))
+    });
+    match deployed {
+        Ok((deploy_gas, ret)) => {
+            println!("  部署用了 {} gas；调用 INC 的执行跟踪 (偏移、指令、执行前的栈):", deploy_gas);
+            for line in vm.take_trace() {
+                println!("    {}", line);
+            }
+            let logs: Vec<String> = vm.logs.iter().map(|log| log.to_string()).collect();
+            println!("  返回 {}，这次调用用了 {} gas，日志 [{}]", ret, vm.meter.used - deploy_gas, logs.join(", "));
+        }
+        Err(e) => println!("  ❌ {} (不应该发生)", e),
+    }
+
+    // ==========================================
+    // 2. 上链：部署 + 调用
+    // ==========================================
+    println!("\n[2] 上链：Alice {}、Bob {}，gas_price {}", short_address(alice.address), short_address(bob.address), GAS_PRICE);
+    let mut chain = VmChain::new(DIFFICULTY, miner.address, &[(alice.address, 1_000_000), (bob.address, 1_000_000)]);
+    println!("  创世 state_root {}..", short_hex(&chain.world.state_root()));
+    let deploy = alice.tx(&chain, 0, TxKind::Deploy { code: code.clone(), value: 0 });
+    let receipts = mine(&mut chain, &["Alice 部署计数器"], vec![deploy]);
+    let Some(counter) = receipts.first().and_then(|r| r.created) else {
+        println!("  ❌ 计数器没部署上 (不应该发生)");
+        return;
+    };
+    let call = |to: Address, selector: u64| TxKind::Call { to, value: 0, input: vec![selector] };
+    let txs = vec![alice.tx(&chain, 0, call(counter, INC)), bob.tx(&chain, 0, call(counter, INC)), alice.tx(&chain, 1, call(counter, INC)), bob.tx(&chain, 1, call(counter, GET))];
+    mine(&mut chain, &["Alice INC", "Bob INC", "Alice INC", "Bob GET"], txs);
+    let last = chain.world.storage(counter, 1);
+    println!(
+        "  计数器存储: [0] = {}，[1] = {} ({})",
+        chain.world.storage(counter, 0),
+        short_address(last),
+        if last == alice.address { alice.name } else { bob.name }
+    );
+    if let Some(account) = chain.world.account(counter) {
+        println!("  计数器账户: nonce {}，代码 {} 字节，存储根 {}..", account.nonce, account.code.len(), short_hex(&account.storage_root()));
+    }
+
+    // ==========================================
+    // 3. 合约调合约
+    // ==========================================
+    println!("\n[3] 代理合约：把 input[0] 用 CALL 转发给计数器");
+    let proxy = proxy_code(counter);
+    show_code(&proxy);
+    let deploy = bob.tx(&chain, 0, TxKind::Deploy { code: proxy, value: 0 });
+    let receipts = mine(&mut chain, &["Bob 部署代理"], vec![deploy]);
+    let Some(proxy) = receipts.first().and_then(|r| r.created) else {
+        println!("  ❌ 代理没部署上 (不应该发生)");
+        return;
+    };
+    let txs = vec![alice.tx(&chain, 0, call(proxy, INC)), alice.tx(&chain, 1, call(proxy, 9))];
+    mine(&mut chain, &["Alice 通过代理 INC", "Alice 通过代理调选择子 9"], txs);
+    let last = chain.world.storage(counter, 1);
+    println!("  计数器 [0] = {}，最后的调用者 {} {}", chain.world.storage(counter, 0), short_address(last), if last == proxy { "= 代理合约，不是 Alice" } else { "(不应该发生)" });
+
+    // ==========================================
+    // 4. 失败的交易
+    // ==========================================
+    println!("\n[4] ❌ 失败的交易 (执行撤销，gas 照收)");
+    let forever = encode(&[Instr::Push(0), Instr::Jump]);
+    let bad_jump = encode(&[Instr::Push(1), Instr::Jump]);
+    let underflow = encode(&[Instr::Push(1), Instr::Add]);
+    let txs = vec![
+        alice.tx(&chain, 0, TxKind::Deploy { code: forever, value: 0 }),
+        alice.tx(&chain, 1, TxKind::Deploy { code: bad_jump, value: 0 }),
+        alice.tx(&chain, 2, TxKind::Deploy { code: underflow, value: 0 }),
+        alice.tx(&chain, 3, TxKind::Deploy { code: vec![0x60, 0x00, 0x01], value: 0 }),
+    ];
+    let receipts = mine(&mut chain, &["部署死循环", "部署跳进立即数", "部署栈下溢", "部署截断的 PUSH"], txs);
+    let created: Vec<Address> = receipts.iter().filter_map(|r| r.created).collect();
+    let &[forever, bad_jump, underflow] = created.as_slice() else {
+        println!("  ❌ 前三个合约应该部署成功 (不应该发生)");
+        return;
+    };
+
+    let before = chain.world.balance(bob.address);
+    let txs = vec![
+        bob.tx(&chain, 0, call(forever, 0)),
+        bob.tx(&chain, 1, call(bad_jump, 0)),
+        bob.tx(&chain, 2, call(underflow, 0)),
+        bob.tx(&chain, 3, call(counter, 7)),
+        bob.tx(&chain, 4, TxKind::Call { to: counter, value: 10_000_000, input: vec![INC] }),
+        bob.tx(&chain, 5, TxKind::Transfer { to: alice.address, amount: 10_000_000 }),
+    ];
+    mine(&mut chain, &["调用死循环", "调用跳进立即数", "调用栈下溢", "计数器选择子 7", "带 10000000 调用计数器", "转给 Alice 10000000"], txs);
+    println!("  Bob 余额 {} -> {}，少掉的都是手续费；计数器还是 {}", before, chain.world.balance(bob.address), chain.world.storage(counter, 0));
+    println!("  Miner 累计收到手续费 {}；链高 {}", chain.world.balance(miner.address), chain.chain.height());
+}
+
+/*
+关键点总结：
+    1. 字节码是确定性的：
+        同样的代码、同样的输入、同样的状态，每个节点执行出同样的结果和同样的 gas。所以区块头只需要写执行后的
+        state_root，收到区块的节点自己重放一遍，对不上就拒绝 (apply_block)。
+
+    2. gas 是停机问题的工程解：
+        节点没法判断一段代码会不会停，只能按步收费、扣到 gas_limit 为止。死循环不会卡死网络，只会烧掉发送者的 gas_limit。
+
+    3. 失败 = 撤销执行，不撤销交易：
+        REVERT、非法跳转、栈下溢都把合约状态恢复到调用前；但 nonce +1 和手续费保留 —— 否则攻击者可以免费让全网做无用功。
+
+    4. 合约调合约：
+        CALL 在同一份 gas 里执行另一个合约；被调用者看到的 CALLER 是调用它的合约，不是发交易的人。
+        被调用者失败只会让 CALL 压入 "失败"，要不要跟着 REVERT 由调用者自己决定 (代理合约就选择了跟着失败)。
+*/
@@ -0,0 +1,303 @@
+// src/s07_vm/ex02_assembler.rs
+use crate::common::input::read_line;
+use crate::s05_zk_lab::hash::short_hex;
+
+use super::asm::{assemble, disassemble};
+use super::chain::{Receipt, TxKind, TxStatus, VmChain, VmTx};
+use super::world::{address_of, short_address, Address};
+
+/*
+业务场景：Ex01 的计数器是一条条 Instr 拼出来的，跳转目标还要自己数字节回填 —— 写个十几行的合约就很痛苦。
+    真实的工具链里，人写的是文本 (汇编 / 高级语言)，编译器负责算偏移；区块浏览器反过来把链上的字节码还原成可读的指令。
+
+本练习：
+    1. 同一个计数器用汇编写：标签代替手算偏移；汇编 -> 反汇编 -> 再汇编，字节码完全一致
+       再看几段写错的源码，汇编器报出第几行错在哪
+    2. 工作台：你是 Alice，在一条合约链上自己写源码、汇编、部署、调用，随时查看合约的存储
+*/
+
+const DIFFICULTY: u32 = 8;
+const GAS_LIMIT: u64 = 500_000;
+const GAS_PRICE: u64 = 1;
+
+const COUNTER: &str = "\
+; 计数器：input[0] = 0 加一并返回，= 1 读取，其它 REVERT
+    PUSH 0
+    ARG
+    DUP 1
+    ISZERO
+    PUSH inc
+    JUMPI
+    DUP 1
+    PUSH 1
+    EQ
+    PUSH get
+    JUMPI
+    REVERT
+inc:
+    POP
+    PUSH 0
+    LOAD
+    PUSH 1
+    ADD
+    DUP 1
+    PUSH 0
+    STORE          ; storage[0] = 计数 + 1
+    DUP 1
+    LOG
+    RETURN
+get:
+    POP
+    PUSH 0
+    LOAD
+    RETURN
+";
+
+// 每段都有一处错误
+const BROKEN: [(&str, &str); 4] = [
+    ("未定义的标签", "PUSH done\nJUMP\n"),
+    ("DUP 深度越界", "PUSH 1\nDUP 17\n"),
+    ("拼错的指令", "PUSH 1\nPUSH 2\nADDD\n"),
+    ("标签重复", "top:\nPUSH top\ntop:\nJUMP\n"),
+];
+
+fn show_receipt(receipt: &Receipt) {
+    match &receipt.status {
+        TxStatus::Success { ret } => println!("  ✅ 返回 {}  (gas {})", ret, receipt.gas_used),
+        TxStatus::Failed(e) => println!("  ❌ {}  (gas {})", e, receipt.gas_used),
+    }
+    for log in &receipt.logs {
+        println!("      日志 {}", log);
+    }
+}
+
+fn show_source(source: &str) {
+    for (i, line) in source.lines().enumerate() {
+        println!("  {:>3} | {}", i + 1, line);
+    }
+}
+
+// 逐行输入，空行结束
+fn read_source() -> String {
+    println!("  逐行输入源码，空行结束:");
+    let mut source = String::new();
+    loop {
+        let line = read_line("  > ");
+        if line.is_empty() {
+            break;
+        }
+        source.push_str(&line);
+        source.push('\n');
+    }
+    source
+}
+
+fn read_numbers(prompt: &str) -> Result<Vec<u64>, String> {
+    read_line(prompt).split_whitespace().map(|word| word.parse::<u64>().map_err(|_| format!("{:?} 不是非负整数", word))).collect()
+}
+
+fn read_amount(prompt: &str) -> Result<u64, String> {
+    match read_numbers(prompt)?.as_slice() {
+        [] => Ok(0),
+        [value] => Ok(*value),
+        _ => Err(String::from("只要一个金额")),
+    }
+}
+
+struct Workbench {
+    chain: VmChain,
+    alice: Address,
+    source: String,
+    contracts: Vec<Address>,
+}
+
+impl Workbench {
+    fn send(&mut self, kind: TxKind) -> Option<Receipt> {
+        let tx = VmTx { from: self.alice, nonce: self.chain.world.nonce(self.alice), gas_limit: GAS_LIMIT, gas_price: GAS_PRICE, kind };
+        match self.chain.produce(vec![tx]) {
+            Ok(mut receipts) => {
+                println!("  ⛏️ #{} state_root {}..", self.chain.chain.height(), short_hex(&self.chain.chain.tip().header.state_root));
+                receipts.pop()
+            }
+            Err(e) => {
+                println!("  ❌ 交易上不了链: {}", e);
+                None
+            }
+        }
+    }
+
+    fn show_assembly(&self) {
+        match assemble(&self.source) {
+            Ok(code) => {
+                println!("  字节码 ({} 字节): {}", code.len(), hex::encode(&code));
+                match disassemble(&code) {
+                    Ok(text) => print!("{}", text),
+                    Err(e) => println!("  ❌ 反汇编失败 (不应该发生): {}", e),
+                }
+            }
+            Err(e) => println!("  ❌ {}", e),
+        }
+    }
+
+    fn deploy(&mut self) {
+        let code = match assemble(&self.source) {
+            Ok(code) => code,
+            Err(e) => {
+                println!("  ❌ {}", e);
+                return;
+            }
+        };
+        let value = match read_amount("  随合约转入多少 (回车 = 0): ") {
+            Ok(value) => value,
+            Err(e) => {
+                println!("❌ {}", e);
+                return;
+            }
+        };
+        if let Some(receipt) = self.send(TxKind::Deploy { code, value }) {
+            show_receipt(&receipt);
+            if let Some(address) = receipt.created {
+                self.contracts.push(address);
+                println!("  合约 #{} 部署在 {}", self.contracts.len(), short_address(address));
+            }
+        }
+    }
+
+    fn call(&mut self) {
+        if self.contracts.is_empty() {
+            println!("  还没有部署合约");
+            return;
+        }
+        self.list();
+        let Some(&to) = read_line("  调用第几个合约: ").parse::<usize>().ok().and_then(|n| n.checked_sub(1)).and_then(|i| self.contracts.get(i)) else {
+            println!("❌ 没有这个合约");
+            return;
+        };
+        let input = match read_numbers("  参数 input[0] input[1] ... (空格分隔): ") {
+            Ok(input) => input,
+            Err(e) => {
+                println!("❌ {}", e);
+                return;
+            }
+        };
+        let value = match read_amount("  转入金额 (回车 = 0): ") {
+            Ok(value) => value,
+            Err(e) => {
+                println!("❌ {}", e);
+                return;
+            }
+        };
+        if let Some(receipt) = self.send(TxKind::Call { to, value, input }) {
+            show_receipt(&receipt);
+        }
+    }
+
+    fn list(&self) {
+        println!("  Alice {}: 余额 {}，nonce {}", short_address(self.alice), self.chain.world.balance(self.alice), self.chain.world.nonce(self.alice));
+        for (i, address) in self.contracts.iter().enumerate() {
+            let Some(account) = self.chain.world.account(*address) else {
+                continue;
+            };
+            let mut slots: Vec<(&u64, &u64)> = account.storage.iter().collect();
+            slots.sort();
+            let slots: Vec<String> = slots.iter().map(|(k, v)| format!("[{}] = {}", k, v)).collect();
+            println!("  #{} {}: 余额 {}，代码 {} 字节，存储 {{{}}}", i + 1, short_address(*address), account.balance, account.code.len(), slots.join(", "));
+        }
+    }
+}
+
+pub fn run() {
+    println!("--- S07 Ex02: 汇编器与反汇编器 ---");
+
+    // ==========================================
+    // 1. 汇编、反汇编、再汇编
+    // ==========================================
+    println!("\n[1] 用汇编写的计数器");
+    show_source(COUNTER);
+    let code = match assemble(COUNTER) {
+        Ok(code) => code,
+        Err(e) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    println!("  汇编得到 {} 字节: {}", code.len(), hex::encode(&code));
+    let listing = match disassemble(&code) {
+        Ok(listing) => listing,
+        Err(e) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    println!("  反汇编 (注释和标签名丢了，跳转目标还原成 L<偏移>):");
+    print!("{}", listing);
+    match assemble(&listing) {
+        Ok(again) if again == code => println!("  ✅ 反汇编结果再汇编，字节码完全一致"),
+        Ok(_) => println!("  ❌ 再汇编的字节码不一样 (不应该发生)"),
+        Err(e) => println!("  ❌ 反汇编结果汇编失败 (不应该发生): {}", e),
+    }
+    println!("  写错的源码:");
+    for (name, source) in BROKEN {
+        match assemble(source) {
+            Ok(_) => println!("    {}: ✅ 居然汇编成功了 (不应该发生)", name),
+            Err(e) => println!("    {}: ❌ {}", name, e),
+        }
+    }
+    match disassemble(&[0x60, 0x01, 0x02]) {
+        Ok(_) => println!("    截断的字节码: ✅ 居然反汇编成功了 (不应该发生)"),
+        Err(e) => println!("    截断的字节码: ❌ {}", e),
+    }
+
+    // ==========================================
+    // 2. 工作台
+    // ==========================================
+    let alice = address_of("Alice");
+    let mut bench = Workbench {
+        chain: VmChain::new(DIFFICULTY, address_of("Miner"), &[(alice, 10_000_000)]),
+        alice,
+        source: COUNTER.to_string(),
+        contracts: Vec::new(),
+    };
+    println!("\n[2] 工作台：Alice 有 {}，每笔交易 gas_limit {}；当前源码是上面的计数器", bench.chain.world.balance(alice), GAS_LIMIT);
+    loop {
+        println!("  1. 查看源码");
+        println!("  2. 重新编写源码");
+        println!("  3. 汇编 (字节码 + 反汇编)");
+        println!("  4. 部署");
+        println!("  5. 调用合约");
+        println!("  6. 查看账户和合约存储");
+        println!("  0. 返回");
+        match read_line("  选择: ").as_str() {
+            "1" => show_source(&bench.source),
+            "2" => {
+                let source = read_source();
+                match assemble(&source) {
+                    Ok(code) => println!("  ✅ 汇编通过，{} 字节", code.len()),
+                    Err(e) => println!("  ⚠️ 先存下来，但是汇编不过: {}", e),
+                }
+                bench.source = source;
+            }
+            "3" => bench.show_assembly(),
+            "4" => bench.deploy(),
+            "5" => bench.call(),
+            "6" => bench.list(),
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}
+
+/*
+关键点总结：
+    1. 两遍汇编：
+        第一遍只算每条指令的长度、记下标签的偏移；第二遍把标签换成数字。
+        PUSH 固定 9 字节是前提 —— 如果立即数是变长的 (EVM 的 PUSH1..PUSH32)，标签的值会影响长度，就得反复迭代。
+
+    2. 反汇编会丢信息：
+        注释、标签名、"这个 PUSH 是跳转目标还是普通数字" 都不在字节码里，只能猜 (这里只认紧挨着 JUMP 的 PUSH)。
+        但指令本身是无损的：反汇编再汇编，字节码一位不差。
+
+    3. 错误要带位置：
+        汇编器报 "第几行、错在哪"；字节码解码器报 "第几个字节"。
+        部署前先在本地汇编，错误就不会变成一笔白交 gas 的失败交易。
+*/
@@ -0,0 +1,246 @@
+// src/s07_vm/ex03_reentrancy.rs
+use super::asm::assemble;
+use super::chain::{Receipt, TxKind, TxStatus, VmChain, VmTx};
+use super::world::{address_of, short_address, Address};
+
+/*
+业务场景：一个"银行"合约替大家保管钱：存款记在 storage[存款人地址] 里，取款时把记录的金额转回给调用者。
+    转账 = CALL 对方；如果对方是个合约，它的代码会在银行的取款还没执行完时运行 —— 它可以再调一次取款。
+
+本练习：
+    1. 有漏洞的银行：先转账 (CALL)，后把余额记录清零。
+       Alice、Bob 各存 30；Mallory 部署攻击合约，存 10 再取款，攻击合约在"收款回调"里反复重入取款，把银行掏空
+       之后 Alice 想取回自己的 30，银行已经没钱了
+    2. 修好的银行：检查-生效-交互 (Checks-Effects-Interactions)：先清零记录，再转账。
+       同样的攻击只拿回自己的 10
+*/
+
+const DIFFICULTY: u32 = 8;
+const GAS_LIMIT: u64 = 1_000_000;
+const GAS_PRICE: u64 = 1;
+
+const DEPOSIT: u64 = 0;
+const WITHDRAW: u64 = 1;
+
+// 两个版本只有取款的顺序不同
+fn bank_source(fixed: bool) -> String {
+    let withdraw = if fixed {
+        "\
+    PUSH 0
+    CALLER
+    STORE           ; 先清零 (生效)
+    PUSH 0
+    DUP 2
+    CALLER
+    CALL            ; 再转账 (交互)
+    ISZERO
+    PUSH fail
+    JUMPI
+    POP"
+    } else {
+        "\
+    PUSH 0
+    DUP 2
+    CALLER
+    CALL            ; 先转账：对方的代码在这里运行
+    ISZERO
+    PUSH fail
+    JUMPI
+    POP
+    PUSH 0
+    CALLER
+    STORE           ; 转完才清零"
+    };
+    format!(
+        "\
+; 银行：storage[地址] = 存款
+    PUSH 0
+    ARG
+    DUP 1
+    ISZERO
+    PUSH deposit
+    JUMPI
+    DUP 1
+    PUSH 1
+    EQ
+    PUSH withdraw
+    JUMPI
+    REVERT
+deposit:            ; storage[调用者] += 转入金额
+    POP
+    CALLER
+    LOAD
+    CALLVALUE
+    ADD
+    DUP 1
+    CALLER
+    STORE
+    RETURN
+withdraw:           ; 取出调用者的全部存款
+    POP
+    CALLER
+    LOAD            ; 检查：有存款才取
+    DUP 1
+    ISZERO
+    PUSH done
+    JUMPI
+    DUP 1
+    LOG             ; 日志：这次转出多少
+{withdraw}
+done:
+    RETURN
+fail:
+    REVERT
+"
+    )
+}
+
+// input[0] = 1：把转入的钱存进银行再取出来；其它 (银行转账过来的回调)：银行里还够就再取一次
+fn attacker_source(bank: Address) -> String {
+    format!(
+        "\
+    PUSH 0
+    ARG
+    PUSH 1
+    EQ
+    PUSH attack
+    JUMPI
+; 收款回调
+    CALLER
+    BALANCE
+    DUP 1
+    LOG             ; 日志：银行还剩多少
+    CALLVALUE
+    LT
+    PUSH stop
+    JUMPI
+    PUSH {WITHDRAW}
+    PUSH 0
+    CALLER
+    CALL            ; 重入：银行的上一次取款还没清零
+    POP
+    POP
+stop:
+    STOP
+attack:
+    PUSH {DEPOSIT}
+    CALLVALUE
+    PUSH {bank}
+    CALL
+    POP
+    POP
+    PUSH {WITHDRAW}
+    PUSH 0
+    PUSH {bank}
+    CALL
+    POP
+    POP
+    STOP
+"
+    )
+}
+
+struct Scenario {
+    chain: VmChain,
+}
+
+impl Scenario {
+    fn send(&mut self, label: &str, from: Address, kind: TxKind) -> Option<Receipt> {
+        let tx = VmTx { from, nonce: self.chain.world.nonce(from), gas_limit: GAS_LIMIT, gas_price: GAS_PRICE, kind };
+        let receipt = match self.chain.produce(vec![tx]) {
+            Ok(mut receipts) => receipts.pop(),
+            Err(e) => {
+                println!("  {}: ❌ 交易上不了链 (不应该发生): {}", label, e);
+                return None;
+            }
+        }?;
+        match &receipt.status {
+            TxStatus::Success { ret } => println!("  {}: ✅ 返回 {}  (gas {})", label, ret, receipt.gas_used),
+            TxStatus::Failed(e) => println!("  {}: ❌ {}  (gas {})", label, e, receipt.gas_used),
+        }
+        if !receipt.logs.is_empty() {
+            let logs: Vec<String> = receipt.logs.iter().map(|log| log.to_string()).collect();
+            println!("      日志 [{}]", logs.join(", "));
+        }
+        Some(receipt)
+    }
+
+    fn deploy(&mut self, label: &str, from: Address, source: &str) -> Option<Address> {
+        let code = match assemble(source) {
+            Ok(code) => code,
+            Err(e) => {
+                println!("  ❌ {} 汇编失败 (不应该发生): {}", label, e);
+                return None;
+            }
+        };
+        let created = self.send(label, from, TxKind::Deploy { code, value: 0 })?.created;
+        if let Some(address) = created {
+            println!("      地址 {}", short_address(address));
+        }
+        created
+    }
+}
+
+fn play(fixed: bool) {
+    let [alice, bob, mallory] = ["Alice", "Bob", "Mallory"].map(address_of);
+    let mut scenario = Scenario { chain: VmChain::new(DIFFICULTY, address_of("Miner"), &[(alice, 10_000_000), (bob, 10_000_000), (mallory, 10_000_000)]) };
+    let Some(bank) = scenario.deploy("部署银行", alice, &bank_source(fixed)) else {
+        return;
+    };
+    let deposit = |value: u64| TxKind::Call { to: bank, value, input: vec![DEPOSIT] };
+    scenario.send("Alice 存 30", alice, deposit(30));
+    scenario.send("Bob 存 30", bob, deposit(30));
+    let Some(attacker) = scenario.deploy("Mallory 部署攻击合约", mallory, &attacker_source(bank)) else {
+        return;
+    };
+    scenario.send("Mallory 通过攻击合约存 10、取款", mallory, TxKind::Call { to: attacker, value: 10, input: vec![1] });
+
+    let world = &scenario.chain.world;
+    let recorded: u64 = [alice, bob, attacker].iter().map(|a| world.storage(bank, *a)).sum();
+    println!(
+        "  银行账上记录: Alice {}，Bob {}，攻击合约 {} (合计 {})；银行实际余额 {}；攻击合约余额 {}",
+        world.storage(bank, alice),
+        world.storage(bank, bob),
+        world.storage(bank, attacker),
+        recorded,
+        world.balance(bank),
+        world.balance(attacker)
+    );
+    scenario.send("Alice 取款", alice, TxKind::Call { to: bank, value: 0, input: vec![WITHDRAW] });
+    let world = &scenario.chain.world;
+    println!("  银行实际余额 {}，Alice 的记录 {}", world.balance(bank), world.storage(bank, alice));
+}
+
+pub fn run() {
+    println!("--- S07 Ex03: 重入攻击 (先转账后记账的银行被掏空) ---");
+
+    // ==========================================
+    // 1. 有漏洞的银行
+    // ==========================================
+    println!("\n[1] 有漏洞的银行：先 CALL 转账，再清零记录");
+    play(false);
+
+    // ==========================================
+    // 2. 检查-生效-交互
+    // ==========================================
+    println!("\n[2] 修好的银行：先清零记录，再 CALL 转账");
+    play(true);
+}
+
+/*
+关键点总结：
+    1. CALL 会把控制权交出去：
+        给合约转账就是执行它的代码。银行在 CALL 那一刻以为自己在"付款"，实际上是在运行攻击者的程序，
+        而银行自己的状态 (storage[攻击合约] 还是 10) 停在一个不一致的中间态。
+
+    2. 攻击者没有破坏任何规则：
+        每一次重入都是合法的取款调用，银行每次都"检查"了存款记录 —— 只是记录还没来得及更新。
+        被掏走的是 Alice、Bob 的钱，账上的记录却一分没少，事后才发现对不上。
+
+    3. 检查-生效-交互：
+        先检查条件，再把自己的状态改完，最后才和外部交互。重入进来看到的已经是清零后的记录，取不到第二次。
+        另一种做法是重入锁 (进入时置位 storage 里的标志，重入就 REVERT)。
+
+    4. 调用深度上限不是防御：
+        MAX_DEPTH 只限制重入的次数，每层取 10 就能拿走 10 × 层数；要防重入还得靠代码的顺序。
+*/
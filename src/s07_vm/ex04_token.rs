@@ -0,0 +1,126 @@
+// src/s07_vm/ex04_token.rs
+use std::collections::HashMap;
+
+use super::token::{Event, Token, TokenError, MINT_ADDRESS};
+use super::world::{address_of, short_address, Address};
+
+/*
+业务场景：链上的"积分"、稳定币、治理代币 —— 成千上万种代币都长一个样子：
+    余额表 + 转账 + "授权别人替我转" (交易所撮合、订阅扣款)。ERC-20 把这套接口定下来，钱包和交易所只要对接一次。
+
+本练习：
+    1. 账本操作：铸造、转账、授权 (approve)、代转 (transfer_from)，以及各种失败；每一步后检查 总供应量 == 余额之和
+    2. 事件：打印完整的事件日志，只靠重放事件重建余额表，和账本对比；再按地址查出 Carol 的收款历史
+    3. ⚠️ 改额度的竞争：Alice 想把给交易所的额度从 300 改成 100，交易所抢在改额度之前把 300 花掉，改完又花掉新的 100
+*/
+
+fn show_result(label: &str, result: Result<(), TokenError>) {
+    match result {
+        Ok(()) => println!("  {}: ✅", label),
+        Err(e) => println!("  {}: ❌ {}", label, e),
+    }
+}
+
+fn show_balances(token: &Token, people: &[(&str, Address)]) {
+    let line: Vec<String> = people.iter().map(|(name, address)| format!("{} {}", name, token.balance_of(*address))).collect();
+    let sum: u64 = people.iter().map(|(_, address)| token.balance_of(*address)).sum();
+    let check = if sum == token.total_supply() { "✅" } else { "❌ (不应该发生)" };
+    println!("    余额: {}  | 之和 {} / 总供应量 {} {}", line.join("  "), sum, token.total_supply(), check);
+}
+
+// 链外的索引器：只看事件
+fn replay(events: &[Event]) -> HashMap<Address, i128> {
+    let mut balances = HashMap::new();
+    for event in events {
+        if let Event::Transfer { from, to, amount } = event {
+            *balances.entry(*from).or_insert(0) -= *amount as i128;
+            *balances.entry(*to).or_insert(0) += *amount as i128;
+        }
+    }
+    balances
+}
+
+pub fn run() {
+    println!("--- S07 Ex04: ERC-20 代币账本 ---");
+    let people = [("Alice", address_of("Alice")), ("Bob", address_of("Bob")), ("Carol", address_of("Carol")), ("Exchange", address_of("Exchange"))];
+    let [(_, alice), (_, bob), (_, carol), (_, exchange)] = people;
+    let legend: Vec<String> = people.iter().map(|(name, address)| format!("{} = {}", name, short_address(*address))).collect();
+    println!("  {}", legend.join("  "));
+
+    // ==========================================
+    // 1. 账本操作
+    // ==========================================
+    let mut token = Token::new("Lab Coin", "LAB");
+    println!("\n[1] {} ({})", token.name, token.symbol);
+    show_result("铸造 1000 给 Alice", token.mint(alice, 1000));
+    show_result("铸造 50 给 Bob", token.mint(bob, 50));
+    show_result("铸造 u64::MAX 给 Bob", token.mint(bob, u64::MAX));
+    show_balances(&token, &people);
+    show_result("Alice 转 100 给 Bob", token.transfer(alice, bob, 100));
+    show_result("Bob 转 500 给 Carol", token.transfer(bob, carol, 500));
+    show_result("Alice 转 10 给 0 地址", token.transfer(alice, MINT_ADDRESS, 10));
+    show_balances(&token, &people);
+
+    token.approve(alice, exchange, 300);
+    println!("  Alice 授权 Exchange 300: ✅ 额度 {}", token.allowance(alice, exchange));
+    show_result("Exchange 替 Alice 转 200 给 Carol", token.transfer_from(exchange, alice, carol, 200));
+    show_result("Exchange 再替 Alice 转 200 给 Carol", token.transfer_from(exchange, alice, carol, 200));
+    show_result("Exchange 替 Bob 转 10 给自己 (Bob 没授权)", token.transfer_from(exchange, bob, exchange, 10));
+    println!("    Alice 给 Exchange 的额度还剩 {}", token.allowance(alice, exchange));
+    show_balances(&token, &people);
+
+    // ==========================================
+    // 2. 事件
+    // ==========================================
+    println!("\n[2] 事件日志 (失败的操作没有事件):");
+    for (i, event) in token.events().iter().enumerate() {
+        println!("    #{} {}", i, event);
+    }
+    let rebuilt = replay(token.events());
+    let all_match = people.iter().all(|(_, address)| rebuilt.get(address).copied().unwrap_or(0) == token.balance_of(*address) as i128);
+    println!("  只重放 Transfer 事件重建余额: {}", if all_match { "✅ 和账本完全一致" } else { "❌ 对不上 (不应该发生)" });
+    println!("    0 地址在重放里是 {} = -总供应量 (所有铸造都从它\"转出\")", rebuilt.get(&MINT_ADDRESS).copied().unwrap_or(0));
+    println!("  Carol 的收款历史:");
+    for event in token.events() {
+        if let Event::Transfer { from, to, amount } = event {
+            if *to == carol {
+                let name = people.iter().find(|(_, a)| a == from).map_or("?", |(name, _)| name);
+                println!("    来自 {} {}", name, amount);
+            }
+        }
+    }
+
+    // ==========================================
+    // 3. 改额度的竞争
+    // ==========================================
+    println!("\n[3] ⚠️ Alice 想把给 Exchange 的额度从 300 改成 100");
+    let mut token = Token::new("Lab Coin", "LAB");
+    show_result("铸造 1000 给 Alice", token.mint(alice, 1000));
+    token.approve(alice, exchange, 300);
+    println!("  Alice 授权 Exchange 300");
+    println!("  Alice 发出 approve(100)；交易还在交易池里，Exchange 看到了，抢先发一笔手续费更高的 transfer_from:");
+    show_result("  (先上链) Exchange 花掉旧额度 300", token.transfer_from(exchange, alice, exchange, 300));
+    token.approve(alice, exchange, 100);
+    println!("    (后上链) Alice 的 approve(100)：覆盖额度为 {}", token.allowance(alice, exchange));
+    show_result("  Exchange 再花掉新额度 100", token.transfer_from(exchange, alice, exchange, 100));
+    println!("    Alice 本想最多给 100，结果被拿走 {}", 1000 - token.balance_of(alice));
+    println!("  ✅ 稳妥的改法：先 approve(0)，确认上链、看清被花掉多少，再 approve 新额度");
+}
+
+/*
+关键点总结：
+    1. 两张表：
+        balances 是 地址 -> 余额 的 HashMap；allowances 用 (持有人, 被授权人) 元组当键，一个人可以分别授权给很多人。
+        transfer_from 先检查额度和余额，都够了才改状态 —— 和 S07 Ex03 的"先检查、再生效"一样。
+
+    2. 不变量：
+        总供应量 == 余额之和。只有铸造 (和销毁) 能改总量，转账只是搬动；每一步后检查它是抓 bug 最便宜的办法。
+
+    3. 事件是给链外的：
+        浏览器、钱包、税务工具都不读合约存储，而是按顺序重放事件；所以每个改余额的操作都必须发事件，
+        而且失败的操作不能发 (否则重放出来的余额是错的)。
+
+    4. approve 是覆盖不是累加：
+        这让"改额度"变成一个竞争条件：被授权人可以在新额度生效前花掉旧额度，生效后再花新额度。
+        交易池里的交易对所有人可见，谁出的手续费高谁先上链 —— 链上的很多漏洞都出在"交易排序"上。
+*/
@@ -0,0 +1,333 @@
+// src/s07_vm/ex05_event_logs.rs
+use crate::common::input::read_line;
+use crate::common::rng::SimpleRng;
+
+use super::asm::assemble;
+use super::chain::{TxKind, TxStatus, VmChain, VmTx};
+use super::receipts::{false_positive_rate, Bloom, LogFilter, LogQuery, BLOOM_BITS};
+use super::world::{address_of, short_address, Address};
+
+/*
+业务场景：钱包想知道"我的代币合约发生过哪些 Mint / Burn"，运营想找出"哪个块触发过告警事件"。
+    合约通过 EMIT 记事件，事件写在交易收据里，不进世界状态。全节点按块存收据，浏览器提供按合约、按主题的查询。
+    链越长，逐块翻收据越慢 —— 每个块的 Bloom 过滤器先告诉你"这块一定没有"，大部分块直接跳过。
+
+本练习：
+    1. 部署 Token、Poll 两个事件发射合约，出 16 个块；每块列出交易数、日志数、Bloom 置 1 的位数
+    2. 一个块的全部收据：状态、gas、事件；执行失败的交易事件被回滚，收据里没有
+    3. 按主题 / 合约查询：Bloom 跳过了多少块、翻了多少块、误报几次
+    4. Bloom 误报率：放进去的条目越多，误报越多 (实测 vs 公式)
+    5. 交互式浏览器：区块列表、查看收据、按合约 / 主题查日志
+*/
+
+const DIFFICULTY: u32 = 8;
+const GAS_LIMIT: u64 = 1_000_000;
+const GAS_PRICE: u64 = 1;
+const BLOCKS: usize = 16;
+// 第几个交易块里出现唯一一次告警
+const ALARM_BLOCK: usize = 11;
+
+// 主题 = 名字的 ASCII 码拼成的 u64
+const TOPICS: [(&str, u64); 4] = [("mint", 0x4d49_4e54), ("burn", 0x4255_524e), ("vote", 0x564f_5445), ("alarm", 0x414c_524d)];
+const MINT: u64 = TOPICS[0].1;
+const BURN: u64 = TOPICS[1].1;
+const VOTE: u64 = TOPICS[2].1;
+const ALARM: u64 = TOPICS[3].1;
+
+const EMITTER: &str = "\
+; 事件发射器：input[0] = 数据，input[1] = 主题
+; 数据为 0 时先记一条事件再 REVERT —— 回滚之后这条事件不会出现在收据里
+    PUSH 0
+    ARG
+    DUP 1
+    ISZERO
+    PUSH fail
+    JUMPI
+    PUSH 1
+    ARG
+    EMIT
+    PUSH 1
+    RETURN
+fail:
+    PUSH 1
+    ARG
+    EMIT
+    REVERT
+";
+
+fn topic_name(topic: u64) -> String {
+    TOPICS.iter().find(|(_, t)| *t == topic).map_or_else(|| format!("0x{:x}", topic), |(name, _)| name.to_uppercase())
+}
+
+struct Lab {
+    chain: VmChain,
+    // 名字 -> 地址，打印用
+    book: Vec<(&'static str, Address)>,
+    token: Address,
+    poll: Address,
+}
+
+impl Lab {
+    fn name(&self, address: Address) -> String {
+        self.book.iter().find(|(_, a)| *a == address).map_or_else(|| short_address(address), |(name, _)| name.to_string())
+    }
+
+    fn call(&self, from: Address, to: Address, data: u64, topic: u64) -> VmTx {
+        let kind = TxKind::Call { to, value: 0, input: vec![data, topic] };
+        VmTx { from, nonce: self.chain.world.nonce(from), gas_limit: GAS_LIMIT, gas_price: GAS_PRICE, kind }
+    }
+
+    fn setup() -> Result<Self, String> {
+        let users = ["Alice", "Bob", "Carol"];
+        let mut book: Vec<(&'static str, Address)> = users.iter().map(|&name| (name, address_of(name))).collect();
+        let allocations: Vec<(Address, u64)> = book.iter().map(|&(_, a)| (a, 10_000_000)).collect();
+        let mut chain = VmChain::new(DIFFICULTY, address_of("Miner"), &allocations);
+        let code = assemble(EMITTER)?;
+        let deployer = book[0].1;
+        let nonce = chain.world.nonce(deployer);
+        let deploy = |offset: u64| VmTx {
+            from: deployer,
+            nonce: nonce + offset,
+            gas_limit: GAS_LIMIT,
+            gas_price: GAS_PRICE,
+            kind: TxKind::Deploy { code: code.clone(), value: 0 },
+        };
+        let receipts = chain.produce(vec![deploy(0), deploy(1)])?;
+        let [token, poll] = [0, 1].map(|i| receipts.get(i).and_then(|r| r.created));
+        let (Some(token), Some(poll)) = (token, poll) else {
+            return Err(String::from("部署失败"));
+        };
+        book.extend([("Token", token), ("Poll", poll)]);
+        Ok(Lab { chain, book, token, poll })
+    }
+
+    // 每个用户 2/3 的概率发一笔：Token 上 MINT / BURN，Poll 上 VOTE；数据随机，偶尔是 0 (交易失败)
+    fn random_block(&mut self, rng: &mut SimpleRng, alarm: bool) -> Result<(), String> {
+        let users: Vec<Address> = self.book[..3].iter().map(|&(_, a)| a).collect();
+        let mut txs = Vec::new();
+        for (i, &user) in users.iter().enumerate() {
+            if alarm && i == 1 {
+                txs.push(self.call(user, self.token, 0, BURN));
+            } else if alarm && i == 2 {
+                txs.push(self.call(user, self.token, 999, ALARM));
+            } else if rng.gen_range(3) > 0 {
+                let data = rng.gen_range(12);
+                txs.push(match rng.gen_range(3) {
+                    0 => self.call(user, self.token, data, MINT),
+                    1 => self.call(user, self.token, data, BURN),
+                    _ => self.call(user, self.poll, data, VOTE),
+                });
+            }
+        }
+        self.chain.produce(txs).map(|_| ())
+    }
+
+    fn list_blocks(&self) {
+        for (block, receipts) in self.chain.chain.blocks.iter().zip(&self.chain.receipts) {
+            let failed = receipts.receipts.iter().filter(|r| matches!(r.status, TxStatus::Failed(_))).count();
+            println!(
+                "    #{:<3} 交易 {} (失败 {})  日志 {}  Bloom 置 1 {:>3}/{}",
+                block.header.height,
+                block.txs.len(),
+                failed,
+                receipts.log_count(),
+                receipts.bloom.ones(),
+                BLOOM_BITS
+            );
+        }
+    }
+
+    fn show_receipts(&self, height: u64) {
+        let Some(block) = self.chain.chain.blocks.get(height as usize) else {
+            println!("  ❌ 没有高度为 {} 的区块 (链高 {})", height, self.chain.chain.height());
+            return;
+        };
+        println!("  区块 #{}：{} 笔交易", height, block.txs.len());
+        for index in 0..block.txs.len() {
+            let Some((tx, receipt)) = self.chain.receipt(height, index) else {
+                continue;
+            };
+            let target = match &tx.kind {
+                TxKind::Call { to, input, .. } => match input.as_slice() {
+                    [data, topic] => format!("调用 {} (数据 {}，主题 {})", self.name(*to), data, topic_name(*topic)),
+                    _ => format!("调用 {} {:?}", self.name(*to), input),
+                },
+                TxKind::Deploy { .. } => String::from("部署合约"),
+                TxKind::Transfer { to, amount } => format!("转 {} 给 {}", amount, self.name(*to)),
+            };
+            let status = match &receipt.status {
+                TxStatus::Success { ret } => format!("✅ 返回 {}", ret),
+                TxStatus::Failed(e) => format!("❌ {}", e),
+            };
+            println!("    [{}] {} {}：{}  (gas {})", index, self.name(tx.from), target, status, receipt.gas_used);
+            if let Some(address) = receipt.created {
+                println!("        新合约 {}", self.name(address));
+            }
+            for log in &receipt.logs {
+                let topics: Vec<String> = log.topics.iter().map(|t| topic_name(*t)).collect();
+                println!("        事件 {} [{}] 数据 {}", self.name(log.address), topics.join(", "), log.data);
+            }
+            if receipt.logs.is_empty() && matches!(receipt.status, TxStatus::Failed(_)) {
+                println!("        (执行时记过事件，但随 REVERT 一起回滚了)");
+            }
+        }
+    }
+
+    fn show_query(&self, label: &str, filter: &LogFilter) -> LogQuery {
+        let result = self.chain.logs(filter);
+        println!(
+            "  {}: 命中 {} 条；Bloom 跳过 {} 块，翻了 {} 块 (误报 {})",
+            label,
+            result.hits.len(),
+            result.skipped,
+            result.scanned,
+            result.false_positives
+        );
+        for hit in result.hits.iter().take(6) {
+            let topics: Vec<String> = hit.log.topics.iter().map(|t| topic_name(*t)).collect();
+            println!("      #{} 第 {} 笔  {} [{}] 数据 {}", hit.height, hit.tx_index, self.name(hit.log.address), topics.join(", "), hit.log.data);
+        }
+        if result.hits.len() > 6 {
+            println!("      ... 还有 {} 条", result.hits.len() - 6);
+        }
+        result
+    }
+}
+
+fn parse_topic(text: &str) -> Option<u64> {
+    let text = text.to_lowercase();
+    TOPICS.iter().find(|(name, _)| *name == text).map(|(_, t)| *t).or_else(|| text.parse().ok())
+}
+
+fn explorer(lab: &Lab) {
+    loop {
+        println!("\n浏览器 (链高 {}):", lab.chain.chain.height());
+        println!("  1. 区块列表");
+        println!("  2. 查看区块的收据");
+        println!("  3. 按合约 / 主题查日志");
+        println!("  0. 返回");
+        match read_line("请输入: ").as_str() {
+            "1" => lab.list_blocks(),
+            "2" => match read_line("  区块高度: ").parse::<u64>() {
+                Ok(height) => lab.show_receipts(height),
+                Err(_) => println!("  ❌ 不是数字"),
+            },
+            "3" => {
+                let contract = read_line("  合约 (Token / Poll，回车不限): ");
+                let address = match contract.to_lowercase().as_str() {
+                    "" => None,
+                    "token" => Some(lab.token),
+                    "poll" => Some(lab.poll),
+                    other => {
+                        println!("  ❌ 不认识的合约 {:?}", other);
+                        continue;
+                    }
+                };
+                let topic_text = read_line("  主题 (mint / burn / vote / alarm / 数字，回车不限): ");
+                let topic = if topic_text.is_empty() {
+                    None
+                } else {
+                    match parse_topic(&topic_text) {
+                        Some(topic) => Some(topic),
+                        None => {
+                            println!("  ❌ 不认识的主题 {:?}", topic_text);
+                            continue;
+                        }
+                    }
+                };
+                lab.show_query("查询结果", &LogFilter { address, topic });
+            }
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}
+
+pub fn run() {
+    println!("--- S07 Ex05: 事件日志与收据 (按块存收据，Bloom 过滤器快速筛块) ---");
+    let mut rng = SimpleRng::from_time();
+
+    // ==========================================
+    // 1. 出块
+    // ==========================================
+    println!("\n[1] Alice 部署 Token、Poll 两个事件发射合约，之后出 {} 个块", BLOCKS);
+    let mut lab = match Lab::setup() {
+        Ok(lab) => lab,
+        Err(e) => {
+            println!("  ❌ {} (不应该发生)", e);
+            return;
+        }
+    };
+    for i in 0..BLOCKS {
+        if let Err(e) = lab.random_block(&mut rng, i == ALARM_BLOCK) {
+            println!("  ❌ 出块失败 (不应该发生): {}", e);
+            return;
+        }
+    }
+    lab.list_blocks();
+
+    // ==========================================
+    // 2. 一个块的收据
+    // ==========================================
+    let alarm_height = (ALARM_BLOCK + 2) as u64;
+    println!("\n[2] 区块 #{} 的收据 (Bob 的 BURN 数据是 0，合约记完事件后 REVERT)", alarm_height);
+    lab.show_receipts(alarm_height);
+
+    // ==========================================
+    // 3. 按主题查询
+    // ==========================================
+    println!("\n[3] 按合约 / 主题查日志 (共 {} 个块)", lab.chain.receipts.len());
+    let alarm = lab.show_query("主题 ALARM", &LogFilter { address: None, topic: Some(ALARM) });
+    if alarm.hits.iter().any(|hit| hit.height == alarm_height) {
+        println!("      ✅ 告警就在 #{}，没有漏报", alarm_height);
+    } else {
+        println!("      ❌ 漏掉了 #{} 的告警 (不应该发生)", alarm_height);
+    }
+    lab.show_query("Poll 合约的 VOTE", &LogFilter { address: Some(lab.poll), topic: Some(VOTE) });
+    lab.show_query("Token 合约的全部事件", &LogFilter { address: Some(lab.token), topic: None });
+    lab.show_query("从没出现过的主题 0x42", &LogFilter { address: None, topic: Some(0x42) });
+    println!("  没被跳过又查不到的块就是误报：Bloom 只能说\"一定没有\"或者\"可能有\"");
+
+    // ==========================================
+    // 4. 误报率
+    // ==========================================
+    println!("\n[4] {} 位的 Bloom 里放 n 个条目，再拿 2000 个没放进去的去问", BLOOM_BITS);
+    for n in [4, 16, 32, 64, 128] {
+        let mut bloom = Bloom::default();
+        for _ in 0..n {
+            bloom.insert(&rng.next_u64().to_be_bytes());
+        }
+        let hits = (0..2000).filter(|_| bloom.might_contain(&rng.next_u64().to_be_bytes())).count();
+        println!(
+            "  n = {:<4} 置 1 {:>3} 位  误报 实测 {:>5.1}%  公式 {:>5.1}%",
+            n,
+            bloom.ones(),
+            hits as f64 / 20.0,
+            false_positive_rate(n) * 100.0
+        );
+    }
+
+    // ==========================================
+    // 5. 浏览器
+    // ==========================================
+    println!("\n[5] 交互式浏览器");
+    explorer(&lab);
+}
+
+/*
+关键点总结：
+    1. 状态和日志分开：
+        状态 (余额、存储) 是合约以后还要读的，所有节点都得存在状态树里；
+        日志只给链外看，写进收据就行，合约自己读不到，所以 EMIT 比 STORE 便宜得多。
+
+    2. 收据和交易一一对应：
+        失败的交易也有收据 (gas 照收)，但它执行过程中记的事件随 REVERT 一起作废 —— 和状态改动同生共死。
+
+    3. Bloom 过滤器：
+        k 个哈希位置全为 1 才说"可能有"：不会漏报，只会误报；位越满，误报越多。
+        以太坊每个区块头里都有 logsBloom，轻客户端只下载区块头就能先筛一遍，再向全节点要可能命中的块的收据。
+
+    4. 代价：
+        Bloom 大小固定，放的条目多了就几乎全是 1，等于没过滤 —— 以太坊上热门合约的地址几乎出现在每个块的 Bloom 里。
+        所以真正的索引服务 (The Graph、浏览器的数据库) 还是会把日志按主题建倒排索引。
+*/
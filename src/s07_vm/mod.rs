@@ -0,0 +1,45 @@
+// src/s07_vm/mod.rs
+
+// 公共工具
+pub mod asm;
+pub mod chain;
+pub mod opcode;
+pub mod receipts;
+pub mod token;
+pub mod vm;
+pub mod world;
+
+// 练习
+pub mod ex01_stack_vm;
+pub mod ex02_assembler;
+pub mod ex03_reentrancy;
+pub mod ex04_token;
+pub mod ex05_event_logs;
+
+use std::io;
+
+pub fn run_experiments() {
+    loop {
+        println!("\n--- 🖥️ S07 合约虚拟机 (Toy VM) ---");
+        println!("1. 栈式虚拟机 (字节码合约：部署、调用、合约存储、合约调合约)");
+        println!("2. 汇编器与反汇编器 (标签、两遍汇编，交互式编写、部署、调用合约)");
+        println!("3. 重入攻击 (先转账后记账的银行被攻击合约掏空，检查-生效-交互修复)");
+        println!("4. ERC-20 代币账本 (余额、授权额度、transfer_from，事件日志重建余额)");
+        println!("5. 事件日志与收据 (按块存收据，Bloom 过滤器按主题快速筛块)");
+        println!("0. 返回主菜单");
+        println!("请输入练习编号:");
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("读取失败");
+
+        match input.trim() {
+            "1" => ex01_stack_vm::run(),
+            "2" => ex02_assembler::run(),
+            "3" => ex03_reentrancy::run(),
+            "4" => ex04_token::run(),
+            "5" => ex05_event_logs::run(),
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+// src/s07_vm/opcode.rs
+use std::fmt;
+
+use crate::s06_chain::gas;
+
+/*
+指令集：一台 64 位的栈机器
+
+    和 EVM 一样，所有指令都只和一个栈打交道：从栈顶取操作数，结果压回栈顶。
+    没有寄存器，也没有内存 —— 要长期保存的数据写进合约存储 (Store / Load)。
+    下面的"栈"写法：[a, b] 表示 b 在栈顶；ADD 弹出 a、b，压入 a + b。
+
+    字节码：每条指令一个字节的操作码，PUSH 后面跟 8 字节的立即数 (大端)，DUP / SWAP 跟 1 字节的深度。
+    JUMP / JUMPI 的目标是字节偏移，必须落在某条指令的开头 (不能跳进 PUSH 的立即数里)。
+
+    算术都是 wrapping 的 (溢出回绕，和 EVM 一样)；除以 0 得 0，不报错。
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instr {
+    Stop,
+    Push(u64),
+    Pop,
+    // 把从栈顶往下数第 n 个 (1 = 栈顶) 复制一份压到栈顶
+    Dup(u8),
+    // 栈顶和往下第 n 个交换
+    Swap(u8),
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Lt,
+    Gt,
+    Eq,
+    IsZero,
+    // [target] 无条件跳转；[cond, target] 条件非 0 才跳 (target 在栈顶)
+    Jump,
+    JumpI,
+    // [key] -> [storage[key]]；[value, key] -> 写 storage[key] = value
+    Load,
+    Store,
+    // 调用环境
+    Caller,
+    CallValue,
+    Address,
+    // [i] -> [input[i]]，越界得 0
+    Arg,
+    // [address] -> [余额]
+    Balance,
+    // [arg, value, to] -> [返回值, 成功?]：调用另一个合约 (或者给普通账户转账)，成功标志在栈顶
+    Call,
+    // [value] -> 记一条日志
+    Log,
+    // [data, topic] -> 记一条带主题的事件 (topic 在栈顶)，收据里按主题查询
+    Emit,
+    // [value] 正常结束并返回 value；Revert 撤销本次调用的所有改动
+    Return,
+    Revert,
+}
+
+// 操作码：数值参照 EVM 的分组，但不追求一致
+const STOP: u8 = 0x00;
+const ADD: u8 = 0x01;
+const MUL: u8 = 0x02;
+const SUB: u8 = 0x03;
+const DIV: u8 = 0x04;
+const LT: u8 = 0x10;
+const GT: u8 = 0x11;
+const EQ: u8 = 0x14;
+const ISZERO: u8 = 0x15;
+const ADDRESS: u8 = 0x30;
+const BALANCE: u8 = 0x31;
+const CALLER: u8 = 0x33;
+const CALLVALUE: u8 = 0x34;
+const ARG: u8 = 0x35;
+const POP: u8 = 0x50;
+const LOAD: u8 = 0x54;
+const STORE: u8 = 0x55;
+const JUMP: u8 = 0x56;
+const JUMPI: u8 = 0x57;
+const PUSH: u8 = 0x60;
+const DUP: u8 = 0x80;
+const SWAP: u8 = 0x90;
+const LOG: u8 = 0xa0;
+const EMIT: u8 = 0xa1;
+const CALL: u8 = 0xf1;
+const RETURN: u8 = 0xf3;
+const REVERT: u8 = 0xfd;
+
+// 简单指令的 gas (Store、Call 的价格还取决于状态，在 vm.rs 里加)
+pub const STEP: u64 = 3;
+pub const ARITH: u64 = 5;
+pub const JUMP_GAS: u64 = 8;
+pub const BALANCE_GAS: u64 = 400;
+pub const CALL_GAS: u64 = 700;
+
+impl Instr {
+    pub fn gas(&self) -> u64 {
+        match self {
+            Instr::Stop | Instr::Return | Instr::Revert => 0,
+            Instr::Mul | Instr::Div => ARITH,
+            Instr::Jump | Instr::JumpI => JUMP_GAS,
+            Instr::Load => gas::LOAD,
+            // 写存储的基础价是改写旧槽；新开槽的差价在执行时补
+            Instr::Store => gas::STORE_UPDATE,
+            Instr::Balance => BALANCE_GAS,
+            Instr::Call => CALL_GAS,
+            Instr::Log | Instr::Emit => gas::LOG_BASE,
+            _ => STEP,
+        }
+    }
+
+    pub fn encode_into(&self, out: &mut Vec<u8>) {
+        match *self {
+            Instr::Push(value) => {
+                out.push(PUSH);
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+            Instr::Dup(n) => out.extend_from_slice(&[DUP, n]),
+            Instr::Swap(n) => out.extend_from_slice(&[SWAP, n]),
+            other => out.push(match other {
+                Instr::Stop => STOP,
+                Instr::Pop => POP,
+                Instr::Add => ADD,
+                Instr::Sub => SUB,
+                Instr::Mul => MUL,
+                Instr::Div => DIV,
+                Instr::Lt => LT,
+                Instr::Gt => GT,
+                Instr::Eq => EQ,
+                Instr::IsZero => ISZERO,
+                Instr::Jump => JUMP,
+                Instr::JumpI => JUMPI,
+                Instr::Load => LOAD,
+                Instr::Store => STORE,
+                Instr::Caller => CALLER,
+                Instr::CallValue => CALLVALUE,
+                Instr::Address => ADDRESS,
+                Instr::Arg => ARG,
+                Instr::Balance => BALANCE,
+                Instr::Call => CALL,
+                Instr::Log => LOG,
+                Instr::Emit => EMIT,
+                Instr::Return => RETURN,
+                Instr::Revert => REVERT,
+                Instr::Push(_) | Instr::Dup(_) | Instr::Swap(_) => unreachable!("handled above"),
+            }),
+        }
+    }
+
+    // 编码后占几个字节
+    pub fn size(&self) -> usize {
+        match self {
+            Instr::Push(_) => 9,
+            Instr::Dup(_) | Instr::Swap(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+pub fn encode(program: &[Instr]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for instr in program {
+        instr.encode_into(&mut out);
+    }
+    out
+}
+
+// 字节码 -> (偏移, 指令) 列表；遇到不认识的操作码或者被截断的立即数就报错
+pub fn decode(code: &[u8]) -> Result<Vec<(usize, Instr)>, String> {
+    let mut program = Vec::new();
+    let mut pc = 0;
+    while pc < code.len() {
+        let byte = code[pc];
+        let instr = match byte {
+            PUSH => {
+                let bytes: [u8; 8] = code.get(pc + 1..pc + 9).and_then(|b| b.try_into().ok()).ok_or_else(|| format!("偏移 {} 的 PUSH 立即数被截断", pc))?;
+                Instr::Push(u64::from_be_bytes(bytes))
+            }
+            DUP | SWAP => {
+                let n = *code.get(pc + 1).ok_or_else(|| format!("偏移 {} 的 DUP/SWAP 缺深度", pc))?;
+                if n == 0 || n > 16 {
+                    return Err(format!("偏移 {} 的 DUP/SWAP 深度 {} 不在 1..=16", pc, n));
+                }
+                if byte == DUP {
+                    Instr::Dup(n)
+                } else {
+                    Instr::Swap(n)
+                }
+            }
+            STOP => Instr::Stop,
+            POP => Instr::Pop,
+            ADD => Instr::Add,
+            SUB => Instr::Sub,
+            MUL => Instr::Mul,
+            DIV => Instr::Div,
+            LT => Instr::Lt,
+            GT => Instr::Gt,
+            EQ => Instr::Eq,
+            ISZERO => Instr::IsZero,
+            JUMP => Instr::Jump,
+            JUMPI => Instr::JumpI,
+            LOAD => Instr::Load,
+            STORE => Instr::Store,
+            CALLER => Instr::Caller,
+            CALLVALUE => Instr::CallValue,
+            ADDRESS => Instr::Address,
+            ARG => Instr::Arg,
+            BALANCE => Instr::Balance,
+            CALL => Instr::Call,
+            LOG => Instr::Log,
+            EMIT => Instr::Emit,
+            RETURN => Instr::Return,
+            REVERT => Instr::Revert,
+            other => return Err(format!("偏移 {} 是不认识的操作码 0x{:02x}", pc, other)),
+        };
+        program.push((pc, instr));
+        pc += instr.size();
+    }
+    Ok(program)
+}
+
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instr::Push(value) => write!(f, "PUSH {}", value),
+            Instr::Dup(n) => write!(f, "DUP {}", n),
+            Instr::Swap(n) => write!(f, "SWAP {}", n),
+            other => write!(f, "{}", format!("{:?}", other).to_uppercase()),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// src/s07_vm/receipts.rs
+use crate::s05_zk_lab::hash::sha256;
+
+use super::chain::Receipt;
+use super::vm::LogEntry;
+use super::world::Address;
+
+/*
+收据与事件日志：区块执行的"输出"
+
+    交易改了什么状态，看状态根；交易"说了什么"，看收据：成功与否、用了多少 gas、记了哪些日志。
+    日志不进世界状态 (合约自己读不到)，只写进收据 —— 它是给链外的钱包、浏览器看的，所以比写存储便宜得多。
+    每个区块的收据按交易顺序存一份 (BlockReceipts)；以太坊还会把收据的 Merkle 根写进区块头 (receipts_root)。
+
+按主题查日志：
+    "这个合约的 Transfer 事件都在哪些块里？" 逐块翻收据要读遍整条链。
+    所以每个块附一个 Bloom 过滤器 (以太坊的 logsBloom 是 2048 位，这里 256 位)：
+        每条日志的合约地址、每个主题各算 3 个比特位置 (SHA-256 的前 3 个字节)，置 1
+        查询时算出同样的 3 个位置：有一位是 0 -> 这块里一定没有，跳过；全是 1 -> 可能有，再去翻收据
+    只会误报 (位全是 1 但其实没有)，不会漏报。误报率 ≈ (1 - e^(-k·n/m))^k，n 是放进去的条目数。
+*/
+
+pub const BLOOM_BITS: usize = 256;
+pub const BLOOM_HASHES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Bloom([u8; BLOOM_BITS / 8]);
+
+impl Bloom {
+    fn positions(item: &[u8]) -> [usize; BLOOM_HASHES] {
+        let digest = sha256(item);
+        std::array::from_fn(|i| digest[i] as usize % BLOOM_BITS)
+    }
+
+    pub fn insert(&mut self, item: &[u8]) {
+        for p in Self::positions(item) {
+            self.0[p / 8] |= 1 << (p % 8);
+        }
+    }
+
+    pub fn might_contain(&self, item: &[u8]) -> bool {
+        Self::positions(item).iter().all(|&p| self.0[p / 8] & (1 << (p % 8)) != 0)
+    }
+
+    // 置 1 的位数：越满误报越多
+    pub fn ones(&self) -> u32 {
+        self.0.iter().map(|b| b.count_ones()).sum()
+    }
+
+    pub fn add_log(&mut self, log: &LogEntry) {
+        self.insert(&log.address.to_be_bytes());
+        for topic in &log.topics {
+            self.insert(&topic.to_be_bytes());
+        }
+    }
+}
+
+// 放进 items 个条目之后的理论误报率
+pub fn false_positive_rate(items: usize) -> f64 {
+    let (k, m) = (BLOOM_HASHES as f64, BLOOM_BITS as f64);
+    (1.0 - (-k * items as f64 / m).exp()).powf(k)
+}
+
+// 一个区块的全部收据，和 block.txs 一一对应
+#[derive(Debug, Clone)]
+pub struct BlockReceipts {
+    pub height: u64,
+    pub receipts: Vec<Receipt>,
+    pub bloom: Bloom,
+}
+
+impl BlockReceipts {
+    pub fn new(height: u64, receipts: Vec<Receipt>) -> Self {
+        let mut bloom = Bloom::default();
+        for log in receipts.iter().flat_map(|r| &r.logs) {
+            bloom.add_log(log);
+        }
+        BlockReceipts { height, receipts, bloom }
+    }
+
+    pub fn log_count(&self) -> usize {
+        self.receipts.iter().map(|r| r.logs.len()).sum()
+    }
+}
+
+// 查询条件：None 表示这一项不限
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogFilter {
+    pub address: Option<Address>,
+    pub topic: Option<u64>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, log: &LogEntry) -> bool {
+        self.address.is_none_or(|a| a == log.address) && self.topic.is_none_or(|t| log.topics.contains(&t))
+    }
+
+    pub fn might_match(&self, bloom: &Bloom) -> bool {
+        self.address.is_none_or(|a| bloom.might_contain(&a.to_be_bytes())) && self.topic.is_none_or(|t| bloom.might_contain(&t.to_be_bytes()))
+    }
+}
+
+// 查到的一条日志：区块高度、块内第几笔交易、日志本身
+#[derive(Debug, Clone)]
+pub struct LogHit {
+    pub height: u64,
+    pub tx_index: usize,
+    pub log: LogEntry,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    pub hits: Vec<LogHit>,
+    // Bloom 说"一定没有"、直接跳过的块
+    pub skipped: usize,
+    // Bloom 说"可能有"、翻了收据的块；其中一条都没找到的是误报
+    pub scanned: usize,
+    pub false_positives: usize,
+}
+
+pub fn query(blocks: &[BlockReceipts], filter: &LogFilter) -> LogQuery {
+    let mut result = LogQuery::default();
+    for block in blocks {
+        if !filter.might_match(&block.bloom) {
+            result.skipped += 1;
+            continue;
+        }
+        result.scanned += 1;
+        let before = result.hits.len();
+        for (tx_index, receipt) in block.receipts.iter().enumerate() {
+            for log in receipt.logs.iter().filter(|log| filter.matches(log)) {
+                result.hits.push(LogHit { height: block.height, tx_index, log: log.clone() });
+            }
+        }
+        if result.hits.len() == before {
+            result.false_positives += 1;
+        }
+    }
+    result
+}
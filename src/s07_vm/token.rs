@@ -0,0 +1,131 @@
+// src/s07_vm/token.rs
+use std::collections::HashMap;
+use std::fmt;
+
+use super::world::{short_address, Address};
+
+/*
+同质化代币账本 (ERC-20 的接口)
+
+    balances   : 地址 -> 余额
+    allowances : (持有人, 被授权人) -> 额度；approve 之后，被授权人可以用 transfer_from 替持有人转账，
+                 每转一次扣减额度 (交易所、自动扣款都是这么接的)
+    total_supply 恒等于所有余额之和；铸造从 0 地址"转入"，和 ERC-20 的约定一样
+
+事件：每个成功的操作追加一条 Transfer / Approval。
+    合约自己不需要事件 (状态都在 storage 里)，它们是写给链外看的：钱包、浏览器只要按顺序重放事件，就能重建出余额，
+    不用去读合约存储。失败的操作不产生事件。
+*/
+
+// 铸造时的"来源地址"
+pub const MINT_ADDRESS: Address = 0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Transfer { from: Address, to: Address, amount: u64 },
+    Approval { owner: Address, spender: Address, amount: u64 },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Transfer { from, to, amount } => write!(f, "Transfer({} -> {}, {})", short_address(*from), short_address(*to), amount),
+            Event::Approval { owner, spender, amount } => write!(f, "Approval({} 授权 {}, {})", short_address(*owner), short_address(*spender), amount),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    InsufficientBalance { owner: Address, balance: u64, needed: u64 },
+    InsufficientAllowance { owner: Address, spender: Address, allowance: u64, needed: u64 },
+    SupplyOverflow,
+    ZeroAddress,
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenError::InsufficientBalance { owner, balance, needed } => write!(f, "{} 余额 {}，不够 {}", short_address(*owner), balance, needed),
+            TokenError::InsufficientAllowance { owner, spender, allowance, needed } => {
+                write!(f, "{} 给 {} 的额度只剩 {}，不够 {}", short_address(*owner), short_address(*spender), allowance, needed)
+            }
+            TokenError::SupplyOverflow => write!(f, "总供应量溢出 u64"),
+            TokenError::ZeroAddress => write!(f, "不能转给 0 地址 (那是铸造的来源，转过去的币谁也花不了)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub name: String,
+    pub symbol: String,
+    total_supply: u64,
+    balances: HashMap<Address, u64>,
+    allowances: HashMap<(Address, Address), u64>,
+    events: Vec<Event>,
+}
+
+impl Token {
+    pub fn new(name: &str, symbol: &str) -> Self {
+        Token { name: name.to_string(), symbol: symbol.to_string(), total_supply: 0, balances: HashMap::new(), allowances: HashMap::new(), events: Vec::new() }
+    }
+
+    pub fn total_supply(&self) -> u64 {
+        self.total_supply
+    }
+
+    pub fn balance_of(&self, owner: Address) -> u64 {
+        self.balances.get(&owner).copied().unwrap_or(0)
+    }
+
+    pub fn allowance(&self, owner: Address, spender: Address) -> u64 {
+        self.allowances.get(&(owner, spender)).copied().unwrap_or(0)
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn mint(&mut self, to: Address, amount: u64) -> Result<(), TokenError> {
+        if to == MINT_ADDRESS {
+            return Err(TokenError::ZeroAddress);
+        }
+        self.total_supply = self.total_supply.checked_add(amount).ok_or(TokenError::SupplyOverflow)?;
+        // 余额之和 == total_supply，总量没溢出，单个余额也不会溢出
+        *self.balances.entry(to).or_insert(0) += amount;
+        self.events.push(Event::Transfer { from: MINT_ADDRESS, to, amount });
+        Ok(())
+    }
+
+    pub fn transfer(&mut self, from: Address, to: Address, amount: u64) -> Result<(), TokenError> {
+        if to == MINT_ADDRESS {
+            return Err(TokenError::ZeroAddress);
+        }
+        let balance = self.balance_of(from);
+        if balance < amount {
+            return Err(TokenError::InsufficientBalance { owner: from, balance, needed: amount });
+        }
+        self.balances.insert(from, balance - amount);
+        *self.balances.entry(to).or_insert(0) += amount;
+        self.events.push(Event::Transfer { from, to, amount });
+        Ok(())
+    }
+
+    // 直接覆盖额度 (不是累加)，和 ERC-20 一样
+    pub fn approve(&mut self, owner: Address, spender: Address, amount: u64) {
+        self.allowances.insert((owner, spender), amount);
+        self.events.push(Event::Approval { owner, spender, amount });
+    }
+
+    // spender 替 from 转账：额度和余额都要够，两个都检查完才改状态
+    pub fn transfer_from(&mut self, spender: Address, from: Address, to: Address, amount: u64) -> Result<(), TokenError> {
+        let allowance = self.allowance(from, spender);
+        if allowance < amount {
+            return Err(TokenError::InsufficientAllowance { owner: from, spender, allowance, needed: amount });
+        }
+        self.transfer(from, to, amount)?;
+        self.allowances.insert((from, spender), allowance - amount);
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+// src/s07_vm/vm.rs
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::s06_chain::gas::{self, GasMeter, OutOfGas};
+
+use super::opcode::{decode, Instr};
+use super::world::{contract_address, short_address, Address, World};
+
+/*
+解释器：逐条执行字节码
+
+    每一步先扣 gas 再执行 (和 S06 的 GasMeter 一样，扣不动就停)。
+    一笔交易只有一个计量表：合约调合约用的是同一份 gas，任何一层 out of gas 整笔交易都停。
+
+调用 (call)：一次调用 = 转 value + 执行对方的代码，返回一个 u64
+    1. 进入之前给整个世界状态拍快照 (玩具规模，直接 clone)
+    2. 调用失败 (Revert、非法跳转、栈下溢……) 就恢复快照，这一层和它里面所有嵌套调用的改动、日志全部作废
+    3. CALL 指令里的失败不会传染给调用方：压入 成功 = 0，调用方自己决定要不要 Revert
+       唯一的例外是 out of gas —— gas 用完了，谁都没法继续
+    调用深度有上限，否则合约可以无限递归把节点的栈撑爆。
+*/
+
+pub const MAX_STACK: usize = 1024;
+pub const MAX_DEPTH: usize = 16;
+// 部署：固定开销 + 按代码长度收费 (代码要永久保存在每个节点上)
+pub const CREATE_GAS: u64 = 32_000;
+pub const CODE_BYTE_GAS: u64 = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmError {
+    OutOfGas(OutOfGas),
+    StackUnderflow { pc: usize, instr: Instr },
+    StackOverflow { pc: usize },
+    BadJump { pc: usize, target: u64 },
+    InvalidCode(String),
+    Reverted { address: Address, pc: usize },
+    CallTooDeep,
+    Transfer(String),
+}
+
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VmError::OutOfGas(e) => write!(f, "{}", e),
+            VmError::StackUnderflow { pc, instr } => write!(f, "偏移 {} 的 {} 栈里的数不够", pc, instr),
+            VmError::StackOverflow { pc } => write!(f, "偏移 {} 栈溢出 (超过 {} 个)", pc, MAX_STACK),
+            VmError::BadJump { pc, target } => write!(f, "偏移 {} 跳到 {}，那里不是一条指令的开头", pc, target),
+            VmError::InvalidCode(e) => write!(f, "字节码不合法: {}", e),
+            VmError::Reverted { address, pc } => write!(f, "合约 {} 在偏移 {} 执行了 REVERT", short_address(*address), pc),
+            VmError::CallTooDeep => write!(f, "调用深度超过 {}", MAX_DEPTH),
+            VmError::Transfer(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+// 一条日志：哪个合约记的、主题 (LOG 没有，EMIT 有一个)、数据
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub address: Address,
+    pub topics: Vec<u64>,
+    pub data: u64,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.topics.as_slice() {
+            [] => write!(f, "{}: {}", short_address(self.address), self.data),
+            topics => write!(f, "{} {:?}: {}", short_address(self.address), topics, self.data),
+        }
+    }
+}
+
+// 合约代码能看到的调用环境
+#[derive(Debug, Clone)]
+pub struct CallContext {
+    pub caller: Address,
+    pub address: Address,
+    pub value: u64,
+    pub input: Vec<u64>,
+}
+
+pub struct Vm<'w> {
+    world: &'w mut World,
+    pub meter: GasMeter,
+    pub logs: Vec<LogEntry>,
+    // Some 时记录每一步 (偏移、指令、执行前的栈)
+    trace: Option<Vec<String>>,
+    depth: usize,
+}
+
+impl<'w> Vm<'w> {
+    pub fn new(world: &'w mut World, meter: GasMeter) -> Self {
+        Vm { world, meter, logs: Vec::new(), trace: None, depth: 0 }
+    }
+
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    pub fn take_trace(&mut self) -> Vec<String> {
+        self.trace.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    // 部署：合约地址由部署者和 nonce 决定；代码原样存下 (这里没有以太坊的构造函数)
+    pub fn deploy(&mut self, deployer: Address, nonce: u64, code: &[u8], value: u64) -> Result<Address, VmError> {
+        self.meter.charge(CREATE_GAS + CODE_BYTE_GAS * code.len() as u64).map_err(VmError::OutOfGas)?;
+        decode(code).map_err(VmError::InvalidCode)?;
+        if code.is_empty() {
+            return Err(VmError::InvalidCode(String::from("代码是空的")));
+        }
+        let address = contract_address(deployer, nonce);
+        if self.world.account(address).is_some_and(|a| a.is_contract()) {
+            return Err(VmError::InvalidCode(format!("{} 已经有合约了", short_address(address))));
+        }
+        self.world.transfer(deployer, address, value).map_err(VmError::Transfer)?;
+        self.world.account_mut(address).code = code.to_vec();
+        Ok(address)
+    }
+
+    pub fn call(&mut self, caller: Address, to: Address, value: u64, input: Vec<u64>) -> Result<u64, VmError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(VmError::CallTooDeep);
+        }
+        let snapshot = self.world.clone();
+        let log_count = self.logs.len();
+        self.depth += 1;
+        let result = match self.world.transfer(caller, to, value) {
+            Err(e) => Err(VmError::Transfer(e)),
+            // 普通账户没有代码：只是转账
+            Ok(()) if !self.world.account(to).is_some_and(|a| a.is_contract()) => Ok(0),
+            Ok(()) => {
+                let code = self.world.code(to).to_vec();
+                self.run(&code, &CallContext { caller, address: to, value, input })
+            }
+        };
+        self.depth -= 1;
+        if result.is_err() {
+            *self.world = snapshot;
+            self.logs.truncate(log_count);
+        }
+        result
+    }
+
+    fn run(&mut self, code: &[u8], ctx: &CallContext) -> Result<u64, VmError> {
+        let program = decode(code).map_err(VmError::InvalidCode)?;
+        // 字节偏移 -> 第几条指令，跳转用
+        let starts: HashMap<usize, usize> = program.iter().enumerate().map(|(i, (pc, _))| (*pc, i)).collect();
+        let mut stack: Vec<u64> = Vec::new();
+        let mut next = 0;
+        while let Some(&(pc, instr)) = program.get(next) {
+            if let Some(trace) = self.trace.as_mut() {
+                let shown: Vec<String> = stack.iter().map(|v| v.to_string()).collect();
+                trace.push(format!("{}{:>4}  {:<10} [{}]", "  ".repeat(self.depth - 1), pc, instr.to_string(), shown.join(", ")));
+            }
+            self.meter.charge(instr.gas()).map_err(VmError::OutOfGas)?;
+            next += 1;
+            let underflow = || VmError::StackUnderflow { pc, instr };
+            let pop = |stack: &mut Vec<u64>| stack.pop().ok_or_else(underflow);
+            match instr {
+                Instr::Stop => return Ok(0),
+                Instr::Push(value) => stack.push(value),
+                Instr::Pop => {
+                    pop(&mut stack)?;
+                }
+                Instr::Dup(n) => {
+                    let value = *stack.iter().rev().nth(n as usize - 1).ok_or_else(underflow)?;
+                    stack.push(value);
+                }
+                Instr::Swap(n) => {
+                    let top = stack.len().checked_sub(1).ok_or_else(underflow)?;
+                    let other = top.checked_sub(n as usize).ok_or_else(underflow)?;
+                    stack.swap(top, other);
+                }
+                Instr::Add | Instr::Sub | Instr::Mul | Instr::Div | Instr::Lt | Instr::Gt | Instr::Eq => {
+                    let b = pop(&mut stack)?;
+                    let a = pop(&mut stack)?;
+                    stack.push(match instr {
+                        Instr::Add => a.wrapping_add(b),
+                        Instr::Sub => a.wrapping_sub(b),
+                        Instr::Mul => a.wrapping_mul(b),
+                        Instr::Div => a.checked_div(b).unwrap_or(0),
+                        Instr::Lt => (a < b) as u64,
+                        Instr::Gt => (a > b) as u64,
+                        _ => (a == b) as u64,
+                    });
+                }
+                Instr::IsZero => {
+                    let a = pop(&mut stack)?;
+                    stack.push((a == 0) as u64);
+                }
+                Instr::Jump | Instr::JumpI => {
+                    let target = pop(&mut stack)?;
+                    let taken = instr == Instr::Jump || pop(&mut stack)? != 0;
+                    if taken {
+                        next = *starts.get(&(target as usize)).ok_or(VmError::BadJump { pc, target })?;
+                    }
+                }
+                Instr::Load => {
+                    let key = pop(&mut stack)?;
+                    stack.push(self.world.storage(ctx.address, key));
+                }
+                Instr::Store => {
+                    let key = pop(&mut stack)?;
+                    let value = pop(&mut stack)?;
+                    let account = self.world.account_mut(ctx.address);
+                    if !account.storage.contains_key(&key) {
+                        self.meter.charge(gas::STORE_NEW - gas::STORE_UPDATE).map_err(VmError::OutOfGas)?;
+                    }
+                    self.world.account_mut(ctx.address).storage.insert(key, value);
+                }
+                Instr::Caller => stack.push(ctx.caller),
+                Instr::CallValue => stack.push(ctx.value),
+                Instr::Address => stack.push(ctx.address),
+                Instr::Arg => {
+                    let i = pop(&mut stack)?;
+                    stack.push(ctx.input.get(i as usize).copied().unwrap_or(0));
+                }
+                Instr::Balance => {
+                    let address = pop(&mut stack)?;
+                    stack.push(self.world.balance(address));
+                }
+                Instr::Call => {
+                    let to = pop(&mut stack)?;
+                    let value = pop(&mut stack)?;
+                    let arg = pop(&mut stack)?;
+                    if value > 0 {
+                        self.meter.charge(gas::TRANSFER).map_err(VmError::OutOfGas)?;
+                    }
+                    match self.call(ctx.address, to, value, vec![arg]) {
+                        Ok(ret) => stack.extend([ret, 1]),
+                        Err(e @ VmError::OutOfGas(_)) => return Err(e),
+                        Err(_) => stack.extend([0, 0]),
+                    }
+                }
+                Instr::Log => {
+                    let value = pop(&mut stack)?;
+                    self.meter.charge(gas::LOG_BYTE * 8).map_err(VmError::OutOfGas)?;
+                    self.logs.push(LogEntry { address: ctx.address, topics: Vec::new(), data: value });
+                }
+                Instr::Emit => {
+                    let topic = pop(&mut stack)?;
+                    let data = pop(&mut stack)?;
+                    self.meter.charge(gas::LOG_BYTE * 16).map_err(VmError::OutOfGas)?;
+                    self.logs.push(LogEntry { address: ctx.address, topics: vec![topic], data });
+                }
+                Instr::Return => return pop(&mut stack),
+                Instr::Revert => return Err(VmError::Reverted { address: ctx.address, pc }),
+            }
+            if stack.len() > MAX_STACK {
+                return Err(VmError::StackOverflow { pc });
+            }
+        }
+        // 走到代码末尾 = STOP
+        Ok(0)
+    }
+}
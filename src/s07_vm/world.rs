@@ -0,0 +1,130 @@
+// src/s07_vm/world.rs
+use std::collections::{BTreeMap, HashMap};
+
+use crate::s05_zk_lab::hash::{sha256, Digest};
+use crate::s06_chain::trie::PatriciaTrie;
+
+/*
+合约链的世界状态：地址 -> 账户
+
+    和 S06 的 account.rs 一样是账户模型，多了两样东西：
+        code    : 合约的字节码；普通账户 (外部账户，有私钥的人) 的 code 是空的
+        storage : 合约自己的存储，u64 -> u64 的 HashMap；只有合约自己的代码能读写
+    地址是 u64 (栈上一个字就能放下)：
+        外部账户 = SHA-256(名字) 的前 8 字节
+        合约     = SHA-256(部署者地址, 部署者的 nonce) 的前 8 字节 —— 部署前就能算出来，而且不会重复
+
+状态根：和 S06 一样两层 Patricia 树。存储是 HashMap，遍历顺序每次不一样，
+    但 Patricia 树的根只由 (键, 值) 集合决定，和插入顺序无关，所以直接遍历就行。
+*/
+
+pub type Address = u64;
+
+fn truncate(digest: &Digest) -> Address {
+    u64::from_be_bytes(digest[..8].try_into().expect("digest has 32 bytes"))
+}
+
+pub fn address_of(name: &str) -> Address {
+    truncate(&sha256(name.as_bytes()))
+}
+
+pub fn contract_address(deployer: Address, nonce: u64) -> Address {
+    let mut data = deployer.to_be_bytes().to_vec();
+    data.extend_from_slice(&nonce.to_be_bytes());
+    truncate(&sha256(&data))
+}
+
+// 打印用：前 4 字节
+pub fn short_address(address: Address) -> String {
+    format!("0x{:08x}..", address >> 32)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Account {
+    pub balance: u64,
+    pub nonce: u64,
+    pub code: Vec<u8>,
+    pub storage: HashMap<u64, u64>,
+}
+
+impl Account {
+    pub fn is_contract(&self) -> bool {
+        !self.code.is_empty()
+    }
+
+    pub fn storage_root(&self) -> Digest {
+        let mut trie = PatriciaTrie::new();
+        for (key, value) in &self.storage {
+            trie.insert(&key.to_be_bytes(), value.to_be_bytes().to_vec());
+        }
+        trie.root_hash()
+    }
+
+    // 余额 + nonce + 代码哈希 + 存储根
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(80);
+        data.extend_from_slice(&self.balance.to_be_bytes());
+        data.extend_from_slice(&self.nonce.to_be_bytes());
+        data.extend_from_slice(&sha256(&self.code));
+        data.extend_from_slice(&self.storage_root());
+        data
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct World {
+    accounts: BTreeMap<Address, Account>,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn credit(&mut self, address: Address, amount: u64) {
+        self.accounts.entry(address).or_default().balance += amount;
+    }
+
+    pub fn account(&self, address: Address) -> Option<&Account> {
+        self.accounts.get(&address)
+    }
+
+    // 没有的账户当场建一个空的 (转账给新地址、部署合约都是这样"出生"的)
+    pub fn account_mut(&mut self, address: Address) -> &mut Account {
+        self.accounts.entry(address).or_default()
+    }
+
+    pub fn balance(&self, address: Address) -> u64 {
+        self.account(address).map_or(0, |a| a.balance)
+    }
+
+    pub fn nonce(&self, address: Address) -> u64 {
+        self.account(address).map_or(0, |a| a.nonce)
+    }
+
+    pub fn code(&self, address: Address) -> &[u8] {
+        self.account(address).map_or(&[], |a| &a.code)
+    }
+
+    pub fn storage(&self, address: Address, key: u64) -> u64 {
+        self.account(address).and_then(|a| a.storage.get(&key)).copied().unwrap_or(0)
+    }
+
+    pub fn transfer(&mut self, from: Address, to: Address, amount: u64) -> Result<(), String> {
+        let balance = self.balance(from);
+        if balance < amount {
+            return Err(format!("{} 余额 {} 不够转 {}", short_address(from), balance, amount));
+        }
+        self.account_mut(from).balance -= amount;
+        self.credit(to, amount);
+        Ok(())
+    }
+
+    pub fn state_root(&self) -> Digest {
+        let mut trie = PatriciaTrie::new();
+        for (address, account) in &self.accounts {
+            trie.insert(&address.to_be_bytes(), account.encode());
+        }
+        trie.root_hash()
+    }
+}
@@ -0,0 +1,156 @@
+// src/s08_async/ex01_executor.rs
+use std::mem;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::executor::{block_on, sleep, Executor};
+
+/*
+业务场景：节点启动时向几个种子节点请求区块头。每个请求 = 建立连接 (等网络) + 下载 (等网络)，CPU 几乎不干活。
+    S04 的做法是一个请求一个线程；请求成千上万时，线程的栈和上下文切换都是成本。
+    async 的做法：每个请求是一个 Future (状态机)，一个线程轮流推进它们 —— 谁在等网络，就先去 poll 别人。
+
+本练习 (手写执行器，见 executor.rs)：
+    1. Future 是惰性的：调用 async fn 只是造出一个状态机，不 poll 它什么都不会发生；block_on 跑完一个请求
+    2. 在一个 async 块里依次 .await 四个请求：总耗时 ≈ 各请求之和
+    3. 四个请求 spawn 成四个任务，交给执行器：总耗时 ≈ 最慢的那个；打印每一次 poll，全程只有一个线程
+    4. 错误示范：其中一个任务在 async 代码里调用阻塞的 thread::sleep —— 整个执行器跟着卡住
+*/
+
+#[derive(Debug, Clone, Copy)]
+struct Peer {
+    name: &'static str,
+    connect_ms: u64,
+    download_ms: u64,
+    headers: u64,
+}
+
+const PEERS: [Peer; 4] = [
+    Peer { name: "seed-1", connect_ms: 60, download_ms: 90, headers: 10 },
+    Peer { name: "seed-2", connect_ms: 30, download_ms: 50, headers: 8 },
+    Peer { name: "seed-3", connect_ms: 120, download_ms: 100, headers: 12 },
+    Peer { name: "seed-4", connect_ms: 20, download_ms: 30, headers: 5 },
+];
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+// 模拟一次网络请求，返回完成的时刻 (从 start 算起)
+async fn fetch(peer: Peer, start: Instant, blocking: bool) -> Duration {
+    println!("  → [{}] {:>7.1?} 发起连接", peer.name, start.elapsed());
+    sleep(ms(peer.connect_ms)).await;
+    println!("  ⇄ [{}] {:>7.1?} 已连接，请求 {} 个区块头", peer.name, start.elapsed(), peer.headers);
+    if blocking {
+        // ❌ 阻塞调用：执行器线程就睡在这里，别的任务的定时器到点了也没人去 poll 它们
+        thread::sleep(ms(peer.download_ms));
+    } else {
+        sleep(ms(peer.download_ms)).await;
+    }
+    let done = start.elapsed();
+    println!("  ← [{}] {:>7.1?} 收到 {} 个区块头", peer.name, done, peer.headers);
+    done
+}
+
+struct FetchAll {
+    elapsed: Duration,
+    // 按完成先后：(节点, 完成时刻)
+    finished: Vec<(&'static str, Duration)>,
+    polls: u64,
+    stale_wakes: u64,
+}
+
+// 四个请求各 spawn 成一个任务；blocking_peer 指定哪个任务用阻塞的 thread::sleep
+fn fetch_all(trace: bool, blocking_peer: Option<&str>) -> FetchAll {
+    let start = Instant::now();
+    let mut executor = Executor::new().with_trace(trace);
+    let spawner = executor.spawner();
+    let finished = Arc::new(Mutex::new(Vec::new()));
+    for peer in PEERS {
+        let finished = Arc::clone(&finished);
+        let blocking = blocking_peer == Some(peer.name);
+        spawner.spawn(peer.name, async move {
+            let done = fetch(peer, start, blocking).await;
+            finished.lock().unwrap().push((peer.name, done));
+        });
+    }
+    executor.run();
+    let finished = finished.lock().unwrap().clone();
+    FetchAll { elapsed: start.elapsed(), finished, polls: executor.polls(), stale_wakes: executor.stale_wakes() }
+}
+
+pub fn run() {
+    println!("--- S08 Ex01: 手写执行器 (任务队列、Arc<Task> 做 Waker、block_on，并发模拟网络请求) ---");
+    let sum: u64 = PEERS.iter().map(|p| p.connect_ms + p.download_ms).sum();
+    let max = PEERS.iter().map(|p| p.connect_ms + p.download_ms).max().unwrap_or(0);
+
+    // ==========================================
+    // 1. Future 是惰性的
+    // ==========================================
+    println!("\n[1] 调用 async fn，但先不 poll");
+    let start = Instant::now();
+    let future = fetch(PEERS[0], start, false);
+    println!("  造出了一个 Future (状态机 {} 字节)，上面一行\"发起连接\"都没打印：没人 poll 它，它就什么都不做", mem::size_of_val(&future));
+    println!("  交给 block_on：poll -> Pending -> park 线程 -> 定时器 unpark -> 再 poll ...");
+    let done = block_on(future);
+    println!("  block_on 返回，用时 {:.1?}", done);
+
+    // ==========================================
+    // 2. 依次 await
+    // ==========================================
+    println!("\n[2] 一个 async 块里依次 .await 四个请求");
+    let start = Instant::now();
+    block_on(async {
+        for peer in PEERS {
+            fetch(peer, start, false).await;
+        }
+    });
+    println!("  总用时 {:.0?} ≈ 各请求之和 {}ms：.await 是\"等它做完再往下走\"，不会自动并发", start.elapsed(), sum);
+
+    // ==========================================
+    // 3. spawn 成任务，并发执行
+    // ==========================================
+    println!("\n[3] 四个请求 spawn 成四个任务，执行器在一个线程上轮流 poll");
+    let main_thread = thread::current().id();
+    let report = fetch_all(true, None);
+    println!("  总用时 {:.0?} ≈ 最慢的请求 {}ms；一共 poll 了 {} 次 (每个任务: 连接 Pending、下载 Pending、Ready)", report.elapsed, max, report.polls);
+    println!("  任务完成之后才到的唤醒 {} 次 (执行器直接丢掉)", report.stale_wakes);
+    println!("  所有 poll 都发生在主线程 {:?} 上：并发 (交替推进)，不是并行", main_thread);
+
+    // ==========================================
+    // 4. 在 async 代码里阻塞
+    // ==========================================
+    let culprit = PEERS[2];
+    println!("\n[4] 同样四个任务，但 {} 下载时调用阻塞的 thread::sleep({}ms)", culprit.name, culprit.download_ms);
+    let report = fetch_all(false, Some(culprit.name));
+    println!("  总用时 {:.0?}；各任务完成时刻 (和 [3] 里的理想时刻比):", report.elapsed);
+    for (name, done) in report.finished {
+        let peer = PEERS.iter().find(|p| p.name == name).copied().unwrap_or(culprit);
+        let ideal = ms(peer.connect_ms + peer.download_ms);
+        let late = done.saturating_sub(ideal);
+        let mark = if late > ms(10) { "❌ 被拖慢" } else { "✅" };
+        println!("    {}  理想 {:>6.0?}，实际 {:>6.0?}  {}", name, ideal, done, mark);
+    }
+    println!("  {} 睡着的时候执行器线程也睡着：别人的网络早就回来了，也要等它醒来才轮得到", culprit.name);
+}
+
+/*
+关键点总结：
+    1. Future 三件事：
+        惰性：async fn 返回一个状态机，每个 .await 是一个暂停点，poll 一次最多推进到下一个暂停点；
+        Pending 必须带着 Waker 走：返回 Pending 之前把 cx.waker() 交给会让它就绪的东西 (这里是定时器)，否则它永远不会再被 poll；
+        Pin：状态机里可能有指向自己字段的引用 (跨 .await 借用局部变量)，一旦被 poll 就不能再挪动，所以任务是 Pin<Box<..>>。
+
+    2. 执行器 = 就绪队列 + 一个循环：
+        取出任务 -> 用它自己的 Arc<Task> 造 Waker -> poll；
+        Pending 的任务不在队列里，只有 Waker 被调用时才回来 —— 没有忙等，队列空了线程就睡下。
+
+    3. 一个线程也能"同时"等很多个请求：
+        等待的是网络 (定时器)，不是 CPU；一个请求在等，线程就去推进别的请求。
+        代价是任务必须"合作"：poll 里不能阻塞，也不能长时间占着 CPU，否则所有任务一起卡住。
+
+    4. 阻塞操作怎么办：
+        真实运行时提供异步版本的 sleep / 读写 (tokio::time::sleep、TcpStream)；
+        实在要调用阻塞代码 (重计算、同步库)，交给专门的线程池 (tokio 的 spawn_blocking)，别在执行器线程上做。
+*/
@@ -0,0 +1,325 @@
+// src/s08_async/ex02_manual_future.rs
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::executor::Executor;
+
+/*
+业务场景：ex01 里的 sleep() 和 async fn 都是现成的，这次把 Future 的 poll 亲手写出来。
+    矿工每隔一段时间出一个块 (TimerFuture)，验证任务从一条异步 channel 里等区块 (ChannelRecvFuture)。
+    两个 Future 的套路一样：一份共享状态 (Arc<Mutex<..>>) 里放"结果"和"Waker"，
+    poll 时有结果就 Ready，没有就把 cx.waker() 存进去、返回 Pending；产生结果的一方 (计时线程 / 发送端) 负责 wake。
+
+    每次 poll 都打印一行状态转移：
+        [起始状态] --原因--> [新状态]  => Pending / Ready
+
+    TimerFuture:                                   ChannelRecvFuture:
+        [Init] --启动计时线程, 存 Waker--> [Waiting]       [Empty] --存 Waker--> [Waiting]
+        [Waiting] --还没到点, 更新 Waker--> [Waiting]      [Waiting] --队列里有区块--> [Got]      => Ready(Some)
+        [Waiting] --completed = true--> [Done]          [Waiting] --发送端全部 drop--> [Closed] => Ready(None)
+
+本练习：
+    1. 一个 TimerFuture 跑在手写执行器上：看执行器的 poll 和 Future 自己的状态转移交替打印
+    2. 矿工任务 (TimerFuture + send) 和一个 P2P 线程 (普通 OS 线程) 同时往 channel 里发区块，验证任务 recv().await
+    3. 手动 poll：用一个只会计数的 Waker 当执行器 —— 再对比一个"忘了存 Waker"的 TimerFuture：到点了也没人叫醒它
+*/
+
+fn transition(label: &str, poll: u32, from: &str, why: &str, to: &str, result: &str) {
+    println!("      {} poll #{}: [{}] --{}--> [{}]  => {}", label, poll, from, why, to, result);
+}
+
+// ==========================================
+// TimerFuture
+// ==========================================
+struct TimerShared {
+    completed: bool,
+    waker: Option<Waker>,
+}
+
+struct TimerFuture {
+    label: String,
+    duration: Duration,
+    shared: Arc<Mutex<TimerShared>>,
+    started: bool,
+    polls: u32,
+    // 故意写错：Pending 时不存 Waker
+    forget_waker: bool,
+}
+
+impl TimerFuture {
+    fn new(label: &str, duration: Duration) -> Self {
+        TimerFuture {
+            label: label.to_string(),
+            duration,
+            shared: Arc::new(Mutex::new(TimerShared { completed: false, waker: None })),
+            started: false,
+            polls: 0,
+            forget_waker: false,
+        }
+    }
+
+    fn forgetting_waker(mut self) -> Self {
+        self.forget_waker = true;
+        self
+    }
+}
+
+impl Future for TimerFuture {
+    type Output = ();
+
+    // 字段全是 Unpin，TimerFuture 也就是 Unpin：可以直接 get_mut() 拿到 &mut Self
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        this.polls += 1;
+        let mut shared = this.shared.lock().unwrap();
+        if shared.completed {
+            transition(&this.label, this.polls, "Waiting", "completed = true", "Done", "Ready");
+            return Poll::Ready(());
+        }
+        // 每次 Pending 都换成最新的 Waker：Future 可能被挪到了别的任务上，旧 Waker 叫醒的是别人
+        if !this.forget_waker {
+            shared.waker = Some(cx.waker().clone());
+        }
+        let stored = if this.forget_waker { "❌ 没存 Waker" } else { "存 Waker" };
+        if this.started {
+            transition(&this.label, this.polls, "Waiting", &format!("还没到点, {}", stored), "Waiting", "Pending");
+        } else {
+            this.started = true;
+            transition(&this.label, this.polls, "Init", &format!("启动计时线程, {}", stored), "Waiting", "Pending");
+            let (shared, duration, label) = (Arc::clone(&this.shared), this.duration, this.label.clone());
+            thread::spawn(move || {
+                thread::sleep(duration);
+                let mut shared = shared.lock().unwrap();
+                shared.completed = true;
+                match shared.waker.take() {
+                    Some(waker) => {
+                        println!("      ⏰ {} 计时线程: 到点，wake()", label);
+                        // 先把 Waker 从锁里拿出来再调用也行；这里执行器的 wake 只是往 channel 里发一下，不会回头抢这把锁
+                        waker.wake();
+                    }
+                    None => println!("      ⏰ {} 计时线程: 到点，但手里没有 Waker，叫不醒任何人", label),
+                }
+            });
+        }
+        Poll::Pending
+    }
+}
+
+// ==========================================
+// 异步 channel 与 ChannelRecvFuture
+// ==========================================
+struct ChanState<T> {
+    queue: VecDeque<T>,
+    waker: Option<Waker>,
+    senders: usize,
+}
+
+struct AsyncSender<T> {
+    state: Arc<Mutex<ChanState<T>>>,
+}
+
+struct AsyncReceiver<T> {
+    state: Arc<Mutex<ChanState<T>>>,
+    recvs: u32,
+}
+
+fn channel<T>() -> (AsyncSender<T>, AsyncReceiver<T>) {
+    let state = Arc::new(Mutex::new(ChanState { queue: VecDeque::new(), waker: None, senders: 1 }));
+    (AsyncSender { state: Arc::clone(&state) }, AsyncReceiver { state, recvs: 0 })
+}
+
+impl<T> AsyncSender<T> {
+    // 不会阻塞：放进队列，叫醒等着的接收方
+    fn send(&self, item: T) {
+        let waker = {
+            let mut state = self.state.lock().unwrap();
+            state.queue.push_back(item);
+            state.waker.take()
+        };
+        // 在锁外面 wake：被叫醒的一方如果在别的线程上立刻 poll，不用等这把锁
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Clone for AsyncSender<T> {
+    fn clone(&self) -> Self {
+        self.state.lock().unwrap().senders += 1;
+        AsyncSender { state: Arc::clone(&self.state) }
+    }
+}
+
+// 最后一个发送端消失时也要 wake：接收方要醒来看到"关闭了"，否则它会永远 Pending
+impl<T> Drop for AsyncSender<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.state.lock().unwrap();
+            state.senders -= 1;
+            if state.senders == 0 {
+                state.waker.take()
+            } else {
+                None
+            }
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> AsyncReceiver<T> {
+    // &mut self：同一时刻只能有一个 recv 在等 (单消费者)，编译器替我们保证
+    fn recv(&mut self) -> ChannelRecvFuture<'_, T> {
+        self.recvs += 1;
+        ChannelRecvFuture { state: &self.state, label: format!("recv#{}", self.recvs), polls: 0 }
+    }
+}
+
+struct ChannelRecvFuture<'a, T> {
+    state: &'a Mutex<ChanState<T>>,
+    label: String,
+    polls: u32,
+}
+
+impl<T> Future for ChannelRecvFuture<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        this.polls += 1;
+        let from = if this.polls == 1 { "Empty" } else { "Waiting" };
+        let mut state = this.state.lock().unwrap();
+        if let Some(item) = state.queue.pop_front() {
+            transition(&this.label, this.polls, from, "队列里有区块", "Got", "Ready(Some)");
+            return Poll::Ready(Some(item));
+        }
+        if state.senders == 0 {
+            transition(&this.label, this.polls, from, "发送端全部 drop", "Closed", "Ready(None)");
+            return Poll::Ready(None);
+        }
+        state.waker = Some(cx.waker().clone());
+        transition(&this.label, this.polls, from, "队列空, 存 Waker", "Waiting", "Pending");
+        Poll::Pending
+    }
+}
+
+// ==========================================
+// 手动 poll 用的 Waker：只数自己被叫了几次
+// ==========================================
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn poll_by_hand(mut timer: TimerFuture) {
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = Waker::from(Arc::clone(&counter));
+    let mut cx = Context::from_waker(&waker);
+    let wait = timer.duration + Duration::from_millis(30);
+
+    let first = Pin::new(&mut timer).poll(&mut cx);
+    thread::sleep(wait);
+    let wakes = counter.0.load(Ordering::SeqCst);
+    println!("    睡了 {:?}，Waker 被叫了 {} 次", wait, wakes);
+    if wakes > 0 {
+        let second = Pin::new(&mut timer).poll(&mut cx);
+        println!("    有人叫 -> 执行器会再 poll 一次：{:?} -> {:?} ✅", first, second);
+    } else {
+        println!("    没人叫 -> 真正的执行器永远不会再 poll 它：这个任务就此挂起，不报错、不退出 ❌");
+        println!("    (其实 completed 早就是 true 了，只要再 poll 一次就是 Ready —— 但没有人知道该 poll 它)");
+    }
+}
+
+pub fn run() {
+    println!("--- S08 Ex02: 手写 Future (TimerFuture、ChannelRecvFuture，打印 poll 的状态转移) ---");
+
+    // ==========================================
+    // 1. TimerFuture 跑在执行器上
+    // ==========================================
+    println!("\n[1] 一个任务 await 一个 50ms 的 TimerFuture");
+    let mut executor = Executor::new().with_trace(true);
+    executor.spawner().spawn("出块计时", async {
+        TimerFuture::new("timer", Duration::from_millis(50)).await;
+    });
+    executor.run();
+    println!("  执行器一共 poll 了 {} 次：第一次启动计时线程后 Pending，wake 之后第二次 Ready", executor.polls());
+
+    // ==========================================
+    // 2. 异步 channel：任务和普通线程一起发区块
+    // ==========================================
+    println!("\n[2] 矿工任务每 40ms 出一个块，P2P 线程 (普通 OS 线程) 每 70ms 转发一个块，验证任务逐个 recv().await");
+    let start = Instant::now();
+    let mut executor = Executor::new().with_trace(true);
+    let spawner = executor.spawner();
+    let (tx, mut rx) = channel::<String>();
+
+    let p2p_tx = tx.clone();
+    let p2p = thread::spawn(move || {
+        for n in 1..=2 {
+            thread::sleep(Duration::from_millis(70));
+            println!("    [p2p 线程 {:>6.1?}] send(peer-block-{})", start.elapsed(), n);
+            p2p_tx.send(format!("peer-block-{}", n));
+        }
+        // p2p_tx 在这里 drop
+    });
+    spawner.spawn("miner", async move {
+        for n in 1..=3 {
+            TimerFuture::new(&format!("timer#{}", n), Duration::from_millis(40)).await;
+            println!("    [miner {:>6.1?}] send(block-{})", start.elapsed(), n);
+            tx.send(format!("block-{}", n));
+        }
+        // tx 在这里 drop；两个发送端都没了，验证任务的下一次 recv 返回 None
+    });
+    spawner.spawn("validator", async move {
+        let mut got = Vec::new();
+        while let Some(block) = rx.recv().await {
+            println!("    [validator {:>6.1?}] ✅ 验证 {}", start.elapsed(), block);
+            got.push(block);
+        }
+        println!("    [validator {:>6.1?}] channel 关闭，一共验证 {} 个块：{:?}", start.elapsed(), got.len(), got);
+    });
+    executor.run();
+    p2p.join().unwrap();
+
+    // ==========================================
+    // 3. 手动 poll
+    // ==========================================
+    println!("\n[3] 不用执行器，手动 poll：Waker 只会数数");
+    println!("  a. 正常的 TimerFuture");
+    poll_by_hand(TimerFuture::new("timer", Duration::from_millis(30)));
+    println!("  b. 忘了存 Waker 的 TimerFuture");
+    poll_by_hand(TimerFuture::new("broken", Duration::from_millis(30)).forgetting_waker());
+}
+
+/*
+关键点总结：
+    1. 手写 Future 的固定套路：
+        共享状态里放"结果"和"Waker"；poll 时先看结果，有就 Ready；
+        没有就把 cx.waker().clone() 存进去再返回 Pending —— 存 Waker 和检查结果要在同一把锁里，
+        否则结果恰好在两步之间到达，wake 时还没有 Waker，唤醒就丢了。
+
+    2. Pending 的契约：
+        返回 Pending 等于承诺"将来会有人调用这个 Waker"。
+        忘了存 Waker 不会报错，任务只是永远停住 —— 这是手写 Future 最常见的 bug。
+
+    3. 唤醒可以来自任何线程：
+        计时线程、P2P 线程都是普通 OS 线程，它们只需要一个 Waker (Send + Sync) 就能让执行器线程上的任务继续；
+        Waker 就是 async 世界和外部世界 (线程、IO 事件) 之间的接口。
+
+    4. 关闭也要唤醒：
+        最后一个发送端 drop 时叫醒接收方，它才能看到 None 退出；和 S04 ex12 里 close() 要 notify_all 是同一个道理。
+
+    5. Pin 和 Unpin：
+        这两个 Future 的字段都是普通数据，是 Unpin 的，poll 里可以直接 get_mut()，手动 poll 时用 Pin::new(&mut f) 就行；
+        async fn 生成的状态机可能引用自身，不是 Unpin，只能 Box::pin / pin! 之后再 poll。
+*/
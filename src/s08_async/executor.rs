@@ -0,0 +1,222 @@
+// src/s08_async/executor.rs
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+/*
+手写的单线程执行器 (executor)：不引入 tokio，只用标准库
+
+    async fn 被编译成一个状态机，它实现了 Future：
+        poll(cx) -> Poll::Ready(结果)  做完了
+        poll(cx) -> Poll::Pending      还没好；返回之前必须把 cx.waker() 交给"会让它变好的那个东西"
+    Future 自己不会动，得有人反复 poll 它 —— 这就是执行器：
+        就绪队列 (mpsc channel) 里放着"值得再 poll 一次"的任务，执行器一个一个取出来 poll；
+        Pending 的任务不会被忙等，它躺着不动，直到有人调用它的 Waker，把它重新放回就绪队列。
+
+    Waker 怎么来：任务本身就是 Arc<Task>，Task 实现了 std::task::Wake，
+        Waker::from(Arc<Task>) 就是一个 Waker；wake() = 把这个 Arc<Task> 发回就绪队列。
+
+    谁来调用 wake：真实运行时里是 epoll/kqueue 这样的"反应器" (reactor)。
+    这里只模拟网络延迟，所以只有一个定时器线程：sleep() 把 (到期时间, Waker) 登记给它，到点它就 wake。
+
+    block_on：不需要任务队列的最小执行器 —— 在当前线程上 poll 一个 Future，
+        Pending 就 park 当前线程，Waker 被调用时 unpark。
+*/
+
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+pub struct Task {
+    id: usize,
+    name: String,
+    // 执行器 poll 时把 Future 取出来，Pending 再放回去；None 表示已经完成
+    future: Mutex<Option<BoxFuture>>,
+    ready: Sender<Arc<Task>>,
+}
+
+impl Wake for Task {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        // 执行器只要还在，发送就不会失败；执行器不在了，唤醒也就没有意义了
+        let _ = self.ready.send(Arc::clone(self));
+    }
+}
+
+// 往执行器里放任务的句柄：可以 clone，任务里也可以再 spawn 新任务
+#[derive(Clone)]
+pub struct Spawner {
+    ready: Sender<Arc<Task>>,
+    live: Arc<AtomicUsize>,
+    next_id: Arc<AtomicUsize>,
+}
+
+impl Spawner {
+    pub fn spawn(&self, name: &str, future: impl Future<Output = ()> + Send + 'static) {
+        let task = Arc::new(Task {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            name: name.to_string(),
+            future: Mutex::new(Some(Box::pin(future))),
+            ready: self.ready.clone(),
+        });
+        self.live.fetch_add(1, Ordering::SeqCst);
+        // 新任务先 poll 一次：不 poll 它永远不会开始
+        let _ = self.ready.send(task);
+    }
+}
+
+pub struct Executor {
+    ready: Receiver<Arc<Task>>,
+    spawner: Spawner,
+    trace: bool,
+    polls: u64,
+    // 任务已经完成之后才收到的唤醒 (比如同一个 Waker 被登记了两次)
+    stale_wakes: u64,
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Executor {
+    pub fn new() -> Self {
+        let (ready_tx, ready) = mpsc::channel();
+        let spawner = Spawner { ready: ready_tx, live: Arc::new(AtomicUsize::new(0)), next_id: Arc::new(AtomicUsize::new(1)) };
+        Executor { ready, spawner, trace: false, polls: 0, stale_wakes: 0 }
+    }
+
+    // 打印每一次 poll 的结果
+    pub fn with_trace(mut self, trace: bool) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    pub fn spawner(&self) -> Spawner {
+        self.spawner.clone()
+    }
+
+    pub fn polls(&self) -> u64 {
+        self.polls
+    }
+
+    pub fn stale_wakes(&self) -> u64 {
+        self.stale_wakes
+    }
+
+    // 一直跑到所有任务都完成
+    pub fn run(&mut self) {
+        let start = Instant::now();
+        while self.spawner.live.load(Ordering::SeqCst) > 0 {
+            // 队列空了就阻塞在这里 (线程睡下，不占 CPU)，直到某个 Waker 把任务送回来
+            let task = self.ready.recv().expect("执行器自己持有一个 Sender，队列不会断开");
+            let mut slot = task.future.lock().unwrap();
+            let Some(mut future) = slot.take() else {
+                self.stale_wakes += 1;
+                continue;
+            };
+            let waker = Waker::from(Arc::clone(&task));
+            let mut cx = Context::from_waker(&waker);
+            self.polls += 1;
+            let result = future.as_mut().poll(&mut cx);
+            if self.trace {
+                let state = if result.is_ready() { "Ready ✅" } else { "Pending (等 Waker)" };
+                println!("    [executor {:>6.1?}] poll 任务 #{} {:<10} -> {}", start.elapsed(), task.id, task.name, state);
+            }
+            match result {
+                Poll::Pending => *slot = Some(future),
+                Poll::Ready(()) => {
+                    self.spawner.live.fetch_sub(1, Ordering::SeqCst);
+                }
+            }
+        }
+    }
+}
+
+// ==========================================
+// block_on：park / unpark 当前线程
+// ==========================================
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// 在当前线程上把一个 Future 跑到底，返回它的结果
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    // 钉在栈上：poll 需要 Pin<&mut F>，async 状态机可能引用自己的字段，poll 过之后就不能再挪动
+    let mut future = std::pin::pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        // unpark 先于 park 到达也没关系：park 会立刻返回 (许可证语义)；偶尔的虚假醒来只是多 poll 一次
+        thread::park();
+    }
+}
+
+// ==========================================
+// 定时器"反应器"：模拟网络延迟
+// ==========================================
+fn timer() -> &'static Sender<(Instant, Waker)> {
+    static TIMER: OnceLock<Sender<(Instant, Waker)>> = OnceLock::new();
+    TIMER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<(Instant, Waker)>();
+        thread::spawn(move || {
+            let mut pending: Vec<(Instant, Waker)> = Vec::new();
+            loop {
+                let now = Instant::now();
+                pending.retain(|(at, waker)| {
+                    if *at <= now {
+                        waker.wake_by_ref();
+                    }
+                    *at > now
+                });
+                // 睡到最近的一个到期时间，或者有新的登记进来
+                let next = match pending.iter().map(|(at, _)| *at).min() {
+                    Some(at) => rx.recv_timeout(at.saturating_duration_since(now)),
+                    None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+                match next {
+                    Ok(entry) => pending.push(entry),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+        });
+        tx
+    })
+}
+
+pub struct Sleep {
+    deadline: Instant,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+        // 每次 Pending 都重新登记：两次 poll 之间任务可能换了 Waker，重复登记最多多一次唤醒
+        let _ = timer().send((self.deadline, cx.waker().clone()));
+        Poll::Pending
+    }
+}
+
+// 异步的 sleep：不阻塞线程，只是让当前任务 Pending 到 deadline
+pub fn sleep(duration: Duration) -> Sleep {
+    Sleep { deadline: Instant::now() + duration }
+}
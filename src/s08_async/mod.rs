@@ -0,0 +1,30 @@
+// src/s08_async/mod.rs
+
+// 公共工具
+pub mod executor;
+
+// 练习
+pub mod ex01_executor;
+pub mod ex02_manual_future;
+
+use std::io;
+
+pub fn run_experiments() {
+    loop {
+        println!("\n--- ⏳ S08 异步 (Async，手写执行器，不用 tokio) ---");
+        println!("1. 手写执行器 (任务队列、Arc<Task> 做 Waker、block_on，并发模拟网络请求)");
+        println!("2. 手写 Future (TimerFuture、ChannelRecvFuture，打印 poll 的状态转移)");
+        println!("0. 返回主菜单");
+        println!("请输入练习编号:");
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("读取失败");
+
+        match input.trim() {
+            "1" => ex01_executor::run(),
+            "2" => ex02_manual_future::run(),
+            "0" => break,
+            _ => println!("❌ 无效选择"),
+        }
+    }
+}